@@ -3,6 +3,12 @@ use serde::{Deserialize, Serialize};
 
 pub const DOOR_THICKNESS: f32 = 0.6;
 pub const WINDOW_THICKNESS: f32 = 0.75;
+/// Walls are drawn as a post this thick with arms out to each joined neighbour
+pub const WALL_THICKNESS: f32 = 0.75;
+/// How far a door or window frame's jamb reaches in from each end, as a share of a tile
+pub const FRAME_DEPTH: f32 = 0.2;
+/// Fences, hedges and gates are drawn as a low block this share of a tile across
+pub const BOUNDARY_SIZE: f32 = 0.5;
 
 #[derive(Component, Debug, Clone, Copy)]
 pub struct GridPosition {
@@ -20,8 +26,76 @@ impl GridPosition {
     }
 }
 
+/// A built wall; painting it sets a tint over the bare stone
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Wall {
+    pub tint: Option<PaintColor>,
+}
+
+impl Wall {
+    pub fn color(&self) -> Color {
+        self.tint.map_or(WallMaterial::Stone.color(), |tint| tint.color())
+    }
+}
+
+/// Which neighbouring tiles a wall joins up with; picks the straight, corner, T or
+/// cross shape the wall is drawn with
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WallConnections {
+    pub north: bool,
+    pub east: bool,
+    pub south: bool,
+    pub west: bool,
+}
+
+impl WallConnections {
+    /// Packs the four sides into a 0-15 index, one bit per side (N, E, S, W)
+    pub fn mask(&self) -> usize {
+        (self.north as usize)
+            | (self.east as usize) << 1
+            | (self.south as usize) << 2
+            | (self.west as usize) << 3
+    }
+
+    pub fn from_mask(mask: usize) -> Self {
+        Self {
+            north: mask & 1 != 0,
+            east: mask & 2 != 0,
+            south: mask & 4 != 0,
+            west: mask & 8 != 0,
+        }
+    }
+}
+
+/// Which ends of a door or window butt up against a wall (or another opening), picking the
+/// frame variant that carries the wall on across the seam. `start` is the left or bottom end.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameJoins {
+    pub start: bool,
+    pub end: bool,
+}
+
+impl FrameJoins {
+    /// Packs both ends into a 0-3 index, one bit per end (start, end)
+    pub fn mask(&self) -> usize {
+        (self.start as usize) | (self.end as usize) << 1
+    }
+
+    pub fn from_mask(mask: usize) -> Self {
+        Self {
+            start: mask & 1 != 0,
+            end: mask & 2 != 0,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.start || self.end
+    }
+}
+
+/// The frame drawn around a door or window, as a child of it
 #[derive(Component)]
-pub struct Wall;
+pub struct OpeningFrame;
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WallProjection {
@@ -74,6 +148,27 @@ pub enum DoorState {
     Open,
 }
 
+/// Set from a door's right-click menu. A door normally swings open for whoever comes
+/// near and shuts a moment after they leave; it can instead be propped open, or locked,
+/// which makes it a wall for walking and for telling rooms apart.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoorMode {
+    #[default]
+    Auto,
+    HeldOpen,
+    Locked,
+}
+
+impl DoorMode {
+    pub fn name(&self) -> &str {
+        match self {
+            DoorMode::Auto => "Auto",
+            DoorMode::HeldOpen => "Held open",
+            DoorMode::Locked => "Locked",
+        }
+    }
+}
+
 impl Door {
     pub fn new(orientation: DoorOrientation) -> Self {
         Self {
@@ -92,7 +187,38 @@ impl Door {
 }
 
 #[derive(Component)]
-pub struct Window;
+pub struct Window {
+    pub orientation: WindowOrientation,
+}
+
+/// Which way the wall a window is set into runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowOrientation {
+    #[default]
+    Horizontal, // Wall runs left-right
+    Vertical,   // Wall runs up-down
+}
+
+impl WindowOrientation {
+    pub fn rotated(&self) -> Self {
+        match self {
+            WindowOrientation::Horizontal => WindowOrientation::Vertical,
+            WindowOrientation::Vertical => WindowOrientation::Horizontal,
+        }
+    }
+
+    /// Lines a window up with the wall around it. Returns None when the neighbours
+    /// don't settle it, such as a lone tile or a corner.
+    pub fn from_neighbours(pos: IVec2, is_wall: impl Fn(IVec2) -> bool) -> Option<Self> {
+        let horizontal = is_wall(pos - IVec2::X) || is_wall(pos + IVec2::X);
+        let vertical = is_wall(pos - IVec2::Y) || is_wall(pos + IVec2::Y);
+        match (horizontal, vertical) {
+            (true, false) => Some(WindowOrientation::Horizontal),
+            (false, true) => Some(WindowOrientation::Vertical),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct Building;
@@ -100,9 +226,24 @@ pub struct Building;
 #[derive(Component)]
 pub struct Floor {
     pub floor_type: FloorType,
+    /// Paint over the floor; the material still shows through underneath
+    pub tint: Option<PaintColor>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl Floor {
+    pub fn new(floor_type: FloorType) -> Self {
+        Self {
+            floor_type,
+            tint: None,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        self.tint.map_or(self.floor_type.color(), |tint| tint.over(self.floor_type.color()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FloorType {
     Wood,
     Stone,
@@ -111,6 +252,15 @@ pub enum FloorType {
 }
 
 impl FloorType {
+    pub fn all() -> Vec<FloorType> {
+        vec![
+            FloorType::Wood,
+            FloorType::Stone,
+            FloorType::Carpet,
+            FloorType::Tile,
+        ]
+    }
+
     pub fn color(&self) -> Color {
         match self {
             FloorType::Wood => Color::srgb(0.6, 0.4, 0.2),
@@ -121,9 +271,58 @@ impl FloorType {
     }
 }
 
+/// A fence, hedge or gate marking out the grounds. Fences and hedges keep people from
+/// walking through but, being low, never close off a room; gates let everyone pass.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Boundary {
+    pub boundary_type: BoundaryType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BoundaryType {
+    Fence,
+    Hedge,
+    Gate,
+}
+
+impl BoundaryType {
+    pub fn all() -> [BoundaryType; 3] {
+        [BoundaryType::Fence, BoundaryType::Hedge, BoundaryType::Gate]
+    }
+
+    pub fn blocks_movement(&self) -> bool {
+        *self != BoundaryType::Gate
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            BoundaryType::Fence => Color::srgb(0.75, 0.65, 0.45),
+            BoundaryType::Hedge => Color::srgb(0.2, 0.45, 0.2),
+            BoundaryType::Gate => Color::srgb(0.55, 0.4, 0.25),
+        }
+    }
+
+    pub fn ascii_char(&self) -> char {
+        match self {
+            BoundaryType::Fence => ':',
+            BoundaryType::Hedge => '%',
+            BoundaryType::Gate => '-',
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct PlacementPreview;
 
+/// Text near the cursor explaining why the previewed placement is blocked
+#[derive(Component)]
+pub struct PlacementHint;
+
+/// Line above the toolbar spelling out what the selected building needs, shown once
+/// placing it has failed a few times in a row
+#[derive(Component)]
+pub struct PlacementRequirementsHint;
+
 // Material types for buildings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WallMaterial {
@@ -141,3 +340,72 @@ impl WallMaterial {
         }
     }
 }
+
+/// The colours a Paint order can put on walls and floors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum PaintColor {
+    #[default]
+    Cream,
+    Sand,
+    Terracotta,
+    Coral,
+    Sage,
+    Seafoam,
+    Sky,
+    Slate,
+}
+
+impl PaintColor {
+    pub fn all() -> [PaintColor; 8] {
+        [
+            PaintColor::Cream,
+            PaintColor::Sand,
+            PaintColor::Terracotta,
+            PaintColor::Coral,
+            PaintColor::Sage,
+            PaintColor::Seafoam,
+            PaintColor::Sky,
+            PaintColor::Slate,
+        ]
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PaintColor::Cream => Color::srgb(0.93, 0.89, 0.78),
+            PaintColor::Sand => Color::srgb(0.84, 0.74, 0.55),
+            PaintColor::Terracotta => Color::srgb(0.74, 0.42, 0.3),
+            PaintColor::Coral => Color::srgb(0.95, 0.55, 0.5),
+            PaintColor::Sage => Color::srgb(0.6, 0.7, 0.56),
+            PaintColor::Seafoam => Color::srgb(0.5, 0.8, 0.74),
+            PaintColor::Sky => Color::srgb(0.55, 0.72, 0.9),
+            PaintColor::Slate => Color::srgb(0.4, 0.45, 0.52),
+        }
+    }
+
+    /// The paint over a floor, letting a little of the material show through
+    pub fn over(&self, base: Color) -> Color {
+        const COVERAGE: f32 = 0.7;
+        let base = base.to_srgba();
+        let paint = self.color().to_srgba();
+        let mix = |under: f32, over: f32| under + (over - under) * COVERAGE;
+        Color::srgb(
+            mix(base.red, paint.red),
+            mix(base.green, paint.green),
+            mix(base.blue, paint.blue),
+        )
+    }
+
+    /// Localization key for the colour's swatch in the Orders tab
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            PaintColor::Cream => "toolbar.paint.cream",
+            PaintColor::Sand => "toolbar.paint.sand",
+            PaintColor::Terracotta => "toolbar.paint.terracotta",
+            PaintColor::Coral => "toolbar.paint.coral",
+            PaintColor::Sage => "toolbar.paint.sage",
+            PaintColor::Seafoam => "toolbar.paint.seafoam",
+            PaintColor::Sky => "toolbar.paint.sky",
+            PaintColor::Slate => "toolbar.paint.slate",
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use super::furniture_catalog::*;
+use super::zone::ZoneType;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -9,7 +11,7 @@ pub struct Bed {
     pub bed_type: BedType,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BedType {
     Single,
     Double,
@@ -40,6 +42,36 @@ impl FurnitureOrientation {
             FurnitureOrientation::East | FurnitureOrientation::West
         )
     }
+    /// Arrow pointing the way `facing` does, for signs
+    pub fn arrow(&self) -> char {
+        match self {
+            FurnitureOrientation::East => '→',
+            FurnitureOrientation::South => '↓',
+            FurnitureOrientation::West => '←',
+            FurnitureOrientation::North => '↑',
+        }
+    }
+
+    /// Grid step towards the side the piece's front faces, matching the front art
+    /// being shown for South
+    pub fn facing(&self) -> IVec2 {
+        match self {
+            FurnitureOrientation::East => IVec2::X,
+            FurnitureOrientation::South => IVec2::NEG_Y,
+            FurnitureOrientation::West => IVec2::NEG_X,
+            FurnitureOrientation::North => IVec2::Y,
+        }
+    }
+}
+
+impl BedType {
+    /// Guests who can sleep in one bed of this type
+    pub fn capacity(&self) -> u8 {
+        match self {
+            BedType::Single => 1,
+            BedType::Double => 2,
+        }
+    }
 }
 
 impl Bed {
@@ -48,18 +80,7 @@ impl Bed {
     }
 
     pub fn tiles_occupied(&self, base_pos: IVec2) -> Vec<IVec2> {
-        let (width, height) = match self.bed_type {
-            BedType::Single => (2, 3),
-            BedType::Double => (4, 4),
-        };
-
-        let mut tiles = Vec::new();
-        for x in 0..width {
-            for y in 0..height {
-                tiles.push(base_pos + IVec2::new(x, y));
-            }
-        }
-        tiles
+        FurnitureType::Bed(self.bed_type).tiles_occupied(base_pos, FurnitureOrientation::East)
     }
 }
 
@@ -78,6 +99,83 @@ pub struct Nightstand;
 #[derive(Component)]
 pub struct Toilet;
 
+#[derive(Component)]
+pub struct StaffLocker;
+
+#[derive(Component)]
+pub struct StaffCouch;
+
+#[derive(Component)]
+pub struct MassageTable;
+
+/// Shuttle stop where guests arrive and leave on the shuttle schedule
+#[derive(Component)]
+pub struct ArrivalPoint;
+
+#[derive(Component)]
+pub struct Sauna;
+
+/// Rack of hammers that construction workers take before building and hang back up after
+#[derive(Component)]
+pub struct ToolRack;
+
+impl ToolRack {
+    pub const HAMMERS: usize = 4;
+}
+
+/// Lights the room it stands in, day and night
+#[derive(Component)]
+pub struct Lamp;
+
+/// Plays the ambience chosen for the zone it stands in to a camera close enough to hear
+#[derive(Component)]
+pub struct Speaker;
+
+/// A signpost pointing guests the way it faces, towards an amenity
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sign {
+    pub icon: SignIcon,
+    /// What the player wrote on it; blank shows the icon's name
+    pub text: String,
+}
+
+/// The kind of amenity a sign points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SignIcon {
+    #[default]
+    Pool,
+    Restaurant,
+    Spa,
+}
+
+impl SignIcon {
+    pub fn next(self) -> Self {
+        match self {
+            SignIcon::Pool => SignIcon::Restaurant,
+            SignIcon::Restaurant => SignIcon::Spa,
+            SignIcon::Spa => SignIcon::Pool,
+        }
+    }
+
+    /// The amenity guests following the sign are looking for
+    pub fn destination(&self) -> ZoneType {
+        match self {
+            SignIcon::Pool => ZoneType::Relaxation,
+            SignIcon::Restaurant => ZoneType::Culinary,
+            SignIcon::Spa => ZoneType::Spa,
+        }
+    }
+
+    /// Localization key for the icon's name, which blank signs show
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            SignIcon::Pool => "sign.icon.pool",
+            SignIcon::Restaurant => "sign.icon.restaurant",
+            SignIcon::Spa => "sign.icon.spa",
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Sink;
 
@@ -87,17 +185,34 @@ pub struct Tub;
 #[derive(Component)]
 pub struct ReceptionConsole {
     pub placed_on_desk: Option<Entity>, // Reference to the desk it's on
+    /// Tile behind the desk where the receptionist stands facing the screen, once found
+    pub work_spot: Option<IVec2>,
+    /// A receptionist is at the work spot, so arriving parties queue here
+    pub open: bool,
+    /// The party being checked in, one at a time
+    pub serving: Option<Entity>,
+    /// Game hours of paperwork left for the party being served
+    pub check_in_hours_left: f32,
 }
 
 impl ReceptionConsole {
     pub fn new() -> Self {
         Self {
             placed_on_desk: None,
+            work_spot: None,
+            open: false,
+            serving: None,
+            check_in_hours_left: 0.0,
         }
     }
+
+    /// Checking a party in, as opposed to open and waiting for the next one
+    pub fn is_busy(&self) -> bool {
+        self.serving.is_some()
+    }
 }
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FurnitureType {
     Bed(BedType),
     Desk,
@@ -108,23 +223,71 @@ pub enum FurnitureType {
     Sink,
     Tub,
     ReceptionConsole,
+    StaffLocker,
+    StaffCouch,
+    MassageTable,
+    Sauna,
+    ShuttleStop,
+    ToolRack,
+    Signpost,
+    Lamp,
+    /// Furniture added by a mod catalog
+    Custom(CustomFurnitureId),
+}
+
+/// Stable hash of a mod furniture id, so saves keep pointing at the same piece
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CustomFurnitureId(pub u32);
+
+impl CustomFurnitureId {
+    pub fn from_id(id: &str) -> Self {
+        // FNV-1a, which unlike std's hasher is the same on every run and platform
+        let hash = id.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+        });
+        Self(hash)
+    }
 }
 
 impl FurnitureType {
-    pub fn color(&self) -> Color {
-        match self {
-            FurnitureType::Bed(_) => Color::srgb(0.8, 0.7, 0.6), // Beige/tan
-            FurnitureType::Desk => Color::srgb(0.5, 0.3, 0.1),   // Dark brown
-            FurnitureType::Chair => Color::srgb(0.6, 0.4, 0.2),  // Medium brown
-            FurnitureType::Dresser => Color::srgb(0.5, 0.3, 0.1), // Dark brown
-            FurnitureType::Nightstand => Color::srgb(0.6, 0.4, 0.2), // Medium brown
-            FurnitureType::Toilet => Color::srgb(0.9, 0.9, 0.95),
-            FurnitureType::Sink => Color::srgb(0.9, 0.9, 0.95),
-            FurnitureType::Tub => Color::srgb(0.9, 0.9, 0.95),
-            FurnitureType::ReceptionConsole => Color::srgb(0.3, 0.5, 0.7), // Blue-gray
+    /// Maps a catalog id onto the built-in type it describes, or a mod type otherwise
+    pub fn from_id(id: &str) -> FurnitureType {
+        match id {
+            "single_bed" => FurnitureType::Bed(BedType::Single),
+            "double_bed" => FurnitureType::Bed(BedType::Double),
+            "desk" => FurnitureType::Desk,
+            "chair" => FurnitureType::Chair,
+            "dresser" => FurnitureType::Dresser,
+            "nightstand" => FurnitureType::Nightstand,
+            "toilet" => FurnitureType::Toilet,
+            "sink" => FurnitureType::Sink,
+            "tub" => FurnitureType::Tub,
+            "reception_console" => FurnitureType::ReceptionConsole,
+            "staff_locker" => FurnitureType::StaffLocker,
+            "staff_couch" => FurnitureType::StaffCouch,
+            "massage_table" => FurnitureType::MassageTable,
+            "sauna" => FurnitureType::Sauna,
+            "shuttle_stop" => FurnitureType::ShuttleStop,
+            "tool_rack" => FurnitureType::ToolRack,
+            "signpost" => FurnitureType::Signpost,
+            "lamp" => FurnitureType::Lamp,
+            _ => FurnitureType::Custom(CustomFurnitureId::from_id(id)),
         }
     }
 
+    /// Everything in the furniture catalog, in catalog order
+    pub fn all() -> Vec<FurnitureType> {
+        FurnitureRegistry::global().types().collect()
+    }
+
+    pub fn definition(&self) -> &'static FurnitureDefinition {
+        FurnitureRegistry::global().definition(*self)
+    }
+
+    pub fn color(&self) -> Color {
+        self.definition().color()
+    }
+
     pub fn tiles_occupied(&self, base_pos: IVec2, orientation: FurnitureOrientation) -> Vec<IVec2> {
         let (width, height) = self.oriented_dimensions(orientation);
         let mut tiles = Vec::new();
@@ -136,9 +299,18 @@ impl FurnitureType {
         tiles
     }
 
-    pub fn size(&self) -> (f32, f32) {
-        let (width, height) = self.base_dimensions();
-        (width as f32, height as f32)
+    /// Whether a piece anchored at `base_pos` covers `tile`, anywhere on its footprint
+    pub fn covers(&self, base_pos: IVec2, orientation: FurnitureOrientation, tile: IVec2) -> bool {
+        let (width, height) = self.oriented_dimensions(orientation);
+        let offset = tile - base_pos;
+        (0..width).contains(&offset.x) && (0..height).contains(&offset.y)
+    }
+
+    /// Offset in tiles from the anchor tile to the middle of the footprint, which is
+    /// where the piece is drawn and turned about
+    pub fn footprint_center_offset(&self, orientation: FurnitureOrientation) -> Vec2 {
+        let (width, height) = self.oriented_dimensions(orientation);
+        Vec2::new(width as f32 - 1.0, height as f32 - 1.0) / 2.0
     }
 
     pub fn oriented_dimensions(&self, orientation: FurnitureOrientation) -> (i32, i32) {
@@ -151,46 +323,117 @@ impl FurnitureType {
     }
 
     pub fn base_dimensions(&self) -> (i32, i32) {
-        match self {
-            FurnitureType::Bed(BedType::Single) => (2, 3),
-            FurnitureType::Bed(BedType::Double) => (4, 4),
-            FurnitureType::Desk => (2, 2),
-            FurnitureType::Chair => (1, 1),
-            FurnitureType::Dresser => (2, 2),
-            FurnitureType::Nightstand => (1, 1),
-            FurnitureType::Toilet => (2, 2),
-            FurnitureType::Sink => (1, 1),
-            FurnitureType::Tub => (2, 4),
-            FurnitureType::ReceptionConsole => (1, 1),
-        }
+        self.definition().size
     }
 
     pub fn name(&self) -> &str {
-        match self {
-            FurnitureType::Bed(BedType::Single) => "Single Bed",
-            FurnitureType::Bed(BedType::Double) => "Double Bed",
-            FurnitureType::Desk => "Desk",
-            FurnitureType::Chair => "Chair",
-            FurnitureType::Dresser => "Dresser",
-            FurnitureType::Nightstand => "Nightstand",
-            FurnitureType::Toilet => "Toilet",
-            FurnitureType::Sink => "Sink",
-            FurnitureType::Tub => "Tub",
-            FurnitureType::ReceptionConsole => "Reception Console",
-        }
+        &self.definition().name
+    }
+
+    pub fn cost(&self) -> i32 {
+        self.definition().cost
+    }
+
+    /// In-game day this furniture becomes available in a normal game
+    pub fn unlock_day(&self) -> u32 {
+        self.definition().unlock_day
+    }
+
+    /// The calendar event this decoration is only sold during, if any
+    pub fn event(&self) -> Option<&str> {
+        self.definition().event.as_deref()
     }
 
     pub fn ascii_char(&self) -> char {
-        match self {
-            FurnitureType::Bed(_) => '▬',
-            FurnitureType::Desk => '═',
-            FurnitureType::Chair => 'π',
-            FurnitureType::Dresser => '▓',
-            FurnitureType::Nightstand => '□',
-            FurnitureType::Toilet => '╥',
-            FurnitureType::Sink => '○',
-            FurnitureType::Tub => '≋',
-            FurnitureType::ReceptionConsole => '▣',
+        self.definition().ascii
+    }
+
+    /// Desk-top furniture goes on a desk rather than claiming floor tiles of its own
+    pub fn sits_on_desk(&self) -> bool {
+        self.definition().placement == FurniturePlacement::Desk
+    }
+
+    pub fn at_map_edge(&self) -> bool {
+        self.definition().placement == FurniturePlacement::MapEdge
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const ORIENTATIONS: [FurnitureOrientation; 4] = [
+        FurnitureOrientation::East,
+        FurnitureOrientation::South,
+        FurnitureOrientation::West,
+        FurnitureOrientation::North,
+    ];
+
+    /// Every catalog piece in every orientation, anchored away from the origin so a
+    /// sign slip in the offsets can't cancel out
+    fn footprints() -> Vec<(FurnitureType, FurnitureOrientation, IVec2, Vec<IVec2>)> {
+        let anchor = IVec2::new(10, 20);
+        FurnitureType::all()
+            .into_iter()
+            .flat_map(|furniture_type| {
+                ORIENTATIONS.map(|orientation| {
+                    let tiles = furniture_type.tiles_occupied(anchor, orientation);
+                    (furniture_type, orientation, anchor, tiles)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn footprint_matches_oriented_dimensions() {
+        for (furniture_type, orientation, _, tiles) in footprints() {
+            let (width, height) = furniture_type.oriented_dimensions(orientation);
+            let unique: HashSet<IVec2> = tiles.iter().copied().collect();
+            assert_eq!(
+                unique.len(),
+                (width * height) as usize,
+                "{:?} facing {:?}",
+                furniture_type,
+                orientation
+            );
+            assert_eq!(unique.len(), tiles.len());
+        }
+    }
+
+    #[test]
+    fn sprite_center_is_footprint_centroid() {
+        for (furniture_type, orientation, anchor, tiles) in footprints() {
+            let centroid =
+                tiles.iter().map(|tile| tile.as_vec2()).sum::<Vec2>() / tiles.len() as f32;
+            let center = anchor.as_vec2() + furniture_type.footprint_center_offset(orientation);
+            assert!(
+                center.abs_diff_eq(centroid, 1e-5),
+                "{:?} facing {:?}: drawn at {} but covers tiles around {}",
+                furniture_type,
+                orientation,
+                center,
+                centroid
+            );
+        }
+    }
+
+    #[test]
+    fn covers_matches_tiles_occupied() {
+        for (furniture_type, orientation, anchor, tiles) in footprints() {
+            for x in -4..8 {
+                for y in -4..8 {
+                    let tile = anchor + IVec2::new(x, y);
+                    assert_eq!(
+                        furniture_type.covers(anchor, orientation, tile),
+                        tiles.contains(&tile),
+                        "{:?} facing {:?} at {}",
+                        furniture_type,
+                        orientation,
+                        tile
+                    );
+                }
+            }
         }
     }
 }
@@ -0,0 +1,235 @@
+use super::furniture::FurnitureType;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Every `.json` file in here is read at startup, in file name order, so mods can add
+/// furniture or override the stock pieces by dropping in a catalog of their own
+pub const FURNITURE_CATALOG_DIR: &str = "assets/furniture";
+
+/// The stock catalog is compiled in so the game still has furniture without an assets folder
+const BASE_CATALOG: &str = include_str!("../../assets/furniture/base.json");
+
+/// Which build tab a piece of furniture is listed under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FurnitureCategory {
+    #[default]
+    Furniture,
+    Bath,
+    Staff,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FurniturePlacement {
+    /// Needs free floor under every tile it covers
+    #[default]
+    Floor,
+    /// Sits on top of a desk instead of taking up floor
+    Desk,
+    /// Built outdoors at the edge of the map, like the shuttle stop guests arrive at
+    MapEdge,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FurnitureSprite {
+    /// One image rotated to face each direction
+    Rotating(String),
+    /// Separate art per facing, with the side view mirrored for west
+    Directional {
+        front: String,
+        back: String,
+        side: String,
+    },
+}
+
+/// One catalog entry. Furniture without a sprite is drawn as a coloured block.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FurnitureDefinition {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub category: FurnitureCategory,
+    /// Width and height in tiles when facing east
+    pub size: (i32, i32),
+    pub cost: i32,
+    #[serde(default = "default_unlock_day")]
+    pub unlock_day: u32,
+    /// Id of the calendar event a decoration is only sold during, such as
+    /// "winter_holidays"; pieces without one are sold all year
+    #[serde(default)]
+    pub event: Option<String>,
+    pub color: [f32; 3],
+    #[serde(default = "default_ascii")]
+    pub ascii: char,
+    #[serde(default)]
+    pub sprite: Option<FurnitureSprite>,
+    #[serde(default = "default_sprite_scale")]
+    pub sprite_scale: f32,
+    #[serde(default)]
+    pub placement: FurniturePlacement,
+    /// Gameplay roles such as "bed" or "toilet"; each adds the matching component
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_unlock_day() -> u32 {
+    1
+}
+
+fn default_ascii() -> char {
+    '#'
+}
+
+fn default_sprite_scale() -> f32 {
+    1.0
+}
+
+impl FurnitureDefinition {
+    pub fn color(&self) -> Color {
+        Color::srgb(self.color[0], self.color[1], self.color[2])
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Stand-in for furniture a save refers to but no loaded catalog defines
+    fn missing() -> Self {
+        Self {
+            id: "missing".to_string(),
+            name: "Missing Furniture".to_string(),
+            category: FurnitureCategory::Furniture,
+            size: (1, 1),
+            cost: 0,
+            unlock_day: default_unlock_day(),
+            event: None,
+            color: [1.0, 0.0, 1.0],
+            ascii: '?',
+            sprite: None,
+            sprite_scale: default_sprite_scale(),
+            placement: FurniturePlacement::Floor,
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// All furniture the game knows about, built from the stock catalog plus any mod catalogs
+#[derive(Resource, Debug, Clone)]
+pub struct FurnitureRegistry {
+    definitions: Vec<(FurnitureType, FurnitureDefinition)>,
+    index: HashMap<FurnitureType, usize>,
+    missing: FurnitureDefinition,
+}
+
+impl FurnitureRegistry {
+    /// The catalog loaded from disk on first use. `FurnitureType` is copied into plain
+    /// helper functions all over the codebase, so its accessors read this directly.
+    pub fn global() -> &'static FurnitureRegistry {
+        static REGISTRY: OnceLock<FurnitureRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(|| FurnitureRegistry::load(Path::new(FURNITURE_CATALOG_DIR)))
+    }
+
+    /// Reads the stock catalog, then every catalog file in `dir` on top of it
+    pub fn load(dir: &Path) -> Self {
+        let mut registry = Self {
+            definitions: Vec::new(),
+            index: HashMap::new(),
+            missing: FurnitureDefinition::missing(),
+        };
+        registry.add_catalog("built-in catalog", BASE_CATALOG);
+
+        let mut files: Vec<_> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect(),
+            Err(err) => {
+                warn!("No furniture catalogs in {}: {}", dir.display(), err);
+                Vec::new()
+            }
+        };
+        files.sort();
+
+        for path in files {
+            match fs::read_to_string(&path) {
+                Ok(contents) => registry.add_catalog(&path.display().to_string(), &contents),
+                Err(err) => warn!("Failed to read {}: {}", path.display(), err),
+            }
+        }
+
+        registry
+    }
+
+    fn add_catalog(&mut self, source: &str, contents: &str) {
+        let definitions: Vec<FurnitureDefinition> = match serde_json::from_str(contents) {
+            Ok(definitions) => definitions,
+            Err(err) => {
+                warn!("Skipping furniture catalog {}: {}", source, err);
+                return;
+            }
+        };
+
+        for definition in definitions {
+            self.add(source, definition);
+        }
+    }
+
+    // Later catalogs replace earlier entries with the same id
+    fn add(&mut self, source: &str, definition: FurnitureDefinition) {
+        if definition.size.0 < 1 || definition.size.1 < 1 {
+            warn!("Skipping furniture '{}' from {}: size must be at least 1x1", definition.id, source);
+            return;
+        }
+
+        let furniture_type = FurnitureType::from_id(&definition.id);
+        match self.index.get(&furniture_type) {
+            Some(&slot) if self.definitions[slot].1.id != definition.id => {
+                warn!(
+                    "Skipping furniture '{}' from {}: its id clashes with '{}', pick another",
+                    definition.id, source, self.definitions[slot].1.id
+                );
+            }
+            Some(&slot) => self.definitions[slot].1 = definition,
+            None => {
+                self.index.insert(furniture_type, self.definitions.len());
+                self.definitions.push((furniture_type, definition));
+            }
+        }
+    }
+
+    pub fn get(&self, furniture_type: FurnitureType) -> Option<&FurnitureDefinition> {
+        self.index
+            .get(&furniture_type)
+            .map(|&slot| &self.definitions[slot].1)
+    }
+
+    pub fn contains(&self, furniture_type: FurnitureType) -> bool {
+        self.index.contains_key(&furniture_type)
+    }
+
+    /// Like `get`, but furniture from a mod that is no longer installed gets a placeholder
+    pub fn definition(&self, furniture_type: FurnitureType) -> &FurnitureDefinition {
+        self.get(furniture_type).unwrap_or(&self.missing)
+    }
+
+    /// Every furniture type in catalog order
+    pub fn types(&self) -> impl Iterator<Item = FurnitureType> + '_ {
+        self.definitions.iter().map(|(furniture_type, _)| *furniture_type)
+    }
+
+    pub fn in_category(
+        &self,
+        category: FurnitureCategory,
+    ) -> impl Iterator<Item = (FurnitureType, &FurnitureDefinition)> + '_ {
+        self.definitions
+            .iter()
+            .filter(move |(_, definition)| definition.category == category)
+            .map(|(furniture_type, definition)| (*furniture_type, definition))
+    }
+}
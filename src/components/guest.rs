@@ -0,0 +1,567 @@
+use super::item::ItemKind;
+use super::work_assignment::WorkType;
+use super::zone::{ZoneQuality, ZoneType};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Parties of this size or larger are families and need a bigger room
+pub const FAMILY_PARTY_SIZE: u8 = 3;
+/// Smallest bedroom a family will accept
+pub const FAMILY_ROOM_MIN_TILES: usize = 20;
+pub const MAX_PARTY_SIZE: u8 = 4;
+
+/// What brings a party to the resort. It sets how many travel together, what they will
+/// pay for a night, the room they expect and how they like to spend the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuestArchetype {
+    Backpacker,
+    Business,
+    Honeymooners,
+    Family,
+}
+
+/// How keen a party is on each sort of outing; 1 is ordinary
+#[derive(Debug, Clone, Copy)]
+pub struct OutingPreferences {
+    pub meals: f32,
+    pub leisure: f32,
+    pub spa: f32,
+}
+
+impl GuestArchetype {
+    pub const ALL: [GuestArchetype; 4] = [
+        GuestArchetype::Backpacker,
+        GuestArchetype::Business,
+        GuestArchetype::Honeymooners,
+        GuestArchetype::Family,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            GuestArchetype::Backpacker => "Backpacker",
+            GuestArchetype::Business => "Business traveller",
+            GuestArchetype::Honeymooners => "Honeymooners",
+            GuestArchetype::Family => "Family",
+        }
+    }
+
+    /// Relative odds of each party size arriving (1, 2, 3, 4 guests)
+    pub fn size_weights(&self) -> [u32; 4] {
+        match self {
+            GuestArchetype::Backpacker => [55, 45, 0, 0],
+            GuestArchetype::Business => [85, 15, 0, 0],
+            GuestArchetype::Honeymooners => [0, 1, 0, 0],
+            GuestArchetype::Family => [0, 0, 60, 40],
+        }
+    }
+
+    /// Most the party will pay for a night's stay
+    pub fn nightly_budget(&self) -> i32 {
+        match self {
+            GuestArchetype::Backpacker => 120,
+            GuestArchetype::Business => 300,
+            GuestArchetype::Honeymooners => 450,
+            GuestArchetype::Family => 200,
+        }
+    }
+
+    /// The least the party expects of their room; a worse one starts the stay off badly
+    pub fn expected_quality(&self) -> ZoneQuality {
+        match self {
+            GuestArchetype::Backpacker => ZoneQuality::Basic,
+            GuestArchetype::Business => ZoneQuality::Good,
+            GuestArchetype::Honeymooners => ZoneQuality::Excellent,
+            GuestArchetype::Family => ZoneQuality::Good,
+        }
+    }
+
+    pub fn outing_preferences(&self) -> OutingPreferences {
+        let (meals, leisure, spa) = match self {
+            GuestArchetype::Backpacker => (0.8, 1.3, 0.4),
+            GuestArchetype::Business => (1.3, 0.6, 1.0),
+            GuestArchetype::Honeymooners => (1.2, 0.9, 1.5),
+            GuestArchetype::Family => (1.0, 1.4, 0.5),
+        };
+        OutingPreferences {
+            meals,
+            leisure,
+            spa,
+        }
+    }
+
+    /// Relative odds of this kind of party arriving. `reputation` runs from 0 to 1 and
+    /// `price_level` is what the rooms cost against their fair rate. A well-reviewed
+    /// resort draws couples and business travellers; high prices put off backpackers and
+    /// families.
+    pub fn arrival_weight(&self, reputation: f32, price_level: f32) -> f32 {
+        let (base, reputation_pull, price_sensitivity) = match self {
+            GuestArchetype::Backpacker => (30.0, -0.5, -2.0),
+            GuestArchetype::Business => (25.0, 0.5, 0.5),
+            GuestArchetype::Honeymooners => (15.0, 1.0, 1.0),
+            GuestArchetype::Family => (30.0, 0.0, -1.0),
+        };
+        let reputation_factor = 1.0 + reputation_pull * (reputation.clamp(0.0, 1.0) - 0.5) * 2.0;
+        let price_factor = price_level.max(0.1).powf(price_sensitivity);
+        (base * reputation_factor * price_factor).max(0.0)
+    }
+}
+
+/// A group of guests travelling together on one booking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestParty {
+    pub size: u8,
+    pub archetype: GuestArchetype,
+}
+
+impl GuestParty {
+    pub fn new(archetype: GuestArchetype, size: u8) -> Self {
+        Self {
+            size: size.clamp(1, MAX_PARTY_SIZE),
+            archetype,
+        }
+    }
+
+    pub fn is_family(&self) -> bool {
+        self.size >= FAMILY_PARTY_SIZE
+    }
+
+    pub fn name(&self) -> &str {
+        match self.size {
+            1 => "Solo traveller",
+            2 => "Couple",
+            3 => "Family of 3",
+            _ => "Family of 4",
+        }
+    }
+}
+
+/// A party staying in a bedroom zone; lives on the zone entity while the stay lasts
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct Booking {
+    pub party: GuestParty,
+    pub nights_remaining: u32,
+    pub nights_stayed: u32,
+    /// Rate agreed at check-in, so later price changes don't touch the bill
+    pub nightly_rate: i32,
+    /// Meals at culinary zones, billed on check-out
+    pub restaurant_charges: i32,
+    /// Spending at every other amenity, billed on check-out
+    pub extra_charges: i32,
+    /// How happy the party is with their stay, from 0 to 1; complaints knock it down
+    pub satisfaction: f32,
+    /// Everything the party has had to put up with so far, for their review
+    pub complaints: Vec<ReviewComplaint>,
+}
+
+impl Booking {
+    /// Satisfaction lost up front for each star the room falls short of what the party
+    /// expected
+    pub const DISAPPOINTMENT_PER_STAR: f32 = 0.2;
+
+    pub fn new(party: GuestParty, nights: u32, nightly_rate: i32, quality: ZoneQuality) -> Self {
+        let stars_short = party
+            .archetype
+            .expected_quality()
+            .stars()
+            .saturating_sub(quality.stars());
+        Self {
+            party,
+            nights_remaining: nights,
+            nights_stayed: 0,
+            nightly_rate,
+            restaurant_charges: 0,
+            extra_charges: 0,
+            satisfaction: 1.0 - stars_short as f32 * Self::DISAPPOINTMENT_PER_STAR,
+            complaints: Vec::new(),
+        }
+    }
+
+    /// Notes something the party will bring up in their review, once however often it
+    /// happens
+    pub fn complain(&mut self, complaint: ReviewComplaint) {
+        if !self.complaints.contains(&complaint) {
+            self.complaints.push(complaint);
+        }
+    }
+
+    /// Puts a visit to an amenity on the party's tab
+    pub fn charge_visit(&mut self, zone_type: ZoneType) {
+        let amount = zone_type.visit_charge() * self.party.size as i32;
+        if zone_type == ZoneType::Culinary {
+            self.restaurant_charges += amount;
+        } else {
+            self.extra_charges += amount;
+        }
+    }
+}
+
+/// A booked party that hasn't reached the resort yet; lives on the bedroom zone until
+/// the party steps off the next shuttle
+#[derive(Component)]
+pub struct AwaitingShuttle;
+
+/// What a newly arrived party is waiting for before they can go up to their room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WaitReason {
+    /// Someone on the reception desk to check them in
+    CheckIn,
+    /// Their room to be cleaned
+    Cleaning,
+}
+
+/// A party held up on arrival; they walk out if kept waiting too long
+#[derive(Component)]
+pub struct WaitingGuest {
+    pub reason: WaitReason,
+    /// Game hours spent waiting for the current reason
+    pub hours_waited: f32,
+}
+
+impl WaitingGuest {
+    pub fn new(reason: WaitReason) -> Self {
+        Self {
+            reason,
+            hours_waited: 0.0,
+        }
+    }
+}
+
+/// A guest whose stay is over, waiting at the shuttle stop for a ride home
+#[derive(Component)]
+pub struct Departing;
+
+/// Put on a bed or toilet while a guest is using it, so no one else heads for it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FurnitureClaim {
+    pub claimant: Entity,
+    /// What the claimant was doing when they claimed it; once they're doing anything
+    /// else they've been interrupted and let it go
+    pub activity: GuestActivity,
+    /// Game hours after which the claim lapses even if nothing released it
+    pub expires_hours: f32,
+}
+
+/// Keeps a bedroom out of check-in, e.g. while it is being renovated
+#[derive(Component)]
+pub struct RoomBlocked;
+
+/// Why a bedroom isn't private enough to let; check-in passes it over until it is fixed
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivacyIssue {
+    /// Part of the room isn't closed in by walls
+    NotEnclosed,
+    /// The bed is in the lobby, with the reception desk
+    SharesLobby,
+    /// Walls all round but no door to get in by
+    NoDoor,
+    /// People pass through the room to get somewhere else, like a corridor
+    Thoroughfare,
+}
+
+impl PrivacyIssue {
+    /// Localization key for the reason shown in the Rooms panel
+    pub fn reason_key(&self) -> &'static str {
+        match self {
+            PrivacyIssue::NotEnclosed => "rooms.privacy.not_enclosed",
+            PrivacyIssue::SharesLobby => "rooms.privacy.shares_lobby",
+            PrivacyIssue::NoDoor => "rooms.privacy.no_door",
+            PrivacyIssue::Thoroughfare => "rooms.privacy.thoroughfare",
+        }
+    }
+}
+
+/// How clean a bedroom is, from 0 (filthy) to 1 (spotless); each night of a stay wears it down
+#[derive(Component)]
+pub struct Cleanliness {
+    pub level: f32,
+}
+
+impl Default for Cleanliness {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+impl Cleanliness {
+    pub fn percent(&self) -> u32 {
+        (self.level.clamp(0.0, 1.0) * 100.0).round() as u32
+    }
+}
+
+/// What a guest party is doing right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestActivity {
+    InRoom,
+    /// In their room for the night
+    Sleeping,
+    /// Out at an amenity zone
+    Visiting(Entity),
+}
+
+impl GuestActivity {
+    pub fn in_room(&self) -> bool {
+        matches!(self, GuestActivity::InRoom | GuestActivity::Sleeping)
+    }
+}
+
+/// How well a party's needs are met, each from 0 (desperate) to 1 (satisfied).
+/// They drain while the party is awake and drive what it wants to do next.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GuestNeeds {
+    pub hunger: f32,
+    pub energy: f32,
+    pub fun: f32,
+}
+
+impl Default for GuestNeeds {
+    fn default() -> Self {
+        Self {
+            hunger: 0.7,
+            energy: 0.8,
+            fun: 0.5,
+        }
+    }
+}
+
+/// A guest party walking around the resort for the length of their booking
+#[derive(Component)]
+pub struct Guest {
+    pub party: GuestParty,
+    /// The bedroom zone the party is booked into
+    pub room: Entity,
+    pub move_speed: f32,
+    pub activity: GuestActivity,
+    pub needs: GuestNeeds,
+    /// Game hours left before the party decides what to do next
+    pub hours_left: f32,
+}
+
+/// What a guest is complaining about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncidentKind {
+    /// A family next door kept the party up
+    NoisyNeighbor,
+    /// No tub or sink in or next to the bedroom
+    ColdShower,
+    /// More guests at an amenity than it has room for
+    LongQueue,
+    /// Too little light in the bedroom after sunset
+    DarkRoom,
+}
+
+impl IncidentKind {
+    pub fn name(&self) -> &str {
+        match self {
+            IncidentKind::NoisyNeighbor => "Noisy neighbours",
+            IncidentKind::ColdShower => "Cold shower",
+            IncidentKind::LongQueue => "Long queue",
+            IncidentKind::DarkRoom => "Dark room",
+        }
+    }
+}
+
+/// Something a party held against their stay, tagged on the review they leave. Listed
+/// from most to least damning, which is the order reviews show them in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ReviewComplaint {
+    /// The room was filthy, or never got cleaned for them to move in
+    DirtyRoom,
+    /// Nobody was on reception to check them in
+    SlowCheckIn,
+    /// No toilet in the bedroom or a room through one of its doors
+    NoBathroom,
+    /// Nobody came to sort out one of their complaints
+    IgnoredComplaint,
+    Pests,
+    Noise,
+    ColdShower,
+    DarkRoom,
+    LateRoomService,
+    LongQueue,
+    /// Their room was pulled down around them
+    Renovation,
+    /// They came for a festival and found nothing put up for it
+    NoFestiveDecor,
+    /// Cramped, dark or bare corridors and other public spaces
+    ShabbyCorridors,
+    /// The room was worse than they paid for
+    DisappointingRoom,
+}
+
+impl ReviewComplaint {
+    pub fn name(&self) -> &str {
+        match self {
+            ReviewComplaint::DirtyRoom => "Dirty room",
+            ReviewComplaint::SlowCheckIn => "Slow check-in",
+            ReviewComplaint::NoBathroom => "No bathroom",
+            ReviewComplaint::IgnoredComplaint => "Ignored complaint",
+            ReviewComplaint::Pests => "Pests",
+            ReviewComplaint::Noise => "Noise",
+            ReviewComplaint::ColdShower => "Cold shower",
+            ReviewComplaint::DarkRoom => "Dark room",
+            ReviewComplaint::LateRoomService => "Late room service",
+            ReviewComplaint::LongQueue => "Long queue",
+            ReviewComplaint::Renovation => "Renovation",
+            ReviewComplaint::NoFestiveDecor => "No festive decorations",
+            ReviewComplaint::ShabbyCorridors => "Shabby corridors",
+            ReviewComplaint::DisappointingRoom => "Disappointing room",
+        }
+    }
+
+    /// Whether it is put right in the room the party stayed in, rather than by the staff
+    pub fn about_room(&self) -> bool {
+        !matches!(
+            self,
+            ReviewComplaint::SlowCheckIn
+                | ReviewComplaint::IgnoredComplaint
+                | ReviewComplaint::Pests
+                | ReviewComplaint::LateRoomService
+                | ReviewComplaint::LongQueue
+        )
+    }
+}
+
+impl From<IncidentKind> for ReviewComplaint {
+    fn from(kind: IncidentKind) -> Self {
+        match kind {
+            IncidentKind::NoisyNeighbor => ReviewComplaint::Noise,
+            IncidentKind::ColdShower => ReviewComplaint::ColdShower,
+            IncidentKind::LongQueue => ReviewComplaint::LongQueue,
+            IncidentKind::DarkRoom => ReviewComplaint::DarkRoom,
+        }
+    }
+}
+
+/// A guest complaint waiting for a member of staff to come and sort it out. Doubles as
+/// the job the responding worker is assigned to.
+#[derive(Component)]
+pub struct Incident {
+    pub kind: IncidentKind,
+    /// The guest who complained
+    pub guest: Entity,
+    /// Bedroom zone of the complaining party, whose booking takes the satisfaction hit
+    pub room: Entity,
+    /// Where staff have to go to deal with it
+    pub location: Vec2,
+    /// Game hours left to respond before the party gives up waiting
+    pub hours_left: f32,
+    pub assigned_pawn: Option<Entity>,
+    pub work_required: f32,
+    pub work_done: f32,
+}
+
+impl Incident {
+    pub fn new(kind: IncidentKind, guest: Entity, room: Entity, location: Vec2) -> Self {
+        Self {
+            kind,
+            guest,
+            room,
+            location,
+            hours_left: 3.0,
+            assigned_pawn: None,
+            work_required: 40.0,
+            work_done: 0.0,
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.work_done >= self.work_required
+    }
+}
+
+/// What a party can have brought up to their room
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomServiceItem {
+    /// A meal from the kitchen, delivered by cooks
+    Food,
+    /// Fresh towels from the staff room, delivered by housekeeping
+    Towels,
+}
+
+impl RoomServiceItem {
+    pub fn name(&self) -> &str {
+        match self {
+            RoomServiceItem::Food => "Food",
+            RoomServiceItem::Towels => "Towels",
+        }
+    }
+
+    /// Where the order is collected from
+    pub fn pickup_zone(&self) -> ZoneType {
+        match self {
+            RoomServiceItem::Food => ZoneType::Culinary,
+            RoomServiceItem::Towels => ZoneType::StaffRoom,
+        }
+    }
+
+    pub fn work_type(&self) -> WorkType {
+        match self {
+            RoomServiceItem::Food => WorkType::Cooking,
+            RoomServiceItem::Towels => WorkType::Cleaning,
+        }
+    }
+
+    /// What the worker carries up
+    pub fn item_kind(&self) -> ItemKind {
+        match self {
+            RoomServiceItem::Food => ItemKind::FoodPlate,
+            RoomServiceItem::Towels => ItemKind::Towels,
+        }
+    }
+
+    /// Added to the party's bill when the order arrives
+    pub fn fee(&self, party: GuestParty) -> i32 {
+        match self {
+            RoomServiceItem::Food => 30 * party.size as i32,
+            RoomServiceItem::Towels => 10,
+        }
+    }
+}
+
+/// A party's order waiting to be fetched and taken up to their room. Doubles as the job
+/// the delivering worker is assigned to.
+#[derive(Component)]
+pub struct RoomServiceOrder {
+    pub item: RoomServiceItem,
+    /// The guest who ordered
+    pub guest: Entity,
+    /// Bedroom zone the order goes to, whose booking pays for it
+    pub room: Entity,
+    /// The kitchen or staff room nearest the bedroom
+    pub pickup: Vec2,
+    /// Where in the bedroom the order is left
+    pub drop_off: Vec2,
+    /// Game hours left to deliver before the party gives up on it
+    pub hours_left: f32,
+    pub assigned_pawn: Option<Entity>,
+    /// The order in the worker's hands, once collected
+    pub carried: Option<Entity>,
+}
+
+impl RoomServiceOrder {
+    pub fn new(
+        item: RoomServiceItem,
+        guest: Entity,
+        room: Entity,
+        pickup: Vec2,
+        drop_off: Vec2,
+    ) -> Self {
+        Self {
+            item,
+            guest,
+            room,
+            pickup,
+            drop_off,
+            hours_left: 2.0,
+            assigned_pawn: None,
+            carried: None,
+        }
+    }
+}
+
+/// Guest traffic through an amenity zone
+#[derive(Component, Default)]
+pub struct ZoneVisitors {
+    pub visits_today: u32,
+    pub visits_total: u32,
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+/// Something small enough to be carried around: building supplies, a party's bags,
+/// fresh towels, dirty plates or litter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    Materials,
+    Luggage,
+    Towels,
+    FoodPlate,
+    Trash,
+}
+
+impl ItemKind {
+    pub fn name(&self) -> &str {
+        match self {
+            ItemKind::Materials => "Materials",
+            ItemKind::Luggage => "Luggage",
+            ItemKind::Towels => "Towels",
+            ItemKind::FoodPlate => "Food plate",
+            ItemKind::Trash => "Trash",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            ItemKind::Materials => Color::srgb(0.75, 0.55, 0.3),
+            ItemKind::Luggage => Color::srgb(0.45, 0.25, 0.55),
+            ItemKind::Towels => Color::srgb(0.7, 0.85, 0.95),
+            ItemKind::FoodPlate => Color::srgb(0.95, 0.95, 0.9),
+            ItemKind::Trash => Color::srgb(0.4, 0.45, 0.3),
+        }
+    }
+
+    pub fn glyph(&self) -> char {
+        match self {
+            ItemKind::Materials => '=',
+            ItemKind::Luggage => 'b',
+            ItemKind::Towels => '~',
+            ItemKind::FoodPlate => 'o',
+            ItemKind::Trash => '%',
+        }
+    }
+
+    /// Left behind by guests rather than put down on purpose
+    pub fn is_litter(&self) -> bool {
+        matches!(self, ItemKind::FoodPlate | ItemKind::Trash)
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Item {
+    pub kind: ItemKind,
+    /// Whoever the item belongs to, e.g. the party travelling with the luggage
+    pub owner: Option<Entity>,
+}
+
+impl Item {
+    pub fn new(kind: ItemKind) -> Self {
+        Self { kind, owner: None }
+    }
+
+    pub fn owned_by(kind: ItemKind, owner: Entity) -> Self {
+        Self {
+            kind,
+            owner: Some(owner),
+        }
+    }
+}
+
+/// An item in someone's hands; it is drawn on top of them and moves with them.
+/// Items without it lie on the ground.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Carried {
+    pub by: Entity,
+}
@@ -1,13 +1,21 @@
 pub mod building;
 pub mod furniture;
+pub mod furniture_catalog;
+pub mod guest;
+pub mod item;
 pub mod pawn;
+pub mod stable_id;
 pub mod work;
 pub mod work_assignment;
 pub mod zone;
 
 pub use building::*;
 pub use furniture::*;
+pub use furniture_catalog::*;
+pub use guest::*;
+pub use item::*;
 pub use pawn::*;
+pub use stable_id::*;
 pub use work::*;
 pub use work_assignment::*;
 pub use zone::*;
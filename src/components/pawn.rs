@@ -1,5 +1,6 @@
-use super::GridPosition;
+use super::WorkType;
 use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
 
 #[derive(Component)]
 pub struct Pawn {
@@ -16,15 +17,263 @@ impl Default for Pawn {
     }
 }
 
+/// How rested and content a worker is, each from 0 to 1. Tired or unhappy staff
+/// work more slowly, and staff who stay miserable for too long quit.
+#[derive(Component)]
+pub struct StaffNeeds {
+    pub energy: f32,
+    pub morale: f32,
+    /// Share of recent hours spent working, from 0 (idle) to 1 (never stopped)
+    pub workload: f32,
+    /// Game hours morale has been below the quitting threshold without a let-up
+    pub miserable_hours: f32,
+}
+
+impl Default for StaffNeeds {
+    fn default() -> Self {
+        Self {
+            energy: 1.0,
+            morale: 0.8,
+            workload: 0.0,
+            miserable_hours: 0.0,
+        }
+    }
+}
+
+impl StaffNeeds {
+    /// Multiplier on how fast this worker builds, deconstructs and repairs
+    pub fn work_speed(&self) -> f32 {
+        let morale_factor = 0.6 + 0.4 * self.morale;
+        let energy_factor = if self.energy < 0.2 { 0.75 } else { 1.0 };
+        morale_factor * energy_factor
+    }
+}
+
+/// How practised a worker is at each kind of work, from 1 to MAX_LEVEL. Every level
+/// above the first makes them quicker at it; training courses are the only way up.
+#[derive(Component, Debug, Clone)]
+pub struct StaffSkills {
+    levels: HashMap<WorkType, u8>,
+}
+
+impl Default for StaffSkills {
+    fn default() -> Self {
+        Self {
+            levels: Self::TRAINABLE
+                .into_iter()
+                .map(|work_type| (work_type, 1))
+                .collect(),
+        }
+    }
+}
+
+impl StaffSkills {
+    pub const MAX_LEVEL: u8 = 5;
+    /// The kinds of work a worker gets quicker at with practice, and so the only ones
+    /// with courses
+    pub const TRAINABLE: [WorkType; 3] = [
+        WorkType::Construction,
+        WorkType::Reception,
+        WorkType::Cleaning,
+    ];
+    /// Extra work speed each level above the first brings
+    const SPEED_PER_LEVEL: f32 = 0.15;
+
+    pub fn level(&self, work_type: WorkType) -> u8 {
+        self.levels.get(&work_type).copied().unwrap_or(1)
+    }
+
+    pub fn raise(&mut self, work_type: WorkType) {
+        let level = (self.level(work_type) + 1).min(Self::MAX_LEVEL);
+        self.levels.insert(work_type, level);
+    }
+
+    /// Multiplier on how fast this worker does `work_type`
+    pub fn speed(&self, work_type: WorkType) -> f32 {
+        1.0 + Self::SPEED_PER_LEVEL * (self.level(work_type) - 1) as f32
+    }
+}
+
+/// A worker booked onto a training course, who leaves for it once their current job is
+/// done
+#[derive(Component)]
+pub struct TrainingEnrolment {
+    pub skill: WorkType,
+}
+
+/// A worker away on a training course: off duty and off the map until it ends, when
+/// the skill goes up a level
+#[derive(Component)]
+pub struct InTraining {
+    pub skill: WorkType,
+    /// GameClock hours when the course ends
+    pub ends_hours: f32,
+}
+
+/// A worker heading to or resting in a staff room; they take no jobs until rested
+#[derive(Component)]
+pub struct OnBreak {
+    pub zone: Entity,
+    /// GameClock hours when the break began
+    pub started_hours: f32,
+}
+
 #[derive(Component)]
 pub struct MovementTarget {
     pub target: Vec2,
 }
 
+/// Waypoints (world positions) towards the current MovementTarget
+#[derive(Component)]
+pub struct PawnPath {
+    pub waypoints: Vec<Vec2>,
+    pub goal: Vec2,
+    pub replan_timer: f32,
+}
+
+/// How a worker's route to its MovementTarget has been shrinking, so one pinned against a
+/// new wall or sent somewhere unreachable is noticed
+#[derive(Component)]
+pub struct StuckWatch {
+    pub goal: Vec2,
+    /// Shortest remaining route seen since the last real progress
+    pub best_remaining: f32,
+    pub stalled_seconds: f32,
+    pub repaths: u32,
+}
+
+/// A job this worker gave up on as out of reach; they leave it to others for a while
+#[derive(Component)]
+pub struct UnreachableJob {
+    pub job: Entity,
+    pub seconds_left: f32,
+}
+
 #[derive(Component, Default)]
 pub struct CurrentJob {
     pub job_id: Option<Entity>,
 }
 
-// A pawn occupies 2x2 tiles
+/// Finished jobs a worker's history keeps, oldest dropped first
+pub const JOB_HISTORY_LENGTH: usize = 30;
+
+/// The kinds of job a worker can see through to the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobKind {
+    Construction,
+    Deconstruction,
+    Repair,
+    Cleaning,
+    RoomService,
+    Incident,
+    PestControl,
+}
+
+impl JobKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobKind::Construction => "Construction",
+            JobKind::Deconstruction => "Deconstruction",
+            JobKind::Repair => "Repair",
+            JobKind::Cleaning => "Cleaning",
+            JobKind::RoomService => "Room Service",
+            JobKind::Incident => "Incident",
+            JobKind::PestControl => "Pest Control",
+        }
+    }
+}
+
+/// A job a worker finished, and how long it took from taking it on
+#[derive(Debug, Clone, Copy)]
+pub struct JobRecord {
+    pub kind: JobKind,
+    pub hours: f32,
+    /// GameClock hours when it was finished
+    pub finished_hours: f32,
+}
+
+/// The last jobs a worker finished, oldest first, and when they took on the one in hand
+#[derive(Component, Default)]
+pub struct JobHistory {
+    records: VecDeque<JobRecord>,
+    started: Option<(Entity, f32)>,
+}
+
+impl JobHistory {
+    pub fn start(&mut self, job: Entity, hours: f32) {
+        if self.started.is_none_or(|(current, _)| current != job) {
+            self.started = Some((job, hours));
+        }
+    }
+
+    /// Records `job` as done, if it's the one this worker took on. Jobs they were
+    /// given some other way have no start to time them from, so they go unrecorded.
+    pub fn finish(&mut self, job: Entity, kind: JobKind, hours: f32) {
+        let Some((_, started_hours)) = self.started.take_if(|(current, _)| *current == job) else {
+            return;
+        };
+
+        if self.records.len() == JOB_HISTORY_LENGTH {
+            self.records.pop_front();
+        }
+        self.records.push_back(JobRecord {
+            kind,
+            hours: (hours - started_hours).max(0.0),
+            finished_hours: hours,
+        });
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &JobRecord> {
+        self.records.iter()
+    }
+
+    /// Jobs finished in the `window` game hours up to `now`
+    pub fn jobs_within(&self, now: f32, window: f32) -> usize {
+        self.records
+            .iter()
+            .filter(|record| now - record.finished_hours <= window)
+            .count()
+    }
+
+    /// Mean hours a job took, over the whole history
+    pub fn average_hours(&self) -> Option<f32> {
+        if self.records.is_empty() {
+            return None;
+        }
+        let total: f32 = self.records.iter().map(|record| record.hours).sum();
+        Some(total / self.records.len() as f32)
+    }
+
+    /// The kind of job that fills most of the history
+    pub fn usual_kind(&self) -> Option<JobKind> {
+        let mut counts: Vec<(JobKind, usize)> = Vec::new();
+        for record in &self.records {
+            match counts.iter_mut().find(|(kind, _)| *kind == record.kind) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((record.kind, 1)),
+            }
+        }
+        counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(kind, _)| kind)
+    }
+}
+
+// In RimWorld, a pawn occupies 1 tile. Here a pawn covers 2x2 tiles, which gives finer
+// granularity for smaller objects and installations
 pub const PAWN_GRID_SIZE: i32 = 2;
+
+/// The tiles a pawn anchored on `anchor` covers, reaching up and right from it
+pub fn pawn_footprint(anchor: IVec2) -> impl Iterator<Item = IVec2> {
+    (0..PAWN_GRID_SIZE)
+        .flat_map(move |dx| (0..PAWN_GRID_SIZE).map(move |dy| anchor + IVec2::new(dx, dy)))
+}
+
+/// Whether a pawn can stand on `tile`: it is open, along with the rest of at least one
+/// pawn-sized block covering it
+pub fn fits_pawn(tile: IVec2, is_open: impl Fn(IVec2) -> bool) -> bool {
+    is_open(tile)
+        && pawn_footprint(tile - IVec2::splat(PAWN_GRID_SIZE - 1))
+            .any(|anchor| pawn_footprint(anchor).all(&is_open))
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A name for an entity that survives saving and loading. Bevy hands out new `Entity`
+/// ids every time the resort is rebuilt from a save, so anything that has to point at
+/// furniture, a pawn or a zone across a load refers to it by this instead. Ids are
+/// random UUIDs, so ones made in different games never collide.
+#[derive(
+    Component, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct StableId(pub Uuid);
+
+impl StableId {
+    /// A fresh id no other entity has
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
@@ -5,6 +5,8 @@ pub struct Blueprint {
     pub building_type: BlueprintType,
     pub work_required: f32,
     pub work_done: f32,
+    /// No worker can get near enough to build it, so it waits for a way in
+    pub blocked: bool,
 }
 
 impl Blueprint {
@@ -12,15 +14,17 @@ impl Blueprint {
         let work_required = match building_type {
             BlueprintType::Wall => 100.0,
             BlueprintType::Door(_) => 150.0,
-            BlueprintType::Window => 120.0,
+            BlueprintType::Window(_) => 120.0,
             BlueprintType::Floor(_) => 50.0, // Floors are faster to build
             BlueprintType::Furniture(_) => 80.0, // Furniture takes moderate time
+            BlueprintType::Boundary(_) => 60.0,
         };
 
         Self {
             building_type,
             work_required,
             work_done: 0.0,
+            blocked: false,
         }
     }
 
@@ -37,30 +41,63 @@ impl Blueprint {
 pub enum BlueprintType {
     Wall,
     Door(DoorOrientation),
-    Window,
+    Window(WindowOrientation),
     Floor(FloorType),
     Furniture(FurnitureType),
+    Boundary(BoundaryType),
 }
 
-use crate::components::{DoorOrientation, FloorType, FurnitureType};
+use crate::components::{
+    BoundaryType, DoorOrientation, FloorType, FurnitureType, WindowOrientation,
+};
 
 #[derive(Component)]
 pub struct ConstructionJob {
     pub blueprint: Entity,
     pub assigned_pawn: Option<Entity>,
     pub priority: i32,
+    /// Money set aside when the blueprint was placed. It is only charged once the
+    /// building is finished, and is released if the job is cancelled.
+    pub escrow: i32,
 }
 
 impl ConstructionJob {
-    pub fn new(blueprint: Entity) -> Self {
+    pub fn new(blueprint: Entity, escrow: i32) -> Self {
         Self {
             blueprint,
             assigned_pawn: None,
             priority: 5,
+            escrow,
         }
     }
 }
 
+/// Construction jobs for a run of touching blueprints of the same kind, in the order they
+/// should be built. One worker takes the whole run and walks along it instead of everyone
+/// grabbing whichever tile is nearest.
+#[derive(Component, Debug, Default)]
+pub struct JobBatch {
+    /// Jobs still to be built, from the end the worker started at
+    pub jobs: Vec<Entity>,
+    pub worker: Option<Entity>,
+}
+
+/// A hammer taken from a rack; it goes back to the same rack once nothing is left to build
+#[derive(Component)]
+pub struct HeldHammer {
+    pub rack: Entity,
+}
+
+/// On the way to a rack for a hammer before starting a construction job
+#[derive(Component)]
+pub struct FetchingHammer {
+    pub rack: Entity,
+}
+
+/// Walking a hammer back to its rack
+#[derive(Component)]
+pub struct ReturningHammer;
+
 #[derive(Component)]
 pub struct WorkInProgress {
     pub work_speed: f32, // work units per second
@@ -113,3 +150,109 @@ impl DeconstructionJob {
         }
     }
 }
+
+/// Marks a structure that needs a pawn to repair it (e.g. after a storm)
+#[derive(Component)]
+pub struct Damaged {
+    pub work_required: f32,
+    pub work_done: f32,
+}
+
+impl Default for Damaged {
+    fn default() -> Self {
+        Self {
+            work_required: 60.0,
+            work_done: 0.0,
+        }
+    }
+}
+
+impl Damaged {
+    pub fn is_repaired(&self) -> bool {
+        self.work_done >= self.work_required
+    }
+}
+
+#[derive(Component)]
+pub struct RepairJob {
+    pub target: Entity,
+    pub assigned_pawn: Option<Entity>,
+}
+
+impl RepairJob {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            assigned_pawn: None,
+        }
+    }
+}
+
+/// A bedroom that needs cleaning, and whoever has been sent to do it
+#[derive(Component)]
+pub struct CleaningJob {
+    pub room: Entity,
+    /// Where in the room the cleaner heads
+    pub spot: Vec2,
+    pub assigned_pawn: Option<Entity>,
+}
+
+impl CleaningJob {
+    pub fn new(room: Entity, spot: Vec2) -> Self {
+        Self {
+            room,
+            spot,
+            assigned_pawn: None,
+        }
+    }
+}
+
+/// What has moved in uninvited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PestKind {
+    /// Go for food areas
+    Ants,
+    /// Swoop on amenities that open straight outside, only where there is sea nearby
+    Seagulls,
+}
+
+impl PestKind {
+    pub fn name(&self) -> &str {
+        match self {
+            PestKind::Ants => "Ants",
+            PestKind::Seagulls => "Seagulls",
+        }
+    }
+}
+
+/// Pests in a zone, putting off the guests there until a cleaner sees them off. Doubles
+/// as the job the cleaner is assigned to.
+#[derive(Component)]
+pub struct Infestation {
+    pub kind: PestKind,
+    pub zone: Entity,
+    /// Where in the zone the pests are, and the cleaner heads
+    pub location: Vec2,
+    /// Game hours before the pests wander off on their own
+    pub hours_left: f32,
+    pub assigned_pawn: Option<Entity>,
+    /// Cleared like a dirty room, from 0 to 1
+    pub work_done: f32,
+}
+
+impl Infestation {
+    pub fn new(kind: PestKind, zone: Entity, location: Vec2) -> Self {
+        Self {
+            kind,
+            zone,
+            location,
+            hours_left: 24.0,
+            assigned_pawn: None,
+            work_done: 0.0,
+        }
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        self.work_done >= 1.0
+    }
+}
@@ -7,6 +7,7 @@ pub enum WorkType {
     Reception,
     Cleaning,
     Cooking,
+    SpaTherapist,
 }
 
 impl WorkType {
@@ -16,6 +17,18 @@ impl WorkType {
             WorkType::Reception => "Reception",
             WorkType::Cleaning => "Cleaning",
             WorkType::Cooking => "Cooking",
+            WorkType::SpaTherapist => "Spa Therapist",
+        }
+    }
+
+    /// Uniform worn by staff whose main job this is, so roles stand out on the map
+    pub fn uniform_color(&self) -> Color {
+        match self {
+            WorkType::Construction => Color::srgb(0.95, 0.6, 0.15),
+            WorkType::Reception => Color::srgb(0.2, 0.35, 0.75),
+            WorkType::Cleaning => Color::srgb(0.45, 0.8, 0.75),
+            WorkType::Cooking => Color::srgb(0.95, 0.95, 0.9),
+            WorkType::SpaTherapist => Color::srgb(0.75, 0.5, 0.85),
         }
     }
 
@@ -25,6 +38,7 @@ impl WorkType {
             WorkType::Reception,
             WorkType::Cleaning,
             WorkType::Cooking,
+            WorkType::SpaTherapist,
         ]
     }
 }
@@ -83,6 +97,7 @@ impl Default for WorkAssignments {
         priorities.insert(WorkType::Reception, WorkPriority::DISABLED);
         priorities.insert(WorkType::Cleaning, WorkPriority::DISABLED);
         priorities.insert(WorkType::Cooking, WorkPriority::DISABLED);
+        priorities.insert(WorkType::SpaTherapist, WorkPriority::DISABLED);
 
         Self { priorities }
     }
@@ -125,6 +140,12 @@ impl WorkAssignments {
             .min_by_key(|&&work_type| self.get_priority(work_type))
             .copied()
     }
+
+    /// The work the pawn puts first, which decides the uniform they wear. Ties go to
+    /// whichever comes first in the work panel.
+    pub fn main_role(&self) -> Option<WorkType> {
+        self.get_highest_priority_work(&WorkType::all())
+    }
 }
 
 /// Component marking a pawn currently staffing a reception desk
@@ -132,3 +153,20 @@ impl WorkAssignments {
 pub struct StaffingReception {
     pub desk_entity: Entity,
 }
+
+/// The tile a worker has claimed to stand on while doing its job. Nobody else is sent
+/// to work from there until the worker lets it go.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WorkSpot {
+    /// The job the spot was claimed for
+    pub job: Entity,
+    pub tile: IVec2,
+    /// World position of the tile, where the worker walks to
+    pub position: Vec2,
+}
+
+/// Component marking a pawn on duty at a spa massage table
+#[derive(Component)]
+pub struct StaffingSpa {
+    pub table_entity: Entity,
+}
@@ -1,4 +1,6 @@
+use super::guest::{GuestParty, FAMILY_ROOM_MIN_TILES};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Represents a zone/district in the resort
@@ -8,6 +10,12 @@ pub struct Zone {
     pub tiles: HashSet<IVec2>,
     pub quality: ZoneQuality,
     pub name: String,
+    /// Guests the zone's beds can sleep (only bedrooms have any)
+    pub capacity: u8,
+    /// Who may walk into the zone
+    pub access: ZoneAccess,
+    /// What its speakers play
+    pub ambience: ZoneAmbience,
 }
 
 impl Zone {
@@ -17,9 +25,21 @@ impl Zone {
             tiles: HashSet::new(),
             quality: ZoneQuality::None,
             name,
+            capacity: 0,
+            access: ZoneAccess::default_for(zone_type),
+            ambience: ZoneAmbience::default_for(zone_type),
         }
     }
 
+    /// Whether this bedroom can take the whole party: enough beds, and families need space
+    pub fn can_host(&self, party: GuestParty) -> bool {
+        self.zone_type == ZoneType::GuestBedroom
+            && self.access.admits_guests()
+            && self.quality != ZoneQuality::None
+            && self.capacity >= party.size
+            && (!party.is_family() || self.tile_count() >= FAMILY_ROOM_MIN_TILES)
+    }
+
     pub fn contains_tile(&self, pos: IVec2) -> bool {
         self.tiles.contains(&pos)
     }
@@ -38,7 +58,7 @@ impl Zone {
 }
 
 /// Types of zones in the resort
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ZoneType {
     Lobby,
     GuestBedroom,
@@ -47,9 +67,56 @@ pub enum ZoneType {
     FamilyFun,
     Adventure,
     Culinary,
+    /// Massages and saunas, open while a therapist is on duty
+    Spa,
+    /// Back-of-house room where staff take their breaks
+    StaffRoom,
 }
 
 impl ZoneType {
+    pub fn all() -> [ZoneType; 9] {
+        [
+            ZoneType::Lobby,
+            ZoneType::GuestBedroom,
+            ZoneType::Relaxation,
+            ZoneType::Luxury,
+            ZoneType::FamilyFun,
+            ZoneType::Adventure,
+            ZoneType::Culinary,
+            ZoneType::Spa,
+            ZoneType::StaffRoom,
+        ]
+    }
+
+    /// Zones guests can spend time at during their stay
+    pub fn amenities() -> [ZoneType; 6] {
+        [
+            ZoneType::Relaxation,
+            ZoneType::Luxury,
+            ZoneType::FamilyFun,
+            ZoneType::Adventure,
+            ZoneType::Culinary,
+            ZoneType::Spa,
+        ]
+    }
+
+    pub fn is_amenity(&self) -> bool {
+        Self::amenities().contains(self)
+    }
+
+    /// What each guest spends on a visit, added to their bill at check-out
+    pub fn visit_charge(&self) -> i32 {
+        match self {
+            ZoneType::Lobby | ZoneType::GuestBedroom | ZoneType::StaffRoom => 0,
+            ZoneType::Relaxation => 15,
+            ZoneType::Luxury => 40,
+            ZoneType::FamilyFun => 10,
+            ZoneType::Adventure => 25,
+            ZoneType::Culinary => 20,
+            ZoneType::Spa => 60,
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self {
             ZoneType::Lobby => "Lobby",
@@ -59,6 +126,8 @@ impl ZoneType {
             ZoneType::FamilyFun => "Family/Fun Zone",
             ZoneType::Adventure => "Adventure Zone",
             ZoneType::Culinary => "Culinary Zone",
+            ZoneType::Spa => "Spa",
+            ZoneType::StaffRoom => "Staff Room",
         }
     }
 
@@ -71,6 +140,24 @@ impl ZoneType {
             ZoneType::FamilyFun => Color::srgba(1.0, 0.5, 0.7, 0.3), // Pink
             ZoneType::Adventure => Color::srgba(1.0, 0.5, 0.2, 0.3), // Orange
             ZoneType::Culinary => Color::srgba(0.9, 0.3, 0.3, 0.3), // Red
+            ZoneType::Spa => Color::srgba(0.4, 0.85, 0.85, 0.3), // Teal
+            ZoneType::StaffRoom => Color::srgba(0.6, 0.6, 0.6, 0.3), // Grey
+        }
+    }
+
+    /// Tints that stay distinct with red-green colour blindness, mostly from the
+    /// Okabe-Ito set and told apart by lightness where hues run out
+    pub fn colorblind_color(&self) -> Color {
+        match self {
+            ZoneType::Lobby => Color::srgba(0.0, 0.45, 0.7, 0.3), // Blue
+            ZoneType::GuestBedroom => Color::srgba(0.35, 0.7, 0.9, 0.3), // Sky blue
+            ZoneType::Relaxation => Color::srgba(0.0, 0.6, 0.5, 0.3), // Bluish green
+            ZoneType::Luxury => Color::srgba(0.9, 0.6, 0.0, 0.3), // Orange
+            ZoneType::FamilyFun => Color::srgba(0.8, 0.6, 0.7, 0.3), // Reddish purple
+            ZoneType::Adventure => Color::srgba(0.8, 0.4, 0.0, 0.3), // Vermillion
+            ZoneType::Culinary => Color::srgba(0.45, 0.25, 0.6, 0.3), // Deep purple
+            ZoneType::Spa => Color::srgba(0.7, 0.9, 0.95, 0.3), // Pale cyan
+            ZoneType::StaffRoom => Color::srgba(0.6, 0.6, 0.6, 0.3), // Grey
         }
     }
 
@@ -105,12 +192,131 @@ impl ZoneType {
                 min_tiles: 20,
                 required_furniture: vec![],
             },
+            ZoneType::Spa => ZoneRequirements {
+                min_tiles: 16,
+                required_furniture: vec![],
+            },
+            ZoneType::StaffRoom => ZoneRequirements {
+                min_tiles: 9, // A 3x3 break room is enough
+                required_furniture: vec![],
+            },
+        }
+    }
+}
+
+/// Who may walk into a zone. Pathfinding never routes anyone through a zone closed to
+/// them, so a guest can't end up in a staff-only kitchen even on the way somewhere else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ZoneAccess {
+    #[default]
+    Public,
+    GuestsOnly,
+    StaffOnly,
+}
+
+impl ZoneAccess {
+    /// Staff rooms start closed to guests; everything else starts open to all
+    pub fn default_for(zone_type: ZoneType) -> Self {
+        match zone_type {
+            ZoneType::StaffRoom => ZoneAccess::StaffOnly,
+            _ => ZoneAccess::Public,
+        }
+    }
+
+    pub fn admits_guests(&self) -> bool {
+        *self != ZoneAccess::StaffOnly
+    }
+
+    pub fn admits_staff(&self) -> bool {
+        *self != ZoneAccess::GuestsOnly
+    }
+
+    /// The next setting along, for the inspector's button to cycle through
+    pub fn next(&self) -> Self {
+        match self {
+            ZoneAccess::Public => ZoneAccess::GuestsOnly,
+            ZoneAccess::GuestsOnly => ZoneAccess::StaffOnly,
+            ZoneAccess::StaffOnly => ZoneAccess::Public,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ZoneAccess::Public => "Public",
+            ZoneAccess::GuestsOnly => "Guests Only",
+            ZoneAccess::StaffOnly => "Staff Only",
+        }
+    }
+}
+
+/// Music or background sound a zone's speakers play. The tracks are read from
+/// `assets/audio/ambience/`; speakers set to one that isn't there stay quiet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ZoneAmbience {
+    #[default]
+    ElevatorMusic,
+    LoungeJazz,
+    OceanWaves,
+    Birdsong,
+    SpaChimes,
+    UpbeatPop,
+    Silence,
+}
+
+impl ZoneAmbience {
+    /// What suits each kind of zone; speakers outside any zone play elevator music
+    pub fn default_for(zone_type: ZoneType) -> Self {
+        match zone_type {
+            ZoneType::Lobby | ZoneType::StaffRoom => ZoneAmbience::ElevatorMusic,
+            ZoneType::Luxury | ZoneType::Culinary => ZoneAmbience::LoungeJazz,
+            ZoneType::GuestBedroom | ZoneType::Relaxation => ZoneAmbience::OceanWaves,
+            ZoneType::Adventure => ZoneAmbience::Birdsong,
+            ZoneType::Spa => ZoneAmbience::SpaChimes,
+            ZoneType::FamilyFun => ZoneAmbience::UpbeatPop,
+        }
+    }
+
+    /// The next setting along, for the inspector's button to cycle through
+    pub fn next(&self) -> Self {
+        match self {
+            ZoneAmbience::ElevatorMusic => ZoneAmbience::LoungeJazz,
+            ZoneAmbience::LoungeJazz => ZoneAmbience::OceanWaves,
+            ZoneAmbience::OceanWaves => ZoneAmbience::Birdsong,
+            ZoneAmbience::Birdsong => ZoneAmbience::SpaChimes,
+            ZoneAmbience::SpaChimes => ZoneAmbience::UpbeatPop,
+            ZoneAmbience::UpbeatPop => ZoneAmbience::Silence,
+            ZoneAmbience::Silence => ZoneAmbience::ElevatorMusic,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ZoneAmbience::ElevatorMusic => "Elevator Music",
+            ZoneAmbience::LoungeJazz => "Lounge Jazz",
+            ZoneAmbience::OceanWaves => "Ocean Waves",
+            ZoneAmbience::Birdsong => "Birdsong",
+            ZoneAmbience::SpaChimes => "Spa Chimes",
+            ZoneAmbience::UpbeatPop => "Upbeat Pop",
+            ZoneAmbience::Silence => "Silence",
+        }
+    }
+
+    /// Asset path of the looping track, or None for silence
+    pub fn track(&self) -> Option<&'static str> {
+        match self {
+            ZoneAmbience::ElevatorMusic => Some("audio/ambience/elevator_music.ogg"),
+            ZoneAmbience::LoungeJazz => Some("audio/ambience/lounge_jazz.ogg"),
+            ZoneAmbience::OceanWaves => Some("audio/ambience/ocean_waves.ogg"),
+            ZoneAmbience::Birdsong => Some("audio/ambience/birdsong.ogg"),
+            ZoneAmbience::SpaChimes => Some("audio/ambience/spa_chimes.ogg"),
+            ZoneAmbience::UpbeatPop => Some("audio/ambience/upbeat_pop.ogg"),
+            ZoneAmbience::Silence => None,
         }
     }
 }
 
 /// Quality rating for a zone
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ZoneQuality {
     None,      // Not valid/missing requirements
     Basic,     // Meets minimum requirements
@@ -139,6 +345,42 @@ impl ZoneQuality {
             ZoneQuality::Luxury => "Luxury",
         }
     }
+
+    /// One tier better, for rooms with a view; rooms that don't rate at all still don't
+    pub fn raised(&self) -> Self {
+        match self {
+            ZoneQuality::None => ZoneQuality::None,
+            ZoneQuality::Basic => ZoneQuality::Good,
+            ZoneQuality::Good => ZoneQuality::Excellent,
+            ZoneQuality::Excellent | ZoneQuality::Luxury => ZoneQuality::Luxury,
+        }
+    }
+
+    /// How strongly the zone overlay tints a zone of this quality
+    pub fn overlay_alpha(&self) -> f32 {
+        0.12 + 0.06 * self.stars() as f32
+    }
+
+    /// Nightly rate guests consider reasonable for a room of this quality
+    pub fn fair_nightly_rate(&self) -> i32 {
+        match self {
+            ZoneQuality::None => 0,
+            ZoneQuality::Basic => 80,
+            ZoneQuality::Good => 140,
+            ZoneQuality::Excellent => 220,
+            ZoneQuality::Luxury => 350,
+        }
+    }
+
+    /// Tiers that can be rented out and priced
+    pub fn rentable() -> [ZoneQuality; 4] {
+        [
+            ZoneQuality::Basic,
+            ZoneQuality::Good,
+            ZoneQuality::Excellent,
+            ZoneQuality::Luxury,
+        ]
+    }
 }
 
 /// Requirements for a zone to be valid
@@ -161,11 +403,48 @@ pub enum RequiredFurniture {
 #[derive(Component)]
 pub struct Room {
     pub tiles: HashSet<IVec2>,
+    /// Built doors set into the room's walls
+    pub doors: Vec<Entity>,
+    /// Rooms reached through one of those doors
+    pub adjacent_rooms: Vec<Entity>,
+    /// Whether any window in the room's walls looks outdoors
+    pub has_exterior_window: bool,
+    /// The best sight from any of those windows
+    pub scenic_view: Option<ScenicView>,
+    /// How much of the floor and surrounding walls is painted, from 0 to 1
+    pub painted_share: f32,
+}
+
+/// Something worth looking at from a window, which raises the room's quality a tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScenicView {
+    /// Down over lower ground from the top of a cliff
+    Cliff,
+    Ocean,
 }
 
 impl Room {
     pub fn new(tiles: HashSet<IVec2>) -> Self {
-        Self { tiles }
+        Self {
+            tiles,
+            doors: Vec::new(),
+            adjacent_rooms: Vec::new(),
+            has_exterior_window: false,
+            scenic_view: None,
+            painted_share: 0.0,
+        }
+    }
+
+    /// Paint counts towards a room's quality like furniture does: half painted is
+    /// worth one more piece, fully painted two
+    pub fn decor_bonus(&self) -> usize {
+        if self.painted_share >= 1.0 {
+            2
+        } else if self.painted_share >= 0.5 {
+            1
+        } else {
+            0
+        }
     }
 
     pub fn contains_tile(&self, pos: IVec2) -> bool {
@@ -176,3 +455,62 @@ impl Room {
         self.tiles.len()
     }
 }
+
+/// A corridor or other room that isn't zoned for anything, rated for the guests walking
+/// through it. Kept on the room's entity.
+#[derive(Component, Debug, Clone)]
+pub struct PublicSpace {
+    pub quality: ZoneQuality,
+    /// What kept it from rating higher
+    pub shortfalls: Vec<PublicSpaceShortfall>,
+}
+
+impl PublicSpace {
+    /// Each thing done right takes it a tier above Basic
+    pub fn new(shortfalls: Vec<PublicSpaceShortfall>) -> Self {
+        let quality = match PublicSpaceShortfall::ALL.len() - shortfalls.len() {
+            0 | 1 => ZoneQuality::Basic,
+            2 => ZoneQuality::Good,
+            3 => ZoneQuality::Excellent,
+            _ => ZoneQuality::Luxury,
+        };
+        Self {
+            quality,
+            shortfalls,
+        }
+    }
+
+    /// Whether guests walking through it think less of the resort
+    pub fn is_shabby(&self) -> bool {
+        self.quality <= ZoneQuality::Basic
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicSpaceShortfall {
+    /// Mostly a single tile wide
+    Narrow,
+    Dark,
+    /// Too little of it has a floor laid
+    BareFloor,
+    /// Too few furniture pieces or too little paint for its size
+    Undecorated,
+}
+
+impl PublicSpaceShortfall {
+    pub const ALL: [PublicSpaceShortfall; 4] = [
+        PublicSpaceShortfall::Narrow,
+        PublicSpaceShortfall::Dark,
+        PublicSpaceShortfall::BareFloor,
+        PublicSpaceShortfall::Undecorated,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            PublicSpaceShortfall::Narrow => "Narrow",
+            PublicSpaceShortfall::Dark => "Dark",
+            PublicSpaceShortfall::BareFloor => "Bare floor",
+            PublicSpaceShortfall::Undecorated => "Undecorated",
+        }
+    }
+}
@@ -5,15 +5,26 @@ mod systems;
 mod ui;
 
 use systems::{
-    AsciiRendererPlugin, BuildingPlugin, CameraPlugin, EconomyPlugin, GridPlugin, PawnPlugin,
-    RoomDetectionPlugin, SaveLoadPlugin, TimeControlPlugin, WorkPlugin, ZoneVisualizationPlugin,
+    AmbiencePlugin, AmenitiesPlugin, AppStatePlugin, AsciiRendererPlugin, BalancePlugin,
+    BudgetPlugin, BuildingPlugin, CalendarEventsPlugin, CameraPlugin, ControlsPlugin, CursorPlugin,
+    DemandPlugin, EconomyPlugin, FurnitureClaimsPlugin, GameRulesPlugin, GameStatePlugin,
+    GridPlugin, GuestsPlugin, HousekeepingPlugin, IncidentsPlugin, ItemsPlugin,
+    JobPrioritiesPlugin, LightingPlugin, NoisePlugin, PathDebugPlugin, PathfindingPlugin,
+    PawnPlugin, PestsPlugin, PhotoModePlugin, ReceptionPlugin, ReviewsPlugin, RoomDetectionPlugin,
+    RoomServicePlugin, SafetyPlugin, SaveLoadPlugin, SharedAssetsPlugin, SignagePlugin,
+    StableIdPlugin, StaffPlugin, StatisticsPlugin, TerrainPlugin, TimeControlPlugin, ToolsPlugin,
+    TrainingPlugin, TransportPlugin, WeatherPlugin, WorkPlugin, WorkVisualsPlugin,
+    ZoneVisualizationPlugin,
+};
+use ui::{
+    BuildingRulesPanelPlugin, CalendarDisplayPlugin, EconomyPanelPlugin, GameOverPanelPlugin,
+    GuestEmotesPlugin, HotbarPlugin, HoverTooltipPlugin, LocalizationPlugin, MainMenuPlugin,
+    MoneyDisplayPlugin, NewGamePanelPlugin, NightAuditPanelPlugin, NotificationsPlugin,
+    PauseMenuPlugin, ReceiptPopupPlugin, ReviewsPanelPlugin, RoomInspectorPlugin, RoomsPanelPlugin,
+    SaveLoadPanelPlugin, ScenarioEditorPlugin, SettingsPanelPlugin, SettingsPlugin,
+    SignEditorPlugin, SpeedControlPlugin, StatisticsPanelPlugin, ToolbarPlugin, UiFeedbackPlugin,
+    WorkAssignmentsPlugin,
 };
-use ui::{MoneyDisplayPlugin, SaveLoadPanelPlugin, SpeedControlPlugin, ToolbarPlugin, WorkAssignmentsPlugin};
-
-// Tile system constants
-// In RimWorld, a pawn occupies 1 tile. In our game, a pawn will occupy 2x2 tiles (4 tiles)
-// This gives us finer granularity for smaller objects and installations
-const PAWN_TILE_SIZE: usize = 2; // A pawn occupies 2x2 tiles
 
 fn main() {
     App::new()
@@ -31,20 +42,85 @@ fn main() {
             ToolbarPlugin,
             SpeedControlPlugin,
             MoneyDisplayPlugin,
+            UiFeedbackPlugin,
+            CalendarDisplayPlugin,
+            EconomyPanelPlugin,
             WorkAssignmentsPlugin,
+            RoomInspectorPlugin,
+            RoomsPanelPlugin,
+            BuildingRulesPanelPlugin,
+            NewGamePanelPlugin,
             SaveLoadPanelPlugin,
             BuildingPlugin,
         ))
         .add_plugins((
+            SharedAssetsPlugin,
             SaveLoadPlugin,
+            PathfindingPlugin,
             PawnPlugin,
             WorkPlugin,
             AsciiRendererPlugin,
             TimeControlPlugin,
             EconomyPlugin,
+            GameRulesPlugin,
             RoomDetectionPlugin,
             ZoneVisualizationPlugin,
+            WeatherPlugin,
+            DemandPlugin,
+            GuestsPlugin,
+            PhotoModePlugin,
+        ))
+        .add_plugins((
+            WorkVisualsPlugin,
+            AmenitiesPlugin,
+            LocalizationPlugin,
+            ReceiptPopupPlugin,
+            PathDebugPlugin,
+            StaffPlugin,
+            NotificationsPlugin,
+            TransportPlugin,
+            HoverTooltipPlugin,
+            CursorPlugin,
+            StatisticsPlugin,
+            StatisticsPanelPlugin,
+            ToolsPlugin,
+            IncidentsPlugin,
+            ItemsPlugin,
+        ))
+        .add_plugins((
+            SafetyPlugin,
+            TerrainPlugin,
+            HousekeepingPlugin,
+            ControlsPlugin,
+            SettingsPlugin,
+            SettingsPanelPlugin,
+            GameStatePlugin,
+            GameOverPanelPlugin,
+            BalancePlugin,
+            ReceptionPlugin,
+            SignagePlugin,
+            SignEditorPlugin,
+            NightAuditPanelPlugin,
+            RoomServicePlugin,
+        ))
+        .add_plugins((
+            AppStatePlugin,
+            MainMenuPlugin,
+            PauseMenuPlugin,
+            ScenarioEditorPlugin,
+            NoisePlugin,
+            StableIdPlugin,
+            GuestEmotesPlugin,
+            LightingPlugin,
+            BudgetPlugin,
+            PestsPlugin,
+            JobPrioritiesPlugin,
+            ReviewsPlugin,
+            ReviewsPanelPlugin,
+            CalendarEventsPlugin,
+            TrainingPlugin,
         ))
+        .add_plugins((AmbiencePlugin, FurnitureClaimsPlugin, HotbarPlugin))
         .add_systems(Startup, setup)
         .run();
 }
@@ -0,0 +1,188 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::camera::CameraController;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use bevy::asset::io::AssetReaderError;
+use bevy::asset::{AssetLoadError, AssetLoadFailedEvent};
+use bevy::audio::{AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Speakers can be heard from this far from the middle of the screen, in world units at
+/// normal zoom, getting quieter towards the edge
+const HEARING_RANGE: f32 = 320.0;
+const SPEAKER_VOLUME: f32 = 0.6;
+
+/// The track a speaker is playing, kept so it only restarts when its zone's ambience changes
+#[derive(Component)]
+pub struct SpeakerTrack {
+    pub ambience: ZoneAmbience,
+}
+
+/// The looping sound under a speaker
+#[derive(Component)]
+pub struct AmbienceSound;
+
+/// Ambience tracks are loaded once, the first time a zone or speaker is set to them, and
+/// any the asset server can't find are remembered so nothing looks them up again
+#[derive(Resource, Default)]
+pub struct AmbienceTracks {
+    handles: HashMap<&'static str, Handle<AudioSource>>,
+    /// File the asset server looked for, by track that failed to load
+    missing: HashMap<&'static str, String>,
+}
+
+impl AmbienceTracks {
+    /// The loaded track, starting the load if this is the first time it's asked for
+    fn load(&mut self, asset_server: &AssetServer, track: &'static str) -> Handle<AudioSource> {
+        self.handles
+            .entry(track)
+            .or_insert_with(|| asset_server.load(track))
+            .clone()
+    }
+
+    /// The file a track failed to load from, if it did
+    pub fn missing_file(&self, track: &str) -> Option<&str> {
+        self.missing.get(track).map(String::as_str)
+    }
+}
+
+pub struct AmbiencePlugin;
+
+impl Plugin for AmbiencePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AmbienceTracks>().add_systems(
+            Update,
+            (
+                load_zone_tracks,
+                note_missing_tracks,
+                choose_speaker_tracks,
+                fade_ambience_with_distance,
+            )
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Zones start loading their track as soon as it's chosen, so the inspector can tell
+/// whether it's there before any speaker plays it
+fn load_zone_tracks(
+    asset_server: Res<AssetServer>,
+    mut tracks: ResMut<AmbienceTracks>,
+    zone_query: Query<&Zone, Changed<Zone>>,
+) {
+    for zone in &zone_query {
+        if let Some(track) = zone.ambience.track() {
+            tracks.load(&asset_server, track);
+        }
+    }
+}
+
+/// Tracks the asset server couldn't load are kept with the file it looked for, which is
+/// resolved against the asset folder it was configured with rather than the working
+/// directory
+fn note_missing_tracks(
+    mut events: EventReader<AssetLoadFailedEvent<AudioSource>>,
+    mut tracks: ResMut<AmbienceTracks>,
+) {
+    for event in events.read() {
+        let Some(track) = tracks
+            .handles
+            .iter()
+            .find(|(_, handle)| handle.id() == event.id)
+            .map(|(&track, _)| track)
+        else {
+            continue;
+        };
+        let file = match &event.error {
+            AssetLoadError::AssetReaderError(AssetReaderError::NotFound(file)) => {
+                file.display().to_string()
+            }
+            _ => event.path.to_string(),
+        };
+        warn!("No ambience track at {}, speakers stay quiet", file);
+        tracks.missing.insert(track, file);
+    }
+}
+
+/// Each speaker plays what the zone it stands in is set to, or elevator music outside
+/// any zone, swapping tracks when the zone's setting changes
+fn choose_speaker_tracks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut tracks: ResMut<AmbienceTracks>,
+    speaker_query: Query<(Entity, &GridPosition, Option<&SpeakerTrack>), With<Speaker>>,
+    zone_query: Query<&Zone>,
+    sound_query: Query<(Entity, &Parent), With<AmbienceSound>>,
+) {
+    for (speaker_entity, pos, current) in &speaker_query {
+        let tile = pos.to_ivec2();
+        let ambience = zone_query
+            .iter()
+            .find(|zone| zone.contains_tile(tile))
+            .map(|zone| zone.ambience)
+            .unwrap_or_default();
+        if current.is_some_and(|current| current.ambience == ambience) {
+            continue;
+        }
+
+        for (sound_entity, parent) in &sound_query {
+            if parent.get() == speaker_entity {
+                commands.entity(sound_entity).despawn();
+            }
+        }
+        commands
+            .entity(speaker_entity)
+            .insert(SpeakerTrack { ambience });
+
+        let Some(track) = ambience.track() else {
+            continue;
+        };
+        if tracks.missing_file(track).is_some() {
+            continue;
+        }
+        // Starts silent; the fade system brings it up once the camera is near
+        let sound = commands
+            .spawn((
+                AudioPlayer::new(tracks.load(&asset_server, track)),
+                PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+                Transform::default(),
+                AmbienceSound,
+            ))
+            .id();
+        commands.entity(speaker_entity).add_child(sound);
+    }
+}
+
+/// Speakers get louder the nearer they are to the middle of the screen, and quieter as
+/// the camera zooms out
+fn fade_ambience_with_distance(
+    grid_settings: Res<GridSettings>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<CameraController>>,
+    speaker_query: Query<&GridPosition, With<Speaker>>,
+    sound_query: Query<(&Parent, &AudioSink), With<AmbienceSound>>,
+) {
+    let Ok((camera, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let listener = camera.translation.truncate();
+    let range = HEARING_RANGE * projection.scale.max(1.0);
+
+    for (parent, sink) in &sound_query {
+        let Ok(pos) = speaker_query.get(parent.get()) else {
+            continue;
+        };
+        let speaker = grid_to_world(
+            pos.to_ivec2(),
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+        let closeness = (1.0 - listener.distance(speaker) / range).clamp(0.0, 1.0);
+        let volume = SPEAKER_VOLUME * closeness / projection.scale.max(1.0);
+        if (sink.volume() - volume).abs() > 0.01 {
+            sink.set_volume(volume);
+        }
+    }
+}
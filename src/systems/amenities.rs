@@ -0,0 +1,176 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+/// Walking this many tiles halves how appealing an amenity is
+const DISTANCE_FALLOFF_TILES: f32 = 40.0;
+/// Amenities further than this from a guest are hard to find without a sign
+const REMOTE_AMENITY_TILES: f32 = 30.0;
+/// Guests read the signs within this many tiles of where they set out from
+const SIGN_READ_TILES: f32 = 12.0;
+/// How squarely a sign has to point at an amenity to count, as the cosine of the angle
+const SIGN_POINTING: f32 = 0.5;
+/// Remote amenities no sign points to are this much less likely to be picked, since
+/// guests who can't find their way give up and do something else
+const UNMARKED_APPEAL: f32 = 0.3;
+
+/// An amenity zone guests can choose to spend time at
+#[derive(Debug, Clone)]
+pub struct Amenity {
+    pub zone: Entity,
+    pub zone_type: ZoneType,
+    pub quality: ZoneQuality,
+    pub center: Vec2,
+}
+
+impl Amenity {
+    /// Better amenities are worth a longer walk
+    pub fn appeal(&self, from: Vec2) -> f32 {
+        let tiles = self.center.distance(from) / TILE_SIZE;
+        self.quality.stars() as f32 / (1.0 + tiles / DISTANCE_FALLOFF_TILES)
+    }
+}
+
+/// A sign as guests read it: where it stands, which way it points and to what
+#[derive(Debug, Clone)]
+pub struct Signpost {
+    pub position: Vec2,
+    pub direction: Vec2,
+    pub destination: ZoneType,
+}
+
+impl Signpost {
+    fn points_to(&self, amenity: &Amenity) -> bool {
+        let heading = (amenity.center - self.position).normalize_or_zero();
+        amenity.zone_type == self.destination && heading.dot(self.direction) >= SIGN_POINTING
+    }
+}
+
+/// Every amenity zone currently open to guests, rebuilt whenever zones or spa staffing
+/// change, and the signs pointing the way to them
+#[derive(Resource, Default)]
+pub struct AmenityRegistry {
+    pub amenities: Vec<Amenity>,
+    pub signs: Vec<Signpost>,
+}
+
+impl AmenityRegistry {
+    /// Nearby amenities are easy to find. Remote ones are only found as often when a sign
+    /// near the guest points the way.
+    pub fn wayfinding(&self, amenity: &Amenity, from: Vec2) -> f32 {
+        if amenity.center.distance(from) / TILE_SIZE <= REMOTE_AMENITY_TILES {
+            return 1.0;
+        }
+
+        let signposted = self.signs.iter().any(|sign| {
+            sign.position.distance(from) / TILE_SIZE <= SIGN_READ_TILES && sign.points_to(amenity)
+        });
+        if signposted {
+            1.0
+        } else {
+            UNMARKED_APPEAL
+        }
+    }
+
+    /// Picks somewhere for a guest at `from` to go among the amenities `filter` accepts,
+    /// weighted by appeal and how easy it is to find, so good nearby amenities are
+    /// likeliest but the rest still see some traffic
+    pub fn choose(
+        &self,
+        from: Vec2,
+        rng: &mut impl Rng,
+        filter: impl Fn(&Amenity) -> bool,
+    ) -> Option<&Amenity> {
+        self.choose_weighted(from, rng, |amenity| if filter(amenity) { 1.0 } else { 0.0 })
+    }
+
+    /// Like [`Self::choose`], with `weight` making some amenities more or less popular
+    /// than their appeal alone. Those it weighs at nothing are left out.
+    pub fn choose_weighted(
+        &self,
+        from: Vec2,
+        rng: &mut impl Rng,
+        weight: impl Fn(&Amenity) -> f32,
+    ) -> Option<&Amenity> {
+        let weights: Vec<f32> = self
+            .amenities
+            .iter()
+            .map(|amenity| weight(amenity) * amenity.appeal(from) * self.wayfinding(amenity, from))
+            .collect();
+        let distribution = WeightedIndex::new(&weights).ok()?;
+        self.amenities.get(distribution.sample(rng))
+    }
+}
+
+pub struct AmenitiesPlugin;
+
+impl Plugin for AmenitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AmenityRegistry>()
+            .add_systems(Update, update_amenity_registry.in_set(GameplaySet));
+    }
+}
+
+/// A spa only takes guests while a therapist is on duty at one of its massage tables
+fn has_therapist(
+    zone: &Zone,
+    staffing_query: &Query<&StaffingSpa>,
+    table_query: &Query<&GridPosition, With<MassageTable>>,
+) -> bool {
+    staffing_query.iter().any(|staffing| {
+        table_query
+            .get(staffing.table_entity)
+            .is_ok_and(|pos| zone.contains_tile(pos.to_ivec2()))
+    })
+}
+
+fn update_amenity_registry(
+    mut registry: ResMut<AmenityRegistry>,
+    grid_settings: Res<GridSettings>,
+    zone_query: Query<(Entity, &Zone)>,
+    changed_zones: Query<(), Changed<Zone>>,
+    mut removed_zones: RemovedComponents<Zone>,
+    staffing_query: Query<&StaffingSpa>,
+    changed_staffing: Query<(), Changed<StaffingSpa>>,
+    mut removed_staffing: RemovedComponents<StaffingSpa>,
+    table_query: Query<&GridPosition, With<MassageTable>>,
+) {
+    let removed = removed_zones.read().count() > 0;
+    let staffing_removed = removed_staffing.read().count() > 0;
+    if changed_zones.is_empty() && !removed && changed_staffing.is_empty() && !staffing_removed {
+        return;
+    }
+
+    registry.amenities = zone_query
+        .iter()
+        .filter(|(_, zone)| zone.zone_type.is_amenity() && zone.quality != ZoneQuality::None)
+        .filter(|(_, zone)| zone.tile_count() > 0 && zone.access.admits_guests())
+        .filter(|(_, zone)| {
+            zone.zone_type != ZoneType::Spa || has_therapist(zone, &staffing_query, &table_query)
+        })
+        .map(|(entity, zone)| {
+            let total: Vec2 = zone
+                .tiles
+                .iter()
+                .map(|tile| {
+                    grid_to_world(
+                        *tile,
+                        grid_settings.tile_size,
+                        grid_settings.width,
+                        grid_settings.height,
+                    )
+                })
+                .sum();
+
+            Amenity {
+                zone: entity,
+                zone_type: zone.zone_type,
+                quality: zone.quality,
+                center: total / zone.tile_count() as f32,
+            }
+        })
+        .collect();
+}
@@ -0,0 +1,89 @@
+use crate::systems::photo_mode::PhotoMode;
+use crate::systems::save_load::{process_load_requests, LoadRequestState};
+use crate::systems::time_control::TimeSpeed;
+use crate::ui::UiInputBlocker;
+use bevy::prelude::*;
+
+/// Where the app is: on the main menu, building a resort from a save, playing, or
+/// stopped on the pause menu. Only `InGame` runs the simulation and takes world input.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Loading,
+    InGame,
+    Paused,
+}
+
+/// Every system that plays the game, rather than drawing menus. It only runs `InGame`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+pub struct AppStatePlugin;
+
+impl Plugin for AppStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .configure_sets(PreUpdate, GameplaySet.run_if(in_state(AppState::InGame)))
+            .configure_sets(Update, GameplaySet.run_if(in_state(AppState::InGame)))
+            .configure_sets(FixedUpdate, GameplaySet.run_if(in_state(AppState::InGame)))
+            .configure_sets(PostUpdate, GameplaySet.run_if(in_state(AppState::InGame)))
+            .add_systems(
+                Update,
+                (
+                    toggle_pause_menu,
+                    finish_loading
+                        .after(process_load_requests)
+                        .run_if(in_state(AppState::Loading)),
+                ),
+            )
+            .add_systems(OnEnter(AppState::Paused), stop_time)
+            .add_systems(OnExit(AppState::Paused), restart_time);
+    }
+}
+
+/// Escape or the gamepad's Start button opens and closes the pause menu. Escape is left
+/// to photo mode and text fields while they are using it.
+fn toggle_pause_menu(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    ui_blocker: Res<UiInputBlocker>,
+    photo_mode: Res<PhotoMode>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let escape = keyboard.just_pressed(KeyCode::Escape)
+        && !ui_blocker.text_input_active
+        && !photo_mode.active;
+    let start = gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::Start));
+    if !escape && !start {
+        return;
+    }
+
+    match state.get() {
+        AppState::InGame => next_state.set(AppState::Paused),
+        AppState::Paused => next_state.set(AppState::InGame),
+        AppState::MainMenu | AppState::Loading => {}
+    }
+}
+
+/// Play starts once the requested save, if any, has been built
+fn finish_loading(load_state: Res<LoadRequestState>, mut next_state: ResMut<NextState<AppState>>) {
+    if !load_state.is_pending() {
+        next_state.set(AppState::InGame);
+    }
+}
+
+// The clock already stands still with the simulation stopped; stopping virtual time
+// as well keeps animations and anything else reading `Time` frozen behind the menu
+fn stop_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn restart_time(time_speed: Res<TimeSpeed>, mut time: ResMut<Time<Virtual>>) {
+    if !time_speed.is_paused() {
+        time.unpause();
+    }
+}
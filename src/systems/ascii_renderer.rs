@@ -1,29 +1,206 @@
 use crate::components::{self, *};
+use crate::systems::app_state::GameplaySet;
+use crate::systems::grid::TILE_SIZE;
+use crate::ui::UiInputBlocker;
 use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
 
 const BLUEPRINT_CHAR: char = 'B';
+const ASCII_BACKGROUND: Color = Color::srgb(0.02, 0.02, 0.03);
+
+/// Draw order of glyphs, so pawns read on top of furniture on top of floors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsciiLayer {
+    Floor,
+    Structure,
+    Furniture,
+    Marker,
+    Pawn,
+}
+
+impl AsciiLayer {
+    fn z(&self) -> f32 {
+        match self {
+            AsciiLayer::Floor => 1.0,
+            AsciiLayer::Structure => 2.0,
+            AsciiLayer::Furniture => 3.0,
+            AsciiLayer::Marker => 4.0,
+            AsciiLayer::Pawn => 5.0,
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct AsciiSprite {
     pub character: char,
     pub color: Color,
+    pub layer: AsciiLayer,
+}
+
+impl AsciiSprite {
+    pub fn new(character: char, color: Color, layer: AsciiLayer) -> Self {
+        Self {
+            character,
+            color,
+            layer,
+        }
+    }
+}
+
+/// The text entity drawing a source entity's glyph while ASCII mode is on
+#[derive(Component)]
+struct AsciiGlyph;
+
+/// Toggleable display mode that swaps every sprite and mesh for a colored glyph
+#[derive(Resource, Default)]
+pub struct AsciiMode {
+    pub enabled: bool,
+    /// Glyph entity for each source entity
+    glyphs: HashMap<Entity, Entity>,
+    saved_clear_color: Option<Color>,
 }
 
 pub struct AsciiRendererPlugin;
 
 impl Plugin for AsciiRendererPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                // Blueprints are now rendered as translucent white meshes, no ASCII needed
-                render_ascii_sprites,
-                render_wall_projections,
-            ),
-        );
+        app.init_resource::<AsciiMode>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        toggle_ascii_mode,
+                        add_ascii_to_structures,
+                        add_ascii_to_blueprints,
+                        update_door_glyphs,
+                        sync_ascii_glyphs,
+                    )
+                        .chain(),
+                    render_wall_projections,
+                )
+                    .in_set(GameplaySet),
+            );
     }
 }
 
+fn toggle_ascii_mode(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut ascii_mode: ResMut<AsciiMode>,
+    mut clear_color: ResMut<ClearColor>,
+    mut source_query: Query<&mut Visibility, With<AsciiSprite>>,
+) {
+    if ui_blocker.text_input_active || !keyboard.just_pressed(KeyCode::F3) {
+        return;
+    }
+
+    ascii_mode.enabled = !ascii_mode.enabled;
+
+    if ascii_mode.enabled {
+        ascii_mode.saved_clear_color = Some(clear_color.0);
+        clear_color.0 = ASCII_BACKGROUND;
+        info!("ASCII display mode on (F3 to switch back)");
+    } else {
+        // Tear down the glyph layer and bring the regular sprites back
+        for (_, glyph) in ascii_mode.glyphs.drain() {
+            commands.entity(glyph).despawn();
+        }
+        for mut visibility in &mut source_query {
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+        if let Some(color) = ascii_mode.saved_clear_color.take() {
+            clear_color.0 = color;
+        }
+        info!("ASCII display mode off");
+    }
+}
+
+/// Gives every world entity a glyph as it appears, so toggling the mode is instant
+fn add_ascii_to_structures(
+    mut commands: Commands,
+    wall_query: Query<(Entity, &Wall), Without<AsciiSprite>>,
+    window_query: Query<Entity, (With<components::Window>, Without<AsciiSprite>)>,
+    door_query: Query<(Entity, &Door), Without<AsciiSprite>>,
+    floor_query: Query<(Entity, &Floor), Without<AsciiSprite>>,
+    boundary_query: Query<(Entity, &Boundary), Without<AsciiSprite>>,
+    furniture_query: Query<
+        (Entity, &FurnitureType),
+        (With<Furniture>, Without<Blueprint>, Without<AsciiSprite>),
+    >,
+    pawn_query: Query<Entity, (With<Pawn>, Without<AsciiSprite>)>,
+    marker_query: Query<Entity, (With<DeconstructionMarker>, Without<AsciiSprite>)>,
+) {
+    for (entity, wall) in &wall_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            '#',
+            wall.color(),
+            AsciiLayer::Structure,
+        ));
+    }
+
+    for entity in &window_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            '=',
+            Color::srgb(0.6, 0.8, 1.0),
+            AsciiLayer::Structure,
+        ));
+    }
+
+    for (entity, door) in &door_query {
+        commands.entity(entity).insert(door_glyph(door));
+    }
+
+    for (entity, floor) in &floor_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            '.',
+            floor.color(),
+            AsciiLayer::Floor,
+        ));
+    }
+
+    for (entity, boundary) in &boundary_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            boundary.boundary_type.ascii_char(),
+            boundary.boundary_type.color(),
+            AsciiLayer::Structure,
+        ));
+    }
+
+    for (entity, furniture_type) in &furniture_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            furniture_type.ascii_char(),
+            furniture_type.color(),
+            AsciiLayer::Furniture,
+        ));
+    }
+
+    for entity in &pawn_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            '@',
+            Color::srgb(0.3, 0.8, 1.0),
+            AsciiLayer::Pawn,
+        ));
+    }
+
+    for entity in &marker_query {
+        commands.entity(entity).insert(AsciiSprite::new(
+            'X',
+            Color::srgb(1.0, 0.2, 0.2),
+            AsciiLayer::Marker,
+        ));
+    }
+}
+
+fn door_glyph(door: &Door) -> AsciiSprite {
+    let character = match door.state {
+        DoorState::Closed => '+',
+        DoorState::Open => '/',
+    };
+    AsciiSprite::new(character, Color::srgb(0.7, 0.5, 0.3), AsciiLayer::Structure)
+}
+
 fn add_ascii_to_blueprints(
     mut commands: Commands,
     query: Query<(Entity, &Blueprint), Without<AsciiSprite>>,
@@ -32,36 +209,89 @@ fn add_ascii_to_blueprints(
         let color = match blueprint.building_type {
             BlueprintType::Wall => Color::srgba(0.5, 0.5, 0.5, 0.5),
             BlueprintType::Door(_) => Color::srgba(0.4, 0.3, 0.2, 0.5),
-            BlueprintType::Window => Color::srgba(0.6, 0.8, 1.0, 0.5),
+            BlueprintType::Window(_) => Color::srgba(0.6, 0.8, 1.0, 0.5),
             BlueprintType::Floor(floor_type) => floor_type.color().with_alpha(0.5),
             BlueprintType::Furniture(furniture_type) => furniture_type.color().with_alpha(0.5),
+            BlueprintType::Boundary(boundary_type) => boundary_type.color().with_alpha(0.5),
         };
 
-        commands.entity(entity).insert(AsciiSprite {
-            character: BLUEPRINT_CHAR,
+        commands.entity(entity).insert(AsciiSprite::new(
+            BLUEPRINT_CHAR,
             color,
-        });
+            AsciiLayer::Marker,
+        ));
     }
 }
 
-fn render_ascii_sprites(
+fn update_door_glyphs(mut door_query: Query<(&Door, &mut AsciiSprite), Changed<Door>>) {
+    for (door, mut ascii) in &mut door_query {
+        let glyph = door_glyph(door);
+        if ascii.character != glyph.character {
+            *ascii = glyph;
+        }
+    }
+}
+
+/// Keeps one glyph per source entity while ASCII mode is on: spawns missing ones,
+/// follows moving sources, and drops glyphs whose source is gone
+fn sync_ascii_glyphs(
     mut commands: Commands,
-    query: Query<(Entity, &AsciiSprite), (Changed<AsciiSprite>, Without<Text2d>)>,
+    mut ascii_mode: ResMut<AsciiMode>,
+    mut source_query: Query<(Entity, Ref<AsciiSprite>, &GlobalTransform, &mut Visibility)>,
+    mut glyph_query: Query<(&mut Text2d, &mut TextColor, &mut Transform), With<AsciiGlyph>>,
 ) {
-    for (entity, ascii) in &query {
-        commands.entity(entity).despawn_descendants();
-        commands.entity(entity).with_children(|parent| {
-            parent.spawn((
-                Text2d::new(ascii.character.to_string()),
-                TextFont {
-                    font_size: 20.0,
-                    ..default()
-                },
-                TextColor(ascii.color),
-                Transform::from_xyz(0.0, 0.0, 1.0),
-            ));
-        });
+    if !ascii_mode.enabled {
+        return;
+    }
+
+    let mut live = HashSet::new();
+
+    for (source, ascii, global_transform, mut visibility) in &mut source_query {
+        // The glyph replaces the regular rendering (and any child visuals with it)
+        visibility.set_if_neq(Visibility::Hidden);
+        live.insert(source);
+
+        let position = global_transform.translation().truncate();
+        let translation = position.extend(ascii.layer.z());
+
+        match ascii_mode.glyphs.get(&source) {
+            Some(glyph) => {
+                let Ok((mut text, mut color, mut transform)) = glyph_query.get_mut(*glyph) else {
+                    continue;
+                };
+                if transform.translation != translation {
+                    transform.translation = translation;
+                }
+                if ascii.is_changed() {
+                    **text = ascii.character.to_string();
+                    color.0 = ascii.color;
+                }
+            }
+            None => {
+                let glyph = commands
+                    .spawn((
+                        Text2d::new(ascii.character.to_string()),
+                        TextFont {
+                            font_size: TILE_SIZE,
+                            ..default()
+                        },
+                        TextColor(ascii.color),
+                        Transform::from_translation(translation),
+                        AsciiGlyph,
+                    ))
+                    .id();
+                ascii_mode.glyphs.insert(source, glyph);
+            }
+        }
     }
+
+    ascii_mode.glyphs.retain(|source, glyph| {
+        let keep = live.contains(source);
+        if !keep {
+            commands.entity(*glyph).despawn();
+        }
+        keep
+    });
 }
 
 // Marker components for projection visuals
@@ -78,14 +308,24 @@ fn render_wall_projections(
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut wall_sets: ParamSet<(
         Query<
-            (Entity, &WallProjection, Option<&Children>),
+            (
+                Entity,
+                &WallProjection,
+                Option<&WallConnections>,
+                Option<&Children>,
+            ),
             (
                 Or<(With<Wall>, With<components::Window>)>,
-                Changed<WallProjection>,
+                Or<(Changed<WallProjection>, Changed<WallConnections>)>,
             ),
         >,
         Query<
-            (Entity, &WallProjection, Option<&Children>),
+            (
+                Entity,
+                &WallProjection,
+                Option<&WallConnections>,
+                Option<&Children>,
+            ),
             (
                 Or<(With<Wall>, With<components::Window>)>,
                 Without<WallProjectionVisualized>,
@@ -108,6 +348,7 @@ fn render_wall_projections(
 
     let mut rebuild = |entity: Entity,
                        projection: &WallProjection,
+                       connections: Option<&WallConnections>,
                        children: Option<&Children>,
                        commands: &mut Commands| {
         if let Some(children) = children {
@@ -130,50 +371,65 @@ fn render_wall_projections(
 
         commands.entity(entity).insert(WallProjectionVisualized);
 
+        // Auto-tiled walls only fill a post and arms, so the shading hugs that shape:
+        // (left, right) of the top edge and (bottom, top) of the side edges
+        let (north_span, side_span, edge) = match connections {
+            Some(connections) => {
+                let core = TILE_SIZE * WALL_THICKNESS / 2.0;
+                let reach = |joined: bool| if joined { HALF_TILE } else { core };
+                (
+                    (-reach(connections.west), reach(connections.east)),
+                    (-reach(connections.south), reach(connections.north)),
+                    core,
+                )
+            }
+            None => (
+                (-HALF_TILE, HALF_TILE),
+                (-SIDE_SHADE_HEIGHT / 2.0, SIDE_SHADE_HEIGHT / 2.0),
+                HALF_TILE,
+            ),
+        };
+        let north_width = north_span.1 - north_span.0;
+        let north_center = (north_span.0 + north_span.1) / 2.0;
+        let side_height = side_span.1 - side_span.0;
+        let side_center = (side_span.0 + side_span.1) / 2.0;
+
         commands.entity(entity).with_children(|parent| {
             if active_projection.north {
                 parent.spawn((
-                    Mesh2d(meshes.add(Rectangle::new(TILE_SIZE, SHADE_THICKNESS))),
+                    Mesh2d(meshes.add(Rectangle::new(north_width, SHADE_THICKNESS))),
                     MeshMaterial2d(materials.add(NORTH_SHADE_COLOR)),
-                    Transform::from_xyz(0.0, HALF_TILE - SHADE_THICKNESS / 2.0, 0.15),
+                    Transform::from_xyz(north_center, edge - SHADE_THICKNESS / 2.0, 0.15),
                     WallProjectionVisual,
                 ));
             }
 
-            // Side shadows are always full height and centered for consistency
+            // Side shadows cover the full height of the shape on that side
             if active_projection.east {
                 parent.spawn((
-                    Mesh2d(meshes.add(Rectangle::new(SIDE_SHADE_WIDTH, SIDE_SHADE_HEIGHT))),
+                    Mesh2d(meshes.add(Rectangle::new(SIDE_SHADE_WIDTH, side_height))),
                     MeshMaterial2d(materials.add(SIDE_SHADE_COLOR)),
-                    Transform::from_xyz(
-                        HALF_TILE - SIDE_SHADE_WIDTH / 2.0,
-                        0.0,
-                        0.1,
-                    ),
+                    Transform::from_xyz(edge - SIDE_SHADE_WIDTH / 2.0, side_center, 0.1),
                     WallProjectionVisual,
                 ));
             }
 
             if active_projection.west {
                 parent.spawn((
-                    Mesh2d(meshes.add(Rectangle::new(SIDE_SHADE_WIDTH, SIDE_SHADE_HEIGHT))),
+                    Mesh2d(meshes.add(Rectangle::new(SIDE_SHADE_WIDTH, side_height))),
                     MeshMaterial2d(materials.add(SIDE_SHADE_COLOR)),
-                    Transform::from_xyz(
-                        -HALF_TILE + SIDE_SHADE_WIDTH / 2.0,
-                        0.0,
-                        0.1,
-                    ),
+                    Transform::from_xyz(-edge + SIDE_SHADE_WIDTH / 2.0, side_center, 0.1),
                     WallProjectionVisual,
                 ));
             }
         });
     };
 
-    for (entity, projection, children) in wall_sets.p0().iter() {
-        rebuild(entity, projection, children, &mut commands);
+    for (entity, projection, connections, children) in wall_sets.p0().iter() {
+        rebuild(entity, projection, connections, children, &mut commands);
     }
 
-    for (entity, projection, children) in wall_sets.p1().iter() {
-        rebuild(entity, projection, children, &mut commands);
+    for (entity, projection, connections, children) in wall_sets.p1().iter() {
+        rebuild(entity, projection, connections, children, &mut commands);
     }
 }
@@ -0,0 +1,176 @@
+use crate::components::{BoundaryType, FloorType, ZoneQuality};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Where the balance table is read from, relative to the working directory
+pub const BALANCE_PATH: &str = "assets/balance.ron";
+/// Used when the file on disk is missing or broken, so the game always starts
+const BASE_BALANCE: &str = include_str!("../../assets/balance.ron");
+/// How often debug builds look for edits to the balance file
+#[cfg(debug_assertions)]
+const RELOAD_CHECK_SECONDS: f32 = 1.0;
+
+/// Costs, work rates and quality thresholds, kept in `assets/balance.ron` so the economy
+/// can be tuned without recompiling
+#[derive(Resource, Debug, Clone, Deserialize)]
+pub struct Balance {
+    pub costs: BuildCosts,
+    pub work: WorkRates,
+    pub quality: QualityThresholds,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuildCosts {
+    pub wall: i32,
+    pub door: i32,
+    pub window: i32,
+    pub floors: HashMap<FloorType, i32>,
+    pub boundaries: HashMap<BoundaryType, i32>,
+}
+
+impl BuildCosts {
+    /// Floors missing from the table are free rather than unbuildable
+    pub fn floor(&self, floor_type: FloorType) -> i32 {
+        self.floors.get(&floor_type).copied().unwrap_or(0)
+    }
+
+    pub fn boundary(&self, boundary_type: BoundaryType) -> i32 {
+        self.boundaries.get(&boundary_type).copied().unwrap_or(0)
+    }
+}
+
+/// Work units a worker gets through each second, before their morale and energy
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkRates {
+    pub construction: f32,
+    pub deconstruction: f32,
+    pub repair: f32,
+    pub incident: f32,
+    /// Cleanliness, from 0 to 1, restored each second
+    pub cleaning: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QualityThresholds {
+    pub bedroom: RoomQualityTiers,
+    /// Amenity rooms are rated like lobbies
+    pub lobby: RoomQualityTiers,
+    pub staff_room: RoomQualityTiers,
+}
+
+/// What a room needs to reach each tier above Basic
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoomQualityTiers {
+    pub good: QualityTier,
+    pub excellent: QualityTier,
+    /// Rooms of this kind can't be Luxury without it
+    pub luxury: Option<QualityTier>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct QualityTier {
+    pub tiles: usize,
+    pub furniture: usize,
+}
+
+impl RoomQualityTiers {
+    /// Rates a room that is already big enough for its type
+    pub fn rate(&self, tile_count: usize, furniture_count: usize) -> ZoneQuality {
+        let reaches =
+            |tier: &QualityTier| tile_count >= tier.tiles && furniture_count >= tier.furniture;
+
+        if self.luxury.as_ref().is_some_and(reaches) {
+            ZoneQuality::Luxury
+        } else if reaches(&self.excellent) {
+            ZoneQuality::Excellent
+        } else if reaches(&self.good) {
+            ZoneQuality::Good
+        } else {
+            ZoneQuality::Basic
+        }
+    }
+}
+
+impl Default for Balance {
+    fn default() -> Self {
+        ron::from_str(BASE_BALANCE).expect("the built-in balance table should parse")
+    }
+}
+
+impl Balance {
+    /// Reads the balance file, falling back to the built-in table if it can't be used
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&path.display().to_string(), &contents).unwrap_or_default(),
+            Err(err) => {
+                warn!(
+                    "Using the built-in balance table, {} unreadable: {}",
+                    path.display(),
+                    err
+                );
+                Self::default()
+            }
+        }
+    }
+
+    fn parse(source: &str, contents: &str) -> Option<Self> {
+        match ron::from_str(contents) {
+            Ok(balance) => Some(balance),
+            Err(err) => {
+                warn!("Ignoring balance table {}: {}", source, err);
+                None
+            }
+        }
+    }
+}
+
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Balance::load(Path::new(BALANCE_PATH)));
+
+        #[cfg(debug_assertions)]
+        app.add_systems(Update, reload_balance);
+    }
+}
+
+/// Picks up edits to the balance file while a debug build runs. A broken edit is
+/// reported and the current numbers kept.
+#[cfg(debug_assertions)]
+fn reload_balance(
+    time: Res<Time<Real>>,
+    mut balance: ResMut<Balance>,
+    mut next_check: Local<f32>,
+    mut last_modified: Local<Option<SystemTime>>,
+) {
+    *next_check -= time.delta_secs();
+    if *next_check > 0.0 {
+        return;
+    }
+    *next_check = RELOAD_CHECK_SECONDS;
+
+    let path = Path::new(BALANCE_PATH);
+    let Ok(modified) = fs::metadata(path).and_then(|metadata| metadata.modified()) else {
+        return;
+    };
+    // The first look only notes the time; the file was read at startup
+    let Some(previous) = last_modified.replace(modified) else {
+        return;
+    };
+    if previous == modified {
+        return;
+    }
+
+    let Ok(contents) = fs::read_to_string(path) else {
+        return;
+    };
+    if let Some(reloaded) = Balance::parse(BALANCE_PATH, &contents) {
+        *balance = reloaded;
+        info!("Reloaded balance table from {}", BALANCE_PATH);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::systems::app_state::GameplaySet;
+use crate::systems::economy::{FinanceLedger, Money};
+use crate::systems::game_state::ScenarioGoals;
+use crate::systems::time_control::{GameClock, SpeedOption, TimeSpeed};
+use crate::ui::{GameSettings, Localization, Notification};
+use bevy::prelude::*;
+
+/// Share of the debt added to it each morning the resort starts in the red
+pub const DEBT_INTEREST_RATE: f32 = 0.02;
+
+/// Interest owed on a night spent `amount` in the red, rounded up to the dollar
+pub fn debt_interest(amount: i32) -> i32 {
+    if amount >= 0 {
+        return 0;
+    }
+    (-amount as f32 * DEBT_INTEREST_RATE).ceil() as i32
+}
+
+pub struct BudgetPlugin;
+
+impl Plugin for BudgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (charge_debt_interest, watch_balance)
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Debt grows each morning by a share of what is owed, so staying in the red gets harder
+/// to climb out of the longer it lasts
+fn charge_debt_interest(
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut money: ResMut<Money>,
+    mut finance: ResMut<FinanceLedger>,
+    mut last_day: Local<Option<u32>>,
+) {
+    let day = clock.day();
+    let Some(previous) = last_day.replace(day) else {
+        return;
+    };
+    if previous == day || money.infinite {
+        return;
+    }
+
+    let interest = debt_interest(money.amount);
+    if interest == 0 {
+        return;
+    }
+    money.add(-interest);
+    finance.record(day, localization.t("budget.interest"), -interest);
+}
+
+/// Warns once when spending money drops under the alert set in the settings, and again
+/// when the balance goes below zero, optionally pausing the game there. Each warning is
+/// given again after the balance has recovered.
+fn watch_balance(
+    money: Res<Money>,
+    settings: Res<GameSettings>,
+    goals: Res<ScenarioGoals>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut time_speed: ResMut<TimeSpeed>,
+    mut last_available: Local<Option<i32>>,
+    mut last_amount: Local<Option<i32>>,
+) {
+    if money.infinite || !money.is_changed() {
+        return;
+    }
+    let available = money.available();
+    let previous_available = last_available.replace(available);
+    let previous_amount = last_amount.replace(money.amount);
+
+    // A balance that was already low (say, from a loaded save) is not news
+    let (Some(previous_available), Some(previous_amount)) = (previous_available, previous_amount)
+    else {
+        return;
+    };
+
+    let alert = settings.low_funds_alert;
+    if alert > 0 && available < alert && previous_available >= alert && money.amount >= 0 {
+        notifications.send(Notification {
+            message: localization
+                .format("budget.low_funds", &[("available", available.to_string())]),
+        });
+    }
+
+    if money.amount < 0 && previous_amount >= 0 {
+        notifications.send(Notification {
+            message: localization.format(
+                "budget.in_debt",
+                &[
+                    ("percent", format!("{:.0}", DEBT_INTEREST_RATE * 100.0)),
+                    ("limit", goals.bankruptcy_limit.to_string()),
+                    ("days", goals.bankruptcy_days.to_string()),
+                ],
+            ),
+        });
+        if settings.pause_in_debt && !time_speed.is_paused() {
+            time_speed.set_speed(SpeedOption::Paused);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use crate::components::*;
+use crate::systems::guests::{SatisfactionCause, SatisfactionEvent};
+use crate::systems::shared_assets::SharedAssets;
+use crate::ui::{DeconstructFilter, LocalizedText, Localization, UiInputBlocker};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Satisfaction a party loses when the player goes ahead with work in their room
+const RENOVATION_SATISFACTION_PENALTY: f32 = 0.2;
+
+/// What kind of thing a deconstruction target is, used for filtering and the summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeconstructKind {
+    Wall,
+    Door,
+    Window,
+    Furniture,
+    Floor,
+    Boundary,
+}
+
+impl DeconstructKind {
+    pub fn all() -> [DeconstructKind; 6] {
+        [
+            DeconstructKind::Wall,
+            DeconstructKind::Door,
+            DeconstructKind::Window,
+            DeconstructKind::Boundary,
+            DeconstructKind::Furniture,
+            DeconstructKind::Floor,
+        ]
+    }
+
+    pub fn plural_name(&self) -> &str {
+        match self {
+            DeconstructKind::Wall => "Walls",
+            DeconstructKind::Door => "Doors",
+            DeconstructKind::Window => "Windows",
+            DeconstructKind::Furniture => "Furniture",
+            DeconstructKind::Floor => "Floors",
+            DeconstructKind::Boundary => "Fences",
+        }
+    }
+
+    /// Floors are only torn up when explicitly asked for, so a plain drag never strips them
+    pub fn matches(&self, filter: DeconstructFilter) -> bool {
+        match filter {
+            DeconstructFilter::All => *self != DeconstructKind::Floor,
+            DeconstructFilter::Structures => matches!(
+                self,
+                DeconstructKind::Wall
+                    | DeconstructKind::Door
+                    | DeconstructKind::Window
+                    | DeconstructKind::Boundary
+            ),
+            DeconstructFilter::Furniture => *self == DeconstructKind::Furniture,
+            DeconstructFilter::Floors => *self == DeconstructKind::Floor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DeconstructTarget {
+    pub entity: Entity,
+    pub kind: DeconstructKind,
+    pub grid_pos: IVec2,
+    pub world_pos: Vec2,
+    /// The booked guest bedroom the target is in, whose party the work would disturb
+    pub guest_room: Option<Entity>,
+}
+
+/// Targets collected by a deconstruct drag, waiting for the player to confirm
+#[derive(Resource, Default)]
+pub struct PendingDeconstruction {
+    pub targets: Vec<DeconstructTarget>,
+}
+
+impl PendingDeconstruction {
+    pub fn is_pending(&self) -> bool {
+        !self.targets.is_empty()
+    }
+
+    pub fn count(&self, kind: DeconstructKind) -> usize {
+        self.targets
+            .iter()
+            .filter(|target| target.kind == kind)
+            .count()
+    }
+
+    pub fn summary(&self, localization: &Localization) -> String {
+        let mut lines = vec![localization.format(
+            "deconstruct.summary",
+            &[("count", self.targets.len().to_string())],
+        )];
+        for kind in DeconstructKind::all() {
+            let count = self.count(kind);
+            if count > 0 {
+                let name = localization.term("deconstruct_kind", kind.plural_name());
+                lines.push(format!("{}: {}", name, count));
+            }
+        }
+        let in_guest_rooms = self
+            .targets
+            .iter()
+            .filter(|target| target.guest_room.is_some())
+            .count();
+        if in_guest_rooms > 0 {
+            lines.push(localization.format(
+                "deconstruct.guests_staying",
+                &[("count", in_guest_rooms.to_string())],
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Spawns the red overlay marker and the job pawns pick up to remove the target
+pub fn spawn_deconstruction_marker(
+    commands: &mut Commands,
+    shared_assets: &SharedAssets,
+    materials: &mut Assets<ColorMaterial>,
+    target_entity: Entity,
+    grid_pos: IVec2,
+    world_pos: Vec2,
+) {
+    let marker_entity = commands
+        .spawn((
+            Mesh2d(shared_assets.tile_mesh.clone()),
+            // Own material: its opacity tracks this marker's progress
+            MeshMaterial2d(materials.add(Color::srgba(1.0, 0.0, 0.0, 0.4))),
+            Transform::from_xyz(world_pos.x, world_pos.y, 10.0), // High z-level to render on top
+            DeconstructionMarker::new(target_entity),
+            GridPosition::new(grid_pos.x, grid_pos.y),
+        ))
+        .id();
+
+    commands.spawn(DeconstructionJob::new(marker_entity));
+}
+
+#[derive(Component)]
+pub struct DeconstructConfirmPanel;
+
+#[derive(Component)]
+pub struct DeconstructSummaryText;
+
+#[derive(Component)]
+pub struct ConfirmDeconstructButton;
+
+#[derive(Component)]
+pub struct CancelDeconstructButton;
+
+pub fn setup_deconstruct_confirm_panel(mut commands: Commands) {
+    // Hidden panel just above the toolbar, shown after a deconstruct drag
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(90.0),
+                left: Val::Percent(40.0),
+                width: Val::Px(240.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(8.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            DeconstructConfirmPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                DeconstructSummaryText,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(5.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_confirm_button(row, "deconstruct.confirm", ConfirmDeconstructButton);
+                    spawn_confirm_button(row, "deconstruct.cancel", CancelDeconstructButton);
+                });
+        });
+}
+
+fn spawn_confirm_button(parent: &mut ChildBuilder, label_key: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(30.0),
+                padding: UiRect::horizontal(Val::Px(12.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+pub fn update_deconstruct_confirm_panel(
+    pending: Res<PendingDeconstruction>,
+    mut panel_query: Query<&mut Node, With<DeconstructConfirmPanel>>,
+    mut text_query: Query<&mut Text, With<DeconstructSummaryText>>,
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    interaction_query: Query<
+        &Interaction,
+        Or<(
+            With<ConfirmDeconstructButton>,
+            With<CancelDeconstructButton>,
+        )>,
+    >,
+    localization: Res<Localization>,
+) {
+    let should_block = pending.is_pending()
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.deconstruct_confirm_blocking != should_block {
+        ui_blocker.deconstruct_confirm_blocking = should_block;
+        ui_blocker.recompute();
+    }
+
+    if !pending.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        node.display = if pending.is_pending() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        **text = pending.summary(&localization);
+    }
+}
+
+pub fn handle_deconstruct_confirm_clicks(
+    mut commands: Commands,
+    shared_assets: Res<SharedAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut pending: ResMut<PendingDeconstruction>,
+    confirm_query: Query<&Interaction, (Changed<Interaction>, With<ConfirmDeconstructButton>)>,
+    cancel_query: Query<&Interaction, (Changed<Interaction>, With<CancelDeconstructButton>)>,
+    marker_query: Query<&DeconstructionMarker>,
+    mut booking_query: Query<&mut Booking>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+) {
+    if cancel_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        pending.targets.clear();
+        return;
+    }
+
+    if !confirm_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+
+    // Each party put up with the work once, however much of their room is torn out
+    let mut disturbed = HashSet::new();
+    for target in pending.targets.drain(..) {
+        // Skip anything that was marked (or removed) since the drag
+        let already_marked = marker_query
+            .iter()
+            .any(|marker| marker.target_entity == target.entity);
+        if already_marked || commands.get_entity(target.entity).is_none() {
+            continue;
+        }
+
+        spawn_deconstruction_marker(
+            &mut commands,
+            &shared_assets,
+            &mut materials,
+            target.entity,
+            target.grid_pos,
+            target.world_pos,
+        );
+        disturbed.extend(target.guest_room);
+    }
+
+    for room in disturbed {
+        if let Ok(mut booking) = booking_query.get_mut(room) {
+            booking.satisfaction =
+                (booking.satisfaction - RENOVATION_SATISFACTION_PENALTY).max(0.0);
+            satisfaction_events.send(SatisfactionEvent {
+                room,
+                guest: None,
+                cause: SatisfactionCause::Renovation,
+            });
+        }
+    }
+}
+
+pub fn update_deconstruct_confirm_button_colors(
+    mut button_query: Query<
+        (
+            &mut BackgroundColor,
+            &Interaction,
+            Has<ConfirmDeconstructButton>,
+        ),
+        Or<(
+            With<ConfirmDeconstructButton>,
+            With<CancelDeconstructButton>,
+        )>,
+    >,
+) {
+    for (mut color, interaction, is_confirm) in &mut button_query {
+        *color = match (interaction, is_confirm) {
+            (Interaction::Hovered, true) => Color::srgb(0.7, 0.4, 0.4).into(), // Red (destructive)
+            (Interaction::Hovered, false) => Color::srgb(0.35, 0.35, 0.35).into(),
+            _ => Color::srgb(0.25, 0.25, 0.25).into(),
+        };
+    }
+}
@@ -13,34 +13,67 @@ pub fn insert_furniture_component(
         .insert(furniture_type)
         .insert(orientation);
 
-    // Add furniture-specific marker/data components
-    match furniture_type {
-        FurnitureType::Bed(bed_type) => {
-            commands.entity(entity).insert(Bed::new(bed_type));
-        }
-        FurnitureType::Desk => {
-            commands.entity(entity).insert(Desk);
-        }
-        FurnitureType::Chair => {
-            commands.entity(entity).insert(Chair);
-        }
-        FurnitureType::Dresser => {
-            commands.entity(entity).insert(Dresser);
-        }
-        FurnitureType::Nightstand => {
-            commands.entity(entity).insert(Nightstand);
-        }
-        FurnitureType::Toilet => {
-            commands.entity(entity).insert(Toilet);
-        }
-        FurnitureType::Sink => {
-            commands.entity(entity).insert(Sink);
-        }
-        FurnitureType::Tub => {
-            commands.entity(entity).insert(Tub);
-        }
-        FurnitureType::ReceptionConsole => {
-            commands.entity(entity).insert(ReceptionConsole::new());
+    // Add furniture-specific marker/data components from the catalog tags
+    for tag in &furniture_type.definition().tags {
+        match tag.as_str() {
+            "bed" => {
+                commands.entity(entity).insert(Bed::new(BedType::Single));
+            }
+            "double_bed" => {
+                commands.entity(entity).insert(Bed::new(BedType::Double));
+            }
+            "desk" => {
+                commands.entity(entity).insert(Desk);
+            }
+            "chair" => {
+                commands.entity(entity).insert(Chair);
+            }
+            "dresser" => {
+                commands.entity(entity).insert(Dresser);
+            }
+            "nightstand" => {
+                commands.entity(entity).insert(Nightstand);
+            }
+            "toilet" => {
+                commands.entity(entity).insert(Toilet);
+            }
+            "sink" => {
+                commands.entity(entity).insert(Sink);
+            }
+            "tub" => {
+                commands.entity(entity).insert(Tub);
+            }
+            "reception" => {
+                commands.entity(entity).insert(ReceptionConsole::new());
+            }
+            "locker" => {
+                commands.entity(entity).insert(StaffLocker);
+            }
+            "couch" => {
+                commands.entity(entity).insert(StaffCouch);
+            }
+            "massage" => {
+                commands.entity(entity).insert(MassageTable);
+            }
+            "sauna" => {
+                commands.entity(entity).insert(Sauna);
+            }
+            "arrival" => {
+                commands.entity(entity).insert(ArrivalPoint);
+            }
+            "tools" => {
+                commands.entity(entity).insert(ToolRack);
+            }
+            "sign" => {
+                commands.entity(entity).insert(Sign::default());
+            }
+            "lamp" => {
+                commands.entity(entity).insert(Lamp);
+            }
+            "speaker" => {
+                commands.entity(entity).insert(Speaker);
+            }
+            other => warn!("Unknown tag '{}' on furniture '{}'", other, furniture_type.name()),
         }
     }
 }
@@ -1,20 +1,7 @@
 use bevy::prelude::*;
 use crate::components::furniture::*;
-use crate::systems::grid::GridSettings;
-
-// Sprite path constants
-const SINGLE_BED_SPRITE_PATH: &str = "generated/furniture/bed.png";
-const DOUBLE_BED_SPRITE_PATH: &str = "generated/furniture/double_bed.png";
-const DRESSER_FRONT_SPRITE_PATH: &str = "generated/furniture/dresser.png";
-const DRESSER_BACK_SPRITE_PATH: &str = "generated/furniture/dresser_back.png";
-const DRESSER_SIDE_SPRITE_PATH: &str = "generated/furniture/dresser_side.png";
-const TUB_SPRITE_PATH: &str = "generated/furniture/tub.png";
-const TOILET_SPRITE_PATH: &str = "generated/furniture/toilet.png";
-const SINK_SPRITE_PATH: &str = "generated/furniture/sink.png";
-const END_TABLE_SPRITE_PATH: &str = "generated/furniture/end_table.png";
-const COMPUTER_SIDE_SPRITE_PATH: &str = "generated/furniture/computer_side.png";
-const COMPUTER_FRONT_SPRITE_PATH: &str = "generated/furniture/computer_front.png";
-const COMPUTER_BACK_SPRITE_PATH: &str = "generated/furniture/computer_back.png";
+use crate::components::furniture_catalog::FurnitureSprite;
+use crate::systems::grid::{grid_to_world, GridSettings};
 
 pub enum FurnitureSpriteConfig {
     Rotating {
@@ -24,9 +11,7 @@ pub enum FurnitureSpriteConfig {
     Directional {
         sprite: Sprite,
     },
-    Mesh {
-        color: Color,
-    },
+    Mesh,
 }
 
 /// Converts furniture orientation to rotation in radians
@@ -39,6 +24,23 @@ pub fn furniture_rotation_radians(orientation: FurnitureOrientation) -> f32 {
     }
 }
 
+/// Where furniture anchored at `grid_pos` is drawn: the middle of the tiles it occupies,
+/// so the preview, the placed piece and its footprint line up in every orientation
+pub fn furniture_world_center(
+    furniture_type: FurnitureType,
+    grid_pos: IVec2,
+    orientation: FurnitureOrientation,
+    grid_settings: &GridSettings,
+) -> Vec2 {
+    let anchor = grid_to_world(
+        grid_pos,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+    anchor + furniture_type.footprint_center_offset(orientation) * grid_settings.tile_size
+}
+
 /// Creates the sprite configuration for furniture placement/preview
 pub fn create_furniture_sprite(
     furniture_type: FurnitureType,
@@ -47,105 +49,64 @@ pub fn create_furniture_sprite(
     grid_settings: &GridSettings,
     _is_preview: bool,
 ) -> FurnitureSpriteConfig {
-    let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-    let sprite_size = Vec2::new(
-        base_width_tiles as f32 * grid_settings.tile_size,
-        base_height_tiles as f32 * grid_settings.tile_size,
-    );
-
-    match furniture_type {
-        FurnitureType::Bed(bed_type) => {
-            let sprite_path = match bed_type {
-                BedType::Single => SINGLE_BED_SPRITE_PATH,
-                BedType::Double => DOUBLE_BED_SPRITE_PATH,
-            };
+    let definition = furniture_type.definition();
 
-            FurnitureSpriteConfig::Rotating {
-                sprite: Sprite {
-                    image: asset_server.load(sprite_path),
-                    custom_size: Some(sprite_size),
-                    ..default()
-                },
-                rotation_radians: furniture_rotation_radians(orientation),
-            }
-        }
-        FurnitureType::Dresser => {
-            let (sprite_path, flip_x) = match orientation {
-                FurnitureOrientation::South => (DRESSER_FRONT_SPRITE_PATH, false),
-                FurnitureOrientation::North => (DRESSER_BACK_SPRITE_PATH, false),
-                FurnitureOrientation::East => (DRESSER_SIDE_SPRITE_PATH, false),
-                FurnitureOrientation::West => (DRESSER_SIDE_SPRITE_PATH, true),
-            };
+    // Rotating art is drawn facing East and turned with the piece, which swaps its sides
+    // just as the footprint's are. Directional art is never turned, so it is sized to
+    // the footprint as it stands.
+    let (width_tiles, height_tiles) = match definition.sprite {
+        Some(FurnitureSprite::Directional { .. }) => furniture_type.oriented_dimensions(orientation),
+        _ => furniture_type.base_dimensions(),
+    };
+    let sprite_size = Vec2::new(
+        width_tiles as f32 * grid_settings.tile_size,
+        height_tiles as f32 * grid_settings.tile_size,
+    ) * definition.sprite_scale;
 
-            let mut sprite = Sprite {
-                image: asset_server.load(sprite_path),
+    match &definition.sprite {
+        Some(FurnitureSprite::Rotating(sprite_path)) => FurnitureSpriteConfig::Rotating {
+            sprite: Sprite {
+                image: asset_server.load(sprite_path.clone()),
                 custom_size: Some(sprite_size),
                 ..default()
-            };
-            sprite.flip_x = flip_x;
-
-            FurnitureSpriteConfig::Directional { sprite }
-        }
-        FurnitureType::Tub => {
-            FurnitureSpriteConfig::Rotating {
-                sprite: Sprite {
-                    image: asset_server.load(TUB_SPRITE_PATH),
-                    custom_size: Some(sprite_size),
-                    ..default()
-                },
-                rotation_radians: furniture_rotation_radians(orientation),
-            }
-        }
-        FurnitureType::Toilet => {
-            FurnitureSpriteConfig::Rotating {
-                sprite: Sprite {
-                    image: asset_server.load(TOILET_SPRITE_PATH),
-                    custom_size: Some(sprite_size),
-                    ..default()
-                },
-                rotation_radians: furniture_rotation_radians(orientation),
-            }
-        }
-        FurnitureType::Sink => {
-            FurnitureSpriteConfig::Rotating {
-                sprite: Sprite {
-                    image: asset_server.load(SINK_SPRITE_PATH),
-                    custom_size: Some(sprite_size),
-                    ..default()
-                },
-                rotation_radians: furniture_rotation_radians(orientation),
-            }
-        }
-        FurnitureType::Nightstand => {
-            FurnitureSpriteConfig::Rotating {
-                sprite: Sprite {
-                    image: asset_server.load(END_TABLE_SPRITE_PATH),
-                    custom_size: Some(sprite_size),
-                    ..default()
-                },
-                rotation_radians: furniture_rotation_radians(orientation),
-            }
-        }
-        FurnitureType::ReceptionConsole => {
+            },
+            rotation_radians: furniture_rotation_radians(orientation),
+        },
+        Some(FurnitureSprite::Directional { front, back, side }) => {
             let (sprite_path, flip_x) = match orientation {
-                FurnitureOrientation::East => (COMPUTER_SIDE_SPRITE_PATH, false),
-                FurnitureOrientation::West => (COMPUTER_SIDE_SPRITE_PATH, true),
-                FurnitureOrientation::South => (COMPUTER_FRONT_SPRITE_PATH, false),
-                FurnitureOrientation::North => (COMPUTER_BACK_SPRITE_PATH, false),
+                FurnitureOrientation::South => (front, false),
+                FurnitureOrientation::North => (back, false),
+                FurnitureOrientation::East => (side, false),
+                FurnitureOrientation::West => (side, true),
             };
 
             let mut sprite = Sprite {
-                image: asset_server.load(sprite_path),
-                custom_size: Some(Vec2::splat(grid_settings.tile_size * 0.9)),
+                image: asset_server.load(sprite_path.clone()),
+                custom_size: Some(sprite_size),
                 ..default()
             };
             sprite.flip_x = flip_x;
 
             FurnitureSpriteConfig::Directional { sprite }
         }
-        // Default fallback for furniture types without specific sprites
-        _ => FurnitureSpriteConfig::Mesh {
-            color: furniture_type.color(),
-        },
+        // Furniture without art is drawn as a coloured block
+        None => FurnitureSpriteConfig::Mesh,
+    }
+}
+
+/// Desk-top furniture is always a sprite drawn over the desk; pieces without art get a
+/// plain coloured square instead of a mesh
+pub fn desk_furniture_sprite(
+    furniture_type: FurnitureType,
+    sprite_config: FurnitureSpriteConfig,
+    grid_settings: &GridSettings,
+) -> Sprite {
+    match sprite_config {
+        FurnitureSpriteConfig::Rotating { sprite, .. }
+        | FurnitureSpriteConfig::Directional { sprite } => sprite,
+        FurnitureSpriteConfig::Mesh => Sprite::from_color(
+            furniture_type.color(),
+            Vec2::splat(grid_settings.tile_size * furniture_type.definition().sprite_scale),
+        ),
     }
 }
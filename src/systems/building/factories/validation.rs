@@ -1,7 +1,16 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use crate::components::furniture::*;
 use crate::components::building::GridPosition;
+use crate::components::{fits_pawn, Booking, Room, Zone, ZoneType};
 use crate::systems::building::BuildingMap;
+use crate::systems::grid::GridSettings;
+use crate::systems::terrain::Terrain;
+use crate::ui::BuildingType;
+use std::collections::{HashSet, VecDeque};
+
+/// How close to the map border edge-placed furniture such as the shuttle stop must reach
+pub const MAP_EDGE_TILES: i32 = 2;
 
 /// Validates if furniture can be placed at the given position
 pub fn validate_furniture_placement(
@@ -13,8 +22,8 @@ pub fn validate_furniture_placement(
 ) -> bool {
     let furniture_tiles = furniture_type.tiles_occupied(grid_pos, orientation);
 
-    // Special case: Reception console requires a desk underneath
-    if furniture_type == FurnitureType::ReceptionConsole {
+    // Special case: desk-top furniture like the reception console needs a desk underneath
+    if furniture_type.sits_on_desk() {
         let has_desk = desk_query
             .map(|query| {
                 query.iter().any(|desk_pos| {
@@ -32,7 +41,7 @@ pub fn validate_furniture_placement(
         if !has_desk {
             return false;
         }
-        // Reception console doesn't occupy tiles (desk handles that)
+        // Desk-top furniture doesn't occupy tiles (desk handles that)
         return true;
     }
 
@@ -41,5 +50,467 @@ pub fn validate_furniture_placement(
         building_map.floors.contains(pos)
             && !building_map.occupied.contains(pos)
             && !building_map.doors.contains_key(pos)
+            && !building_map.boundaries.contains_key(pos)
     })
 }
+
+/// Optional construction rules layered on top of the basic overlap checks.
+/// Sandbox mode switches every rule off for creative players.
+#[derive(Resource)]
+pub struct BuildingRules {
+    pub walls_require_floor: bool,
+    pub furniture_requires_room: bool,
+    pub openings_require_wall: bool,
+    /// Keeps building work out of guest bedrooms while a party is booked in
+    pub protect_guest_rooms: bool,
+    pub sandbox: bool,
+}
+
+impl Default for BuildingRules {
+    fn default() -> Self {
+        Self {
+            walls_require_floor: false,
+            furniture_requires_room: false,
+            // Doors and windows floating in open floor make no sense, so this starts on
+            openings_require_wall: true,
+            protect_guest_rooms: true,
+            sandbox: false,
+        }
+    }
+}
+
+impl BuildingRules {
+    pub fn walls_need_floor(&self) -> bool {
+        self.walls_require_floor && !self.sandbox
+    }
+
+    pub fn furniture_needs_room(&self) -> bool {
+        self.furniture_requires_room && !self.sandbox
+    }
+
+    pub fn openings_need_wall(&self) -> bool {
+        self.openings_require_wall && !self.sandbox
+    }
+
+    pub fn guest_rooms_protected(&self) -> bool {
+        self.protect_guest_rooms && !self.sandbox
+    }
+}
+
+/// Why a placement was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementError {
+    Occupied,
+    NoFloor,
+    NoAdjacentFloor,
+    OutsideRoom,
+    NoDesk,
+    NoWall,
+    NotAtMapEdge,
+    OnWater,
+    GuestsStaying,
+}
+
+impl PlacementError {
+    pub fn reason(&self) -> &str {
+        match self {
+            PlacementError::Occupied => "Tile is already occupied",
+            PlacementError::NoFloor => "Needs a floor underneath",
+            PlacementError::NoAdjacentFloor => "Walls must be built next to a floor",
+            PlacementError::OutsideRoom => "Furniture must be placed inside a room",
+            PlacementError::NoDesk => "Must sit on a desk",
+            PlacementError::NoWall => "Must replace a wall or bridge two wall segments",
+            PlacementError::NotAtMapEdge => "Must be built at the edge of the map",
+            PlacementError::OnWater => "Can't build on the sea",
+            PlacementError::GuestsStaying => "Guests are staying in this room",
+        }
+    }
+
+    /// Localization key for the reason shown next to the cursor
+    pub fn reason_key(&self) -> &'static str {
+        match self {
+            PlacementError::Occupied => "placement.occupied",
+            PlacementError::NoFloor => "placement.no_floor",
+            PlacementError::NoAdjacentFloor => "placement.no_adjacent_floor",
+            PlacementError::OutsideRoom => "placement.outside_room",
+            PlacementError::NoDesk => "placement.no_desk",
+            PlacementError::NoWall => "placement.no_wall",
+            PlacementError::NotAtMapEdge => "placement.not_at_map_edge",
+            PlacementError::OnWater => "placement.on_water",
+            PlacementError::GuestsStaying => "placement.guests_staying",
+        }
+    }
+}
+
+/// The preview asks about the narrow route every frame, and each answer floods the
+/// whole room twice, so the last one is kept while the cursor covers the same tiles and
+/// nothing is built or turned into a new room
+#[derive(SystemParam)]
+pub struct NarrowRouteCache<'w, 's> {
+    last: Local<'s, Option<(Vec<IVec2>, bool)>>,
+    new_rooms: Query<'w, 's, (), Added<Room>>,
+}
+
+impl NarrowRouteCache<'_, '_> {
+    /// Call every frame, before anything can return early, so no change is missed
+    pub fn forget_if_stale(&mut self, building_map: &Res<BuildingMap>) {
+        if building_map.is_changed() || !self.new_rooms.is_empty() {
+            *self.last = None;
+        }
+    }
+
+    pub fn narrows_route(&mut self, validator: &PlacementValidator, tiles: &[IVec2]) -> bool {
+        if let Some((last_tiles, narrows)) = self.last.as_ref() {
+            if last_tiles == tiles {
+                return *narrows;
+            }
+        }
+        let narrows = validator.narrows_route(tiles);
+        *self.last = Some((tiles.to_vec(), narrows));
+        narrows
+    }
+}
+
+/// Everything placement checks need besides the building map, bundled so
+/// placement systems stay under Bevy's system parameter limit
+#[derive(SystemParam)]
+pub struct PlacementContext<'w, 's> {
+    pub rules: Res<'w, BuildingRules>,
+    pub rooms: Query<'w, 's, &'static Room>,
+    pub desks: Query<'w, 's, &'static GridPosition, With<Desk>>,
+    pub grid_settings: Res<'w, GridSettings>,
+    pub terrain: Res<'w, Terrain>,
+    pub booked_zones: Query<'w, 's, &'static Zone, With<Booking>>,
+}
+
+impl PlacementContext<'_, '_> {
+    pub fn rooms(&self) -> Vec<&Room> {
+        self.rooms.iter().collect()
+    }
+
+    pub fn validator<'a>(
+        &'a self,
+        building_map: &'a BuildingMap,
+        rooms: &'a [&'a Room],
+    ) -> PlacementValidator<'a> {
+        PlacementValidator {
+            building_map,
+            rules: &self.rules,
+            rooms,
+            desks: &self.desks,
+            grid_settings: &self.grid_settings,
+            terrain: &self.terrain,
+            booked_zones: &self.booked_zones,
+        }
+    }
+}
+
+/// Single source of truth for whether something can be built at a position
+pub struct PlacementValidator<'a> {
+    building_map: &'a BuildingMap,
+    rules: &'a BuildingRules,
+    rooms: &'a [&'a Room],
+    desks: &'a Query<'a, 'a, &'static GridPosition, With<Desk>>,
+    grid_settings: &'a GridSettings,
+    terrain: &'a Terrain,
+    booked_zones: &'a Query<'a, 'a, &'static Zone, With<Booking>>,
+}
+
+impl PlacementValidator<'_> {
+    /// Walls, windows, floors, fences, hedges and gates (single tile structures)
+    pub fn check_structure(
+        &self,
+        building_type: BuildingType,
+        grid_pos: IVec2,
+    ) -> Result<(), PlacementError> {
+        let map = self.building_map;
+
+        if self.terrain.is_sea(grid_pos) {
+            return Err(PlacementError::OnWater);
+        }
+
+        let blocked = match building_type {
+            // Windows can replace walls, but not doors or other structures
+            BuildingType::Window => {
+                let has_wall = map.walls.contains_key(&grid_pos);
+                (map.occupied.contains(&grid_pos) && !has_wall) || map.doors.contains_key(&grid_pos)
+            }
+            // Fences run up to doors, not across them
+            BuildingType::Boundary(_) => {
+                map.occupied.contains(&grid_pos) || map.doors.contains_key(&grid_pos)
+            }
+            _ => map.occupied.contains(&grid_pos),
+        };
+        // Gates don't block the way, so only the boundary map has them
+        if blocked || map.boundaries.contains_key(&grid_pos) {
+            return Err(PlacementError::Occupied);
+        }
+
+        if matches!(building_type, BuildingType::Wall | BuildingType::Window)
+            && self.rules.walls_need_floor()
+            && !self.has_floor_nearby(grid_pos)
+        {
+            return Err(PlacementError::NoAdjacentFloor);
+        }
+
+        if building_type == BuildingType::Window
+            && self.rules.openings_need_wall()
+            && !map.walls.contains_key(&grid_pos)
+            && !self.bridges_walls(grid_pos, grid_pos, IVec2::X)
+            && !self.bridges_walls(grid_pos, grid_pos, IVec2::Y)
+        {
+            return Err(PlacementError::NoWall);
+        }
+
+        self.check_guest_rooms(&[grid_pos])?;
+
+        Ok(())
+    }
+
+    /// Doors span two tiles and may replace walls
+    pub fn check_door(&self, door_tiles: &[IVec2]) -> Result<(), PlacementError> {
+        let map = self.building_map;
+
+        if door_tiles.iter().any(|pos| self.terrain.is_sea(*pos)) {
+            return Err(PlacementError::OnWater);
+        }
+
+        let all_available = door_tiles.iter().all(|pos| {
+            let has_wall = map.walls.contains_key(pos);
+            let has_door = map.doors.contains_key(pos);
+            let has_other =
+                (map.occupied.contains(pos) && !has_wall) || map.boundaries.contains_key(pos);
+
+            // Allow if empty OR if it's a wall (it gets replaced)
+            !has_door && !has_other
+        });
+        if !all_available {
+            return Err(PlacementError::Occupied);
+        }
+
+        if self.rules.walls_need_floor()
+            && !door_tiles.iter().any(|pos| self.has_floor_nearby(*pos))
+        {
+            return Err(PlacementError::NoAdjacentFloor);
+        }
+
+        if self.rules.openings_need_wall() {
+            let replaces_walls = door_tiles.iter().all(|pos| map.walls.contains_key(pos));
+            let bridges = match (door_tiles.first(), door_tiles.last()) {
+                (Some(first), Some(last)) => {
+                    self.bridges_walls(*first, *last, (*last - *first).signum())
+                }
+                _ => false,
+            };
+            if !replaces_walls && !bridges {
+                return Err(PlacementError::NoWall);
+            }
+        }
+
+        self.check_guest_rooms(door_tiles)?;
+
+        Ok(())
+    }
+
+    pub fn check_furniture(
+        &self,
+        furniture_type: FurnitureType,
+        grid_pos: IVec2,
+        orientation: FurnitureOrientation,
+    ) -> Result<(), PlacementError> {
+        if furniture_type.at_map_edge() {
+            return self.check_map_edge_furniture(furniture_type, grid_pos, orientation);
+        }
+
+        let valid = validate_furniture_placement(
+            furniture_type,
+            grid_pos,
+            orientation,
+            self.building_map,
+            Some(self.desks),
+        );
+
+        if !valid {
+            return Err(if furniture_type.sits_on_desk() {
+                PlacementError::NoDesk
+            } else if furniture_type
+                .tiles_occupied(grid_pos, orientation)
+                .iter()
+                .any(|pos| !self.building_map.floors.contains(pos))
+            {
+                PlacementError::NoFloor
+            } else {
+                PlacementError::Occupied
+            });
+        }
+
+        if self.rules.furniture_needs_room() {
+            let tiles = furniture_type.tiles_occupied(grid_pos, orientation);
+            let inside_room = self
+                .rooms
+                .iter()
+                .any(|room| tiles.iter().all(|tile| room.contains_tile(*tile)));
+            if !inside_room {
+                return Err(PlacementError::OutsideRoom);
+            }
+        }
+
+        self.check_guest_rooms(&furniture_type.tiles_occupied(grid_pos, orientation))?;
+
+        Ok(())
+    }
+
+    /// What `building_type` needs of the ground it goes on under the current rules, as
+    /// the reasons a placement breaking them is turned down with. Being in the way of
+    /// something else, or of booked guests, depends on the spot and isn't listed.
+    pub fn requirements(&self, building_type: BuildingType) -> Vec<PlacementError> {
+        let mut requirements = Vec::new();
+        match building_type {
+            BuildingType::Furniture(furniture_type) if furniture_type.sits_on_desk() => {
+                // The desk underneath already stands on dry floor
+                return vec![PlacementError::NoDesk];
+            }
+            BuildingType::Furniture(furniture_type) if furniture_type.at_map_edge() => {
+                requirements.push(PlacementError::NotAtMapEdge);
+            }
+            BuildingType::Furniture(_) => {
+                requirements.push(PlacementError::NoFloor);
+                if self.rules.furniture_needs_room() {
+                    requirements.push(PlacementError::OutsideRoom);
+                }
+            }
+            BuildingType::Wall | BuildingType::Door | BuildingType::Window => {
+                let opening = building_type != BuildingType::Wall;
+                if opening && self.rules.openings_need_wall() {
+                    requirements.push(PlacementError::NoWall);
+                }
+                if self.rules.walls_need_floor() {
+                    requirements.push(PlacementError::NoAdjacentFloor);
+                }
+            }
+            BuildingType::Floor(_) | BuildingType::Boundary(_) => {}
+        }
+        requirements.push(PlacementError::OnWater);
+        requirements
+    }
+
+    /// Nothing goes up in a bedroom while guests are booked into it
+    fn check_guest_rooms(&self, tiles: &[IVec2]) -> Result<(), PlacementError> {
+        if !self.rules.guest_rooms_protected() {
+            return Ok(());
+        }
+        let disturbs_guests = self.booked_zones.iter().any(|zone| {
+            zone.zone_type == ZoneType::GuestBedroom
+                && tiles.iter().any(|tile| zone.tiles.contains(tile))
+        });
+        if disturbs_guests {
+            return Err(PlacementError::GuestsStaying);
+        }
+        Ok(())
+    }
+
+    /// Whether blocking `tiles` would cut the room they are in apart for pawns, who
+    /// need a route two tiles wide. Only a warning: the placement itself is allowed.
+    pub fn narrows_route(&self, tiles: &[IVec2]) -> bool {
+        let Some(room) = self
+            .rooms
+            .iter()
+            .find(|room| tiles.iter().any(|tile| room.contains_tile(*tile)))
+        else {
+            return false;
+        };
+
+        let before = self.pawn_regions(room, &[]);
+        let after = self.pawn_regions(room, tiles);
+        after > before || (before > 0 && after == 0)
+    }
+
+    /// Separate parts of a room a pawn can stand in and walk between
+    fn pawn_regions(&self, room: &Room, blocked: &[IVec2]) -> usize {
+        let map = self.building_map;
+        let is_open = |tile: IVec2| {
+            (room.contains_tile(tile) || map.doors.contains_key(&tile))
+                && !map.occupied.contains(&tile)
+                && !blocked.contains(&tile)
+        };
+        let fits = |tile: IVec2| room.contains_tile(tile) && fits_pawn(tile, is_open);
+
+        let mut seen = HashSet::new();
+        let mut regions = 0;
+        for &start in &room.tiles {
+            if seen.contains(&start) || !fits(start) {
+                continue;
+            }
+
+            regions += 1;
+            seen.insert(start);
+            let mut queue = VecDeque::from([start]);
+            while let Some(tile) = queue.pop_front() {
+                for offset in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                    let next = tile + offset;
+                    if !seen.contains(&next) && fits(next) {
+                        seen.insert(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        regions
+    }
+
+    /// Edge furniture stands outdoors, so it needs no floor or room, only free tiles
+    /// reaching the map border
+    fn check_map_edge_furniture(
+        &self,
+        furniture_type: FurnitureType,
+        grid_pos: IVec2,
+        orientation: FurnitureOrientation,
+    ) -> Result<(), PlacementError> {
+        let map = self.building_map;
+        let tiles = furniture_type.tiles_occupied(grid_pos, orientation);
+        let (width, height) = (self.grid_settings.width, self.grid_settings.height);
+
+        let in_bounds = |pos: &IVec2| pos.x >= 0 && pos.y >= 0 && pos.x < width && pos.y < height;
+        if tiles.iter().any(|pos| {
+            !in_bounds(pos)
+                || map.occupied.contains(pos)
+                || map.doors.contains_key(pos)
+                || map.boundaries.contains_key(pos)
+        }) {
+            return Err(PlacementError::Occupied);
+        }
+        if tiles.iter().any(|pos| self.terrain.is_sea(*pos)) {
+            return Err(PlacementError::OnWater);
+        }
+
+        let near_edge = |pos: &IVec2| {
+            pos.x < MAP_EDGE_TILES
+                || pos.y < MAP_EDGE_TILES
+                || pos.x >= width - MAP_EDGE_TILES
+                || pos.y >= height - MAP_EDGE_TILES
+        };
+        if !tiles.iter().any(near_edge) {
+            return Err(PlacementError::NotAtMapEdge);
+        }
+
+        Ok(())
+    }
+
+    /// True when the tiles just past both ends of a span along `axis` are wall or door,
+    /// so an opening placed there closes a gap in an existing wall line
+    fn bridges_walls(&self, first: IVec2, last: IVec2, axis: IVec2) -> bool {
+        if axis == IVec2::ZERO {
+            return false;
+        }
+        let map = self.building_map;
+        let is_structural =
+            |pos: IVec2| map.walls.contains_key(&pos) || map.doors.contains_key(&pos);
+        is_structural(first - axis) && is_structural(last + axis)
+    }
+
+    fn has_floor_nearby(&self, grid_pos: IVec2) -> bool {
+        [IVec2::ZERO, IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .iter()
+            .any(|offset| self.building_map.floors.contains(&(grid_pos + *offset)))
+    }
+}
@@ -3,11 +3,13 @@ use crate::components::furniture::*;
 use crate::components::*;
 use crate::systems::grid::GridSettings;
 use crate::systems::building::BuildingMap;
+use crate::systems::shared_assets::SharedAssets;
 use super::super::factories::*;
 
-/// Places a reception console on a desk
-pub fn place_reception_console(
+/// Places desk-top furniture, such as a reception console, on a desk
+pub fn place_desk_furniture(
     commands: &mut Commands,
+    furniture_type: FurnitureType,
     grid_pos: IVec2,
     orientation: FurnitureOrientation,
     grid_settings: &GridSettings,
@@ -22,17 +24,13 @@ pub fn place_reception_console(
 
     // Create sprite using factory function
     let sprite_config = create_furniture_sprite(
-        FurnitureType::ReceptionConsole,
+        furniture_type,
         orientation,
         asset_server,
         grid_settings,
         false,
     );
-
-    let sprite = match sprite_config {
-        FurnitureSpriteConfig::Directional { sprite } => sprite,
-        _ => panic!("Reception console should use directional sprite"),
-    };
+    let sprite = desk_furniture_sprite(furniture_type, sprite_config, grid_settings);
 
     let furniture_entity = commands.spawn((
         sprite,
@@ -42,7 +40,7 @@ pub fn place_reception_console(
     )).id();
 
     // Insert components using factory function
-    insert_furniture_component(furniture_entity, FurnitureType::ReceptionConsole, orientation, commands);
+    insert_furniture_component(furniture_entity, furniture_type, orientation, commands);
 
     furniture_entity
 }
@@ -50,8 +48,7 @@ pub fn place_reception_console(
 /// Places regular furniture (beds, dressers, etc.)
 pub fn place_regular_furniture(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
+    shared_assets: &SharedAssets,
     furniture_type: FurnitureType,
     grid_pos: IVec2,
     orientation: FurnitureOrientation,
@@ -61,20 +58,8 @@ pub fn place_regular_furniture(
 ) -> Entity {
     let furniture_tiles = furniture_type.tiles_occupied(grid_pos, orientation);
 
-    // Calculate center position for multi-tile furniture
-    let (width_tiles, height_tiles) = furniture_type.oriented_dimensions(orientation);
-    let offset = Vec2::new(
-        (width_tiles as f32 - 1.0) * grid_settings.tile_size / 2.0,
-        (height_tiles as f32 - 1.0) * grid_settings.tile_size / 2.0,
-    );
-
-    let base_world_pos = crate::systems::grid::grid_to_world(
-        grid_pos,
-        grid_settings.tile_size,
-        grid_settings.width,
-        grid_settings.height,
-    );
-    let furniture_pos = base_world_pos + offset;
+    // Multi-tile furniture sits over the middle of its footprint
+    let furniture_pos = furniture_world_center(furniture_type, grid_pos, orientation, grid_settings);
 
     // Create sprite using factory function
     let sprite_config = create_furniture_sprite(
@@ -112,19 +97,15 @@ pub fn place_regular_furniture(
                 ))
                 .id()
         }
-        FurnitureSpriteConfig::Mesh { color } => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
+        FurnitureSpriteConfig::Mesh => {
             let rotation_radians = furniture_rotation_radians(orientation);
             let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
             transform.rotate_z(rotation_radians);
 
             commands
                 .spawn((
-                    Mesh2d(meshes.add(Rectangle::new(
-                        base_width_tiles as f32 * grid_settings.tile_size,
-                        base_height_tiles as f32 * grid_settings.tile_size,
-                    ))),
-                    MeshMaterial2d(materials.add(color)),
+                    Mesh2d(shared_assets.furniture_mesh(furniture_type)),
+                    MeshMaterial2d(shared_assets.furniture_material(furniture_type)),
                     transform,
                     GridPosition::new(grid_pos.x, grid_pos.y),
                     Furniture,
@@ -2,33 +2,43 @@ use bevy::prelude::*;
 use crate::components::furniture::*;
 use crate::components::*;
 use crate::systems::grid::{GridSettings, grid_to_world};
-use super::super::BuildingMap;
 use super::super::factories::*;
+use super::super::structures::draw_tile_outline;
+use crate::ui::ColorPalette;
+
+/// The persistent preview sprite, updated in place each frame instead of being respawned
+pub struct FurniturePreviewSprite<'a> {
+    pub sprite: &'a mut Sprite,
+    pub transform: &'a mut Transform,
+    pub visibility: &'a mut Visibility,
+}
 
-/// Shows preview for reception console (special case - must be on desk)
-pub fn show_reception_console_preview(
-    commands: &mut Commands,
+impl FurniturePreviewSprite<'_> {
+    fn show(&mut self, sprite: Sprite, transform: Transform) {
+        *self.sprite = sprite;
+        *self.transform = transform;
+        *self.visibility = Visibility::Visible;
+    }
+}
+
+/// Shows preview for desk-top furniture like the reception console (must be on a desk)
+pub fn show_desk_furniture_preview(
+    gizmos: &mut Gizmos,
+    preview: &mut FurniturePreviewSprite,
+    furniture_type: FurnitureType,
     grid_pos: IVec2,
     orientation: FurnitureOrientation,
     grid_settings: &GridSettings,
-    building_map: &BuildingMap,
+    validator: &PlacementValidator,
     asset_server: &AssetServer,
-    desk_query: &Query<&GridPosition, With<Desk>>,
-) {
+    palette: ColorPalette,
+) -> Result<(), PlacementError> {
     // Validate placement
-    let is_valid = validate_furniture_placement(
-        FurnitureType::ReceptionConsole,
-        grid_pos,
-        orientation,
-        building_map,
-        Some(desk_query),
-    );
+    let result = validator.check_furniture(furniture_type, grid_pos, orientation);
+    let is_valid = result.is_ok();
 
-    let preview_color = if !is_valid {
-        Color::srgba(1.0, 0.3, 0.3, 1.0)  // Red if no desk
-    } else {
-        Color::srgba(1.0, 1.0, 1.0, 0.7)  // White if desk present, preserves sprite alpha
-    };
+    // Tinted if there's no desk under it
+    let preview_color = palette.preview_tint(!is_valid);
 
     let world_pos = grid_to_world(
         grid_pos,
@@ -39,62 +49,47 @@ pub fn show_reception_console_preview(
 
     // Create sprite using factory function
     let sprite_config = create_furniture_sprite(
-        FurnitureType::ReceptionConsole,
+        furniture_type,
         orientation,
         asset_server,
         grid_settings,
         true,
     );
 
-    let mut sprite = match sprite_config {
-        FurnitureSpriteConfig::Directional { sprite } => sprite,
-        _ => panic!("Reception console should use directional sprite"),
-    };
+    let mut sprite = desk_furniture_sprite(furniture_type, sprite_config, grid_settings);
     sprite.color = preview_color;
 
     // Use higher z-level so it appears above desk
-    commands.spawn((
-        sprite,
-        Transform::from_xyz(world_pos.x, world_pos.y, 4.0),
-        PlacementPreview,
-    ));
+    preview.show(sprite, Transform::from_xyz(world_pos.x, world_pos.y, 4.0));
+
+    let outline_color = if is_valid {
+        palette.preview_valid()
+    } else {
+        palette.preview_blocked()
+    };
+    draw_tile_outline(gizmos, grid_pos, grid_settings, outline_color);
+
+    result
 }
 
 /// Shows preview for regular furniture
 pub fn show_regular_furniture_preview(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
+    gizmos: &mut Gizmos,
+    preview: &mut FurniturePreviewSprite,
     furniture_type: FurnitureType,
     grid_pos: IVec2,
     orientation: FurnitureOrientation,
     grid_settings: &GridSettings,
-    building_map: &BuildingMap,
+    validator: &PlacementValidator,
     asset_server: &AssetServer,
-) {
+    palette: ColorPalette,
+) -> Result<(), PlacementError> {
     // Validate placement
-    let is_blocked = !validate_furniture_placement(
-        furniture_type,
-        grid_pos,
-        orientation,
-        building_map,
-        None,
-    );
+    let result = validator.check_furniture(furniture_type, grid_pos, orientation);
+    let is_blocked = result.is_err();
 
-    // Calculate center position for preview
-    let (width_tiles, height_tiles) = furniture_type.oriented_dimensions(orientation);
-    let offset = Vec2::new(
-        (width_tiles as f32 - 1.0) * grid_settings.tile_size / 2.0,
-        (height_tiles as f32 - 1.0) * grid_settings.tile_size / 2.0,
-    );
-
-    let base_world_pos = grid_to_world(
-        grid_pos,
-        grid_settings.tile_size,
-        grid_settings.width,
-        grid_settings.height,
-    );
-    let preview_pos = base_world_pos + offset;
+    // Drawn exactly where the placed piece will be, over the tiles outlined below
+    let preview_pos = furniture_world_center(furniture_type, grid_pos, orientation, grid_settings);
 
     // Create sprite using factory function
     let sprite_config = create_furniture_sprite(
@@ -106,57 +101,37 @@ pub fn show_regular_furniture_preview(
     );
 
     // Apply color tint based on placement validity
-    let preview_color = if is_blocked {
-        Color::srgba(1.0, 0.3, 0.3, 1.0)  // Red for blocked
-    } else {
-        Color::srgba(1.0, 1.0, 1.0, 0.7)  // White for valid, preserves sprite alpha
-    };
+    let preview_color = palette.preview_tint(is_blocked);
 
-    // Spawn preview based on sprite config
+    // Update the preview sprite based on sprite config
     match sprite_config {
         FurnitureSpriteConfig::Rotating { mut sprite, rotation_radians } => {
             sprite.color = preview_color;
             let mut transform = Transform::from_xyz(preview_pos.x, preview_pos.y, 4.0);
             transform.rotate_z(rotation_radians);
 
-            commands.spawn((
-                sprite,
-                transform,
-                PlacementPreview,
-            ));
+            preview.show(sprite, transform);
         }
         FurnitureSpriteConfig::Directional { mut sprite } => {
             sprite.color = preview_color;
             let transform = Transform::from_xyz(preview_pos.x, preview_pos.y, 4.0);
 
-            commands.spawn((
-                sprite,
-                transform,
-                PlacementPreview,
-            ));
+            preview.show(sprite, transform);
         }
-        FurnitureSpriteConfig::Mesh { color: _ } => {
-            // For mesh-based furniture, use semi-transparent color
-            let mesh_color = if is_blocked {
-                Color::srgba(1.0, 0.3, 0.3, 0.5)  // Red for blocked
-            } else {
-                Color::srgba(1.0, 1.0, 1.0, 0.5)  // White for valid
-            };
-
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let rotation_radians = furniture_rotation_radians(orientation);
-            let mut transform = Transform::from_xyz(preview_pos.x, preview_pos.y, 4.0);
-            transform.rotate_z(rotation_radians);
-
-            commands.spawn((
-                Mesh2d(meshes.add(Rectangle::new(
-                    base_width_tiles as f32 * grid_settings.tile_size,
-                    base_height_tiles as f32 * grid_settings.tile_size,
-                ))),
-                MeshMaterial2d(materials.add(mesh_color)),
-                transform,
-                PlacementPreview,
-            ));
+        FurnitureSpriteConfig::Mesh => {
+            // Mesh-based furniture has no sprite; the footprint outline below is the preview
         }
     }
+
+    // Outline every occupied tile so the footprint is visible regardless of sprite art
+    let outline_color = if is_blocked {
+        palette.preview_blocked()
+    } else {
+        palette.preview_valid()
+    };
+    for tile_pos in furniture_type.tiles_occupied(grid_pos, orientation) {
+        draw_tile_outline(gizmos, tile_pos, grid_settings, outline_color);
+    }
+
+    result
 }
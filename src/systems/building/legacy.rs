@@ -1,13 +1,22 @@
 use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::controls::PointerAction;
+use crate::systems::cursor::CursorWorldPosition;
 use crate::systems::grid::*;
-use crate::systems::Money;
-use crate::ui::{BuildingType, OrderType, ToolbarState, UiInputBlocker};
+use crate::systems::shared_assets::SharedAssets;
+use crate::ui::{
+    BuildingType, GameSettings, LocalizedText, Localization, OrderType, ToolbarState,
+    UiInputBlocker,
+};
 use bevy::prelude::*;
-use bevy::window::{PrimaryWindow, Window as BevyWindow};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use super::factories::*;
 use super::structures;
 use super::furniture;
+use super::deconstruction::*;
+use super::placement::*;
+use super::paint::*;
+use super::plans::*;
 
 #[derive(Resource)]
 pub struct BuildingMap {
@@ -15,6 +24,8 @@ pub struct BuildingMap {
     pub walls: std::collections::HashMap<IVec2, Entity>, // Wall entities by position
     pub doors: std::collections::HashMap<IVec2, Entity>, // Door tiles (can pass when open)
     pub floors: std::collections::HashSet<IVec2>,   // Floors (don't block building)
+    pub locked_doors: std::collections::HashSet<IVec2>, // Door tiles locked shut (block movement)
+    pub boundaries: std::collections::HashMap<IVec2, Entity>, // Fences, hedges and gates
 }
 
 impl Default for BuildingMap {
@@ -24,6 +35,8 @@ impl Default for BuildingMap {
             walls: std::collections::HashMap::new(),
             doors: std::collections::HashMap::new(),
             floors: std::collections::HashSet::new(),
+            locked_doors: std::collections::HashSet::new(),
+            boundaries: std::collections::HashMap::new(),
         }
     }
 }
@@ -32,6 +45,13 @@ impl BuildingMap {
     pub fn is_occupied(&self, pos: IVec2) -> bool {
         self.occupied.contains(&pos) || self.walls.contains_key(&pos)
     }
+
+    /// Whether `pos` separates one room from the next. Fences and hedges are in the way
+    /// but too low to enclose anything.
+    pub fn divides_rooms(&self, pos: IVec2) -> bool {
+        (self.is_occupied(pos) && !self.boundaries.contains_key(&pos))
+            || self.doors.contains_key(&pos)
+    }
 }
 
 #[derive(Resource, Default)]
@@ -41,15 +61,19 @@ pub struct DragState {
     pub current_pos: Option<IVec2>,
 }
 
+/// Orientation picked with R for openings set into walls
 #[derive(Resource)]
 pub struct DoorPlacementState {
     pub orientation: DoorOrientation,
+    /// Only used when the walls around a window don't decide which way it faces
+    pub window_orientation: WindowOrientation,
 }
 
 impl Default for DoorPlacementState {
     fn default() -> Self {
         Self {
             orientation: DoorOrientation::Horizontal,
+            window_orientation: WindowOrientation::Horizontal,
         }
     }
 }
@@ -122,21 +146,53 @@ impl Plugin for BuildingPlugin {
             .init_resource::<FurniturePlacementState>()
             .init_resource::<ContextMenuState>()
             .init_resource::<UiInputBlocker>()
-            .add_systems(Startup, setup_context_menu)
+            .init_resource::<BuildingRules>()
+            .init_resource::<PendingDeconstruction>()
+            .init_resource::<PlacementFailures>()
+            .add_event::<PlaceBuildingEvent>()
+            .add_event::<SketchPlanEvent>()
+            .add_event::<PlanOrder>()
+            .add_systems(
+                Startup,
+                (
+                    setup_context_menu,
+                    setup_placement_preview,
+                    setup_deconstruct_confirm_panel,
+                ),
+            )
             .add_systems(
                 Update,
                 (
                     handle_rotation_input,
                     handle_drag_input,
                     update_placement_preview,
-                    handle_building_placement,
+                    // Plans being converted go through the same placement as clicks
+                    (
+                        handle_building_placement,
+                        sketch_plans,
+                        handle_plan_orders,
+                        place_buildings,
+                        report_unbuilt_plans,
+                        clear_plans_on_load,
+                    )
+                        .chain(),
+                    update_placement_requirements_hint,
                     handle_deconstruction_placement,
+                    handle_paint_orders,
+                    update_deconstruct_confirm_panel,
+                    handle_deconstruct_confirm_clicks,
+                    update_deconstruct_confirm_button_colors,
                     handle_right_click_deconstruct,
                     update_context_menu,
                     handle_context_menu_clicks,
+                    handle_door_mode_clicks,
+                    sync_locked_doors,
                     update_wall_projections,
+                    update_wall_tiles,
+                    update_opening_frames,
                 )
-                    .chain(),
+                    .chain()
+                    .in_set(GameplaySet),
             );
     }
 }
@@ -145,9 +201,9 @@ fn handle_rotation_input(
     mut door_state: ResMut<DoorPlacementState>,
     mut furniture_state: ResMut<FurniturePlacementState>,
     toolbar_state: Res<ToolbarState>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ButtonInput<PointerAction>>,
 ) {
-    if !keyboard.just_pressed(KeyCode::KeyR) {
+    if !actions.just_pressed(PointerAction::Rotate) {
         return;
     }
 
@@ -158,6 +214,9 @@ fn handle_rotation_input(
                 DoorOrientation::Vertical => DoorOrientation::Horizontal,
             };
         }
+        Some(BuildingType::Window) => {
+            door_state.window_orientation = door_state.window_orientation.rotated();
+        }
         Some(BuildingType::Furniture(_)) => {
             furniture_state.orientation = furniture_state.orientation.next();
         }
@@ -168,17 +227,19 @@ fn handle_rotation_input(
 fn handle_drag_input(
     mut drag_state: ResMut<DragState>,
     toolbar_state: Res<ToolbarState>,
-    grid_settings: Res<GridSettings>,
-    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    mouse_button: Res<ButtonInput<MouseButton>>,
+    cursor: Res<CursorWorldPosition>,
+    actions: Res<ButtonInput<PointerAction>>,
     ui_blocker: Res<UiInputBlocker>,
 ) {
-    // Allow dragging for walls and floors
-    let allow_drag = matches!(
-        toolbar_state.selected_building,
-        Some(BuildingType::Wall) | Some(BuildingType::Floor(_))
-    );
+    // Orders run their own drags
+    if toolbar_state.selected_order.is_some() {
+        return;
+    }
+
+    // Allow dragging for walls, floors and fence lines
+    let allow_drag = toolbar_state
+        .selected_building
+        .is_some_and(|building_type| building_type.drag_buildable());
 
     if !allow_drag {
         if drag_state.is_dragging {
@@ -193,525 +254,345 @@ fn handle_drag_input(
         return;
     }
 
-    let window = window_query.single();
-    let (camera, camera_transform) = camera_query.single();
-
-    if let Some(cursor_pos) = window.cursor_position() {
-        // Ignore clicks in toolbar area (bottom 80 pixels)
-        const TOOLBAR_HEIGHT: f32 = 80.0;
-        if cursor_pos.y > window.height() - TOOLBAR_HEIGHT {
-            return;
+    // Clicks in the toolbar area don't reach the world
+    if let Some(grid_pos) = cursor.world_tile() {
+        // Start drag on mouse press
+        if actions.just_pressed(PointerAction::Confirm) {
+            drag_state.start(grid_pos);
         }
-        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-            if let Some(grid_pos) = world_to_grid(
-                world_pos,
-                grid_settings.tile_size,
-                grid_settings.width,
-                grid_settings.height,
-            ) {
-                // Start drag on mouse press
-                if mouse_button.just_pressed(MouseButton::Left) {
-                    drag_state.start(grid_pos);
-                }
-                // Update drag position while holding
-                else if mouse_button.pressed(MouseButton::Left) && drag_state.is_dragging {
-                    drag_state.update(grid_pos);
-                }
-            }
+        // Update drag position while holding
+        else if actions.pressed(PointerAction::Confirm) && drag_state.is_dragging {
+            drag_state.update(grid_pos);
         }
     }
 
     // Note: Don't call drag_state.end() here - let handle_building_placement do it
 }
 
+fn setup_placement_preview(mut commands: Commands) {
+    // A single reusable sprite for furniture previews; tile previews are drawn with gizmos
+    commands.spawn((
+        Sprite::default(),
+        Transform::default(),
+        Visibility::Hidden,
+        PlacementPreview,
+    ));
+
+    // Explains why the previewed placement is blocked; follows the cursor
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.6, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.85)),
+        Visibility::Hidden,
+        PlacementHint,
+    ));
+
+    // Spells out what the selected building needs once placing it keeps failing
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.5)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(90.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+        Visibility::Hidden,
+        PlacementRequirementsHint,
+    ));
+}
+
+/// Placements turned down this many frames in a row bring up the requirements line
+const FAILURES_BEFORE_REQUIREMENTS_HINT: u32 = 2;
+
+/// Shows the requirements line while the building that keeps failing is still selected,
+/// with why the last attempt failed in front of everything the building needs
+fn update_placement_requirements_hint(
+    toolbar_state: Res<ToolbarState>,
+    mut failures: ResMut<PlacementFailures>,
+    building_map: Res<BuildingMap>,
+    placement: PlacementContext,
+    localization: Res<Localization>,
+    mut hint_query: Query<(&mut Text, &mut Visibility), With<PlacementRequirementsHint>>,
+) {
+    let Ok((mut hint_text, mut hint_visibility)) = hint_query.get_single_mut() else {
+        return;
+    };
+
+    if failures.building_type.is_some() && failures.building_type != toolbar_state.selected_building
+    {
+        failures.clear();
+    }
+    let (Some(building_type), Some(error)) = (failures.building_type, failures.last_error) else {
+        *hint_visibility = Visibility::Hidden;
+        return;
+    };
+    if failures.count < FAILURES_BEFORE_REQUIREMENTS_HINT {
+        *hint_visibility = Visibility::Hidden;
+        return;
+    }
+
+    let rooms = placement.rooms();
+    let requirements = placement
+        .validator(&building_map, &rooms)
+        .requirements(building_type)
+        .iter()
+        .map(|requirement| localization.t_or(requirement.reason_key(), requirement.reason()))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let text = format!(
+        "{} — {}",
+        localization.t_or(error.reason_key(), error.reason()),
+        localization.format("placement.requirements", &[("requirements", requirements)])
+    );
+    if hint_text.0 != text {
+        hint_text.0 = text;
+    }
+    *hint_visibility = Visibility::Visible;
+}
+
 fn update_placement_preview(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut gizmos: Gizmos,
     toolbar_state: Res<ToolbarState>,
     drag_state: Res<DragState>,
     door_state: Res<DoorPlacementState>,
     furniture_state: Res<FurniturePlacementState>,
     grid_settings: Res<GridSettings>,
-    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    preview_query: Query<Entity, With<PlacementPreview>>,
+    cursor: Res<CursorWorldPosition>,
+    mut preview_query: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<PlacementPreview>>,
     building_map: Res<BuildingMap>,
-    desk_query: Query<&GridPosition, With<Desk>>,
+    placement: PlacementContext,
+    mut route_cache: NarrowRouteCache,
     ui_blocker: Res<UiInputBlocker>,
     asset_server: Res<AssetServer>,
+    mut hint_query: Query<
+        (&mut Text, &mut Node, &mut Visibility),
+        (With<PlacementHint>, Without<PlacementPreview>),
+    >,
+    localization: Res<Localization>,
+    settings: Res<GameSettings>,
 ) {
-    let window = window_query.single();
-    let (camera, camera_transform) = camera_query.single();
+    route_cache.forget_if_stale(&building_map);
 
-    // Remove old preview
-    for entity in &preview_query {
-        commands.entity(entity).despawn();
-    }
+    let Ok((mut preview_sprite, mut preview_transform, mut preview_visibility)) =
+        preview_query.get_single_mut()
+    else {
+        return;
+    };
+    let Ok((mut hint_text, mut hint_node, mut hint_visibility)) = hint_query.get_single_mut()
+    else {
+        return;
+    };
+
+    // Hide the furniture preview and hint unless a placement below shows them again
+    preview_visibility.set_if_neq(Visibility::Hidden);
+    hint_visibility.set_if_neq(Visibility::Hidden);
 
     if ui_blocker.block_world_input {
         return;
     }
 
+    let rooms = placement.rooms();
+    let validator = placement.validator(&building_map, &rooms);
+    let palette = settings.palette;
+
+    // Order drags show their selection rectangle, in the paint colour when painting
+    if let Some(order) = toolbar_state.selected_order.filter(|_| drag_state.is_dragging) {
+        if let (Some(start), Some(end)) = (drag_state.start_pos, drag_state.current_pos) {
+            let color = match order {
+                OrderType::Deconstruct => structures::DECONSTRUCT_SELECTION_COLOR,
+                OrderType::Paint => toolbar_state.paint_color.color().with_alpha(0.9),
+            };
+            structures::draw_selection_box(&mut gizmos, start, end, &grid_settings, color);
+        }
+        return;
+    }
+
     // Only show preview if a building is selected
     if let Some(building_type) = toolbar_state.selected_building {
-        // If dragging walls or floors, show all positions in the drag area
-        let is_dragging_multi =
-            matches!(building_type, BuildingType::Wall | BuildingType::Floor(_))
-                && drag_state.is_dragging;
+        // If dragging walls, floors or fences, show all positions in the drag area
+        let is_dragging_multi = building_type.drag_buildable() && drag_state.is_dragging;
 
         if is_dragging_multi {
             let positions = drag_state.get_drag_positions();
-            let is_floor = matches!(building_type, BuildingType::Floor(_));
 
             structures::show_drag_area_preview(
-                &mut commands,
-                &mut meshes,
-                &mut materials,
+                &mut gizmos,
                 positions,
+                building_type,
                 &grid_settings,
-                &building_map,
-                is_floor,
+                &validator,
+                palette,
             );
         }
-        // Otherwise show single preview at cursor
-        else if let Some(cursor_pos) = window.cursor_position() {
-            // Don't show preview in toolbar area (bottom 80 pixels)
-            const TOOLBAR_HEIGHT: f32 = 80.0;
-            if cursor_pos.y > window.height() - TOOLBAR_HEIGHT {
-                return;
-            }
+        // Otherwise show single preview at cursor, unless it is over the toolbar
+        else if let (Some(cursor_pos), Some(grid_pos)) = (cursor.ui, cursor.world_tile()) {
+            let mut furniture_preview = furniture::FurniturePreviewSprite {
+                sprite: &mut preview_sprite,
+                transform: &mut preview_transform,
+                visibility: &mut preview_visibility,
+            };
 
-            if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-                if let Some(grid_pos) = world_to_grid(
-                    world_pos,
-                    grid_settings.tile_size,
-                    grid_settings.width,
-                    grid_settings.height,
-                ) {
-                    let world_pos = grid_to_world(
+            // Handle door preview (2x1)
+            let result = if building_type == BuildingType::Door {
+                structures::show_door_preview(
+                    &mut gizmos,
+                    grid_pos,
+                    door_state.orientation,
+                    &grid_settings,
+                    &validator,
+                    palette,
+                )
+            } else if let BuildingType::Furniture(furniture_type) = building_type {
+                // Special preview for desk-top furniture like the reception console - check for desk
+                if furniture_type.sits_on_desk() {
+                    let orientation = furniture_state.orientation;
+                    furniture::show_desk_furniture_preview(
+                        &mut gizmos,
+                        &mut furniture_preview,
+                        furniture_type,
                         grid_pos,
-                        grid_settings.tile_size,
-                        grid_settings.width,
-                        grid_settings.height,
-                    );
-
-                    // Handle door preview (2x1)
-                    if building_type == BuildingType::Door {
-                        structures::show_door_preview(
-                            &mut commands,
-                            &mut meshes,
-                            &mut materials,
-                            grid_pos,
-                            door_state.orientation,
-                            &grid_settings,
-                            &building_map,
-                        );
-                    } else if let BuildingType::Furniture(furniture_type) = building_type {
-                        // Special preview for reception console - check for desk
-                        if furniture_type == FurnitureType::ReceptionConsole {
-                            let orientation = furniture_state.orientation;
-                            furniture::show_reception_console_preview(
-                                &mut commands,
-                                grid_pos,
-                                orientation,
-                                &grid_settings,
-                                &building_map,
-                                &asset_server,
-                                &desk_query,
-                            );
-                        } else {
-                            // Show actual furniture shape as preview
-                            let orientation = furniture_state.orientation;
-                            furniture::show_regular_furniture_preview(
-                                &mut commands,
-                                &mut meshes,
-                                &mut materials,
-                                furniture_type,
-                                grid_pos,
-                                orientation,
-                                &grid_settings,
-                                &building_map,
-                                &asset_server,
-                            );
-                        }
-                    } else {
-                        // Single tile preview for other buildings (walls, windows)
-                        structures::show_single_tile_preview(
-                            &mut commands,
-                            &mut meshes,
-                            &mut materials,
-                            grid_pos,
-                            &grid_settings,
-                            &building_map,
-                        );
-                    }
+                        orientation,
+                        &grid_settings,
+                        &validator,
+                        &asset_server,
+                        palette,
+                    )
+                } else {
+                    // Show actual furniture shape as preview
+                    let orientation = furniture_state.orientation;
+                    furniture::show_regular_furniture_preview(
+                        &mut gizmos,
+                        &mut furniture_preview,
+                        furniture_type,
+                        grid_pos,
+                        orientation,
+                        &grid_settings,
+                        &validator,
+                        &asset_server,
+                        palette,
+                    )
                 }
+            } else {
+                // Single tile preview for other buildings (walls, windows)
+                structures::show_single_tile_preview(
+                    &mut gizmos,
+                    grid_pos,
+                    building_type,
+                    &grid_settings,
+                    &validator,
+                    palette,
+                )
+            };
+
+            // Allowed placements can still leave pawns without a wide enough way through
+            let blocked_tiles = match building_type {
+                BuildingType::Wall => vec![grid_pos],
+                BuildingType::Boundary(boundary_type) if boundary_type.blocks_movement() => {
+                    vec![grid_pos]
+                }
+                BuildingType::Furniture(furniture_type) if !furniture_type.sits_on_desk() => {
+                    furniture_type.tiles_occupied(grid_pos, furniture_state.orientation)
+                }
+                _ => Vec::new(),
+            };
+            let hint = match result {
+                Err(error) => {
+                    Some(localization.t_or(error.reason_key(), error.reason()).to_string())
+                }
+                Ok(()) if route_cache.narrows_route(&validator, &blocked_tiles) => {
+                    Some(localization.t("placement.narrow_route").to_string())
+                }
+                Ok(()) => None,
+            };
+
+            if let Some(hint) = hint {
+                **hint_text = hint;
+                hint_node.left = Val::Px(cursor_pos.x + 16.0);
+                hint_node.top = Val::Px(cursor_pos.y + 16.0);
+                *hint_visibility = Visibility::Visible;
             }
         }
     }
 }
 
+/// Turns clicks (or the gamepad's confirm) into placement requests: one per tile of a
+/// wall or floor drag, or one at the cursor for anything else. While planning they only
+/// sketch plan markers.
 fn handle_building_placement(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut place_events: EventWriter<PlaceBuildingEvent>,
+    mut sketch_events: EventWriter<SketchPlanEvent>,
     toolbar_state: Res<ToolbarState>,
     mut drag_state: ResMut<DragState>,
     door_state: Res<DoorPlacementState>,
     furniture_state: Res<FurniturePlacementState>,
-    grid_settings: Res<GridSettings>,
-    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    mouse_button: Res<ButtonInput<MouseButton>>,
-    mut building_map: ResMut<BuildingMap>,
-    mut money: ResMut<Money>,
-    asset_server: Res<AssetServer>,
-    desk_query: Query<&GridPosition, With<Desk>>,
+    cursor: Res<CursorWorldPosition>,
+    actions: Res<ButtonInput<PointerAction>>,
     ui_blocker: Res<UiInputBlocker>,
 ) {
     if ui_blocker.block_world_input {
         return;
     }
 
-    if let Some(building_type) = toolbar_state.selected_building {
-        // Handle drag building for walls and floors
-        let is_drag_buildable =
-            matches!(building_type, BuildingType::Wall | BuildingType::Floor(_));
-
-        if is_drag_buildable && mouse_button.just_released(MouseButton::Left) {
-            if let Some((start, end)) = drag_state.end() {
-                // Place all buildings in the drag area
-                let positions = {
-                    let min_x = start.x.min(end.x);
-                    let max_x = start.x.max(end.x);
-                    let min_y = start.y.min(end.y);
-                    let max_y = start.y.max(end.y);
-
-                    let mut positions = Vec::new();
-                    for x in min_x..=max_x {
-                        for y in min_y..=max_y {
-                            positions.push(IVec2::new(x, y));
-                        }
-                    }
-                    positions
-                };
-
-                for grid_pos in positions {
-                    // For structures, skip if occupied; for floors, skip if structure exists
-                    let should_skip = match building_type {
-                        BuildingType::Floor(_) => building_map.occupied.contains(&grid_pos),
-                        _ => building_map.occupied.contains(&grid_pos),
-                    };
-
-                    if should_skip {
-                        continue;
-                    }
-
-                    // Check if player can afford this tile
-                    let cost = building_type.cost();
-                    if !money.can_afford(cost) {
-                        continue; // Skip this tile if can't afford
-                    }
+    let Some(building_type) = toolbar_state.selected_building else {
+        return;
+    };
+    let orientation = PlacementOrientation {
+        door: door_state.orientation,
+        window: door_state.window_orientation,
+        furniture: furniture_state.orientation,
+    };
+    let mut place = |position: IVec2| {
+        if toolbar_state.planning {
+            sketch_events.send(SketchPlanEvent {
+                building_type,
+                position,
+                orientation,
+            });
+        } else {
+            place_events.send(PlaceBuildingEvent {
+                building_type,
+                position,
+                orientation,
+                plan: None,
+            });
+        }
+    };
 
-                    let world_pos = grid_to_world(
-                        grid_pos,
-                        grid_settings.tile_size,
-                        grid_settings.width,
-                        grid_settings.height,
-                    );
-
-                    let blueprint_type = match building_type {
-                        BuildingType::Wall => BlueprintType::Wall,
-                        BuildingType::Floor(floor_type) => BlueprintType::Floor(floor_type),
-                        _ => continue,
-                    };
-
-                    // Deduct money
-                    money.deduct(cost);
-
-                    let blueprint_entity = structures::spawn_blueprint(
-                        &mut commands,
-                        &mut meshes,
-                        &mut materials,
-                        blueprint_type,
-                        grid_pos,
-                        world_pos,
-                        grid_settings.tile_size,
-                    );
-
-                    commands.spawn(ConstructionJob::new(blueprint_entity));
-
-                    // Track placement
-                    match building_type {
-                        BuildingType::Floor(_) => {
-                            building_map.floors.insert(grid_pos);
-                        }
-                        BuildingType::Wall => {
-                            building_map.occupied.insert(grid_pos);
-                            building_map.walls.insert(grid_pos, blueprint_entity);
-                        }
-                        _ => {
-                            building_map.occupied.insert(grid_pos);
-                        }
-                    }
+    // Handle drag building for walls, floors and fences
+    if building_type.drag_buildable() && actions.just_released(PointerAction::Confirm) {
+        if let Some((start, end)) = drag_state.end() {
+            let min = start.min(end);
+            let max = start.max(end);
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    place(IVec2::new(x, y));
                 }
-                return;
             }
+            return;
         }
+    }
 
-        // Handle single building placement for non-walls or single clicks
-        if mouse_button.just_pressed(MouseButton::Left) && !drag_state.is_dragging {
-            let window = window_query.single();
-            let (camera, camera_transform) = camera_query.single();
-
-            if let Some(cursor_pos) = window.cursor_position() {
-                // Ignore clicks in toolbar area (bottom 80 pixels)
-                const TOOLBAR_HEIGHT: f32 = 80.0;
-                if cursor_pos.y > window.height() - TOOLBAR_HEIGHT {
-                    return;
-                }
-                if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-                    if let Some(grid_pos) = world_to_grid(
-                        world_pos,
-                        grid_settings.tile_size,
-                        grid_settings.width,
-                        grid_settings.height,
-                    ) {
-                        // Handle door placement (2x1)
-                        if building_type == BuildingType::Door {
-                            let door_tiles = match door_state.orientation {
-                                DoorOrientation::Horizontal => {
-                                    vec![grid_pos, grid_pos + IVec2::new(1, 0)]
-                                }
-                                DoorOrientation::Vertical => {
-                                    vec![grid_pos, grid_pos + IVec2::new(0, 1)]
-                                }
-                            };
-
-                            // Check if all tiles are available (walls can be replaced, but not doors or windows)
-                            let all_available = door_tiles.iter().all(|pos| {
-                                let has_wall = building_map.walls.contains_key(pos);
-                                let has_door = building_map.doors.contains_key(pos);
-                                let has_other = building_map.occupied.contains(pos) && !has_wall;
-
-                                // Allow if empty OR if it's a wall (we'll replace it)
-                                !has_door && !has_other
-                            });
-
-                            if !all_available {
-                                return;
-                            }
-
-                            // Check if player can afford the door
-                            let cost = building_type.cost();
-                            if !money.can_afford(cost) {
-                                return;
-                            }
-
-                            // Deduct money
-                            money.deduct(cost);
-
-                            // Remove walls that are being replaced
-                            for tile_pos in &door_tiles {
-                                if let Some(wall_entity) = building_map.walls.remove(tile_pos) {
-                                    commands.entity(wall_entity).despawn_recursive();
-                                    building_map.occupied.remove(tile_pos);
-                                }
-                            }
-
-                            // Calculate center position for door
-                            let center_pos = match door_state.orientation {
-                                DoorOrientation::Horizontal => Vec2::new(
-                                    (door_tiles[0].x + door_tiles[1].x) as f32
-                                        * grid_settings.tile_size
-                                        / 2.0
-                                        - (grid_settings.width as f32 * grid_settings.tile_size)
-                                            / 2.0,
-                                    door_tiles[0].y as f32 * grid_settings.tile_size
-                                        - (grid_settings.height as f32 * grid_settings.tile_size)
-                                            / 2.0
-                                        + grid_settings.tile_size / 2.0,
-                                ),
-                                DoorOrientation::Vertical => Vec2::new(
-                                    door_tiles[0].x as f32 * grid_settings.tile_size
-                                        - (grid_settings.width as f32 * grid_settings.tile_size)
-                                            / 2.0
-                                        + grid_settings.tile_size / 2.0,
-                                    (door_tiles[0].y + door_tiles[1].y) as f32
-                                        * grid_settings.tile_size
-                                        / 2.0
-                                        - (grid_settings.height as f32 * grid_settings.tile_size)
-                                            / 2.0,
-                                ),
-                            };
-
-                            let blueprint_entity = structures::spawn_door_blueprint(
-                                &mut commands,
-                                &mut meshes,
-                                &mut materials,
-                                grid_pos,
-                                center_pos,
-                                grid_settings.tile_size,
-                                door_state.orientation,
-                            );
-
-                            commands.spawn(ConstructionJob::new(blueprint_entity));
-
-                            // Track door placement - reserve tiles but don't block (pawns can pass when open)
-                            for tile_pos in door_tiles {
-                                building_map.doors.insert(tile_pos, blueprint_entity);
-                            }
-                        } else if let BuildingType::Furniture(furniture_type) = building_type {
-                            // Special handling for reception console - must be placed on a desk
-                            if furniture_type == FurnitureType::ReceptionConsole {
-                                // Validate placement using factory function
-                                let orientation = furniture_state.orientation;
-                                if !validate_furniture_placement(
-                                    furniture_type,
-                                    grid_pos,
-                                    orientation,
-                                    &building_map,
-                                    Some(&desk_query),
-                                ) {
-                                    return;
-                                }
-
-                                // Check if player can afford the reception console
-                                let cost = building_type.cost();
-                                if !money.can_afford(cost) {
-                                    return;
-                                }
-
-                                // Deduct money
-                                money.deduct(cost);
-
-                                // Place reception console using helper function
-                                furniture::place_reception_console(
-                                    &mut commands,
-                                    grid_pos,
-                                    orientation,
-                                    &grid_settings,
-                                    &asset_server,
-                                );
-
-                                // Don't mark tiles as occupied - desk already occupies them
-                                return;
-                            }
-
-                            // Handle regular furniture placement
-                            let orientation = furniture_state.orientation;
-
-                            // Validate placement using factory function
-                            if !validate_furniture_placement(
-                                furniture_type,
-                                grid_pos,
-                                orientation,
-                                &building_map,
-                                None,
-                            ) {
-                                return;
-                            }
-
-                            // Check if player can afford the furniture
-                            let cost = building_type.cost();
-                            if !money.can_afford(cost) {
-                                return;
-                            }
-
-                            // Deduct money
-                            money.deduct(cost);
-
-                            // Place furniture using helper function
-                            furniture::place_regular_furniture(
-                                &mut commands,
-                                &mut meshes,
-                                &mut materials,
-                                furniture_type,
-                                grid_pos,
-                                orientation,
-                                &grid_settings,
-                                &asset_server,
-                                &mut building_map,
-                            );
-                        } else {
-                            // Regular building placement
-                            let should_skip = match building_type {
-                                BuildingType::Floor(_) => building_map.occupied.contains(&grid_pos),
-                                BuildingType::Window => {
-                                    // Windows can replace walls
-                                    let has_wall = building_map.walls.contains_key(&grid_pos);
-                                    let has_other =
-                                        building_map.occupied.contains(&grid_pos) && !has_wall;
-                                    has_other || building_map.doors.contains_key(&grid_pos)
-                                }
-                                _ => building_map.occupied.contains(&grid_pos),
-                            };
-
-                            if should_skip {
-                                return;
-                            }
-
-                            // Check if player can afford this building
-                            let cost = building_type.cost();
-                            if !money.can_afford(cost) {
-                                return;
-                            }
-
-                            // Deduct money
-                            money.deduct(cost);
-
-                            // Remove wall if placing window over it
-                            if building_type == BuildingType::Window {
-                                if let Some(wall_entity) = building_map.walls.remove(&grid_pos) {
-                                    commands.entity(wall_entity).despawn_recursive();
-                                    building_map.occupied.remove(&grid_pos);
-                                }
-                            }
-
-                            let world_pos = grid_to_world(
-                                grid_pos,
-                                grid_settings.tile_size,
-                                grid_settings.width,
-                                grid_settings.height,
-                            );
-
-                            let blueprint_type = match building_type {
-                                BuildingType::Wall => BlueprintType::Wall,
-                                BuildingType::Window => BlueprintType::Window,
-                                BuildingType::Floor(floor_type) => BlueprintType::Floor(floor_type),
-                                _ => return,
-                            };
-
-                            let blueprint_entity = structures::spawn_blueprint(
-                                &mut commands,
-                                &mut meshes,
-                                &mut materials,
-                                blueprint_type,
-                                grid_pos,
-                                world_pos,
-                                grid_settings.tile_size,
-                            );
-
-                            commands.spawn(ConstructionJob::new(blueprint_entity));
-
-                            // Track placement
-                            match building_type {
-                                BuildingType::Floor(_) => {
-                                    building_map.floors.insert(grid_pos);
-                                }
-                                BuildingType::Wall => {
-                                    building_map.occupied.insert(grid_pos);
-                                    building_map.walls.insert(grid_pos, blueprint_entity);
-                                }
-                                _ => {
-                                    building_map.occupied.insert(grid_pos);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    // Handle single building placement for non-walls or single clicks. Clicks in the
+    // toolbar area don't reach the world.
+    if actions.just_pressed(PointerAction::Confirm) && !drag_state.is_dragging {
+        if let Some(grid_pos) = cursor.world_tile() {
+            place(grid_pos);
         }
     }
 }
@@ -719,29 +600,41 @@ fn handle_building_placement(
 
 // Handle left-click deconstruction placement with Deconstruct order selected
 fn handle_deconstruction_placement(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut pending: ResMut<PendingDeconstruction>,
     toolbar_state: Res<ToolbarState>,
     mut drag_state: ResMut<DragState>,
-    grid_settings: Res<GridSettings>,
-    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    mouse_button: Res<ButtonInput<MouseButton>>,
+    cursor: Res<CursorWorldPosition>,
+    actions: Res<ButtonInput<PointerAction>>,
     deconstructible_query: Query<
-        (Entity, &GridPosition, &Transform),
+        (
+            Entity,
+            &GridPosition,
+            &Transform,
+            Has<Wall>,
+            Option<&Door>,
+            Has<crate::components::Window>,
+            Option<(&FurnitureType, &FurnitureOrientation)>,
+            Has<Boundary>,
+        ),
         Or<(
             With<Wall>,
             With<Door>,
             With<crate::components::Window>,
             With<Furniture>,
+            With<Floor>,
+            With<Boundary>,
         )>,
     >,
     marker_query: Query<&DeconstructionMarker>,
+    rules: Res<BuildingRules>,
+    booked_zone_query: Query<(Entity, &Zone), With<Booking>>,
     ui_blocker: Res<UiInputBlocker>,
 ) {
     // Only handle when deconstruct order is selected
     if toolbar_state.selected_order != Some(OrderType::Deconstruct) {
+        if pending.is_pending() {
+            pending.targets.clear();
+        }
         return;
     }
 
@@ -749,86 +642,117 @@ fn handle_deconstruction_placement(
         return;
     }
 
-    let window = window_query.single();
-    let (camera, camera_transform) = camera_query.single();
+    // Ignore clicks in toolbar area
+    if cursor.over_toolbar {
+        return;
+    }
 
-    if let Some(cursor_pos) = window.cursor_position() {
-        // Ignore clicks in toolbar area
-        const TOOLBAR_HEIGHT: f32 = 80.0;
-        if cursor_pos.y > window.height() - TOOLBAR_HEIGHT {
-            return;
+    if let Some(grid_pos) = cursor.grid {
+        // Start drag on mouse press
+        if actions.just_pressed(PointerAction::Confirm) {
+            drag_state.start(grid_pos);
         }
-
-        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-            if let Some(grid_pos) = world_to_grid(
-                world_pos,
-                grid_settings.tile_size,
-                grid_settings.width,
-                grid_settings.height,
-            ) {
-                // Start drag on mouse press
-                if mouse_button.just_pressed(MouseButton::Left) {
-                    drag_state.start(grid_pos);
-                }
-                // Update drag position while holding
-                else if mouse_button.pressed(MouseButton::Left) && drag_state.is_dragging {
-                    drag_state.update(grid_pos);
-                }
-            }
+        // Update drag position while holding
+        else if actions.pressed(PointerAction::Confirm) && drag_state.is_dragging {
+            drag_state.update(grid_pos);
         }
     }
 
-    // Handle drag end
-    if mouse_button.just_released(MouseButton::Left) && drag_state.is_dragging {
+    // Handle drag end: collect matching targets and wait for confirmation
+    if actions.just_released(PointerAction::Confirm) && drag_state.is_dragging {
         if let Some((start, end)) = drag_state.end() {
-            let min_x = start.x.min(end.x);
-            let max_x = start.x.max(end.x);
-            let min_y = start.y.min(end.y);
-            let max_y = start.y.max(end.y);
+            let min = start.min(end);
+            let max = start.max(end);
+            let filter = toolbar_state.deconstruct_filter;
+
+            let mut targets = Vec::new();
+            for (
+                entity,
+                entity_grid_pos,
+                entity_transform,
+                is_wall,
+                door,
+                is_window,
+                furniture,
+                is_boundary,
+            ) in &deconstructible_query
+            {
+                // Anything reaching into the dragged area counts, so dragging over any
+                // part of a large piece picks it up
+                let grid_pos = entity_grid_pos.to_ivec2();
+                let in_area = covered_tiles(grid_pos, door, furniture)
+                    .iter()
+                    .any(|tile| tile.cmpge(min).all() && tile.cmple(max).all());
+                if !in_area {
+                    continue;
+                }
 
-            for x in min_x..=max_x {
-                for y in min_y..=max_y {
-                    let grid_pos = IVec2::new(x, y);
-                    // Find deconstructible entity at this position
-                    for (entity, entity_grid_pos, entity_transform) in &deconstructible_query {
-                        if entity_grid_pos.to_ivec2() == grid_pos {
-                            // Check if already marked for deconstruction
-                            let already_marked = marker_query
-                                .iter()
-                                .any(|marker| marker.target_entity == entity);
-                            if already_marked {
-                                continue;
-                            }
-
-                            // Create deconstruction marker
-                            let marker_entity = commands
-                                .spawn((
-                                    Mesh2d(meshes.add(Rectangle::new(
-                                        grid_settings.tile_size,
-                                        grid_settings.tile_size,
-                                    ))),
-                                    MeshMaterial2d(materials.add(Color::srgba(1.0, 0.0, 0.0, 0.4))),
-                                    Transform::from_xyz(
-                                        entity_transform.translation.x,
-                                        entity_transform.translation.y,
-                                        10.0, // High z-level to render on top
-                                    ),
-                                    DeconstructionMarker::new(entity),
-                                    GridPosition::new(grid_pos.x, grid_pos.y),
-                                ))
-                                .id();
-
-                            // Create deconstruction job
-                            commands.spawn(DeconstructionJob::new(marker_entity));
-                            break;
-                        }
-                    }
+                let kind = if is_wall {
+                    DeconstructKind::Wall
+                } else if door.is_some() {
+                    DeconstructKind::Door
+                } else if is_window {
+                    DeconstructKind::Window
+                } else if furniture.is_some() {
+                    DeconstructKind::Furniture
+                } else if is_boundary {
+                    DeconstructKind::Boundary
+                } else {
+                    DeconstructKind::Floor
+                };
+
+                if !kind.matches(filter) {
+                    continue;
+                }
+
+                // Check if already marked for deconstruction
+                let already_marked = marker_query
+                    .iter()
+                    .any(|marker| marker.target_entity == entity);
+                if already_marked {
+                    continue;
                 }
+
+                // Work in a booked bedroom needs confirming, and costs its party some
+                // satisfaction
+                let guest_room = booked_zone_query
+                    .iter()
+                    .find(|(_, zone)| {
+                        zone.zone_type == ZoneType::GuestBedroom && zone.tiles.contains(&grid_pos)
+                    })
+                    .map(|(zone_entity, _)| zone_entity)
+                    .filter(|_| rules.guest_rooms_protected());
+
+                targets.push(DeconstructTarget {
+                    entity,
+                    kind,
+                    grid_pos,
+                    world_pos: entity_transform.translation.truncate(),
+                    guest_room,
+                });
             }
+
+            pending.targets = targets;
         }
     }
 }
 
+/// Every tile a built piece stands on: the whole footprint of furniture, both tiles of a
+/// door and the one tile of anything else
+fn covered_tiles(
+    anchor: IVec2,
+    door: Option<&Door>,
+    furniture: Option<(&FurnitureType, &FurnitureOrientation)>,
+) -> Vec<IVec2> {
+    match (door, furniture) {
+        (Some(door), _) => door.tiles_occupied(anchor),
+        (None, Some((furniture_type, orientation))) => {
+            furniture_type.tiles_occupied(anchor, *orientation)
+        }
+        (None, None) => vec![anchor],
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ContextMenuState {
     pub visible: bool,
@@ -839,12 +763,15 @@ pub struct ContextMenuState {
 // Handle right-click to show context menu
 fn handle_right_click_deconstruct(
     mut context_menu_state: ResMut<ContextMenuState>,
-    grid_settings: Res<GridSettings>,
-    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    cursor: Res<CursorWorldPosition>,
     mouse_button: Res<ButtonInput<MouseButton>>,
     deconstructible_query: Query<
-        (Entity, &GridPosition),
+        (
+            Entity,
+            &GridPosition,
+            Option<&Door>,
+            Option<(&FurnitureType, &FurnitureOrientation)>,
+        ),
         Or<(
             With<Wall>,
             With<Door>,
@@ -862,33 +789,20 @@ fn handle_right_click_deconstruct(
         return;
     }
 
-    let window = window_query.single();
-    let (camera, camera_transform) = camera_query.single();
-
-    if let Some(cursor_pos) = window.cursor_position() {
-        // Ignore clicks in toolbar area
-        const TOOLBAR_HEIGHT: f32 = 80.0;
-        if cursor_pos.y > window.height() - TOOLBAR_HEIGHT {
-            return;
-        }
+    // Ignore clicks in toolbar area
+    if cursor.over_toolbar {
+        return;
+    }
 
-        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-            if let Some(grid_pos) = world_to_grid(
-                world_pos,
-                grid_settings.tile_size,
-                grid_settings.width,
-                grid_settings.height,
-            ) {
-                // Find deconstructible entity at this position
-                for (entity, entity_grid_pos) in &deconstructible_query {
-                    if entity_grid_pos.to_ivec2() == grid_pos {
-                        // Show context menu
-                        context_menu_state.visible = true;
-                        context_menu_state.target_entity = Some(entity);
-                        context_menu_state.position = cursor_pos;
-                        return;
-                    }
-                }
+    if let (Some(cursor_pos), Some(grid_pos)) = (cursor.ui, cursor.grid) {
+        // Find deconstructible entity covering this position, not just anchored on it
+        for (entity, entity_grid_pos, door, furniture) in &deconstructible_query {
+            if covered_tiles(entity_grid_pos.to_ivec2(), door, furniture).contains(&grid_pos) {
+                // Show context menu
+                context_menu_state.visible = true;
+                context_menu_state.target_entity = Some(entity);
+                context_menu_state.position = cursor_pos;
+                return;
             }
         }
     }
@@ -903,6 +817,10 @@ struct ContextMenu;
 #[derive(Component)]
 struct DeconstructButton;
 
+/// Switches a door to this mode, or back to opening by itself if it is already in it
+#[derive(Component)]
+struct DoorModeButton(DoorMode);
+
 fn setup_context_menu(mut commands: Commands) {
     // Create hidden context menu
     commands
@@ -910,7 +828,6 @@ fn setup_context_menu(mut commands: Commands) {
             Node {
                 position_type: PositionType::Absolute,
                 width: Val::Px(120.0),
-                height: Val::Px(40.0),
                 flex_direction: FlexDirection::Column,
                 display: Display::None, // Hidden by default
                 ..default()
@@ -919,6 +836,38 @@ fn setup_context_menu(mut commands: Commands) {
             ContextMenu,
         ))
         .with_children(|parent| {
+            // Only shown for doors
+            for (mode, label_key) in [
+                (DoorMode::HeldOpen, "context_menu.hold_open"),
+                (DoorMode::Locked, "context_menu.lock"),
+            ] {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            display: Display::None,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                        DoorModeButton(mode),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::default(),
+                            LocalizedText::new(label_key),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+
             parent
                 .spawn((
                     Button,
@@ -934,7 +883,8 @@ fn setup_context_menu(mut commands: Commands) {
                 ))
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Deconstruct"),
+                        Text::default(),
+                        LocalizedText::new("toolbar.order.deconstruct"),
                         TextFont {
                             font_size: 14.0,
                             ..default()
@@ -947,6 +897,11 @@ fn setup_context_menu(mut commands: Commands) {
 
 fn update_context_menu(
     mut menu_query: Query<&mut Node, With<ContextMenu>>,
+    mut door_button_query: Query<
+        (&mut Node, &mut BackgroundColor, &DoorModeButton),
+        Without<ContextMenu>,
+    >,
+    door_query: Query<&DoorMode>,
     context_menu_state: Res<ContextMenuState>,
     mut ui_blocker: ResMut<UiInputBlocker>,
 ) {
@@ -960,13 +915,32 @@ fn update_context_menu(
         }
     }
 
+    let door_mode = context_menu_state
+        .target_entity
+        .and_then(|entity| door_query.get(entity).ok());
+    for (mut node, mut color, button) in &mut door_button_query {
+        let display = if door_mode.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+        *color = if door_mode == Some(&button.0) {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else {
+            Color::srgb(0.3, 0.3, 0.3).into()
+        };
+    }
+
     ui_blocker.context_menu_blocking = context_menu_state.visible;
     ui_blocker.recompute();
 }
 
 fn handle_context_menu_clicks(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
+    shared_assets: Res<SharedAssets>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut interaction_query: Query<(&Interaction, &DeconstructButton), Changed<Interaction>>,
     mut context_menu_state: ResMut<ContextMenuState>,
@@ -980,7 +954,6 @@ fn handle_context_menu_clicks(
         )>,
     >,
     marker_query: Query<&DeconstructionMarker>,
-    grid_settings: Res<GridSettings>,
 ) {
     for (interaction, _) in &mut interaction_query {
         if *interaction == Interaction::Pressed {
@@ -992,25 +965,14 @@ fn handle_context_menu_clicks(
 
                 if !already_marked {
                     if let Ok((grid_pos, transform)) = deconstructible_query.get(target_entity) {
-                        // Create deconstruction marker
-                        let marker_entity = commands
-                            .spawn((
-                                Mesh2d(meshes.add(Rectangle::new(
-                                    grid_settings.tile_size,
-                                    grid_settings.tile_size,
-                                ))),
-                                MeshMaterial2d(materials.add(Color::srgba(1.0, 0.0, 0.0, 0.4))),
-                                Transform::from_xyz(
-                                    transform.translation.x,
-                                    transform.translation.y,
-                                    10.0,
-                                ),
-                                DeconstructionMarker::new(target_entity),
-                                GridPosition::new(grid_pos.x, grid_pos.y),
-                            ))
-                            .id();
-
-                        commands.spawn(DeconstructionJob::new(marker_entity));
+                        spawn_deconstruction_marker(
+                            &mut commands,
+                            &shared_assets,
+                            &mut materials,
+                            target_entity,
+                            grid_pos.to_ivec2(),
+                            transform.translation.truncate(),
+                        );
                     }
                 }
             }
@@ -1021,47 +983,300 @@ fn handle_context_menu_clicks(
     }
 }
 
-// Update wall projections based on adjacent walls
-fn update_wall_projections(
-    mut commands: Commands,
-    structure_query: Query<
-        (Entity, &GridPosition, Option<&WallProjection>),
-        Or<(With<Wall>, With<crate::components::Window>)>,
-    >,
-    all_structures_query: Query<&GridPosition, Or<(With<Wall>, With<crate::components::Window>)>>,
+fn handle_door_mode_clicks(
+    interaction_query: Query<(&Interaction, &DoorModeButton), Changed<Interaction>>,
+    mut context_menu_state: ResMut<ContextMenuState>,
+    mut door_query: Query<&mut DoorMode>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let door = context_menu_state
+            .target_entity
+            .and_then(|entity| door_query.get_mut(entity).ok());
+        if let Some(mut mode) = door {
+            *mode = if *mode == button.0 {
+                DoorMode::Auto
+            } else {
+                button.0
+            };
+            info!("Door set to {}", mode.name());
+        }
+
+        context_menu_state.visible = false;
+    }
+}
+
+/// Keeps the building map's locked door tiles, which pathfinding treats as walls, in step
+/// with the doors
+fn sync_locked_doors(
+    mut building_map: ResMut<BuildingMap>,
+    door_query: Query<(&GridPosition, &Door, &DoorMode)>,
+    changed_query: Query<(), Changed<DoorMode>>,
+    mut removed: RemovedComponents<DoorMode>,
 ) {
-    let occupied_positions: HashSet<IVec2> = all_structures_query
+    if changed_query.is_empty() && removed.read().count() == 0 {
+        return;
+    }
+
+    let locked_doors: HashSet<IVec2> = door_query
         .iter()
-        .map(|pos| pos.to_ivec2())
+        .filter(|(_, _, mode)| **mode == DoorMode::Locked)
+        .flat_map(|(pos, door, _)| door.tiles_occupied(pos.to_ivec2()))
         .collect();
+    if building_map.locked_doors != locked_doors {
+        building_map.locked_doors = locked_doors;
+    }
+}
 
-    for (entity, pos, existing_projection) in &structure_query {
-        let current_pos = pos.to_ivec2();
+/// Which wall and window entities stand on each tile, kept in step with spawns and
+/// despawns so projections only need recomputing around what changed
+#[derive(Default)]
+struct ProjectionTiles {
+    tiles: HashMap<IVec2, Vec<Entity>>,
+    positions: HashMap<Entity, IVec2>,
+}
 
-        // Check adjacent positions
-        let has_north = occupied_positions.contains(&(current_pos + IVec2::new(0, 1)));
-        let has_east = occupied_positions.contains(&(current_pos + IVec2::new(1, 0)));
-        let has_west = occupied_positions.contains(&(current_pos + IVec2::new(-1, 0)));
+impl ProjectionTiles {
+    fn insert(&mut self, entity: Entity, pos: IVec2) {
+        self.positions.insert(entity, pos);
+        self.tiles.entry(pos).or_default().push(entity);
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<IVec2> {
+        let pos = self.positions.remove(&entity)?;
+        if let Some(entities) = self.tiles.get_mut(&pos) {
+            entities.retain(|other| *other != entity);
+            if entities.is_empty() {
+                self.tiles.remove(&pos);
+            }
+        }
+        Some(pos)
+    }
+
+    fn is_structure(&self, pos: IVec2) -> bool {
+        self.tiles.contains_key(&pos)
+    }
+}
+
+// Update wall projections based on adjacent walls, only on tiles next to a wall or
+// window that was just built or removed
+fn update_wall_projections(
+    mut commands: Commands,
+    mut tiles: Local<ProjectionTiles>,
+    added_query: Query<
+        (Entity, &GridPosition),
+        Or<(Added<Wall>, Added<crate::components::Window>)>,
+    >,
+    mut removed_walls: RemovedComponents<Wall>,
+    mut removed_windows: RemovedComponents<crate::components::Window>,
+    projection_query: Query<Option<&WallProjection>>,
+) {
+    // Each tile's projection depends on the structures beside it, so a change touches
+    // its own tile and the four around it
+    let mut dirty: HashSet<IVec2> = HashSet::new();
+    let mut mark = |pos: IVec2| {
+        dirty.insert(pos);
+        dirty.extend([IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|offset| pos + offset));
+    };
+
+    for entity in removed_walls.read().chain(removed_windows.read()) {
+        if let Some(pos) = tiles.remove(entity) {
+            mark(pos);
+        }
+    }
+    for (entity, pos) in &added_query {
+        let pos = pos.to_ivec2();
+        tiles.insert(entity, pos);
+        mark(pos);
+    }
+
+    for pos in dirty {
+        let Some(entities) = tiles.tiles.get(&pos) else {
+            continue;
+        };
 
         let mut projection = WallProjection::new();
 
         // Show top shadow if no wall above - this creates the main depth effect
-        if !has_north {
+        if !tiles.is_structure(pos + IVec2::new(0, 1)) {
             projection = projection.with_north();
         }
 
         // Show east shadow if no wall to the east - creates right edge depth
-        if !has_east {
+        if !tiles.is_structure(pos + IVec2::new(1, 0)) {
             projection = projection.with_east();
         }
 
         // Show west shadow if no wall to the west - creates left edge depth
-        if !has_west {
+        if !tiles.is_structure(pos + IVec2::new(-1, 0)) {
             projection = projection.with_west();
         }
 
-        if existing_projection.copied() != Some(projection) {
-            commands.entity(entity).insert(projection);
+        for entity in entities {
+            let Ok(existing_projection) = projection_query.get(*entity) else {
+                continue;
+            };
+            if existing_projection.copied() != Some(projection) {
+                commands.entity(*entity).insert(projection);
+            }
+        }
+    }
+}
+
+// Auto-tile walls: pick the straight/corner/T/cross shape from the neighbouring walls,
+// windows and doors so runs of wall join up instead of reading as separate squares
+fn update_wall_tiles(
+    mut commands: Commands,
+    building_map: Res<BuildingMap>,
+    shared_assets: Res<SharedAssets>,
+    mut wall_query: Query<
+        (Entity, &GridPosition, Option<&WallConnections>, &mut Mesh2d),
+        With<Wall>,
+    >,
+    window_query: Query<&GridPosition, With<crate::components::Window>>,
+    added_walls: Query<(), Added<Wall>>,
+) {
+    if !building_map.is_changed() && added_walls.is_empty() {
+        return;
+    }
+
+    let windows: HashSet<IVec2> = window_query.iter().map(|pos| pos.to_ivec2()).collect();
+    let joins = |pos: IVec2| {
+        building_map.walls.contains_key(&pos)
+            || building_map.doors.contains_key(&pos)
+            || windows.contains(&pos)
+    };
+
+    for (entity, pos, existing, mut mesh) in &mut wall_query {
+        let pos = pos.to_ivec2();
+        let connections = WallConnections {
+            north: joins(pos + IVec2::Y),
+            east: joins(pos + IVec2::X),
+            south: joins(pos - IVec2::Y),
+            west: joins(pos - IVec2::X),
+        };
+
+        if existing.copied() != Some(connections) {
+            mesh.0 = shared_assets.wall_mesh(connections);
+            commands.entity(entity).insert(connections);
+        }
+    }
+}
+
+// Frame doors and windows to match the walls they're set into: a jamb at each end that
+// meets a wall, door or window, painted like the wall beside it. Ends left open get none.
+fn update_opening_frames(
+    mut commands: Commands,
+    building_map: Res<BuildingMap>,
+    shared_assets: Res<SharedAssets>,
+    opening_query: Query<(
+        Entity,
+        &GridPosition,
+        AnyOf<(&Door, &crate::components::Window)>,
+        Option<&FrameJoins>,
+    )>,
+    window_query: Query<&GridPosition, With<crate::components::Window>>,
+    wall_query: Query<&Wall>,
+    changed_walls: Query<(), Changed<Wall>>,
+    added_openings: Query<(), Or<(Added<Door>, Added<crate::components::Window>)>>,
+    mut frame_query: Query<
+        (
+            Entity,
+            &Parent,
+            &mut Mesh2d,
+            &mut MeshMaterial2d<ColorMaterial>,
+            &mut Visibility,
+        ),
+        With<OpeningFrame>,
+    >,
+) {
+    if !building_map.is_changed() && added_openings.is_empty() && changed_walls.is_empty() {
+        return;
+    }
+
+    let windows: HashSet<IVec2> = window_query.iter().map(|pos| pos.to_ivec2()).collect();
+    let joins = |pos: IVec2| {
+        building_map.walls.contains_key(&pos)
+            || building_map.doors.contains_key(&pos)
+            || windows.contains(&pos)
+    };
+    let frames: HashMap<Entity, Entity> = frame_query
+        .iter()
+        .map(|(frame, parent, ..)| (parent.get(), frame))
+        .collect();
+
+    for (entity, pos, opening, existing) in &opening_query {
+        let pos = pos.to_ivec2();
+        // The run an opening lies along, and how many tiles of it the opening takes up
+        let (axis, span) = match opening {
+            (Some(door), _) => match door.orientation {
+                DoorOrientation::Horizontal => (IVec2::X, 2),
+                DoorOrientation::Vertical => (IVec2::Y, 2),
+            },
+            (None, Some(window)) => match window.orientation {
+                WindowOrientation::Horizontal => (IVec2::X, 1),
+                WindowOrientation::Vertical => (IVec2::Y, 1),
+            },
+            (None, None) => continue,
+        };
+        let ends = [pos - axis, pos + axis * span];
+        let frame_joins = FrameJoins {
+            start: joins(ends[0]),
+            end: joins(ends[1]),
+        };
+
+        let mesh = match opening {
+            (Some(door), _) => shared_assets.door_frame_mesh(door.orientation, frame_joins),
+            (None, Some(window)) => {
+                shared_assets.window_frame_mesh(window.orientation, frame_joins)
+            }
+            (None, None) => continue,
+        };
+        let tint = ends
+            .iter()
+            .filter_map(|end| building_map.walls.get(end))
+            .find_map(|wall| wall_query.get(*wall).ok().and_then(|wall| wall.tint));
+        let material = shared_assets.painted_wall_material(tint);
+        let visibility = if frame_joins.any() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        match frames
+            .get(&entity)
+            .and_then(|frame| frame_query.get_mut(*frame).ok())
+        {
+            Some((_, _, mut frame_mesh, mut frame_material, mut frame_visibility)) => {
+                if frame_mesh.0 != mesh {
+                    frame_mesh.0 = mesh;
+                }
+                if frame_material.0 != material {
+                    frame_material.0 = material;
+                }
+                if *frame_visibility != visibility {
+                    *frame_visibility = visibility;
+                }
+            }
+            None => {
+                // Just above the door or window, so the jambs cover its ends
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        Mesh2d(mesh),
+                        MeshMaterial2d(material),
+                        Transform::from_xyz(0.0, 0.0, 0.1),
+                        visibility,
+                        OpeningFrame,
+                    ));
+                });
+            }
+        }
+
+        if existing.copied() != Some(frame_joins) {
+            commands.entity(entity).insert(frame_joins);
         }
     }
 }
@@ -1,6 +1,10 @@
 pub mod factories;
 pub mod structures;
 pub mod furniture;
+pub mod deconstruction;
+pub mod placement;
+pub mod paint;
+pub mod plans;
 mod legacy;
 
 // Re-export everything from legacy for now
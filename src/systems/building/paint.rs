@@ -0,0 +1,119 @@
+use super::DragState;
+use crate::components::*;
+use crate::systems::ascii_renderer::AsciiSprite;
+use crate::systems::controls::PointerAction;
+use crate::systems::cursor::CursorWorldPosition;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::shared_assets::SharedAssets;
+use crate::systems::Purchases;
+use crate::ui::{OrderType, ToolbarState, UiInputBlocker};
+use bevy::prelude::*;
+
+/// What a coat of paint costs for each wall or floor tile
+pub const PAINT_COST_PER_TILE: i32 = 2;
+
+/// Paints every wall and floor inside a Paint drag in the chosen colour. There is no
+/// building to wait for, so the whole drag is paid for and painted at once; tiles
+/// already that colour are left alone and cost nothing.
+pub fn handle_paint_orders(
+    mut drag_state: ResMut<DragState>,
+    toolbar_state: Res<ToolbarState>,
+    cursor: Res<CursorWorldPosition>,
+    actions: Res<ButtonInput<PointerAction>>,
+    ui_blocker: Res<UiInputBlocker>,
+    grid_settings: Res<GridSettings>,
+    shared_assets: Res<SharedAssets>,
+    mut purchases: Purchases,
+    mut wall_query: Query<
+        (
+            &GridPosition,
+            &mut Wall,
+            &mut MeshMaterial2d<ColorMaterial>,
+            Option<&mut AsciiSprite>,
+        ),
+        Without<Floor>,
+    >,
+    mut floor_query: Query<
+        (
+            &GridPosition,
+            &mut Floor,
+            &mut MeshMaterial2d<ColorMaterial>,
+            Option<&mut AsciiSprite>,
+        ),
+        Without<Wall>,
+    >,
+) {
+    if toolbar_state.selected_order != Some(OrderType::Paint)
+        || ui_blocker.block_world_input
+        || cursor.over_toolbar
+    {
+        return;
+    }
+
+    if let Some(grid_pos) = cursor.grid {
+        if actions.just_pressed(PointerAction::Confirm) {
+            drag_state.start(grid_pos);
+        } else if actions.pressed(PointerAction::Confirm) && drag_state.is_dragging {
+            drag_state.update(grid_pos);
+        }
+    }
+
+    if !actions.just_released(PointerAction::Confirm) {
+        return;
+    }
+    let Some((start, end)) = drag_state.end() else {
+        return;
+    };
+
+    let min = start.min(end);
+    let max = start.max(end);
+    let tint = Some(toolbar_state.paint_color);
+    let in_drag = |pos: &GridPosition| {
+        let pos = pos.to_ivec2();
+        !(pos.cmplt(min).any() || pos.cmpgt(max).any())
+    };
+
+    let tiles = wall_query
+        .iter()
+        .filter(|(pos, wall, _, _)| in_drag(pos) && wall.tint != tint)
+        .count()
+        + floor_query
+            .iter()
+            .filter(|(pos, floor, _, _)| in_drag(pos) && floor.tint != tint)
+            .count();
+    if tiles == 0 {
+        return;
+    }
+
+    let world_pos = grid_to_world(
+        (min + max) / 2,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+    if !purchases.try_buy(tiles as i32 * PAINT_COST_PER_TILE, world_pos) {
+        return;
+    }
+
+    for (pos, mut wall, mut material, ascii) in &mut wall_query {
+        if !in_drag(pos) || wall.tint == tint {
+            continue;
+        }
+        wall.tint = tint;
+        material.0 = shared_assets.painted_wall_material(tint);
+        if let Some(mut ascii) = ascii {
+            ascii.color = wall.color();
+        }
+    }
+
+    for (pos, mut floor, mut material, ascii) in &mut floor_query {
+        if !in_drag(pos) || floor.tint == tint {
+            continue;
+        }
+        floor.tint = tint;
+        material.0 = shared_assets.painted_floor_material(floor.floor_type, tint);
+        if let Some(mut ascii) = ascii {
+            ascii.color = floor.color();
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use super::factories::{PlacementContext, PlacementError};
+use super::furniture;
+use super::structures;
+use super::BuildingMap;
+use crate::components::*;
+use crate::systems::balance::Balance;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::shared_assets::SharedAssets;
+use crate::systems::work::ConstructionCompletedEvent;
+use crate::systems::Purchases;
+use crate::ui::BuildingType;
+use bevy::prelude::*;
+
+/// How whatever is being placed is turned. Each building type only reads its own field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlacementOrientation {
+    pub door: DoorOrientation,
+    /// Only used where the walls around a window don't already decide which way it runs
+    pub window: WindowOrientation,
+    pub furniture: FurnitureOrientation,
+}
+
+impl Default for PlacementOrientation {
+    fn default() -> Self {
+        Self {
+            door: DoorOrientation::Horizontal,
+            window: WindowOrientation::Horizontal,
+            furniture: FurnitureOrientation::East,
+        }
+    }
+}
+
+/// Asks for a building to be placed at `position`. Validation, payment and spawning all
+/// happen in `place_buildings`, so the mouse, the gamepad and anything scripted go through
+/// the same checks. Requests that fail are dropped.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlaceBuildingEvent {
+    pub building_type: BuildingType,
+    pub position: IVec2,
+    pub orientation: PlacementOrientation,
+    /// The plan marker this request builds, cleared away once the building goes down
+    pub plan: Option<Entity>,
+}
+
+/// Frames in a row in which every placement of a building was turned down, and why the
+/// last one was. A frame where anything goes down starts the count again.
+#[derive(Resource, Default, Debug)]
+pub struct PlacementFailures {
+    pub building_type: Option<BuildingType>,
+    pub count: u32,
+    pub last_error: Option<PlacementError>,
+}
+
+impl PlacementFailures {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn record(&mut self, building_type: BuildingType, error: PlacementError) {
+        if self.building_type != Some(building_type) {
+            self.building_type = Some(building_type);
+            self.count = 0;
+        }
+        self.count += 1;
+        self.last_error = Some(error);
+    }
+}
+
+/// The two tiles a door covers, starting from `position`
+pub fn door_tiles(position: IVec2, orientation: DoorOrientation) -> Vec<IVec2> {
+    match orientation {
+        DoorOrientation::Horizontal => vec![position, position + IVec2::new(1, 0)],
+        DoorOrientation::Vertical => vec![position, position + IVec2::new(0, 1)],
+    }
+}
+
+/// Places every requested building that passes validation and can be paid for, in the
+/// order asked. Furniture is bought outright and counts as finished straight away;
+/// everything else has its cost set aside until it is built.
+pub fn place_buildings(
+    mut commands: Commands,
+    mut events: EventReader<PlaceBuildingEvent>,
+    shared_assets: Res<SharedAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    grid_settings: Res<GridSettings>,
+    mut building_map: ResMut<BuildingMap>,
+    mut purchases: Purchases,
+    asset_server: Res<AssetServer>,
+    placement: PlacementContext,
+    mut completed: EventWriter<ConstructionCompletedEvent>,
+    balance: Res<Balance>,
+    mut failures: ResMut<PlacementFailures>,
+) {
+    if events.is_empty() {
+        return;
+    }
+
+    let rooms = placement.rooms();
+    // Once something is turned down for money, anything costing as much is too. Skipping
+    // them keeps a long drag from flashing the warning once per tile.
+    let mut unaffordable: Option<i32> = None;
+    let mut rejected = None;
+    let mut placed_any = false;
+
+    for event in events.read() {
+        let PlaceBuildingEvent {
+            building_type,
+            position: grid_pos,
+            orientation,
+            plan,
+        } = *event;
+        let cost = building_type.cost(&balance);
+        if unaffordable.is_some_and(|limit| cost >= limit) {
+            continue;
+        }
+
+        let validator = placement.validator(&building_map, &rooms);
+        let check = match building_type {
+            BuildingType::Door => validator.check_door(&door_tiles(grid_pos, orientation.door)),
+            BuildingType::Furniture(furniture_type) => {
+                validator.check_furniture(furniture_type, grid_pos, orientation.furniture)
+            }
+            _ => validator.check_structure(building_type, grid_pos),
+        };
+        if let Err(error) = check {
+            debug!(
+                "Can't place {:?} at {}: {}",
+                building_type,
+                grid_pos,
+                error.reason()
+            );
+            rejected = Some((building_type, error));
+            continue;
+        }
+
+        let world_pos = grid_to_world(
+            grid_pos,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+        let paid = if matches!(building_type, BuildingType::Furniture(_)) {
+            purchases.try_buy(cost, world_pos)
+        } else {
+            purchases.try_reserve(cost, world_pos)
+        };
+        if !paid {
+            unaffordable = Some(cost);
+            continue;
+        }
+        placed_any = true;
+        if let Some(plan_entity) = plan {
+            commands.entity(plan_entity).despawn_recursive();
+        }
+
+        match building_type {
+            BuildingType::Door => {
+                let door_tiles = door_tiles(grid_pos, orientation.door);
+
+                // Doors replace the walls under them
+                for tile_pos in &door_tiles {
+                    if let Some(wall_entity) = building_map.walls.remove(tile_pos) {
+                        commands.entity(wall_entity).despawn_recursive();
+                        building_map.occupied.remove(tile_pos);
+                    }
+                }
+
+                // Centered between the two tiles
+                let center_pos = match orientation.door {
+                    DoorOrientation::Horizontal => Vec2::new(
+                        (door_tiles[0].x + door_tiles[1].x) as f32 * grid_settings.tile_size / 2.0
+                            - (grid_settings.width as f32 * grid_settings.tile_size) / 2.0,
+                        door_tiles[0].y as f32 * grid_settings.tile_size
+                            - (grid_settings.height as f32 * grid_settings.tile_size) / 2.0
+                            + grid_settings.tile_size / 2.0,
+                    ),
+                    DoorOrientation::Vertical => Vec2::new(
+                        door_tiles[0].x as f32 * grid_settings.tile_size
+                            - (grid_settings.width as f32 * grid_settings.tile_size) / 2.0
+                            + grid_settings.tile_size / 2.0,
+                        (door_tiles[0].y + door_tiles[1].y) as f32 * grid_settings.tile_size / 2.0
+                            - (grid_settings.height as f32 * grid_settings.tile_size) / 2.0,
+                    ),
+                };
+
+                let blueprint_entity = structures::spawn_door_blueprint(
+                    &mut commands,
+                    &shared_assets,
+                    &mut materials,
+                    grid_pos,
+                    center_pos,
+                    grid_settings.tile_size,
+                    orientation.door,
+                );
+                commands.spawn(ConstructionJob::new(blueprint_entity, cost));
+
+                // Door tiles are reserved but don't block; pawns pass through when open
+                for tile_pos in door_tiles {
+                    building_map.doors.insert(tile_pos, blueprint_entity);
+                }
+            }
+            BuildingType::Furniture(furniture_type) if furniture_type.sits_on_desk() => {
+                // The desk underneath already occupies the tiles
+                let entity = furniture::place_desk_furniture(
+                    &mut commands,
+                    furniture_type,
+                    grid_pos,
+                    orientation.furniture,
+                    &grid_settings,
+                    &asset_server,
+                );
+                completed.send(ConstructionCompletedEvent {
+                    entity,
+                    building_type: BlueprintType::Furniture(furniture_type),
+                    grid_pos,
+                    world_pos,
+                });
+            }
+            BuildingType::Furniture(furniture_type) => {
+                let entity = furniture::place_regular_furniture(
+                    &mut commands,
+                    &shared_assets,
+                    furniture_type,
+                    grid_pos,
+                    orientation.furniture,
+                    &grid_settings,
+                    &asset_server,
+                    &mut building_map,
+                );
+                completed.send(ConstructionCompletedEvent {
+                    entity,
+                    building_type: BlueprintType::Furniture(furniture_type),
+                    grid_pos,
+                    world_pos,
+                });
+            }
+            BuildingType::Wall
+            | BuildingType::Window
+            | BuildingType::Floor(_)
+            | BuildingType::Boundary(_) => {
+                // Windows replace the wall they are set into
+                if building_type == BuildingType::Window {
+                    if let Some(wall_entity) = building_map.walls.remove(&grid_pos) {
+                        commands.entity(wall_entity).despawn_recursive();
+                        building_map.occupied.remove(&grid_pos);
+                    }
+                }
+
+                let blueprint_type = match building_type {
+                    // Windows follow the wall they sit in; the requested orientation only
+                    // decides when the walls around don't
+                    BuildingType::Window => BlueprintType::Window(
+                        WindowOrientation::from_neighbours(grid_pos, |pos| {
+                            building_map.walls.contains_key(&pos)
+                                || building_map.doors.contains_key(&pos)
+                        })
+                        .unwrap_or(orientation.window),
+                    ),
+                    BuildingType::Floor(floor_type) => BlueprintType::Floor(floor_type),
+                    BuildingType::Boundary(boundary_type) => BlueprintType::Boundary(boundary_type),
+                    _ => BlueprintType::Wall,
+                };
+
+                let blueprint_entity = structures::spawn_blueprint(
+                    &mut commands,
+                    &shared_assets,
+                    &mut materials,
+                    blueprint_type,
+                    grid_pos,
+                    world_pos,
+                );
+                commands.spawn(ConstructionJob::new(blueprint_entity, cost));
+
+                match building_type {
+                    BuildingType::Floor(_) => {
+                        building_map.floors.insert(grid_pos);
+                    }
+                    BuildingType::Wall => {
+                        building_map.occupied.insert(grid_pos);
+                        building_map.walls.insert(grid_pos, blueprint_entity);
+                    }
+                    BuildingType::Boundary(boundary_type) => {
+                        if boundary_type.blocks_movement() {
+                            building_map.occupied.insert(grid_pos);
+                        }
+                        building_map.boundaries.insert(grid_pos, blueprint_entity);
+                    }
+                    _ => {
+                        building_map.occupied.insert(grid_pos);
+                    }
+                }
+            }
+        }
+    }
+
+    // A drag only counts as failing if nothing in it went down
+    if placed_any {
+        failures.clear();
+    } else if let Some((building_type, error)) = rejected {
+        failures.record(building_type, error);
+    }
+}
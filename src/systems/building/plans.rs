@@ -0,0 +1,197 @@
+use super::placement::{door_tiles, PlaceBuildingEvent, PlacementOrientation};
+use crate::components::*;
+use crate::systems::balance::Balance;
+use crate::systems::economy::Money;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::save_load::SaveLoadedEvent;
+use crate::ui::{BuildingType, Localization, Notification};
+use bevy::prelude::*;
+
+/// Plans are drawn in blue so they can't be mistaken for blueprints
+pub const PLAN_COLOR: Color = Color::srgba(0.3, 0.6, 1.0, 0.35);
+const PLAN_Z: f32 = 2.9;
+
+/// Asks for a plan marker at `position` while planning. Nothing is checked or paid for
+/// until the plans are converted.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SketchPlanEvent {
+    pub building_type: BuildingType,
+    pub position: IVec2,
+    pub orientation: PlacementOrientation,
+}
+
+/// Something done to every plan on the map at once
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanOrder {
+    /// Turns the plans into real placements, paying for them all together
+    Convert,
+    Clear,
+}
+
+/// A building sketched in planning mode. It costs nothing, blocks nothing and no one
+/// works on it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PlanMarker {
+    pub building_type: BuildingType,
+    pub orientation: PlacementOrientation,
+}
+
+impl PlanMarker {
+    fn tiles(&self, position: IVec2) -> Vec<IVec2> {
+        match self.building_type {
+            BuildingType::Door => door_tiles(position, self.orientation.door),
+            BuildingType::Furniture(furniture_type) if !furniture_type.sits_on_desk() => {
+                furniture_type.tiles_occupied(position, self.orientation.furniture)
+            }
+            _ => vec![position],
+        }
+    }
+
+    /// Plans are placed walls first and desk-top pieces last, so windows and doors find
+    /// their walls and furniture its floor and desk
+    fn build_order(&self) -> u8 {
+        match self.building_type {
+            BuildingType::Wall | BuildingType::Boundary(_) => 0,
+            BuildingType::Floor(_) => 1,
+            BuildingType::Window | BuildingType::Door => 2,
+            BuildingType::Furniture(furniture_type) if furniture_type.sits_on_desk() => 4,
+            BuildingType::Furniture(_) => 3,
+        }
+    }
+}
+
+/// Drops a plan marker for each sketch, replacing any plan already started on that tile
+pub fn sketch_plans(
+    mut commands: Commands,
+    mut events: EventReader<SketchPlanEvent>,
+    grid_settings: Res<GridSettings>,
+    plan_query: Query<(Entity, &GridPosition), With<PlanMarker>>,
+) {
+    for event in events.read() {
+        for (plan_entity, pos) in &plan_query {
+            if pos.to_ivec2() == event.position {
+                commands.entity(plan_entity).despawn_recursive();
+            }
+        }
+
+        let marker = PlanMarker {
+            building_type: event.building_type,
+            orientation: event.orientation,
+        };
+        let world_pos = grid_to_world(
+            event.position,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+        commands
+            .spawn((
+                marker,
+                GridPosition::new(event.position.x, event.position.y),
+                Transform::from_xyz(world_pos.x, world_pos.y, PLAN_Z),
+                Visibility::default(),
+            ))
+            .with_children(|parent| {
+                for tile in marker.tiles(event.position) {
+                    let offset = (tile - event.position).as_vec2() * grid_settings.tile_size;
+                    parent.spawn((
+                        Sprite::from_color(PLAN_COLOR, Vec2::splat(grid_settings.tile_size)),
+                        Transform::from_xyz(offset.x, offset.y, 0.0),
+                    ));
+                }
+            });
+    }
+}
+
+/// Converting only goes ahead when every plan can be paid for together. Each plan then
+/// goes through the usual placement checks, and any that fail stay on the map.
+pub fn handle_plan_orders(
+    mut commands: Commands,
+    mut orders: EventReader<PlanOrder>,
+    plan_query: Query<(Entity, &GridPosition, &PlanMarker)>,
+    money: Res<Money>,
+    balance: Res<Balance>,
+    localization: Res<Localization>,
+    mut place_events: EventWriter<PlaceBuildingEvent>,
+    mut notifications: EventWriter<Notification>,
+) {
+    for order in orders.read() {
+        match order {
+            PlanOrder::Clear => {
+                for (plan_entity, _, _) in &plan_query {
+                    commands.entity(plan_entity).despawn_recursive();
+                }
+            }
+            PlanOrder::Convert => {
+                if plan_query.is_empty() {
+                    notifications.send(Notification {
+                        message: localization.t("plans.none").to_string(),
+                    });
+                    continue;
+                }
+
+                let cost: i32 = plan_query
+                    .iter()
+                    .map(|(_, _, plan)| plan.building_type.cost(&balance))
+                    .sum();
+                if !money.can_afford(cost) {
+                    notifications.send(Notification {
+                        message: localization.format(
+                            "plans.unaffordable",
+                            &[
+                                ("cost", cost.to_string()),
+                                ("available", money.available().to_string()),
+                            ],
+                        ),
+                    });
+                    continue;
+                }
+
+                let mut plans: Vec<_> = plan_query.iter().collect();
+                plans.sort_by_key(|(_, _, plan)| plan.build_order());
+                for (plan_entity, pos, plan) in plans {
+                    place_events.send(PlaceBuildingEvent {
+                        building_type: plan.building_type,
+                        position: pos.to_ivec2(),
+                        orientation: plan.orientation,
+                        plan: Some(plan_entity),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Says how many of the plans just converted are still on the map, since those are the
+/// ones that didn't pass
+pub fn report_unbuilt_plans(
+    mut place_events: EventReader<PlaceBuildingEvent>,
+    plan_query: Query<(), With<PlanMarker>>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let unbuilt = place_events
+        .read()
+        .filter_map(|event| event.plan)
+        .filter(|plan_entity| plan_query.contains(*plan_entity))
+        .count();
+    if unbuilt > 0 {
+        notifications.send(Notification {
+            message: localization.format("plans.unbuilt", &[("count", unbuilt.to_string())]),
+        });
+    }
+}
+
+/// Plans aren't saved, so a loaded resort starts without any
+pub fn clear_plans_on_load(
+    mut commands: Commands,
+    mut loaded: EventReader<SaveLoadedEvent>,
+    plan_query: Query<Entity, With<PlanMarker>>,
+) {
+    if loaded.read().count() == 0 {
+        return;
+    }
+    for plan_entity in &plan_query {
+        commands.entity(plan_entity).despawn_recursive();
+    }
+}
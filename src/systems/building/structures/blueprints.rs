@@ -1,51 +1,56 @@
 use bevy::prelude::*;
 use crate::components::*;
+use crate::systems::shared_assets::SharedAssets;
 
-const WINDOW_THICKNESS: f32 = 0.2;
-const DOOR_THICKNESS: f32 = 0.2;
-
-/// Spawns a blueprint for structures (walls, windows, floors)
+/// Spawns a blueprint for structures (walls, windows, floors, fences)
+///
+/// Meshes come from `SharedAssets`, but each blueprint gets its own material because
+/// its opacity tracks that blueprint's construction progress.
 pub fn spawn_blueprint(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
+    shared_assets: &SharedAssets,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     blueprint_type: BlueprintType,
     grid_pos: IVec2,
     world_pos: Vec2,
-    tile_size: f32,
 ) -> Entity {
     // Blueprints are translucent white (floors lighter, structures more visible)
-    let (color, z_level, mesh_size) = match blueprint_type {
+    let (color, z_level, mesh) = match blueprint_type {
         BlueprintType::Wall => (
             Color::srgba(1.0, 1.0, 1.0, 0.6),  // More opaque for walls
             1.5,
-            (tile_size, tile_size)  // Full square
+            shared_assets.tile_mesh.clone()  // Full square
         ),
         BlueprintType::Door(_) => (
             Color::srgba(1.0, 1.0, 1.0, 0.6),
             1.5,
-            (tile_size, tile_size)  // Full square
+            shared_assets.tile_mesh.clone()  // Full square
         ),
-        BlueprintType::Window => (
+        BlueprintType::Window(orientation) => (
             Color::srgba(1.0, 1.0, 1.0, 0.6),
             1.5,
-            (tile_size, tile_size * WINDOW_THICKNESS)  // Thin for windows
+            shared_assets.blueprint_window_mesh(orientation)  // Thin, along the wall
         ),
         BlueprintType::Floor(_) => (
             Color::srgba(1.0, 1.0, 1.0, 0.3),  // More translucent for floors
             0.5,
-            (tile_size, tile_size)  // Full square
+            shared_assets.tile_mesh.clone()  // Full square
         ),
         BlueprintType::Furniture(_) => (
             Color::srgba(1.0, 1.0, 1.0, 0.6),
             2.5,
-            (tile_size, tile_size)  // Full square
+            shared_assets.tile_mesh.clone()  // Full square
+        ),
+        BlueprintType::Boundary(_) => (
+            Color::srgba(1.0, 1.0, 1.0, 0.6),
+            1.5,
+            shared_assets.boundary_mesh.clone()  // Low block in the middle of the tile
         ),
     };
 
     commands
         .spawn((
-            Mesh2d(meshes.add(Rectangle::new(mesh_size.0, mesh_size.1))),
+            Mesh2d(mesh),
             MeshMaterial2d(materials.add(color)),
             Transform::from_xyz(world_pos.x, world_pos.y, z_level),
             Blueprint::new(blueprint_type),
@@ -57,37 +62,25 @@ pub fn spawn_blueprint(
 /// Spawns a blueprint specifically for doors (2x1 size)
 pub fn spawn_door_blueprint(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
+    shared_assets: &SharedAssets,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     grid_pos: IVec2,
     center_pos: Vec2,
     tile_size: f32,
     orientation: DoorOrientation,
 ) -> Entity {
-    let (width, height, offset) = match orientation {
-        DoorOrientation::Horizontal => {
-            // 2 tiles wide: shift right by half a tile to center between both tiles
-            (
-                tile_size * 2.0,
-                tile_size * DOOR_THICKNESS,
-                Vec2::new(tile_size / 2.0, 0.0),
-            )
-        }
-        DoorOrientation::Vertical => {
-            // 2 tiles tall: shift up by half a tile to center between both tiles
-            (
-                tile_size * DOOR_THICKNESS,
-                tile_size * 2.0,
-                Vec2::new(0.0, tile_size / 2.0),
-            )
-        }
+    let offset = match orientation {
+        // 2 tiles wide: shift right by half a tile to center between both tiles
+        DoorOrientation::Horizontal => Vec2::new(tile_size / 2.0, 0.0),
+        // 2 tiles tall: shift up by half a tile to center between both tiles
+        DoorOrientation::Vertical => Vec2::new(0.0, tile_size / 2.0),
     };
 
     let adjusted_pos = center_pos + offset;
 
     commands
         .spawn((
-            Mesh2d(meshes.add(Rectangle::new(width, height))),
+            Mesh2d(shared_assets.blueprint_door_mesh(orientation)),
             MeshMaterial2d(materials.add(Color::srgba(0.4, 0.3, 0.2, 0.5))),
             Transform::from_xyz(adjusted_pos.x, adjusted_pos.y, 1.5),
             Blueprint::new(BlueprintType::Door(orientation)),
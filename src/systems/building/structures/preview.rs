@@ -1,18 +1,71 @@
 use bevy::prelude::*;
 use crate::components::*;
 use crate::systems::grid::{GridSettings, grid_to_world};
-use super::super::BuildingMap;
+use crate::ui::{BuildingType, ColorPalette};
+use super::super::factories::{PlacementError, PlacementValidator};
+
+pub const DECONSTRUCT_SELECTION_COLOR: Color = Color::srgba(1.0, 0.2, 0.2, 0.9);
+
+/// Outlines a single tile, inset slightly so neighbouring outlines don't merge
+pub fn draw_tile_outline(
+    gizmos: &mut Gizmos,
+    grid_pos: IVec2,
+    grid_settings: &GridSettings,
+    color: Color,
+) {
+    let world_pos = grid_to_world(
+        grid_pos,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+
+    gizmos.rect_2d(
+        Isometry2d::from_translation(world_pos),
+        Vec2::splat(grid_settings.tile_size - 2.0),
+        color,
+    );
+}
+
+/// Outlines the rectangle covering every tile between two grid corners
+pub fn draw_selection_box(
+    gizmos: &mut Gizmos,
+    start: IVec2,
+    end: IVec2,
+    grid_settings: &GridSettings,
+    color: Color,
+) {
+    let min = start.min(end);
+    let max = start.max(end);
+
+    let min_world = grid_to_world(
+        min,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+    let max_world = grid_to_world(
+        max,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+
+    let center = (min_world + max_world) / 2.0;
+    let size = (max_world - min_world) + Vec2::splat(grid_settings.tile_size);
+
+    gizmos.rect_2d(Isometry2d::from_translation(center), size, color);
+}
 
 /// Shows preview for door placement (2x1 tiles)
 pub fn show_door_preview(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
+    gizmos: &mut Gizmos,
     grid_pos: IVec2,
     orientation: DoorOrientation,
     grid_settings: &GridSettings,
-    building_map: &BuildingMap,
-) {
+    validator: &PlacementValidator,
+    palette: ColorPalette,
+) -> Result<(), PlacementError> {
     let door_tiles = match orientation {
         DoorOrientation::Horizontal => {
             vec![grid_pos, grid_pos + IVec2::new(1, 0)]
@@ -22,107 +75,57 @@ pub fn show_door_preview(
         }
     };
 
-    for tile_pos in door_tiles {
-        let tile_world_pos = grid_to_world(
-            tile_pos,
-            grid_settings.tile_size,
-            grid_settings.width,
-            grid_settings.height,
-        );
-
-        let is_blocked = building_map.occupied.contains(&tile_pos)
-            || building_map.doors.contains_key(&tile_pos);
-        let color = if is_blocked {
-            Color::srgba(1.0, 0.3, 0.3, 0.5)  // Red for blocked
-        } else {
-            Color::srgba(1.0, 1.0, 1.0, 0.5)  // White for valid
-        };
+    let result = validator.check_door(&door_tiles);
+    let color = if result.is_ok() {
+        palette.preview_valid()
+    } else {
+        palette.preview_blocked()
+    };
 
-        commands.spawn((
-            Mesh2d(meshes.add(Rectangle::new(
-                grid_settings.tile_size,
-                grid_settings.tile_size,
-            ))),
-            MeshMaterial2d(materials.add(color)),
-            Transform::from_xyz(tile_world_pos.x, tile_world_pos.y, 1.0),
-            PlacementPreview,
-        ));
+    for tile_pos in door_tiles {
+        draw_tile_outline(gizmos, tile_pos, grid_settings, color);
     }
+
+    result
 }
 
 /// Shows preview for single-tile structures (walls, windows)
 pub fn show_single_tile_preview(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
+    gizmos: &mut Gizmos,
     grid_pos: IVec2,
+    building_type: BuildingType,
     grid_settings: &GridSettings,
-    building_map: &BuildingMap,
-) {
-    let world_pos = grid_to_world(
-        grid_pos,
-        grid_settings.tile_size,
-        grid_settings.width,
-        grid_settings.height,
-    );
-
-    let is_occupied = building_map.occupied.contains(&grid_pos);
-    let color = if is_occupied {
-        Color::srgba(1.0, 0.3, 0.3, 0.5)  // Red for blocked
+    validator: &PlacementValidator,
+    palette: ColorPalette,
+) -> Result<(), PlacementError> {
+    let result = validator.check_structure(building_type, grid_pos);
+    let color = if result.is_ok() {
+        palette.preview_valid()
     } else {
-        Color::srgba(1.0, 1.0, 1.0, 0.5)  // White for valid
+        palette.preview_blocked()
     };
 
-    commands.spawn((
-        Mesh2d(meshes.add(Rectangle::new(
-            grid_settings.tile_size,
-            grid_settings.tile_size,
-        ))),
-        MeshMaterial2d(materials.add(color)),
-        Transform::from_xyz(world_pos.x, world_pos.y, 1.0),
-        PlacementPreview,
-    ));
+    draw_tile_outline(gizmos, grid_pos, grid_settings, color);
+
+    result
 }
 
 /// Shows preview for drag area (walls or floors)
 pub fn show_drag_area_preview(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
+    gizmos: &mut Gizmos,
     positions: Vec<IVec2>,
+    building_type: BuildingType,
     grid_settings: &GridSettings,
-    building_map: &BuildingMap,
-    is_floor: bool,
+    validator: &PlacementValidator,
+    palette: ColorPalette,
 ) {
     for grid_pos in positions {
-        let world_pos = grid_to_world(
-            grid_pos,
-            grid_settings.tile_size,
-            grid_settings.width,
-            grid_settings.height,
-        );
-
-        // For floors, check if structure is blocking; for structures, check if occupied
-        let is_blocked = if is_floor {
-            building_map.occupied.contains(&grid_pos)
-        } else {
-            building_map.occupied.contains(&grid_pos)
-        };
-
-        let color = if is_blocked {
-            Color::srgba(1.0, 0.3, 0.3, 0.5)  // Red for blocked
+        let color = if validator.check_structure(building_type, grid_pos).is_ok() {
+            palette.preview_valid()
         } else {
-            Color::srgba(1.0, 1.0, 1.0, 0.5)  // White for valid
+            palette.preview_blocked()
         };
 
-        commands.spawn((
-            Mesh2d(meshes.add(Rectangle::new(
-                grid_settings.tile_size,
-                grid_settings.tile_size,
-            ))),
-            MeshMaterial2d(materials.add(color)),
-            Transform::from_xyz(world_pos.x, world_pos.y, 1.0),
-            PlacementPreview,
-        ));
+        draw_tile_outline(gizmos, grid_pos, grid_settings, color);
     }
 }
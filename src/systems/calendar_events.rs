@@ -0,0 +1,185 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::guests::{SatisfactionCause, SatisfactionEvent};
+use crate::systems::time_control::{GameClock, Season, DAYS_PER_SEASON};
+use bevy::prelude::*;
+
+/// Satisfaction a party who came for a festival gains when their room or a lobby is
+/// decorated for it, and loses when neither is
+const DECORATED_BOOST: f32 = 0.05;
+const UNDECORATED_PENALTY: f32 = 0.1;
+
+/// Holiday weeks and festivals that come round on the same days of their season every
+/// year. Each one draws extra guests, puts its decorations on sale and has the parties
+/// who come for it looking for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalendarEvent {
+    BlossomFestival,
+    SummerHolidays,
+    HarvestFestival,
+    WinterHolidays,
+}
+
+impl CalendarEvent {
+    /// In calendar order
+    pub const ALL: [CalendarEvent; 4] = [
+        CalendarEvent::BlossomFestival,
+        CalendarEvent::SummerHolidays,
+        CalendarEvent::HarvestFestival,
+        CalendarEvent::WinterHolidays,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            CalendarEvent::BlossomFestival => "Blossom Festival",
+            CalendarEvent::SummerHolidays => "Summer Holidays",
+            CalendarEvent::HarvestFestival => "Harvest Festival",
+            CalendarEvent::WinterHolidays => "Winter Holidays",
+        }
+    }
+
+    /// What the furniture catalog's "event" field calls it
+    pub fn id(&self) -> &str {
+        match self {
+            CalendarEvent::BlossomFestival => "blossom_festival",
+            CalendarEvent::SummerHolidays => "summer_holidays",
+            CalendarEvent::HarvestFestival => "harvest_festival",
+            CalendarEvent::WinterHolidays => "winter_holidays",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<CalendarEvent> {
+        Self::ALL.into_iter().find(|event| event.id() == id)
+    }
+
+    pub fn season(&self) -> Season {
+        match self {
+            CalendarEvent::BlossomFestival => Season::Spring,
+            CalendarEvent::SummerHolidays => Season::Summer,
+            CalendarEvent::HarvestFestival => Season::Autumn,
+            CalendarEvent::WinterHolidays => Season::Winter,
+        }
+    }
+
+    /// First and last day of its season it runs on, counting from 1
+    fn season_days(&self) -> (u32, u32) {
+        match self {
+            CalendarEvent::BlossomFestival => (4, 5),
+            CalendarEvent::SummerHolidays => (3, 7),
+            CalendarEvent::HarvestFestival => (5, 6),
+            CalendarEvent::WinterHolidays => (4, 7),
+        }
+    }
+
+    /// How strongly it pulls guests in, on top of the season's own pull
+    pub fn demand_multiplier(&self) -> f32 {
+        match self {
+            CalendarEvent::BlossomFestival => 1.3,
+            CalendarEvent::SummerHolidays => 1.25,
+            CalendarEvent::HarvestFestival => 1.3,
+            CalendarEvent::WinterHolidays => 1.5,
+        }
+    }
+
+    /// The event running on a calendar day, if any
+    pub fn on_day(day: u32) -> Option<CalendarEvent> {
+        let day_of_season = day.saturating_sub(1) % DAYS_PER_SEASON + 1;
+        let season = Season::of_day(day);
+        Self::ALL.into_iter().find(|event| {
+            let (first, last) = event.season_days();
+            event.season() == season && (first..=last).contains(&day_of_season)
+        })
+    }
+
+    /// The last calendar day of the run that `day` falls in
+    pub fn last_day_from(&self, day: u32) -> u32 {
+        let day_of_season = day.saturating_sub(1) % DAYS_PER_SEASON + 1;
+        day + self.season_days().1.saturating_sub(day_of_season)
+    }
+
+    /// The calendar day it next starts on, `day` itself included
+    pub fn next_start(&self, day: u32) -> u32 {
+        let year = DAYS_PER_SEASON * 4;
+        let season_index = Self::ALL
+            .iter()
+            .position(|event| event.season() == self.season())
+            .unwrap_or(0) as u32;
+        let year_start = day - day.saturating_sub(1) % year;
+        let start = year_start + season_index * DAYS_PER_SEASON + self.season_days().0 - 1;
+        if start >= day {
+            start
+        } else {
+            start + year
+        }
+    }
+
+    /// The next event to start after `day`, and the day it starts on
+    pub fn next_after(day: u32) -> Option<(CalendarEvent, u32)> {
+        Self::ALL
+            .into_iter()
+            .map(|event| (event, event.next_start(day + 1)))
+            .min_by_key(|(_, start)| *start)
+    }
+
+    /// Whether `furniture_type` is one of its decorations
+    pub fn decorated_with(&self, furniture_type: FurnitureType) -> bool {
+        furniture_type.event() == Some(self.id())
+    }
+}
+
+pub struct CalendarEventsPlugin;
+
+impl Plugin for CalendarEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, look_for_festive_decorations.in_set(GameplaySet));
+    }
+}
+
+/// A party shown to their room while an event is on looks round it and the lobbies for
+/// that event's decorations, pleased to find some and let down otherwise
+fn look_for_festive_decorations(
+    mut events: EventReader<SatisfactionEvent>,
+    clock: Res<GameClock>,
+    decoration_query: Query<
+        (&GridPosition, &FurnitureType, &FurnitureOrientation),
+        With<Furniture>,
+    >,
+    lobby_query: Query<&Zone>,
+    mut booking_query: Query<(&Zone, &mut Booking)>,
+) {
+    let event_on = CalendarEvent::on_day(clock.day());
+    let in_lobby = |tile: IVec2| {
+        lobby_query
+            .iter()
+            .any(|zone| zone.zone_type == ZoneType::Lobby && zone.tiles.contains(&tile))
+    };
+    for event in events.read() {
+        if !matches!(
+            event.cause,
+            SatisfactionCause::GreatRoom | SatisfactionCause::DisappointingRoom
+        ) {
+            continue;
+        }
+        let Some(calendar_event) = event_on else {
+            continue;
+        };
+        let Ok((room, mut booking)) = booking_query.get_mut(event.room) else {
+            continue;
+        };
+
+        let decorated = decoration_query
+            .iter()
+            .filter(|(_, furniture_type, _)| calendar_event.decorated_with(**furniture_type))
+            .flat_map(|(pos, furniture_type, orientation)| {
+                furniture_type.tiles_occupied(pos.to_ivec2(), *orientation)
+            })
+            .any(|tile| room.tiles.contains(&tile) || in_lobby(tile));
+
+        if decorated {
+            booking.satisfaction = (booking.satisfaction + DECORATED_BOOST).min(1.0);
+        } else {
+            booking.satisfaction = (booking.satisfaction - UNDECORATED_PENALTY).max(0.0);
+            booking.complain(ReviewComplaint::NoFestiveDecor);
+        }
+    }
+}
@@ -1,6 +1,16 @@
 use bevy::prelude::*;
 use bevy::input::mouse::{MouseMotion, MouseWheel};
 
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::cursor::CursorWorldPosition;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::photo_mode::{photo_mode_active, PhotoMode};
+use crate::ui::{GameSettings, UiInputBlocker};
+
+/// Scroll notches' worth of zoom per second with a trigger pulled all the way
+const GAMEPAD_ZOOM_RATE: f32 = 10.0;
+
 #[derive(Component)]
 pub struct CameraController {
     pub pan_speed: f32,
@@ -24,16 +34,25 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (camera_pan, camera_zoom));
+        // Photo mode drives the camera itself while active
+        app.init_resource::<PhotoMode>().add_systems(
+            Update,
+            (camera_pan, camera_zoom, center_camera_on_resort)
+                .run_if(not(photo_mode_active))
+                .in_set(GameplaySet),
+        );
     }
 }
 
 fn camera_pan(
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mouse_button: Res<ButtonInput<MouseButton>>,
     mut mouse_motion: EventReader<MouseMotion>,
+    gamepads: Query<&Gamepad>,
     mut query: Query<(&mut Transform, &OrthographicProjection, &CameraController), With<Camera>>,
+    ui_blocker: Res<UiInputBlocker>,
+    settings: Res<GameSettings>,
 ) {
     let Ok((mut transform, projection, controller)) = query.get_single_mut() else {
         return;
@@ -41,25 +60,37 @@ fn camera_pan(
 
     let mut pan_delta = Vec2::ZERO;
 
-    // Keyboard panning (WASD or Arrow Keys)
-    if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
-        pan_delta.y += 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
-        pan_delta.y -= 1.0;
+    // Keyboard panning (WASD or Arrow Keys), unless the player is typing
+    if !ui_blocker.text_input_active {
+        if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
+            pan_delta.y += 1.0;
+        }
+        if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
+            pan_delta.y -= 1.0;
+        }
+        if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
+            pan_delta.x -= 1.0;
+        }
+        if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
+            pan_delta.x += 1.0;
+        }
     }
-    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
-        pan_delta.x -= 1.0;
+
+    // Diagonals shouldn't pan faster than straight lines
+    if pan_delta != Vec2::ZERO {
+        pan_delta = pan_delta.normalize();
     }
-    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
-        pan_delta.x += 1.0;
+
+    // The right stick pans too, at a speed matching how far it is pushed
+    for gamepad in &gamepads {
+        pan_delta += gamepad.right_stick();
     }
 
-    // Apply keyboard pan
     if pan_delta != Vec2::ZERO {
-        pan_delta = pan_delta.normalize();
-        transform.translation.x += pan_delta.x * controller.pan_speed * time.delta_secs() * projection.scale;
-        transform.translation.y += pan_delta.y * controller.pan_speed * time.delta_secs() * projection.scale;
+        pan_delta = pan_delta.clamp_length_max(1.0);
+        let pan_speed = controller.pan_speed * settings.camera_speed;
+        transform.translation.x += pan_delta.x * pan_speed * time.delta_secs() * projection.scale;
+        transform.translation.y += pan_delta.y * pan_speed * time.delta_secs() * projection.scale;
     }
 
     // Mouse panning (Middle Mouse Button)
@@ -73,16 +104,86 @@ fn camera_pan(
 }
 
 fn camera_zoom(
+    time: Res<Time<Real>>,
     mut scroll_events: EventReader<MouseWheel>,
-    mut query: Query<(&mut OrthographicProjection, &CameraController), With<Camera>>,
+    gamepads: Query<&Gamepad>,
+    cursor: Res<CursorWorldPosition>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut OrthographicProjection,
+            &CameraController,
+        ),
+        With<Camera>,
+    >,
 ) {
-    let Ok((mut projection, controller)) = query.get_single_mut() else {
+    let Ok((mut transform, mut projection, controller)) = query.get_single_mut() else {
         return;
     };
 
     for event in scroll_events.read() {
         // Zoom in/out based on scroll direction
         let zoom_delta = -event.y * controller.zoom_speed;
-        projection.scale = (projection.scale + zoom_delta).clamp(controller.min_zoom, controller.max_zoom);
+        let scale = (projection.scale + zoom_delta).clamp(controller.min_zoom, controller.max_zoom);
+
+        // Whatever is under the cursor stays under it, so scrolling homes in on that spot
+        // rather than the middle of the screen
+        if let Some(focus) = cursor.world {
+            let camera = transform.translation.truncate();
+            let moved = focus + (camera - focus) * scale / projection.scale;
+            transform.translation.x = moved.x;
+            transform.translation.y = moved.y;
+        }
+        projection.scale = scale;
     }
+
+    // Right trigger zooms in and left zooms out, as fast as a steady scroll when held down
+    for gamepad in &gamepads {
+        let trigger = |button| gamepad.get(button).unwrap_or(0.0);
+        let pull = trigger(GamepadButton::LeftTrigger2) - trigger(GamepadButton::RightTrigger2);
+        let zoom_delta = pull * controller.zoom_speed * GAMEPAD_ZOOM_RATE * time.delta_secs();
+        projection.scale =
+            (projection.scale + zoom_delta).clamp(controller.min_zoom, controller.max_zoom);
+    }
+}
+
+/// Home brings the camera back over the middle of everything built, or the middle of the
+/// map before anything is
+fn center_camera_on_resort(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    mut query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
+) {
+    if ui_blocker.text_input_active || !keyboard.just_pressed(KeyCode::Home) {
+        return;
+    }
+    let Ok(mut transform) = query.get_single_mut() else {
+        return;
+    };
+
+    let built = building_map
+        .walls
+        .keys()
+        .chain(building_map.doors.keys())
+        .chain(building_map.floors.iter())
+        .copied();
+    let bounds = built.fold(None, |bounds: Option<(IVec2, IVec2)>, tile| {
+        Some(bounds.map_or((tile, tile), |(min, max)| (min.min(tile), max.max(tile))))
+    });
+    let to_world = |tile| {
+        grid_to_world(
+            tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+    };
+    let center = match bounds {
+        Some((min, max)) => (to_world(min) + to_world(max)) / 2.0,
+        None => to_world(IVec2::new(grid_settings.width, grid_settings.height) / 2),
+    };
+    transform.translation.x = center.x;
+    transform.translation.y = center.y;
 }
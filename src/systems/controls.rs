@@ -0,0 +1,192 @@
+use crate::systems::app_state::GameplaySet;
+use crate::systems::camera::CameraController;
+use crate::systems::cursor::update_cursor_world_position;
+use crate::systems::grid::{grid_to_world, world_to_grid, GridSettings};
+use crate::systems::DragState;
+use crate::ui::{ToolbarState, UiInputBlocker};
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+/// D-pad steps the build cursor once when pressed, then keeps stepping while held
+const REPEAT_DELAY: f32 = 0.3;
+const REPEAT_INTERVAL: f32 = 0.12;
+const DPAD: [(GamepadButton, IVec2); 4] = [
+    (GamepadButton::DPadUp, IVec2::Y),
+    (GamepadButton::DPadDown, IVec2::NEG_Y),
+    (GamepadButton::DPadLeft, IVec2::NEG_X),
+    (GamepadButton::DPadRight, IVec2::X),
+];
+const GAMEPAD_CURSOR_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// What the player is asking the world to do, whichever device they asked with. Placement
+/// reads these instead of the mouse buttons so mouse and gamepad work side by side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerAction {
+    /// Left click, or A on a gamepad: place, or hold and drag
+    Confirm,
+    /// B on a gamepad: drop the current drag or build tool
+    Cancel,
+    /// R, or X on a gamepad: turn whatever is being placed
+    Rotate,
+}
+
+/// A build cursor the d-pad moves tile by tile. While active it stands in for the mouse.
+#[derive(Resource, Default, Debug)]
+pub struct GamepadCursor {
+    pub active: bool,
+    pub tile: IVec2,
+}
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ButtonInput<PointerAction>>()
+            .init_resource::<GamepadCursor>()
+            .add_systems(
+                PreUpdate,
+                (update_pointer_actions, move_gamepad_cursor)
+                    .after(UiSystem::Focus)
+                    .before(update_cursor_world_position)
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                (cancel_placement, draw_gamepad_cursor).in_set(GameplaySet),
+            );
+    }
+}
+
+/// Folds the mouse, keyboard and every connected gamepad into one set of actions
+fn update_pointer_actions(
+    mut actions: ResMut<ButtonInput<PointerAction>>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    gamepads: Query<&Gamepad>,
+) {
+    let gamepad_held =
+        |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.pressed(button));
+    let held = [
+        (
+            PointerAction::Confirm,
+            mouse_button.pressed(MouseButton::Left) || gamepad_held(GamepadButton::South),
+        ),
+        (PointerAction::Cancel, gamepad_held(GamepadButton::East)),
+        (
+            PointerAction::Rotate,
+            (!ui_blocker.text_input_active && keyboard.pressed(KeyCode::KeyR))
+                || gamepad_held(GamepadButton::West),
+        ),
+    ];
+
+    // Pressing and releasing against last frame's state is what sets the just_ flags
+    actions.clear();
+    for (action, held) in held {
+        if held {
+            actions.press(action);
+        } else {
+            actions.release(action);
+        }
+    }
+}
+
+/// The first d-pad press puts the cursor in the middle of the screen; moving the mouse
+/// hands control back to it
+fn move_gamepad_cursor(
+    time: Res<Time<Real>>,
+    grid_settings: Res<GridSettings>,
+    mut gamepad_cursor: ResMut<GamepadCursor>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    gamepads: Query<&Gamepad>,
+    camera_query: Query<&Transform, With<CameraController>>,
+    mut repeat_in: Local<f32>,
+) {
+    if cursor_moved.read().count() > 0 {
+        gamepad_cursor.active = false;
+    }
+
+    let mut direction = IVec2::ZERO;
+    let mut fresh = false;
+    for gamepad in &gamepads {
+        for (button, step) in DPAD {
+            if gamepad.pressed(button) {
+                direction += step;
+            }
+            fresh |= gamepad.just_pressed(button);
+        }
+    }
+    let direction = direction.clamp(IVec2::NEG_ONE, IVec2::ONE);
+    if direction == IVec2::ZERO {
+        return;
+    }
+
+    if fresh {
+        *repeat_in = REPEAT_DELAY;
+    } else {
+        *repeat_in -= time.delta_secs();
+        if *repeat_in > 0.0 {
+            return;
+        }
+        *repeat_in += REPEAT_INTERVAL;
+    }
+
+    if !gamepad_cursor.active {
+        let center = camera_query.get_single().ok().and_then(|transform| {
+            world_to_grid(
+                transform.translation.truncate(),
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        });
+        gamepad_cursor.active = true;
+        gamepad_cursor.tile =
+            center.unwrap_or(IVec2::new(grid_settings.width, grid_settings.height) / 2);
+        return;
+    }
+
+    let last = IVec2::new(grid_settings.width - 1, grid_settings.height - 1);
+    gamepad_cursor.tile = (gamepad_cursor.tile + direction).clamp(IVec2::ZERO, last);
+}
+
+/// Cancel lets go of a drag in progress, or failing that puts the build tool away
+fn cancel_placement(
+    actions: Res<ButtonInput<PointerAction>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut drag_state: ResMut<DragState>,
+    mut toolbar_state: ResMut<ToolbarState>,
+) {
+    if !actions.just_pressed(PointerAction::Cancel) || ui_blocker.block_world_input {
+        return;
+    }
+
+    if drag_state.is_dragging {
+        drag_state.end();
+    } else {
+        toolbar_state.selected_building = None;
+        toolbar_state.selected_order = None;
+    }
+}
+
+fn draw_gamepad_cursor(
+    gamepad_cursor: Res<GamepadCursor>,
+    grid_settings: Res<GridSettings>,
+    mut gizmos: Gizmos,
+) {
+    if !gamepad_cursor.active {
+        return;
+    }
+
+    let center = grid_to_world(
+        gamepad_cursor.tile,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+    gizmos.rect_2d(
+        Isometry2d::from_translation(center),
+        Vec2::splat(grid_settings.tile_size),
+        GAMEPAD_CURSOR_COLOR,
+    );
+}
@@ -0,0 +1,104 @@
+use crate::systems::app_state::GameplaySet;
+use crate::systems::controls::GamepadCursor;
+use crate::systems::grid::{grid_to_world, world_to_grid, GridSettings};
+use crate::ui::TOOLBAR_HEIGHT;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::window::{PrimaryWindow, Window as BevyWindow};
+
+/// Where the mouse, or the gamepad's build cursor, is this frame, worked out once before
+/// `Update` so world-input systems don't each repeat the window, camera and grid lookups
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct CursorWorldPosition {
+    /// Cursor in UI coordinates, ready for placing nodes next to it; these are window
+    /// coordinates shrunk by the UI scale
+    pub ui: Option<Vec2>,
+    /// The point in the world under the cursor
+    pub world: Option<Vec2>,
+    /// The tile under the cursor, while it is over the map
+    pub grid: Option<IVec2>,
+    /// Over a button or other interactive UI node
+    pub over_ui: bool,
+    /// Over the toolbar strip at the bottom of the window, where clicks never reach the world
+    pub over_toolbar: bool,
+}
+
+impl CursorWorldPosition {
+    /// The tile under the cursor, unless the cursor is down on the toolbar
+    pub fn world_tile(&self) -> Option<IVec2> {
+        self.grid.filter(|_| !self.over_toolbar)
+    }
+}
+
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorWorldPosition>().add_systems(
+            PreUpdate,
+            update_cursor_world_position
+                .after(UiSystem::Focus)
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+pub fn update_cursor_world_position(
+    mut cursor: ResMut<CursorWorldPosition>,
+    grid_settings: Res<GridSettings>,
+    gamepad_cursor: Res<GamepadCursor>,
+    ui_scale: Res<UiScale>,
+    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    interaction_query: Query<&Interaction>,
+) {
+    // The gamepad cursor sits on a tile out in the world, never over the UI
+    if gamepad_cursor.active {
+        let world = grid_to_world(
+            gamepad_cursor.tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+        let screen = camera_query
+            .get_single()
+            .ok()
+            .and_then(|(camera, camera_transform)| {
+                camera.world_to_viewport(camera_transform, world.extend(0.0)).ok()
+            });
+        *cursor = CursorWorldPosition {
+            ui: screen.map(|screen| screen / ui_scale.0),
+            world: Some(world),
+            grid: Some(gamepad_cursor.tile),
+            over_ui: false,
+            over_toolbar: false,
+        };
+        return;
+    }
+
+    let window = window_query.get_single().ok();
+    let screen = window.and_then(|window| window.cursor_position());
+    let world = screen.and_then(|screen| {
+        let (camera, camera_transform) = camera_query.get_single().ok()?;
+        camera.viewport_to_world_2d(camera_transform, screen).ok()
+    });
+
+    *cursor = CursorWorldPosition {
+        ui: screen.map(|screen| screen / ui_scale.0),
+        world,
+        grid: world.and_then(|world| {
+            world_to_grid(
+                world,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        }),
+        over_ui: interaction_query
+            .iter()
+            .any(|interaction| *interaction != Interaction::None),
+        over_toolbar: window.zip(screen).is_some_and(|(window, screen)| {
+            screen.y > window.height() - TOOLBAR_HEIGHT * ui_scale.0
+        }),
+    };
+}
@@ -0,0 +1,196 @@
+use crate::components::{GuestArchetype, ZoneQuality};
+use crate::systems::app_state::GameplaySet;
+use crate::systems::calendar_events::CalendarEvent;
+use crate::systems::economy::{Marketing, RoomRates};
+use crate::systems::game_rules::GameRules;
+use crate::systems::game_state::resort_reputation;
+use crate::systems::statistics::StatsHistory;
+use crate::systems::time_control::GameClock;
+use crate::systems::weather::WeatherState;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Share of rooms filled at a fair price in neutral season and weather
+const BASE_OCCUPANCY: f32 = 0.75;
+/// How sharply guests react to prices above or below the fair rate
+const PRICE_ELASTICITY: f32 = 1.5;
+/// Demand knocked off by each bad review, how low reviews can drag it, and how much of
+/// it comes back each game hour as the reviews are forgotten
+const BAD_REVIEW_PENALTY: f32 = 0.05;
+const MIN_REVIEW_FACTOR: f32 = 0.5;
+const REVIEW_RECOVERY_PER_HOUR: f32 = 0.005;
+
+/// Who a scenario sends to the resort: how many parties, and which kinds. Saved with
+/// the resort so a scenario keeps its guests through saves.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuestSpawnSettings {
+    /// Multiplier on how many parties turn up
+    pub arrival_rate: f32,
+    /// Which kinds of party can come, in [`GuestArchetype::ALL`] order
+    pub archetypes: [bool; GuestArchetype::ALL.len()],
+}
+
+impl Default for GuestSpawnSettings {
+    fn default() -> Self {
+        Self {
+            arrival_rate: 1.0,
+            archetypes: [true; GuestArchetype::ALL.len()],
+        }
+    }
+}
+
+impl GuestSpawnSettings {
+    pub const MIN_ARRIVAL_RATE: f32 = 0.0;
+    pub const MAX_ARRIVAL_RATE: f32 = 3.0;
+}
+
+/// How eager guests currently are to visit, driven by season, calendar events, weather,
+/// marketing and recent reviews, and which kinds of guest are coming
+#[derive(Resource)]
+pub struct GuestDemand {
+    pub season_factor: f32,
+    /// Above 1 while a holiday or festival is on
+    pub event_factor: f32,
+    pub weather_factor: f32,
+    pub marketing_factor: f32,
+    /// Below 1 while bad reviews from parties who walked out are still fresh
+    pub review_factor: f32,
+    /// The scenario's arrival rate; nobody comes while a scenario is being edited
+    pub spawn_factor: f32,
+    /// How busy pools get next to a mild day, with the weather and season
+    pub pool_usage: f32,
+    /// Relative odds of each kind of party among new arrivals, in
+    /// [`GuestArchetype::ALL`] order
+    pub archetype_mix: [f32; GuestArchetype::ALL.len()],
+}
+
+impl Default for GuestDemand {
+    fn default() -> Self {
+        Self {
+            season_factor: 1.0,
+            event_factor: 1.0,
+            weather_factor: 1.0,
+            marketing_factor: 1.0,
+            review_factor: 1.0,
+            spawn_factor: 1.0,
+            pool_usage: 1.0,
+            archetype_mix: archetype_mix(0.5, 1.0),
+        }
+    }
+}
+
+impl GuestDemand {
+    /// Multiplier applied to the base guest arrival rate
+    pub fn arrival_multiplier(&self) -> f32 {
+        self.season_factor
+            * self.event_factor
+            * self.weather_factor
+            * self.marketing_factor
+            * self.review_factor
+            * self.spawn_factor
+    }
+
+    pub fn add_bad_review(&mut self) {
+        self.review_factor = (self.review_factor - BAD_REVIEW_PENALTY).max(MIN_REVIEW_FACTOR);
+    }
+
+    /// Demand response to the asking price relative to what the room quality is worth
+    pub fn price_factor(quality: ZoneQuality, rate: i32) -> f32 {
+        let fair = quality.fair_nightly_rate();
+        if fair <= 0 || rate <= 0 {
+            return 0.0;
+        }
+
+        (fair as f32 / rate as f32).powf(PRICE_ELASTICITY).min(1.5)
+    }
+
+    /// Expected fraction of rooms of this tier that will be booked
+    pub fn expected_occupancy(&self, quality: ZoneQuality, rates: &RoomRates) -> f32 {
+        let price_factor = Self::price_factor(quality, rates.rate(quality));
+        (BASE_OCCUPANCY * self.arrival_multiplier() * price_factor).clamp(0.0, 1.0)
+    }
+}
+
+/// Arrival odds of every kind of party for a reputation from 0 to 1 and a price level
+/// relative to fair rates
+pub fn archetype_mix(reputation: f32, price_level: f32) -> [f32; GuestArchetype::ALL.len()] {
+    GuestArchetype::ALL.map(|archetype| archetype.arrival_weight(reputation, price_level))
+}
+
+pub struct DemandPlugin;
+
+impl Plugin for DemandPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GuestDemand>()
+            .init_resource::<GuestSpawnSettings>()
+            .add_systems(Update, update_guest_demand.in_set(GameplaySet));
+    }
+}
+
+fn update_guest_demand(
+    clock: Res<GameClock>,
+    weather: Res<WeatherState>,
+    marketing: Res<Marketing>,
+    rates: Res<RoomRates>,
+    stats: Res<StatsHistory>,
+    spawn_settings: Res<GuestSpawnSettings>,
+    rules: Res<GameRules>,
+    mut demand: ResMut<GuestDemand>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    if !clock.is_changed()
+        && !weather.is_changed()
+        && !marketing.is_changed()
+        && !rates.is_changed()
+        && !stats.is_changed()
+        && !spawn_settings.is_changed()
+        && !rules.is_changed()
+    {
+        return;
+    }
+
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+    if elapsed > 0.0 && demand.review_factor < 1.0 {
+        demand.review_factor = (demand.review_factor + REVIEW_RECOVERY_PER_HOUR * elapsed).min(1.0);
+    }
+
+    let season_factor = clock.season().demand_multiplier();
+    let event_factor =
+        CalendarEvent::on_day(clock.day()).map_or(1.0, |event| event.demand_multiplier());
+    let weather_factor = weather.current.demand_multiplier();
+    let marketing_factor = marketing.arrival_multiplier();
+    let pool_usage = weather.current.pool_usage_multiplier() * season_factor;
+    let spawn_factor = if rules.is_editor() {
+        0.0
+    } else {
+        spawn_settings.arrival_rate
+    };
+    let mut mix = archetype_mix(
+        resort_reputation(&stats, &demand) / 5.0,
+        rates.price_level(),
+    );
+    for (weight, allowed) in mix.iter_mut().zip(spawn_settings.archetypes) {
+        if !allowed {
+            *weight = 0.0;
+        }
+    }
+
+    // Only write when something moved so change detection stays meaningful for the HUD
+    if demand.season_factor != season_factor
+        || demand.event_factor != event_factor
+        || demand.weather_factor != weather_factor
+        || demand.marketing_factor != marketing_factor
+        || demand.spawn_factor != spawn_factor
+        || demand.pool_usage != pool_usage
+        || demand.archetype_mix != mix
+    {
+        demand.season_factor = season_factor;
+        demand.event_factor = event_factor;
+        demand.weather_factor = weather_factor;
+        demand.marketing_factor = marketing_factor;
+        demand.spawn_factor = spawn_factor;
+        demand.pool_usage = pool_usage;
+        demand.archetype_mix = mix;
+    }
+}
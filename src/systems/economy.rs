@@ -1,25 +1,65 @@
+use crate::components::{ConstructionJob, GuestParty, ZoneQuality};
+use crate::systems::app_state::GameplaySet;
+use crate::systems::time_control::SECONDS_PER_GAME_HOUR;
+use crate::ui::UiFeedbackEvent;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use std::collections::VecDeque;
 
 #[derive(Resource)]
 pub struct Money {
     pub amount: i32,
+    /// Set aside for blueprints that haven't been built yet; still part of `amount`
+    pub reserved: i32,
+    /// Sandbox games never run out of money
+    pub infinite: bool,
 }
 
 impl Default for Money {
     fn default() -> Self {
         Self {
             amount: 10000, // Starting money
+            reserved: 0,
+            infinite: false,
         }
     }
 }
 
 impl Money {
+    /// What can still be spent once reserved construction funds are set aside
+    pub fn available(&self) -> i32 {
+        self.amount - self.reserved
+    }
+
     pub fn can_afford(&self, cost: i32) -> bool {
-        self.amount >= cost
+        self.infinite || self.available() >= cost
+    }
+
+    /// Sets `cost` aside for construction without spending it yet
+    pub fn reserve(&mut self, cost: i32) -> bool {
+        if self.infinite {
+            true
+        } else if self.can_afford(cost) {
+            self.reserved += cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Spends money reserved earlier, once the construction it was set aside for is done
+    pub fn settle(&mut self, escrow: i32) {
+        if self.infinite {
+            return;
+        }
+        self.amount -= escrow;
+        self.reserved = (self.reserved - escrow).max(0);
     }
 
     pub fn deduct(&mut self, cost: i32) -> bool {
-        if self.can_afford(cost) {
+        if self.infinite {
+            true
+        } else if self.can_afford(cost) {
             self.amount -= cost;
             true
         } else {
@@ -32,10 +72,335 @@ impl Money {
     }
 }
 
+/// Money access for placement systems that also reports each purchase (or refusal)
+/// so the UI can show it
+#[derive(SystemParam)]
+pub struct Purchases<'w> {
+    pub money: ResMut<'w, Money>,
+    pub feedback: EventWriter<'w, UiFeedbackEvent>,
+}
+
+impl Purchases<'_> {
+    /// Pays for something placed at `world_pos`; returns false if it can't be afforded
+    pub fn try_buy(&mut self, cost: i32, world_pos: Vec2) -> bool {
+        if !self.money.deduct(cost) {
+            self.reject(cost, world_pos);
+            return false;
+        }
+
+        self.report_spent(cost, world_pos);
+        true
+    }
+
+    /// Reserves the cost of a blueprint placed at `world_pos`; returns false if it
+    /// can't be afforded. Nothing is spent until the blueprint is built.
+    pub fn try_reserve(&mut self, cost: i32, world_pos: Vec2) -> bool {
+        if !self.money.reserve(cost) {
+            self.reject(cost, world_pos);
+            return false;
+        }
+        true
+    }
+
+    /// Charges a finished blueprint's escrow and shows the spend where it was built
+    pub fn settle(&mut self, escrow: i32, world_pos: Vec2) {
+        self.money.settle(escrow);
+        self.report_spent(escrow, world_pos);
+    }
+
+    pub fn reject(&mut self, cost: i32, world_pos: Vec2) {
+        self.feedback
+            .send(UiFeedbackEvent::InsufficientFunds { cost, world_pos });
+    }
+
+    /// Shows a spend that was already deducted (e.g. a finished blueprint's escrow)
+    pub fn report_spent(&mut self, amount: i32, world_pos: Vec2) {
+        if amount > 0 && !self.money.infinite {
+            self.feedback.send(UiFeedbackEvent::Spent { amount, world_pos });
+        }
+    }
+}
+
+/// Nightly room rates the player charges, per room quality tier
+#[derive(Resource)]
+pub struct RoomRates {
+    pub basic: i32,
+    pub good: i32,
+    pub excellent: i32,
+    pub luxury: i32,
+}
+
+impl Default for RoomRates {
+    fn default() -> Self {
+        Self {
+            basic: ZoneQuality::Basic.fair_nightly_rate(),
+            good: ZoneQuality::Good.fair_nightly_rate(),
+            excellent: ZoneQuality::Excellent.fair_nightly_rate(),
+            luxury: ZoneQuality::Luxury.fair_nightly_rate(),
+        }
+    }
+}
+
+impl RoomRates {
+    pub const MIN_RATE: i32 = 10;
+    pub const MAX_RATE: i32 = 2000;
+
+    pub fn rate(&self, quality: ZoneQuality) -> i32 {
+        match quality {
+            ZoneQuality::None => 0,
+            ZoneQuality::Basic => self.basic,
+            ZoneQuality::Good => self.good,
+            ZoneQuality::Excellent => self.excellent,
+            ZoneQuality::Luxury => self.luxury,
+        }
+    }
+
+    /// How dear the rooms are on the whole, as the average of each tier's rate over its
+    /// fair rate; 1 is fairly priced
+    pub fn price_level(&self) -> f32 {
+        let tiers = ZoneQuality::rentable();
+        let total: f32 = tiers
+            .iter()
+            .map(|quality| self.rate(*quality) as f32 / quality.fair_nightly_rate() as f32)
+            .sum();
+        total / tiers.len() as f32
+    }
+
+    pub fn adjust(&mut self, quality: ZoneQuality, delta: i32) {
+        let rate = match quality {
+            ZoneQuality::None => return,
+            ZoneQuality::Basic => &mut self.basic,
+            ZoneQuality::Good => &mut self.good,
+            ZoneQuality::Excellent => &mut self.excellent,
+            ZoneQuality::Luxury => &mut self.luxury,
+        };
+        *rate = (*rate + delta).clamp(Self::MIN_RATE, Self::MAX_RATE);
+    }
+}
+
+/// Daily wage paid to every worker. Paying above the going rate lifts morale and
+/// paying below it wears staff down.
+#[derive(Resource)]
+pub struct StaffWages {
+    pub daily_wage: i32,
+}
+
+impl Default for StaffWages {
+    fn default() -> Self {
+        Self {
+            daily_wage: Self::FAIR_WAGE,
+        }
+    }
+}
+
+impl StaffWages {
+    pub const FAIR_WAGE: i32 = 100;
+    pub const MIN_WAGE: i32 = 40;
+    pub const MAX_WAGE: i32 = 300;
+
+    pub fn adjust(&mut self, delta: i32) {
+        self.daily_wage = (self.daily_wage + delta).clamp(Self::MIN_WAGE, Self::MAX_WAGE);
+    }
+
+    /// How far the wage pushes morale up or down
+    pub fn morale_effect(&self) -> f32 {
+        let ratio = self.daily_wage as f32 / Self::FAIR_WAGE as f32;
+        ((ratio - 1.0) * 0.4).clamp(-0.3, 0.2)
+    }
+}
+
+/// What a party paid on check-out, itemised for the receipt
+#[derive(Debug, Clone)]
+pub struct GuestBill {
+    pub party: GuestParty,
+    pub room: String,
+    pub nights: u32,
+    pub nightly_rate: i32,
+    pub restaurant: i32,
+    pub extras: i32,
+    /// Handed back to a party that left early unhappy
+    pub refund: i32,
+}
+
+impl GuestBill {
+    pub fn room_charges(&self) -> i32 {
+        self.nights as i32 * self.nightly_rate
+    }
+
+    pub fn total(&self) -> i32 {
+        self.room_charges() + self.restaurant + self.extras - self.refund
+    }
+}
+
+pub struct FinanceEntry {
+    pub day: u32,
+    pub description: String,
+    pub amount: i32,
+    /// Money that never came in, listed so the player sees what it cost; not in the balance
+    pub lost: bool,
+}
+
+/// Recent income and spending, newest last
+#[derive(Resource, Default)]
+pub struct FinanceLedger {
+    pub entries: VecDeque<FinanceEntry>,
+}
+
+impl FinanceLedger {
+    /// Older entries are dropped once the ledger holds this many
+    pub const MAX_ENTRIES: usize = 200;
+
+    pub fn record(&mut self, day: u32, description: impl Into<String>, amount: i32) {
+        self.push(FinanceEntry {
+            day,
+            description: description.into(),
+            amount,
+            lost: false,
+        });
+    }
+
+    /// Notes income the resort missed out on, such as a party walking out before check-in
+    pub fn record_lost(&mut self, day: u32, description: impl Into<String>, amount: i32) {
+        self.push(FinanceEntry {
+            day,
+            description: description.into(),
+            amount,
+            lost: true,
+        });
+    }
+
+    fn push(&mut self, entry: FinanceEntry) {
+        self.entries.push_back(entry);
+        while self.entries.len() > Self::MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketingCampaign {
+    Flyers,
+    OnlineAds,
+    TvSpot,
+}
+
+impl MarketingCampaign {
+    pub fn all() -> [MarketingCampaign; 3] {
+        [
+            MarketingCampaign::Flyers,
+            MarketingCampaign::OnlineAds,
+            MarketingCampaign::TvSpot,
+        ]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            MarketingCampaign::Flyers => "Flyers",
+            MarketingCampaign::OnlineAds => "Online Ads",
+            MarketingCampaign::TvSpot => "TV Spot",
+        }
+    }
+
+    pub fn cost(&self) -> i32 {
+        match self {
+            MarketingCampaign::Flyers => 500,
+            MarketingCampaign::OnlineAds => 1500,
+            MarketingCampaign::TvSpot => 5000,
+        }
+    }
+
+    /// Length of the campaign in game hours
+    pub fn duration_hours(&self) -> f32 {
+        match self {
+            MarketingCampaign::Flyers => 48.0,
+            MarketingCampaign::OnlineAds => 72.0,
+            MarketingCampaign::TvSpot => 120.0,
+        }
+    }
+
+    /// Extra guest arrivals while the campaign runs (0.15 = +15%)
+    pub fn arrival_boost(&self) -> f32 {
+        match self {
+            MarketingCampaign::Flyers => 0.15,
+            MarketingCampaign::OnlineAds => 0.35,
+            MarketingCampaign::TvSpot => 0.75,
+        }
+    }
+}
+
+pub struct ActiveCampaign {
+    pub campaign: MarketingCampaign,
+    pub hours_remaining: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct Marketing {
+    pub active: Vec<ActiveCampaign>,
+}
+
+impl Marketing {
+    /// Pays for and starts a campaign; returns false if it can't be afforded
+    pub fn launch(&mut self, campaign: MarketingCampaign, money: &mut Money) -> bool {
+        if !money.deduct(campaign.cost()) {
+            return false;
+        }
+
+        self.active.push(ActiveCampaign {
+            campaign,
+            hours_remaining: campaign.duration_hours(),
+        });
+        true
+    }
+
+    /// Combined arrival multiplier from every running campaign
+    pub fn arrival_multiplier(&self) -> f32 {
+        1.0 + self
+            .active
+            .iter()
+            .map(|active| active.campaign.arrival_boost())
+            .sum::<f32>()
+    }
+}
+
 pub struct EconomyPlugin;
 
 impl Plugin for EconomyPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Money>();
+        app.init_resource::<Money>()
+            .init_resource::<RoomRates>()
+            .init_resource::<StaffWages>()
+            .init_resource::<Marketing>()
+            .init_resource::<FinanceLedger>()
+            .add_systems(FixedUpdate, tick_marketing_campaigns.in_set(GameplaySet))
+            // After Update's commands are applied, so new and cancelled jobs are counted
+            .add_systems(PostUpdate, sync_reserved_funds.in_set(GameplaySet));
+    }
+}
+
+/// Reserved funds always match the escrow of the construction jobs still open, so a
+/// job that disappears without being built (cancelled, orphaned, cleared by a load)
+/// hands its money back
+fn sync_reserved_funds(mut money: ResMut<Money>, job_query: Query<&ConstructionJob>) {
+    let reserved = if money.infinite {
+        0
+    } else {
+        job_query.iter().map(|job| job.escrow).sum()
+    };
+    if money.reserved != reserved {
+        money.reserved = reserved;
+    }
+}
+
+fn tick_marketing_campaigns(time: Res<Time>, mut marketing: ResMut<Marketing>) {
+    let elapsed = time.delta_secs() / SECONDS_PER_GAME_HOUR;
+    if elapsed <= 0.0 || marketing.active.is_empty() {
+        return;
+    }
+
+    for active in marketing.active.iter_mut() {
+        active.hours_remaining -= elapsed;
     }
+    marketing
+        .active
+        .retain(|active| active.hours_remaining > 0.0);
 }
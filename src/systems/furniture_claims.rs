@@ -0,0 +1,186 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::guests::{plan_guest_activities, random_open_tile};
+use crate::systems::incidents::fixture_within_reach;
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::time_control::GameClock;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Game hours a claim outlasts the use it was made for before it lapses on its own
+const CLAIM_GRACE_HOURS: f32 = 1.0;
+/// Hourly odds that a party relaxing in their room needs the toilet
+const TOILET_CHANCE_PER_HOUR: f64 = 0.3;
+/// Game hours a trip to the toilet takes
+const TOILET_HOURS: f32 = 0.25;
+
+pub struct FurnitureClaimsPlugin;
+
+impl Plugin for FurnitureClaimsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (release_furniture_claims, claim_beds, take_toilet_breaks)
+                .chain()
+                // Heading for a bed replaces wherever bedtime sent the party
+                .after(plan_guest_activities)
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+fn walk_to(commands: &mut Commands, guest: Entity, tile: IVec2, grid_settings: &GridSettings) {
+    let target = grid_to_world(
+        tile,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
+    commands.entity(guest).insert(MovementTarget { target });
+}
+
+/// Claims go once the guest is done with them: when they've moved on to something else,
+/// checked out or gone, or the time the claim was good for has run out. Guests back from
+/// the toilet return to their room.
+fn release_furniture_claims(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    claim_query: Query<(Entity, &FurnitureClaim, Has<Toilet>)>,
+    guest_query: Query<&Guest, (Without<Departing>, Without<WaitingGuest>)>,
+    zone_query: Query<&Zone>,
+) {
+    let mut rng = rand::thread_rng();
+    for (furniture_entity, claim, toilet) in &claim_query {
+        let guest = guest_query.get(claim.claimant).ok();
+        let interrupted = !guest.is_some_and(|guest| guest.activity == claim.activity);
+        if !interrupted && clock.elapsed_hours < claim.expires_hours {
+            continue;
+        }
+        commands.entity(furniture_entity).remove::<FurnitureClaim>();
+
+        let Some(guest) = guest.filter(|_| toilet && !interrupted) else {
+            continue;
+        };
+        let tile = zone_query
+            .get(guest.room)
+            .ok()
+            .and_then(|zone| random_open_tile(zone, &building_map, &mut rng));
+        if let Some(tile) = tile {
+            walk_to(&mut commands, claim.claimant, tile, &grid_settings);
+        }
+    }
+}
+
+/// A party going to bed takes a free bed in their room and lies down in it
+fn claim_beds(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    grid_settings: Res<GridSettings>,
+    guest_query: Query<(Entity, &Guest), (Without<Departing>, Without<WaitingGuest>)>,
+    bed_query: Query<(Entity, &GridPosition), (With<Bed>, Without<FurnitureClaim>)>,
+    claim_query: Query<&FurnitureClaim>,
+    zone_query: Query<&Zone>,
+) {
+    let claimants: HashSet<Entity> = claim_query.iter().map(|claim| claim.claimant).collect();
+    let mut taken = HashSet::new();
+
+    for (guest_entity, guest) in &guest_query {
+        if guest.activity != GuestActivity::Sleeping || claimants.contains(&guest_entity) {
+            continue;
+        }
+        let Ok(zone) = zone_query.get(guest.room) else {
+            continue;
+        };
+        let Some((bed_entity, bed_tile)) = bed_query
+            .iter()
+            .map(|(bed_entity, pos)| (bed_entity, pos.to_ivec2()))
+            .find(|(bed_entity, tile)| !taken.contains(bed_entity) && zone.contains_tile(*tile))
+        else {
+            continue;
+        };
+
+        taken.insert(bed_entity);
+        commands.entity(bed_entity).insert(FurnitureClaim {
+            claimant: guest_entity,
+            activity: GuestActivity::Sleeping,
+            expires_hours: clock.elapsed_hours + guest.hours_left + CLAIM_GRACE_HOURS,
+        });
+        walk_to(&mut commands, guest_entity, bed_tile, &grid_settings);
+    }
+}
+
+/// Now and then a party relaxing in their room needs the toilet, and goes to the nearest
+/// free one in their room or through one of its doors. When every one in reach is taken
+/// they hold on and try again later.
+fn take_toilet_breaks(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    adjacency: Res<RoomAdjacency>,
+    grid_settings: Res<GridSettings>,
+    guest_query: Query<(Entity, &Guest, &Transform), (Without<Departing>, Without<WaitingGuest>)>,
+    toilet_query: Query<(Entity, &GridPosition), (With<Toilet>, Without<FurnitureClaim>)>,
+    claim_query: Query<&FurnitureClaim>,
+    zone_query: Query<&Zone>,
+    mut pending_hours: Local<f32>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    *pending_hours += elapsed;
+    if *pending_hours < 1.0 {
+        return;
+    }
+    *pending_hours -= 1.0;
+
+    let claimants: HashSet<Entity> = claim_query.iter().map(|claim| claim.claimant).collect();
+    let mut taken = HashSet::new();
+    let mut rng = rand::thread_rng();
+
+    for (guest_entity, guest, transform) in &guest_query {
+        if guest.activity != GuestActivity::InRoom || claimants.contains(&guest_entity) {
+            continue;
+        }
+        if !rng.gen_bool(TOILET_CHANCE_PER_HOUR) {
+            continue;
+        }
+        let Ok(zone) = zone_query.get(guest.room) else {
+            continue;
+        };
+
+        let position = transform.translation.truncate();
+        let nearest = toilet_query
+            .iter()
+            .filter(|(toilet_entity, pos)| {
+                !taken.contains(toilet_entity)
+                    && fixture_within_reach(zone, &adjacency, &[pos.to_ivec2()])
+            })
+            .map(|(toilet_entity, pos)| {
+                let tile = pos.to_ivec2();
+                let world = grid_to_world(
+                    tile,
+                    grid_settings.tile_size,
+                    grid_settings.width,
+                    grid_settings.height,
+                );
+                (toilet_entity, tile, world.distance(position))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((toilet_entity, tile, _)) = nearest else {
+            continue;
+        };
+
+        taken.insert(toilet_entity);
+        commands.entity(toilet_entity).insert(FurnitureClaim {
+            claimant: guest_entity,
+            activity: GuestActivity::InRoom,
+            expires_hours: clock.elapsed_hours + TOILET_HOURS,
+        });
+        walk_to(&mut commands, guest_entity, tile, &grid_settings);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::factories::BuildingRules;
+use crate::systems::calendar_events::CalendarEvent;
+use crate::systems::economy::Money;
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Normal,
+    Sandbox,
+    /// Building a starting map to export as a scenario
+    Editor,
+}
+
+impl GameMode {
+    pub fn all() -> [GameMode; 3] {
+        [GameMode::Normal, GameMode::Sandbox, GameMode::Editor]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            GameMode::Normal => "Normal",
+            GameMode::Sandbox => "Sandbox",
+            GameMode::Editor => "Scenario Editor",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            GameMode::Normal => "Start with $10,000 and unlock furniture over time",
+            GameMode::Sandbox => "Infinite money, instant construction, everything unlocked",
+            GameMode::Editor => {
+                "Lay out a starting map, paint the terrain and choose who visits, then export it"
+            }
+        }
+    }
+}
+
+/// Rules for the current game, picked once when a new game starts
+#[derive(Resource, Default)]
+pub struct GameRules {
+    pub mode: GameMode,
+    /// False until the player has picked a mode on the new game screen
+    pub chosen: bool,
+}
+
+impl GameRules {
+    /// Sandbox games and the scenario editor build freely
+    fn free_building(&self) -> bool {
+        matches!(self.mode, GameMode::Sandbox | GameMode::Editor)
+    }
+
+    pub fn infinite_money(&self) -> bool {
+        self.free_building()
+    }
+
+    pub fn instant_construction(&self) -> bool {
+        self.free_building()
+    }
+
+    pub fn all_furniture_unlocked(&self) -> bool {
+        self.free_building()
+    }
+
+    pub fn is_editor(&self) -> bool {
+        self.chosen && self.mode == GameMode::Editor
+    }
+
+    /// Event decorations are only on sale while their event is on
+    pub fn is_furniture_unlocked(&self, furniture_type: FurnitureType, day: u32) -> bool {
+        if self.all_furniture_unlocked() {
+            return true;
+        }
+        let on_sale = furniture_type.event().is_none_or(|id| {
+            CalendarEvent::on_day(day).is_some_and(|event| event.id() == id)
+        });
+        day >= furniture_type.unlock_day() && on_sale
+    }
+
+    pub fn choose(&mut self, mode: GameMode) {
+        self.mode = mode;
+        self.chosen = true;
+    }
+}
+
+pub struct GameRulesPlugin;
+
+impl Plugin for GameRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRules>()
+            .add_systems(Update, apply_game_rules.in_set(GameplaySet));
+    }
+}
+
+// Push the chosen mode into the economy and building rule resources
+fn apply_game_rules(
+    rules: Res<GameRules>,
+    mut money: ResMut<Money>,
+    mut building_rules: ResMut<BuildingRules>,
+) {
+    if !rules.is_changed() {
+        return;
+    }
+
+    money.infinite = rules.infinite_money();
+    building_rules.sandbox = rules.free_building();
+}
@@ -0,0 +1,157 @@
+use crate::systems::app_state::GameplaySet;
+use crate::systems::demand::GuestDemand;
+use crate::systems::economy::Money;
+use crate::systems::game_rules::{GameMode, GameRules};
+use crate::systems::save_load::SaveLoadedEvent;
+use crate::systems::statistics::{sample_daily_stats, StatsHistory};
+use crate::systems::time_control::{GameClock, SpeedOption, TimeSpeed};
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+
+/// How a Normal game is won or lost. Sandbox games never end.
+#[derive(Resource, Debug, Clone)]
+pub struct ScenarioGoals {
+    /// Money below minus this much counts as bankrupt
+    pub bankruptcy_limit: i32,
+    /// Days in a row spent bankrupt before the resort is lost
+    pub bankruptcy_days: u32,
+    /// Star reputation, out of five, that wins the game
+    pub target_reputation: f32,
+}
+
+impl Default for ScenarioGoals {
+    fn default() -> Self {
+        Self {
+            bankruptcy_limit: 5000,
+            bankruptcy_days: 3,
+            target_reputation: 5.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScenarioOutcome {
+    #[default]
+    Playing,
+    Won,
+    Bankrupt,
+    /// The player kept going in sandbox after the game ended
+    Continued,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ScenarioState {
+    pub outcome: ScenarioOutcome,
+    /// Days in a row the resort has ended below the bankruptcy limit
+    pub days_bankrupt: u32,
+}
+
+impl ScenarioState {
+    /// Whether the game-over screen should be up
+    pub fn is_over(&self) -> bool {
+        matches!(self.outcome, ScenarioOutcome::Won | ScenarioOutcome::Bankrupt)
+    }
+}
+
+/// The resort's star reputation out of five, to the nearest half star: the rooms'
+/// average quality, with bad reviews pulling it down
+pub fn resort_reputation(stats: &StatsHistory, demand: &GuestDemand) -> f32 {
+    let average_quality = stats.latest().map_or(0.0, |day| day.average_quality);
+    let stars = average_quality / 4.0 * 5.0 * demand.review_factor;
+    (stars * 2.0).round() / 2.0
+}
+
+pub struct GameStatePlugin;
+
+impl Plugin for GameStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScenarioGoals>()
+            .init_resource::<ScenarioState>()
+            .add_systems(
+                Update,
+                (
+                    reset_scenario_on_load,
+                    evaluate_scenario.after(sample_daily_stats),
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Checks the goals once a day, after that day's statistics are in
+fn evaluate_scenario(
+    clock: Res<GameClock>,
+    rules: Res<GameRules>,
+    goals: Res<ScenarioGoals>,
+    mut state: ResMut<ScenarioState>,
+    money: Res<Money>,
+    stats: Res<StatsHistory>,
+    demand: Res<GuestDemand>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut time_speed: ResMut<TimeSpeed>,
+    mut last_day: Local<Option<u32>>,
+) {
+    let day = clock.day();
+    let Some(previous) = last_day.replace(day) else {
+        return;
+    };
+    if previous == day
+        || !rules.chosen
+        || rules.mode != GameMode::Normal
+        || state.outcome != ScenarioOutcome::Playing
+    {
+        return;
+    }
+
+    if money.amount < -goals.bankruptcy_limit {
+        state.days_bankrupt += 1;
+    } else {
+        state.days_bankrupt = 0;
+    }
+
+    // Each day deep in debt counts down the grace the resort has left
+    let days_left = goals.bankruptcy_days.saturating_sub(state.days_bankrupt);
+    if state.days_bankrupt > 0 && days_left > 0 {
+        notifications.send(Notification {
+            message: localization.format(
+                "budget.grace",
+                &[
+                    ("limit", goals.bankruptcy_limit.to_string()),
+                    ("days", days_left.to_string()),
+                ],
+            ),
+        });
+    }
+
+    let outcome = if days_left == 0 {
+        ScenarioOutcome::Bankrupt
+    } else if resort_reputation(&stats, &demand) >= goals.target_reputation {
+        ScenarioOutcome::Won
+    } else {
+        return;
+    };
+
+    info!("Game over on day {}: {:?}", previous, outcome);
+    state.outcome = outcome;
+    // Stop the clock behind the game-over screen
+    time_speed.set_speed(SpeedOption::Paused);
+}
+
+/// A loaded save is a fresh start on the goals
+fn reset_scenario_on_load(
+    mut events: EventReader<SaveLoadedEvent>,
+    mut state: ResMut<ScenarioState>,
+    mut time_speed: ResMut<TimeSpeed>,
+) {
+    if events.is_empty() {
+        return;
+    }
+    events.clear();
+
+    if state.is_over() {
+        time_speed.set_speed(SpeedOption::Normal);
+    }
+    *state = ScenarioState::default();
+}
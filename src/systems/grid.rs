@@ -1,5 +1,5 @@
+use crate::systems::app_state::GameplaySet;
 use bevy::prelude::*;
-use bevy::sprite::*;
 
 pub const TILE_SIZE: f32 = 16.0;
 pub const GRID_WIDTH: i32 = 200;
@@ -24,67 +24,27 @@ impl Default for GridSettings {
     }
 }
 
-#[derive(Component)]
-pub struct GridLines;
-
 pub struct GridPlugin;
 
 impl Plugin for GridPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GridSettings>()
-            .add_systems(Startup, setup_grid)
-            .add_systems(Update, update_grid_visibility);
+            .add_systems(Update, draw_grid.in_set(GameplaySet));
     }
 }
 
-fn setup_grid(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    grid_settings: Res<GridSettings>,
-) {
-    let tile_size = grid_settings.tile_size;
-    let width = grid_settings.width as f32 * tile_size;
-    let height = grid_settings.height as f32 * tile_size;
-
-    // Create vertical lines
-    for x in 0..=grid_settings.width {
-        let x_pos = x as f32 * tile_size - width / 2.0;
-
-        commands.spawn((
-            Mesh2d(meshes.add(Rectangle::new(1.0, height))),
-            MeshMaterial2d(materials.add(Color::srgba(0.3, 0.3, 0.3, 0.2))),
-            Transform::from_xyz(x_pos, 0.0, 0.0),
-            GridLines,
-        ));
+// Grid lines are immediate-mode gizmos so toggling or resizing the grid never touches the ECS
+fn draw_grid(grid_settings: Res<GridSettings>, mut gizmos: Gizmos) {
+    if !grid_settings.show_grid {
+        return;
     }
 
-    // Create horizontal lines
-    for y in 0..=grid_settings.height {
-        let y_pos = y as f32 * tile_size - height / 2.0;
-
-        commands.spawn((
-            Mesh2d(meshes.add(Rectangle::new(width, 1.0))),
-            MeshMaterial2d(materials.add(Color::srgba(0.3, 0.3, 0.3, 0.2))),
-            Transform::from_xyz(0.0, y_pos, 0.0),
-            GridLines,
-        ));
-    }
-}
-
-fn update_grid_visibility(
-    grid_settings: Res<GridSettings>,
-    mut query: Query<&mut Visibility, With<GridLines>>,
-) {
-    if grid_settings.is_changed() {
-        for mut visibility in &mut query {
-            *visibility = if grid_settings.show_grid {
-                Visibility::Visible
-            } else {
-                Visibility::Hidden
-            };
-        }
-    }
+    gizmos.grid_2d(
+        Isometry2d::IDENTITY,
+        UVec2::new(grid_settings.width as u32, grid_settings.height as u32),
+        Vec2::splat(grid_settings.tile_size),
+        Color::srgba(0.3, 0.3, 0.3, 0.2),
+    );
 }
 
 // Helper functions for grid coordinate conversion
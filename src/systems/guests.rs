@@ -0,0 +1,1052 @@
+use crate::components::*;
+use crate::systems::amenities::{Amenity, AmenityRegistry};
+use crate::systems::app_state::GameplaySet;
+use crate::systems::ascii_renderer::{AsciiLayer, AsciiSprite};
+use crate::systems::building::BuildingMap;
+use crate::systems::demand::GuestDemand;
+use crate::systems::economy::{FinanceLedger, GuestBill, Money, RoomRates};
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::items::{spawn_carried_item, spawn_item};
+use crate::systems::noise::RoomNoise;
+use crate::systems::reviews::{GuestReview, ReviewWriter};
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::safety::BuildingSafety;
+use crate::systems::time_control::{GameClock, HOURS_PER_DAY, SECONDS_PER_GAME_HOUR};
+use crate::systems::transport::ArrivalPoints;
+use crate::ui::{Localization, Notification, UiFeedbackEvent};
+use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use std::collections::HashMap;
+
+const MIN_STAY_NIGHTS: u32 = 1;
+const MAX_STAY_NIGHTS: u32 = 4;
+/// Cleanliness lost for each night a party sleeps in a room
+const CLEANLINESS_WEAR_PER_NIGHT: f32 = 0.15;
+/// Guests in a room dirtier than this may cut their stay short
+const DISSATISFIED_CLEANLINESS: f32 = 0.3;
+/// Parties whose complaints have brought their satisfaction below this may also leave early
+const DISSATISFIED_SATISFACTION: f32 = 0.5;
+/// Nightly odds an unhappy party leaves before their booking ends
+const EARLY_DEPARTURE_CHANCE: f64 = 0.5;
+/// Share of the room charges refunded to a party that leaves early
+const EARLY_DEPARTURE_REFUND: f32 = 0.25;
+
+/// Chance a party leaves some litter behind after an outing other than a meal
+const LITTER_CHANCE: f64 = 0.25;
+pub const GUEST_MOVE_SPEED: f32 = 70.0;
+const GUEST_COLOR: Color = Color::srgb(0.95, 0.6, 0.3);
+/// Parties leave and new ones arrive at this hour each day
+const CHECK_OUT_HOUR: f32 = 11.0;
+/// Guests sleep in their rooms from bedtime until they wake; amenities close at bedtime
+const BEDTIME_HOUR: f32 = 22.0;
+const WAKE_HOUR: f32 = 7.0;
+/// Culinary zones open for breakfast as guests wake; other amenities open later
+const AMENITY_OPENING_HOUR: f32 = 9.0;
+/// Breakfast, lunch and dinner, when hungry guests head for a culinary zone
+const MEAL_TIMES: [(f32, f32); 3] = [(7.0, 10.0), (12.0, 14.0), (18.0, 21.0)];
+const MEAL_HOURS: f32 = 1.0;
+
+/// How fast needs drain per game hour while awake
+const HUNGER_PER_HOUR: f32 = 0.06;
+const ENERGY_PER_HOUR: f32 = 0.04;
+const FUN_PER_HOUR: f32 = 0.05;
+/// How fast the matching activity restores a need per game hour
+const MEAL_HUNGER_PER_HOUR: f32 = 0.6;
+const SLEEP_ENERGY_PER_HOUR: f32 = 0.12;
+const REST_ENERGY_PER_HOUR: f32 = 0.08;
+const AMENITY_FUN_PER_HOUR: f32 = 0.3;
+/// A spa treatment is fun and leaves guests more rested than they arrived
+const SPA_FUN_PER_HOUR: f32 = 0.2;
+const SPA_ENERGY_PER_HOUR: f32 = 0.1;
+/// Even a content party sometimes just stays in
+const REST_BASELINE: f32 = 0.15;
+/// Jitter on activity scores so parties with the same needs don't move in lockstep
+const SCORE_NOISE: f32 = 0.15;
+/// Game hours a new party waits at an unstaffed reception, or for a dirty room to be
+/// cleaned, before walking out
+const CHECK_IN_PATIENCE_HOURS: f32 = 2.0;
+const CLEANING_PATIENCE_HOURS: f32 = 3.0;
+/// Satisfaction a party loses each time one of them walks into a shabby corridor or
+/// other public space
+const SHABBY_PUBLIC_SPACE_PENALTY: f32 = 0.02;
+
+/// Running tally of how check-ins went, so the player can see who they are losing
+#[derive(Resource, Default)]
+pub struct GuestLedger {
+    pub parties_staying: usize,
+    pub guests_staying: u32,
+    pub checked_in_today: usize,
+    /// Parties turned away on the last check-in, indexed by party size - 1
+    pub turned_away_today: [u32; MAX_PARTY_SIZE as usize],
+    /// Parties staying, in [`GuestArchetype::ALL`] order
+    pub archetypes_staying: [u32; GuestArchetype::ALL.len()],
+    pub income_today: i32,
+    /// No guests can arrive until a shuttle stop is built
+    pub no_arrival_point: bool,
+    /// Parties were turned away from free rooms in buildings without enough safe exits
+    pub safety_limited: bool,
+    /// Parties were turned away from free rooms that cost more than they would pay
+    pub over_budget: bool,
+}
+
+/// Parties expected at the next check-in, paced by the rooms that will be bookable then
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct ArrivalForecast {
+    pub parties: f32,
+    /// Free rooms too dirty to let, which draw no arrivals until they're cleaned
+    pub dirty_rooms: u32,
+    /// The next check-in is later today rather than tomorrow
+    pub today: bool,
+}
+
+impl GuestLedger {
+    /// Short advice on what furniture would have kept the turned-away parties
+    pub fn turned_away_summary(&self, localization: &Localization) -> String {
+        if self.no_arrival_point {
+            return localization.t("guests.no_arrival_point").to_string();
+        }
+
+        let singles = self.turned_away_today[0];
+        let couples = self.turned_away_today[1];
+        let families: u32 = self.turned_away_today[2..].iter().sum();
+
+        if singles + couples + families == 0 {
+            return localization.t("guests.none_turned_away").to_string();
+        }
+
+        let mut lines = vec![localization.format(
+            "guests.turned_away",
+            &[
+                ("singles", singles.to_string()),
+                ("couples", couples.to_string()),
+                ("families", families.to_string()),
+            ],
+        )];
+        if couples > 0 {
+            lines.push(localization.t("guests.couples_need_double_bed").to_string());
+        }
+        if families > 0 {
+            lines.push(localization.format(
+                "guests.families_need_room",
+                &[("tiles", FAMILY_ROOM_MIN_TILES.to_string())],
+            ));
+        }
+        if self.safety_limited {
+            lines.push(localization.t("guests.safety_limited").to_string());
+        }
+        if self.over_budget {
+            lines.push(localization.t("guests.over_budget").to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+/// A party paid and left; carries the itemised bill for the receipt popup
+#[derive(Event, Debug, Clone)]
+pub struct GuestCheckedOut {
+    pub bill: GuestBill,
+}
+
+/// What moved a party's satisfaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SatisfactionCause {
+    /// They reached a room as good as they expected, or better
+    GreatRoom,
+    /// They reached a room worse than they expected
+    DisappointingRoom,
+    Complaint(IncidentKind),
+    /// Nobody came to sort out their complaint in time
+    ComplaintIgnored,
+    RoomServiceDelivered,
+    RoomServiceLate,
+    /// Their room was marked for deconstruction while they stay in it
+    Renovation,
+    /// Pests at the amenity they are visiting
+    Pests(PestKind),
+    /// They walked into a shabby corridor or other public space
+    ShabbyPublicSpace,
+}
+
+/// A party's satisfaction went up or down for a reason worth showing. `guest` is the
+/// one it happened to, or None when it's the whole party in `room`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SatisfactionEvent {
+    pub room: Entity,
+    pub guest: Option<Entity>,
+    pub cause: SatisfactionCause,
+}
+
+pub struct GuestsPlugin;
+
+impl Plugin for GuestsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GuestLedger>()
+            .init_resource::<ArrivalForecast>()
+            .add_event::<GuestCheckedOut>()
+            .add_event::<SatisfactionEvent>()
+            .add_systems(FixedUpdate, decay_guest_needs.in_set(GameplaySet))
+            .add_systems(
+                Update,
+                (
+                    run_daily_check_in,
+                    send_departed_guests_home,
+                    serve_waiting_guests,
+                    plan_guest_activities,
+                    notice_public_spaces,
+                    forecast_arrivals,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Everything a departing party owes; unhappy early leavers get part of the room back
+fn bill_for(booking: &Booking, zone: &Zone, left_early: bool) -> GuestBill {
+    let mut bill = GuestBill {
+        party: booking.party,
+        room: zone.name.clone(),
+        nights: booking.nights_stayed,
+        nightly_rate: booking.nightly_rate,
+        restaurant: booking.restaurant_charges,
+        extras: booking.extra_charges,
+        refund: 0,
+    };
+    if left_early {
+        bill.refund = (bill.room_charges() as f32 * EARLY_DEPARTURE_REFUND).round() as i32;
+    }
+    bill
+}
+
+/// Stars a departing party gives: never more than two when they leave unhappy, and
+/// never fewer than three when they don't
+fn review_stars(satisfaction: f32, unhappy: bool) -> u8 {
+    let stars = (satisfaction * 5.0).round().clamp(1.0, 5.0) as u8;
+    if unhappy {
+        stars.min(2)
+    } else {
+        stars.max(3)
+    }
+}
+
+/// Middle of a zone in world space, for popups about the whole room and walking into it
+pub fn zone_center(zone: &Zone, grid_settings: &GridSettings) -> Vec2 {
+    let sum = zone.tiles.iter().fold(Vec2::ZERO, |sum, &tile| {
+        sum + grid_to_world(
+            tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+    });
+    sum / zone.tiles.len().max(1) as f32
+}
+
+/// Whether a free room can take new guests: a furnished bedroom that isn't blocked or
+/// open to view, and clean enough that the party wouldn't complain on arrival
+fn is_bookable(zone: &Zone, cleanliness: f32, blocked: bool) -> bool {
+    zone.zone_type == ZoneType::GuestBedroom
+        && zone.quality != ZoneQuality::None
+        && !blocked
+        && cleanliness >= DISSATISFIED_CLEANLINESS
+}
+
+/// Expected arrivals follow the same occupancy model the economy panel shows, one
+/// room's worth at a time, so an unfinished resort only draws the guests it can serve
+fn expected_arrivals<'a>(
+    rooms: impl Iterator<Item = &'a Zone>,
+    demand: &GuestDemand,
+    rates: &RoomRates,
+) -> f32 {
+    rooms
+        .map(|zone| demand.expected_occupancy(zone.quality, rates))
+        .sum()
+}
+
+/// Once a day at check-out time: every staying party has spent another night,
+/// departing parties pay their bill and check out, and new arrivals are matched to
+/// free bedrooms that fit their whole party. Blocked rooms keep their current guests
+/// but take no new ones, and dirty rooms wait for housekeeping before they're let
+/// again. New parties turn up on the next shuttle, so nobody is booked until there is
+/// a shuttle stop.
+fn run_daily_check_in(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    arrival_points: Res<ArrivalPoints>,
+    demand: Res<GuestDemand>,
+    rates: Res<RoomRates>,
+    grid_settings: Res<GridSettings>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<GuestLedger>,
+    mut finance: ResMut<FinanceLedger>,
+    mut reviews: ReviewWriter,
+    mut checkouts: EventWriter<GuestCheckedOut>,
+    mut feedback: EventWriter<UiFeedbackEvent>,
+    localization: Res<Localization>,
+    safety: Res<BuildingSafety>,
+    mut zone_query: Query<(
+        Entity,
+        &Zone,
+        Option<&mut Booking>,
+        Option<&mut Cleanliness>,
+        Has<RoomBlocked>,
+        Has<PrivacyIssue>,
+    )>,
+    mut last_day: Local<Option<u32>>,
+) {
+    // Before check-out time it is still the previous day's turnover
+    let day = if clock.hour() >= CHECK_OUT_HOUR {
+        clock.day()
+    } else {
+        clock.day() - 1
+    };
+    if *last_day == Some(day) {
+        return;
+    }
+    let first_run = last_day.is_none();
+    *last_day = Some(day);
+    if first_run {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut income = 0;
+    let mut parties_staying = 0;
+    let mut guests_staying = 0;
+    let mut archetypes_staying = [0; GuestArchetype::ALL.len()];
+    let mut free_rooms = Vec::new();
+    // Guests staying on in each building, which its safety rating caps
+    let mut building_guests: HashMap<usize, u32> = HashMap::new();
+
+    for (entity, zone, booking, cleanliness, blocked, not_private) in &mut zone_query {
+        // Rooms failing privacy can't be let, just like blocked ones
+        let blocked = blocked || not_private;
+        if let Some(mut booking) = booking {
+            booking.nights_stayed += 1;
+            booking.nights_remaining = booking.nights_remaining.saturating_sub(1);
+            let mut cleanliness_level = 1.0;
+            if let Some(mut cleanliness) = cleanliness {
+                cleanliness.level = (cleanliness.level - CLEANLINESS_WEAR_PER_NIGHT).max(0.0);
+                cleanliness_level = cleanliness.level;
+            }
+
+            let unhappy = cleanliness_level < DISSATISFIED_CLEANLINESS
+                || booking.satisfaction < DISSATISFIED_SATISFACTION;
+            let left_early = booking.nights_remaining > 0
+                && unhappy
+                && rng.gen_bool(EARLY_DEPARTURE_CHANCE);
+            if booking.nights_remaining == 0 || left_early {
+                let bill = bill_for(&booking, zone, left_early);
+                let total = bill.total();
+                info!("{} checked out of {}: ${}", booking.party.name(), zone.name, total);
+                income += total;
+                finance.record(
+                    day,
+                    localization.format(
+                        "receipt.title",
+                        &[
+                            (
+                                "party",
+                                localization.term("party", booking.party.name()).to_string(),
+                            ),
+                            ("room", zone.name.clone()),
+                        ],
+                    ),
+                    total,
+                );
+                let stars = review_stars(booking.satisfaction, unhappy);
+                let mut review = GuestReview::of_stay(day, entity, zone, &booking, stars);
+                if cleanliness_level < DISSATISFIED_CLEANLINESS {
+                    review = review.with_complaint(ReviewComplaint::DirtyRoom);
+                }
+                reviews.write(review);
+
+                feedback.send(UiFeedbackEvent::Earned {
+                    amount: total,
+                    world_pos: zone_center(zone, &grid_settings),
+                });
+                checkouts.send(GuestCheckedOut { bill });
+
+                commands
+                    .entity(entity)
+                    .remove::<(Booking, AwaitingShuttle)>();
+                if is_bookable(zone, cleanliness_level, blocked) {
+                    free_rooms.push((entity, zone));
+                }
+            } else {
+                parties_staying += 1;
+                guests_staying += booking.party.size as u32;
+                archetypes_staying[booking.party.archetype as usize] += 1;
+                if let Some(building) = safety.building_of(entity) {
+                    *building_guests.entry(building).or_default() += booking.party.size as u32;
+                }
+            }
+        } else {
+            let cleanliness_level = cleanliness.map_or(1.0, |cleanliness| cleanliness.level);
+            if is_bookable(zone, cleanliness_level, blocked) {
+                free_rooms.push((entity, zone));
+            }
+        }
+    }
+
+    let expected = expected_arrivals(free_rooms.iter().map(|(_, zone)| *zone), &demand, &rates);
+    let mut arrivals = expected.floor() as usize;
+    if rng.gen::<f32>() < expected.fract() {
+        arrivals += 1;
+    }
+    ledger.no_arrival_point = arrival_points.is_empty();
+    if ledger.no_arrival_point {
+        arrivals = 0;
+    }
+
+    money.add(income);
+    ledger.income_today = income;
+
+    // Who turns up follows the mix the resort's reputation and prices attract
+    let Ok(archetype_distribution) = WeightedIndex::new(demand.archetype_mix) else {
+        return;
+    };
+
+    let mut checked_in = 0;
+    let mut turned_away = [0; MAX_PARTY_SIZE as usize];
+    ledger.safety_limited = false;
+    ledger.over_budget = false;
+
+    for _ in 0..arrivals {
+        let archetype = GuestArchetype::ALL[archetype_distribution.sample(&mut rng)];
+        let Ok(size_distribution) = WeightedIndex::new(archetype.size_weights()) else {
+            continue;
+        };
+        let party = GuestParty::new(archetype, size_distribution.sample(&mut rng) as u8 + 1);
+        let within_safe_limit = |room: Entity, building_guests: &HashMap<usize, u32>| {
+            safety.building_of(room).is_none_or(|building| {
+                let staying = building_guests.get(&building).copied().unwrap_or(0);
+                staying + party.size as u32 <= safety.buildings[building].guest_limit
+            })
+        };
+
+        // Best fit: the smallest free room that sleeps everyone, so big rooms stay open,
+        // preferring one as good as the party expects
+        let hosts: Vec<usize> = free_rooms
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, zone))| zone.can_host(party))
+            .map(|(index, _)| index)
+            .collect();
+        let affordable: Vec<usize> = hosts
+            .iter()
+            .copied()
+            .filter(|index| {
+                rates.rate(free_rooms[*index].1.quality) <= party.archetype.nightly_budget()
+            })
+            .collect();
+        let expected_stars = party.archetype.expected_quality().stars();
+        let best = affordable
+            .iter()
+            .copied()
+            .filter(|index| within_safe_limit(free_rooms[*index].0, &building_guests))
+            .min_by_key(|index| {
+                let (_, zone) = free_rooms[*index];
+                (zone.quality.stars() < expected_stars, zone.capacity)
+            });
+        if affordable.is_empty() && !hosts.is_empty() {
+            ledger.over_budget = true;
+        } else if best.is_none() && !affordable.is_empty() {
+            ledger.safety_limited = true;
+        }
+
+        match best {
+            Some(index) => {
+                let (entity, zone) = free_rooms.swap_remove(index);
+                if let Some(building) = safety.building_of(entity) {
+                    *building_guests.entry(building).or_default() += party.size as u32;
+                }
+                info!("{} checked into {}", party.name(), zone.name);
+                commands.entity(entity).insert((
+                    Booking::new(
+                        party,
+                        rng.gen_range(MIN_STAY_NIGHTS..=MAX_STAY_NIGHTS),
+                        rates.rate(zone.quality),
+                        zone.quality,
+                    ),
+                    AwaitingShuttle,
+                ));
+                checked_in += 1;
+                parties_staying += 1;
+                guests_staying += party.size as u32;
+                archetypes_staying[archetype as usize] += 1;
+            }
+            None => {
+                turned_away[party.size as usize - 1] += 1;
+            }
+        }
+    }
+
+    ledger.checked_in_today = checked_in;
+    ledger.turned_away_today = turned_away;
+    ledger.parties_staying = parties_staying;
+    ledger.guests_staying = guests_staying;
+    ledger.archetypes_staying = archetypes_staying;
+}
+
+/// Keeps the arrival forecast in step with the rooms: those free now, and those whose
+/// party is due to leave at the next check-in after one more night's wear. Rooms that
+/// get cleaned before then will draw more guests than this shows.
+fn forecast_arrivals(
+    clock: Res<GameClock>,
+    arrival_points: Res<ArrivalPoints>,
+    demand: Res<GuestDemand>,
+    rates: Res<RoomRates>,
+    mut forecast: ResMut<ArrivalForecast>,
+    zone_query: Query<(
+        &Zone,
+        Option<&Booking>,
+        Option<&Cleanliness>,
+        Has<RoomBlocked>,
+        Has<PrivacyIssue>,
+    )>,
+) {
+    let mut rooms = Vec::new();
+    let mut dirty_rooms = 0;
+    for (zone, booking, cleanliness, blocked, not_private) in &zone_query {
+        let mut cleanliness_level = cleanliness.map_or(1.0, |cleanliness| cleanliness.level);
+        match booking {
+            Some(booking) if booking.nights_remaining <= 1 => {
+                cleanliness_level = (cleanliness_level - CLEANLINESS_WEAR_PER_NIGHT).max(0.0);
+            }
+            Some(_) => continue,
+            None => {}
+        }
+
+        if is_bookable(zone, cleanliness_level, blocked || not_private) {
+            rooms.push(zone);
+        } else if is_bookable(zone, 1.0, blocked || not_private) {
+            dirty_rooms += 1;
+        }
+    }
+
+    let parties = if arrival_points.is_empty() {
+        0.0
+    } else {
+        expected_arrivals(rooms.into_iter(), &demand, &rates)
+    };
+    forecast.set_if_neq(ArrivalForecast {
+        parties,
+        dirty_rooms,
+        today: clock.hour() < CHECK_OUT_HOUR,
+    });
+}
+
+/// A walkable tile inside a zone for a guest to head to
+pub fn random_open_tile(
+    zone: &Zone,
+    building_map: &BuildingMap,
+    rng: &mut impl Rng,
+) -> Option<IVec2> {
+    zone.tiles
+        .iter()
+        .filter(|tile| !building_map.occupied.contains(tile))
+        .choose(rng)
+        .copied()
+}
+
+/// Spawns a party at `position` (the shuttle stop) and sends them on to `target`
+pub fn spawn_guest(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    room: Entity,
+    party: GuestParty,
+    position: Vec2,
+    target: Vec2,
+) -> Entity {
+    let mut rng = rand::thread_rng();
+
+    let guest = restore_guest(
+        commands,
+        meshes,
+        materials,
+        Guest {
+            party,
+            room,
+            move_speed: GUEST_MOVE_SPEED,
+            activity: GuestActivity::InRoom,
+            needs: GuestNeeds::default(),
+            hours_left: rng.gen_range(1.0..3.0),
+        },
+        position,
+    );
+    commands.entity(guest).insert(MovementTarget { target });
+
+    // Every party arrives with bags to drop off in their room
+    spawn_carried_item(
+        commands,
+        Item::owned_by(ItemKind::Luggage, guest),
+        guest,
+        position,
+    );
+    guest
+}
+
+/// Puts a party on the map at `position` as they are, standing still. Luggage isn't
+/// saved, so parties brought back by a load come without it.
+pub fn restore_guest(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    guest: Guest,
+    position: Vec2,
+) -> Entity {
+    commands
+        .spawn((
+            Mesh2d(meshes.add(Circle::new(TILE_SIZE * 0.6))),
+            MeshMaterial2d(materials.add(GUEST_COLOR)),
+            Transform::from_translation(position.extend(9.0)),
+            GridPosition::new(0, 0),
+            guest,
+            AsciiSprite::new('g', GUEST_COLOR, AsciiLayer::Pawn),
+        ))
+        .id()
+}
+
+/// New arrivals check in at reception, where there is one, and can't move in until their
+/// room is clean. Kept waiting too long, they cancel the whole stay and leave a bad
+/// review; the stay they would have paid for goes in the ledger as lost. The desks do
+/// the checking in themselves, in the reception systems.
+fn serve_waiting_guests(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    localization: Res<Localization>,
+    mut demand: ResMut<GuestDemand>,
+    mut finance: ResMut<FinanceLedger>,
+    mut reviews: ReviewWriter,
+    mut notifications: EventWriter<Notification>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut guest_query: Query<(Entity, &Guest, &mut WaitingGuest), Without<Departing>>,
+    console_query: Query<(), With<ReceptionConsole>>,
+    zone_query: Query<(&Zone, Option<&Cleanliness>, Option<&Booking>)>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    let mut rng = rand::thread_rng();
+
+    for (entity, guest, mut waiting) in &mut guest_query {
+        // A room that's gone or no longer booked sends the party home instead
+        let Ok((zone, cleanliness, Some(booking))) = zone_query.get(guest.room) else {
+            continue;
+        };
+        let room_ready =
+            cleanliness.is_none_or(|cleanliness| cleanliness.level >= DISSATISFIED_CLEANLINESS);
+
+        let served = match waiting.reason {
+            // Without a reception desk anywhere, parties let themselves in
+            WaitReason::CheckIn => console_query.is_empty(),
+            WaitReason::Cleaning => room_ready,
+        };
+
+        if served && !room_ready {
+            *waiting = WaitingGuest::new(WaitReason::Cleaning);
+            continue;
+        }
+        if served {
+            commands.entity(entity).remove::<WaitingGuest>();
+            let expected_stars = guest.party.archetype.expected_quality().stars();
+            satisfaction_events.send(SatisfactionEvent {
+                room: guest.room,
+                guest: Some(entity),
+                cause: if zone.quality.stars() >= expected_stars {
+                    SatisfactionCause::GreatRoom
+                } else {
+                    SatisfactionCause::DisappointingRoom
+                },
+            });
+            if let Some(tile) = random_open_tile(zone, &building_map, &mut rng) {
+                let target = grid_to_world(
+                    tile,
+                    grid_settings.tile_size,
+                    grid_settings.width,
+                    grid_settings.height,
+                );
+                commands.entity(entity).insert(MovementTarget { target });
+            }
+            continue;
+        }
+
+        waiting.hours_waited += elapsed;
+        let (patience, key, complaint) = match waiting.reason {
+            WaitReason::CheckIn => (
+                CHECK_IN_PATIENCE_HOURS,
+                "guests.left_no_check_in",
+                ReviewComplaint::SlowCheckIn,
+            ),
+            WaitReason::Cleaning => (
+                CLEANING_PATIENCE_HOURS,
+                "guests.left_room_dirty",
+                ReviewComplaint::DirtyRoom,
+            ),
+        };
+        if waiting.hours_waited < patience {
+            continue;
+        }
+
+        let party = localization.term("party", guest.party.name()).to_string();
+        let lost = booking.nightly_rate * booking.nights_remaining as i32;
+        info!(
+            "{} walked out of {} after {:.1}h waiting, losing ${}",
+            guest.party.name(),
+            zone.name,
+            waiting.hours_waited,
+            lost
+        );
+        finance.record_lost(
+            clock.day(),
+            localization.format(
+                "guests.walked_out",
+                &[("party", party.clone()), ("room", zone.name.clone())],
+            ),
+            lost,
+        );
+        demand.add_bad_review();
+        reviews.write(
+            GuestReview::of_stay(clock.day(), guest.room, zone, booking, 1)
+                .with_complaint(complaint),
+        );
+        notifications.send(Notification {
+            message: localization.format(key, &[("party", party), ("room", zone.name.clone())]),
+        });
+
+        // With the booking gone, send_departed_guests_home walks them back to the shuttle
+        commands.entity(guest.room).remove::<Booking>();
+        commands.entity(entity).remove::<WaitingGuest>();
+    }
+}
+
+// Once their room no longer holds their booking, guests walk to the nearest shuttle
+// stop to wait for a ride home. With no stop left to leave from they just go.
+fn send_departed_guests_home(
+    mut commands: Commands,
+    arrival_points: Res<ArrivalPoints>,
+    guest_query: Query<(Entity, &Guest, &Transform, Has<Departing>)>,
+    booking_query: Query<(), With<Booking>>,
+) {
+    for (entity, guest, transform, departing) in &guest_query {
+        if booking_query.get(guest.room).is_ok() || (departing && !arrival_points.is_empty()) {
+            continue;
+        }
+
+        match arrival_points.nearest(transform.translation.truncate()) {
+            Some(stop) => {
+                commands
+                    .entity(entity)
+                    .remove::<PawnPath>()
+                    .insert((Departing, MovementTarget { target: stop }));
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+}
+
+/// Guests walking from one room into a public space that rates poorly think a little
+/// less of the resort, once for each time they go in
+fn notice_public_spaces(
+    adjacency: Res<RoomAdjacency>,
+    guest_query: Query<(Entity, &Guest, &GridPosition)>,
+    public_space_query: Query<&PublicSpace>,
+    mut booking_query: Query<&mut Booking>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut last_tiles: Local<HashMap<Entity, IVec2>>,
+) {
+    let mut tiles = HashMap::new();
+    for (entity, guest, grid_pos) in &guest_query {
+        let tile = grid_pos.to_ivec2();
+        tiles.insert(entity, tile);
+
+        let Some(last_tile) = last_tiles.get(&entity).copied() else {
+            continue;
+        };
+        let room = adjacency.room_at(tile);
+        if room.is_none() || room == adjacency.room_at(last_tile) {
+            continue;
+        }
+        let shabby = room
+            .and_then(|room| public_space_query.get(room).ok())
+            .is_some_and(|space| space.is_shabby());
+        if !shabby {
+            continue;
+        }
+        let Ok(mut booking) = booking_query.get_mut(guest.room) else {
+            continue;
+        };
+
+        booking.satisfaction = (booking.satisfaction - SHABBY_PUBLIC_SPACE_PENALTY).max(0.0);
+        satisfaction_events.send(SatisfactionEvent {
+            room: guest.room,
+            guest: Some(entity),
+            cause: SatisfactionCause::ShabbyPublicSpace,
+        });
+    }
+    *last_tiles = tiles;
+}
+
+fn is_meal_time(hour: f32) -> bool {
+    MEAL_TIMES
+        .iter()
+        .any(|(start, end)| (*start..*end).contains(&hour))
+}
+
+fn is_open(zone_type: ZoneType, hour: f32) -> bool {
+    let opening = if zone_type == ZoneType::Culinary {
+        WAKE_HOUR
+    } else {
+        AMENITY_OPENING_HOUR
+    };
+    (opening..BEDTIME_HOUR).contains(&hour)
+}
+
+/// Needs drain while awake and the current activity tops up the one it serves. A noisy
+/// room lowers `sleep_quality`, and with it how much a night's sleep restores.
+fn update_needs(
+    needs: &mut GuestNeeds,
+    activity: GuestActivity,
+    visiting: Option<ZoneType>,
+    sleep_quality: f32,
+    hours: f32,
+) {
+    needs.hunger -= HUNGER_PER_HOUR * hours;
+    needs.fun -= FUN_PER_HOUR * hours;
+
+    match (activity, visiting) {
+        (GuestActivity::Sleeping, _) => {
+            needs.energy += SLEEP_ENERGY_PER_HOUR * sleep_quality * hours
+        }
+        (GuestActivity::InRoom, _) => {
+            needs.energy += (REST_ENERGY_PER_HOUR - ENERGY_PER_HOUR) * hours
+        }
+        (GuestActivity::Visiting(_), Some(ZoneType::Culinary)) => {
+            needs.hunger += MEAL_HUNGER_PER_HOUR * hours;
+            needs.energy -= ENERGY_PER_HOUR * hours;
+        }
+        (GuestActivity::Visiting(_), Some(ZoneType::Spa)) => {
+            needs.fun += SPA_FUN_PER_HOUR * hours;
+            needs.energy += SPA_ENERGY_PER_HOUR * hours;
+        }
+        (GuestActivity::Visiting(_), _) => {
+            needs.fun += AMENITY_FUN_PER_HOUR * hours;
+            needs.energy -= ENERGY_PER_HOUR * hours;
+        }
+    }
+
+    needs.hunger = needs.hunger.clamp(0.0, 1.0);
+    needs.energy = needs.energy.clamp(0.0, 1.0);
+    needs.fun = needs.fun.clamp(0.0, 1.0);
+}
+
+/// Needs run down a fixed step at a time, so they come out the same at any speed and
+/// frame rate
+fn decay_guest_needs(
+    time: Res<Time>,
+    noise: Res<RoomNoise>,
+    mut guest_query: Query<&mut Guest, (Without<Departing>, Without<WaitingGuest>)>,
+    zone_query: Query<&Zone>,
+) {
+    let hours = time.delta_secs() / SECONDS_PER_GAME_HOUR;
+    if hours <= 0.0 {
+        return;
+    }
+
+    for mut guest in &mut guest_query {
+        let visiting = match guest.activity {
+            GuestActivity::Visiting(zone) => zone_query.get(zone).ok().map(|zone| zone.zone_type),
+            _ => None,
+        };
+        let activity = guest.activity;
+        let sleep_quality = noise.sleep_quality(guest.room);
+        update_needs(&mut guest.needs, activity, visiting, sleep_quality, hours);
+    }
+}
+
+/// Utility AI for a waking party: eating, an amenity, the spa and resting in the room
+/// are each scored by how badly the party needs them, and the best open option wins.
+/// The spa appeals to parties that are both bored and tired, and the pool draws more
+/// or fewer of them as weather and season make `pool_usage` rise and fall.
+/// Returns the amenity to visit, or None to stay in.
+fn choose_outing(
+    needs: &GuestNeeds,
+    preferences: OutingPreferences,
+    hour: f32,
+    pool_usage: f32,
+    from: Vec2,
+    registry: &AmenityRegistry,
+    rng: &mut impl Rng,
+) -> Option<Amenity> {
+    let mut noise = || rng.gen_range(0.0..SCORE_NOISE);
+    let meal_weight = if is_meal_time(hour) { 2.0 } else { 0.5 };
+    // Each kind of party leans towards the outings it enjoys most
+    let meal_score = (1.0 - needs.hunger) * meal_weight * preferences.meals + noise();
+    let fun_score = (1.0 - needs.fun) * preferences.leisure + noise();
+    let rest_score = (1.0 - needs.energy) * 0.8 + REST_BASELINE + noise();
+    let spa_score =
+        ((1.0 - needs.fun) * 0.5 + (1.0 - needs.energy) * 0.8) * preferences.spa + noise();
+
+    let meal = registry.choose(from, rng, |amenity| {
+        amenity.zone_type == ZoneType::Culinary && is_open(amenity.zone_type, hour)
+    });
+    let outing = registry.choose_weighted(from, rng, |amenity| match amenity.zone_type {
+        _ if !is_open(amenity.zone_type, hour) => 0.0,
+        ZoneType::Culinary | ZoneType::Spa => 0.0,
+        // Tubs make pool rooms
+        ZoneType::Relaxation => pool_usage,
+        _ => 1.0,
+    });
+    let spa = registry.choose(from, rng, |amenity| {
+        amenity.zone_type == ZoneType::Spa && is_open(amenity.zone_type, hour)
+    });
+
+    [(meal, meal_score), (outing, fun_score), (spa, spa_score)]
+        .into_iter()
+        .filter_map(|(amenity, score)| amenity.map(|amenity| (amenity, score)))
+        .filter(|(_, score)| *score > rest_score)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(amenity, _)| amenity.clone())
+}
+
+/// Guests follow the clock: asleep in their rooms overnight, and through the day doing
+/// whatever their needs call for, from meals at culinary zones to other amenities
+pub fn plan_guest_activities(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    demand: Res<GuestDemand>,
+    registry: Res<AmenityRegistry>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    mut guest_query: Query<
+        (Entity, &mut Guest, &Transform),
+        (Without<Departing>, Without<WaitingGuest>),
+    >,
+    mut zone_query: Query<(&Zone, Option<&mut ZoneVisitors>)>,
+    mut booking_query: Query<&mut Booking>,
+    mut last_hours: Local<Option<f32>>,
+    mut last_day: Local<u32>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+    if elapsed <= 0.0 {
+        return;
+    }
+
+    if *last_day != clock.day() {
+        *last_day = clock.day();
+        for (_, visitors) in &mut zone_query {
+            if let Some(mut visitors) = visitors {
+                visitors.visits_today = 0;
+            }
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    let hour = clock.hour();
+    let bedtime = !(WAKE_HOUR..BEDTIME_HOUR).contains(&hour);
+
+    for (entity, mut guest, transform) in &mut guest_query {
+        let visiting = match guest.activity {
+            GuestActivity::Visiting(zone) => {
+                zone_query.get(zone).ok().map(|(zone, _)| zone.zone_type)
+            }
+            _ => None,
+        };
+        // Bedtime closes the amenities and sends everyone still up to sleep
+        let sent_to_bed = bedtime && guest.activity != GuestActivity::Sleeping;
+        guest.hours_left -= elapsed;
+        if guest.hours_left > 0.0 && !sent_to_bed {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let outing = if bedtime {
+            None
+        } else {
+            choose_outing(
+                &guest.needs,
+                guest.party.archetype.outing_preferences(),
+                hour,
+                demand.pool_usage,
+                position,
+                &registry,
+                &mut rng,
+            )
+        };
+
+        let (activity, destination, hours) = match &outing {
+            Some(amenity) if amenity.zone_type == ZoneType::Culinary => {
+                (GuestActivity::Visiting(amenity.zone), amenity.zone, MEAL_HOURS)
+            }
+            Some(amenity) => (
+                GuestActivity::Visiting(amenity.zone),
+                amenity.zone,
+                rng.gen_range(1.0..3.0),
+            ),
+            // Asleep until morning
+            None if bedtime => (
+                GuestActivity::Sleeping,
+                guest.room,
+                (WAKE_HOUR - hour).rem_euclid(HOURS_PER_DAY),
+            ),
+            None => (GuestActivity::InRoom, guest.room, rng.gen_range(1.0..2.0)),
+        };
+
+        guest.hours_left = hours;
+        if activity == guest.activity {
+            continue;
+        }
+        // Diners leave their plates behind, and other outings now and then some litter
+        let litter = match visiting {
+            Some(ZoneType::Culinary) => Some(ItemKind::FoodPlate),
+            Some(_) => rng.gen_bool(LITTER_CHANCE).then_some(ItemKind::Trash),
+            None => None,
+        };
+        if let Some(kind) = litter {
+            spawn_item(&mut commands, Item::new(kind), position);
+        }
+        // Falling asleep or waking up happens without leaving the room
+        let moving = !(activity.in_room() && guest.activity.in_room());
+        guest.activity = activity;
+        if !moving {
+            continue;
+        }
+
+        let Ok((zone, visitors)) = zone_query.get_mut(destination) else {
+            continue;
+        };
+        if let Some(mut visitors) = visitors {
+            visitors.visits_today += 1;
+            visitors.visits_total += 1;
+        }
+        if let Some(amenity) = outing {
+            if let Ok(mut booking) = booking_query.get_mut(guest.room) {
+                booking.charge_visit(amenity.zone_type);
+            }
+            info!(
+                "{} heading to {} ({})",
+                guest.party.name(),
+                zone.name,
+                amenity.zone_type.name()
+            );
+        }
+
+        if let Some(tile) = random_open_tile(zone, &building_map, &mut rng) {
+            let target = grid_to_world(
+                tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            );
+            commands.entity(entity).insert(MovementTarget { target });
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::balance::Balance;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::guests::{random_open_tile, zone_center};
+use crate::systems::job_priorities::JobPriorities;
+use crate::systems::staff::{start_staff_breaks, JobFinishedEvent};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Bedrooms at or below this cleanliness go on the cleaning rota
+const NEEDS_CLEANING: f32 = 0.7;
+
+pub struct HousekeepingPlugin;
+
+impl Plugin for HousekeepingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                post_cleaning_jobs,
+                assign_cleaners.after(start_staff_breaks),
+                finish_cleaning_jobs,
+            )
+                .chain()
+                .in_set(GameplaySet),
+        )
+        // Cleaning is work like repairs, so it advances in fixed ticks too
+        .add_systems(FixedUpdate, clean_rooms.in_set(GameplaySet));
+    }
+}
+
+/// Every bedroom that has worn down gets one cleaning job, unless staff are kept out of it
+fn post_cleaning_jobs(
+    mut commands: Commands,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    zone_query: Query<(Entity, &Zone, &Cleanliness)>,
+    job_query: Query<&CleaningJob>,
+) {
+    let listed: HashSet<Entity> = job_query.iter().map(|job| job.room).collect();
+    let mut rng = rand::thread_rng();
+
+    for (room, zone, cleanliness) in &zone_query {
+        if zone.zone_type != ZoneType::GuestBedroom
+            || !zone.access.admits_staff()
+            || cleanliness.level > NEEDS_CLEANING
+            || listed.contains(&room)
+        {
+            continue;
+        }
+
+        let spot = random_open_tile(zone, &building_map, &mut rng)
+            .map(|tile| {
+                grid_to_world(
+                    tile,
+                    grid_settings.tile_size,
+                    grid_settings.width,
+                    grid_settings.height,
+                )
+            })
+            .unwrap_or_else(|| zone_center(zone, &grid_settings));
+        commands.spawn(CleaningJob::new(room, spot));
+    }
+}
+
+/// Idle workers with cleaning enabled take the dirtiest room first
+fn assign_cleaners(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &WorkAssignments,
+            Option<&UnreachableJob>,
+        ),
+        (
+            With<Pawn>,
+            Without<OnBreak>,
+            Without<InTraining>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+        ),
+    >,
+    mut job_query: Query<(Entity, &mut CleaningJob)>,
+    cleanliness_query: Query<&Cleanliness>,
+    priorities: Res<JobPriorities>,
+) {
+    for (pawn_entity, pawn_transform, mut current_job, work_assignments, unreachable) in
+        &mut pawn_query
+    {
+        if current_job.job_id.is_some()
+            || !work_assignments.can_do_work(WorkType::Cleaning)
+            || priorities.passes_over(work_assignments, WorkType::Cleaning)
+        {
+            continue;
+        }
+
+        // Dirtiest first, and the nearest of equally dirty rooms
+        let pawn_pos = pawn_transform.translation.truncate();
+        let chosen = job_query
+            .iter()
+            .filter(|(job_entity, job)| {
+                job.assigned_pawn.is_none()
+                    && unreachable.is_none_or(|unreachable| unreachable.job != *job_entity)
+            })
+            .filter_map(|(job_entity, job)| {
+                let level = cleanliness_query.get(job.room).ok()?.level;
+                Some((job_entity, level, job.spot.distance(pawn_pos)))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+            .map(|(job_entity, _, _)| job_entity);
+
+        let Some(job_entity) = chosen else {
+            continue;
+        };
+        if let Ok((_, mut job)) = job_query.get_mut(job_entity) {
+            job.assigned_pawn = Some(pawn_entity);
+            current_job.job_id = Some(job_entity);
+            commands
+                .entity(pawn_entity)
+                .insert(MovementTarget { target: job.spot });
+        }
+    }
+}
+
+/// Cleaners in the room bring it back up to spotless
+fn clean_rooms(
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &Transform, &CurrentJob, &StaffNeeds, &StaffSkills), With<Pawn>>,
+    job_query: Query<&CleaningJob>,
+    mut cleanliness_query: Query<&mut Cleanliness>,
+    balance: Res<Balance>,
+    time: Res<Time>,
+) {
+    for (pawn_entity, pawn_transform, current_job, needs, skills) in &pawn_query {
+        let Some(job) = current_job.job_id.and_then(|job_id| job_query.get(job_id).ok()) else {
+            continue;
+        };
+        let Ok(mut cleanliness) = cleanliness_query.get_mut(job.room) else {
+            continue;
+        };
+
+        let distance = pawn_transform.translation.truncate().distance(job.spot);
+        if distance < TILE_SIZE * 3.0 {
+            commands.entity(pawn_entity).remove::<MovementTarget>();
+
+            let cleaned = balance.work.cleaning
+                * needs.work_speed()
+                * skills.speed(WorkType::Cleaning)
+                * time.delta_secs();
+            cleanliness.level = (cleanliness.level + cleaned).min(1.0);
+        }
+    }
+}
+
+/// Frees the cleaner once the room is spotless, or gone
+fn finish_cleaning_jobs(
+    mut commands: Commands,
+    job_query: Query<(Entity, &CleaningJob)>,
+    cleanliness_query: Query<&Cleanliness>,
+    mut pawn_query: Query<&mut CurrentJob, With<Pawn>>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
+) {
+    for (job_entity, job) in &job_query {
+        let spotless = cleanliness_query
+            .get(job.room)
+            .ok()
+            .map(|cleanliness| cleanliness.level >= 1.0);
+        if spotless == Some(false) {
+            continue;
+        }
+
+        if let Some(pawn_entity) = job.assigned_pawn {
+            if let Ok(mut current_job) = pawn_query.get_mut(pawn_entity) {
+                current_job.job_id = None;
+            }
+            commands.entity(pawn_entity).remove::<MovementTarget>();
+            // A room that stopped being one wasn't cleaned
+            if spotless.is_some() {
+                finished_jobs.send(JobFinishedEvent {
+                    pawn: pawn_entity,
+                    job: job_entity,
+                    kind: JobKind::Cleaning,
+                });
+            }
+        }
+        commands.entity(job_entity).despawn();
+    }
+}
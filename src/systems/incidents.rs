@@ -0,0 +1,359 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::balance::Balance;
+use crate::systems::grid::{GridSettings, TILE_SIZE};
+use crate::systems::guests::{zone_center, SatisfactionCause, SatisfactionEvent};
+use crate::systems::job_priorities::JobPriorities;
+use crate::systems::lighting::{LightMap, DARK_ROOM_LIGHT};
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::staff::{start_staff_breaks, JobFinishedEvent};
+use crate::systems::statistics::DayLog;
+use crate::systems::time_control::GameClock;
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Hourly odds that a party with something to complain about actually does
+const COMPLAINT_CHANCE_PER_HOUR: f64 = 0.15;
+/// Satisfaction lost when staff sort a complaint out in time, and when nobody comes
+const RESOLVED_SATISFACTION_PENALTY: f32 = 0.05;
+const IGNORED_SATISFACTION_PENALTY: f32 = 0.3;
+/// Cold showers are noticed in the first couple of hours after waking
+const SHOWER_HOURS: (f32, f32) = (7.0, 9.0);
+/// Dark bedrooms are noticed from sunset until bed
+const DUSK_HOUR: f32 = 19.0;
+const BEDTIME_HOUR: f32 = 22.0;
+const WAKE_HOUR: f32 = 7.0;
+/// Floor space an amenity needs for each party visiting at once before a queue forms
+const TILES_PER_VISITING_PARTY: usize = 6;
+/// Bedrooms this close share a wall, so noise carries between them
+const NEIGHBOR_DISTANCE: i32 = 2;
+
+pub struct IncidentsPlugin;
+
+impl Plugin for IncidentsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                raise_incidents,
+                assign_incident_responders.after(start_staff_breaks),
+                settle_incidents,
+                draw_incident_markers,
+            )
+                .in_set(GameplaySet),
+        )
+        // Responding is work like repairs, so it advances in fixed ticks too
+        .add_systems(FixedUpdate, respond_to_incidents.in_set(GameplaySet));
+    }
+}
+
+fn shares_wall(a: &Zone, b: &Zone) -> bool {
+    a.tiles.iter().any(|tile| {
+        b.tiles
+            .iter()
+            .any(|other| (*tile - *other).abs().element_sum() <= NEIGHBOR_DISTANCE)
+    })
+}
+
+/// Whether any of `fixtures` is in the zone's room itself or a room through one of its
+/// doors, such as a tub or sink for a bedroom
+pub fn fixture_within_reach(zone: &Zone, adjacency: &RoomAdjacency, fixtures: &[IVec2]) -> bool {
+    let Some(room) = zone.tiles.iter().find_map(|tile| adjacency.room_at(*tile)) else {
+        return false;
+    };
+    fixtures.iter().any(|tile| {
+        adjacency.room_at(*tile).is_some_and(|fixture_room| {
+            fixture_room == room || adjacency.neighbors(room).any(|r| r == fixture_room)
+        })
+    })
+}
+
+/// Every game hour, parties with something to put up with may complain: a family next
+/// door at night, no hot water in the morning, no lamp to see by in the evening, or a
+/// crowd at the amenity they are at.
+/// Each party complains at most once a day.
+fn raise_incidents(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    adjacency: Res<RoomAdjacency>,
+    light_map: Res<LightMap>,
+    grid_settings: Res<GridSettings>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut day_log: ResMut<DayLog>,
+    guest_query: Query<(Entity, &Guest), Without<Departing>>,
+    zone_query: Query<(&Zone, Option<&Booking>)>,
+    plumbing_query: Query<&GridPosition, Or<(With<Sink>, With<Tub>)>>,
+    incident_query: Query<&Incident>,
+    mut pending_hours: Local<f32>,
+    mut last_hours: Local<Option<f32>>,
+    mut complained: Local<HashMap<Entity, u32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    *pending_hours += elapsed;
+    if *pending_hours < 1.0 {
+        return;
+    }
+    *pending_hours -= 1.0;
+
+    let day = clock.day();
+    let hour = clock.hour();
+    let night = !(WAKE_HOUR..BEDTIME_HOUR).contains(&hour);
+    let shower_time = (SHOWER_HOURS.0..SHOWER_HOURS.1).contains(&hour);
+    let evening = (DUSK_HOUR..BEDTIME_HOUR).contains(&hour);
+    complained.retain(|_, complained_day| *complained_day == day);
+
+    let plumbing: Vec<IVec2> = plumbing_query.iter().map(|pos| pos.to_ivec2()).collect();
+
+    // Rooms with a family asleep in them, and how busy each amenity is
+    let mut noisy_rooms = Vec::new();
+    let mut visitors: HashMap<Entity, usize> = HashMap::new();
+    for (_, guest) in &guest_query {
+        match guest.activity {
+            GuestActivity::Sleeping if guest.party.is_family() => noisy_rooms.push(guest.room),
+            GuestActivity::Visiting(zone) => *visitors.entry(zone).or_default() += 1,
+            _ => {}
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    for (guest_entity, guest) in &guest_query {
+        if complained.contains_key(&guest.room)
+            || incident_query
+                .iter()
+                .any(|incident| incident.room == guest.room)
+        {
+            continue;
+        }
+        let Ok((room_zone, Some(_))) = zone_query.get(guest.room) else {
+            continue;
+        };
+
+        let complaint = match guest.activity {
+            GuestActivity::Sleeping if night => noisy_rooms
+                .iter()
+                .filter(|room| **room != guest.room)
+                .filter_map(|room| zone_query.get(*room).ok())
+                .any(|(zone, _)| shares_wall(room_zone, zone))
+                .then_some((IncidentKind::NoisyNeighbor, room_zone)),
+            GuestActivity::InRoom if shower_time => {
+                (!fixture_within_reach(room_zone, &adjacency, &plumbing))
+                    .then_some((IncidentKind::ColdShower, room_zone))
+            }
+            GuestActivity::InRoom if evening => {
+                let light = light_map.average(&room_zone.tiles, hour);
+                (light < DARK_ROOM_LIGHT).then_some((IncidentKind::DarkRoom, room_zone))
+            }
+            GuestActivity::Visiting(amenity) => {
+                zone_query.get(amenity).ok().and_then(|(zone, _)| {
+                    let capacity = (zone.tiles.len() / TILES_PER_VISITING_PARTY).max(1);
+                    (visitors.get(&amenity).copied().unwrap_or(0) > capacity)
+                        .then_some((IncidentKind::LongQueue, zone))
+                })
+            }
+            _ => None,
+        };
+        let Some((kind, place)) = complaint else {
+            continue;
+        };
+        if !rng.gen_bool(COMPLAINT_CHANCE_PER_HOUR) {
+            continue;
+        }
+
+        let key = match kind {
+            IncidentKind::NoisyNeighbor => "incident.noisy_neighbor",
+            IncidentKind::ColdShower => "incident.cold_shower",
+            IncidentKind::LongQueue => "incident.long_queue",
+            IncidentKind::DarkRoom => "incident.dark_room",
+        };
+        let message = localization.format(
+            key,
+            &[
+                (
+                    "party",
+                    localization.term("party", guest.party.name()).to_string(),
+                ),
+                ("room", room_zone.name.clone()),
+                ("zone", place.name.clone()),
+            ],
+        );
+        day_log.incident(day, message.clone());
+        notifications.send(Notification { message });
+
+        complained.insert(guest.room, day);
+        satisfaction_events.send(SatisfactionEvent {
+            room: guest.room,
+            guest: Some(guest_entity),
+            cause: SatisfactionCause::Complaint(kind),
+        });
+        commands.spawn(Incident::new(
+            kind,
+            guest_entity,
+            guest.room,
+            zone_center(place, &grid_settings),
+        ));
+    }
+}
+
+/// Idle reception staff go and deal with the nearest complaint. Whoever is on the desk
+/// stays there.
+fn assign_incident_responders(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (Entity, &Transform, &mut CurrentJob, &WorkAssignments),
+        (
+            With<Pawn>,
+            Without<OnBreak>,
+            Without<InTraining>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+        ),
+    >,
+    mut incident_query: Query<(Entity, &mut Incident)>,
+    priorities: Res<JobPriorities>,
+) {
+    for (pawn_entity, pawn_transform, mut current_job, work_assignments) in &mut pawn_query {
+        if current_job.job_id.is_some()
+            || !work_assignments.can_do_work(WorkType::Reception)
+            || priorities.passes_over(work_assignments, WorkType::Reception)
+        {
+            continue;
+        }
+
+        let pawn_pos = pawn_transform.translation.truncate();
+        let nearest = incident_query
+            .iter()
+            .filter(|(_, incident)| incident.assigned_pawn.is_none())
+            .min_by(|(_, a), (_, b)| {
+                a.location
+                    .distance(pawn_pos)
+                    .total_cmp(&b.location.distance(pawn_pos))
+            })
+            .map(|(entity, _)| entity);
+
+        let Some(incident_entity) = nearest else {
+            continue;
+        };
+        if let Ok((_, mut incident)) = incident_query.get_mut(incident_entity) {
+            incident.assigned_pawn = Some(pawn_entity);
+            current_job.job_id = Some(incident_entity);
+            commands.entity(pawn_entity).insert(MovementTarget {
+                target: incident.location,
+            });
+        }
+    }
+}
+
+/// Staff on the scene calm the guests down
+fn respond_to_incidents(
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &Transform, &CurrentJob, &StaffNeeds, &StaffSkills), With<Pawn>>,
+    mut incident_query: Query<&mut Incident>,
+    balance: Res<Balance>,
+    time: Res<Time>,
+) {
+    for (pawn_entity, pawn_transform, current_job, needs, skills) in &pawn_query {
+        let Some(job_id) = current_job.job_id else {
+            continue;
+        };
+        let Ok(mut incident) = incident_query.get_mut(job_id) else {
+            continue;
+        };
+
+        let distance = pawn_transform
+            .translation
+            .truncate()
+            .distance(incident.location);
+        if distance < TILE_SIZE * 3.0 {
+            commands.entity(pawn_entity).remove::<MovementTarget>();
+
+            let work_speed =
+                balance.work.incident * needs.work_speed() * skills.speed(WorkType::Reception);
+            incident.work_done =
+                (incident.work_done + work_speed * time.delta_secs()).min(incident.work_required);
+        }
+    }
+}
+
+/// Closes complaints that were sorted out, ran out of time or whose party has left.
+/// An answered complaint costs a little satisfaction, an ignored one a lot.
+fn settle_incidents(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut incident_query: Query<(Entity, &mut Incident)>,
+    mut zone_query: Query<(&Zone, &mut Booking)>,
+    guest_query: Query<(), With<Guest>>,
+    mut pawn_query: Query<&mut CurrentJob, With<Pawn>>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    for (incident_entity, mut incident) in &mut incident_query {
+        if incident
+            .assigned_pawn
+            .is_some_and(|pawn| pawn_query.get(pawn).is_err())
+        {
+            incident.assigned_pawn = None;
+        }
+        incident.hours_left -= elapsed;
+
+        // With the party checked out, the complaint just lapses
+        let party_here = guest_query.contains(incident.guest);
+        if let (true, Ok((zone, mut booking))) = (party_here, zone_query.get_mut(incident.room)) {
+            let (key, penalty) = if incident.is_resolved() {
+                ("incident.resolved", RESOLVED_SATISFACTION_PENALTY)
+            } else if incident.hours_left <= 0.0 {
+                ("incident.ignored", IGNORED_SATISFACTION_PENALTY)
+            } else {
+                continue;
+            };
+            booking.satisfaction = (booking.satisfaction - penalty).max(0.0);
+            notifications.send(Notification {
+                message: localization.format(key, &[("room", zone.name.clone())]),
+            });
+            if !incident.is_resolved() {
+                satisfaction_events.send(SatisfactionEvent {
+                    room: incident.room,
+                    guest: Some(incident.guest),
+                    cause: SatisfactionCause::ComplaintIgnored,
+                });
+            }
+        }
+
+        if let Some(pawn) = incident.assigned_pawn {
+            if let Ok(mut current_job) = pawn_query.get_mut(pawn) {
+                current_job.job_id = None;
+            }
+            commands.entity(pawn).remove::<MovementTarget>();
+            if incident.is_resolved() {
+                finished_jobs.send(JobFinishedEvent {
+                    pawn,
+                    job: incident_entity,
+                    kind: JobKind::Incident,
+                });
+            }
+        }
+        commands.entity(incident_entity).despawn();
+    }
+}
+
+/// A red ring marks each complaint still waiting for staff
+fn draw_incident_markers(incident_query: Query<&Incident>, mut gizmos: Gizmos) {
+    for incident in &incident_query {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(incident.location),
+            TILE_SIZE * 0.6,
+            Color::srgb(0.95, 0.25, 0.25),
+        );
+    }
+}
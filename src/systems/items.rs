@@ -0,0 +1,223 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::ascii_renderer::{AsciiLayer, AsciiSprite};
+use crate::systems::grid::TILE_SIZE;
+use crate::systems::time_control::GameClock;
+use crate::systems::transport::ArrivalPoints;
+use bevy::prelude::*;
+
+/// Loose items sit above furniture but below the people walking over them
+const GROUND_Z: f32 = 4.5;
+/// Carried items are drawn up and to the side of whoever holds them
+const CARRY_OFFSET: Vec2 = Vec2::new(TILE_SIZE * 0.7, TILE_SIZE * 0.5);
+const ITEM_SIZE: f32 = TILE_SIZE * 0.8;
+/// How close someone has to be to an item to pick it up
+const PICKUP_REACH: f32 = TILE_SIZE * 1.5;
+
+/// Puts `item` in `carrier`'s hands, wherever it was before
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PickUpItem {
+    pub item: Entity,
+    pub carrier: Entity,
+}
+
+/// Sets a carried item down at its carrier's feet
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DropItem {
+    pub item: Entity,
+}
+
+pub struct ItemsPlugin;
+
+impl Plugin for ItemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PickUpItem>()
+            .add_event::<DropItem>()
+            .add_systems(
+                Update,
+                (
+                    unpack_luggage,
+                    pack_luggage,
+                    drop_items,
+                    pick_up_items,
+                    carry_items,
+                    remove_lost_luggage,
+                    sweep_litter,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Spawns an item lying on the ground at `position`
+pub fn spawn_item(commands: &mut Commands, item: Item, position: Vec2) -> Entity {
+    commands
+        .spawn((
+            Sprite {
+                color: item.kind.color(),
+                custom_size: Some(Vec2::splat(ITEM_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(position.extend(GROUND_Z)),
+            AsciiSprite::new(item.kind.glyph(), item.kind.color(), AsciiLayer::Marker),
+            item,
+        ))
+        .id()
+}
+
+/// Spawns an item already in `carrier`'s hands
+pub fn spawn_carried_item(
+    commands: &mut Commands,
+    item: Item,
+    carrier: Entity,
+    carrier_position: Vec2,
+) -> Entity {
+    let entity = spawn_item(commands, item, carrier_position + CARRY_OFFSET);
+    commands.entity(entity).insert(Carried { by: carrier });
+    entity
+}
+
+fn pick_up_items(
+    mut commands: Commands,
+    mut events: EventReader<PickUpItem>,
+    item_query: Query<(), With<Item>>,
+) {
+    for event in events.read() {
+        if item_query.contains(event.item) {
+            commands
+                .entity(event.item)
+                .insert(Carried { by: event.carrier });
+        }
+    }
+}
+
+fn drop_items(
+    mut commands: Commands,
+    mut events: EventReader<DropItem>,
+    mut item_query: Query<(&Carried, &mut Transform), With<Item>>,
+    carrier_query: Query<&Transform, Without<Item>>,
+) {
+    for event in events.read() {
+        let Ok((carried, mut transform)) = item_query.get_mut(event.item) else {
+            continue;
+        };
+        if let Ok(carrier_transform) = carrier_query.get(carried.by) {
+            transform.translation = carrier_transform.translation.truncate().extend(GROUND_Z);
+        }
+        commands.entity(event.item).remove::<Carried>();
+    }
+}
+
+/// Keeps carried items in their carrier's hands. Anything held by someone who has left
+/// the resort went with them.
+fn carry_items(
+    mut commands: Commands,
+    mut item_query: Query<(Entity, &Carried, &mut Transform), With<Item>>,
+    carrier_query: Query<&Transform, Without<Item>>,
+) {
+    for (entity, carried, mut transform) in &mut item_query {
+        match carrier_query.get(carried.by) {
+            Ok(carrier_transform) => {
+                let position = carrier_transform.translation.truncate() + CARRY_OFFSET;
+                transform.translation = position.extend(carrier_transform.translation.z + 0.5);
+            }
+            Err(_) => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+/// Guests put their bags down once they reach their room
+fn unpack_luggage(
+    mut drops: EventWriter<DropItem>,
+    item_query: Query<(Entity, &Item, &Carried)>,
+    guest_query: Query<(&Guest, &Transform, &MovementTarget), Without<Departing>>,
+) {
+    for (entity, item, carried) in &item_query {
+        if item.kind != ItemKind::Luggage {
+            continue;
+        }
+        let Ok((guest, transform, target)) = guest_query.get(carried.by) else {
+            continue;
+        };
+        let arrived = transform.translation.truncate().distance(target.target) <= TILE_SIZE;
+        if guest.activity.in_room() && arrived {
+            drops.send(DropItem { item: entity });
+        }
+    }
+}
+
+/// Departing guests go back for their bags before heading to the shuttle stop
+fn pack_luggage(
+    mut commands: Commands,
+    mut pickups: EventWriter<PickUpItem>,
+    arrival_points: Res<ArrivalPoints>,
+    item_query: Query<(Entity, &Item, &Transform), Without<Carried>>,
+    guest_query: Query<(&Transform, &MovementTarget), (With<Guest>, With<Departing>)>,
+) {
+    for (entity, item, item_transform) in &item_query {
+        let Some(owner) = item.owner.filter(|_| item.kind == ItemKind::Luggage) else {
+            continue;
+        };
+        let Ok((transform, target)) = guest_query.get(owner) else {
+            continue;
+        };
+
+        let luggage_position = item_transform.translation.truncate();
+        let position = transform.translation.truncate();
+        if position.distance(luggage_position) <= PICKUP_REACH {
+            pickups.send(PickUpItem {
+                item: entity,
+                carrier: owner,
+            });
+            if let Some(stop) = arrival_points.nearest(position) {
+                commands
+                    .entity(owner)
+                    .insert(MovementTarget { target: stop });
+            }
+        } else if target.target != luggage_position {
+            commands.entity(owner).insert(MovementTarget {
+                target: luggage_position,
+            });
+        }
+    }
+}
+
+/// Bags left behind by parties that have already gone are sent on after them
+fn remove_lost_luggage(
+    mut commands: Commands,
+    item_query: Query<(Entity, &Item), Without<Carried>>,
+    guest_query: Query<(), With<Guest>>,
+) {
+    for (entity, item) in &item_query {
+        let lost = item.kind == ItemKind::Luggage
+            && item.owner.is_some_and(|owner| !guest_query.contains(owner));
+        if lost {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// The overnight crew clears away plates and litter still lying around each morning
+fn sweep_litter(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    item_query: Query<(Entity, &Item), Without<Carried>>,
+    mut last_day: Local<Option<u32>>,
+) {
+    let day = clock.day();
+    if last_day.replace(day).is_none_or(|last| last == day) {
+        return;
+    }
+
+    let mut swept = 0;
+    for (entity, item) in &item_query {
+        if item.kind.is_litter() {
+            commands.entity(entity).despawn();
+            swept += 1;
+        }
+    }
+    if swept > 0 {
+        info!("Cleared away {} plates and bits of litter overnight", swept);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::staff::start_staff_breaks;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Slider setting every kind of work starts at; each step either side moves it one place
+/// up or down against a worker's own priorities
+pub const DEFAULT_JOB_LEVEL: u8 = 3;
+pub const MAX_JOB_LEVEL: u8 = 5;
+
+/// How much the whole staff leans towards each kind of work, set with the sliders in the
+/// work panel. It only comes into play when more than one kind of work is waiting: a
+/// worker free for both then takes whichever ranks ahead once their own priority and the
+/// slider are weighed together.
+#[derive(Resource, Debug)]
+pub struct JobPriorities {
+    levels: HashMap<WorkType, u8>,
+    /// Kinds of work with a job, desk or table nobody has taken yet
+    waiting: HashSet<WorkType>,
+}
+
+impl Default for JobPriorities {
+    fn default() -> Self {
+        Self {
+            levels: WorkType::all()
+                .into_iter()
+                .map(|work_type| (work_type, DEFAULT_JOB_LEVEL))
+                .collect(),
+            waiting: HashSet::new(),
+        }
+    }
+}
+
+impl JobPriorities {
+    /// From 1 (least urgent) to MAX_JOB_LEVEL (most)
+    pub fn level(&self, work_type: WorkType) -> u8 {
+        self.levels
+            .get(&work_type)
+            .copied()
+            .unwrap_or(DEFAULT_JOB_LEVEL)
+    }
+
+    pub fn set_level(&mut self, work_type: WorkType, level: u8) {
+        self.levels.insert(work_type, level.clamp(1, MAX_JOB_LEVEL));
+    }
+
+    pub fn level_name(level: u8) -> &'static str {
+        match level {
+            1 => "Lowest",
+            2 => "Low",
+            3 => "Normal",
+            4 => "High",
+            _ => "Highest",
+        }
+    }
+
+    /// Where `work_type` comes for a worker, lower first: their own priority, moved up
+    /// or down by the slider
+    fn rank(&self, assignments: &WorkAssignments, work_type: WorkType) -> i32 {
+        assignments.get_priority(work_type).0 as i32 + DEFAULT_JOB_LEVEL as i32
+            - self.level(work_type) as i32
+    }
+
+    /// Whether a free worker should leave `work_type` be because other work they do is
+    /// waiting and ranks ahead of it. Ties don't hold anyone back.
+    pub fn passes_over(&self, assignments: &WorkAssignments, work_type: WorkType) -> bool {
+        let rank = self.rank(assignments, work_type);
+        self.waiting.iter().any(|other| {
+            *other != work_type
+                && assignments.can_do_work(*other)
+                && self.rank(assignments, *other) < rank
+        })
+    }
+}
+
+pub struct JobPrioritiesPlugin;
+
+impl Plugin for JobPrioritiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JobPriorities>().add_systems(
+            Update,
+            survey_waiting_work
+                .before(start_staff_breaks)
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Notes which kinds of work have something nobody has taken yet, ahead of the systems
+/// handing out jobs. Blueprints nobody can reach don't count.
+fn survey_waiting_work(
+    construction_query: Query<&ConstructionJob>,
+    blueprint_query: Query<&Blueprint>,
+    deconstruction_query: Query<&DeconstructionJob>,
+    repair_query: Query<&RepairJob>,
+    cleaning_query: Query<&CleaningJob>,
+    infestation_query: Query<&Infestation>,
+    incident_query: Query<&Incident>,
+    order_query: Query<&RoomServiceOrder>,
+    console_query: Query<Entity, With<ReceptionConsole>>,
+    table_query: Query<Entity, With<MassageTable>>,
+    reception_query: Query<&StaffingReception>,
+    spa_query: Query<&StaffingSpa>,
+    mut priorities: ResMut<JobPriorities>,
+) {
+    let mut waiting = HashSet::new();
+
+    let building = construction_query.iter().any(|job| {
+        job.assigned_pawn.is_none()
+            && blueprint_query
+                .get(job.blueprint)
+                .is_ok_and(|blueprint| !blueprint.blocked)
+    });
+    let tearing_down = deconstruction_query
+        .iter()
+        .any(|job| job.assigned_pawn.is_none());
+    let repairing = repair_query.iter().any(|job| job.assigned_pawn.is_none());
+    if building || tearing_down || repairing {
+        waiting.insert(WorkType::Construction);
+    }
+
+    let staffed_desks: HashSet<Entity> = reception_query
+        .iter()
+        .map(|staffing| staffing.desk_entity)
+        .collect();
+    let reception = console_query
+        .iter()
+        .any(|console| !staffed_desks.contains(&console))
+        || incident_query
+            .iter()
+            .any(|incident| incident.assigned_pawn.is_none());
+    if reception {
+        waiting.insert(WorkType::Reception);
+    }
+
+    let cleaning = cleaning_query.iter().any(|job| job.assigned_pawn.is_none())
+        || infestation_query
+            .iter()
+            .any(|pests| pests.assigned_pawn.is_none());
+    if cleaning {
+        waiting.insert(WorkType::Cleaning);
+    }
+
+    let staffed_tables: HashSet<Entity> = spa_query
+        .iter()
+        .map(|staffing| staffing.table_entity)
+        .collect();
+    if table_query
+        .iter()
+        .any(|table| !staffed_tables.contains(&table))
+    {
+        waiting.insert(WorkType::SpaTherapist);
+    }
+
+    waiting.extend(
+        order_query
+            .iter()
+            .filter(|order| order.assigned_pawn.is_none())
+            .map(|order| order.item.work_type()),
+    );
+
+    // Only touched when it differs, so the work panel isn't redrawn every frame
+    if priorities.waiting != waiting {
+        priorities.waiting = waiting;
+    }
+}
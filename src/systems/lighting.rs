@@ -0,0 +1,233 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::time_control::GameClock;
+use crate::ui::UiInputBlocker;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Tiles a window lights into the room behind it, and a lamp all around it
+const WINDOW_REACH: f32 = 6.0;
+const LAMP_REACH: f32 = 5.0;
+/// Daylight is full between these hours and fades out over an hour either side
+const SUNRISE_HOUR: f32 = 7.0;
+const SUNSET_HOUR: f32 = 19.0;
+/// Rooms averaging less light than this count as dark
+pub const DARK_ROOM_LIGHT: f32 = 0.3;
+/// The best a bedroom too dark to read in can rate
+pub const DARK_ROOM_MAX_QUALITY: ZoneQuality = ZoneQuality::Basic;
+const DARK_OVERLAY_COLOR: Color = Color::srgba(0.1, 0.15, 0.45, 0.55);
+const BRIGHT_OVERLAY_COLOR: Color = Color::srgba(1.0, 0.9, 0.4, 0.45);
+
+/// How much light reaches the sky, from 0 at night to 1 through the day
+pub fn daylight(hour: f32) -> f32 {
+    let dawn = hour - (SUNRISE_HOUR - 1.0);
+    let dusk = (SUNSET_HOUR + 1.0) - hour;
+    dawn.min(dusk).clamp(0.0, 1.0)
+}
+
+/// Light on one indoor tile from each kind of source, from 0 to 1
+#[derive(Debug, Clone, Copy, Default)]
+struct TileLight {
+    /// From windows, at full daylight
+    window: f32,
+    /// From lamps, day and night
+    lamp: f32,
+}
+
+/// How well lit every indoor tile is, rebuilt whenever rooms, windows or lamps change.
+/// Tiles outdoors get whatever daylight there is.
+#[derive(Resource, Default, Debug)]
+pub struct LightMap {
+    tiles: HashMap<IVec2, TileLight>,
+}
+
+impl LightMap {
+    /// Light on `tile` at `hour`, from 0 (pitch dark) to 1
+    pub fn level(&self, tile: IVec2, hour: f32) -> f32 {
+        match self.tiles.get(&tile) {
+            Some(light) => light.lamp.max(light.window * daylight(hour)),
+            None => daylight(hour),
+        }
+    }
+
+    /// Light on `tile` at the brightest time of day
+    pub fn brightest(&self, tile: IVec2) -> f32 {
+        self.tiles
+            .get(&tile)
+            .map_or(1.0, |light| light.lamp.max(light.window))
+    }
+
+    /// Mean light over `tiles` at `hour`
+    pub fn average(&self, tiles: &HashSet<IVec2>, hour: f32) -> f32 {
+        mean(tiles.iter().map(|tile| self.level(*tile, hour)))
+    }
+
+    /// Mean light over `tiles` at the brightest time of day
+    pub fn average_brightest(&self, tiles: &HashSet<IVec2>) -> f32 {
+        mean(tiles.iter().map(|tile| self.brightest(*tile)))
+    }
+}
+
+fn mean(levels: impl ExactSizeIterator<Item = f32>) -> f32 {
+    let count = levels.len();
+    if count == 0 {
+        return 0.0;
+    }
+    levels.sum::<f32>() / count as f32
+}
+
+/// Caps a bedroom's rating when it stays dark even at midday
+pub fn with_lighting(
+    quality: ZoneQuality,
+    light_map: &LightMap,
+    tiles: &HashSet<IVec2>,
+) -> ZoneQuality {
+    if light_map.average_brightest(tiles) < DARK_ROOM_LIGHT {
+        quality.min(DARK_ROOM_MAX_QUALITY)
+    } else {
+        quality
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct LightOverlaySettings {
+    pub visible: bool,
+}
+
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LightMap>()
+            .init_resource::<LightOverlaySettings>()
+            .add_systems(
+                Update,
+                (update_light_map, toggle_light_overlay, draw_light_overlay)
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Falls off evenly from full at the source to nothing at `reach`
+fn falloff(distance: f32, reach: f32) -> f32 {
+    (1.0 - distance / reach).max(0.0)
+}
+
+/// Windows light the room behind them while the sun is up, and lamps the room they stand
+/// in at any hour. Light stops at walls: each source only reaches its own room.
+fn update_light_map(
+    adjacency: Res<RoomAdjacency>,
+    building_map: Res<BuildingMap>,
+    window_query: Query<&GridPosition, With<crate::components::Window>>,
+    lamp_query: Query<&GridPosition, With<Lamp>>,
+    added_sources: Query<(), Or<(Added<crate::components::Window>, Added<Lamp>)>>,
+    mut removed_windows: RemovedComponents<crate::components::Window>,
+    mut removed_lamps: RemovedComponents<Lamp>,
+    mut light_map: ResMut<LightMap>,
+) {
+    // Windows take the place of a wall, so finishing one leaves the rooms as they were
+    let removed = removed_windows.read().count() + removed_lamps.read().count();
+    let sources_changed = !added_sources.is_empty() || removed > 0;
+    if !adjacency.is_changed() && !sources_changed {
+        return;
+    }
+
+    let mut room_tiles: HashMap<Entity, Vec<IVec2>> = HashMap::new();
+    for (tile, room) in &adjacency.tile_rooms {
+        room_tiles.entry(*room).or_default().push(*tile);
+    }
+
+    let mut tiles: HashMap<IVec2, TileLight> = adjacency
+        .tile_rooms
+        .keys()
+        .map(|tile| (*tile, TileLight::default()))
+        .collect();
+    let mut light_room = |room: Entity, source: IVec2, reach: f32, lamp: bool| {
+        for tile in room_tiles.get(&room).into_iter().flatten() {
+            let level = falloff(tile.as_vec2().distance(source.as_vec2()), reach);
+            if let Some(light) = tiles.get_mut(tile) {
+                let current = if lamp {
+                    &mut light.lamp
+                } else {
+                    &mut light.window
+                };
+                *current = current.max(level);
+            }
+        }
+    };
+
+    for window in &window_query {
+        let window = window.to_ivec2();
+        for offset in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+            // Daylight comes in from open ground outside and lights the room inside
+            let (Some(room), None) = (
+                adjacency.room_at(window + offset),
+                adjacency.room_at(window - offset),
+            ) else {
+                continue;
+            };
+            if building_map.divides_rooms(window - offset) {
+                continue;
+            }
+            light_room(room, window, WINDOW_REACH, false);
+        }
+    }
+
+    for lamp in &lamp_query {
+        let lamp = lamp.to_ivec2();
+        // Lamps stand on their own tile, so the room is found from whichever side is open
+        let room = std::iter::once(lamp)
+            .chain([IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|offset| lamp + offset))
+            .find_map(|tile| adjacency.room_at(tile));
+        if let Some(room) = room {
+            light_room(room, lamp, LAMP_REACH, true);
+        }
+    }
+
+    light_map.tiles = tiles;
+}
+
+fn toggle_light_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut overlay_settings: ResMut<LightOverlaySettings>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::F7) {
+        overlay_settings.visible = !overlay_settings.visible;
+    }
+}
+
+/// Shades every indoor tile from deep blue in the dark to warm yellow in full light, as
+/// it is at the current hour
+fn draw_light_overlay(
+    overlay_settings: Res<LightOverlaySettings>,
+    light_map: Res<LightMap>,
+    clock: Res<GameClock>,
+    grid_settings: Res<GridSettings>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay_settings.visible {
+        return;
+    }
+
+    let hour = clock.hour();
+    let size = Vec2::splat(grid_settings.tile_size * 0.9);
+    for tile in light_map.tiles.keys() {
+        let level = light_map.level(*tile, hour);
+        let center = grid_to_world(
+            *tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+        gizmos.rect_2d(
+            Isometry2d::from_translation(center),
+            size,
+            DARK_OVERLAY_COLOR.mix(&BRIGHT_OVERLAY_COLOR, level),
+        );
+    }
+}
@@ -1,23 +1,102 @@
+pub mod ambience;
+pub mod amenities;
+pub mod app_state;
 pub mod ascii_renderer;
+pub mod balance;
+pub mod budget;
 pub mod building;
+pub mod calendar_events;
 pub mod camera;
+pub mod controls;
+pub mod cursor;
+pub mod demand;
 pub mod economy;
+pub mod furniture_claims;
+pub mod game_rules;
+pub mod game_state;
 pub mod grid;
+pub mod guests;
+pub mod housekeeping;
+pub mod incidents;
+pub mod items;
+pub mod job_priorities;
+pub mod lighting;
+pub mod noise;
+pub mod path_debug;
+pub mod pathfinding;
 pub mod pawn;
+pub mod pawn_names;
+pub mod pests;
+pub mod photo_mode;
+pub mod reception;
+pub mod reviews;
 pub mod room_detection;
+pub mod room_service;
+pub mod safety;
 pub mod save_load;
+pub mod shared_assets;
+pub mod signage;
+pub mod stable_id;
+pub mod staff;
+pub mod statistics;
+pub mod terrain;
 pub mod time_control;
+pub mod tools;
+pub mod training;
+pub mod transport;
+pub mod weather;
 pub mod work;
+pub mod work_spots;
+pub mod work_visuals;
 pub mod zone;
 
+pub use ambience::*;
+pub use amenities::*;
+pub use app_state::*;
 pub use ascii_renderer::*;
+pub use balance::*;
+pub use budget::*;
 pub use building::*;
+pub use calendar_events::*;
 pub use camera::*;
+pub use controls::*;
+pub use cursor::*;
+pub use demand::*;
 pub use economy::*;
+pub use furniture_claims::*;
+pub use game_rules::*;
+pub use game_state::*;
 pub use grid::*;
+pub use guests::*;
+pub use housekeeping::*;
+pub use incidents::*;
+pub use items::*;
+pub use job_priorities::*;
+pub use lighting::*;
+pub use noise::*;
+pub use path_debug::*;
+pub use pathfinding::*;
 pub use pawn::*;
+pub use pawn_names::*;
+pub use pests::*;
+pub use photo_mode::*;
+pub use reception::*;
+pub use reviews::*;
 pub use room_detection::*;
+pub use room_service::*;
+pub use safety::*;
 pub use save_load::*;
+pub use shared_assets::*;
+pub use signage::*;
+pub use stable_id::*;
+pub use staff::*;
+pub use statistics::*;
+pub use terrain::*;
 pub use time_control::*;
+pub use tools::*;
+pub use training::*;
+pub use transport::*;
+pub use weather::*;
 pub use work::*;
+pub use work_visuals::*;
 pub use zone::*;
@@ -0,0 +1,131 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::grid::{world_to_grid, GridSettings};
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::work_visuals::HammeringAnimation;
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+/// How loud each source is in its own room, from 0 (silent) to 1
+const LOBBY_NOISE: f32 = 0.5;
+const CULINARY_NOISE: f32 = 0.7;
+const CONSTRUCTION_NOISE: f32 = 1.0;
+/// Share of the noise that carries into the next room through a door, and through a wall
+const DOOR_TRANSMISSION: f32 = 0.5;
+const WALL_TRANSMISSION: f32 = 0.2;
+/// Quieter than this and noise stops spreading
+const NOISE_CUTOFF: f32 = 0.05;
+/// Sleep lost in a room at full noise
+const MAX_SLEEP_LOSS: f32 = 0.6;
+/// Where the inspector stops calling a room quiet, and starts calling it loud
+const QUIET_BELOW: f32 = 0.1;
+const LOUD_FROM: f32 = 0.35;
+
+/// How loud each zone is right now, from the busy zones and building work around it
+#[derive(Resource, Default, Debug)]
+pub struct RoomNoise {
+    levels: HashMap<Entity, f32>,
+}
+
+impl RoomNoise {
+    pub fn level(&self, zone: Entity) -> f32 {
+        self.levels.get(&zone).copied().unwrap_or(0.0)
+    }
+
+    /// Share of a night's rest a guest gets in the zone, 1 when it is quiet
+    pub fn sleep_quality(&self, zone: Entity) -> f32 {
+        1.0 - self.level(zone) * MAX_SLEEP_LOSS
+    }
+
+    pub fn describe(level: f32) -> &'static str {
+        if level < QUIET_BELOW {
+            "Quiet"
+        } else if level < LOUD_FROM {
+            "Moderate"
+        } else {
+            "Loud"
+        }
+    }
+}
+
+pub struct NoisePlugin;
+
+impl Plugin for NoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoomNoise>()
+            .add_systems(Update, propagate_noise.in_set(GameplaySet));
+    }
+}
+
+/// Lobbies, culinary zones and rooms with someone hammering in them are noisy. The noise
+/// spreads room to room through the adjacency graph, losing most of itself through each
+/// door and more through each wall, and each zone takes the loudest that reaches it.
+fn propagate_noise(
+    adjacency: Res<RoomAdjacency>,
+    grid_settings: Res<GridSettings>,
+    zone_query: Query<(Entity, &Zone)>,
+    builder_query: Query<&Transform, (With<Pawn>, With<HammeringAnimation>)>,
+    mut noise: ResMut<RoomNoise>,
+) {
+    let zone_room = |zone: &Zone| zone.tiles.iter().find_map(|tile| adjacency.room_at(*tile));
+
+    let mut sources: HashMap<Entity, f32> = HashMap::new();
+    let mut add_source = |room: Entity, level: f32| {
+        let loudest = sources.entry(room).or_default();
+        *loudest = loudest.max(level);
+    };
+    for (_, zone) in &zone_query {
+        let level = match zone.zone_type {
+            ZoneType::Lobby => LOBBY_NOISE,
+            ZoneType::Culinary => CULINARY_NOISE,
+            _ => continue,
+        };
+        if let Some(room) = zone_room(zone) {
+            add_source(room, level);
+        }
+    }
+    for transform in &builder_query {
+        let room = world_to_grid(
+            transform.translation.truncate(),
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+        .and_then(|tile| adjacency.room_at(tile));
+        if let Some(room) = room {
+            add_source(room, CONSTRUCTION_NOISE);
+        }
+    }
+
+    // Noise only ever fades as it spreads, so revisiting a room only when something
+    // louder arrives settles quickly
+    let mut levels = sources.clone();
+    let mut queue: VecDeque<Entity> = sources.into_keys().collect();
+    while let Some(room) = queue.pop_front() {
+        let level = levels[&room];
+        let through_doors = adjacency
+            .neighbors(room)
+            .map(|neighbor| (neighbor, level * DOOR_TRANSMISSION));
+        let through_walls = adjacency
+            .wall_neighbors(room)
+            .map(|neighbor| (neighbor, level * WALL_TRANSMISSION));
+        for (neighbor, heard) in through_doors.chain(through_walls) {
+            if heard < NOISE_CUTOFF {
+                continue;
+            }
+            let current = levels.entry(neighbor).or_default();
+            if heard > *current {
+                *current = heard;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    noise.levels = zone_query
+        .iter()
+        .filter_map(|(entity, zone)| {
+            let level = zone_room(zone).and_then(|room| levels.get(&room))?;
+            Some((entity, *level))
+        })
+        .collect();
+}
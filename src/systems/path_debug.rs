@@ -0,0 +1,250 @@
+use crate::components::{Guest, PawnPath};
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::cursor::CursorWorldPosition;
+use crate::systems::grid::*;
+use crate::systems::pathfinding::{
+    has_clearance, is_walkable, CongestionMap, FailedPaths, FAILED_PATH_SECONDS,
+};
+use crate::systems::terrain::Terrain;
+use crate::ui::UiInputBlocker;
+use bevy::prelude::*;
+
+const PAWN_PATH_COLOR: Color = Color::srgb(0.3, 0.8, 1.0);
+const GUEST_PATH_COLOR: Color = Color::srgb(1.0, 0.7, 0.3);
+const BLOCKED_TILE_COLOR: Color = Color::srgba(1.0, 0.2, 0.2, 0.6);
+const DOOR_TILE_COLOR: Color = Color::srgba(1.0, 0.9, 0.2, 0.6);
+const FAILED_PATH_COLOR: Color = Color::srgb(1.0, 0.1, 0.4);
+
+/// Debug overlay for diagnosing stuck pawns: their planned paths, what blocks movement,
+/// and searches that recently failed. Toggled with F4.
+#[derive(Resource, Default)]
+pub struct PathDebugOverlay {
+    pub enabled: bool,
+}
+
+/// Occupancy readout for the tile under the cursor
+#[derive(Component)]
+pub struct PathDebugTooltip;
+
+pub struct PathDebugPlugin;
+
+impl Plugin for PathDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PathDebugOverlay>()
+            .add_systems(Startup, setup_path_debug_tooltip)
+            .add_systems(
+                Update,
+                (
+                    toggle_path_debug_overlay,
+                    (draw_blocked_tiles, draw_pawn_paths, draw_failed_paths)
+                        .run_if(path_debug_enabled),
+                    update_path_debug_tooltip,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+pub fn path_debug_enabled(overlay: Res<PathDebugOverlay>) -> bool {
+    overlay.enabled
+}
+
+fn setup_path_debug_tooltip(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.9)),
+        Visibility::Hidden,
+        PathDebugTooltip,
+    ));
+}
+
+fn toggle_path_debug_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut overlay: ResMut<PathDebugOverlay>,
+) {
+    if ui_blocker.text_input_active || !keyboard.just_pressed(KeyCode::F4) {
+        return;
+    }
+
+    overlay.enabled = !overlay.enabled;
+    info!(
+        "Path debug overlay {}",
+        if overlay.enabled { "on" } else { "off" }
+    );
+}
+
+// Only tiles inside the camera view are outlined, so big maps stay cheap to draw
+fn draw_blocked_tiles(
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let center = camera_transform.translation.truncate();
+    let view = Rect::from_corners(center + projection.area.min, center + projection.area.max)
+        .inflate(grid_settings.tile_size);
+    let size = Vec2::splat(grid_settings.tile_size * 0.85);
+
+    let to_world = |tile: IVec2| {
+        grid_to_world(
+            tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+    };
+
+    for &tile in &building_map.occupied {
+        let position = to_world(tile);
+        if view.contains(position) {
+            gizmos.rect_2d(
+                Isometry2d::from_translation(position),
+                size,
+                BLOCKED_TILE_COLOR,
+            );
+        }
+    }
+
+    for &tile in building_map.doors.keys() {
+        let position = to_world(tile);
+        if view.contains(position) {
+            gizmos.rect_2d(
+                Isometry2d::from_translation(position),
+                size,
+                DOOR_TILE_COLOR,
+            );
+        }
+    }
+}
+
+fn draw_pawn_paths(
+    path_query: Query<(&Transform, &PawnPath, Has<Guest>)>,
+    grid_settings: Res<GridSettings>,
+    mut gizmos: Gizmos,
+) {
+    let node_radius = grid_settings.tile_size * 0.15;
+
+    for (transform, path, is_guest) in &path_query {
+        if path.waypoints.is_empty() {
+            continue;
+        }
+
+        let color = if is_guest {
+            GUEST_PATH_COLOR
+        } else {
+            PAWN_PATH_COLOR
+        };
+
+        let points =
+            std::iter::once(transform.translation.truncate()).chain(path.waypoints.iter().copied());
+        gizmos.linestrip_2d(points, color);
+
+        for &waypoint in &path.waypoints {
+            gizmos.circle_2d(Isometry2d::from_translation(waypoint), node_radius, color);
+        }
+        gizmos.circle_2d(
+            Isometry2d::from_translation(path.goal),
+            node_radius * 2.5,
+            color,
+        );
+    }
+}
+
+// Failed searches fade out as they age; the goal is crossed out
+fn draw_failed_paths(
+    failed_paths: Res<FailedPaths>,
+    grid_settings: Res<GridSettings>,
+    mut gizmos: Gizmos,
+) {
+    let half = grid_settings.tile_size * 0.5;
+    let to_world = |tile: IVec2| {
+        grid_to_world(
+            tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+    };
+
+    for attempt in &failed_paths.attempts {
+        let fade = 1.0 - attempt.age / FAILED_PATH_SECONDS;
+        let color = FAILED_PATH_COLOR.with_alpha(fade.clamp(0.2, 1.0));
+        let start = to_world(attempt.start);
+        let goal = to_world(attempt.goal);
+
+        gizmos.line_2d(start, goal, color);
+        gizmos.line_2d(goal - Vec2::splat(half), goal + Vec2::splat(half), color);
+        gizmos.line_2d(
+            goal + Vec2::new(-half, half),
+            goal + Vec2::new(half, -half),
+            color,
+        );
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+fn update_path_debug_tooltip(
+    overlay: Res<PathDebugOverlay>,
+    building_map: Res<BuildingMap>,
+    congestion: Res<CongestionMap>,
+    grid_settings: Res<GridSettings>,
+    terrain: Res<Terrain>,
+    cursor: Res<CursorWorldPosition>,
+    mut tooltip_query: Query<(&mut Text, &mut Node, &mut Visibility), With<PathDebugTooltip>>,
+) {
+    let Ok((mut text, mut node, mut visibility)) = tooltip_query.get_single_mut() else {
+        return;
+    };
+
+    let hovered = cursor.ui.zip(cursor.grid);
+    let Some((cursor_pos, tile)) = hovered.filter(|_| overlay.enabled) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let label = format!(
+        "Tile ({}, {})\nElevation: {}{}\nWalkable: {}\nPawn fits: {}\nBlocked: {}\nWall: {}\n\
+         Door: {}\nFloor: {}\nPawns here: {}",
+        tile.x,
+        tile.y,
+        terrain.elevation(tile),
+        if terrain.is_ramp(tile) { " (ramp)" } else { "" },
+        yes_no(is_walkable(tile, &building_map, &grid_settings, &terrain)),
+        yes_no(has_clearance(tile, &building_map, &grid_settings, &terrain)),
+        yes_no(building_map.occupied.contains(&tile)),
+        yes_no(building_map.walls.contains_key(&tile)),
+        yes_no(building_map.doors.contains_key(&tile)),
+        yes_no(building_map.floors.contains(&tile)),
+        congestion.pawns_at(tile),
+    );
+    if **text != label {
+        **text = label;
+    }
+    node.left = Val::Px(cursor_pos.x + 16.0);
+    node.top = Val::Px(cursor_pos.y + 16.0);
+    visibility.set_if_neq(Visibility::Visible);
+}
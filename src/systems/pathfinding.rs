@@ -0,0 +1,359 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::*;
+use crate::systems::terrain::Terrain;
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+// Costs are kept as integers (tenths of a tile) so they can live in a BinaryHeap
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+/// Extra cost for stepping onto a tile per pawn already standing there
+const CONGESTION_COST: u32 = 25;
+/// Safety valve so an unreachable goal can't stall a frame on a 200x200 map
+const MAX_EXPANDED_NODES: usize = 20_000;
+/// Seconds a failed search stays on the debug overlay
+pub const FAILED_PATH_SECONDS: f32 = 5.0;
+const MAX_FAILED_PATHS: usize = 64;
+
+/// How many pawns currently stand on each tile, rebuilt every frame
+#[derive(Resource, Default)]
+pub struct CongestionMap {
+    pub pawns_per_tile: HashMap<IVec2, u32>,
+}
+
+impl CongestionMap {
+    pub fn pawns_at(&self, tile: IVec2) -> u32 {
+        self.pawns_per_tile.get(&tile).copied().unwrap_or(0)
+    }
+
+    /// Pawns in the 3x3 block around a tile, used to slow movement in crowds
+    pub fn crowding_around(&self, tile: IVec2) -> u32 {
+        let mut count = 0;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                count += self.pawns_at(tile + IVec2::new(dx, dy));
+            }
+        }
+        count
+    }
+}
+
+/// Who is looking for a way through, since zones can be closed to one or the other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Walker {
+    Staff,
+    Guest,
+}
+
+/// Tiles inside zones closed to guests or to staff, rebuilt whenever zones change
+#[derive(Resource, Default, PartialEq)]
+pub struct ZoneAccessMap {
+    closed_to_guests: HashSet<IVec2>,
+    closed_to_staff: HashSet<IVec2>,
+}
+
+impl ZoneAccessMap {
+    /// Tiles `walker` may never step onto
+    pub fn closed_to(&self, walker: Walker) -> &HashSet<IVec2> {
+        match walker {
+            Walker::Staff => &self.closed_to_staff,
+            Walker::Guest => &self.closed_to_guests,
+        }
+    }
+}
+
+pub struct FailedPath {
+    pub start: IVec2,
+    pub goal: IVec2,
+    /// Real seconds since the search last failed
+    pub age: f32,
+}
+
+/// Recent searches that found no route, kept for the path debug overlay
+#[derive(Resource, Default)]
+pub struct FailedPaths {
+    pub attempts: VecDeque<FailedPath>,
+}
+
+impl FailedPaths {
+    /// A pawn retrying the same unreachable goal refreshes its entry instead of piling up
+    pub fn record(&mut self, start: IVec2, goal: IVec2) {
+        if let Some(attempt) = self
+            .attempts
+            .iter_mut()
+            .find(|attempt| attempt.start == start && attempt.goal == goal)
+        {
+            attempt.age = 0.0;
+            return;
+        }
+
+        self.attempts.push_back(FailedPath {
+            start,
+            goal,
+            age: 0.0,
+        });
+        while self.attempts.len() > MAX_FAILED_PATHS {
+            self.attempts.pop_front();
+        }
+    }
+}
+
+pub struct PathfindingPlugin;
+
+impl Plugin for PathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CongestionMap>()
+            .init_resource::<FailedPaths>()
+            .init_resource::<ZoneAccessMap>()
+            .add_systems(
+                Update,
+                (
+                    update_congestion_map,
+                    update_zone_access_map,
+                    expire_failed_paths,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn expire_failed_paths(time: Res<Time<Real>>, mut failed_paths: ResMut<FailedPaths>) {
+    if failed_paths.attempts.is_empty() {
+        return;
+    }
+
+    for attempt in failed_paths.attempts.iter_mut() {
+        attempt.age += time.delta_secs();
+    }
+    failed_paths
+        .attempts
+        .retain(|attempt| attempt.age < FAILED_PATH_SECONDS);
+}
+
+fn update_congestion_map(
+    pawn_query: Query<&GridPosition, With<Pawn>>,
+    mut congestion: ResMut<CongestionMap>,
+) {
+    congestion.pawns_per_tile.clear();
+
+    // A pawn covers a 2x2 block anchored at its grid position
+    for grid_pos in &pawn_query {
+        for tile in pawn_footprint(grid_pos.to_ivec2()) {
+            *congestion.pawns_per_tile.entry(tile).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Room detection touches zones often without changing who may enter them, so the map is
+/// only replaced (and paths replanned) when the closed tiles actually differ
+fn update_zone_access_map(
+    zone_query: Query<&Zone>,
+    changed_zones: Query<(), Changed<Zone>>,
+    mut removed_zones: RemovedComponents<Zone>,
+    mut access_map: ResMut<ZoneAccessMap>,
+) {
+    let removed = removed_zones.read().count() > 0;
+    if changed_zones.is_empty() && !removed {
+        return;
+    }
+
+    let mut rebuilt = ZoneAccessMap::default();
+    for zone in &zone_query {
+        if !zone.access.admits_guests() {
+            rebuilt.closed_to_guests.extend(zone.tiles.iter().copied());
+        }
+        if !zone.access.admits_staff() {
+            rebuilt.closed_to_staff.extend(zone.tiles.iter().copied());
+        }
+    }
+    access_map.set_if_neq(rebuilt);
+}
+
+pub fn is_walkable(
+    tile: IVec2,
+    building_map: &BuildingMap,
+    grid_settings: &GridSettings,
+    terrain: &Terrain,
+) -> bool {
+    tile.x >= 0
+        && tile.y >= 0
+        && tile.x < grid_settings.width
+        && tile.y < grid_settings.height
+        && !building_map.occupied.contains(&tile)
+        && !building_map.locked_doors.contains(&tile)
+        && !terrain.is_sea(tile)
+}
+
+/// Whether a pawn fits on `tile`: some 2x2 block covering it is walkable all over, so
+/// routes hug walls freely but never squeeze through a one-tile gap
+pub fn has_clearance(
+    tile: IVec2,
+    building_map: &BuildingMap,
+    grid_settings: &GridSettings,
+    terrain: &Terrain,
+) -> bool {
+    fits_pawn(tile, |covered| is_walkable(covered, building_map, grid_settings, terrain))
+}
+
+fn heuristic(a: IVec2, b: IVec2) -> u32 {
+    let d = (a - b).abs();
+    let (min, max) = (d.x.min(d.y) as u32, d.x.max(d.y) as u32);
+    DIAGONAL_COST * min + STRAIGHT_COST * (max - min)
+}
+
+/// A* over the tile grid. Walls, windows, furniture, locked doors and the sea block; other
+/// doors are passable, and cliffs can only be climbed at a ramp. Pawns are two tiles
+/// across, so every step needs room for their whole footprint. Crowded tiles cost more,
+/// so pawns prefer a wider or emptier route when one exists.
+/// The goal itself may be blocked (e.g. a wall being deconstructed); the path then
+/// ends on the last walkable tile next to it.
+/// Tiles in `closed` are never stepped onto, goal included, unless the walker is already
+/// standing among them (say, the zone was closed while they were inside) and must get out.
+pub fn find_path(
+    start: IVec2,
+    goal: IVec2,
+    building_map: &BuildingMap,
+    congestion: &CongestionMap,
+    grid_settings: &GridSettings,
+    terrain: &Terrain,
+    closed: &HashSet<IVec2>,
+) -> Option<Vec<IVec2>> {
+    if start == goal {
+        return Some(vec![goal]);
+    }
+
+    let no_tiles = HashSet::new();
+    let closed = if closed.contains(&start) {
+        &no_tiles
+    } else {
+        closed
+    };
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+    let mut best_cost: HashMap<IVec2, u32> = HashMap::new();
+
+    best_cost.insert(start, 0);
+    open.push(Reverse((heuristic(start, goal), start.x, start.y)));
+
+    let mut expanded = 0;
+
+    while let Some(Reverse((_, x, y))) = open.pop() {
+        let current = IVec2::new(x, y);
+
+        if current == goal {
+            let mut path = vec![current];
+            let mut node = current;
+            while let Some(&previous) = came_from.get(&node) {
+                if previous == start {
+                    break;
+                }
+                path.push(previous);
+                node = previous;
+            }
+            path.reverse();
+
+            // Don't ask the pawn to step into a blocked goal tile
+            if !is_walkable(goal, building_map, grid_settings, terrain) {
+                path.pop();
+            }
+            return Some(path);
+        }
+
+        expanded += 1;
+        if expanded > MAX_EXPANDED_NODES {
+            return None;
+        }
+
+        let current_cost = best_cost[&current];
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                let next = current + IVec2::new(dx, dy);
+                if next != goal && !has_clearance(next, building_map, grid_settings, terrain) {
+                    continue;
+                }
+                if closed.contains(&next) {
+                    continue;
+                }
+                if !terrain.can_step(current, next) {
+                    continue;
+                }
+
+                let diagonal = dx != 0 && dy != 0;
+                if diagonal {
+                    // No cutting corners past walls
+                    let side_a = current + IVec2::new(dx, 0);
+                    let side_b = current + IVec2::new(0, dy);
+                    let passable = |side| {
+                        has_clearance(side, building_map, grid_settings, terrain)
+                            && terrain.can_step(current, side)
+                            && !closed.contains(&side)
+                    };
+                    if !passable(side_a) || !passable(side_b) {
+                        continue;
+                    }
+                }
+
+                let step_cost = if diagonal {
+                    DIAGONAL_COST
+                } else {
+                    STRAIGHT_COST
+                };
+                let new_cost =
+                    current_cost + step_cost + congestion.pawns_at(next) * CONGESTION_COST;
+
+                if best_cost.get(&next).is_none_or(|&cost| new_cost < cost) {
+                    best_cost.insert(next, new_cost);
+                    came_from.insert(next, current);
+                    open.push(Reverse((new_cost + heuristic(next, goal), next.x, next.y)));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Every tile a pawn starting from one of `starts` could walk to, by the same rules as
+/// `find_path`. Diagonal steps need both sides open there, so straight steps reach
+/// everything they do.
+pub fn reachable_tiles(
+    starts: impl IntoIterator<Item = IVec2>,
+    building_map: &BuildingMap,
+    grid_settings: &GridSettings,
+    terrain: &Terrain,
+    closed: &HashSet<IVec2>,
+) -> HashSet<IVec2> {
+    let mut reached: HashSet<IVec2> = HashSet::new();
+    let mut frontier: VecDeque<IVec2> = VecDeque::new();
+    for start in starts {
+        if reached.insert(start) {
+            frontier.push_back(start);
+        }
+    }
+
+    while let Some(current) = frontier.pop_front() {
+        for offset in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+            let next = current + offset;
+            if reached.contains(&next)
+                || closed.contains(&next)
+                || !has_clearance(next, building_map, grid_settings, terrain)
+                || !terrain.can_step(current, next)
+            {
+                continue;
+            }
+            reached.insert(next);
+            frontier.push_back(next);
+        }
+    }
+
+    reached
+}
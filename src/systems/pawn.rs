@@ -1,16 +1,49 @@
 use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
 use crate::systems::grid::*;
+use crate::systems::pathfinding::{find_path, CongestionMap, FailedPaths, Walker, ZoneAccessMap};
+use crate::systems::pawn_names::generate_pawn_name;
+use crate::systems::terrain::Terrain;
+use crate::systems::work_visuals::HammeringAnimation;
+use crate::ui::{Localization, Notification};
 use bevy::prelude::*;
 use bevy::sprite::*;
+use std::collections::HashSet;
 
 const PAWN_SIZE: f32 = TILE_SIZE * 2.0; // Pawns occupy 2x2 tiles
+const PAWN_RADIUS: f32 = PAWN_SIZE * 0.4;
+/// Worn by pawns with every kind of work switched off
+const UNASSIGNED_COLOR: Color = Color::srgb(0.2, 0.6, 0.8);
+const REPLAN_INTERVAL: f32 = 1.0; // Seconds between re-routing around crowds
+const CROWD_SLOWDOWN: f32 = 0.1; // Speed lost per neighbouring pawn tile
+const MIN_CROWD_SPEED: f32 = 0.4;
+const SEPARATION_RATE: f32 = 8.0; // How quickly overlapping pawns are pushed apart
+/// A worker whose route hasn't shrunk by this much in STUCK_SECONDS is stuck
+const STUCK_PROGRESS: f32 = TILE_SIZE * 0.5;
+const STUCK_SECONDS: f32 = 4.0;
+/// Fresh routes tried before a stuck worker gives up on where they were going
+const STUCK_REPATHS: u32 = 2;
+/// How long a worker leaves a job they couldn't reach to everyone else
+const UNREACHABLE_JOB_SECONDS: f32 = 30.0;
 
 pub struct PawnPlugin;
 
 impl Plugin for PawnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_initial_pawns)
-            .add_systems(Update, (move_pawns, update_pawn_positions));
+        app.add_systems(Startup, spawn_initial_pawns).add_systems(
+            Update,
+            (
+                plan_pawn_paths,
+                move_pawns,
+                separate_pawns,
+                update_pawn_positions,
+                watch_for_stuck_pawns,
+                dress_pawns_for_role,
+            )
+                .chain()
+                .in_set(GameplaySet),
+        );
     }
 }
 
@@ -19,44 +52,355 @@ fn spawn_initial_pawns(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
+    let mut rng = rand::thread_rng();
+    let mut names = Vec::new();
+
     // Spawn 3 initial worker pawns
     for i in 0..3 {
         let x_offset = (i as f32 - 1.0) * PAWN_SIZE * 1.5;
+        let name = generate_pawn_name(&mut rng, &names);
+        names.push(name.clone());
+
+        spawn_pawn(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            Vec2::new(x_offset, 0.0),
+            name,
+        );
+    }
+}
 
-        commands.spawn((
+/// Spawns a worker ready to take jobs, used for the starting team and new hires
+pub fn spawn_pawn(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    position: Vec2,
+    name: String,
+) -> Entity {
+    commands
+        .spawn((
             Mesh2d(meshes.add(Circle::new(PAWN_SIZE * 0.4))),
-            MeshMaterial2d(materials.add(Color::srgb(0.2, 0.6, 0.8))),
-            Transform::from_xyz(x_offset, 0.0, 10.0),
+            MeshMaterial2d(materials.add(UNASSIGNED_COLOR)),
+            Transform::from_translation(position.extend(10.0)),
             Pawn {
-                name: format!("Worker {}", i + 1),
+                name,
                 move_speed: 100.0,
             },
             GridPosition::new(0, 0),
             CurrentJob::default(),
             WorkAssignments::default(),
-        ));
+            StaffNeeds::default(),
+            StaffSkills::default(),
+            JobHistory::default(),
+        ))
+        .id()
+}
+
+/// Pawns change into the uniform of their main role whenever their work priorities do.
+/// A pawn mid hammer-swing gets it once the swing is over.
+fn dress_pawns_for_role(
+    mut pawn_query: Query<
+        (
+            &WorkAssignments,
+            &MeshMaterial2d<ColorMaterial>,
+            Option<&mut HammeringAnimation>,
+        ),
+        (With<Pawn>, Changed<WorkAssignments>),
+    >,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (assignments, material_handle, hammering) in &mut pawn_query {
+        let color = assignments
+            .main_role()
+            .map_or(UNASSIGNED_COLOR, |role| role.uniform_color());
+        match hammering {
+            Some(mut hammering) => hammering.base_color = color,
+            None => {
+                if let Some(material) = materials.get_mut(&material_handle.0) {
+                    material.color = color;
+                }
+            }
+        }
+    }
+}
+
+// Plan (or re-plan) a tile path whenever the target moves, the map or zone access
+// changes, or enough time has passed that congestion may have shifted
+fn plan_pawn_paths(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&MovementTarget>,
+            Option<&mut PawnPath>,
+            Has<Guest>,
+        ),
+        Or<(With<Pawn>, With<Guest>)>,
+    >,
+    building_map: Res<BuildingMap>,
+    access_map: Res<ZoneAccessMap>,
+    congestion: Res<CongestionMap>,
+    grid_settings: Res<GridSettings>,
+    terrain: Res<Terrain>,
+    time: Res<Time>,
+    mut failed_paths: ResMut<FailedPaths>,
+) {
+    for (entity, transform, target, path, is_guest) in &mut pawn_query {
+        let Some(target) = target else {
+            if path.is_some() {
+                commands.entity(entity).remove::<PawnPath>();
+            }
+            continue;
+        };
+
+        if let Some(mut path) = path {
+            path.replan_timer -= time.delta_secs();
+            let stale = path.goal != target.target
+                || path.replan_timer <= 0.0
+                || building_map.is_changed()
+                || access_map.is_changed();
+            if !stale {
+                continue;
+            }
+        }
+
+        let walker = if is_guest {
+            Walker::Guest
+        } else {
+            Walker::Staff
+        };
+        let waypoints = match plan_waypoints(
+            transform.translation.truncate(),
+            target.target,
+            &building_map,
+            access_map.closed_to(walker),
+            &congestion,
+            &grid_settings,
+            &terrain,
+        ) {
+            Ok(waypoints) => waypoints,
+            Err(unrouted) => {
+                failed_paths.record(unrouted.start, unrouted.goal);
+                unrouted.fallback
+            }
+        };
+
+        commands.entity(entity).insert(PawnPath {
+            waypoints,
+            goal: target.target,
+            replan_timer: REPLAN_INTERVAL,
+        });
+    }
+}
+
+/// A search that found no route, and where the walker heads instead
+struct Unrouted {
+    start: IVec2,
+    goal: IVec2,
+    fallback: Vec<Vec2>,
+}
+
+fn plan_waypoints(
+    from: Vec2,
+    to: Vec2,
+    building_map: &BuildingMap,
+    closed: &HashSet<IVec2>,
+    congestion: &CongestionMap,
+    grid_settings: &GridSettings,
+    terrain: &Terrain,
+) -> Result<Vec<Vec2>, Unrouted> {
+    let to_grid = |pos: Vec2| {
+        world_to_grid(
+            pos,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+    };
+
+    let (Some(start), Some(goal)) = (to_grid(from), to_grid(to)) else {
+        return Ok(vec![to]);
+    };
+
+    // No route found: fall back to walking straight at the target, unless that would
+    // cut through a zone the walker isn't allowed in, in which case they wait where they are
+    let Some(tiles) = find_path(
+        start,
+        goal,
+        building_map,
+        congestion,
+        grid_settings,
+        terrain,
+        closed,
+    ) else {
+        let crosses_closed =
+            !closed.contains(&start) && tiles_along(start, goal).any(|tile| closed.contains(&tile));
+        return Err(Unrouted {
+            start,
+            goal,
+            fallback: vec![if crosses_closed { from } else { to }],
+        });
+    };
+
+    let mut waypoints: Vec<Vec2> = tiles
+        .iter()
+        .map(|tile| {
+            grid_to_world(
+                *tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        })
+        .collect();
+
+    // Finish on the exact target when it's reachable, rather than the tile centre
+    if !building_map.occupied.contains(&goal) {
+        waypoints.pop();
+        waypoints.push(to);
+    }
+
+    Ok(waypoints)
+}
+
+/// The tiles a straight line from `start` to `goal` passes over
+fn tiles_along(start: IVec2, goal: IVec2) -> impl Iterator<Item = IVec2> {
+    let steps = (goal - start).abs().max_element().max(1);
+    let delta = (goal - start).as_vec2();
+    (0..=steps).map(move |step| start + (delta * step as f32 / steps as f32).round().as_ivec2())
+}
+
+// Guests walk the same paths as staff, just at their own pace
+fn move_pawns(
+    mut query: Query<(
+        &mut Transform,
+        AnyOf<(&Pawn, &Guest)>,
+        &GridPosition,
+        &MovementTarget,
+        Option<&mut PawnPath>,
+    )>,
+    congestion: Res<CongestionMap>,
+    time: Res<Time>,
+) {
+    for (mut transform, walker, grid_pos, target, mut path) in &mut query {
+        let move_speed = match walker {
+            (Some(pawn), _) => pawn.move_speed,
+            (None, Some(guest)) => guest.move_speed,
+            (None, None) => continue,
+        };
+
+        // Crowded corridors slow everyone down; don't count the pawn's own tiles
+        let own_tiles = (PAWN_GRID_SIZE * PAWN_GRID_SIZE) as u32;
+        let others = congestion
+            .crowding_around(grid_pos.to_ivec2())
+            .saturating_sub(own_tiles);
+        let speed_factor = (1.0 / (1.0 + CROWD_SLOWDOWN * others as f32)).max(MIN_CROWD_SPEED);
+        let mut remaining_step = move_speed * speed_factor * time.delta_secs();
+
+        while remaining_step > 0.0 {
+            let waypoint = match path.as_deref() {
+                Some(path) => match path.waypoints.first() {
+                    Some(waypoint) => *waypoint,
+                    None => break, // Already at the end of the path
+                },
+                None => target.target,
+            };
+
+            let current_pos = transform.translation.truncate();
+            let direction = waypoint - current_pos;
+            let distance = direction.length();
+
+            if distance <= remaining_step {
+                transform.translation = waypoint.extend(transform.translation.z);
+                remaining_step -= distance;
+
+                match path.as_deref_mut() {
+                    Some(path) => {
+                        path.waypoints.remove(0);
+                    }
+                    None => break,
+                }
+            } else {
+                transform.translation += (direction / distance * remaining_step).extend(0.0);
+                break;
+            }
+        }
     }
 }
 
-fn move_pawns(mut query: Query<(&mut Transform, &Pawn, &MovementTarget)>, time: Res<Time>) {
-    for (mut transform, pawn, target) in &mut query {
-        let current_pos = transform.translation.truncate();
-        let direction = target.target - current_pos;
-        let distance = direction.length();
+// Soft collision: overlapping pawns nudge each other apart instead of stacking. Staff
+// away training aren't on the map to bump into.
+fn separate_pawns(
+    mut query: Query<(Entity, &mut Transform), (With<Pawn>, Without<InTraining>)>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    time: Res<Time>,
+) {
+    let positions: Vec<(Entity, Vec2)> = query
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation.truncate()))
+        .collect();
+
+    let strength = (SEPARATION_RATE * time.delta_secs()).min(1.0);
+
+    for (index, (entity, position)) in positions.iter().enumerate() {
+        let mut push = Vec2::ZERO;
+
+        for (other_index, (_, other_position)) in positions.iter().enumerate() {
+            if index == other_index {
+                continue;
+            }
 
-        if distance > 1.0 {
-            let movement = direction.normalize() * pawn.move_speed * time.delta_secs();
-            if movement.length() < distance {
-                transform.translation += movement.extend(0.0);
+            let offset = *position - *other_position;
+            let distance = offset.length();
+            let overlap = PAWN_RADIUS * 2.0 - distance;
+            if overlap <= 0.0 {
+                continue;
+            }
+
+            // Pawns standing exactly on top of each other split along a stable axis
+            let away = if distance > f32::EPSILON {
+                offset / distance
+            } else if index < other_index {
+                Vec2::X
             } else {
-                transform.translation = target.target.extend(transform.translation.z);
+                Vec2::NEG_X
+            };
+            push += away * overlap * 0.5;
+        }
+
+        if push == Vec2::ZERO {
+            continue;
+        }
+
+        let new_position = *position + push * strength;
+
+        // Never shove a pawn into a wall
+        let blocked = world_to_grid(
+            new_position,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+        .is_none_or(|tile| building_map.occupied.contains(&tile));
+
+        if !blocked {
+            if let Ok((_, mut transform)) = query.get_mut(*entity) {
+                transform.translation.x = new_position.x;
+                transform.translation.y = new_position.y;
             }
         }
     }
 }
 
 fn update_pawn_positions(
-    mut query: Query<(&Transform, &mut GridPosition), (With<Pawn>, Changed<Transform>)>,
+    mut query: Query<
+        (&Transform, &mut GridPosition),
+        (Or<(With<Pawn>, With<Guest>)>, Changed<Transform>),
+    >,
     grid_settings: Res<GridSettings>,
 ) {
     for (transform, mut grid_pos) in &mut query {
@@ -72,3 +416,152 @@ fn update_pawn_positions(
         }
     }
 }
+
+// Remaining walk along the planned route, or straight at the target without one
+fn remaining_route(position: Vec2, target: Vec2, path: Option<&PawnPath>) -> f32 {
+    let Some(path) = path.filter(|path| !path.waypoints.is_empty()) else {
+        return position.distance(target);
+    };
+
+    let mut remaining = position.distance(path.waypoints[0]);
+    for leg in path.waypoints.windows(2) {
+        remaining += leg[0].distance(leg[1]);
+    }
+    remaining
+}
+
+// Workers whose route stops shrinking get a fresh route, and if that doesn't help they
+// drop what they were heading for so the rest of the queue isn't held up behind them
+fn watch_for_stuck_pawns(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Pawn,
+            &Transform,
+            &mut CurrentJob,
+            Option<&MovementTarget>,
+            Option<&PawnPath>,
+            Option<&mut StuckWatch>,
+            Option<&mut UnreachableJob>,
+        ),
+        Without<OnBreak>,
+    >,
+    mut job_query: Query<
+        AnyOf<(
+            &mut ConstructionJob,
+            &mut DeconstructionJob,
+            &mut RepairJob,
+            &mut CleaningJob,
+            &mut Incident,
+            &mut RoomServiceOrder,
+            &mut Infestation,
+        )>,
+    >,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, pawn, transform, mut current_job, target, path, watch, unreachable) in
+        &mut pawn_query
+    {
+        if let Some(mut unreachable) = unreachable {
+            unreachable.seconds_left -= delta;
+            if unreachable.seconds_left <= 0.0 {
+                commands.entity(entity).remove::<UnreachableJob>();
+            }
+        }
+
+        let Some(target) = target else {
+            if watch.is_some() {
+                commands.entity(entity).remove::<StuckWatch>();
+            }
+            continue;
+        };
+
+        let remaining = remaining_route(transform.translation.truncate(), target.target, path);
+        let Some(mut watch) = watch.filter(|watch| watch.goal == target.target) else {
+            commands.entity(entity).insert(StuckWatch {
+                goal: target.target,
+                best_remaining: remaining,
+                stalled_seconds: 0.0,
+                repaths: 0,
+            });
+            continue;
+        };
+
+        // Standing at the target (say, on duty at a desk) isn't being stuck
+        if remaining <= TILE_SIZE || remaining < watch.best_remaining - STUCK_PROGRESS {
+            watch.best_remaining = remaining;
+            watch.stalled_seconds = 0.0;
+            continue;
+        }
+        // A new route around something can be longer; measure progress along that one
+        if remaining > watch.best_remaining + TILE_SIZE {
+            watch.best_remaining = remaining;
+        }
+
+        watch.stalled_seconds += delta;
+        if watch.stalled_seconds < STUCK_SECONDS {
+            continue;
+        }
+
+        watch.stalled_seconds = 0.0;
+        watch.best_remaining = remaining;
+        if watch.repaths < STUCK_REPATHS {
+            // With no path left, the next planning pass starts over from where they are
+            watch.repaths += 1;
+            commands.entity(entity).remove::<PawnPath>();
+            continue;
+        }
+
+        commands.entity(entity).remove::<(
+            MovementTarget,
+            PawnPath,
+            StuckWatch,
+            StaffingReception,
+            StaffingSpa,
+            WorkSpot,
+        )>();
+
+        let Some(job_id) = current_job.job_id.take() else {
+            continue;
+        };
+        if let Ok((construction, deconstruction, repair, cleaning, incident, room_service, pests)) =
+            job_query.get_mut(job_id)
+        {
+            if let Some(mut job) = construction {
+                job.assigned_pawn = None;
+            }
+            if let Some(mut job) = deconstruction {
+                job.assigned_pawn = None;
+            }
+            if let Some(mut job) = repair {
+                job.assigned_pawn = None;
+            }
+            if let Some(mut job) = cleaning {
+                job.assigned_pawn = None;
+            }
+            if let Some(mut incident) = incident {
+                incident.assigned_pawn = None;
+            }
+            if let Some(mut order) = room_service {
+                order.assigned_pawn = None;
+            }
+            if let Some(mut pests) = pests {
+                pests.assigned_pawn = None;
+            }
+        }
+        commands.entity(entity).insert(UnreachableJob {
+            job: job_id,
+            seconds_left: UNREACHABLE_JOB_SECONDS,
+        });
+
+        warn!("{} is stuck and gave up job {:?}", pawn.name, job_id);
+        notifications.send(Notification {
+            message: localization.format("staff.stuck", &[("name", pawn.name.clone())]),
+        });
+    }
+}
@@ -0,0 +1,31 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub const MAX_PAWN_NAME_LENGTH: usize = 24;
+
+const FIRST_NAMES: [&str; 24] = [
+    "Ada", "Bruno", "Carmen", "Dev", "Elena", "Felix", "Greta", "Hugo", "Iris", "Jonah", "Kiko",
+    "Lena", "Marco", "Nadia", "Omar", "Priya", "Quinn", "Rosa", "Sami", "Tess", "Umar", "Vera",
+    "Wes", "Yuki",
+];
+
+const LAST_NAMES: [&str; 24] = [
+    "Alvarez", "Baker", "Chen", "Dubois", "Eriksen", "Fischer", "Garcia", "Hale", "Ito",
+    "Jensen", "Kowalski", "Laine", "Moreau", "Novak", "Okafor", "Petrov", "Quint", "Rossi",
+    "Sato", "Tanaka", "Ueda", "Varga", "Weber", "Young",
+];
+
+/// Picks a random "First Last" name that nobody in `taken` already has.
+/// Falls back to numbering once the pools run dry.
+pub fn generate_pawn_name(rng: &mut impl Rng, taken: &[String]) -> String {
+    for _ in 0..32 {
+        let first = FIRST_NAMES.choose(rng).copied().unwrap_or("Worker");
+        let last = LAST_NAMES.choose(rng).copied().unwrap_or("Smith");
+        let name = format!("{} {}", first, last);
+        if !taken.contains(&name) {
+            return name;
+        }
+    }
+
+    format!("Worker {}", taken.len() + 1)
+}
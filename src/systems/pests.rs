@@ -0,0 +1,400 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::balance::Balance;
+use crate::systems::building::BuildingMap;
+use crate::systems::economy::Money;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::guests::{random_open_tile, zone_center, SatisfactionCause, SatisfactionEvent};
+use crate::systems::job_priorities::JobPriorities;
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::staff::{start_staff_breaks, JobFinishedEvent};
+use crate::systems::statistics::DayLog;
+use crate::systems::terrain::Terrain;
+use crate::systems::time_control::GameClock;
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Hourly odds that pests turn up somewhere they can get at
+const PEST_CHANCE_PER_HOUR: f64 = 0.03;
+/// Share of those odds left while a pest-control contract runs
+const CONTRACT_PEST_FACTOR: f64 = 0.25;
+/// Satisfaction a party loses for each hour spent at an amenity with pests in it
+const PEST_SATISFACTION_PER_HOUR: f32 = 0.04;
+const ANT_COLOR: Color = Color::srgb(0.35, 0.2, 0.1);
+const SEAGULL_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+/// Where each pest is drawn around the spot they gather at, in tiles
+const PEST_OFFSETS: [Vec2; 5] = [
+    Vec2::new(0.0, 0.0),
+    Vec2::new(0.4, 0.25),
+    Vec2::new(-0.35, 0.3),
+    Vec2::new(0.25, -0.4),
+    Vec2::new(-0.3, -0.3),
+];
+
+/// A pest-control firm on call, bought from the economy panel. Pests still turn up while
+/// it runs, just far less often.
+#[derive(Resource, Default)]
+pub struct PestControlContract {
+    pub hours_remaining: f32,
+}
+
+impl PestControlContract {
+    pub const COST: i32 = 1000;
+    pub const DURATION_HOURS: f32 = 168.0;
+
+    pub fn is_active(&self) -> bool {
+        self.hours_remaining > 0.0
+    }
+
+    /// How much less often pests turn up under contract (0.75 = 75% less)
+    pub fn reduction(&self) -> f32 {
+        1.0 - CONTRACT_PEST_FACTOR as f32
+    }
+
+    /// Pays for another week on top of whatever is left; returns false if it can't be
+    /// afforded
+    pub fn sign(&mut self, money: &mut Money) -> bool {
+        if !money.deduct(Self::COST) {
+            return false;
+        }
+
+        self.hours_remaining += Self::DURATION_HOURS;
+        true
+    }
+}
+
+pub struct PestsPlugin;
+
+impl Plugin for PestsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PestControlContract>()
+            .add_systems(
+                Update,
+                (
+                    tick_pest_control_contract,
+                    spawn_pests,
+                    assign_pest_controllers.after(start_staff_breaks),
+                    bother_visiting_guests,
+                    settle_infestations,
+                    draw_pest_markers,
+                )
+                    .in_set(GameplaySet),
+            )
+            // Seeing pests off is cleaning, so it advances in fixed ticks too
+            .add_systems(FixedUpdate, clear_pests.in_set(GameplaySet));
+    }
+}
+
+fn tick_pest_control_contract(
+    clock: Res<GameClock>,
+    mut contract: ResMut<PestControlContract>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    if elapsed <= 0.0 || !contract.is_active() {
+        return;
+    }
+    contract.hours_remaining = (contract.hours_remaining - elapsed).max(0.0);
+}
+
+/// Every game hour pests may move into one zone they can get at: ants into a food area,
+/// or seagulls onto an amenity opening straight outside at a resort by the sea. A zone
+/// only has one lot of pests at a time.
+fn spawn_pests(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    contract: Res<PestControlContract>,
+    terrain: Res<Terrain>,
+    adjacency: Res<RoomAdjacency>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut day_log: ResMut<DayLog>,
+    zone_query: Query<(Entity, &Zone)>,
+    infestation_query: Query<&Infestation>,
+    mut pending_hours: Local<f32>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    *pending_hours += elapsed;
+    if *pending_hours < 1.0 {
+        return;
+    }
+    *pending_hours -= 1.0;
+
+    let chance = if contract.is_active() {
+        PEST_CHANCE_PER_HOUR * CONTRACT_PEST_FACTOR
+    } else {
+        PEST_CHANCE_PER_HOUR
+    };
+    let mut rng = rand::thread_rng();
+    if !rng.gen_bool(chance) {
+        return;
+    }
+
+    let infested: HashSet<Entity> = infestation_query.iter().map(|pests| pests.zone).collect();
+    let seaside = terrain.has_sea();
+    let targets: Vec<(Entity, &Zone, PestKind)> = zone_query
+        .iter()
+        .filter(|(entity, _)| !infested.contains(entity))
+        .filter_map(|(entity, zone)| {
+            if zone.zone_type == ZoneType::Culinary {
+                return Some((entity, zone, PestKind::Ants));
+            }
+            let outdoors = zone.zone_type.is_amenity()
+                && zone
+                    .tiles
+                    .iter()
+                    .find_map(|tile| adjacency.room_at(*tile))
+                    .is_some_and(|room| adjacency.opens_outdoors(room));
+            (seaside && outdoors).then_some((entity, zone, PestKind::Seagulls))
+        })
+        .collect();
+    let Some(&(zone_entity, zone, kind)) = targets.choose(&mut rng) else {
+        return;
+    };
+
+    let location = random_open_tile(zone, &building_map, &mut rng)
+        .map(|tile| {
+            grid_to_world(
+                tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        })
+        .unwrap_or_else(|| zone_center(zone, &grid_settings));
+    commands.spawn(Infestation::new(kind, zone_entity, location));
+
+    let key = match kind {
+        PestKind::Ants => "pests.ants",
+        PestKind::Seagulls => "pests.seagulls",
+    };
+    let message = localization.format(key, &[("zone", zone.name.clone())]);
+    day_log.incident(clock.day(), message.clone());
+    notifications.send(Notification { message });
+}
+
+/// Idle workers with cleaning enabled go after the nearest pests in a zone staff may enter
+fn assign_pest_controllers(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &WorkAssignments,
+            Option<&UnreachableJob>,
+        ),
+        (
+            With<Pawn>,
+            Without<OnBreak>,
+            Without<InTraining>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+        ),
+    >,
+    mut infestation_query: Query<(Entity, &mut Infestation)>,
+    zone_query: Query<&Zone>,
+    priorities: Res<JobPriorities>,
+) {
+    for (pawn_entity, pawn_transform, mut current_job, work_assignments, unreachable) in
+        &mut pawn_query
+    {
+        if current_job.job_id.is_some()
+            || !work_assignments.can_do_work(WorkType::Cleaning)
+            || priorities.passes_over(work_assignments, WorkType::Cleaning)
+        {
+            continue;
+        }
+
+        let pawn_pos = pawn_transform.translation.truncate();
+        let nearest = infestation_query
+            .iter()
+            .filter(|(entity, pests)| {
+                pests.assigned_pawn.is_none()
+                    && unreachable.is_none_or(|unreachable| unreachable.job != *entity)
+                    && zone_query
+                        .get(pests.zone)
+                        .is_ok_and(|zone| zone.access.admits_staff())
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.location
+                    .distance(pawn_pos)
+                    .total_cmp(&b.location.distance(pawn_pos))
+            })
+            .map(|(entity, _)| entity);
+
+        let Some(infestation_entity) = nearest else {
+            continue;
+        };
+        if let Ok((_, mut pests)) = infestation_query.get_mut(infestation_entity) {
+            pests.assigned_pawn = Some(pawn_entity);
+            current_job.job_id = Some(infestation_entity);
+            commands.entity(pawn_entity).insert(MovementTarget {
+                target: pests.location,
+            });
+        }
+    }
+}
+
+/// Cleaners on the spot see the pests off
+fn clear_pests(
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &Transform, &CurrentJob, &StaffNeeds, &StaffSkills), With<Pawn>>,
+    mut infestation_query: Query<&mut Infestation>,
+    balance: Res<Balance>,
+    time: Res<Time>,
+) {
+    for (pawn_entity, pawn_transform, current_job, needs, skills) in &pawn_query {
+        let Some(job_id) = current_job.job_id else {
+            continue;
+        };
+        let Ok(mut pests) = infestation_query.get_mut(job_id) else {
+            continue;
+        };
+
+        let distance = pawn_transform
+            .translation
+            .truncate()
+            .distance(pests.location);
+        if distance < TILE_SIZE * 3.0 {
+            commands.entity(pawn_entity).remove::<MovementTarget>();
+
+            let cleared = balance.work.cleaning
+                * needs.work_speed()
+                * skills.speed(WorkType::Cleaning)
+                * time.delta_secs();
+            pests.work_done = (pests.work_done + cleared).min(1.0);
+        }
+    }
+}
+
+/// Each game hour, every party with someone at an amenity full of pests thinks a little
+/// less of their stay
+fn bother_visiting_guests(
+    clock: Res<GameClock>,
+    guest_query: Query<(Entity, &Guest)>,
+    infestation_query: Query<&Infestation>,
+    mut booking_query: Query<&mut Booking>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut pending_hours: Local<f32>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    *pending_hours += elapsed;
+    if *pending_hours < 1.0 {
+        return;
+    }
+    *pending_hours -= 1.0;
+
+    if infestation_query.is_empty() {
+        return;
+    }
+
+    // A party out together only loses out once
+    let mut bothered = HashSet::new();
+    for (guest_entity, guest) in &guest_query {
+        let GuestActivity::Visiting(zone) = guest.activity else {
+            continue;
+        };
+        let Some(pests) = infestation_query.iter().find(|pests| pests.zone == zone) else {
+            continue;
+        };
+
+        if bothered.insert(guest.room) {
+            if let Ok(mut booking) = booking_query.get_mut(guest.room) {
+                booking.satisfaction = (booking.satisfaction - PEST_SATISFACTION_PER_HOUR).max(0.0);
+            }
+        }
+        satisfaction_events.send(SatisfactionEvent {
+            room: guest.room,
+            guest: Some(guest_entity),
+            cause: SatisfactionCause::Pests(pests.kind),
+        });
+    }
+}
+
+/// Closes infestations that were cleared, wandered off after a day or whose zone is gone
+fn settle_infestations(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
+    mut infestation_query: Query<(Entity, &mut Infestation)>,
+    zone_query: Query<&Zone>,
+    mut pawn_query: Query<&mut CurrentJob, With<Pawn>>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    for (infestation_entity, mut pests) in &mut infestation_query {
+        if pests
+            .assigned_pawn
+            .is_some_and(|pawn| pawn_query.get(pawn).is_err())
+        {
+            pests.assigned_pawn = None;
+        }
+        pests.hours_left -= elapsed;
+
+        let zone = zone_query.get(pests.zone).ok();
+        if zone.is_some() && !pests.is_cleared() && pests.hours_left > 0.0 {
+            continue;
+        }
+
+        if let Some(zone) = zone {
+            let key = if pests.is_cleared() {
+                "pests.cleared"
+            } else {
+                "pests.moved_on"
+            };
+            notifications.send(Notification {
+                message: localization.format(key, &[("zone", zone.name.clone())]),
+            });
+        }
+
+        if let Some(pawn) = pests.assigned_pawn {
+            if let Ok(mut current_job) = pawn_query.get_mut(pawn) {
+                current_job.job_id = None;
+            }
+            commands.entity(pawn).remove::<MovementTarget>();
+            if pests.is_cleared() {
+                finished_jobs.send(JobFinishedEvent {
+                    pawn,
+                    job: infestation_entity,
+                    kind: JobKind::PestControl,
+                });
+            }
+        }
+        commands.entity(infestation_entity).despawn();
+    }
+}
+
+/// A little cluster of dots where the pests have gathered: brown ants or white gulls
+fn draw_pest_markers(infestation_query: Query<&Infestation>, mut gizmos: Gizmos) {
+    for pests in &infestation_query {
+        let (color, radius) = match pests.kind {
+            PestKind::Ants => (ANT_COLOR, 0.06),
+            PestKind::Seagulls => (SEAGULL_COLOR, 0.14),
+        };
+        for offset in PEST_OFFSETS {
+            gizmos.circle_2d(
+                Isometry2d::from_translation(pests.location + offset * TILE_SIZE),
+                TILE_SIZE * radius,
+                color,
+            );
+        }
+    }
+}
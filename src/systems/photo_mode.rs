@@ -0,0 +1,255 @@
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::systems::app_state::GameplaySet;
+use crate::systems::camera::CameraController;
+use crate::ui::UiInputBlocker;
+
+/// Photo mode zooms well past the normal camera limits
+pub const PHOTO_MIN_ZOOM: f32 = 0.1;
+pub const PHOTO_MAX_ZOOM: f32 = 10.0;
+
+const PHOTO_PAN_SPEED: f32 = 400.0;
+const PHOTO_ZOOM_STEP: f32 = 1.1;
+/// How quickly the camera catches up to its target; higher is snappier
+const PHOTO_SMOOTHING: f32 = 6.0;
+
+const SCREENSHOT_DIR: &str = "screenshots";
+
+#[derive(Resource, Default)]
+pub struct PhotoMode {
+    pub active: bool,
+    pub vignette: bool,
+    target_translation: Vec2,
+    target_scale: f32,
+    /// Visibility each UI root had before photo mode hid it, restored on exit
+    hidden_ui: HashMap<Entity, Visibility>,
+}
+
+#[derive(Component)]
+pub struct PhotoVignette;
+
+pub fn photo_mode_active(photo_mode: Res<PhotoMode>) -> bool {
+    photo_mode.active
+}
+
+pub struct PhotoModePlugin;
+
+impl Plugin for PhotoModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhotoMode>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_photo_vignette)
+            .add_systems(
+                Update,
+                (
+                    handle_photo_mode_keys,
+                    handle_screenshot_key,
+                    (hide_ui_in_photo_mode, photo_camera_control).run_if(photo_mode_active),
+                    update_photo_vignette,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_photo_vignette(mut commands: Commands) {
+    // Stacked translucent borders darken the screen edges more than the middle
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            GlobalZIndex(20),
+            PhotoVignette,
+        ))
+        .with_children(|parent| {
+            for border in [4.0, 8.0, 12.0, 16.0] {
+                parent.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        border: UiRect::all(Val::Percent(border)),
+                        ..default()
+                    },
+                    BorderColor(Color::srgba(0.0, 0.0, 0.0, 0.15)),
+                ));
+            }
+        });
+}
+
+fn handle_photo_mode_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut photo_mode: ResMut<PhotoMode>,
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    mut camera_query: Query<(&mut OrthographicProjection, &Transform, &CameraController)>,
+    mut ui_query: Query<&mut Visibility, With<Node>>,
+) {
+    if ui_blocker.text_input_active {
+        return;
+    }
+
+    let toggle = keyboard.just_pressed(KeyCode::F2)
+        || (photo_mode.active && keyboard.just_pressed(KeyCode::Escape));
+
+    if photo_mode.active && keyboard.just_pressed(KeyCode::KeyV) {
+        photo_mode.vignette = !photo_mode.vignette;
+    }
+
+    if !toggle {
+        return;
+    }
+
+    let Ok((mut projection, transform, controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    photo_mode.active = !photo_mode.active;
+
+    if photo_mode.active {
+        photo_mode.target_translation = transform.translation.truncate();
+        photo_mode.target_scale = projection.scale;
+        info!("Photo mode on: F2/Esc to exit, V for vignette, F12 for a screenshot");
+    } else {
+        // Bring the zoom back within the normal limits and put the UI back as it was
+        projection.scale = projection
+            .scale
+            .clamp(controller.min_zoom, controller.max_zoom);
+        for (entity, visibility) in photo_mode.hidden_ui.drain() {
+            if let Ok(mut current) = ui_query.get_mut(entity) {
+                *current = visibility;
+            }
+        }
+        info!("Photo mode off");
+    }
+
+    ui_blocker.photo_mode_blocking = photo_mode.active;
+    ui_blocker.recompute();
+}
+
+// Runs every frame so panels that spawn while in photo mode are hidden too
+fn hide_ui_in_photo_mode(
+    mut photo_mode: ResMut<PhotoMode>,
+    mut ui_query: Query<
+        (Entity, &mut Visibility),
+        (With<Node>, Without<Parent>, Without<PhotoVignette>),
+    >,
+) {
+    for (entity, mut visibility) in &mut ui_query {
+        photo_mode.hidden_ui.entry(entity).or_insert(*visibility);
+        visibility.set_if_neq(Visibility::Hidden);
+    }
+}
+
+fn photo_camera_control(
+    time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mut photo_mode: ResMut<PhotoMode>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+) {
+    let Ok((mut transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let mut pan_delta = Vec2::ZERO;
+    if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
+        pan_delta.y += 1.0;
+    }
+    if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
+        pan_delta.y -= 1.0;
+    }
+    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
+        pan_delta.x -= 1.0;
+    }
+    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
+        pan_delta.x += 1.0;
+    }
+
+    let scale = photo_mode.target_scale;
+    if pan_delta != Vec2::ZERO {
+        photo_mode.target_translation +=
+            pan_delta.normalize() * PHOTO_PAN_SPEED * time.delta_secs() * scale;
+    }
+
+    if mouse_button.pressed(MouseButton::Middle) {
+        for motion in mouse_motion.read() {
+            photo_mode.target_translation += Vec2::new(-motion.delta.x, motion.delta.y) * scale;
+        }
+    }
+
+    // Zoom multiplicatively so steps feel the same at every distance
+    for event in scroll_events.read() {
+        let factor = if event.y > 0.0 {
+            1.0 / PHOTO_ZOOM_STEP
+        } else if event.y < 0.0 {
+            PHOTO_ZOOM_STEP
+        } else {
+            1.0
+        };
+        photo_mode.target_scale =
+            (photo_mode.target_scale * factor).clamp(PHOTO_MIN_ZOOM, PHOTO_MAX_ZOOM);
+    }
+
+    // Ease towards the target for smooth, cinematic movement
+    let blend = 1.0 - (-PHOTO_SMOOTHING * time.delta_secs()).exp();
+    let current = transform.translation.truncate();
+    let next = current.lerp(photo_mode.target_translation, blend);
+    transform.translation.x = next.x;
+    transform.translation.y = next.y;
+    projection.scale += (photo_mode.target_scale - projection.scale) * blend;
+}
+
+fn update_photo_vignette(
+    photo_mode: Res<PhotoMode>,
+    mut vignette_query: Query<&mut Node, With<PhotoVignette>>,
+) {
+    if !photo_mode.is_changed() {
+        return;
+    }
+
+    if let Ok(mut node) = vignette_query.get_single_mut() {
+        node.display = if photo_mode.active && photo_mode.vignette {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn handle_screenshot_key(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+) {
+    if ui_blocker.text_input_active || !keyboard.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    if let Err(error) = std::fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("Failed to create screenshot directory: {}", error);
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let path = format!("{}/resort_{}.png", SCREENSHOT_DIR, timestamp);
+
+    info!("Saving screenshot to {}", path);
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
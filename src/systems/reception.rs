@@ -0,0 +1,319 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::time_control::GameClock;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// How close a party has to be to an open desk to be checked in
+pub const RECEPTION_REACH: f32 = TILE_SIZE * 4.0;
+/// Game hours one check-in takes a receptionist at full speed
+const CHECK_IN_HOURS: f32 = 0.25;
+/// How many tiles back from the console to look for the work spot, past the desk
+const WORK_SPOT_SEARCH: i32 = 3;
+/// Receptionists this close to their work spot are stood on it and kept there
+const WORK_SPOT_SNAP: f32 = TILE_SIZE;
+
+/// Little light in the console's corner: green open, amber checking someone in, red closed
+const INDICATOR_SIZE: f32 = TILE_SIZE * 0.35;
+const INDICATOR_OFFSET: Vec3 = Vec3::new(TILE_SIZE * 0.45, TILE_SIZE * 0.45, 0.5);
+const OPEN_COLOR: Color = Color::srgb(0.3, 0.85, 0.35);
+const BUSY_COLOR: Color = Color::srgb(0.95, 0.7, 0.2);
+const CLOSED_COLOR: Color = Color::srgb(0.85, 0.25, 0.25);
+/// The busy light blinks this many times a second while the paperwork is done
+const BUSY_BLINK_RATE: f32 = 2.0;
+
+#[derive(Component)]
+pub struct ReceptionIndicator;
+
+pub struct ReceptionPlugin;
+
+impl Plugin for ReceptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_reception_indicators,
+                locate_work_spots,
+                man_reception_desks,
+                check_in_guests,
+                direct_guests_to_open_desks,
+                update_reception_indicators,
+            )
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// The first free floor tile behind the console, on the side its screen faces, that
+/// isn't already another console's work spot
+fn work_spot(
+    console: IVec2,
+    orientation: FurnitureOrientation,
+    building_map: &BuildingMap,
+    taken: &HashSet<IVec2>,
+) -> Option<IVec2> {
+    (1..=WORK_SPOT_SEARCH)
+        .map(|step| console + orientation.facing() * step)
+        .find(|tile| !building_map.is_occupied(*tile) && !taken.contains(tile))
+        .filter(|tile| building_map.floors.contains(tile))
+}
+
+/// Where a console's receptionist stands: its work spot, or the console itself when
+/// there is no room behind the desk
+pub fn reception_post(
+    console: &ReceptionConsole,
+    console_pos: IVec2,
+    grid_settings: &GridSettings,
+) -> Vec2 {
+    grid_to_world(
+        console.work_spot.unwrap_or(console_pos),
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    )
+}
+
+fn spawn_reception_indicators(
+    mut commands: Commands,
+    console_query: Query<Entity, Added<ReceptionConsole>>,
+) {
+    for console in &console_query {
+        commands.entity(console).with_children(|parent| {
+            parent.spawn((
+                Sprite::from_color(CLOSED_COLOR, Vec2::splat(INDICATOR_SIZE)),
+                Transform::from_translation(INDICATOR_OFFSET),
+                ReceptionIndicator,
+            ));
+        });
+    }
+}
+
+// Walls and furniture going up or down behind a desk can move its work spot. Two desks
+// never share one, so their receptionists don't end up standing on each other.
+fn locate_work_spots(
+    building_map: Res<BuildingMap>,
+    added_query: Query<(), Added<ReceptionConsole>>,
+    mut console_query: Query<(
+        Entity,
+        &GridPosition,
+        &FurnitureOrientation,
+        &mut ReceptionConsole,
+    )>,
+) {
+    if !building_map.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    let mut consoles: Vec<_> = console_query.iter_mut().collect();
+    consoles.sort_by_key(|(entity, _, _, _)| *entity);
+
+    let mut taken = HashSet::new();
+    for (_, grid_pos, orientation, mut console) in consoles {
+        let spot = work_spot(grid_pos.to_ivec2(), *orientation, &building_map, &taken);
+        taken.extend(spot);
+        if console.work_spot != spot {
+            console.work_spot = spot;
+        }
+    }
+}
+
+/// Receptionists who reach their desk take their place behind it and stay put there.
+/// A desk is open while its receptionist is at their post.
+fn man_reception_desks(
+    mut commands: Commands,
+    grid_settings: Res<GridSettings>,
+    mut console_query: Query<(Entity, &GridPosition, &mut ReceptionConsole)>,
+    mut staff_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &StaffingReception,
+            Has<MovementTarget>,
+        ),
+        With<Pawn>,
+    >,
+) {
+    for (console_entity, console_pos, mut console) in &mut console_query {
+        let post = reception_post(&console, console_pos.to_ivec2(), &grid_settings);
+        let mut open = false;
+
+        for (pawn_entity, mut transform, staffing, walking) in &mut staff_query {
+            if staffing.desk_entity != console_entity {
+                continue;
+            }
+            let position = transform.translation.truncate();
+
+            if console.work_spot.is_none() {
+                // Nowhere to stand behind the desk, so near it will do
+                open |= position.distance(post) <= RECEPTION_REACH;
+                continue;
+            }
+            if position.distance(post) > WORK_SPOT_SNAP {
+                continue;
+            }
+
+            if position != post {
+                transform.translation.x = post.x;
+                transform.translation.y = post.y;
+            }
+            if walking {
+                commands
+                    .entity(pawn_entity)
+                    .remove::<(MovementTarget, PawnPath)>();
+            }
+            open = true;
+        }
+
+        if console.open != open {
+            console.open = open;
+        }
+    }
+}
+
+/// Each open desk checks in the nearest party in reach, one at a time. A checked-in
+/// party moves on to waiting for their room to be clean.
+fn check_in_guests(
+    clock: Res<GameClock>,
+    mut console_query: Query<(Entity, &Transform, &mut ReceptionConsole)>,
+    mut guest_query: Query<
+        (Entity, &Transform, &mut WaitingGuest),
+        (With<Guest>, Without<Departing>),
+    >,
+    staff_query: Query<(&StaffingReception, &StaffNeeds, &StaffSkills), With<Pawn>>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    let mut taken: HashSet<Entity> = console_query
+        .iter()
+        .filter_map(|(_, _, console)| console.serving)
+        .collect();
+
+    for (console_entity, console_transform, mut console) in &mut console_query {
+        // A party that walked out, or has already been seen to, frees the desk
+        if let Some(guest) = console.serving {
+            let still_waiting = guest_query
+                .get(guest)
+                .is_ok_and(|(_, _, waiting)| waiting.reason == WaitReason::CheckIn);
+            if !still_waiting {
+                console.serving = None;
+            }
+        }
+        // Paperwork waits while the receptionist is away
+        if !console.open {
+            continue;
+        }
+
+        if console.serving.is_none() {
+            let desk = console_transform.translation.truncate();
+            let next = guest_query
+                .iter()
+                .filter(|(guest, _, waiting)| {
+                    waiting.reason == WaitReason::CheckIn && !taken.contains(guest)
+                })
+                .map(|(guest, transform, _)| {
+                    (guest, transform.translation.truncate().distance(desk))
+                })
+                .filter(|(_, distance)| *distance <= RECEPTION_REACH)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(guest, _)| guest);
+            let Some(guest) = next else {
+                continue;
+            };
+
+            console.serving = Some(guest);
+            console.check_in_hours_left = CHECK_IN_HOURS;
+            taken.insert(guest);
+        }
+
+        let work_speed = staff_query
+            .iter()
+            .find(|(staffing, _, _)| staffing.desk_entity == console_entity)
+            .map_or(1.0, |(_, needs, skills)| {
+                needs.work_speed() * skills.speed(WorkType::Reception)
+            });
+        console.check_in_hours_left -= elapsed * work_speed;
+        if console.check_in_hours_left > 0.0 {
+            continue;
+        }
+
+        if let Some(guest) = console.serving.take() {
+            if let Ok((_, _, mut waiting)) = guest_query.get_mut(guest) {
+                *waiting = WaitingGuest::new(WaitReason::Cleaning);
+            }
+        }
+    }
+}
+
+/// Parties look for an open desk before queueing. One waiting at a closed desk moves on
+/// to the nearest open one, if there is one.
+fn direct_guests_to_open_desks(
+    mut commands: Commands,
+    console_query: Query<(&Transform, &ReceptionConsole)>,
+    guest_query: Query<
+        (Entity, &Transform, &WaitingGuest, Option<&MovementTarget>),
+        (With<Guest>, Without<Departing>),
+    >,
+) {
+    let open_desks: Vec<Vec2> = console_query
+        .iter()
+        .filter(|(_, console)| console.open)
+        .map(|(transform, _)| transform.translation.truncate())
+        .collect();
+    if open_desks.is_empty() {
+        return;
+    }
+
+    for (entity, transform, waiting, target) in &guest_query {
+        if waiting.reason != WaitReason::CheckIn {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let destination = target.map_or(position, |target| target.target);
+        if open_desks
+            .iter()
+            .any(|desk| desk.distance(destination) <= RECEPTION_REACH)
+        {
+            continue;
+        }
+
+        let nearest = open_desks
+            .iter()
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)));
+        if let Some(&desk) = nearest {
+            commands
+                .entity(entity)
+                .remove::<PawnPath>()
+                .insert(MovementTarget { target: desk });
+        }
+    }
+}
+
+fn update_reception_indicators(
+    time: Res<Time<Real>>,
+    console_query: Query<(&ReceptionConsole, &Children)>,
+    mut indicator_query: Query<&mut Sprite, With<ReceptionIndicator>>,
+) {
+    let blink = (time.elapsed_secs() * BUSY_BLINK_RATE * std::f32::consts::TAU).sin();
+
+    for (console, children) in &console_query {
+        let color = if !console.open {
+            CLOSED_COLOR
+        } else if console.is_busy() {
+            BUSY_COLOR.with_alpha(0.6 + 0.4 * blink)
+        } else {
+            OPEN_COLOR
+        };
+
+        for child in children.iter() {
+            if let Ok(mut sprite) = indicator_query.get_mut(*child) {
+                sprite.color = color;
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::guests::{SatisfactionCause, SatisfactionEvent};
+use crate::systems::incidents::fixture_within_reach;
+use crate::systems::room_detection::RoomAdjacency;
+use crate::systems::statistics::{DayLog, Review};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Reviews with at least this many stars count as good ones in the night audit
+pub const GOOD_REVIEW_STARS: u8 = 3;
+
+/// One party's verdict on their stay, left as they checked out or walked out
+#[derive(Debug, Clone)]
+pub struct GuestReview {
+    pub day: u32,
+    pub party: GuestParty,
+    /// The bedroom zone they stayed in, which may since have gone
+    pub room: Entity,
+    pub room_name: String,
+    /// From 1 to 5
+    pub stars: u8,
+    pub complaints: Vec<ReviewComplaint>,
+}
+
+impl GuestReview {
+    /// A review of the stay booked in `room`, raising everything the booking noted
+    pub fn of_stay(day: u32, room: Entity, zone: &Zone, booking: &Booking, stars: u8) -> Self {
+        Self {
+            day,
+            party: booking.party,
+            room,
+            room_name: zone.name.clone(),
+            stars: stars.clamp(1, 5),
+            complaints: booking.complaints.clone(),
+        }
+    }
+
+    /// Adds a complaint the booking hadn't noted yet
+    pub fn with_complaint(mut self, complaint: ReviewComplaint) -> Self {
+        if !self.complaints.contains(&complaint) {
+            self.complaints.push(complaint);
+        }
+        self
+    }
+
+    pub fn is_good(&self) -> bool {
+        self.stars >= GOOD_REVIEW_STARS
+    }
+
+    /// The complaints most worth acting on first
+    pub fn top_complaints(&self, count: usize) -> Vec<ReviewComplaint> {
+        let mut complaints = self.complaints.clone();
+        complaints.sort();
+        complaints.truncate(count);
+        complaints
+    }
+}
+
+/// The latest reviews, newest first, for the reviews panel
+#[derive(Resource, Debug, Default)]
+pub struct GuestReviews {
+    pub recent: VecDeque<GuestReview>,
+}
+
+impl GuestReviews {
+    /// Older reviews are dropped once this many are kept
+    pub const MAX_REVIEWS: usize = 30;
+
+    pub fn add(&mut self, review: GuestReview) {
+        self.recent.push_front(review);
+        self.recent.truncate(Self::MAX_REVIEWS);
+    }
+
+    /// Mean stars across the reviews kept, if there are any
+    pub fn average_stars(&self) -> Option<f32> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let total: u32 = self.recent.iter().map(|review| review.stars as u32).sum();
+        Some(total as f32 / self.recent.len() as f32)
+    }
+
+    /// The complaint raised in the most reviews kept, the more damning one on a tie
+    pub fn most_common_complaint(&self) -> Option<ReviewComplaint> {
+        let mut counts: Vec<(ReviewComplaint, usize)> = Vec::new();
+        for complaint in self.recent.iter().flat_map(|review| &review.complaints) {
+            match counts.iter_mut().find(|(counted, _)| counted == complaint) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*complaint, 1)),
+            }
+        }
+        counts
+            .into_iter()
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+            .map(|(complaint, _)| complaint)
+    }
+}
+
+/// Somewhere to leave reviews: each is tallied for the night audit and kept for the
+/// reviews panel
+#[derive(SystemParam)]
+pub struct ReviewWriter<'w> {
+    day_log: ResMut<'w, DayLog>,
+    reviews: ResMut<'w, GuestReviews>,
+}
+
+impl ReviewWriter<'_> {
+    pub fn write(&mut self, review: GuestReview) {
+        let verdict = if review.is_good() {
+            Review::Good
+        } else {
+            Review::Bad
+        };
+        self.day_log.review(review.day, verdict);
+        self.reviews.add(review);
+    }
+}
+
+pub struct ReviewsPlugin;
+
+impl Plugin for ReviewsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GuestReviews>()
+            .add_systems(Update, note_review_complaints.in_set(GameplaySet));
+    }
+}
+
+/// Keeps a tally on each booking of what the party has had to put up with. Shown to
+/// their room, a party also looks for a toilet in it or through one of its doors.
+fn note_review_complaints(
+    mut events: EventReader<SatisfactionEvent>,
+    adjacency: Res<RoomAdjacency>,
+    toilet_query: Query<&GridPosition, With<Toilet>>,
+    mut zone_query: Query<(&Zone, &mut Booking)>,
+) {
+    for event in events.read() {
+        let Ok((zone, mut booking)) = zone_query.get_mut(event.room) else {
+            continue;
+        };
+
+        let complaint = match event.cause {
+            SatisfactionCause::GreatRoom | SatisfactionCause::DisappointingRoom => {
+                let toilets: Vec<IVec2> = toilet_query.iter().map(|pos| pos.to_ivec2()).collect();
+                if !fixture_within_reach(zone, &adjacency, &toilets) {
+                    booking.complain(ReviewComplaint::NoBathroom);
+                }
+                (event.cause == SatisfactionCause::DisappointingRoom)
+                    .then_some(ReviewComplaint::DisappointingRoom)
+            }
+            SatisfactionCause::Complaint(kind) => Some(kind.into()),
+            SatisfactionCause::ComplaintIgnored => Some(ReviewComplaint::IgnoredComplaint),
+            SatisfactionCause::RoomServiceLate => Some(ReviewComplaint::LateRoomService),
+            SatisfactionCause::Renovation => Some(ReviewComplaint::Renovation),
+            SatisfactionCause::Pests(_) => Some(ReviewComplaint::Pests),
+            SatisfactionCause::ShabbyPublicSpace => Some(ReviewComplaint::ShabbyCorridors),
+            SatisfactionCause::RoomServiceDelivered => None,
+        };
+        if let Some(complaint) = complaint {
+            booking.complain(complaint);
+        }
+    }
+}
@@ -1,36 +1,131 @@
 use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::balance::Balance;
 use crate::systems::building::BuildingMap;
 use crate::systems::grid::*;
+use crate::systems::lighting::{with_lighting, LightMap, DARK_ROOM_LIGHT};
+use crate::systems::terrain::Terrain;
 use bevy::prelude::*;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub struct RoomDetectionPlugin;
 
 impl Plugin for RoomDetectionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.init_resource::<RoomAdjacency>().add_systems(
             Update,
             (
                 detect_rooms,
                 auto_assign_bedroom_zones,
                 auto_assign_lobby_zones,
+                auto_assign_staff_room_zones,
+                auto_assign_amenity_zones,
+                assess_bedroom_privacy,
+                rate_public_spaces,
             )
-                .chain(),
+                .chain()
+                .in_set(GameplaySet),
         );
     }
 }
 
+/// How the detected rooms connect to each other through their doors and walls, rebuilt with
+/// the rooms so guest pathing, privacy, distance scoring and noise can ask about connectivity
+#[derive(Resource, Default, Debug)]
+pub struct RoomAdjacency {
+    /// Rooms each room opens onto through a shared door
+    pub neighbors: HashMap<Entity, HashSet<Entity>>,
+    /// Rooms back to back with each room, with a single wall between them
+    pub wall_neighbors: HashMap<Entity, HashSet<Entity>>,
+    /// Rooms on either side of each door; a door with only one leads out of every room
+    pub door_rooms: HashMap<Entity, Vec<Entity>>,
+    /// Which room each indoor tile belongs to
+    pub tile_rooms: HashMap<IVec2, Entity>,
+}
+
+impl RoomAdjacency {
+    pub fn room_at(&self, tile: IVec2) -> Option<Entity> {
+        self.tile_rooms.get(&tile).copied()
+    }
+
+    pub fn neighbors(&self, room: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.neighbors.get(&room).into_iter().flatten().copied()
+    }
+
+    pub fn wall_neighbors(&self, room: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.wall_neighbors
+            .get(&room)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// Whether the room has a door that doesn't lead into another room, usually outdoors
+    pub fn opens_outdoors(&self, room: Entity) -> bool {
+        self.door_rooms
+            .values()
+            .any(|rooms| rooms.len() == 1 && rooms[0] == room)
+    }
+
+    /// Fewest doors walked through to get from one room to another, if they connect at all
+    pub fn door_distance(&self, from: Entity, to: Entity) -> Option<usize> {
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([(from, 0)]);
+        while let Some((room, distance)) = queue.pop_front() {
+            if room == to {
+                return Some(distance);
+            }
+            for neighbor in self.neighbors(room) {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The four tiles sharing an edge with a position
+const NEIGHBOR_OFFSETS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+
+/// A public space narrower than this on average, across and along, feels cramped
+const PUBLIC_SPACE_MIN_WIDTH: f32 = 2.0;
+/// Share of a public space that needs a floor laid
+const PUBLIC_SPACE_FLOORED_SHARE: f32 = 0.75;
+/// A public space wants a furniture piece, or its worth in paint, for every this many tiles
+const PUBLIC_SPACE_TILES_PER_DECOR: usize = 12;
+
 /// Detects enclosed rooms by finding connected open spaces surrounded by walls
 fn detect_rooms(
     mut commands: Commands,
     building_map: Res<BuildingMap>,
     grid_settings: Res<GridSettings>,
+    terrain: Res<Terrain>,
+    mut adjacency: ResMut<RoomAdjacency>,
     // Only re-detect when buildings change
-    wall_query: Query<&GridPosition, (With<Wall>, Changed<GridPosition>)>,
+    structure_query: Query<
+        (),
+        (
+            Or<(With<Wall>, With<Door>, With<crate::components::Window>)>,
+            Changed<GridPosition>,
+        ),
+    >,
+    // A fresh coat of paint changes how the rooms score
+    repainted_query: Query<(), Or<(Changed<Wall>, Changed<Floor>)>>,
+    // Locking or unlocking a door opens or closes off the rooms either side
+    door_mode_query: Query<(), Changed<DoorMode>>,
+    door_query: Query<&DoorMode, With<Door>>,
+    window_query: Query<&GridPosition, With<crate::components::Window>>,
+    wall_query: Query<&Wall>,
+    floor_query: Query<(&GridPosition, &Floor)>,
     existing_rooms: Query<Entity, With<Room>>,
 ) {
-    // Only run detection if walls have changed
-    if wall_query.is_empty() {
+    // Only run detection if walls, doors, windows, paint or the views out have changed
+    if structure_query.is_empty()
+        && repainted_query.is_empty()
+        && door_mode_query.is_empty()
+        && !terrain.is_changed()
+    {
         return;
     }
 
@@ -39,22 +134,120 @@ fn detect_rooms(
         commands.entity(room_entity).despawn();
     }
 
-    // Find all enclosed rooms
-    let rooms = find_enclosed_rooms(&building_map, &grid_settings);
+    // Find all enclosed rooms, and the open ground reaching the map edge
+    let (room_tiles, outdoors) = find_enclosed_rooms(&building_map, &grid_settings);
+
+    let mut rooms: Vec<(Entity, Room)> = room_tiles
+        .into_iter()
+        .map(|tiles| (commands.spawn_empty().id(), Room::new(tiles)))
+        .collect();
+
+    *adjacency = RoomAdjacency::default();
+    for (room_entity, room) in &rooms {
+        for tile in &room.tiles {
+            adjacency.tile_rooms.insert(*tile, *room_entity);
+        }
+    }
+
+    let windows: HashSet<IVec2> = window_query.iter().map(|pos| pos.to_ivec2()).collect();
+    let painted_floors: HashSet<IVec2> = floor_query
+        .iter()
+        .filter(|(_, floor)| floor.tint.is_some())
+        .map(|(pos, _)| pos.to_ivec2())
+        .collect();
+    let in_bounds = |pos: IVec2| {
+        pos.x >= 0 && pos.y >= 0 && pos.x < grid_settings.width && pos.y < grid_settings.height
+    };
+
+    let mut wall_neighbors: HashMap<Entity, HashSet<Entity>> = HashMap::new();
+    for (room_entity, room) in &mut rooms {
+        let mut walls = HashSet::new();
+        for tile in &room.tiles {
+            for offset in NEIGHBOR_OFFSETS {
+                let neighbor = *tile + offset;
+
+                let wall = building_map.walls.get(&neighbor);
+                if let Some(&wall) = wall {
+                    walls.insert(wall);
+                }
+                let door = building_map.doors.get(&neighbor).copied();
+                let locked =
+                    door.and_then(|door| door_query.get(door).ok()) == Some(&DoorMode::Locked);
+
+                // A locked door closes off its doorway like a wall
+                if wall.is_some() || locked {
+                    if let Some(beyond) = adjacency.room_at(neighbor + offset) {
+                        if beyond != *room_entity {
+                            wall_neighbors
+                                .entry(*room_entity)
+                                .or_default()
+                                .insert(beyond);
+                        }
+                    }
+                } else if let Some(door) = door {
+                    // Door blueprints hold the spot too, but can't be walked through yet
+                    if door_query.contains(door) && !room.doors.contains(&door) {
+                        room.doors.push(door);
+                    }
+                } else if windows.contains(&neighbor) {
+                    // A window looks outdoors when the far side is open ground or off the map
+                    let beyond = neighbor + offset;
+                    if !in_bounds(beyond) || outdoors.contains(&beyond) {
+                        room.has_exterior_window = true;
+                        let view = terrain.view_from(neighbor, offset, &building_map);
+                        room.scenic_view = room.scenic_view.max(view);
+                    }
+                }
+            }
+        }
+
+        let painted_walls = walls
+            .iter()
+            .filter(|wall| wall_query.get(**wall).is_ok_and(|wall| wall.tint.is_some()))
+            .count();
+        let painted_tiles = room
+            .tiles
+            .iter()
+            .filter(|tile| painted_floors.contains(*tile))
+            .count();
+        room.painted_share =
+            (painted_walls + painted_tiles) as f32 / (walls.len() + room.tiles.len()) as f32;
+
+        for door in &room.doors {
+            adjacency
+                .door_rooms
+                .entry(*door)
+                .or_default()
+                .push(*room_entity);
+        }
+    }
+
+    let mut neighbors: HashMap<Entity, HashSet<Entity>> = HashMap::new();
+    for door_rooms in adjacency.door_rooms.values() {
+        for &room in door_rooms {
+            let others = door_rooms.iter().copied().filter(|other| *other != room);
+            neighbors.entry(room).or_default().extend(others);
+        }
+    }
+    adjacency.neighbors = neighbors;
+    adjacency.wall_neighbors = wall_neighbors;
 
     // Spawn room entities
-    for room_tiles in rooms {
-        commands.spawn(Room::new(room_tiles));
+    for (room_entity, mut room) in rooms {
+        room.adjacent_rooms = adjacency.neighbors(room_entity).collect();
+        commands.entity(room_entity).insert(room);
     }
 }
 
-/// Flood-fill algorithm to find enclosed rooms
+/// Flood-fill algorithm to find enclosed rooms. Also returns the open tiles that reach the
+/// map edge, which count as outdoors.
 fn find_enclosed_rooms(
     building_map: &BuildingMap,
     grid_settings: &GridSettings,
-) -> Vec<HashSet<IVec2>> {
+) -> (Vec<HashSet<IVec2>>, HashSet<IVec2>) {
     let mut visited = HashSet::new();
     let mut rooms = Vec::new();
+    let mut outdoors = HashSet::new();
 
     // Check every tile in the grid
     for y in 0..grid_settings.height {
@@ -62,36 +255,33 @@ fn find_enclosed_rooms(
             let pos = IVec2::new(x, y);
 
             // Skip if already visited, occupied by a wall, or has a door
-            if visited.contains(&pos)
-                || building_map.is_occupied(pos)
-                || building_map.doors.contains_key(&pos)
-            {
+            if visited.contains(&pos) || building_map.divides_rooms(pos) {
                 continue;
             }
 
             // Flood fill from this position
-            if let Some(room_tiles) =
-                flood_fill_room(pos, building_map, grid_settings, &mut visited)
-            {
-                if room_tiles.len() >= 4 {
-                    // Minimum room size
-                    rooms.push(room_tiles);
-                }
+            let (tiles, is_enclosed) =
+                flood_fill_room(pos, building_map, grid_settings, &mut visited);
+            if !is_enclosed {
+                outdoors.extend(tiles);
+            } else if tiles.len() >= 4 {
+                // Minimum room size
+                rooms.push(tiles);
             }
         }
     }
 
-    rooms
+    (rooms, outdoors)
 }
 
-/// Flood fill from a position to find all connected open tiles
-/// Returns None if the area is not enclosed (reaches map edge)
+/// Flood fill from a position to find all connected open tiles, and whether they are
+/// enclosed (false when they reach the map edge)
 fn flood_fill_room(
     start_pos: IVec2,
     building_map: &BuildingMap,
     grid_settings: &GridSettings,
     visited: &mut HashSet<IVec2>,
-) -> Option<HashSet<IVec2>> {
+) -> (HashSet<IVec2>, bool) {
     let mut room_tiles = HashSet::new();
     let mut queue = VecDeque::new();
     queue.push_back(start_pos);
@@ -117,14 +307,9 @@ fn flood_fill_room(
         room_tiles.insert(pos);
 
         // Check all four neighbors
-        let neighbors = [
-            pos + IVec2::new(1, 0),
-            pos + IVec2::new(-1, 0),
-            pos + IVec2::new(0, 1),
-            pos + IVec2::new(0, -1),
-        ];
-
-        for neighbor in neighbors {
+        for offset in NEIGHBOR_OFFSETS {
+            let neighbor = pos + offset;
+
             // Skip if out of bounds
             if neighbor.x < 0
                 || neighbor.x >= grid_settings.width
@@ -135,10 +320,7 @@ fn flood_fill_room(
             }
 
             // Skip if already visited, occupied by a wall, or has a door (doors divide rooms)
-            if visited.contains(&neighbor)
-                || building_map.is_occupied(neighbor)
-                || building_map.doors.contains_key(&neighbor)
-            {
+            if visited.contains(&neighbor) || building_map.divides_rooms(neighbor) {
                 continue;
             }
 
@@ -146,40 +328,56 @@ fn flood_fill_room(
         }
     }
 
-    // Only return the room if it's properly enclosed
-    if is_enclosed {
-        Some(room_tiles)
-    } else {
-        None
-    }
+    (room_tiles, is_enclosed)
 }
 
 /// Automatically assigns bedroom zones to rooms that contain beds
 fn auto_assign_bedroom_zones(
     mut commands: Commands,
     room_query: Query<(Entity, &Room), Without<Zone>>,
-    bed_query: Query<&GridPosition, With<Bed>>,
+    bed_query: Query<(&GridPosition, &Bed)>,
     furniture_query: Query<(&GridPosition, &Furniture)>,
     mut existing_zones: Query<(Entity, &mut Zone)>,
+    balance: Res<Balance>,
+    light_map: Res<LightMap>,
 ) {
     for (room_entity, room) in &room_query {
         // Check if this room contains a bed
-        let has_bed = bed_query
+        let beds_in_room: Vec<_> = bed_query
             .iter()
-            .any(|bed_pos| room.contains_tile(bed_pos.to_ivec2()));
+            .filter(|(bed_pos, _)| room.contains_tile(bed_pos.to_ivec2()))
+            .collect();
 
-        if !has_bed {
+        if beds_in_room.is_empty() {
             continue;
         }
 
+        // How many guests the beds sleep decides which parties can book the room
+        let capacity: u8 = beds_in_room
+            .iter()
+            .map(|(_, bed)| bed.bed_type.capacity())
+            .sum();
+
         // Count furniture in this room for quality calculation
         let furniture_in_room: Vec<_> = furniture_query
             .iter()
             .filter(|(pos, _)| room.contains_tile(pos.to_ivec2()))
             .collect();
 
-        // Calculate zone quality based on room size and furniture
-        let quality = calculate_bedroom_quality(room.tile_count(), furniture_in_room.len());
+        // Calculate zone quality based on room size and furniture. A view can't make up
+        // for a room too dark to see it from.
+        let quality = with_lighting(
+            with_view(
+                calculate_bedroom_quality(
+                    &balance,
+                    room.tile_count(),
+                    furniture_in_room.len() + room.decor_bonus(),
+                ),
+                room,
+            ),
+            &light_map,
+            &room.tiles,
+        );
 
         // Check if a zone already exists for this room
         let mut zone_exists = false;
@@ -190,6 +388,7 @@ fn auto_assign_bedroom_zones(
                 // Update existing zone
                 zone.tiles = room.tiles.clone();
                 zone.quality = quality;
+                zone.capacity = capacity;
                 zone_exists = true;
                 break;
             }
@@ -203,26 +402,34 @@ fn auto_assign_bedroom_zones(
             );
             zone.tiles = room.tiles.clone();
             zone.quality = quality;
+            zone.capacity = capacity;
 
-            commands.spawn(zone);
+            commands.spawn((zone, Cleanliness::default()));
         }
     }
 }
 
+/// Guests pay more for a window onto the sea or over a cliff, so a view raises a room a tier
+fn with_view(quality: ZoneQuality, room: &Room) -> ZoneQuality {
+    if room.scenic_view.is_some() {
+        quality.raised()
+    } else {
+        quality
+    }
+}
+
 /// Calculate bedroom quality based on size and furniture count
-fn calculate_bedroom_quality(tile_count: usize, furniture_count: usize) -> ZoneQuality {
+fn calculate_bedroom_quality(
+    balance: &Balance,
+    tile_count: usize,
+    furniture_count: usize,
+) -> ZoneQuality {
     // Basic: Has a bed and minimum size
-    if tile_count < 12 {
+    if tile_count < ZoneType::GuestBedroom.requirements().min_tiles {
         return ZoneQuality::None;
     }
 
-    // Quality based on furniture
-    match furniture_count {
-        0..=1 => ZoneQuality::Basic,     // Just a bed
-        2..=3 => ZoneQuality::Good,      // Bed + nightstand/dresser
-        4..=5 => ZoneQuality::Excellent, // Bed + multiple furniture
-        _ => ZoneQuality::Luxury,        // Fully furnished
-    }
+    balance.quality.bedroom.rate(tile_count, furniture_count)
 }
 
 /// Automatically assigns lobby zones to rooms that contain reception consoles
@@ -232,6 +439,7 @@ fn auto_assign_lobby_zones(
     console_query: Query<&GridPosition, With<ReceptionConsole>>,
     furniture_query: Query<(&GridPosition, &Furniture)>,
     mut existing_zones: Query<(Entity, &mut Zone)>,
+    balance: Res<Balance>,
 ) {
     for (room_entity, room) in &room_query {
         // Check if this room contains a reception console
@@ -250,7 +458,14 @@ fn auto_assign_lobby_zones(
             .collect();
 
         // Calculate zone quality based on room size and furniture
-        let quality = calculate_lobby_quality(room.tile_count(), furniture_in_room.len());
+        let quality = with_view(
+            calculate_lobby_quality(
+                &balance,
+                room.tile_count(),
+                furniture_in_room.len() + room.decor_bonus(),
+            ),
+            room,
+        );
 
         // Check if a zone already exists for this room
         let mut zone_exists = false;
@@ -278,20 +493,342 @@ fn auto_assign_lobby_zones(
 }
 
 /// Calculate lobby quality based on size and furniture count
-fn calculate_lobby_quality(tile_count: usize, furniture_count: usize) -> ZoneQuality {
+fn calculate_lobby_quality(
+    balance: &Balance,
+    tile_count: usize,
+    furniture_count: usize,
+) -> ZoneQuality {
     // Basic: Has a reception console and minimum size
-    if tile_count < 15 {
+    if tile_count < ZoneType::Lobby.requirements().min_tiles {
         return ZoneQuality::None;
     }
 
-    // Quality based on size and furniture
-    if tile_count >= 40 && furniture_count >= 5 {
-        ZoneQuality::Luxury
-    } else if tile_count >= 30 && furniture_count >= 4 {
-        ZoneQuality::Excellent
-    } else if tile_count >= 20 && furniture_count >= 2 {
-        ZoneQuality::Good
+    balance.quality.lobby.rate(tile_count, furniture_count)
+}
+
+/// Automatically assigns staff room zones to rooms with staff lockers or a staff couch
+fn auto_assign_staff_room_zones(
+    mut commands: Commands,
+    room_query: Query<(Entity, &Room), Without<Zone>>,
+    staff_furniture_query: Query<&GridPosition, Or<(With<StaffLocker>, With<StaffCouch>)>>,
+    furniture_query: Query<(&GridPosition, &Furniture)>,
+    mut existing_zones: Query<(Entity, &mut Zone)>,
+    balance: Res<Balance>,
+) {
+    for (room_entity, room) in &room_query {
+        let has_staff_furniture = staff_furniture_query
+            .iter()
+            .any(|pos| room.contains_tile(pos.to_ivec2()));
+
+        if !has_staff_furniture {
+            continue;
+        }
+
+        let furniture_count = furniture_query
+            .iter()
+            .filter(|(pos, _)| room.contains_tile(pos.to_ivec2()))
+            .count();
+        let quality = calculate_staff_room_quality(
+            &balance,
+            room.tile_count(),
+            furniture_count + room.decor_bonus(),
+        );
+
+        // Check if a zone already exists for this room
+        let mut zone_exists = false;
+        for (_, mut zone) in &mut existing_zones {
+            if zone.zone_type == ZoneType::StaffRoom
+                && zone.tiles.iter().any(|tile| room.contains_tile(*tile))
+            {
+                zone.tiles = room.tiles.clone();
+                zone.quality = quality;
+                zone_exists = true;
+                break;
+            }
+        }
+
+        if !zone_exists {
+            let mut zone = Zone::new(
+                ZoneType::StaffRoom,
+                format!("Staff Room {}", room_entity.index()),
+            );
+            zone.tiles = room.tiles.clone();
+            zone.quality = quality;
+
+            commands.spawn(zone);
+        }
+    }
+}
+
+/// Staff rooms only need to be big enough to sit down in; more furniture makes them nicer
+fn calculate_staff_room_quality(
+    balance: &Balance,
+    tile_count: usize,
+    furniture_count: usize,
+) -> ZoneQuality {
+    if tile_count < ZoneType::StaffRoom.requirements().min_tiles {
+        return ZoneQuality::None;
+    }
+
+    balance.quality.staff_room.rate(tile_count, furniture_count)
+}
+
+/// Which amenity a room's furniture advertises: a massage table or sauna makes a spa,
+/// a tub makes a pool room and desks or chairs make a dining room. Bathrooms (anything
+/// with a toilet) don't count.
+/// Goes by catalog tags so mod furniture counts the same as the stock pieces.
+fn amenity_for_furniture(furniture: &[FurnitureType]) -> Option<ZoneType> {
+    let has_tag = |tag: &str| furniture.iter().any(|f| f.definition().has_tag(tag));
+
+    if has_tag("toilet") {
+        None
+    } else if has_tag("massage") || has_tag("sauna") {
+        Some(ZoneType::Spa)
+    } else if has_tag("tub") {
+        Some(ZoneType::Relaxation)
+    } else if has_tag("desk") || has_tag("chair") {
+        Some(ZoneType::Culinary)
     } else {
-        ZoneQuality::Basic
+        None
+    }
+}
+
+/// Automatically assigns amenity zones to furnished rooms that aren't bedrooms or lobbies
+fn auto_assign_amenity_zones(
+    mut commands: Commands,
+    room_query: Query<(Entity, &Room), Without<Zone>>,
+    furniture_query: Query<(&GridPosition, &FurnitureType), With<Furniture>>,
+    mut existing_zones: Query<(Entity, &mut Zone)>,
+    balance: Res<Balance>,
+) {
+    for (room_entity, room) in &room_query {
+        let furniture_in_room: Vec<FurnitureType> = furniture_query
+            .iter()
+            .filter(|(pos, _)| room.contains_tile(pos.to_ivec2()))
+            .map(|(_, furniture_type)| *furniture_type)
+            .collect();
+
+        // Beds, consoles and staff furniture are handled by their own passes
+        if furniture_in_room.iter().any(|f| {
+            let definition = f.definition();
+            ["bed", "double_bed", "reception", "locker", "couch"]
+                .iter()
+                .any(|tag| definition.has_tag(tag))
+        }) {
+            continue;
+        }
+
+        let Some(zone_type) = amenity_for_furniture(&furniture_in_room) else {
+            continue;
+        };
+
+        if room.tile_count() < zone_type.requirements().min_tiles {
+            continue;
+        }
+
+        // Amenities are rated like lobbies, on size and how well furnished they are
+        let quality = with_view(
+            calculate_lobby_quality(
+                &balance,
+                room.tile_count(),
+                furniture_in_room.len() + room.decor_bonus(),
+            ),
+            room,
+        );
+
+        // Check if a zone already exists for this room
+        let mut zone_exists = false;
+        for (_, mut zone) in &mut existing_zones {
+            if zone.zone_type.is_amenity()
+                && zone.tiles.iter().any(|tile| room.contains_tile(*tile))
+            {
+                // Update existing zone, which may now advertise something else
+                zone.zone_type = zone_type;
+                zone.tiles = room.tiles.clone();
+                zone.quality = quality;
+                zone_exists = true;
+                break;
+            }
+        }
+
+        if !zone_exists {
+            let mut zone = Zone::new(
+                zone_type,
+                format!("{} {}", zone_type.name(), room_entity.index()),
+            );
+            zone.tiles = room.tiles.clone();
+            zone.quality = quality;
+
+            commands.spawn((zone, ZoneVisitors::default()));
+        }
+    }
+}
+
+/// Checks every guest bedroom is a room of its own: walled in, with a door, apart from the
+/// lobby and not somewhere people walk through to reach other rooms
+fn assess_bedroom_privacy(
+    mut commands: Commands,
+    adjacency: Res<RoomAdjacency>,
+    room_query: Query<&Room>,
+    zone_query: Query<(Entity, &Zone, Option<&PrivacyIssue>)>,
+    changed_zones: Query<(), Changed<Zone>>,
+) {
+    if !adjacency.is_changed() && changed_zones.is_empty() {
+        return;
+    }
+
+    let lobby_tiles: HashSet<IVec2> = zone_query
+        .iter()
+        .filter(|(_, zone, _)| zone.zone_type == ZoneType::Lobby)
+        .flat_map(|(_, zone, _)| zone.tiles.iter().copied())
+        .collect();
+
+    for (zone_entity, zone, current) in &zone_query {
+        if zone.zone_type != ZoneType::GuestBedroom {
+            continue;
+        }
+
+        // Every tile has to lie in the same enclosed room
+        let mut tile_rooms = zone.tiles.iter().map(|tile| adjacency.room_at(*tile));
+        let room = tile_rooms
+            .next()
+            .flatten()
+            .filter(|room| tile_rooms.all(|other| other == Some(*room)))
+            .and_then(|room| room_query.get(room).ok().map(|r| (room, r)));
+
+        let issue = match room {
+            None => Some(PrivacyIssue::NotEnclosed),
+            Some(_) if zone.tiles.iter().any(|tile| lobby_tiles.contains(tile)) => {
+                Some(PrivacyIssue::SharesLobby)
+            }
+            Some((_, room)) if room.doors.is_empty() => Some(PrivacyIssue::NoDoor),
+            Some((room_entity, room)) => {
+                // Outdoors (None) or a room leading on elsewhere; an en-suite only opening
+                // back into the bedroom doesn't count
+                let ways_on: HashSet<Option<Entity>> = room
+                    .doors
+                    .iter()
+                    .filter_map(|door| adjacency.door_rooms.get(door))
+                    .filter_map(|rooms| match rooms.as_slice() {
+                        [_] => Some(None),
+                        _ => rooms.iter().copied().find(|other| *other != room_entity).map(Some),
+                    })
+                    .filter(|way| {
+                        way.is_none_or(|other| {
+                            adjacency.opens_outdoors(other)
+                                || adjacency.neighbors(other).any(|n| n != room_entity)
+                        })
+                    })
+                    .collect();
+                (ways_on.len() > 1).then_some(PrivacyIssue::Thoroughfare)
+            }
+        };
+
+        if issue.as_ref() != current {
+            match issue {
+                Some(issue) => {
+                    commands.entity(zone_entity).insert(issue);
+                }
+                None => {
+                    commands.entity(zone_entity).remove::<PrivacyIssue>();
+                }
+            }
+        }
+    }
+}
+
+/// Rates every room no zone covers, usually a corridor, on how wide, well lit, floored
+/// and decorated it is
+fn rate_public_spaces(
+    mut commands: Commands,
+    adjacency: Res<RoomAdjacency>,
+    light_map: Res<LightMap>,
+    room_query: Query<(Entity, &Room, Option<&PublicSpace>)>,
+    zone_query: Query<&Zone>,
+    changed_zones: Query<(), Changed<Zone>>,
+    furniture_query: Query<&GridPosition, With<Furniture>>,
+    changed_furniture: Query<(), Changed<Furniture>>,
+    mut removed_furniture: RemovedComponents<Furniture>,
+    floor_query: Query<&GridPosition, With<Floor>>,
+) {
+    let furniture_removed = removed_furniture.read().count() > 0;
+    if !adjacency.is_changed()
+        && !light_map.is_changed()
+        && changed_zones.is_empty()
+        && changed_furniture.is_empty()
+        && !furniture_removed
+    {
+        return;
+    }
+
+    let zoned: HashSet<IVec2> = zone_query
+        .iter()
+        .flat_map(|zone| zone.tiles.iter().copied())
+        .collect();
+    let floored: HashSet<IVec2> = floor_query.iter().map(|pos| pos.to_ivec2()).collect();
+
+    for (room_entity, room, current) in &room_query {
+        if room.tiles.iter().any(|tile| zoned.contains(tile)) {
+            if current.is_some() {
+                commands.entity(room_entity).remove::<PublicSpace>();
+            }
+            continue;
+        }
+
+        let floored_tiles = room
+            .tiles
+            .iter()
+            .filter(|tile| floored.contains(*tile))
+            .count();
+        let decor = furniture_query
+            .iter()
+            .filter(|pos| room.contains_tile(pos.to_ivec2()))
+            .count()
+            + room.decor_bonus();
+
+        let mut shortfalls = Vec::new();
+        if average_width(&room.tiles) < PUBLIC_SPACE_MIN_WIDTH {
+            shortfalls.push(PublicSpaceShortfall::Narrow);
+        }
+        if light_map.average_brightest(&room.tiles) < DARK_ROOM_LIGHT {
+            shortfalls.push(PublicSpaceShortfall::Dark);
+        }
+        if (floored_tiles as f32) < room.tile_count() as f32 * PUBLIC_SPACE_FLOORED_SHARE {
+            shortfalls.push(PublicSpaceShortfall::BareFloor);
+        }
+        if decor < (room.tile_count() / PUBLIC_SPACE_TILES_PER_DECOR).max(1) {
+            shortfalls.push(PublicSpaceShortfall::Undecorated);
+        }
+
+        if current.is_none_or(|current| current.shortfalls != shortfalls) {
+            commands
+                .entity(room_entity)
+                .insert(PublicSpace::new(shortfalls));
+        }
     }
 }
+
+/// How many tiles across the room is at each tile, the shorter of its row and column
+/// through the tile, averaged over the room
+fn average_width(tiles: &HashSet<IVec2>) -> f32 {
+    if tiles.is_empty() {
+        return 0.0;
+    }
+
+    let run = |tile: IVec2, step: IVec2| {
+        let mut length = 1;
+        for direction in [step, -step] {
+            let mut next = tile + direction;
+            while tiles.contains(&next) {
+                length += 1;
+                next += direction;
+            }
+        }
+        length
+    };
+    let total: usize = tiles
+        .iter()
+        .map(|tile| run(*tile, IVec2::X).min(run(*tile, IVec2::Y)))
+        .sum();
+    total as f32 / tiles.len() as f32
+}
@@ -0,0 +1,389 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::guests::{random_open_tile, zone_center, SatisfactionCause, SatisfactionEvent};
+use crate::systems::items::{spawn_carried_item, DropItem};
+use crate::systems::job_priorities::JobPriorities;
+use crate::systems::staff::{start_staff_breaks, JobFinishedEvent};
+use crate::systems::time_control::GameClock;
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Hourly odds that a party relaxing in their room orders something up
+const ORDER_CHANCE_PER_HOUR: f64 = 0.08;
+/// Parties this hungry or worse order a meal; the rest ask for towels
+const HUNGRY: f32 = 0.5;
+/// Room service runs from breakfast until bedtime
+const SERVICE_HOURS: (f32, f32) = (7.0, 22.0);
+/// How close a worker has to get to collect or hand over an order
+const DELIVERY_REACH: f32 = TILE_SIZE * 2.0;
+/// Satisfaction gained from an order that arrives in time, and lost from one that doesn't
+const DELIVERED_SATISFACTION_BOOST: f32 = 0.1;
+const LATE_SATISFACTION_PENALTY: f32 = 0.15;
+
+pub struct RoomServicePlugin;
+
+impl Plugin for RoomServicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                place_room_service_orders,
+                assign_room_service.after(start_staff_breaks),
+                deliver_room_service,
+                expire_room_service_orders,
+                draw_room_service_markers,
+            )
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// A carried order may already have gone with a worker who left the resort
+fn discard_item(commands: &mut Commands, item: Entity) {
+    if let Some(mut item) = commands.get_entity(item) {
+        item.despawn();
+    }
+}
+
+/// Every game hour, parties in their room may ring down for a meal or fresh towels.
+/// Each room has at most one order open, and only if the resort has somewhere to send
+/// for it.
+fn place_room_service_orders(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    guest_query: Query<(Entity, &Guest), (Without<Departing>, Without<WaitingGuest>)>,
+    zone_query: Query<(&Zone, Has<Booking>)>,
+    order_query: Query<&RoomServiceOrder>,
+    mut pending_hours: Local<f32>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    *pending_hours += elapsed;
+    if *pending_hours < 1.0 {
+        return;
+    }
+    *pending_hours -= 1.0;
+
+    if !(SERVICE_HOURS.0..SERVICE_HOURS.1).contains(&clock.hour()) {
+        return;
+    }
+
+    let mut ordered: HashSet<Entity> = order_query.iter().map(|order| order.room).collect();
+    let mut rng = rand::thread_rng();
+
+    for (guest_entity, guest) in &guest_query {
+        if guest.activity != GuestActivity::InRoom || ordered.contains(&guest.room) {
+            continue;
+        }
+        let Ok((room_zone, true)) = zone_query.get(guest.room) else {
+            continue;
+        };
+        if !rng.gen_bool(ORDER_CHANCE_PER_HOUR) {
+            continue;
+        }
+
+        let item = if guest.needs.hunger <= HUNGRY {
+            RoomServiceItem::Food
+        } else {
+            RoomServiceItem::Towels
+        };
+        let room_center = zone_center(room_zone, &grid_settings);
+        let pickup = zone_query
+            .iter()
+            .filter(|(zone, _)| zone.zone_type == item.pickup_zone())
+            .map(|(zone, _)| zone_center(zone, &grid_settings))
+            .min_by(|a, b| a.distance(room_center).total_cmp(&b.distance(room_center)));
+        let Some(pickup) = pickup else {
+            continue;
+        };
+        let drop_off = random_open_tile(room_zone, &building_map, &mut rng)
+            .map(|tile| {
+                grid_to_world(
+                    tile,
+                    grid_settings.tile_size,
+                    grid_settings.width,
+                    grid_settings.height,
+                )
+            })
+            .unwrap_or(room_center);
+
+        notifications.send(Notification {
+            message: localization.format(
+                "room_service.ordered",
+                &[
+                    (
+                        "party",
+                        localization.term("party", guest.party.name()).to_string(),
+                    ),
+                    ("room", room_zone.name.clone()),
+                    (
+                        "item",
+                        localization.term("room_service", item.name()).to_string(),
+                    ),
+                ],
+            ),
+        });
+
+        ordered.insert(guest.room);
+        commands.spawn(RoomServiceOrder::new(
+            item,
+            guest_entity,
+            guest.room,
+            pickup,
+            drop_off,
+        ));
+    }
+}
+
+/// Idle cooks take meal orders and idle housekeepers towel orders, oldest first.
+/// Whoever is on a desk or a massage table stays there.
+fn assign_room_service(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &WorkAssignments,
+            Option<&UnreachableJob>,
+        ),
+        (
+            With<Pawn>,
+            Without<OnBreak>,
+            Without<InTraining>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+        ),
+    >,
+    mut order_query: Query<(Entity, &mut RoomServiceOrder)>,
+    priorities: Res<JobPriorities>,
+) {
+    for (pawn_entity, pawn_transform, mut current_job, work_assignments, unreachable) in
+        &mut pawn_query
+    {
+        if current_job.job_id.is_some() {
+            continue;
+        }
+
+        // Most urgent first, and the nearest pickup of equally urgent orders
+        let pawn_pos = pawn_transform.translation.truncate();
+        let chosen = order_query
+            .iter()
+            .filter(|(order_entity, order)| {
+                order.assigned_pawn.is_none()
+                    && work_assignments.can_do_work(order.item.work_type())
+                    && !priorities.passes_over(work_assignments, order.item.work_type())
+                    && unreachable.is_none_or(|unreachable| unreachable.job != *order_entity)
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.hours_left.total_cmp(&b.hours_left).then(
+                    a.pickup
+                        .distance(pawn_pos)
+                        .total_cmp(&b.pickup.distance(pawn_pos)),
+                )
+            })
+            .map(|(order_entity, _)| order_entity);
+
+        let Some(order_entity) = chosen else {
+            continue;
+        };
+        if let Ok((_, mut order)) = order_query.get_mut(order_entity) {
+            order.assigned_pawn = Some(pawn_entity);
+            current_job.job_id = Some(order_entity);
+            commands.entity(pawn_entity).insert(MovementTarget {
+                target: order.pickup,
+            });
+        }
+    }
+}
+
+/// Workers collect the order, carry it up and hand it over. A delivered meal leaves its
+/// plate behind in the room; the party pays for the order at check-out.
+fn deliver_room_service(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut drops: EventWriter<DropItem>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut order_query: Query<(Entity, &mut RoomServiceOrder)>,
+    mut pawn_query: Query<(&Transform, &mut CurrentJob), With<Pawn>>,
+    mut zone_query: Query<(&Zone, &mut Booking)>,
+    mut guest_query: Query<&mut Guest>,
+) {
+    for (order_entity, mut order) in &mut order_query {
+        let carrier = order.assigned_pawn.and_then(|pawn| {
+            pawn_query
+                .get(pawn)
+                .ok()
+                .map(|(transform, _)| (pawn, transform))
+        });
+        let Some((pawn_entity, pawn_transform)) = carrier else {
+            // The worker gave up on it or left, so the order goes back to be collected
+            // again by whoever takes it on next
+            order.assigned_pawn = None;
+            if let Some(item) = order.carried.take() {
+                discard_item(&mut commands, item);
+            }
+            continue;
+        };
+        let position = pawn_transform.translation.truncate();
+
+        let Some(item) = order.carried else {
+            if position.distance(order.pickup) <= DELIVERY_REACH {
+                let owner = order.guest;
+                order.carried = Some(spawn_carried_item(
+                    &mut commands,
+                    Item::owned_by(order.item.item_kind(), owner),
+                    pawn_entity,
+                    position,
+                ));
+                commands.entity(pawn_entity).insert(MovementTarget {
+                    target: order.drop_off,
+                });
+            }
+            continue;
+        };
+        if position.distance(order.drop_off) > DELIVERY_REACH {
+            continue;
+        }
+
+        if order.item == RoomServiceItem::Food {
+            drops.send(DropItem { item });
+        } else {
+            commands.entity(item).despawn();
+        }
+
+        if let Ok((zone, mut booking)) = zone_query.get_mut(order.room) {
+            let fee = order.item.fee(booking.party);
+            if order.item == RoomServiceItem::Food {
+                booking.restaurant_charges += fee;
+            } else {
+                booking.extra_charges += fee;
+            }
+            booking.satisfaction = (booking.satisfaction + DELIVERED_SATISFACTION_BOOST).min(1.0);
+            satisfaction_events.send(SatisfactionEvent {
+                room: order.room,
+                guest: Some(order.guest),
+                cause: SatisfactionCause::RoomServiceDelivered,
+            });
+
+            notifications.send(Notification {
+                message: localization.format(
+                    "room_service.delivered",
+                    &[
+                        (
+                            "item",
+                            localization
+                                .term("room_service", order.item.name())
+                                .to_string(),
+                        ),
+                        ("room", zone.name.clone()),
+                        ("fee", fee.to_string()),
+                    ],
+                ),
+            });
+        }
+        if order.item == RoomServiceItem::Food {
+            if let Ok(mut guest) = guest_query.get_mut(order.guest) {
+                guest.needs.hunger = 1.0;
+            }
+        }
+
+        if let Ok((_, mut current_job)) = pawn_query.get_mut(pawn_entity) {
+            current_job.job_id = None;
+        }
+        finished_jobs.send(JobFinishedEvent {
+            pawn: pawn_entity,
+            job: order_entity,
+            kind: JobKind::RoomService,
+        });
+        commands.entity(pawn_entity).remove::<MovementTarget>();
+        commands.entity(order_entity).despawn();
+    }
+}
+
+/// Orders run out of time, or lapse when the party checks out. A party left waiting
+/// past the time limit is let down.
+fn expire_room_service_orders(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut satisfaction_events: EventWriter<SatisfactionEvent>,
+    mut order_query: Query<(Entity, &mut RoomServiceOrder)>,
+    mut zone_query: Query<(&Zone, &mut Booking)>,
+    guest_query: Query<(), With<Guest>>,
+    mut pawn_query: Query<&mut CurrentJob, With<Pawn>>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    for (order_entity, mut order) in &mut order_query {
+        order.hours_left -= elapsed;
+
+        let party_here = guest_query.contains(order.guest);
+        let booking = zone_query.get_mut(order.room).ok().filter(|_| party_here);
+        match booking {
+            Some(_) if order.hours_left > 0.0 => continue,
+            Some((zone, mut booking)) => {
+                booking.satisfaction = (booking.satisfaction - LATE_SATISFACTION_PENALTY).max(0.0);
+                satisfaction_events.send(SatisfactionEvent {
+                    room: order.room,
+                    guest: Some(order.guest),
+                    cause: SatisfactionCause::RoomServiceLate,
+                });
+                notifications.send(Notification {
+                    message: localization.format(
+                        "room_service.late",
+                        &[
+                            (
+                                "item",
+                                localization
+                                    .term("room_service", order.item.name())
+                                    .to_string(),
+                            ),
+                            ("room", zone.name.clone()),
+                        ],
+                    ),
+                });
+            }
+            // With the party checked out, the order just lapses
+            None => {}
+        }
+
+        if let Some(item) = order.carried {
+            discard_item(&mut commands, item);
+        }
+        if let Some(pawn) = order.assigned_pawn {
+            if let Ok(mut current_job) = pawn_query.get_mut(pawn) {
+                current_job.job_id = None;
+            }
+            commands.entity(pawn).remove::<MovementTarget>();
+        }
+        commands.entity(order_entity).despawn();
+    }
+}
+
+/// A small ring marks each room waiting on an order
+fn draw_room_service_markers(order_query: Query<&RoomServiceOrder>, mut gizmos: Gizmos) {
+    for order in &order_query {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(order.drop_off),
+            TILE_SIZE * 0.4,
+            Color::srgb(0.95, 0.8, 0.3),
+        );
+    }
+}
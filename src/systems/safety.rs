@@ -0,0 +1,364 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::room_detection::RoomAdjacency;
+use crate::ui::{Localization, UiInputBlocker};
+use bevy::prelude::*;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Floor space one exit door can empty safely; larger buildings need more exits
+const TILES_PER_EXIT: usize = 120;
+/// Rooms more doors than this away from the nearest exit are too far to evacuate
+const MAX_DOORS_TO_EXIT: usize = 3;
+const SAFE_COLOR: Color = Color::srgb(0.3, 0.85, 0.35);
+const UNSAFE_COLOR: Color = Color::srgb(0.95, 0.25, 0.2);
+
+/// A group of rooms joined by doors, with the ways out of it
+#[derive(Debug, Clone)]
+pub struct Building {
+    pub rooms: HashSet<Entity>,
+    /// Doors leading straight outside
+    pub exits: usize,
+    pub exits_needed: usize,
+    /// Rooms too many doors from an exit, or with no way out at all
+    pub unsafe_rooms: HashSet<Entity>,
+    /// From 0 (no way out) to 1 (enough exits within reach of every room)
+    pub rating: f32,
+    /// Beds in the building's bookable bedrooms
+    pub beds: u32,
+    /// Guests the building may hold at its rating
+    pub guest_limit: u32,
+}
+
+/// Fire safety of every building, rebuilt whenever rooms or zones change. Check-in
+/// won't book more guests into a building than its rating allows.
+#[derive(Resource, Default, Debug)]
+pub struct BuildingSafety {
+    pub buildings: Vec<Building>,
+    /// Which building each zone lies in; zones out in the open have none
+    pub zone_buildings: HashMap<Entity, usize>,
+}
+
+impl BuildingSafety {
+    pub fn building_of(&self, zone: Entity) -> Option<usize> {
+        self.zone_buildings.get(&zone).copied()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SafetyOverlaySettings {
+    pub visible: bool,
+}
+
+/// A building's rating and guest count, shown while the safety overlay is on
+#[derive(Component)]
+struct SafetyLabel;
+
+pub struct SafetyPlugin;
+
+impl Plugin for SafetyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BuildingSafety>()
+            .init_resource::<SafetyOverlaySettings>()
+            .add_systems(
+                Update,
+                (
+                    update_building_safety,
+                    toggle_safety_overlay,
+                    update_safety_labels,
+                    draw_safety_overlay,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn rating_color(rating: f32) -> Color {
+    UNSAFE_COLOR.mix(&SAFE_COLOR, rating.clamp(0.0, 1.0))
+}
+
+/// Splits the rooms into buildings and rates each one on whether its exits can cope with
+/// its size and how far each room is from the nearest one
+fn update_building_safety(
+    adjacency: Res<RoomAdjacency>,
+    zone_query: Query<(Entity, &Zone, Has<PrivacyIssue>)>,
+    changed_zones: Query<(), Or<(Changed<Zone>, Changed<PrivacyIssue>)>>,
+    mut removed_zones: RemovedComponents<Zone>,
+    mut removed_privacy: RemovedComponents<PrivacyIssue>,
+    mut safety: ResMut<BuildingSafety>,
+) {
+    let zones_removed = removed_zones.read().count() + removed_privacy.read().count() > 0;
+    if !adjacency.is_changed() && changed_zones.is_empty() && !zones_removed {
+        return;
+    }
+
+    let mut room_area: HashMap<Entity, usize> = HashMap::new();
+    for room in adjacency.tile_rooms.values() {
+        *room_area.entry(*room).or_default() += 1;
+    }
+    let mut room_exits: HashMap<Entity, usize> = HashMap::new();
+    for rooms in adjacency.door_rooms.values() {
+        if let [room] = rooms.as_slice() {
+            *room_exits.entry(*room).or_default() += 1;
+        }
+    }
+
+    let mut buildings = Vec::new();
+    let mut room_buildings: HashMap<Entity, usize> = HashMap::new();
+    let mut rooms: Vec<Entity> = room_area.keys().copied().collect();
+    rooms.sort();
+    for start in rooms {
+        if room_buildings.contains_key(&start) {
+            continue;
+        }
+
+        // Every room reachable through doors belongs to the same building
+        let index = buildings.len();
+        let mut members = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(room) = queue.pop_front() {
+            room_buildings.insert(room, index);
+            for neighbor in adjacency.neighbors(room) {
+                if members.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        // Doors walked from the nearest exit to each room
+        let mut distances: HashMap<Entity, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for room in &members {
+            if room_exits.contains_key(room) {
+                distances.insert(*room, 0);
+                queue.push_back(*room);
+            }
+        }
+        while let Some(room) = queue.pop_front() {
+            let distance = distances[&room];
+            for neighbor in adjacency.neighbors(room) {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let area: usize = members.iter().map(|room| room_area[room]).sum();
+        let exits: usize = members.iter().filter_map(|room| room_exits.get(room)).sum();
+        let exits_needed = area.div_ceil(TILES_PER_EXIT).max(1);
+        let unsafe_rooms: HashSet<Entity> = members
+            .iter()
+            .filter(|room| distances.get(room).is_none_or(|d| *d > MAX_DOORS_TO_EXIT))
+            .copied()
+            .collect();
+        let unsafe_area: usize = unsafe_rooms.iter().map(|room| room_area[room]).sum();
+
+        // Short on exits scales the rating down, and so does floor space too far from one
+        let exit_share = (exits as f32 / exits_needed as f32).min(1.0);
+        let reachable_share = 1.0 - unsafe_area as f32 / area.max(1) as f32;
+
+        buildings.push(Building {
+            rooms: members,
+            exits,
+            exits_needed,
+            unsafe_rooms,
+            rating: exit_share * reachable_share,
+            beds: 0,
+            guest_limit: 0,
+        });
+    }
+
+    let mut zone_buildings = HashMap::new();
+    for (zone_entity, zone, not_private) in &zone_query {
+        let Some(index) = zone
+            .tiles
+            .iter()
+            .find_map(|tile| adjacency.room_at(*tile))
+            .and_then(|room| room_buildings.get(&room).copied())
+        else {
+            continue;
+        };
+        zone_buildings.insert(zone_entity, index);
+        let bookable = zone.quality != ZoneQuality::None && !not_private;
+        if zone.zone_type == ZoneType::GuestBedroom && bookable {
+            buildings[index].beds += zone.capacity as u32;
+        }
+    }
+    for building in &mut buildings {
+        building.guest_limit = (building.beds as f32 * building.rating).floor() as u32;
+    }
+
+    *safety = BuildingSafety {
+        buildings,
+        zone_buildings,
+    };
+}
+
+fn toggle_safety_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut overlay_settings: ResMut<SafetyOverlaySettings>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::F6) {
+        overlay_settings.visible = !overlay_settings.visible;
+    }
+}
+
+/// One label per building over its middle, respawned whenever ratings or bookings change
+fn update_safety_labels(
+    mut commands: Commands,
+    safety: Res<BuildingSafety>,
+    overlay_settings: Res<SafetyOverlaySettings>,
+    adjacency: Res<RoomAdjacency>,
+    localization: Res<Localization>,
+    grid_settings: Res<GridSettings>,
+    booking_query: Query<(Entity, &Booking)>,
+    changed_bookings: Query<(), Changed<Booking>>,
+    mut removed_bookings: RemovedComponents<Booking>,
+    label_query: Query<Entity, With<SafetyLabel>>,
+) {
+    let bookings_changed = !changed_bookings.is_empty() || removed_bookings.read().count() > 0;
+    if !safety.is_changed()
+        && !overlay_settings.is_changed()
+        && !localization.is_changed()
+        && !bookings_changed
+    {
+        return;
+    }
+
+    for label in &label_query {
+        commands.entity(label).despawn();
+    }
+    if !overlay_settings.visible {
+        return;
+    }
+
+    let mut guests = vec![0; safety.buildings.len()];
+    for (zone, booking) in &booking_query {
+        if let Some(index) = safety.building_of(zone) {
+            guests[index] += booking.party.size as u32;
+        }
+    }
+
+    let mut tiles: Vec<Vec<IVec2>> = vec![Vec::new(); safety.buildings.len()];
+    for (tile, room) in &adjacency.tile_rooms {
+        if let Some(index) = safety
+            .buildings
+            .iter()
+            .position(|building| building.rooms.contains(room))
+        {
+            tiles[index].push(*tile);
+        }
+    }
+
+    for ((building, tiles), guests) in safety.buildings.iter().zip(&tiles).zip(guests) {
+        if tiles.is_empty() {
+            continue;
+        }
+        let sum = tiles.iter().fold(Vec2::ZERO, |acc, tile| {
+            acc + grid_to_world(
+                *tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        });
+        let center = sum / tiles.len() as f32;
+
+        let text = localization.format(
+            "safety.label",
+            &[
+                ("rating", format!("{:.0}", building.rating * 100.0)),
+                ("exits", building.exits.to_string()),
+                ("needed", building.exits_needed.to_string()),
+                ("guests", guests.to_string()),
+                ("limit", building.guest_limit.to_string()),
+            ],
+        );
+        let color = if guests > building.guest_limit {
+            UNSAFE_COLOR
+        } else {
+            rating_color(building.rating)
+        };
+
+        commands.spawn((
+            Text2d::new(text),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(color),
+            // Above zone labels so both can be read when the overlays are on together
+            Transform::from_xyz(center.x, center.y, 51.0),
+            SafetyLabel,
+        ));
+    }
+}
+
+/// Outlines every building in its rating's colour and crosses out rooms too far from
+/// an exit
+fn draw_safety_overlay(
+    safety: Res<BuildingSafety>,
+    overlay_settings: Res<SafetyOverlaySettings>,
+    adjacency: Res<RoomAdjacency>,
+    grid_settings: Res<GridSettings>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay_settings.visible {
+        return;
+    }
+
+    let building_of_room: HashMap<Entity, &Building> = safety
+        .buildings
+        .iter()
+        .flat_map(|building| building.rooms.iter().map(move |room| (*room, building)))
+        .collect();
+
+    let half = grid_settings.tile_size / 2.0;
+    let edges = [
+        (IVec2::X, Vec2::new(half, -half), Vec2::new(half, half)),
+        (
+            IVec2::NEG_X,
+            Vec2::new(-half, -half),
+            Vec2::new(-half, half),
+        ),
+        (IVec2::Y, Vec2::new(-half, half), Vec2::new(half, half)),
+        (
+            IVec2::NEG_Y,
+            Vec2::new(-half, -half),
+            Vec2::new(half, -half),
+        ),
+    ];
+
+    for (tile, room) in &adjacency.tile_rooms {
+        let Some(building) = building_of_room.get(room) else {
+            continue;
+        };
+        let center = grid_to_world(
+            *tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+
+        let color = rating_color(building.rating);
+        for (offset, start, end) in edges {
+            let outside = adjacency
+                .room_at(*tile + offset)
+                .is_none_or(|other| !building.rooms.contains(&other));
+            if outside {
+                gizmos.line_2d(center + start, center + end, color);
+            }
+        }
+
+        if building.unsafe_rooms.contains(room) {
+            let corner = Vec2::splat(half * 0.6);
+            let flipped = Vec2::new(corner.x, -corner.y);
+            gizmos.line_2d(center - corner, center + corner, UNSAFE_COLOR);
+            gizmos.line_2d(center - flipped, center + flipped, UNSAFE_COLOR);
+        }
+    }
+}
@@ -1,15 +1,31 @@
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::sprite::*;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::components::*;
-use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::grid::{grid_to_world, world_to_grid, GridSettings};
+use crate::systems::shared_assets::SharedAssets;
+use crate::systems::stable_id::{EntityRelinker, StableIds};
+use crate::systems::statistics::StatsHistory;
+use crate::systems::app_state::{AppState, GameplaySet};
+use crate::systems::building::furniture;
+use crate::systems::demand::GuestSpawnSettings;
+use crate::systems::economy::Money;
+use crate::systems::guests::{restore_guest, GuestLedger, GUEST_MOVE_SPEED};
+use crate::systems::terrain::{Terrain, TerrainData};
+use crate::systems::time_control::GameClock;
 use crate::systems::BuildingMap;
-
-const DOOR_THICKNESS: f32 = 0.6;
+use crate::ui::{Localization, Notification, UiInputBlocker};
 
 #[derive(Resource)]
 pub struct SaveLoadConfig {
@@ -24,19 +40,74 @@ impl Default for SaveLoadConfig {
     }
 }
 
-#[derive(Resource)]
-struct LoadRequestState {
+/// Where the Save/Load panel keeps saves
+pub const SAVE_DIR: &str = "assets/saves";
+/// Gzip files start with these two bytes, whatever they're named
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// How a save is written to disk, picked from the file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaveFormat {
+    /// Pretty-printed JSON, easy to read and diff by hand
+    #[default]
+    Json,
+    /// Gzip-compressed compact JSON, a fraction of the size for large resorts
+    CompressedJson,
+}
+
+impl SaveFormat {
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(SaveFormat::CompressedJson.extension()) {
+            SaveFormat::CompressedJson
+        } else {
+            SaveFormat::Json
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SaveFormat::Json => ".json",
+            SaveFormat::CompressedJson => ".json.gz",
+        }
+    }
+
+    pub fn is_save_file(filename: &str) -> bool {
+        !Self::strip_extension(filename).is_empty()
+            && (filename.ends_with(SaveFormat::Json.extension())
+                || filename.ends_with(SaveFormat::CompressedJson.extension()))
+    }
+
+    /// The save's name as shown to the player, without either extension
+    pub fn strip_extension(filename: &str) -> &str {
+        let format = Self::from_path(filename);
+        filename
+            .strip_suffix(format.extension())
+            .unwrap_or(filename)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct LoadRequestState {
     pending: bool,
+    /// A file to load other than the configured save, such as a scenario
+    path: Option<String>,
 }
 
-impl Default for LoadRequestState {
-    fn default() -> Self {
-        Self { pending: true }
+impl LoadRequestState {
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Loads `path` once the app is in [`AppState::Loading`], without making it the file
+    /// quick saves go to
+    pub fn request(&mut self, path: String) {
+        self.pending = true;
+        self.path = Some(path);
     }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-struct GridPoint {
+pub struct GridPoint {
     x: i32,
     y: i32,
 }
@@ -69,12 +140,37 @@ impl From<IVec2> for GridPoint {
 struct DoorData {
     position: GridPoint,
     orientation: DoorOrientation,
+    #[serde(default)]
+    mode: DoorMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowData {
+    position: GridPoint,
+    orientation: WindowOrientation,
+    /// Kept so a repair waiting on the window finds it again after a load
+    #[serde(default)]
+    id: Option<StableId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoundaryData {
+    position: GridPoint,
+    boundary_type: BoundaryType,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct FloorData {
     position: GridPoint,
     floor_type: FloorType,
+    #[serde(default)]
+    tint: Option<PaintColor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WallPaintData {
+    position: GridPoint,
+    tint: PaintColor,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,15 +178,693 @@ struct FurnitureData {
     position: GridPoint,
     furniture_type: FurnitureType,
     orientation: FurnitureOrientation,
+    /// What a signpost says and points to
+    #[serde(default)]
+    sign: Option<Sign>,
+    /// Kept so staff and tools pointing at this piece find it again after a load
+    #[serde(default)]
+    id: Option<StableId>,
+}
+
+/// A zone as the player knows it. Rooms are found again on load, and each picks the
+/// saved zone over its tiles back up instead of making a new one with a new name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneData {
+    name: String,
+    zone_type: ZoneType,
+    tiles: Vec<GridPoint>,
+    quality: ZoneQuality,
+    #[serde(default)]
+    capacity: u8,
+    /// Saves from before access settings leave zones at their type's default
+    #[serde(default)]
+    access: Option<ZoneAccess>,
+    /// Likewise for saves from before speakers
+    #[serde(default)]
+    ambience: Option<ZoneAmbience>,
+    /// How clean a bedroom was; saves from before it was kept load spotless
+    #[serde(default)]
+    cleanliness: Option<f32>,
+    #[serde(default)]
+    id: Option<StableId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PawnData {
+    name: String,
+    #[serde(default)]
+    id: Option<StableId>,
+    /// The tile the worker stood on; saves from before it was kept leave workers where
+    /// they are
+    #[serde(default)]
+    position: Option<GridPoint>,
+    #[serde(default)]
+    staffing: Option<StaffingData>,
+    /// The rack the worker's hammer goes back to
+    #[serde(default)]
+    hammer: Option<StableId>,
+}
+
+/// The desk or table a worker was posted to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum StaffingData {
+    Reception(StableId),
+    Spa(StableId),
+}
+
+/// A party's booking, kept with the id of the room it is for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StayData {
+    room: StableId,
+    booking: Booking,
+    /// None while the party is still on their way in on the shuttle
+    guest: Option<GuestData>,
+}
+
+/// How a party already at the resort was getting on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuestData {
+    /// Parties somehow off the map are put back in their room
+    position: Option<GridPoint>,
+    activity: ActivityData,
+    needs: GuestNeeds,
+    hours_left: f32,
+    /// What the party was still held up for on arrival; the wait starts over on load
+    waiting: Option<WaitReason>,
+}
+
+/// [`GuestActivity`] with the amenity being visited kept by its id
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ActivityData {
+    InRoom,
+    Sleeping,
+    Visiting(StableId),
+}
+
+/// Work waiting on something saved
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum JobData {
+    /// Storm damage to a piece of furniture or a window, and how far its repair got
+    Repair {
+        target: StableId,
+        work_required: f32,
+        work_done: f32,
+    },
+    /// A bedroom to clean, and the tile in it the cleaner heads for
+    Cleaning { room: StableId, spot: GridPoint },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SaveData {
+    /// Written first so the Save/Load panel can show it without building the resort.
+    /// Saves from before it existed have none.
+    #[serde(default)]
+    pub metadata: Option<SaveMetadata>,
     pub walls: Vec<GridPoint>,
+    /// Paint on the walls above. Kept apart from them so saves from before walls could
+    /// be painted still load.
+    #[serde(default)]
+    pub wall_paint: Vec<WallPaintData>,
     pub floors: Vec<FloorData>,
     pub doors: Vec<DoorData>,
     #[serde(default)]
+    pub windows: Vec<WindowData>,
+    /// Fences, hedges and gates
+    #[serde(default)]
+    pub boundaries: Vec<BoundaryData>,
+    #[serde(default)]
     pub furniture: Vec<FurnitureData>,
+    /// Saves from before zones were kept have them made afresh by room detection
+    #[serde(default)]
+    pub zones: Vec<ZoneData>,
+    #[serde(default)]
+    pub pawns: Vec<PawnData>,
+    /// Saves from before stays were kept load with nobody booked in
+    #[serde(default)]
+    pub stays: Vec<StayData>,
+    #[serde(default)]
+    pub jobs: Vec<JobData>,
+    #[serde(default)]
+    pub stats: StatsHistory,
+    /// Ground painted in the scenario editor; None for the generated map
+    #[serde(default)]
+    pub terrain: Option<TerrainData>,
+    /// None for the usual guests
+    #[serde(default)]
+    pub guest_spawning: Option<GuestSpawnSettings>,
+}
+
+/// What the Save/Load panel shows about a save so resorts can be told apart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct SaveMetadata {
+    pub day: u32,
+    pub hour: f32,
+    pub money: i32,
+    pub guests: u32,
+    /// Real time spent playing this resort, across every session
+    pub play_time_secs: f32,
+    pub thumbnail: SaveThumbnail,
+}
+
+/// A tiny top-down picture of the resort, one pixel per tile or fewer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct SaveThumbnail {
+    pub width: u32,
+    pub height: u32,
+    /// RGB bytes as hex, row by row from the top
+    pub pixels: String,
+}
+
+impl SaveThumbnail {
+    /// Larger resorts are scaled down to fit
+    pub const MAX_WIDTH: i32 = 64;
+    pub const MAX_HEIGHT: i32 = 40;
+
+    const GROUND: [u8; 3] = [0x2f, 0x4a, 0x2c];
+    const FURNITURE: [u8; 3] = [0x8a, 0x6d, 0x4b];
+    const DOOR: [u8; 3] = [0xb0, 0x7a, 0x40];
+    const WALL: [u8; 3] = [0x3a, 0x34, 0x2e];
+    const WINDOW: [u8; 3] = [0x8c, 0xc8, 0xf0];
+    const BOUNDARY: [u8; 3] = [0x6b, 0x7a, 0x4a];
+
+    /// Draws everything built, cropped to the built area. Later layers are drawn over
+    /// earlier ones, so walls win over the floor beneath them when tiles share a pixel.
+    pub fn render(data: &SaveData) -> Self {
+        let floors = data.floors.iter().map(|floor| {
+            let color = Floor {
+                floor_type: floor.floor_type,
+                tint: floor.tint,
+            }
+            .color()
+            .to_srgba();
+            let channel = |value: f32| (value * 255.0).round() as u8;
+            (
+                floor.position,
+                [channel(color.red), channel(color.green), channel(color.blue)],
+            )
+        });
+        let furniture = data
+            .furniture
+            .iter()
+            .map(|item| (item.position, Self::FURNITURE));
+        let doors = data.doors.iter().map(|door| (door.position, Self::DOOR));
+        let walls = data.walls.iter().map(|wall| (*wall, Self::WALL));
+        let windows = data
+            .windows
+            .iter()
+            .map(|window| (window.position, Self::WINDOW));
+        let boundaries = data
+            .boundaries
+            .iter()
+            .map(|boundary| (boundary.position, Self::BOUNDARY));
+        let tiles: Vec<(GridPoint, [u8; 3])> = floors
+            .chain(boundaries)
+            .chain(furniture)
+            .chain(doors)
+            .chain(walls)
+            .chain(windows)
+            .collect();
+
+        let Some(min) = tiles.iter().map(|(pos, _)| IVec2::from(*pos)).reduce(IVec2::min) else {
+            return Self::default();
+        };
+        let max = tiles
+            .iter()
+            .map(|(pos, _)| IVec2::from(*pos))
+            .fold(min, IVec2::max);
+        let span = max - min + IVec2::ONE;
+        // Tiles per pixel, the same both ways so rooms keep their shape
+        let scale = ((span.x + Self::MAX_WIDTH - 1) / Self::MAX_WIDTH)
+            .max((span.y + Self::MAX_HEIGHT - 1) / Self::MAX_HEIGHT)
+            .max(1);
+        let width = (span.x + scale - 1) / scale;
+        let height = (span.y + scale - 1) / scale;
+
+        let mut pixels = vec![Self::GROUND; (width * height) as usize];
+        for (pos, color) in tiles {
+            let offset = (IVec2::from(pos) - min) / scale;
+            // Grid rows count up from the bottom, image rows down from the top
+            let row = height - 1 - offset.y;
+            pixels[(row * width + offset.x) as usize] = color;
+        }
+
+        Self {
+            width: width as u32,
+            height: height as u32,
+            pixels: pixels
+                .iter()
+                .flatten()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+        }
+    }
+
+    /// RGBA bytes ready for an image, or `None` for an empty or damaged thumbnail
+    pub fn rgba(&self) -> Option<Vec<u8>> {
+        let expected = self.width as usize * self.height as usize * 3;
+        if expected == 0 || self.pixels.len() != expected * 2 || !self.pixels.is_ascii() {
+            return None;
+        }
+
+        let bytes = (0..self.pixels.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&self.pixels[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        Some(
+            bytes
+                .chunks(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX])
+                .collect(),
+        )
+    }
+}
+
+/// Sent once a save has been loaded over the current resort
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SaveLoadedEvent {
+    /// What had to be fixed in the save before it could be built
+    pub repairs: SaveRepairReport,
+}
+
+/// Sent when a save file is written somewhere other than the Save/Load panel, so the
+/// panel can list it
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SaveFilesChanged;
+
+/// Broken entries a load dropped from a save rather than build, counted by problem
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveRepairReport {
+    /// Anything lying partly or wholly off the map
+    pub out_of_bounds: usize,
+    /// Structures and furniture on a tile something else already took
+    pub overlapping: usize,
+    /// Furniture with no floor or desk under it
+    pub unsupported_furniture: usize,
+    /// Doors with no wall next to them
+    pub loose_doors: usize,
+}
+
+impl SaveRepairReport {
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// One line for the player listing what was fixed
+    pub fn describe(&self, localization: &Localization) -> String {
+        let details: Vec<String> = [
+            ("save_repair.out_of_bounds", self.out_of_bounds),
+            ("save_repair.overlapping", self.overlapping),
+            (
+                "save_repair.unsupported_furniture",
+                self.unsupported_furniture,
+            ),
+            ("save_repair.loose_doors", self.loose_doors),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(key, count)| localization.format(key, &[("count", count.to_string())]))
+        .collect();
+
+        localization.format("save_repair.report", &[("details", details.join(", "))])
+    }
+}
+
+/// Real time played since the resort was started, carried over through saves
+#[derive(Resource, Debug, Default)]
+pub struct PlayTime {
+    pub seconds: f32,
+}
+
+/// Where a save's metadata comes from
+#[derive(SystemParam)]
+pub struct SaveMetadataSources<'w> {
+    pub clock: Res<'w, GameClock>,
+    pub money: Res<'w, Money>,
+    pub ledger: Res<'w, GuestLedger>,
+    pub play_time: Res<'w, PlayTime>,
+}
+
+impl SaveMetadataSources<'_> {
+    pub fn capture(&self, data: &SaveData) -> SaveMetadata {
+        SaveMetadata {
+            day: self.clock.day(),
+            hour: self.clock.hour(),
+            money: self.money.amount,
+            guests: self.ledger.guests_staying,
+            play_time_secs: self.play_time.seconds,
+            thumbnail: SaveThumbnail::render(data),
+        }
+    }
+}
+
+/// The ground and the guests a save carries besides what is built on it
+#[derive(SystemParam)]
+pub struct MapSettings<'w> {
+    pub terrain: ResMut<'w, Terrain>,
+    pub guest_spawning: ResMut<'w, GuestSpawnSettings>,
+}
+
+impl MapSettings<'_> {
+    /// Adds painted ground and any change to who visits to a save
+    pub fn capture(&self, data: &mut SaveData) {
+        data.terrain = self.terrain.to_data();
+        data.guest_spawning = Some(self.guest_spawning.clone())
+            .filter(|settings| *settings != GuestSpawnSettings::default());
+    }
+
+    /// Puts back a save's ground and guests. Saves without them get the generated map
+    /// and the usual guests.
+    pub fn apply(&mut self, data: &SaveData, grid_settings: &GridSettings) {
+        let painted = data.terrain.as_ref().and_then(|terrain| {
+            let loaded = Terrain::from_data(terrain, grid_settings.width, grid_settings.height);
+            if loaded.is_none() {
+                warn!("Saved terrain doesn't fit the map, using the generated ground");
+            }
+            loaded
+        });
+        match painted {
+            Some(terrain) => *self.terrain = terrain,
+            None if self.terrain.is_edited() => {
+                *self.terrain = Terrain::generate(grid_settings.width, grid_settings.height);
+            }
+            None => {}
+        }
+
+        let guest_spawning = data.guest_spawning.clone().unwrap_or_default();
+        if *self.guest_spawning != guest_spawning {
+            *self.guest_spawning = guest_spawning;
+        }
+    }
+}
+
+/// Everything a load clears away before the saved resort is built
+#[derive(SystemParam)]
+pub struct ClearQueries<'w, 's> {
+    pub walls: Query<'w, 's, Entity, Or<(With<Wall>, With<crate::components::Window>)>>,
+    pub boundaries: Query<'w, 's, Entity, With<Boundary>>,
+    pub floors: Query<'w, 's, Entity, With<Floor>>,
+    pub doors: Query<'w, 's, Entity, With<Door>>,
+    pub furniture: Query<'w, 's, Entity, With<Furniture>>,
+    pub blueprints: Query<'w, 's, Entity, With<Blueprint>>,
+    pub construction_jobs: Query<'w, 's, Entity, With<ConstructionJob>>,
+    pub deconstruction_jobs: Query<'w, 's, Entity, With<DeconstructionJob>>,
+    pub markers: Query<'w, 's, Entity, With<DeconstructionMarker>>,
+    pub zones: Query<'w, 's, Entity, With<Zone>>,
+}
+
+/// Where the references a save keeps by stable id come from: the desks, tables and
+/// racks workers are tied to, the rooms parties are staying in and the repair and
+/// cleaning jobs waiting
+#[derive(SystemParam)]
+pub struct ReferenceSources<'w, 's> {
+    stable_ids: Res<'w, StableIds>,
+    grid_settings: Res<'w, GridSettings>,
+    pawns: Query<'w, 's, (Entity, &'static Transform), With<Pawn>>,
+    reception_staff: Query<'w, 's, &'static StaffingReception>,
+    spa_staff: Query<'w, 's, &'static StaffingSpa>,
+    held_hammers: Query<'w, 's, &'static HeldHammer>,
+    bookings: Query<'w, 's, (Entity, &'static Booking)>,
+    cleanliness: Query<'w, 's, &'static Cleanliness>,
+    guests: Query<'w, 's, (Entity, &'static Guest, &'static Transform), Without<Departing>>,
+    waiting: Query<'w, 's, &'static WaitingGuest>,
+    repairs: Query<'w, 's, &'static RepairJob>,
+    damage: Query<'w, 's, &'static Damaged>,
+    cleaning: Query<'w, 's, &'static CleaningJob>,
+}
+
+impl ReferenceSources<'_, '_> {
+    /// Fills in the references of a save [`collect_save_data`] made. Anything pointing at
+    /// something without an id is left out.
+    pub fn capture(&self, data: &mut SaveData) {
+        let id_of = |entity: Entity| self.stable_ids.id_of(entity);
+        let tile_of = |transform: &Transform| {
+            world_to_grid(
+                transform.translation.truncate(),
+                self.grid_settings.tile_size,
+                self.grid_settings.width,
+                self.grid_settings.height,
+            )
+            .map(GridPoint::from)
+        };
+
+        // In spawn order, as collect_save_data lists them
+        let mut pawns: Vec<_> = self.pawns.iter().collect();
+        pawns.sort_by_key(|(entity, ..)| *entity);
+        for ((pawn, transform), saved) in pawns.into_iter().zip(&mut data.pawns) {
+            saved.position = tile_of(transform);
+            saved.staffing = self
+                .reception_staff
+                .get(pawn)
+                .ok()
+                .and_then(|staffing| id_of(staffing.desk_entity))
+                .map(StaffingData::Reception)
+                .or_else(|| {
+                    let staffing = self.spa_staff.get(pawn).ok()?;
+                    id_of(staffing.table_entity).map(StaffingData::Spa)
+                });
+            saved.hammer = self
+                .held_hammers
+                .get(pawn)
+                .ok()
+                .and_then(|held| id_of(held.rack));
+        }
+
+        for zone in &mut data.zones {
+            zone.cleanliness = zone
+                .id
+                .and_then(|id| self.stable_ids.get(id))
+                .and_then(|entity| self.cleanliness.get(entity).ok())
+                .map(|cleanliness| cleanliness.level);
+        }
+
+        for (room, booking) in &self.bookings {
+            let Some(room_id) = id_of(room) else {
+                continue;
+            };
+            let guest = self
+                .guests
+                .iter()
+                .find(|(_, guest, _)| guest.room == room)
+                .map(|(entity, guest, transform)| GuestData {
+                    position: tile_of(transform),
+                    activity: match guest.activity {
+                        GuestActivity::InRoom => ActivityData::InRoom,
+                        GuestActivity::Sleeping => ActivityData::Sleeping,
+                        // An amenity without an id sends them home to their room
+                        GuestActivity::Visiting(zone) => {
+                            id_of(zone).map_or(ActivityData::InRoom, ActivityData::Visiting)
+                        }
+                    },
+                    needs: guest.needs,
+                    hours_left: guest.hours_left,
+                    waiting: self.waiting.get(entity).ok().map(|waiting| waiting.reason),
+                });
+            data.stays.push(StayData {
+                room: room_id,
+                booking: booking.clone(),
+                guest,
+            });
+        }
+
+        for job in &self.repairs {
+            let (Some(target), Ok(damage)) = (id_of(job.target), self.damage.get(job.target))
+            else {
+                continue;
+            };
+            data.jobs.push(JobData::Repair {
+                target,
+                work_required: damage.work_required,
+                work_done: damage.work_done,
+            });
+        }
+        for job in &self.cleaning {
+            let spot = world_to_grid(
+                job.spot,
+                self.grid_settings.tile_size,
+                self.grid_settings.width,
+                self.grid_settings.height,
+            );
+            if let (Some(room), Some(spot)) = (id_of(job.room), spot) {
+                data.jobs.push(JobData::Cleaning {
+                    room,
+                    spot: spot.into(),
+                });
+            }
+        }
+    }
+}
+
+/// The workers, guests and jobs a load puts back from the references in a save
+#[derive(SystemParam)]
+pub struct ReferenceHolders<'w, 's> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+    pawns: Query<'w, 's, (Entity, &'static mut Pawn, &'static mut Transform)>,
+    guests: Query<'w, 's, Entity, With<Guest>>,
+    items: Query<'w, 's, (Entity, &'static Item)>,
+    repair_jobs: Query<'w, 's, Entity, With<RepairJob>>,
+    cleaning_jobs: Query<'w, 's, Entity, With<CleaningJob>>,
+    incidents: Query<'w, 's, Entity, With<Incident>>,
+    room_service_orders: Query<'w, 's, Entity, With<RoomServiceOrder>>,
+}
+
+impl ReferenceHolders<'_, '_> {
+    /// Gives existing pawns the names, ids, places and posts stored in the save, in spawn
+    /// order, and replaces the guests and jobs with the saved ones. Run once the saved
+    /// resort has been spawned, so the ids resolve to what was rebuilt. Saves from before
+    /// each was kept leave the current names, ids and places alone.
+    pub fn apply(
+        &mut self,
+        commands: &mut Commands,
+        stable_ids: &mut StableIds,
+        grid_settings: &GridSettings,
+        data: &SaveData,
+    ) {
+        let world_pos = |tile: GridPoint| {
+            grid_to_world(
+                tile.into(),
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        };
+        let furniture_tiles: HashMap<StableId, GridPoint> = data
+            .furniture
+            .iter()
+            .filter_map(|furniture| Some((furniture.id?, furniture.position)))
+            .collect();
+
+        let mut pawns: Vec<_> = self.pawns.iter_mut().collect();
+        pawns.sort_by_key(|(entity, _, _)| *entity);
+        for ((entity, mut pawn, mut transform), saved) in pawns.into_iter().zip(&data.pawns) {
+            if pawn.name != saved.name {
+                pawn.name = saved.name.clone();
+            }
+            if let Some(id) = saved.id {
+                stable_ids.register(id, entity);
+                commands.entity(entity).insert(id);
+            }
+            if let Some(tile) = saved.position {
+                let position = world_pos(tile);
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+                // Any route they were on started somewhere else
+                commands.entity(entity).remove::<PawnPath>();
+            }
+
+            let mut pawn_commands = commands.entity(entity);
+            pawn_commands.remove::<(StaffingReception, StaffingSpa, HeldHammer, ReturningHammer)>();
+            // Posted staff walk back to the desk or table, and are at it already if they
+            // were when the game was saved
+            let post = saved.staffing.and_then(|staffing| match staffing {
+                StaffingData::Reception(id) => stable_ids.get(id).map(|desk_entity| {
+                    pawn_commands.insert(StaffingReception { desk_entity });
+                    id
+                }),
+                StaffingData::Spa(id) => stable_ids.get(id).map(|table_entity| {
+                    pawn_commands.insert(StaffingSpa { table_entity });
+                    id
+                }),
+            });
+            if let Some(&tile) = post.and_then(|id| furniture_tiles.get(&id)) {
+                pawn_commands.insert(MovementTarget {
+                    target: world_pos(tile),
+                });
+            }
+            if let Some(rack) = saved.hammer.and_then(|id| stable_ids.get(id)) {
+                pawn_commands.insert(HeldHammer { rack });
+            }
+        }
+
+        // Guests, their bags and what they asked for belong to the resort being replaced
+        for guest in &self.guests {
+            commands.entity(guest).despawn_recursive();
+        }
+        for (entity, item) in &self.items {
+            if item.kind == ItemKind::Luggage {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        let jobs = self
+            .repair_jobs
+            .iter()
+            .chain(&self.cleaning_jobs)
+            .chain(&self.incidents)
+            .chain(&self.room_service_orders);
+        for job in jobs {
+            commands.entity(job).despawn_recursive();
+        }
+
+        let room_tiles: HashMap<StableId, GridPoint> = data
+            .zones
+            .iter()
+            .filter_map(|zone| Some((zone.id?, *zone.tiles.first()?)))
+            .collect();
+        for stay in &data.stays {
+            let Some(room) = stable_ids.get(stay.room) else {
+                continue;
+            };
+            commands.entity(room).insert(stay.booking.clone());
+            let Some(saved) = &stay.guest else {
+                commands.entity(room).insert(AwaitingShuttle);
+                continue;
+            };
+            let Some(tile) = saved
+                .position
+                .or_else(|| room_tiles.get(&stay.room).copied())
+            else {
+                continue;
+            };
+            let activity = match saved.activity {
+                ActivityData::InRoom => GuestActivity::InRoom,
+                ActivityData::Sleeping => GuestActivity::Sleeping,
+                ActivityData::Visiting(id) => stable_ids
+                    .get(id)
+                    .map_or(GuestActivity::InRoom, GuestActivity::Visiting),
+            };
+            let guest = restore_guest(
+                commands,
+                &mut self.meshes,
+                &mut self.materials,
+                Guest {
+                    party: stay.booking.party,
+                    room,
+                    move_speed: GUEST_MOVE_SPEED,
+                    activity,
+                    needs: saved.needs,
+                    hours_left: saved.hours_left,
+                },
+                world_pos(tile),
+            );
+            if let Some(reason) = saved.waiting {
+                commands.entity(guest).insert(WaitingGuest::new(reason));
+            }
+        }
+
+        for job in &data.jobs {
+            match *job {
+                JobData::Repair {
+                    target,
+                    work_required,
+                    work_done,
+                } => {
+                    let Some(target) = stable_ids.get(target) else {
+                        continue;
+                    };
+                    commands.entity(target).insert(Damaged {
+                        work_required,
+                        work_done,
+                    });
+                    commands.spawn(RepairJob::new(target));
+                }
+                JobData::Cleaning { room, spot } => {
+                    if let Some(room) = stable_ids.get(room) {
+                        commands.spawn(CleaningJob::new(room, world_pos(spot)));
+                    }
+                }
+            }
+        }
+    }
 }
 
 pub struct SaveLoadPlugin;
@@ -99,65 +873,123 @@ impl Plugin for SaveLoadPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SaveLoadConfig>()
             .init_resource::<LoadRequestState>()
-            .add_systems(Update, request_load_on_hotkey)
-            .add_systems(Update, save_game_on_hotkey)
-            .add_systems(Update, process_load_requests.after(request_load_on_hotkey));
+            .init_resource::<PlayTime>()
+            .add_event::<SaveLoadedEvent>()
+            .add_event::<SaveFilesChanged>()
+            .add_systems(
+                Update,
+                (tick_play_time, request_load_on_hotkey, save_game_on_hotkey).in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                process_load_requests
+                    .after(request_load_on_hotkey)
+                    .run_if(in_state(AppState::Loading)),
+            )
+            .add_systems(Update, report_save_repairs.after(process_load_requests));
+    }
+}
+
+/// Lets the player know when a save needed fixing, so missing pieces are not a mystery
+fn report_save_repairs(
+    mut events: EventReader<SaveLoadedEvent>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+) {
+    for event in events.read() {
+        if !event.repairs.is_clean() {
+            notifications.send(Notification {
+                message: event.repairs.describe(&localization),
+            });
+        }
     }
 }
 
+fn tick_play_time(time: Res<Time<Real>>, mut play_time: ResMut<PlayTime>) {
+    play_time.seconds += time.delta_secs();
+}
+
 fn request_load_on_hotkey(
     keys: Res<ButtonInput<KeyCode>>,
     mut load_state: ResMut<LoadRequestState>,
+    mut next_state: ResMut<NextState<AppState>>,
+    ui_blocker: Res<UiInputBlocker>,
 ) {
-    if keys.just_pressed(KeyCode::KeyL) {
+    if !ui_blocker.text_input_active && keys.just_pressed(KeyCode::KeyL) {
         load_state.pending = true;
+        next_state.set(AppState::Loading);
     }
 }
 
 fn save_game_on_hotkey(
     keys: Res<ButtonInput<KeyCode>>,
     config: Res<SaveLoadConfig>,
-    wall_query: Query<&GridPosition, With<Wall>>,
+    wall_query: Query<(&GridPosition, &Wall)>,
     floor_query: Query<(&GridPosition, &Floor)>,
-    door_query: Query<(&GridPosition, &Door)>,
+    door_query: Query<(&GridPosition, &Door, &DoorMode)>,
+    window_query: Query<(&GridPosition, &crate::components::Window, Option<&StableId>)>,
+    boundary_query: Query<(&GridPosition, &Boundary)>,
     furniture_query: Query<(
         &GridPosition,
         &Furniture,
         &FurnitureType,
         &FurnitureOrientation,
+        Option<&Sign>,
+        Option<&StableId>,
     )>,
+    zone_query: Query<(&Zone, Option<&StableId>)>,
+    pawn_query: Query<(Entity, &Pawn, Option<&StableId>)>,
+    stats: Res<StatsHistory>,
+    metadata_sources: SaveMetadataSources,
+    map_settings: MapSettings,
+    references: ReferenceSources,
+    ui_blocker: Res<UiInputBlocker>,
+    mut saved: EventWriter<SaveFilesChanged>,
 ) {
-    if !keys.just_pressed(KeyCode::KeyP) {
+    if ui_blocker.text_input_active || !keys.just_pressed(KeyCode::KeyP) {
         return;
     }
 
-    let mut data = collect_save_data(&wall_query, &floor_query, &door_query, &furniture_query);
+    let mut data = collect_save_data(
+        &wall_query,
+        &floor_query,
+        &door_query,
+        &window_query,
+        &boundary_query,
+        &furniture_query,
+        &zone_query,
+        &pawn_query,
+        &stats,
+    );
     sort_save_data(&mut data);
+    map_settings.capture(&mut data);
+    references.capture(&mut data);
+    data.metadata = Some(metadata_sources.capture(&data));
 
     if let Err(err) = write_save_file(&config.path, &data) {
         error!("Failed to save map to {}: {}", config.path, err);
     } else {
         info!("Saved map to {}", config.path);
+        saved.send(SaveFilesChanged);
     }
 }
 
-fn process_load_requests(
+pub fn process_load_requests(
     mut commands: Commands,
     mut load_state: ResMut<LoadRequestState>,
     config: Res<SaveLoadConfig>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    shared_assets: Res<SharedAssets>,
     asset_server: Res<AssetServer>,
     grid_settings: Res<GridSettings>,
     mut building_map: ResMut<BuildingMap>,
-    wall_query: Query<Entity, With<Wall>>,
-    floor_query: Query<Entity, With<Floor>>,
-    door_query: Query<Entity, With<Door>>,
-    furniture_query: Query<Entity, With<Furniture>>,
-    blueprint_query: Query<Entity, With<Blueprint>>,
-    construction_job_query: Query<Entity, With<ConstructionJob>>,
-    deconstruction_job_query: Query<Entity, With<DeconstructionJob>>,
-    marker_query: Query<Entity, With<DeconstructionMarker>>,
+    clear_queries: ClearQueries,
+    mut references: ReferenceHolders,
+    mut stats: ResMut<StatsHistory>,
+    mut play_time: ResMut<PlayTime>,
+    mut money: ResMut<Money>,
+    mut map_settings: MapSettings,
+    mut relinker: EntityRelinker,
+    mut loaded: EventWriter<SaveLoadedEvent>,
 ) {
     if !load_state.pending {
         return;
@@ -165,106 +997,388 @@ fn process_load_requests(
 
     load_state.pending = false;
 
-    let (data, source) = read_or_create_save_file(&config.path);
+    let path = load_state.path.take().unwrap_or_else(|| config.path.clone());
+    let (mut data, source) = match read_or_create_save_file(&path) {
+        Ok(read) => read,
+        Err(err) => {
+            error!(
+                "Failed to read {}: {}. Keeping the current resort.",
+                path, err
+            );
+            return;
+        }
+    };
+    let repairs = repair_save_data(&mut data, &grid_settings);
     clear_structures(
         &mut commands,
-        &wall_query,
-        &floor_query,
-        &door_query,
-        &furniture_query,
-        &blueprint_query,
-        &construction_job_query,
-        &deconstruction_job_query,
-        &marker_query,
+        &clear_queries.walls,
+        &clear_queries.boundaries,
+        &clear_queries.floors,
+        &clear_queries.doors,
+        &clear_queries.furniture,
+        &clear_queries.blueprints,
+        &clear_queries.construction_jobs,
+        &clear_queries.deconstruction_jobs,
+        &clear_queries.markers,
+        &clear_queries.zones,
     );
     apply_save_data(
         &mut commands,
-        &mut meshes,
-        &mut materials,
+        &shared_assets,
         &asset_server,
         &grid_settings,
         &mut building_map,
+        &mut relinker.stable_ids,
+        &data,
+    );
+    relinker.relink();
+    references.apply(
+        &mut commands,
+        &mut relinker.stable_ids,
+        &grid_settings,
         &data,
     );
+    *stats = data.stats.clone();
+    apply_play_time(&data, &mut play_time);
+    apply_money(&data, &mut money);
+    map_settings.apply(&data, &grid_settings);
+    loaded.send(SaveLoadedEvent { repairs });
 
     info!(
-        "Loaded room from {} (walls: {}, floors: {}, doors: {}, furniture: {})",
+        "Loaded room from {} (walls: {}, floors: {}, doors: {}, windows: {}, fences: {}, furniture: {}, zones: {})",
         source,
         data.walls.len(),
         data.floors.len(),
         data.doors.len(),
-        data.furniture.len()
+        data.windows.len(),
+        data.boundaries.len(),
+        data.furniture.len(),
+        data.zones.len()
     );
 }
 
 pub fn collect_save_data(
-    wall_query: &Query<&GridPosition, With<Wall>>,
+    wall_query: &Query<(&GridPosition, &Wall)>,
     floor_query: &Query<(&GridPosition, &Floor)>,
-    door_query: &Query<(&GridPosition, &Door)>,
+    door_query: &Query<(&GridPosition, &Door, &DoorMode)>,
+    window_query: &Query<(&GridPosition, &crate::components::Window, Option<&StableId>)>,
+    boundary_query: &Query<(&GridPosition, &Boundary)>,
     furniture_query: &Query<(
         &GridPosition,
         &Furniture,
         &FurnitureType,
         &FurnitureOrientation,
+        Option<&Sign>,
+        Option<&StableId>,
     )>,
+    zone_query: &Query<(&Zone, Option<&StableId>)>,
+    pawn_query: &Query<(Entity, &Pawn, Option<&StableId>)>,
+    stats: &StatsHistory,
 ) -> SaveData {
     let mut data = SaveData::default();
 
-    for pos in wall_query {
+    for (pos, wall) in wall_query {
         data.walls.push(GridPoint::from(pos));
+        if let Some(tint) = wall.tint {
+            data.wall_paint.push(WallPaintData {
+                position: GridPoint::from(pos),
+                tint,
+            });
+        }
     }
 
     for (pos, floor) in floor_query {
         data.floors.push(FloorData {
             position: GridPoint::from(pos),
             floor_type: floor.floor_type,
+            tint: floor.tint,
         });
     }
 
-    for (pos, door) in door_query {
+    for (pos, door, mode) in door_query {
         data.doors.push(DoorData {
             position: GridPoint::from(pos),
             orientation: door.orientation,
+            mode: *mode,
+        });
+    }
+
+    for (pos, window, id) in window_query {
+        data.windows.push(WindowData {
+            position: GridPoint::from(pos),
+            orientation: window.orientation,
+            id: id.copied(),
+        });
+    }
+
+    for (pos, boundary) in boundary_query {
+        data.boundaries.push(BoundaryData {
+            position: GridPoint::from(pos),
+            boundary_type: boundary.boundary_type,
         });
     }
 
-    for (pos, _furniture_marker, furniture_type, orientation) in furniture_query {
+    for (pos, _furniture_marker, furniture_type, orientation, sign, id) in furniture_query {
         data.furniture.push(FurnitureData {
             position: GridPoint::from(pos),
             furniture_type: *furniture_type,
             orientation: *orientation,
+            sign: sign.cloned(),
+            id: id.copied(),
         });
     }
 
+    for (zone, id) in zone_query {
+        data.zones.push(ZoneData {
+            name: zone.name.clone(),
+            zone_type: zone.zone_type,
+            tiles: zone
+                .tiles
+                .iter()
+                .map(|tile| GridPoint::from(*tile))
+                .collect(),
+            quality: zone.quality,
+            capacity: zone.capacity,
+            access: Some(zone.access),
+            ambience: Some(zone.ambience),
+            cleanliness: None,
+            id: id.copied(),
+        });
+    }
+
+    // Pawns are kept in spawn order so names and ids line up with the same workers on load
+    let mut pawns: Vec<_> = pawn_query.iter().collect();
+    pawns.sort_by_key(|(entity, _, _)| *entity);
+    data.pawns = pawns
+        .into_iter()
+        .map(|(_, pawn, id)| PawnData {
+            name: pawn.name.clone(),
+            id: id.copied(),
+            position: None,
+            staffing: None,
+            hammer: None,
+        })
+        .collect();
+    data.stats = stats.clone();
+
     data
 }
 
+/// Picks up the play time where the save left off. Older saves start counting again.
+pub fn apply_play_time(data: &SaveData, play_time: &mut PlayTime) {
+    play_time.seconds = data
+        .metadata
+        .as_ref()
+        .map_or(0.0, |metadata| metadata.play_time_secs);
+}
+
+/// Puts the bank balance back to what it was when the game was saved. Older saves
+/// without metadata keep the current balance.
+pub fn apply_money(data: &SaveData, money: &mut Money) {
+    if let Some(metadata) = &data.metadata {
+        money.amount = metadata.money;
+        // Blueprints aren't saved, so nothing is set aside for them any more
+        money.reserved = 0;
+    }
+}
+
 pub fn sort_save_data(data: &mut SaveData) {
     data.walls.sort();
+    data.wall_paint
+        .sort_by_key(|entry| (entry.position.x, entry.position.y));
     data.floors
         .sort_by_key(|entry| (entry.position.x, entry.position.y));
     data.doors
         .sort_by_key(|entry| (entry.position.x, entry.position.y));
+    data.windows
+        .sort_by_key(|entry| (entry.position.x, entry.position.y));
+    data.boundaries
+        .sort_by_key(|entry| (entry.position.x, entry.position.y));
     data.furniture
         .sort_by_key(|entry| (entry.position.x, entry.position.y));
+    for zone in &mut data.zones {
+        zone.tiles.sort();
+    }
+    data.zones
+        .sort_by_key(|zone| zone.tiles.first().map(|tile| (tile.x, tile.y)));
 }
 
-pub fn read_or_create_save_file(path: &str) -> (SaveData, String) {
-    match fs::read_to_string(path) {
-        Ok(contents) => match serde_json::from_str(&contents) {
-            Ok(data) => (data, path.to_string()),
-            Err(err) => {
-                error!("Failed to parse {}: {}. Using default room.", path, err);
-                let default = default_room_layout();
-                let _ = write_save_file(path, &default);
-                (default, "built-in default".to_string())
-            }
-        },
-        Err(_) => {
+/// Drops whatever in a save could not have been built: anything off the map, pieces
+/// stacked on a taken tile, furniture with nothing to stand on and doors with no wall.
+/// Doors and windows take the place of a wall under them, as they do when built.
+pub fn repair_save_data(data: &mut SaveData, grid_settings: &GridSettings) -> SaveRepairReport {
+    let mut report = SaveRepairReport::default();
+    let in_bounds = |pos: IVec2| {
+        pos.x >= 0 && pos.y >= 0 && pos.x < grid_settings.width && pos.y < grid_settings.height
+    };
+    let door_tiles =
+        |door: &DoorData| Door::new(door.orientation).tiles_occupied(door.position.into());
+    // Furniture from a mod that is no longer installed is left for the load to skip
+    let known = |entry: &FurnitureData| FurnitureRegistry::global().contains(entry.furniture_type);
+    let furniture_tiles = |entry: &FurnitureData| {
+        entry
+            .furniture_type
+            .tiles_occupied(entry.position.into(), entry.orientation)
+    };
+
+    report.out_of_bounds +=
+        retain_counted(&mut data.floors, |floor| in_bounds(floor.position.into()));
+    report.out_of_bounds += retain_counted(&mut data.walls, |wall| in_bounds((*wall).into()));
+    report.out_of_bounds += retain_counted(&mut data.doors, |door| {
+        door_tiles(door).into_iter().all(in_bounds)
+    });
+    report.out_of_bounds += retain_counted(&mut data.windows, |window| {
+        in_bounds(window.position.into())
+    });
+    report.out_of_bounds += retain_counted(&mut data.boundaries, |boundary| {
+        in_bounds(boundary.position.into())
+    });
+    report.out_of_bounds += retain_counted(&mut data.furniture, |entry| {
+        !known(entry) || furniture_tiles(entry).into_iter().all(in_bounds)
+    });
+
+    let mut floors = HashSet::new();
+    report.overlapping += retain_counted(&mut data.floors, |floor| {
+        floors.insert(IVec2::from(floor.position))
+    });
+    let mut openings = HashSet::new();
+    report.overlapping += retain_counted(&mut data.doors, |door| {
+        let tiles = door_tiles(door);
+        if tiles.iter().any(|tile| openings.contains(tile)) {
+            return false;
+        }
+        openings.extend(tiles);
+        true
+    });
+    report.overlapping += retain_counted(&mut data.windows, |window| {
+        openings.insert(IVec2::from(window.position))
+    });
+    let mut walls = HashSet::new();
+    report.overlapping += retain_counted(&mut data.walls, |wall| {
+        let pos = IVec2::from(*wall);
+        !openings.contains(&pos) && walls.insert(pos)
+    });
+    data.wall_paint
+        .retain(|paint| walls.contains(&IVec2::from(paint.position)));
+
+    let beside_wall = |tile: IVec2| {
+        [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .iter()
+            .any(|offset| walls.contains(&(tile + *offset)))
+    };
+    report.loose_doors += retain_counted(&mut data.doors, |door| {
+        door_tiles(door).into_iter().any(beside_wall)
+    });
+
+    // Furniture can't share a tile with a structure or with other furniture
+    let mut taken: HashSet<IVec2> = walls.clone();
+    taken.extend(
+        data.windows
+            .iter()
+            .map(|window| IVec2::from(window.position)),
+    );
+    taken.extend(data.doors.iter().flat_map(door_tiles));
+    report.overlapping += retain_counted(&mut data.boundaries, |boundary| {
+        taken.insert(IVec2::from(boundary.position))
+    });
+    let mut desks = HashSet::new();
+    data.furniture.retain(|entry| {
+        if !known(entry) || entry.furniture_type.sits_on_desk() {
+            return true;
+        }
+        let tiles = furniture_tiles(entry);
+        if tiles.iter().any(|tile| taken.contains(tile)) {
+            report.overlapping += 1;
+            return false;
+        }
+        // Edge furniture stands outdoors, off the floors
+        if !entry.furniture_type.at_map_edge() && !tiles.iter().all(|tile| floors.contains(tile)) {
+            report.unsupported_furniture += 1;
+            return false;
+        }
+        if entry.furniture_type == FurnitureType::Desk {
+            desks.extend(tiles.iter().copied());
+        }
+        taken.extend(tiles);
+        true
+    });
+    // Desk-top furniture needs a desk left under it
+    report.unsupported_furniture += retain_counted(&mut data.furniture, |entry| {
+        !known(entry)
+            || !entry.furniture_type.sits_on_desk()
+            || desks.contains(&IVec2::from(entry.position))
+    });
+
+    report
+}
+
+/// Keeps the entries that pass, returning how many were dropped
+fn retain_counted<T>(entries: &mut Vec<T>, mut keep: impl FnMut(&T) -> bool) -> usize {
+    let before = entries.len();
+    entries.retain(|entry| keep(entry));
+    before - entries.len()
+}
+
+/// Reads a save in either format. Compression is detected from the file contents, so
+/// plain JSON saves from older versions load no matter what they are called.
+pub fn read_save_file(path: &str) -> std::io::Result<SaveData> {
+    read_save_json(path)
+}
+
+/// The save files in `dir`, by file name, in name order
+pub fn list_save_files(dir: &str) -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|filename| SaveFormat::is_save_file(filename))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Only the metadata of a save, for listing saves without keeping every resort in memory
+pub fn read_save_metadata(path: &str) -> std::io::Result<Option<SaveMetadata>> {
+    #[derive(Deserialize)]
+    struct SaveHeader {
+        #[serde(default)]
+        metadata: Option<SaveMetadata>,
+    }
+
+    read_save_json::<SaveHeader>(path).map(|header| header.metadata)
+}
+
+fn read_save_json<T: DeserializeOwned>(path: &str) -> std::io::Result<T> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut magic = [0; 2];
+    let read = reader.read(&mut magic)?;
+    let reader = magic[..read].chain(reader);
+
+    let data = if magic[..read] == GZIP_MAGIC {
+        serde_json::from_reader(GzDecoder::new(reader))?
+    } else {
+        serde_json::from_reader(reader)?
+    };
+    Ok(data)
+}
+
+/// Reads the save at `path`, starting it with the default room when there is none yet.
+/// Any other failure is passed on and the file left as it is, so a save that can't be
+/// read right now is never overwritten.
+pub fn read_or_create_save_file(path: &str) -> std::io::Result<(SaveData, String)> {
+    match read_save_file(path) {
+        Ok(data) => Ok((data, path.to_string())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
             let default = default_room_layout();
-            let _ = write_save_file(path, &default);
-            (default, "built-in default".to_string())
+            if let Err(err) = write_save_file(path, &default) {
+                warn!("Couldn't write the default room to {}: {}", path, err);
+            }
+            Ok((default, "built-in default".to_string()))
         }
+        Err(err) => Err(err),
     }
 }
 
@@ -272,13 +1386,28 @@ pub fn write_save_file(path: &str, data: &SaveData) -> std::io::Result<()> {
     if let Some(parent) = Path::new(path).parent() {
         fs::create_dir_all(parent)?;
     }
-    let serialized = serde_json::to_string_pretty(data).expect("save data serialization");
-    fs::write(path, serialized)
+
+    let writer = BufWriter::new(File::create(path)?);
+    match SaveFormat::from_path(path) {
+        SaveFormat::Json => {
+            let mut writer = writer;
+            serde_json::to_writer_pretty(&mut writer, data)?;
+            writer.flush()
+        }
+        SaveFormat::CompressedJson => {
+            let mut encoder = GzEncoder::new(writer, Compression::default());
+            serde_json::to_writer(&mut encoder, data)?;
+            encoder.finish()?.flush()
+        }
+    }
 }
 
+/// Walls and windows are cleared together since windows are set into walls. Zones go
+/// too: the saved ones take their place.
 pub fn clear_structures(
     commands: &mut Commands,
-    wall_query: &Query<Entity, With<Wall>>,
+    wall_query: &Query<Entity, Or<(With<Wall>, With<crate::components::Window>)>>,
+    boundary_query: &Query<Entity, With<Boundary>>,
     floor_query: &Query<Entity, With<Floor>>,
     door_query: &Query<Entity, With<Door>>,
     furniture_query: &Query<Entity, With<Furniture>>,
@@ -286,10 +1415,14 @@ pub fn clear_structures(
     construction_job_query: &Query<Entity, With<ConstructionJob>>,
     deconstruction_job_query: &Query<Entity, With<DeconstructionJob>>,
     marker_query: &Query<Entity, With<DeconstructionMarker>>,
+    zone_query: &Query<Entity, With<Zone>>,
 ) {
     for entity in wall_query {
         commands.entity(entity).despawn_recursive();
     }
+    for entity in boundary_query {
+        commands.entity(entity).despawn_recursive();
+    }
     for entity in floor_query {
         commands.entity(entity).despawn_recursive();
     }
@@ -311,15 +1444,18 @@ pub fn clear_structures(
     for entity in marker_query {
         commands.entity(entity).despawn_recursive();
     }
+    for entity in zone_query {
+        commands.entity(entity).despawn_recursive();
+    }
 }
 
 pub fn apply_save_data(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
+    shared_assets: &SharedAssets,
     asset_server: &AssetServer,
     grid_settings: &GridSettings,
     building_map: &mut BuildingMap,
+    stable_ids: &mut StableIds,
     data: &SaveData,
 ) {
     *building_map = BuildingMap::default();
@@ -327,53 +1463,80 @@ pub fn apply_save_data(
     for floor in &data.floors {
         spawn_floor(
             commands,
-            meshes,
-            materials,
+            shared_assets,
             grid_settings,
             building_map,
             floor,
         );
     }
 
+    let wall_paint: HashMap<IVec2, PaintColor> = data
+        .wall_paint
+        .iter()
+        .map(|paint| (IVec2::from(paint.position), paint.tint))
+        .collect();
     for wall in &data.walls {
         spawn_wall(
             commands,
-            meshes,
-            materials,
+            shared_assets,
             grid_settings,
             building_map,
             *wall,
+            wall_paint.get(&IVec2::from(*wall)).copied(),
         );
     }
 
     for door in &data.doors {
         spawn_door(
             commands,
-            meshes,
-            materials,
+            shared_assets,
             grid_settings,
             building_map,
             door,
         );
     }
 
+    for window in &data.windows {
+        spawn_window(
+            commands,
+            shared_assets,
+            grid_settings,
+            building_map,
+            stable_ids,
+            window,
+        );
+    }
+
+    for boundary in &data.boundaries {
+        spawn_boundary(
+            commands,
+            shared_assets,
+            grid_settings,
+            building_map,
+            boundary,
+        );
+    }
+
     for furniture in &data.furniture {
         spawn_furniture(
             commands,
-            meshes,
-            materials,
+            shared_assets,
             asset_server,
             grid_settings,
             building_map,
+            stable_ids,
             furniture,
         );
     }
+
+    for zone in &data.zones {
+        spawn_zone(commands, stable_ids, zone);
+    }
 }
 
 fn spawn_floor(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
+    shared_assets: &SharedAssets,
     grid_settings: &GridSettings,
     building_map: &mut BuildingMap,
     floor: &FloorData,
@@ -387,14 +1550,12 @@ fn spawn_floor(
     );
 
     commands.spawn((
-        Mesh2d(meshes.add(Rectangle::new(
-            grid_settings.tile_size,
-            grid_settings.tile_size,
-        ))),
-        MeshMaterial2d(materials.add(floor.floor_type.color())),
+        Mesh2d(shared_assets.tile_mesh.clone()),
+        MeshMaterial2d(shared_assets.painted_floor_material(floor.floor_type, floor.tint)),
         Transform::from_xyz(world_pos.x, world_pos.y, 0.5),
         Floor {
             floor_type: floor.floor_type,
+            tint: floor.tint,
         },
         GridPosition::new(pos.x, pos.y),
     ));
@@ -404,11 +1565,11 @@ fn spawn_floor(
 
 fn spawn_wall(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
+    shared_assets: &SharedAssets,
     grid_settings: &GridSettings,
     building_map: &mut BuildingMap,
     wall_point: GridPoint,
+    tint: Option<PaintColor>,
 ) {
     let pos = IVec2::from(wall_point);
     let world_pos = grid_to_world(
@@ -420,13 +1581,10 @@ fn spawn_wall(
 
     let wall_entity = commands
         .spawn((
-            Mesh2d(meshes.add(Rectangle::new(
-                grid_settings.tile_size,
-                grid_settings.tile_size,
-            ))),
-            MeshMaterial2d(materials.add(WallMaterial::Stone.color())),
+            Mesh2d(shared_assets.wall_mesh(WallConnections::default())),
+            MeshMaterial2d(shared_assets.painted_wall_material(tint)),
             Transform::from_xyz(world_pos.x, world_pos.y, 2.0),
-            Wall,
+            Wall { tint },
             Building,
             GridPosition::new(pos.x, pos.y),
         ))
@@ -438,8 +1596,7 @@ fn spawn_wall(
 
 fn spawn_door(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
+    shared_assets: &SharedAssets,
     grid_settings: &GridSettings,
     building_map: &mut BuildingMap,
     door_data: &DoorData,
@@ -448,17 +1605,9 @@ fn spawn_door(
     let door = Door::new(door_data.orientation);
     let tiles = door.tiles_occupied(pos);
 
-    let (width, height, offset) = match door_data.orientation {
-        DoorOrientation::Horizontal => (
-            grid_settings.tile_size * 2.0,
-            grid_settings.tile_size * DOOR_THICKNESS,
-            Vec2::new(grid_settings.tile_size / 2.0, 0.0),
-        ),
-        DoorOrientation::Vertical => (
-            grid_settings.tile_size * DOOR_THICKNESS,
-            grid_settings.tile_size * 2.0,
-            Vec2::new(0.0, grid_settings.tile_size / 2.0),
-        ),
+    let offset = match door_data.orientation {
+        DoorOrientation::Horizontal => Vec2::new(grid_settings.tile_size / 2.0, 0.0),
+        DoorOrientation::Vertical => Vec2::new(0.0, grid_settings.tile_size / 2.0),
     };
 
     let base_world = grid_to_world(
@@ -471,10 +1620,11 @@ fn spawn_door(
 
     let door_entity = commands
         .spawn((
-            Mesh2d(meshes.add(Rectangle::new(width, height))),
-            MeshMaterial2d(materials.add(Color::srgb(0.4, 0.3, 0.2))),
+            Mesh2d(shared_assets.door_mesh(door_data.orientation)),
+            MeshMaterial2d(shared_assets.door_material.clone()),
             Transform::from_xyz(adjusted_pos.x, adjusted_pos.y, 2.0),
             Door::new(door_data.orientation),
+            door_data.mode,
             Building,
             GridPosition::new(pos.x, pos.y),
         ))
@@ -485,314 +1635,152 @@ fn spawn_door(
     }
 }
 
-fn spawn_furniture(
+fn spawn_window(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
-    asset_server: &AssetServer,
+    shared_assets: &SharedAssets,
     grid_settings: &GridSettings,
     building_map: &mut BuildingMap,
-    furniture_data: &FurnitureData,
+    stable_ids: &mut StableIds,
+    window_data: &WindowData,
 ) {
-    let pos = IVec2::from(furniture_data.position);
-    let furniture_type = furniture_data.furniture_type;
-    let orientation = furniture_data.orientation;
-
-    let furniture_tiles = furniture_type.tiles_occupied(pos, orientation);
-    let (width_tiles, height_tiles) = furniture_type.oriented_dimensions(orientation);
-    let width_mult = width_tiles as f32;
-    let height_mult = height_tiles as f32;
-
-    // Calculate center position for multi-tile furniture
-    let offset = Vec2::new(
-        (width_mult - 1.0) * grid_settings.tile_size / 2.0,
-        (height_mult - 1.0) * grid_settings.tile_size / 2.0,
-    );
-
-    let base_world_pos = grid_to_world(
+    let pos = IVec2::from(window_data.position);
+    let world_pos = grid_to_world(
         pos,
         grid_settings.tile_size,
         grid_settings.width,
         grid_settings.height,
     );
-    let furniture_pos = base_world_pos + offset;
-
-    // Calculate rotation
-    let rotation_radians = match orientation {
-        FurnitureOrientation::East => 0.0,
-        FurnitureOrientation::South => std::f32::consts::PI / 2.0,
-        FurnitureOrientation::West => std::f32::consts::PI,
-        FurnitureOrientation::North => -std::f32::consts::PI / 2.0,
-    };
 
-    // Sprite paths (matching building.rs constants)
-    const SINGLE_BED_SPRITE_PATH: &str = "generated/furniture/bed.png";
-    const DOUBLE_BED_SPRITE_PATH: &str = "generated/furniture/double_bed.png";
-    const DRESSER_FRONT_SPRITE_PATH: &str = "generated/furniture/dresser.png";
-    const DRESSER_BACK_SPRITE_PATH: &str = "generated/furniture/dresser_back.png";
-    const DRESSER_SIDE_SPRITE_PATH: &str = "generated/furniture/dresser_side.png";
-    const TUB_SPRITE_PATH: &str = "generated/furniture/tub.png";
-    const TOILET_SPRITE_PATH: &str = "generated/furniture/toilet.png";
-    const SINK_SPRITE_PATH: &str = "generated/furniture/sink.png";
-    const END_TABLE_SPRITE_PATH: &str = "generated/furniture/end_table.png";
-    const COMPUTER_SIDE_SPRITE_PATH: &str = "generated/furniture/computer_side.png";
-    const COMPUTER_FRONT_SPRITE_PATH: &str = "generated/furniture/computer_front.png";
-    const COMPUTER_BACK_SPRITE_PATH: &str = "generated/furniture/computer_back.png";
-
-    // Spawn furniture entity based on type
-    let furniture_entity = match furniture_type {
-        FurnitureType::Bed(bed_type) => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let sprite_size = Vec2::new(
-                base_width_tiles as f32 * grid_settings.tile_size,
-                base_height_tiles as f32 * grid_settings.tile_size,
-            );
+    let id = window_data.id.unwrap_or_else(StableId::random);
+    let entity = commands
+        .spawn((
+            Mesh2d(shared_assets.window_mesh(window_data.orientation)),
+            MeshMaterial2d(shared_assets.window_material.clone()),
+            Transform::from_xyz(world_pos.x, world_pos.y, 2.0),
+            crate::components::Window {
+                orientation: window_data.orientation,
+            },
+            Building,
+            GridPosition::new(pos.x, pos.y),
+            id,
+        ))
+        .id();
+    stable_ids.register(id, entity);
 
-            let sprite_path = match bed_type {
-                BedType::Single => SINGLE_BED_SPRITE_PATH,
-                BedType::Double => DOUBLE_BED_SPRITE_PATH,
-            };
+    building_map.occupied.insert(pos);
+}
 
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
-            transform.rotate_z(rotation_radians);
+fn spawn_boundary(
+    commands: &mut Commands,
+    shared_assets: &SharedAssets,
+    grid_settings: &GridSettings,
+    building_map: &mut BuildingMap,
+    boundary_data: &BoundaryData,
+) {
+    let pos = IVec2::from(boundary_data.position);
+    let boundary_type = boundary_data.boundary_type;
+    let world_pos = grid_to_world(
+        pos,
+        grid_settings.tile_size,
+        grid_settings.width,
+        grid_settings.height,
+    );
 
-            commands
-                .spawn((
-                    Sprite {
-                        image: asset_server.load(sprite_path),
-                        custom_size: Some(sprite_size),
-                        ..default()
-                    },
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        FurnitureType::Dresser => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let sprite_size = Vec2::new(
-                base_width_tiles as f32 * grid_settings.tile_size,
-                base_height_tiles as f32 * grid_settings.tile_size,
-            );
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
+    let boundary_entity = commands
+        .spawn((
+            Mesh2d(shared_assets.boundary_mesh.clone()),
+            MeshMaterial2d(shared_assets.boundary_material(boundary_type)),
+            Transform::from_xyz(world_pos.x, world_pos.y, 2.0),
+            Boundary { boundary_type },
+            Building,
+            GridPosition::new(pos.x, pos.y),
+        ))
+        .id();
 
-            let (sprite_path, flip_x) = match orientation {
-                FurnitureOrientation::South => (DRESSER_FRONT_SPRITE_PATH, false),
-                FurnitureOrientation::North => (DRESSER_BACK_SPRITE_PATH, false),
-                FurnitureOrientation::East => (DRESSER_SIDE_SPRITE_PATH, false),
-                FurnitureOrientation::West => (DRESSER_SIDE_SPRITE_PATH, true),
-            };
+    if boundary_type.blocks_movement() {
+        building_map.occupied.insert(pos);
+    }
+    building_map.boundaries.insert(pos, boundary_entity);
+}
 
-            let mut sprite = Sprite {
-                image: asset_server.load(sprite_path),
-                custom_size: Some(sprite_size),
-                ..default()
-            };
-            sprite.flip_x = flip_x;
-
-            commands
-                .spawn((
-                    sprite,
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        FurnitureType::Tub => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let sprite_size = Vec2::new(
-                base_width_tiles as f32 * grid_settings.tile_size,
-                base_height_tiles as f32 * grid_settings.tile_size,
-            );
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
-            transform.rotate_z(rotation_radians);
-
-            commands
-                .spawn((
-                    Sprite {
-                        image: asset_server.load(TUB_SPRITE_PATH),
-                        custom_size: Some(sprite_size),
-                        ..default()
-                    },
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        FurnitureType::Toilet => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let sprite_size = Vec2::new(
-                base_width_tiles as f32 * grid_settings.tile_size,
-                base_height_tiles as f32 * grid_settings.tile_size,
-            );
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
-            transform.rotate_z(rotation_radians);
-
-            commands
-                .spawn((
-                    Sprite {
-                        image: asset_server.load(TOILET_SPRITE_PATH),
-                        custom_size: Some(sprite_size),
-                        ..default()
-                    },
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        FurnitureType::Sink => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let sprite_size = Vec2::new(
-                base_width_tiles as f32 * grid_settings.tile_size,
-                base_height_tiles as f32 * grid_settings.tile_size,
-            );
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
-            transform.rotate_z(rotation_radians);
-
-            commands
-                .spawn((
-                    Sprite {
-                        image: asset_server.load(SINK_SPRITE_PATH),
-                        custom_size: Some(sprite_size),
-                        ..default()
-                    },
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        FurnitureType::Nightstand => {
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let sprite_size = Vec2::new(
-                base_width_tiles as f32 * grid_settings.tile_size,
-                base_height_tiles as f32 * grid_settings.tile_size,
-            );
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
-            transform.rotate_z(rotation_radians);
-
-            commands
-                .spawn((
-                    Sprite {
-                        image: asset_server.load(END_TABLE_SPRITE_PATH),
-                        custom_size: Some(sprite_size),
-                        ..default()
-                    },
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        FurnitureType::ReceptionConsole => {
-            let base_world_pos = grid_to_world(
-                pos,
-                grid_settings.tile_size,
-                grid_settings.width,
-                grid_settings.height,
-            );
+fn spawn_furniture(
+    commands: &mut Commands,
+    shared_assets: &SharedAssets,
+    asset_server: &AssetServer,
+    grid_settings: &GridSettings,
+    building_map: &mut BuildingMap,
+    stable_ids: &mut StableIds,
+    furniture_data: &FurnitureData,
+) {
+    let pos = IVec2::from(furniture_data.position);
+    let furniture_type = furniture_data.furniture_type;
+    let orientation = furniture_data.orientation;
 
-            let (sprite_path, flip_x) = match orientation {
-                FurnitureOrientation::East => (COMPUTER_SIDE_SPRITE_PATH, false),
-                FurnitureOrientation::West => (COMPUTER_SIDE_SPRITE_PATH, true),
-                FurnitureOrientation::South => (COMPUTER_FRONT_SPRITE_PATH, false),
-                FurnitureOrientation::North => (COMPUTER_BACK_SPRITE_PATH, false),
-            };
+    // Mod furniture whose catalog is no longer installed is left out rather than guessed at
+    if !FurnitureRegistry::global().contains(furniture_type) {
+        warn!("Skipping unknown furniture {:?} at {:?}", furniture_type, pos);
+        return;
+    }
 
-            let mut sprite = Sprite {
-                image: asset_server.load(sprite_path),
-                custom_size: Some(Vec2::splat(grid_settings.tile_size * 0.9)),
-                ..default()
-            };
-            sprite.flip_x = flip_x;
-
-            commands
-                .spawn((
-                    sprite,
-                    Transform::from_xyz(base_world_pos.x, base_world_pos.y, 3.5),
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
-        _ => {
-            // Default fallback for other furniture types (desk, chair, etc.)
-            let (base_width_tiles, base_height_tiles) = furniture_type.base_dimensions();
-            let mut transform = Transform::from_xyz(furniture_pos.x, furniture_pos.y, 3.0);
-            transform.rotate_z(rotation_radians);
-
-            commands
-                .spawn((
-                    Mesh2d(meshes.add(Rectangle::new(
-                        base_width_tiles as f32 * grid_settings.tile_size,
-                        base_height_tiles as f32 * grid_settings.tile_size,
-                    ))),
-                    MeshMaterial2d(materials.add(furniture_type.color())),
-                    transform,
-                    GridPosition::new(pos.x, pos.y),
-                    Furniture,
-                    furniture_type,
-                    orientation,
-                ))
-                .id()
-        }
+    let entity = if furniture_type.sits_on_desk() {
+        furniture::place_desk_furniture(
+            commands,
+            furniture_type,
+            pos,
+            orientation,
+            grid_settings,
+            asset_server,
+        )
+    } else {
+        furniture::place_regular_furniture(
+            commands,
+            shared_assets,
+            furniture_type,
+            pos,
+            orientation,
+            grid_settings,
+            asset_server,
+            building_map,
+        )
     };
 
-    // Add specific furniture component markers
-    match furniture_type {
-        FurnitureType::Bed(bed_type) => {
-            commands.entity(furniture_entity).insert(Bed::new(bed_type));
-        }
-        FurnitureType::Desk => {
-            commands.entity(furniture_entity).insert(Desk);
-        }
-        FurnitureType::Chair => {
-            commands.entity(furniture_entity).insert(Chair);
-        }
-        FurnitureType::Dresser => {
-            commands.entity(furniture_entity).insert(Dresser);
-        }
-        FurnitureType::Nightstand => {
-            commands.entity(furniture_entity).insert(Nightstand);
-        }
-        FurnitureType::Toilet => {
-            commands.entity(furniture_entity).insert(Toilet);
-        }
-        FurnitureType::Sink => {
-            commands.entity(furniture_entity).insert(Sink);
-        }
-        FurnitureType::Tub => {
-            commands.entity(furniture_entity).insert(Tub);
-        }
-        FurnitureType::ReceptionConsole => {
-            commands
-                .entity(furniture_entity)
-                .insert(ReceptionConsole::new());
-        }
+    if let Some(sign) = &furniture_data.sign {
+        commands.entity(entity).insert(sign.clone());
     }
 
-    // Mark tiles as occupied
-    for tile_pos in furniture_tiles {
-        building_map.occupied.insert(tile_pos);
+    let id = furniture_data.id.unwrap_or_else(StableId::random);
+    stable_ids.register(id, entity);
+    commands.entity(entity).insert(id);
+}
+
+fn spawn_zone(commands: &mut Commands, stable_ids: &mut StableIds, zone_data: &ZoneData) {
+    let mut zone = Zone::new(zone_data.zone_type, zone_data.name.clone());
+    zone.tiles = zone_data
+        .tiles
+        .iter()
+        .map(|tile| IVec2::from(*tile))
+        .collect();
+    zone.quality = zone_data.quality;
+    zone.capacity = zone_data.capacity;
+    if let Some(access) = zone_data.access {
+        zone.access = access;
+    }
+    if let Some(ambience) = zone_data.ambience {
+        zone.ambience = ambience;
     }
+
+    let entity = if zone.zone_type == ZoneType::GuestBedroom {
+        let cleanliness = zone_data
+            .cleanliness
+            .map_or_else(Cleanliness::default, |level| Cleanliness { level });
+        commands.spawn((zone, cleanliness)).id()
+    } else {
+        commands.spawn(zone).id()
+    };
+
+    let id = zone_data.id.unwrap_or_else(StableId::random);
+    stable_ids.register(id, entity);
+    commands.entity(entity).insert(id);
 }
 
 fn default_room_layout() -> SaveData {
@@ -808,6 +1796,7 @@ fn default_room_layout() -> SaveData {
             data.floors.push(FloorData {
                 position: GridPoint { x, y },
                 floor_type: FloorType::Wood,
+                tint: None,
             });
         }
     }
@@ -827,8 +1816,227 @@ fn default_room_layout() -> SaveData {
     data.doors.push(DoorData {
         position: GridPoint { x: 49, y: min },
         orientation: DoorOrientation::Horizontal,
+        mode: DoorMode::Auto,
     });
 
     sort_save_data(&mut data);
     data
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Just enough of the game to save the resort on P and load a save back
+    fn app(path: &str) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            .init_asset::<Image>()
+            .init_resource::<SharedAssets>()
+            .init_resource::<GridSettings>()
+            .init_resource::<BuildingMap>()
+            .init_resource::<StableIds>()
+            .init_resource::<StatsHistory>()
+            .init_resource::<PlayTime>()
+            .init_resource::<Money>()
+            .init_resource::<GameClock>()
+            .init_resource::<GuestLedger>()
+            .init_resource::<Terrain>()
+            .init_resource::<GuestSpawnSettings>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<UiInputBlocker>()
+            .init_resource::<LoadRequestState>()
+            .insert_resource(SaveLoadConfig {
+                path: path.to_string(),
+            })
+            .add_event::<SaveFilesChanged>()
+            .add_event::<SaveLoadedEvent>()
+            .add_systems(Update, (save_game_on_hotkey, process_load_requests).chain());
+        app
+    }
+
+    fn load(app: &mut App, path: &str) {
+        app.world_mut()
+            .resource_mut::<LoadRequestState>()
+            .request(path.to_string());
+        app.update();
+    }
+
+    fn save(app: &mut App) {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::KeyP);
+        app.update();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(KeyCode::KeyP);
+        keys.clear();
+    }
+
+    fn entity_with(app: &App, id: StableId) -> Entity {
+        app.world().resource::<StableIds>().get(id).unwrap()
+    }
+
+    fn furniture(furniture_type: FurnitureType, x: i32, id: StableId) -> FurnitureData {
+        FurnitureData {
+            position: GridPoint { x, y: 20 },
+            furniture_type,
+            orientation: FurnitureOrientation::default(),
+            sign: None,
+            id: Some(id),
+        }
+    }
+
+    #[test]
+    fn references_come_back_from_the_save_file() {
+        let path = std::env::temp_dir()
+            .join(format!(
+                "resort-tycoon-references-{}.json",
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .to_string();
+        let mut app = app(&path);
+
+        // A desk, a massage table, a tool rack, a window and a bedroom, built by a load
+        // so each has its id
+        let [desk, table, rack, window, room] = [(); 5].map(|_| StableId::random());
+        let mut resort = SaveData::default();
+        for x in 20..=32 {
+            for y in 18..=24 {
+                resort.floors.push(FloorData {
+                    position: GridPoint { x, y },
+                    floor_type: FloorType::Wood,
+                    tint: None,
+                });
+            }
+        }
+        resort.furniture = vec![
+            furniture(FurnitureType::Desk, 20, desk),
+            furniture(FurnitureType::MassageTable, 24, table),
+            furniture(FurnitureType::ToolRack, 28, rack),
+        ];
+        resort.windows.push(WindowData {
+            position: GridPoint { x: 20, y: 26 },
+            orientation: WindowOrientation::Horizontal,
+            id: Some(window),
+        });
+        resort.zones.push(ZoneData {
+            name: "Room 1".to_string(),
+            zone_type: ZoneType::GuestBedroom,
+            tiles: vec![GridPoint { x: 21, y: 21 }],
+            quality: ZoneQuality::Basic,
+            capacity: 0,
+            access: None,
+            ambience: None,
+            cleanliness: None,
+            id: Some(room),
+        });
+        write_save_file(&path, &resort).unwrap();
+        let receptionist = app
+            .world_mut()
+            .spawn((Pawn::default(), Transform::default()))
+            .id();
+        let therapist = app
+            .world_mut()
+            .spawn((Pawn::default(), Transform::default()))
+            .id();
+        load(&mut app, &path);
+
+        // Staff at their posts, one holding a hammer, a couple in the room, storm damage
+        // on the window and the room due a clean
+        let world = app.world_mut();
+        let desk_entity = world.resource::<StableIds>().get(desk).unwrap();
+        let table_entity = world.resource::<StableIds>().get(table).unwrap();
+        let rack_entity = world.resource::<StableIds>().get(rack).unwrap();
+        let window_entity = world.resource::<StableIds>().get(window).unwrap();
+        let room_entity = world.resource::<StableIds>().get(room).unwrap();
+        world
+            .entity_mut(receptionist)
+            .insert(StaffingReception { desk_entity });
+        world.entity_mut(therapist).insert((
+            StaffingSpa { table_entity },
+            HeldHammer { rack: rack_entity },
+        ));
+        let party = GuestParty::new(GuestArchetype::Honeymooners, 2);
+        world
+            .entity_mut(room_entity)
+            .insert(Booking::new(party, 3, 120, ZoneQuality::Basic));
+        world.spawn((
+            Guest {
+                party,
+                room: room_entity,
+                move_speed: GUEST_MOVE_SPEED,
+                activity: GuestActivity::Sleeping,
+                needs: GuestNeeds::default(),
+                hours_left: 2.0,
+            },
+            Transform::default(),
+        ));
+        world.entity_mut(window_entity).insert(Damaged::default());
+        world.spawn(RepairJob::new(window_entity));
+        world.spawn(CleaningJob::new(room_entity, Vec2::ZERO));
+        save(&mut app);
+
+        // Let go of everything, so only the file can bring it back
+        let world = app.world_mut();
+        world.entity_mut(receptionist).remove::<StaffingReception>();
+        world
+            .entity_mut(therapist)
+            .remove::<(StaffingSpa, HeldHammer)>();
+        let guests: Vec<Entity> = world
+            .query_filtered::<Entity, With<Guest>>()
+            .iter(world)
+            .collect();
+        let jobs: Vec<Entity> = world
+            .query_filtered::<Entity, Or<(With<RepairJob>, With<CleaningJob>)>>()
+            .iter(world)
+            .collect();
+        for entity in guests.into_iter().chain(jobs) {
+            world.despawn(entity);
+        }
+        load(&mut app, &path);
+        fs::remove_file(&path).unwrap();
+
+        let rebuilt_desk = entity_with(&app, desk);
+        let rebuilt_room = entity_with(&app, room);
+        let rebuilt_window = entity_with(&app, window);
+        assert_ne!(rebuilt_desk, desk_entity);
+        let world = app.world_mut();
+        assert_eq!(
+            world
+                .get::<StaffingReception>(receptionist)
+                .unwrap()
+                .desk_entity,
+            rebuilt_desk
+        );
+        assert_eq!(
+            world.get::<StaffingSpa>(therapist).unwrap().table_entity,
+            world.resource::<StableIds>().get(table).unwrap()
+        );
+        assert_eq!(
+            world.get::<HeldHammer>(therapist).unwrap().rack,
+            world.resource::<StableIds>().get(rack).unwrap()
+        );
+
+        let guests: Vec<&Guest> = world.query::<&Guest>().iter(world).collect();
+        assert_eq!(guests.len(), 1);
+        assert_eq!(guests[0].room, rebuilt_room);
+        assert_eq!(guests[0].activity, GuestActivity::Sleeping);
+        assert_eq!(world.get::<Booking>(rebuilt_room).unwrap().party, party);
+
+        let repairs: Vec<Entity> = world
+            .query::<&RepairJob>()
+            .iter(world)
+            .map(|job| job.target)
+            .collect();
+        assert_eq!(repairs, [rebuilt_window]);
+        assert!(world.get::<Damaged>(rebuilt_window).is_some());
+        let cleaning: Vec<Entity> = world
+            .query::<&CleaningJob>()
+            .iter(world)
+            .map(|job| job.room)
+            .collect();
+        assert_eq!(cleaning, [rebuilt_room]);
+    }
+}
@@ -0,0 +1,346 @@
+use crate::components::*;
+use crate::systems::grid::TILE_SIZE;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::ColorMaterial;
+use std::collections::HashMap;
+
+/// Blueprints draw windows and doors thinner than the finished structure
+pub const BLUEPRINT_THICKNESS: f32 = 0.2;
+
+pub const DOOR_COLOR: Color = Color::srgb(0.4, 0.3, 0.2);
+pub const WINDOW_COLOR: Color = Color::srgb(0.6, 0.8, 1.0);
+
+/// Canonical meshes and palette materials shared by every tile of the same kind.
+/// Entities whose material is animated per-instance (blueprints, deconstruction
+/// markers) still get their own material but reuse these meshes.
+#[derive(Resource)]
+pub struct SharedAssets {
+    pub tile_mesh: Handle<Mesh>,
+    /// One auto-tiled wall shape per `WallConnections::mask`
+    pub wall_meshes: Vec<Handle<Mesh>>,
+    pub window_horizontal_mesh: Handle<Mesh>,
+    pub window_vertical_mesh: Handle<Mesh>,
+    pub door_horizontal_mesh: Handle<Mesh>,
+    pub door_vertical_mesh: Handle<Mesh>,
+    pub blueprint_window_horizontal_mesh: Handle<Mesh>,
+    pub blueprint_window_vertical_mesh: Handle<Mesh>,
+    pub blueprint_door_horizontal_mesh: Handle<Mesh>,
+    pub blueprint_door_vertical_mesh: Handle<Mesh>,
+    /// One frame per orientation and `FrameJoins::mask`, horizontal first
+    pub door_frame_meshes: Vec<Handle<Mesh>>,
+    pub window_frame_meshes: Vec<Handle<Mesh>>,
+    pub furniture_meshes: HashMap<FurnitureType, Handle<Mesh>>,
+    pub boundary_mesh: Handle<Mesh>,
+
+    pub wall_material: Handle<ColorMaterial>,
+    pub window_material: Handle<ColorMaterial>,
+    pub door_material: Handle<ColorMaterial>,
+    pub door_open_material: Handle<ColorMaterial>,
+    pub floor_materials: HashMap<FloorType, Handle<ColorMaterial>>,
+    pub painted_wall_materials: HashMap<PaintColor, Handle<ColorMaterial>>,
+    pub painted_floor_materials: HashMap<(FloorType, PaintColor), Handle<ColorMaterial>>,
+    pub furniture_materials: HashMap<FurnitureType, Handle<ColorMaterial>>,
+    pub boundary_materials: HashMap<BoundaryType, Handle<ColorMaterial>>,
+}
+
+impl SharedAssets {
+    pub fn wall_mesh(&self, connections: WallConnections) -> Handle<Mesh> {
+        self.wall_meshes[connections.mask()].clone()
+    }
+
+    pub fn window_mesh(&self, orientation: WindowOrientation) -> Handle<Mesh> {
+        match orientation {
+            WindowOrientation::Horizontal => self.window_horizontal_mesh.clone(),
+            WindowOrientation::Vertical => self.window_vertical_mesh.clone(),
+        }
+    }
+
+    pub fn blueprint_window_mesh(&self, orientation: WindowOrientation) -> Handle<Mesh> {
+        match orientation {
+            WindowOrientation::Horizontal => self.blueprint_window_horizontal_mesh.clone(),
+            WindowOrientation::Vertical => self.blueprint_window_vertical_mesh.clone(),
+        }
+    }
+
+    pub fn door_mesh(&self, orientation: DoorOrientation) -> Handle<Mesh> {
+        match orientation {
+            DoorOrientation::Horizontal => self.door_horizontal_mesh.clone(),
+            DoorOrientation::Vertical => self.door_vertical_mesh.clone(),
+        }
+    }
+
+    pub fn blueprint_door_mesh(&self, orientation: DoorOrientation) -> Handle<Mesh> {
+        match orientation {
+            DoorOrientation::Horizontal => self.blueprint_door_horizontal_mesh.clone(),
+            DoorOrientation::Vertical => self.blueprint_door_vertical_mesh.clone(),
+        }
+    }
+
+    pub fn door_frame_mesh(&self, orientation: DoorOrientation, joins: FrameJoins) -> Handle<Mesh> {
+        let vertical = orientation == DoorOrientation::Vertical;
+        self.door_frame_meshes[frame_index(vertical, joins)].clone()
+    }
+
+    pub fn window_frame_mesh(
+        &self,
+        orientation: WindowOrientation,
+        joins: FrameJoins,
+    ) -> Handle<Mesh> {
+        let vertical = orientation == WindowOrientation::Vertical;
+        self.window_frame_meshes[frame_index(vertical, joins)].clone()
+    }
+
+    pub fn floor_material(&self, floor_type: FloorType) -> Handle<ColorMaterial> {
+        self.floor_materials[&floor_type].clone()
+    }
+
+    /// The material for a wall, painted or bare
+    pub fn painted_wall_material(&self, tint: Option<PaintColor>) -> Handle<ColorMaterial> {
+        match tint {
+            Some(tint) => self.painted_wall_materials[&tint].clone(),
+            None => self.wall_material.clone(),
+        }
+    }
+
+    /// The material for a floor, painted or bare
+    pub fn painted_floor_material(
+        &self,
+        floor_type: FloorType,
+        tint: Option<PaintColor>,
+    ) -> Handle<ColorMaterial> {
+        match tint {
+            Some(tint) => self.painted_floor_materials[&(floor_type, tint)].clone(),
+            None => self.floor_material(floor_type),
+        }
+    }
+
+    pub fn furniture_mesh(&self, furniture_type: FurnitureType) -> Handle<Mesh> {
+        self.furniture_meshes[&furniture_type].clone()
+    }
+
+    pub fn furniture_material(&self, furniture_type: FurnitureType) -> Handle<ColorMaterial> {
+        self.furniture_materials[&furniture_type].clone()
+    }
+
+    pub fn boundary_material(&self, boundary_type: BoundaryType) -> Handle<ColorMaterial> {
+        self.boundary_materials[&boundary_type].clone()
+    }
+}
+
+impl FromWorld for SharedAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+
+        let tile_mesh = meshes.add(Rectangle::new(TILE_SIZE, TILE_SIZE));
+        let wall_meshes = (0..16)
+            .map(|mask| meshes.add(wall_mesh(WallConnections::from_mask(mask))))
+            .collect();
+        let window_horizontal_mesh =
+            meshes.add(Rectangle::new(TILE_SIZE, TILE_SIZE * WINDOW_THICKNESS));
+        let window_vertical_mesh =
+            meshes.add(Rectangle::new(TILE_SIZE * WINDOW_THICKNESS, TILE_SIZE));
+        let door_horizontal_mesh =
+            meshes.add(Rectangle::new(TILE_SIZE * 2.0, TILE_SIZE * DOOR_THICKNESS));
+        let door_vertical_mesh =
+            meshes.add(Rectangle::new(TILE_SIZE * DOOR_THICKNESS, TILE_SIZE * 2.0));
+        let blueprint_window_horizontal_mesh =
+            meshes.add(Rectangle::new(TILE_SIZE, TILE_SIZE * BLUEPRINT_THICKNESS));
+        let blueprint_window_vertical_mesh =
+            meshes.add(Rectangle::new(TILE_SIZE * BLUEPRINT_THICKNESS, TILE_SIZE));
+        let blueprint_door_horizontal_mesh = meshes.add(Rectangle::new(
+            TILE_SIZE * 2.0,
+            TILE_SIZE * BLUEPRINT_THICKNESS,
+        ));
+        let blueprint_door_vertical_mesh = meshes.add(Rectangle::new(
+            TILE_SIZE * BLUEPRINT_THICKNESS,
+            TILE_SIZE * 2.0,
+        ));
+
+        let door_frame_meshes = (0..8)
+            .map(|index| {
+                meshes.add(frame_mesh(
+                    2.0,
+                    index >= 4,
+                    FrameJoins::from_mask(index % 4),
+                ))
+            })
+            .collect();
+        let window_frame_meshes = (0..8)
+            .map(|index| {
+                meshes.add(frame_mesh(
+                    1.0,
+                    index >= 4,
+                    FrameJoins::from_mask(index % 4),
+                ))
+            })
+            .collect();
+
+        // Furniture meshes use the unrotated footprint; orientation is a transform rotation
+        let furniture_meshes = FurnitureType::all()
+            .into_iter()
+            .map(|furniture_type| {
+                let (width, height) = furniture_type.base_dimensions();
+                let mesh = meshes.add(Rectangle::new(
+                    width as f32 * TILE_SIZE,
+                    height as f32 * TILE_SIZE,
+                ));
+                (furniture_type, mesh)
+            })
+            .collect();
+        let boundary_mesh = meshes.add(Rectangle::from_length(TILE_SIZE * BOUNDARY_SIZE));
+
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+
+        let floor_materials = FloorType::all()
+            .into_iter()
+            .map(|floor_type| (floor_type, materials.add(floor_type.color())))
+            .collect();
+        let painted_wall_materials = PaintColor::all()
+            .into_iter()
+            .map(|tint| (tint, materials.add(tint.color())))
+            .collect();
+        let painted_floor_materials = FloorType::all()
+            .into_iter()
+            .flat_map(|floor_type| {
+                PaintColor::all()
+                    .into_iter()
+                    .map(move |tint| (floor_type, tint))
+            })
+            .map(|(floor_type, tint)| {
+                let color = tint.over(floor_type.color());
+                ((floor_type, tint), materials.add(color))
+            })
+            .collect();
+        let furniture_materials = FurnitureType::all()
+            .into_iter()
+            .map(|furniture_type| (furniture_type, materials.add(furniture_type.color())))
+            .collect();
+        let boundary_materials = BoundaryType::all()
+            .into_iter()
+            .map(|boundary_type| (boundary_type, materials.add(boundary_type.color())))
+            .collect();
+
+        Self {
+            tile_mesh,
+            wall_meshes,
+            window_horizontal_mesh,
+            window_vertical_mesh,
+            door_horizontal_mesh,
+            door_vertical_mesh,
+            blueprint_window_horizontal_mesh,
+            blueprint_window_vertical_mesh,
+            blueprint_door_horizontal_mesh,
+            blueprint_door_vertical_mesh,
+            door_frame_meshes,
+            window_frame_meshes,
+            furniture_meshes,
+            boundary_mesh,
+            wall_material: materials.add(WallMaterial::Stone.color()),
+            window_material: materials.add(WINDOW_COLOR),
+            door_material: materials.add(DOOR_COLOR),
+            door_open_material: materials.add(DOOR_COLOR.with_alpha(0.3)),
+            floor_materials,
+            painted_wall_materials,
+            painted_floor_materials,
+            furniture_materials,
+            boundary_materials,
+        }
+    }
+}
+
+/// Builds a wall as a central post plus an arm reaching each connected edge, so
+/// straight runs, corners, T-junctions and crossings all join up cleanly
+fn wall_mesh(connections: WallConnections) -> Mesh {
+    let half = TILE_SIZE / 2.0;
+    let core = TILE_SIZE * WALL_THICKNESS / 2.0;
+
+    // (min, max) corners of each rectangle making up the shape
+    let mut rects = vec![(Vec2::new(-core, -core), Vec2::new(core, core))];
+    if connections.north {
+        rects.push((Vec2::new(-core, core), Vec2::new(core, half)));
+    }
+    if connections.east {
+        rects.push((Vec2::new(core, -core), Vec2::new(half, core)));
+    }
+    if connections.south {
+        rects.push((Vec2::new(-core, -half), Vec2::new(core, -core)));
+    }
+    if connections.west {
+        rects.push((Vec2::new(-half, -core), Vec2::new(-core, core)));
+    }
+
+    rects_mesh(rects)
+}
+
+fn frame_index(vertical: bool, joins: FrameJoins) -> usize {
+    (vertical as usize) << 2 | joins.mask()
+}
+
+/// Builds the frame of a door or window `span` tiles long: a jamb as thick as a wall at
+/// each end that meets one, so the wall runs on into the opening without a step where the
+/// door is thinner or the seam shows. Vertical frames are the horizontal ones turned.
+fn frame_mesh(span: f32, vertical: bool, joins: FrameJoins) -> Mesh {
+    let half = TILE_SIZE * span / 2.0;
+    let depth = TILE_SIZE * FRAME_DEPTH;
+    let core = TILE_SIZE * WALL_THICKNESS / 2.0;
+
+    let mut rects = Vec::new();
+    if joins.start {
+        rects.push((Vec2::new(-half, -core), Vec2::new(-half + depth, core)));
+    }
+    if joins.end {
+        rects.push((Vec2::new(half - depth, -core), Vec2::new(half, core)));
+    }
+    if vertical {
+        rects = rects
+            .into_iter()
+            .map(|(min, max)| (Vec2::new(min.y, min.x), Vec2::new(max.y, max.x)))
+            .collect();
+    }
+
+    rects_mesh(rects)
+}
+
+/// One mesh made of axis-aligned rectangles, each given as its (min, max) corners
+fn rects_mesh(rects: Vec<(Vec2, Vec2)>) -> Mesh {
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+    for (min, max) in rects {
+        let start = positions.len() as u32;
+        for corner in [
+            Vec2::new(min.x, min.y),
+            Vec2::new(max.x, min.y),
+            Vec2::new(max.x, max.y),
+            Vec2::new(min.x, max.y),
+        ] {
+            positions.push([corner.x, corner.y, 0.0]);
+            uvs.push([corner.x / TILE_SIZE + 0.5, 0.5 - corner.y / TILE_SIZE]);
+        }
+        indices.extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+pub struct SharedAssetsPlugin;
+
+impl Plugin for SharedAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        let furniture = FurnitureRegistry::global();
+        info!("Loaded {} furniture definitions", furniture.types().count());
+
+        app.insert_resource(furniture.clone())
+            .init_resource::<SharedAssets>();
+    }
+}
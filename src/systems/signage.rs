@@ -0,0 +1,115 @@
+use crate::components::*;
+use crate::systems::amenities::{AmenityRegistry, Signpost};
+use crate::systems::app_state::GameplaySet;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::ui::Localization;
+use bevy::prelude::*;
+
+/// Sign text floats just above the post
+const LABEL_OFFSET: Vec3 = Vec3::new(0.0, TILE_SIZE * 0.9, 5.0);
+const LABEL_FONT_SIZE: f32 = 11.0;
+const LABEL_COLOR: Color = Color::srgb(0.95, 0.9, 0.75);
+
+#[derive(Component)]
+pub struct SignLabel;
+
+pub struct SignagePlugin;
+
+impl Plugin for SignagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_sign_labels, update_sign_labels, update_signposts)
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// What a sign reads: the player's text, or the icon's name, then the way it points
+pub fn sign_text(
+    sign: &Sign,
+    orientation: FurnitureOrientation,
+    localization: &Localization,
+) -> String {
+    let text = if sign.text.trim().is_empty() {
+        localization.t(sign.icon.label_key())
+    } else {
+        sign.text.as_str()
+    };
+    format!("{} {}", text, orientation.arrow())
+}
+
+fn spawn_sign_labels(mut commands: Commands, sign_query: Query<Entity, Added<Sign>>) {
+    for sign in &sign_query {
+        commands.entity(sign).with_children(|parent| {
+            parent.spawn((
+                Text2d::default(),
+                TextFont {
+                    font_size: LABEL_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(LABEL_COLOR),
+                Transform::from_translation(LABEL_OFFSET),
+                SignLabel,
+            ));
+        });
+    }
+}
+
+fn update_sign_labels(
+    localization: Res<Localization>,
+    sign_query: Query<(Ref<Sign>, Ref<FurnitureOrientation>, &Transform, &Children)>,
+    mut label_query: Query<(&mut Text2d, &mut Transform), (With<SignLabel>, Without<Sign>)>,
+) {
+    for (sign, orientation, transform, children) in &sign_query {
+        if !sign.is_changed() && !orientation.is_changed() && !localization.is_changed() {
+            continue;
+        }
+
+        let text = sign_text(&sign, *orientation, &localization);
+        // The post is turned to face its way; the label above it stays upright
+        let upright = transform.rotation.inverse();
+        for child in children.iter() {
+            if let Ok((mut label, mut label_transform)) = label_query.get_mut(*child) {
+                label.0.clone_from(&text);
+                label_transform.rotation = upright;
+                label_transform.translation = upright * LABEL_OFFSET;
+            }
+        }
+    }
+}
+
+/// Keeps the signs guests read in step with the ones standing in the resort
+fn update_signposts(
+    mut registry: ResMut<AmenityRegistry>,
+    grid_settings: Res<GridSettings>,
+    sign_query: Query<(&GridPosition, &FurnitureOrientation, &Sign)>,
+    changed_signs: Query<
+        (),
+        (
+            With<Sign>,
+            Or<(Changed<Sign>, Changed<FurnitureOrientation>)>,
+        ),
+    >,
+    mut removed_signs: RemovedComponents<Sign>,
+) {
+    let removed = removed_signs.read().count() > 0;
+    if changed_signs.is_empty() && !removed {
+        return;
+    }
+
+    registry.signs = sign_query
+        .iter()
+        .map(|(grid_pos, orientation, sign)| Signpost {
+            position: grid_to_world(
+                grid_pos.to_ivec2(),
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            ),
+            direction: orientation.facing().as_vec2(),
+            destination: sign.icon.destination(),
+        })
+        .collect();
+}
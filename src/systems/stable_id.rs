@@ -0,0 +1,126 @@
+use crate::components::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Which entity carries each stable id
+#[derive(Resource, Default, Debug)]
+pub struct StableIds {
+    entities: HashMap<StableId, Entity>,
+    ids: HashMap<Entity, StableId>,
+}
+
+impl StableIds {
+    pub fn get(&self, id: StableId) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    pub fn id_of(&self, entity: Entity) -> Option<StableId> {
+        self.ids.get(&entity).copied()
+    }
+
+    /// Points `id` at `entity`, taking it over from whatever held it before. An entity
+    /// given a saved id in place of its own lets go of the old one.
+    pub fn register(&mut self, id: StableId, entity: Entity) {
+        if let Some(old) = self.ids.insert(entity, id) {
+            if old != id && self.entities.get(&old) == Some(&entity) {
+                self.entities.remove(&old);
+            }
+        }
+        self.entities.insert(id, entity);
+    }
+
+    fn forget(&mut self, entity: Entity) {
+        if let Some(id) = self.ids.remove(&entity) {
+            // The id may already belong to the entity rebuilt from a save
+            if self.entities.get(&id) == Some(&entity) {
+                self.entities.remove(&id);
+            }
+        }
+    }
+
+    /// Where a reference to `entity` should point now. An entity despawned by a load
+    /// resolves to the one rebuilt with its id; anything else is left as it is.
+    pub fn relinked(&self, entity: Entity) -> Entity {
+        self.id_of(entity)
+            .and_then(|id| self.get(id))
+            .unwrap_or(entity)
+    }
+}
+
+pub struct StableIdPlugin;
+
+impl Plugin for StableIdPlugin {
+    fn build(&self, app: &mut App) {
+        // Not gameplay: loading spawns furniture outside the game too
+        app.init_resource::<StableIds>()
+            .add_systems(PostUpdate, (assign_stable_ids, index_stable_ids).chain());
+    }
+}
+
+/// Furniture, windows, pawns and zones are what other entities point at across a load
+fn assign_stable_ids(
+    mut commands: Commands,
+    mut stable_ids: ResMut<StableIds>,
+    query: Query<
+        Entity,
+        (
+            Without<StableId>,
+            Or<(
+                With<Furniture>,
+                With<crate::components::Window>,
+                With<Pawn>,
+                With<Zone>,
+            )>,
+        ),
+    >,
+) {
+    for entity in &query {
+        let id = StableId::random();
+        stable_ids.register(id, entity);
+        commands.entity(entity).insert(id);
+    }
+}
+
+fn index_stable_ids(
+    mut stable_ids: ResMut<StableIds>,
+    added_query: Query<(Entity, &StableId), Added<StableId>>,
+    mut removed: RemovedComponents<StableId>,
+) {
+    for entity in removed.read() {
+        stable_ids.forget(entity);
+    }
+    for (entity, id) in &added_query {
+        if stable_ids.get(*id) != Some(entity) {
+            stable_ids.register(*id, entity);
+        }
+    }
+}
+
+/// The entity references a load would otherwise leave pointing at despawned furniture
+/// and zones, for what a save doesn't keep: hammers on their way from a rack, workers
+/// on break in a staff room and pests in a room. Staff posts, held hammers, guests and
+/// their rooms, and repair and cleaning jobs are saved by stable id instead.
+#[derive(SystemParam)]
+pub struct EntityRelinker<'w, 's> {
+    pub stable_ids: ResMut<'w, StableIds>,
+    fetching_hammers: Query<'w, 's, &'static mut FetchingHammer>,
+    breaks: Query<'w, 's, &'static mut OnBreak>,
+    infestations: Query<'w, 's, &'static mut Infestation>,
+}
+
+impl EntityRelinker<'_, '_> {
+    /// Run once the saved resort has been spawned, so the rebuilt furniture has its ids
+    pub fn relink(&mut self) {
+        let stable_ids = &self.stable_ids;
+        for mut fetching in &mut self.fetching_hammers {
+            fetching.rack = stable_ids.relinked(fetching.rack);
+        }
+        for mut on_break in &mut self.breaks {
+            on_break.zone = stable_ids.relinked(on_break.zone);
+        }
+        for mut infestation in &mut self.infestations {
+            infestation.zone = stable_ids.relinked(infestation.zone);
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::economy::{FinanceLedger, Money, StaffWages};
+use crate::systems::grid::{grid_to_world, GridSettings};
+use crate::systems::guests::zone_center;
+use crate::systems::time_control::{GameClock, SECONDS_PER_GAME_HOUR};
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+
+/// One-off fee for bringing on a new worker
+pub const HIRE_COST: i32 = 500;
+
+/// Energy lost per game hour on the job, and while standing around between jobs
+const WORK_ENERGY_PER_HOUR: f32 = 0.08;
+const IDLE_ENERGY_PER_HOUR: f32 = 0.03;
+/// Energy regained per game hour resting in a staff room
+const BREAK_ENERGY_PER_HOUR: f32 = 0.5;
+/// Morale drifts toward what the job is currently worth to the worker, sinking more
+/// slowly than it recovers
+const MORALE_DECAY_PER_HOUR: f32 = 0.02;
+const MORALE_RECOVERY_PER_HOUR: f32 = 0.05;
+/// Morale of a fairly paid, rested worker at a resort breaking even
+const BASE_MORALE: f32 = 0.7;
+/// Hours over which workload is averaged
+const WORKLOAD_WINDOW_HOURS: f32 = 12.0;
+/// Working more than this share of the time starts to wear staff down
+const COMFORTABLE_WORKLOAD: f32 = 0.5;
+const OVERWORK_MORALE_PENALTY: f32 = 0.8;
+const NO_STAFF_ROOM_MORALE_PENALTY: f32 = 0.25;
+const EXHAUSTED_MORALE_PENALTY: f32 = 0.15;
+/// Money in the bank at which staff feel the resort is doing as well as it can
+const PROSPEROUS_MONEY: f32 = 20000.0;
+const PROSPERITY_MORALE_BONUS: f32 = 0.1;
+/// Staff whose morale stays below this for a whole day hand in their notice
+const QUITTING_MORALE: f32 = 0.2;
+const QUIT_AFTER_HOURS: f32 = 24.0;
+/// Scheduled breaks: free staff who could use a rest leave within an hour of these
+const BREAK_HOURS: [f32; 3] = [10.0, 15.0, 20.0];
+const BREAK_WINDOW_HOURS: f32 = 1.0;
+/// Staff below this energy take the scheduled break
+const BREAK_WANTED_ENERGY: f32 = 0.8;
+/// Staff below this energy go on break as soon as they are free
+const EXHAUSTED_ENERGY: f32 = 0.25;
+/// A break ends once the worker is this rested, or after the longest break allowed
+const RESTED_ENERGY: f32 = 0.95;
+const MAX_BREAK_HOURS: f32 = 3.0;
+
+/// A worker saw a job through to the end, as opposed to dropping it or having it
+/// cancelled under them
+#[derive(Event, Debug, Clone, Copy)]
+pub struct JobFinishedEvent {
+    pub pawn: Entity,
+    pub job: Entity,
+    pub kind: JobKind,
+}
+
+pub struct StaffPlugin;
+
+impl Plugin for StaffPlugin {
+    fn build(&self, app: &mut App) {
+        // FixedUpdate runs ahead of Update each frame, so needs are settled before anyone
+        // quits or takes a break over them
+        app.add_event::<JobFinishedEvent>()
+            .add_systems(FixedUpdate, update_staff_needs.in_set(GameplaySet))
+            .add_systems(
+                Update,
+                (
+                    (
+                        quit_miserable_staff,
+                        pay_staff_wages,
+                        start_staff_breaks,
+                        end_staff_breaks,
+                    )
+                        .chain(),
+                    (note_job_starts, record_finished_jobs).chain(),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Staff rooms too small to count don't give anyone a break
+fn is_staff_room(zone: &Zone) -> bool {
+    zone.zone_type == ZoneType::StaffRoom && zone.quality != ZoneQuality::None
+}
+
+fn is_break_time(hour: f32) -> bool {
+    BREAK_HOURS
+        .iter()
+        .any(|&start| (start..start + BREAK_WINDOW_HOURS).contains(&hour))
+}
+
+/// How well the resort is doing, as staff see it: in the red hurts, a healthy
+/// balance helps
+fn prosperity(money: &Money) -> f32 {
+    if money.infinite {
+        return PROSPERITY_MORALE_BONUS;
+    }
+    (money.amount as f32 / PROSPEROUS_MONEY).clamp(-1.0, 1.0) * PROSPERITY_MORALE_BONUS
+}
+
+/// The morale a worker settles at under the current pay, workload and conditions
+fn target_morale(
+    needs: &StaffNeeds,
+    wages: &StaffWages,
+    has_staff_room: bool,
+    money: &Money,
+) -> f32 {
+    let mut morale = BASE_MORALE + wages.morale_effect() + prosperity(money);
+    morale -= (needs.workload - COMFORTABLE_WORKLOAD).max(0.0) * OVERWORK_MORALE_PENALTY;
+    if !has_staff_room {
+        morale -= NO_STAFF_ROOM_MORALE_PENALTY;
+    }
+    if needs.energy < EXHAUSTED_ENERGY {
+        morale -= EXHAUSTED_MORALE_PENALTY;
+    }
+    morale.clamp(0.0, 1.0)
+}
+
+/// Free floor nearest the middle of the room, so resting staff aren't sent into furniture
+fn rest_spot(
+    zone: &Zone,
+    building_map: &BuildingMap,
+    grid_settings: &GridSettings,
+) -> Option<Vec2> {
+    let center = zone_center(zone, grid_settings);
+    zone.tiles
+        .iter()
+        .filter(|tile| !building_map.occupied.contains(tile))
+        .map(|&tile| {
+            grid_to_world(
+                tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        })
+        .min_by(|a, b| {
+            a.distance_squared(center)
+                .total_cmp(&b.distance_squared(center))
+        })
+}
+
+/// Energy drains on the job, comes back on a break in a staff room and holds steady on a
+/// training course. Morale drifts with pay, workload, whether there is a staff room, and
+/// how the resort is doing.
+fn update_staff_needs(
+    time: Res<Time>,
+    wages: Res<StaffWages>,
+    money: Res<Money>,
+    zone_query: Query<&Zone>,
+    mut pawn_query: Query<
+        (
+            &mut StaffNeeds,
+            &CurrentJob,
+            &GridPosition,
+            Has<StaffingReception>,
+            Has<StaffingSpa>,
+            Option<&OnBreak>,
+            Has<InTraining>,
+        ),
+        With<Pawn>,
+    >,
+) {
+    let hours = time.delta_secs() / SECONDS_PER_GAME_HOUR;
+    if hours <= 0.0 {
+        return;
+    }
+
+    let has_staff_room = zone_query.iter().any(is_staff_room);
+
+    for (mut needs, current_job, grid_pos, at_desk, at_spa, on_break, training) in &mut pawn_query {
+        let resting = on_break
+            .and_then(|on_break| zone_query.get(on_break.zone).ok())
+            .is_some_and(|zone| zone.contains_tile(grid_pos.to_ivec2()));
+        let working = current_job.job_id.is_some() || at_desk || at_spa;
+
+        // A day on a course is neither rest nor hard work
+        let energy_per_hour = if resting {
+            BREAK_ENERGY_PER_HOUR
+        } else if training {
+            0.0
+        } else if working {
+            -WORK_ENERGY_PER_HOUR
+        } else {
+            -IDLE_ENERGY_PER_HOUR
+        };
+
+        needs.energy = (needs.energy + energy_per_hour * hours).clamp(0.0, 1.0);
+
+        let worked = if working { 1.0 } else { 0.0 };
+        let blend = (hours / WORKLOAD_WINDOW_HOURS).min(1.0);
+        needs.workload += (worked - needs.workload) * blend;
+
+        let target = target_morale(&needs, &wages, has_staff_room, &money);
+        needs.morale = if needs.morale < target {
+            (needs.morale + MORALE_RECOVERY_PER_HOUR * hours).min(target)
+        } else {
+            (needs.morale - MORALE_DECAY_PER_HOUR * hours).max(target)
+        };
+
+        if needs.morale < QUITTING_MORALE {
+            needs.miserable_hours += hours;
+        } else {
+            needs.miserable_hours = 0.0;
+        }
+    }
+}
+
+/// Staff who have been miserable for a whole day walk out. Their job goes back on
+/// the board for someone else.
+fn quit_miserable_staff(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    pawn_query: Query<(Entity, &Pawn, &StaffNeeds)>,
+) {
+    for (pawn_entity, pawn, needs) in &pawn_query {
+        if needs.miserable_hours < QUIT_AFTER_HOURS {
+            continue;
+        }
+
+        notifications.send(Notification {
+            message: localization.format("staff.quit", &[("name", pawn.name.clone())]),
+        });
+        commands.entity(pawn_entity).despawn_recursive();
+    }
+}
+
+/// Wages are paid each morning, even if it puts the resort in the red
+fn pay_staff_wages(
+    clock: Res<GameClock>,
+    wages: Res<StaffWages>,
+    localization: Res<Localization>,
+    mut money: ResMut<Money>,
+    mut finance: ResMut<FinanceLedger>,
+    pawn_query: Query<(), With<Pawn>>,
+    mut last_day: Local<Option<u32>>,
+) {
+    let day = clock.day();
+    let Some(previous) = last_day.replace(day) else {
+        return;
+    };
+    if previous == day {
+        return;
+    }
+
+    let workers = pawn_query.iter().count() as i32;
+    if workers == 0 || money.infinite {
+        return;
+    }
+
+    let total = wages.daily_wage * workers;
+    money.add(-total);
+    finance.record(
+        day,
+        localization.format("staff.payroll", &[("workers", workers.to_string())]),
+        -total,
+    );
+}
+
+/// At the scheduled break times free staff who could use a rest head for the nearest
+/// staff room, and exhausted staff go as soon as they finish their job.
+/// Receptionists and therapists leave their post for someone else to cover.
+pub fn start_staff_breaks(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    zone_query: Query<(Entity, &Zone)>,
+    pawn_query: Query<
+        (Entity, &Transform, &CurrentJob, &StaffNeeds),
+        (With<Pawn>, Without<OnBreak>, Without<InTraining>),
+    >,
+) {
+    let staff_rooms: Vec<_> = zone_query
+        .iter()
+        .filter(|(_, zone)| is_staff_room(zone))
+        .filter_map(|(entity, zone)| {
+            rest_spot(zone, &building_map, &grid_settings).map(|spot| (entity, spot))
+        })
+        .collect();
+    if staff_rooms.is_empty() {
+        return;
+    }
+
+    let break_time = is_break_time(clock.hour());
+
+    for (pawn_entity, transform, current_job, needs) in &pawn_query {
+        if current_job.job_id.is_some() {
+            continue;
+        }
+
+        let wants_break =
+            needs.energy < EXHAUSTED_ENERGY || (break_time && needs.energy < BREAK_WANTED_ENERGY);
+        if !wants_break {
+            continue;
+        }
+
+        let pawn_pos = transform.translation.truncate();
+        let Some(&(zone, spot)) = staff_rooms.iter().min_by(|a, b| {
+            a.1.distance_squared(pawn_pos)
+                .total_cmp(&b.1.distance_squared(pawn_pos))
+        }) else {
+            continue;
+        };
+
+        commands
+            .entity(pawn_entity)
+            .remove::<(StaffingReception, StaffingSpa)>()
+            .insert((
+                OnBreak {
+                    zone,
+                    started_hours: clock.elapsed_hours,
+                },
+                MovementTarget { target: spot },
+            ));
+    }
+}
+
+/// Breaks end once the worker is rested, runs too long (say the room can't be
+/// reached), or the staff room stops being one
+fn end_staff_breaks(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    zone_query: Query<&Zone>,
+    pawn_query: Query<(Entity, &StaffNeeds, &OnBreak)>,
+) {
+    for (pawn_entity, needs, on_break) in &pawn_query {
+        let room_gone = zone_query
+            .get(on_break.zone)
+            .map_or(true, |zone| !is_staff_room(zone));
+        let overran = clock.elapsed_hours - on_break.started_hours > MAX_BREAK_HOURS;
+
+        if needs.energy >= RESTED_ENERGY || overran || room_gone {
+            commands
+                .entity(pawn_entity)
+                .remove::<(OnBreak, MovementTarget, PawnPath)>();
+        }
+    }
+}
+
+/// Notes the time a worker takes on each job, so it can be timed once they finish it
+fn note_job_starts(
+    clock: Res<GameClock>,
+    mut pawn_query: Query<(&CurrentJob, &mut JobHistory), Changed<CurrentJob>>,
+) {
+    for (current_job, mut history) in &mut pawn_query {
+        if let Some(job) = current_job.job_id {
+            history.start(job, clock.elapsed_hours);
+        }
+    }
+}
+
+fn record_finished_jobs(
+    clock: Res<GameClock>,
+    mut events: EventReader<JobFinishedEvent>,
+    mut pawn_query: Query<&mut JobHistory>,
+) {
+    for event in events.read() {
+        if let Ok(mut history) = pawn_query.get_mut(event.pawn) {
+            history.finish(event.job, event.kind, clock.elapsed_hours);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::time_control::GameClock;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Snapshots of how the resort did on one day, taken each midnight
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DailyStats {
+    pub day: u32,
+    /// Share of rentable guest bedrooms with a booking, from 0 to 1
+    pub occupancy: f32,
+    /// Mean star rating of the rentable guest bedrooms
+    pub average_quality: f32,
+    /// Guests staying that night
+    pub guests: u32,
+    /// Nightly room revenue spread over every rentable bedroom, booked or not
+    pub revpar: f32,
+    /// Average share of their time staff spent working, from 0 to 1
+    pub staff_utilization: f32,
+}
+
+/// Day-by-day resort statistics for the statistics panel. Saved with the game.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsHistory {
+    pub samples: Vec<DailyStats>,
+}
+
+impl StatsHistory {
+    /// Older days are dropped once the history holds this many
+    pub const MAX_SAMPLES: usize = 120;
+
+    pub fn record(&mut self, sample: DailyStats) {
+        self.samples.push(sample);
+        if self.samples.len() > Self::MAX_SAMPLES {
+            let excess = self.samples.len() - Self::MAX_SAMPLES;
+            self.samples.drain(..excess);
+        }
+    }
+
+    pub fn latest(&self) -> Option<&DailyStats> {
+        self.samples.last()
+    }
+}
+
+/// How a party rated their stay as they left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Review {
+    Good,
+    Bad,
+}
+
+/// Reviews and notable incidents not yet reported by a night audit, each with the day
+/// it happened on
+#[derive(Resource, Debug, Default)]
+pub struct DayLog {
+    pub reviews: Vec<(u32, Review)>,
+    pub incidents: Vec<(u32, String)>,
+}
+
+impl DayLog {
+    pub fn review(&mut self, day: u32, review: Review) {
+        self.reviews.push((day, review));
+    }
+
+    pub fn incident(&mut self, day: u32, description: impl Into<String>) {
+        self.incidents.push((day, description.into()));
+    }
+
+    /// Hands over everything logged up to the end of `day`, keeping anything later
+    pub fn close_day(&mut self, day: u32) -> DayLog {
+        let (reviews, later_reviews): (Vec<_>, Vec<_>) = self
+            .reviews
+            .drain(..)
+            .partition(|(logged, _)| *logged <= day);
+        let (incidents, later_incidents): (Vec<_>, Vec<_>) = self
+            .incidents
+            .drain(..)
+            .partition(|(logged, _)| *logged <= day);
+        self.reviews = later_reviews;
+        self.incidents = later_incidents;
+        DayLog { reviews, incidents }
+    }
+}
+
+pub struct StatisticsPlugin;
+
+impl Plugin for StatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatsHistory>()
+            .init_resource::<DayLog>()
+            .add_systems(Update, sample_daily_stats.in_set(GameplaySet));
+    }
+}
+
+/// Guest bedrooms that can currently be let: furnished well enough to rate and not blocked
+/// (by the player or for lack of privacy)
+fn is_rentable(zone: &Zone, blocked: bool) -> bool {
+    zone.zone_type == ZoneType::GuestBedroom && zone.quality != ZoneQuality::None && !blocked
+}
+
+/// Takes the day's snapshot as each new day begins
+pub fn sample_daily_stats(
+    clock: Res<GameClock>,
+    mut history: ResMut<StatsHistory>,
+    zone_query: Query<(&Zone, Option<&Booking>, Has<RoomBlocked>, Has<PrivacyIssue>)>,
+    staff_query: Query<&StaffNeeds, With<Pawn>>,
+    mut last_day: Local<Option<u32>>,
+) {
+    let day = clock.day();
+    let Some(previous) = last_day.replace(day) else {
+        return;
+    };
+    if previous == day {
+        return;
+    }
+
+    let mut rooms = 0;
+    let mut booked = 0;
+    let mut stars = 0;
+    let mut guests = 0;
+    let mut room_revenue = 0;
+    for (zone, booking, blocked, not_private) in &zone_query {
+        // Guests already in a room count even if it has since been blocked
+        if let Some(booking) = booking {
+            booked += 1;
+            guests += booking.party.size as u32;
+            room_revenue += booking.nightly_rate;
+        }
+        if is_rentable(zone, blocked || not_private) || booking.is_some() {
+            rooms += 1;
+            stars += zone.quality.stars() as u32;
+        }
+    }
+
+    let staff: Vec<_> = staff_query.iter().collect();
+    let staff_utilization = if staff.is_empty() {
+        0.0
+    } else {
+        staff.iter().map(|needs| needs.workload).sum::<f32>() / staff.len() as f32
+    };
+
+    let per_room = |value: f32| {
+        if rooms == 0 {
+            0.0
+        } else {
+            value / rooms as f32
+        }
+    };
+
+    history.record(DailyStats {
+        day: previous,
+        occupancy: per_room(booked as f32),
+        average_quality: per_room(stars as f32),
+        guests,
+        revpar: per_room(room_revenue as f32),
+        staff_utilization,
+    });
+}
@@ -0,0 +1,433 @@
+use crate::components::ScenicView;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::ascii_renderer::AsciiMode;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::GridSettings;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Elevation of the sea; nothing can be built on it or walk across it
+pub const SEA_LEVEL: u8 = 0;
+/// Elevation of the highland, the top of the map
+const HIGHEST_LEVEL: u8 = 3;
+/// Ramps cross each cliff line this often, and are this many tiles wide
+const RAMP_SPACING: i32 = 32;
+const RAMP_WIDTH: i32 = 3;
+/// How far a window can see across open ground
+const VIEW_DISTANCE: i32 = 40;
+/// Sideways lean of each view ray, per tile looked out, so a window sees a fan rather than
+/// a single line
+const VIEW_SPREAD: [f32; 5] = [-0.5, -0.25, 0.0, 0.25, 0.5];
+/// Drawn beneath floors and everything built on them
+const TERRAIN_Z: f32 = -1.0;
+
+/// Height of the ground on every tile. The map slopes up from the sea along its southern
+/// edge to a beach, then a bluff up to the lowland the resort starts on, and a ridge up to
+/// the highland in the north. Tiles one level apart only connect over a ramp.
+#[derive(Resource)]
+pub struct Terrain {
+    width: i32,
+    height: i32,
+    elevation: Vec<u8>,
+    ramps: HashSet<IVec2>,
+    /// Set once the ground has been painted over, so saves know to keep it
+    edited: bool,
+}
+
+/// What the scenario editor paints onto the ground: one of the four levels, or a ramp
+/// joining two of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerrainBrush {
+    Sea,
+    Beach,
+    Lowland,
+    Highland,
+    Ramp,
+}
+
+impl TerrainBrush {
+    pub const ALL: [TerrainBrush; 5] = [
+        TerrainBrush::Sea,
+        TerrainBrush::Beach,
+        TerrainBrush::Lowland,
+        TerrainBrush::Highland,
+        TerrainBrush::Ramp,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            TerrainBrush::Sea => "Sea",
+            TerrainBrush::Beach => "Beach",
+            TerrainBrush::Lowland => "Lowland",
+            TerrainBrush::Highland => "Highland",
+            TerrainBrush::Ramp => "Ramp",
+        }
+    }
+
+    /// The height this brush levels ground to; ramps leave it as it is
+    fn elevation(&self) -> Option<u8> {
+        match self {
+            TerrainBrush::Sea => Some(SEA_LEVEL),
+            TerrainBrush::Beach => Some(1),
+            TerrainBrush::Lowland => Some(2),
+            TerrainBrush::Highland => Some(HIGHEST_LEVEL),
+            TerrainBrush::Ramp => None,
+        }
+    }
+}
+
+/// Painted ground as saves and scenarios store it: each row of the map from the bottom
+/// up as one height digit per tile, and the tiles with a ramp
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerrainData {
+    pub width: i32,
+    pub height: i32,
+    pub rows: Vec<String>,
+    pub ramps: Vec<[i32; 2]>,
+}
+
+impl Terrain {
+    /// The same map every time, so saves only store ground that was painted over
+    pub fn generate(width: i32, height: i32) -> Self {
+        let wave = |x: i32, period: f32, amplitude: f32, phase: f32| {
+            ((x as f32 / period + phase).sin() * amplitude).round() as i32
+        };
+        // Rows where each level begins, column by column
+        let coast = |x| 12 + wave(x, 9.0, 3.0, 0.0) + wave(x, 23.0, 2.0, 1.3);
+        let bluff = |x| coast(x) + 12 + wave(x, 14.0, 3.0, 2.1);
+        let ridge = |x| height - 30 + wave(x, 17.0, 4.0, 0.7);
+
+        let mut elevation = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                elevation.push(if y < coast(x) {
+                    SEA_LEVEL
+                } else if y < bluff(x) {
+                    1
+                } else if y < ridge(x) {
+                    2
+                } else {
+                    3
+                });
+            }
+        }
+
+        // A ramp covers the tiles either side of the cliff line
+        let mut ramps = HashSet::new();
+        for start in (RAMP_SPACING / 2..width).step_by(RAMP_SPACING as usize) {
+            for x in start..(start + RAMP_WIDTH).min(width) {
+                for line in [bluff(x), ridge(x)] {
+                    ramps.insert(IVec2::new(x, line - 1));
+                    ramps.insert(IVec2::new(x, line));
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            elevation,
+            ramps,
+            edited: false,
+        }
+    }
+
+    /// Painted ground read back from a save. None if it doesn't fit a `width` by `height`
+    /// map or has a height that doesn't exist.
+    pub fn from_data(data: &TerrainData, width: i32, height: i32) -> Option<Self> {
+        if data.width != width || data.height != height || data.rows.len() != height as usize {
+            return None;
+        }
+
+        let mut elevation = Vec::with_capacity((width * height) as usize);
+        for row in &data.rows {
+            if row.chars().count() != width as usize {
+                return None;
+            }
+            for digit in row.chars() {
+                let level = digit.to_digit(10).filter(|level| *level <= HIGHEST_LEVEL as u32)?;
+                elevation.push(level as u8);
+            }
+        }
+
+        let mut terrain = Self {
+            width,
+            height,
+            elevation,
+            ramps: HashSet::new(),
+            edited: true,
+        };
+        terrain.ramps = data
+            .ramps
+            .iter()
+            .map(|[x, y]| IVec2::new(*x, *y))
+            .filter(|tile| terrain.in_bounds(*tile))
+            .collect();
+        Some(terrain)
+    }
+
+    /// Whether the ground has been painted over since it was generated
+    pub fn is_edited(&self) -> bool {
+        self.edited
+    }
+
+    /// The ground for a save to store, or None while it is still the generated map
+    pub fn to_data(&self) -> Option<TerrainData> {
+        if !self.edited {
+            return None;
+        }
+
+        let rows = (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| char::from(b'0' + self.elevation(IVec2::new(x, y))))
+                    .collect()
+            })
+            .collect();
+        let mut ramps: Vec<[i32; 2]> = self.ramps.iter().map(|tile| [tile.x, tile.y]).collect();
+        ramps.sort();
+
+        Some(TerrainData {
+            width: self.width,
+            height: self.height,
+            rows,
+            ramps,
+        })
+    }
+
+    /// Paints one tile; returns whether it changed. Levelling a tile takes away any ramp
+    /// on it, and the sea can't have one.
+    pub fn paint(&mut self, tile: IVec2, brush: TerrainBrush) -> bool {
+        if !self.in_bounds(tile) {
+            return false;
+        }
+
+        let changed = match brush.elevation() {
+            Some(level) => {
+                let index = (tile.y * self.width + tile.x) as usize;
+                let levelled = std::mem::replace(&mut self.elevation[index], level) != level;
+                self.ramps.remove(&tile) || levelled
+            }
+            None => !self.is_sea(tile) && self.ramps.insert(tile),
+        };
+        self.edited |= changed;
+        changed
+    }
+
+    fn in_bounds(&self, tile: IVec2) -> bool {
+        tile.x >= 0 && tile.y >= 0 && tile.x < self.width && tile.y < self.height
+    }
+
+    /// Off the map counts as sea level
+    pub fn elevation(&self, tile: IVec2) -> u8 {
+        if self.in_bounds(tile) {
+            self.elevation[(tile.y * self.width + tile.x) as usize]
+        } else {
+            SEA_LEVEL
+        }
+    }
+
+    pub fn is_sea(&self, tile: IVec2) -> bool {
+        self.in_bounds(tile) && self.elevation(tile) == SEA_LEVEL
+    }
+
+    /// Whether any of the map is sea, which a scenario can paint away
+    pub fn has_sea(&self) -> bool {
+        self.elevation.contains(&SEA_LEVEL)
+    }
+
+    pub fn is_ramp(&self, tile: IVec2) -> bool {
+        self.ramps.contains(&tile)
+    }
+
+    /// A tile at the top of a cliff: next to lower ground with no ramp down
+    pub fn is_cliff_edge(&self, tile: IVec2) -> bool {
+        let here = self.elevation(tile);
+        here > 1
+            && !self.is_ramp(tile)
+            && [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+                .iter()
+                .any(|offset| {
+                    let next = tile + *offset;
+                    self.in_bounds(next) && self.elevation(next) < here
+                })
+    }
+
+    /// Whether someone can walk from one tile onto its neighbour: level ground, or a
+    /// single step up or down over a ramp
+    pub fn can_step(&self, from: IVec2, to: IVec2) -> bool {
+        match self.elevation(from).abs_diff(self.elevation(to)) {
+            0 => true,
+            1 => self.is_ramp(from) || self.is_ramp(to),
+            _ => false,
+        }
+    }
+
+    /// What a window at `window` looking out along `outward` can see. Walls, buildings
+    /// and higher ground block the view; the sea is the best sight, lower ground past a
+    /// cliff the next best.
+    pub fn view_from(
+        &self,
+        window: IVec2,
+        outward: IVec2,
+        building_map: &BuildingMap,
+    ) -> Option<ScenicView> {
+        let eye = self.elevation(window);
+        let across = IVec2::new(outward.y, outward.x).as_vec2();
+
+        let mut best = None;
+        for spread in VIEW_SPREAD {
+            let direction = outward.as_vec2() + across * spread;
+            for step in 1..=VIEW_DISTANCE {
+                let tile = window + (direction * step as f32).round().as_ivec2();
+                // Fences and hedges are too low to block the view
+                let blocked =
+                    building_map.is_occupied(tile) && !building_map.boundaries.contains_key(&tile);
+                if !self.in_bounds(tile) || blocked {
+                    break;
+                }
+                let ground = self.elevation(tile);
+                if ground > eye {
+                    break;
+                }
+                if ground == SEA_LEVEL {
+                    return Some(ScenicView::Ocean);
+                }
+                if ground < eye {
+                    best = Some(ScenicView::Cliff);
+                }
+            }
+        }
+        best
+    }
+
+    fn color(&self, tile: IVec2) -> [u8; 4] {
+        let rgb = if self.is_sea(tile) {
+            // Shallows by the shore are lighter
+            let shallow = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+                .iter()
+                .any(|offset| self.elevation(tile + *offset * 2) > SEA_LEVEL);
+            if shallow {
+                [0.13, 0.27, 0.4]
+            } else {
+                [0.08, 0.18, 0.32]
+            }
+        } else if self.is_ramp(tile) {
+            [0.36, 0.32, 0.24]
+        } else if self.is_cliff_edge(tile) {
+            [0.3, 0.25, 0.2]
+        } else {
+            match self.elevation(tile) {
+                1 => [0.45, 0.41, 0.3],
+                2 => [0.16, 0.22, 0.14],
+                _ => [0.21, 0.27, 0.17],
+            }
+        };
+        let [r, g, b] = rgb.map(|channel: f32| (channel * 255.0) as u8);
+        [r, g, b, 255]
+    }
+
+    /// One pixel per tile, top row first
+    fn image(&self) -> Image {
+        let mut pixels = Vec::with_capacity((self.width * self.height * 4) as usize);
+        for y in (0..self.height).rev() {
+            for x in 0..self.width {
+                pixels.extend(self.color(IVec2::new(x, y)));
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: self.width as u32,
+                height: self.height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            pixels,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        // Keep tile edges crisp when stretched over the map
+        image.sampler = ImageSampler::nearest();
+        image
+    }
+}
+
+impl Default for Terrain {
+    fn default() -> Self {
+        let grid_settings = GridSettings::default();
+        Self::generate(grid_settings.width, grid_settings.height)
+    }
+}
+
+/// The ground drawn under the whole map
+#[derive(Component)]
+struct TerrainBackdrop;
+
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Terrain>()
+            .add_systems(Startup, spawn_terrain_backdrop)
+            .add_systems(
+                Update,
+                (redraw_terrain_backdrop, hide_backdrop_in_ascii_mode).in_set(GameplaySet),
+            );
+    }
+}
+
+fn spawn_terrain_backdrop(
+    mut commands: Commands,
+    terrain: Res<Terrain>,
+    grid_settings: Res<GridSettings>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let size = Vec2::new(grid_settings.width as f32, grid_settings.height as f32)
+        * grid_settings.tile_size;
+
+    commands.spawn((
+        Sprite {
+            image: images.add(terrain.image()),
+            custom_size: Some(size),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, TERRAIN_Z),
+        TerrainBackdrop,
+    ));
+}
+
+/// Painted or loaded ground is drawn afresh
+fn redraw_terrain_backdrop(
+    terrain: Res<Terrain>,
+    backdrop_query: Query<&Sprite, With<TerrainBackdrop>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !terrain.is_changed() || terrain.is_added() {
+        return;
+    }
+    for sprite in &backdrop_query {
+        images.insert(sprite.image.id(), terrain.image());
+    }
+}
+
+/// ASCII mode draws on a plain background, so the ground goes while it is on
+fn hide_backdrop_in_ascii_mode(
+    ascii_mode: Res<AsciiMode>,
+    mut backdrop_query: Query<&mut Visibility, With<TerrainBackdrop>>,
+) {
+    if !ascii_mode.is_changed() {
+        return;
+    }
+    for mut visibility in &mut backdrop_query {
+        *visibility = if ascii_mode.enabled {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
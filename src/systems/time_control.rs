@@ -1,50 +1,481 @@
+use crate::systems::app_state::GameplaySet;
 use bevy::prelude::*;
+use std::time::Duration;
 
 #[derive(Resource, Default, Clone, Copy, PartialEq)]
 pub struct TimeSpeed {
     pub multiplier: f32,
+    /// Speed to go back to when unpausing
+    pub resume_multiplier: f32,
 }
 
 impl TimeSpeed {
     pub fn normal() -> Self {
-        Self { multiplier: 1.0 }
+        Self {
+            multiplier: 1.0,
+            resume_multiplier: 1.0,
+        }
     }
 
     pub fn fast() -> Self {
-        Self { multiplier: 2.0 }
+        Self {
+            multiplier: 2.0,
+            resume_multiplier: 2.0,
+        }
     }
 
     pub fn very_fast() -> Self {
-        Self { multiplier: 3.0 }
+        Self {
+            multiplier: 3.0,
+            resume_multiplier: 3.0,
+        }
     }
 
     pub fn set_speed(&mut self, speed: SpeedOption) {
-        self.multiplier = match speed {
-            SpeedOption::Normal => 1.0,
-            SpeedOption::Fast => 2.0,
-            SpeedOption::VeryFast => 3.0,
+        self.multiplier = speed.multiplier();
+        if speed != SpeedOption::Paused {
+            self.resume_multiplier = self.multiplier;
+        }
+    }
+
+    pub fn is(&self, speed: SpeedOption) -> bool {
+        self.multiplier == speed.multiplier()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is(SpeedOption::Paused)
+    }
+
+    /// Pauses, or picks up again at the speed the game ran at before pausing
+    pub fn toggle_pause(&mut self) {
+        self.multiplier = if self.is_paused() {
+            self.resume_multiplier.max(SpeedOption::Normal.multiplier())
+        } else {
+            SpeedOption::Paused.multiplier()
         };
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpeedOption {
+    /// Simulation stops, but building, deconstruction orders and the camera still work;
+    /// new blueprints and jobs wait until the game is unpaused
+    Paused,
     Normal,
     Fast,
     VeryFast,
+    Ultra,
+    Max,
+}
+
+impl SpeedOption {
+    /// Speed steps offered to the player, slowest first
+    pub fn all() -> [SpeedOption; 6] {
+        [
+            SpeedOption::Paused,
+            SpeedOption::Normal,
+            SpeedOption::Fast,
+            SpeedOption::VeryFast,
+            SpeedOption::Ultra,
+            SpeedOption::Max,
+        ]
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            SpeedOption::Paused => 0.0,
+            SpeedOption::Normal => 1.0,
+            SpeedOption::Fast => 2.0,
+            SpeedOption::VeryFast => 3.0,
+            SpeedOption::Ultra => 5.0,
+            SpeedOption::Max => 10.0,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            SpeedOption::Paused => "||",
+            SpeedOption::Normal => "1x",
+            SpeedOption::Fast => "2x",
+            SpeedOption::VeryFast => "3x",
+            SpeedOption::Ultra => "5x",
+            SpeedOption::Max => "10x",
+        }
+    }
 }
 
 pub struct TimeControlPlugin;
 
 impl Plugin for TimeControlPlugin {
     fn build(&self, app: &mut App) {
+        // The clock, work progress, needs decay and marketing run on the fixed timestep: at
+        // high speeds Bevy runs several fixed ticks per frame instead of one huge delta, so
+        // fast-forward comes out the same however slow the machine. Update systems that
+        // act on the clock (check-ins, wages, plans) still see it once a frame.
         app.insert_resource(TimeSpeed::normal())
-            .add_systems(Update, apply_time_speed);
+            .init_resource::<GameClock>()
+            .add_systems(Update, apply_time_speed)
+            .add_systems(FixedUpdate, advance_game_clock.in_set(GameplaySet));
     }
 }
 
+// Pausing stops virtual time, so FixedUpdate simulation and anything reading `Time` in
+// Update stands still; input and camera systems read `Time<Real>` to keep working
 fn apply_time_speed(time_speed: Res<TimeSpeed>, mut time: ResMut<Time<Virtual>>) {
-    if time_speed.is_changed() {
+    if !time_speed.is_changed() {
+        return;
+    }
+
+    // Bevy drops whatever part of a frame runs past a quarter second, and at 10x every
+    // dropped second is ten of game time; count slow frames in full instead
+    time.set_max_delta(MAX_FRAME_TIME);
+
+    if time_speed.is_paused() {
+        time.pause();
+    } else {
+        time.unpause();
         time.set_relative_speed(time_speed.multiplier);
     }
 }
+
+pub const SECONDS_PER_GAME_HOUR: f32 = 10.0;
+/// Longest real frame that still counts in full toward game time; only true stalls,
+/// like dragging the window, lose time beyond it
+const MAX_FRAME_TIME: Duration = Duration::from_secs(1);
+pub const HOURS_PER_DAY: f32 = 24.0;
+pub const DAYS_PER_SEASON: u32 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// The season a calendar day falls in, the year running spring to winter
+    pub fn of_day(day: u32) -> Self {
+        match (day.saturating_sub(1) / DAYS_PER_SEASON) % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+
+    /// How strongly the season pulls guests in, independent of weather
+    pub fn demand_multiplier(&self) -> f32 {
+        match self {
+            Season::Spring => 1.0,
+            Season::Summer => 1.3,
+            Season::Autumn => 0.9,
+            Season::Winter => 0.6,
+        }
+    }
+}
+
+/// In-game calendar driven by virtual time, so it follows the speed controls
+#[derive(Resource)]
+pub struct GameClock {
+    pub elapsed_hours: f32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            elapsed_hours: 8.0, // Day 1 starts at 08:00
+        }
+    }
+}
+
+impl GameClock {
+    pub fn day(&self) -> u32 {
+        (self.elapsed_hours / HOURS_PER_DAY) as u32 + 1
+    }
+
+    pub fn hour(&self) -> f32 {
+        self.elapsed_hours % HOURS_PER_DAY
+    }
+
+    pub fn season(&self) -> Season {
+        Season::of_day(self.day())
+    }
+
+    pub fn display_time(&self) -> String {
+        let hour = self.hour();
+        let minutes = ((hour.fract()) * 60.0) as u32;
+        format!("{:02}:{:02}", hour as u32, minutes)
+    }
+}
+
+fn advance_game_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+    clock.elapsed_hours += time.delta_secs() / SECONDS_PER_GAME_HOUR;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+    use crate::systems::balance::Balance;
+    use crate::systems::building::deconstruction::{
+        handle_deconstruct_confirm_clicks, ConfirmDeconstructButton, DeconstructKind,
+        DeconstructTarget, PendingDeconstruction,
+    };
+    use crate::systems::building::factories::BuildingRules;
+    use crate::systems::building::paint::handle_paint_orders;
+    use crate::systems::building::placement::{
+        place_buildings, PlaceBuildingEvent, PlacementFailures, PlacementOrientation,
+    };
+    use crate::systems::building::{BuildingMap, DragState};
+    use crate::systems::controls::PointerAction;
+    use crate::systems::cursor::CursorWorldPosition;
+    use crate::systems::economy::Money;
+    use crate::systems::grid::{grid_to_world, GridSettings};
+    use crate::systems::guests::SatisfactionEvent;
+    use crate::systems::shared_assets::SharedAssets;
+    use crate::systems::terrain::Terrain;
+    use crate::systems::work::{
+        work_on_blueprints, work_on_deconstruction, ConstructionCompletedEvent,
+    };
+    use crate::ui::{BuildingType, OrderType, ToolbarState, UiFeedbackEvent, UiInputBlocker};
+    use bevy::time::TimeUpdateStrategy;
+
+    /// Just enough of the game to place a blueprint and build it, paused from the start
+    fn paused_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), TimeControlPlugin))
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            // Each update moves time on by a fixed step, so fixed ticks run when unpaused
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<SharedAssets>()
+            .init_resource::<GridSettings>()
+            .init_resource::<BuildingMap>()
+            .init_resource::<BuildingRules>()
+            .init_resource::<Terrain>()
+            .init_resource::<Balance>()
+            .init_resource::<Money>()
+            .init_resource::<PlacementFailures>()
+            .add_event::<PlaceBuildingEvent>()
+            .add_event::<UiFeedbackEvent>()
+            .add_event::<ConstructionCompletedEvent>()
+            .add_systems(Update, place_buildings)
+            .add_systems(FixedUpdate, work_on_blueprints);
+        app.world_mut()
+            .resource_mut::<TimeSpeed>()
+            .set_speed(SpeedOption::Paused);
+        app.update();
+        app
+    }
+
+    /// A dry tile to build on, wherever the generated coast is
+    fn dry_tile(app: &App) -> IVec2 {
+        let terrain = app.world().resource::<Terrain>();
+        let grid_settings = app.world().resource::<GridSettings>();
+        (0..grid_settings.height)
+            .map(|y| IVec2::new(grid_settings.width / 2, y))
+            .find(|tile| !terrain.is_sea(*tile))
+            .unwrap()
+    }
+
+    /// Where a tile's middle is in the world
+    fn tile_site(app: &App, tile: IVec2) -> Vec2 {
+        let grid_settings = app.world().resource::<GridSettings>();
+        grid_to_world(
+            tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        )
+    }
+
+    /// A worker already standing at `site` with `job` in hand
+    fn spawn_worker(app: &mut App, job: Entity, site: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                Pawn::default(),
+                Transform::from_translation(site.extend(0.0)),
+                CurrentJob { job_id: Some(job) },
+                StaffNeeds::default(),
+                StaffSkills::default(),
+            ))
+            .id()
+    }
+
+    fn place_floor(app: &mut App, tile: IVec2) {
+        app.world_mut().send_event(PlaceBuildingEvent {
+            building_type: BuildingType::Floor(FloorType::Wood),
+            position: tile,
+            orientation: PlacementOrientation {
+                door: DoorOrientation::Horizontal,
+                window: WindowOrientation::Horizontal,
+                furniture: FurnitureOrientation::default(),
+            },
+            plan: None,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn placing_while_paused_queues_the_blueprint_and_job() {
+        let mut app = paused_app();
+        let tile = dry_tile(&app);
+
+        place_floor(&mut app, tile);
+
+        let world = app.world_mut();
+        let blueprints: Vec<_> = world
+            .query::<(&GridPosition, &Blueprint)>()
+            .iter(world)
+            .map(|(pos, blueprint)| (pos.to_ivec2(), blueprint.work_done))
+            .collect();
+        assert_eq!(blueprints, vec![(tile, 0.0)]);
+        assert_eq!(world.query::<&ConstructionJob>().iter(world).count(), 1);
+    }
+
+    #[test]
+    fn queued_work_starts_once_unpaused() {
+        let mut app = paused_app();
+        let tile = dry_tile(&app);
+        place_floor(&mut app, tile);
+
+        let world = app.world_mut();
+        let job = world
+            .query_filtered::<Entity, With<ConstructionJob>>()
+            .single(world);
+        let site = tile_site(&app, tile);
+        let pawn = spawn_worker(&mut app, job, site);
+        app.world_mut()
+            .get_mut::<ConstructionJob>(job)
+            .unwrap()
+            .assigned_pawn = Some(pawn);
+
+        let work_done = |app: &mut App| {
+            let world = app.world_mut();
+            world.query::<&Blueprint>().single(world).work_done
+        };
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(work_done(&mut app), 0.0);
+
+        app.world_mut()
+            .resource_mut::<TimeSpeed>()
+            .set_speed(SpeedOption::Normal);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(work_done(&mut app) > 0.0);
+    }
+
+    #[test]
+    fn deconstruction_confirmed_while_paused_waits_for_unpause() {
+        let mut app = paused_app();
+        app.init_resource::<PendingDeconstruction>()
+            .add_event::<SatisfactionEvent>()
+            .add_systems(Update, handle_deconstruct_confirm_clicks)
+            .add_systems(FixedUpdate, work_on_deconstruction);
+        let tile = dry_tile(&app);
+        let site = tile_site(&app, tile);
+
+        // A wall picked out by a deconstruct drag, and the player confirming it
+        let world = app.world_mut();
+        let wall = world
+            .spawn((Wall::default(), GridPosition::new(tile.x, tile.y)))
+            .id();
+        world
+            .resource_mut::<PendingDeconstruction>()
+            .targets
+            .push(DeconstructTarget {
+                entity: wall,
+                kind: DeconstructKind::Wall,
+                grid_pos: tile,
+                world_pos: site,
+                guest_room: None,
+            });
+        world.spawn((ConfirmDeconstructButton, Interaction::Pressed));
+        app.update();
+
+        let world = app.world_mut();
+        let job = world
+            .query_filtered::<Entity, With<DeconstructionJob>>()
+            .single(world);
+        let pawn = spawn_worker(&mut app, job, site);
+        app.world_mut()
+            .get_mut::<DeconstructionJob>(job)
+            .unwrap()
+            .assigned_pawn = Some(pawn);
+
+        let work_done = |app: &mut App| {
+            let world = app.world_mut();
+            world
+                .query::<&DeconstructionMarker>()
+                .single(world)
+                .work_done
+        };
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(work_done(&mut app), 0.0);
+
+        app.world_mut()
+            .resource_mut::<TimeSpeed>()
+            .set_speed(SpeedOption::Normal);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(work_done(&mut app) > 0.0);
+    }
+
+    #[test]
+    fn painting_while_paused_recolours_at_once() {
+        let mut app = paused_app();
+        app.init_resource::<DragState>()
+            .init_resource::<ToolbarState>()
+            .init_resource::<CursorWorldPosition>()
+            .init_resource::<ButtonInput<PointerAction>>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Update, handle_paint_orders);
+        let tile = dry_tile(&app);
+
+        let world = app.world_mut();
+        let material = world.resource::<SharedAssets>().wall_material.clone();
+        let wall = world
+            .spawn((
+                Wall::default(),
+                GridPosition::new(tile.x, tile.y),
+                MeshMaterial2d(material),
+            ))
+            .id();
+        let mut toolbar = world.resource_mut::<ToolbarState>();
+        toolbar.selected_order = Some(OrderType::Paint);
+        toolbar.paint_color = PaintColor::Sage;
+        world.resource_mut::<CursorWorldPosition>().grid = Some(tile);
+
+        // A single-tile drag: pressed one frame, let go the next
+        let mut actions = world.resource_mut::<ButtonInput<PointerAction>>();
+        actions.press(PointerAction::Confirm);
+        app.update();
+        let mut actions = app.world_mut().resource_mut::<ButtonInput<PointerAction>>();
+        actions.clear();
+        actions.release(PointerAction::Confirm);
+        app.update();
+
+        let tint = app.world().get::<Wall>(wall).unwrap().tint;
+        assert_eq!(tint, Some(PaintColor::Sage));
+    }
+}
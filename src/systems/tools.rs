@@ -0,0 +1,292 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::game_rules::GameRules;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::items::spawn_carried_item;
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// How close a worker has to get to a rack to take or hang up a hammer
+const RACK_REACH: f32 = TILE_SIZE * 3.0;
+/// Workers set their materials down once this close to the building site
+const SITE_REACH: f32 = TILE_SIZE * 3.0;
+
+pub struct ToolsPlugin;
+
+impl Plugin for ToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                pick_up_hammers,
+                deliver_materials,
+                return_idle_hammers,
+                hang_up_hammers,
+                warn_missing_tool_rack,
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Everything but a tool rack takes a hammer to build. Racks go up by hand, so a resort
+/// without one can still build its first.
+pub fn needs_hammer(blueprint: &Blueprint) -> bool {
+    !matches!(
+        blueprint.building_type,
+        BlueprintType::Furniture(furniture_type) if furniture_type.definition().has_tag("tools")
+    )
+}
+
+/// Hammers left on each rack, less those carried off or promised to a worker on the way.
+/// `claims` holds the rack of every hammer taken or being fetched.
+pub fn free_hammers(
+    racks: impl Iterator<Item = Entity>,
+    claims: impl Iterator<Item = Entity>,
+) -> HashMap<Entity, usize> {
+    let mut free: HashMap<Entity, usize> = racks.map(|rack| (rack, ToolRack::HAMMERS)).collect();
+    for rack in claims {
+        if let Some(count) = free.get_mut(&rack) {
+            *count = count.saturating_sub(1);
+        }
+    }
+    free
+}
+
+/// Workers reaching the rack take their hammer, and the materials for the job, and head
+/// on to the building site, or the spot there they claimed
+fn pick_up_hammers(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &FetchingHammer,
+            Option<&WorkSpot>,
+        ),
+        With<Pawn>,
+    >,
+    rack_query: Query<&Transform, With<ToolRack>>,
+    mut job_query: Query<&mut ConstructionJob>,
+    blueprint_query: Query<&GridPosition, With<Blueprint>>,
+    grid_settings: Res<GridSettings>,
+) {
+    for (pawn_entity, transform, mut current_job, fetching, work_spot) in &mut pawn_query {
+        // The job was cancelled on the way, which frees the hammer for someone else
+        let Some(job_id) = current_job
+            .job_id
+            .filter(|job_id| job_query.contains(*job_id))
+        else {
+            commands.entity(pawn_entity).remove::<FetchingHammer>();
+            continue;
+        };
+
+        // The rack was taken down; put the job back on the board
+        let Ok(rack_transform) = rack_query.get(fetching.rack) else {
+            if let Ok(mut job) = job_query.get_mut(job_id) {
+                job.assigned_pawn = None;
+            }
+            current_job.job_id = None;
+            commands
+                .entity(pawn_entity)
+                .remove::<(FetchingHammer, MovementTarget, PawnPath)>();
+            continue;
+        };
+
+        let distance = transform
+            .translation
+            .truncate()
+            .distance(rack_transform.translation.truncate());
+        if distance >= RACK_REACH {
+            continue;
+        }
+
+        spawn_carried_item(
+            &mut commands,
+            Item::new(ItemKind::Materials),
+            pawn_entity,
+            transform.translation.truncate(),
+        );
+        let mut pawn_commands = commands.entity(pawn_entity);
+        pawn_commands.remove::<FetchingHammer>().insert(HeldHammer {
+            rack: fetching.rack,
+        });
+
+        let site = job_query
+            .get(job_id)
+            .ok()
+            .and_then(|job| blueprint_query.get(job.blueprint).ok());
+        if let Some(spot) = work_spot.filter(|spot| spot.job == job_id) {
+            pawn_commands.insert(MovementTarget {
+                target: spot.position,
+            });
+        } else if let Some(blueprint_grid_pos) = site {
+            let target = grid_to_world(
+                blueprint_grid_pos.to_ivec2(),
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            );
+            pawn_commands.insert(MovementTarget { target });
+        }
+    }
+}
+
+/// Materials are used up once the worker reaches the site they were fetched for. If
+/// that job was cancelled on the way they go to whatever the worker builds next.
+fn deliver_materials(
+    mut commands: Commands,
+    item_query: Query<(Entity, &Item, &Carried)>,
+    pawn_query: Query<(&Transform, &CurrentJob), With<Pawn>>,
+    job_query: Query<&ConstructionJob>,
+    blueprint_query: Query<&Transform, With<Blueprint>>,
+) {
+    for (entity, item, carried) in &item_query {
+        if item.kind != ItemKind::Materials {
+            continue;
+        }
+        let Ok((transform, current_job)) = pawn_query.get(carried.by) else {
+            continue;
+        };
+        let site = current_job
+            .job_id
+            .and_then(|job_id| job_query.get(job_id).ok())
+            .and_then(|job| blueprint_query.get(job.blueprint).ok());
+        let Some(site_transform) = site else {
+            continue;
+        };
+
+        let distance = transform
+            .translation
+            .truncate()
+            .distance(site_transform.translation.truncate());
+        if distance < SITE_REACH {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Once nothing is waiting to be built, idle workers walk their hammers back to the rack
+fn return_idle_hammers(
+    mut commands: Commands,
+    pawn_query: Query<
+        (Entity, &CurrentJob, &HeldHammer),
+        (
+            With<Pawn>,
+            Without<ReturningHammer>,
+            Without<OnBreak>,
+            Without<InTraining>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+        ),
+    >,
+    job_query: Query<&ConstructionJob>,
+    rack_query: Query<&Transform, With<ToolRack>>,
+) {
+    if job_query.iter().any(|job| job.assigned_pawn.is_none()) {
+        return;
+    }
+
+    for (pawn_entity, current_job, held) in &pawn_query {
+        if current_job.job_id.is_some() {
+            continue;
+        }
+
+        match rack_query.get(held.rack) {
+            Ok(rack_transform) => {
+                commands.entity(pawn_entity).insert((
+                    MovementTarget {
+                        target: rack_transform.translation.truncate(),
+                    },
+                    ReturningHammer,
+                ));
+            }
+            // Nowhere to hang it any more
+            Err(_) => {
+                commands.entity(pawn_entity).remove::<HeldHammer>();
+            }
+        }
+    }
+}
+
+/// Workers back at the rack hang their hammer up. Anyone given other work on the way
+/// keeps theirs for now.
+fn hang_up_hammers(
+    mut commands: Commands,
+    pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &CurrentJob,
+            &HeldHammer,
+            Has<OnBreak>,
+            Has<StaffingReception>,
+            Has<StaffingSpa>,
+        ),
+        (With<Pawn>, With<ReturningHammer>),
+    >,
+    rack_query: Query<&Transform, With<ToolRack>>,
+    item_query: Query<(Entity, &Item, &Carried)>,
+) {
+    for (pawn_entity, transform, current_job, held, on_break, at_desk, at_spa) in &pawn_query {
+        if current_job.job_id.is_some() || on_break || at_desk || at_spa {
+            commands.entity(pawn_entity).remove::<ReturningHammer>();
+            continue;
+        }
+
+        let Ok(rack_transform) = rack_query.get(held.rack) else {
+            commands
+                .entity(pawn_entity)
+                .remove::<(HeldHammer, ReturningHammer)>();
+            continue;
+        };
+
+        let distance = transform
+            .translation
+            .truncate()
+            .distance(rack_transform.translation.truncate());
+        if distance < RACK_REACH {
+            commands
+                .entity(pawn_entity)
+                .remove::<(HeldHammer, ReturningHammer, MovementTarget)>();
+            // Unused materials go back on the shelf too
+            for (item_entity, item, carried) in &item_query {
+                if carried.by == pawn_entity && item.kind == ItemKind::Materials {
+                    commands.entity(item_entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+/// Tells the player why construction has stalled while there is no rack to take hammers
+/// from, once until a rack is built
+fn warn_missing_tool_rack(
+    rules: Res<GameRules>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    rack_query: Query<(), With<ToolRack>>,
+    job_query: Query<&ConstructionJob>,
+    blueprint_query: Query<&Blueprint>,
+    mut warned: Local<bool>,
+) {
+    if !rack_query.is_empty() {
+        *warned = false;
+        return;
+    }
+    if *warned || rules.instant_construction() {
+        return;
+    }
+
+    let stalled = job_query.iter().any(|job| {
+        job.assigned_pawn.is_none() && blueprint_query.get(job.blueprint).is_ok_and(needs_hammer)
+    });
+    if stalled {
+        notifications.send(Notification {
+            message: localization.t("tools.no_rack").to_string(),
+        });
+        *warned = true;
+    }
+}
@@ -0,0 +1,102 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::staff::start_staff_breaks;
+use crate::systems::time_control::GameClock;
+use crate::ui::{Localization, Notification};
+use bevy::prelude::*;
+
+/// Game hours a course keeps a worker off duty
+pub const TRAINING_HOURS: f32 = 24.0;
+/// Fee for a course taking a skill from level 1 to 2; every level after costs this much
+/// more again
+const TRAINING_COST_PER_LEVEL: i32 = 300;
+
+/// What a course raising a skill from `level` to the next costs
+pub fn training_cost(level: u8) -> i32 {
+    TRAINING_COST_PER_LEVEL * level as i32
+}
+
+pub struct TrainingPlugin;
+
+impl Plugin for TrainingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                // Workers leaving for a course this frame shouldn't be handed a job or
+                // sent on a break too
+                start_training_courses.before(start_staff_breaks),
+                finish_training_courses,
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Enrolled workers leave for their course once they've finished their current job,
+/// giving up any desk or table they were on
+fn start_training_courses(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    pawn_query: Query<(Entity, &CurrentJob, &TrainingEnrolment), Without<InTraining>>,
+) {
+    for (pawn_entity, current_job, enrolment) in &pawn_query {
+        if current_job.job_id.is_some() {
+            continue;
+        }
+
+        commands
+            .entity(pawn_entity)
+            .remove::<(
+                TrainingEnrolment,
+                OnBreak,
+                StaffingReception,
+                StaffingSpa,
+                MovementTarget,
+                PawnPath,
+            )>()
+            .insert((
+                InTraining {
+                    skill: enrolment.skill,
+                    ends_hours: clock.elapsed_hours + TRAINING_HOURS,
+                },
+                Visibility::Hidden,
+            ));
+    }
+}
+
+/// Workers come back from their course a level better at the skill it taught
+fn finish_training_courses(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut pawn_query: Query<(Entity, &Pawn, &InTraining, &mut StaffSkills)>,
+) {
+    for (pawn_entity, pawn, training, mut skills) in &mut pawn_query {
+        if clock.elapsed_hours < training.ends_hours {
+            continue;
+        }
+
+        skills.raise(training.skill);
+        notifications.send(Notification {
+            message: localization.format(
+                "staff.trained",
+                &[
+                    ("name", pawn.name.clone()),
+                    (
+                        "skill",
+                        localization
+                            .term("work_type", training.skill.name())
+                            .to_string(),
+                    ),
+                    ("level", skills.level(training.skill).to_string()),
+                ],
+            ),
+        });
+        commands
+            .entity(pawn_entity)
+            .remove::<InTraining>()
+            .insert(Visibility::Inherited);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::guests::{random_open_tile, spawn_guest, zone_center};
+use crate::systems::pathfinding::is_walkable;
+use crate::systems::terrain::Terrain;
+use crate::systems::time_control::{GameClock, HOURS_PER_DAY};
+use bevy::prelude::*;
+
+/// Shuttles pull in at these hours, dropping off new arrivals and picking up guests
+/// heading home. Check-in and check-out at 11:00 make the noon shuttle the busiest.
+pub const SHUTTLE_HOURS: [f32; 4] = [8.0, 12.0, 16.0, 20.0];
+/// Departing guests this close to the stop make the shuttle; the rest wait for the next
+const BOARDING_DISTANCE: f32 = TILE_SIZE * 3.0;
+
+/// Where guests step off the shuttle: the walkable tile beside each shuttle stop that
+/// faces into the resort
+#[derive(Resource, Default)]
+pub struct ArrivalPoints {
+    pub curbs: Vec<Vec2>,
+}
+
+impl ArrivalPoints {
+    pub fn is_empty(&self) -> bool {
+        self.curbs.is_empty()
+    }
+
+    pub fn nearest(&self, from: Vec2) -> Option<Vec2> {
+        self.curbs.iter().copied().min_by(|a, b| {
+            a.distance_squared(from)
+                .total_cmp(&b.distance_squared(from))
+        })
+    }
+}
+
+/// Hour of the next shuttle after `hour`, wrapping round to tomorrow's first
+pub fn next_shuttle_hour(hour: f32) -> f32 {
+    SHUTTLE_HOURS
+        .iter()
+        .copied()
+        .find(|&shuttle| shuttle > hour)
+        .unwrap_or(SHUTTLE_HOURS[0])
+}
+
+/// Whether a shuttle was due in the game time between `from` and `to`
+fn shuttle_due(from: f32, to: f32) -> bool {
+    if to <= from {
+        return false;
+    }
+
+    // Today's and tomorrow's timetable cover any one frame
+    let day_start = (from / HOURS_PER_DAY).floor() * HOURS_PER_DAY;
+    [0.0, HOURS_PER_DAY]
+        .into_iter()
+        .flat_map(|offset| SHUTTLE_HOURS.map(|hour| day_start + offset + hour))
+        .any(|time| time > from && time <= to)
+}
+
+pub struct TransportPlugin;
+
+impl Plugin for TransportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArrivalPoints>().add_systems(
+            Update,
+            (update_arrival_points, run_shuttles)
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+fn update_arrival_points(
+    mut arrival_points: ResMut<ArrivalPoints>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    terrain: Res<Terrain>,
+    stop_query: Query<(&GridPosition, &FurnitureType, &FurnitureOrientation), With<ArrivalPoint>>,
+    added_stops: Query<(), Added<ArrivalPoint>>,
+    mut removed_stops: RemovedComponents<ArrivalPoint>,
+) {
+    let removed = removed_stops.read().count() > 0;
+    if added_stops.is_empty() && !removed && !building_map.is_changed() && !terrain.is_changed() {
+        return;
+    }
+
+    let map_center = IVec2::new(grid_settings.width / 2, grid_settings.height / 2);
+    arrival_points.curbs = stop_query
+        .iter()
+        .filter_map(|(grid_pos, furniture_type, orientation)| {
+            let tiles = furniture_type.tiles_occupied(grid_pos.to_ivec2(), *orientation);
+            tiles
+                .iter()
+                .flat_map(|tile| {
+                    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|d| *tile + d)
+                })
+                .filter(|tile| {
+                    !tiles.contains(tile)
+                        && is_walkable(*tile, &building_map, &grid_settings, &terrain)
+                })
+                .min_by_key(|tile| (*tile - map_center).length_squared())
+                .map(|tile| {
+                    grid_to_world(
+                        tile,
+                        grid_settings.tile_size,
+                        grid_settings.width,
+                        grid_settings.height,
+                    )
+                })
+        })
+        .collect();
+}
+
+/// Each scheduled shuttle drops off every party booked since the last one and takes
+/// away the departing guests waiting at the stop
+fn run_shuttles(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    arrival_points: Res<ArrivalPoints>,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    awaiting_query: Query<(Entity, &Zone, &Booking), With<AwaitingShuttle>>,
+    departing_query: Query<(Entity, &Transform), (With<Guest>, With<Departing>)>,
+    console_query: Query<(&Transform, &ReceptionConsole)>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let now = clock.elapsed_hours;
+    let previous = last_hours.replace(now).unwrap_or(now);
+    if !shuttle_due(previous, now) || arrival_points.is_empty() {
+        return;
+    }
+
+    let mut departed = 0;
+    for (entity, transform) in &departing_query {
+        let position = transform.translation.truncate();
+        let at_stop = arrival_points
+            .nearest(position)
+            .is_some_and(|stop| stop.distance(position) <= BOARDING_DISTANCE);
+        if at_stop {
+            commands.entity(entity).despawn_recursive();
+            departed += 1;
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut arrived = 0;
+    for (room, zone, booking) in &awaiting_query {
+        let Some(tile) = random_open_tile(zone, &building_map, &mut rng) else {
+            continue;
+        };
+        let Some(stop) = arrival_points.nearest(zone_center(zone, &grid_settings)) else {
+            continue;
+        };
+        let room_target = grid_to_world(
+            tile,
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+        // Everyone checks in on their way up, at the open desk nearest the stop if any
+        // desk is open
+        let reception = console_query
+            .iter()
+            .map(|(transform, console)| (transform.translation.truncate(), console.open))
+            .min_by(|a, b| {
+                b.1.cmp(&a.1)
+                    .then(a.0.distance(stop).total_cmp(&b.0.distance(stop)))
+            })
+            .map(|(desk, _)| desk);
+
+        let guest = spawn_guest(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            room,
+            booking.party,
+            stop,
+            reception.unwrap_or(room_target),
+        );
+        commands
+            .entity(guest)
+            .insert(WaitingGuest::new(WaitReason::CheckIn));
+        commands.entity(room).remove::<AwaitingShuttle>();
+        arrived += 1;
+    }
+
+    if arrived > 0 || departed > 0 {
+        info!(
+            "Shuttle dropped off {} parties and picked up {} guests",
+            arrived, departed
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::*;
+use crate::systems::time_control::{GameClock, Season};
+use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const MIN_WEATHER_HOURS: f32 = 6.0;
+const MAX_WEATHER_HOURS: f32 = 18.0;
+const STORM_DAMAGE_CHANCE_PER_HOUR: f64 = 0.25;
+const RAIN_STREAK_COUNT: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weather {
+    Sunny,
+    Rain,
+    Heatwave,
+    Storm,
+}
+
+impl Weather {
+    pub fn name(&self) -> &str {
+        match self {
+            Weather::Sunny => "Sunny",
+            Weather::Rain => "Rain",
+            Weather::Heatwave => "Heatwave",
+            Weather::Storm => "Storm",
+        }
+    }
+
+    pub fn demand_multiplier(&self) -> f32 {
+        match self {
+            Weather::Sunny => 1.1,
+            Weather::Rain => 0.8,
+            Weather::Heatwave => 1.0,
+            Weather::Storm => 0.5,
+        }
+    }
+
+    pub fn pool_usage_multiplier(&self) -> f32 {
+        match self {
+            Weather::Sunny => 1.2,
+            Weather::Rain => 0.3,
+            Weather::Heatwave => 1.6,
+            Weather::Storm => 0.0,
+        }
+    }
+
+    /// Screen tint drawn over the world while this weather is active
+    pub fn overlay_color(&self) -> Color {
+        match self {
+            Weather::Sunny => Color::NONE,
+            Weather::Rain => Color::srgba(0.2, 0.3, 0.45, 0.15),
+            Weather::Heatwave => Color::srgba(1.0, 0.6, 0.2, 0.12),
+            Weather::Storm => Color::srgba(0.05, 0.05, 0.15, 0.3),
+        }
+    }
+
+    /// Relative odds of each weather type in a season (Sunny, Rain, Heatwave, Storm)
+    fn seasonal_weights(season: Season) -> [u32; 4] {
+        match season {
+            Season::Spring => [50, 35, 5, 10],
+            Season::Summer => [50, 15, 30, 5],
+            Season::Autumn => [40, 40, 0, 20],
+            Season::Winter => [40, 40, 0, 20],
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct WeatherState {
+    pub current: Weather,
+    pub hours_remaining: f32,
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        Self {
+            current: Weather::Sunny,
+            hours_remaining: 12.0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct WeatherOverlay;
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WeatherState>()
+            .add_systems(Startup, setup_weather_overlay)
+            .add_systems(
+                Update,
+                (
+                    update_weather,
+                    update_weather_overlay,
+                    draw_rain,
+                    damage_outdoor_structures_in_storm,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_weather_overlay(mut commands: Commands) {
+    // Full-screen tint kept underneath the rest of the UI
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        GlobalZIndex(-1),
+        WeatherOverlay,
+    ));
+}
+
+fn update_weather(
+    clock: Res<GameClock>,
+    mut weather: ResMut<WeatherState>,
+    mut last_hours: Local<Option<f32>>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    if elapsed <= 0.0 {
+        return;
+    }
+
+    weather.hours_remaining -= elapsed;
+    if weather.hours_remaining > 0.0 {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let options = [
+        Weather::Sunny,
+        Weather::Rain,
+        Weather::Heatwave,
+        Weather::Storm,
+    ];
+    let weights = Weather::seasonal_weights(clock.season());
+    let Ok(distribution) = WeightedIndex::new(weights) else {
+        return;
+    };
+
+    let next = options[distribution.sample(&mut rng)];
+    if next != weather.current {
+        info!(
+            "Weather changed to {} ({})",
+            next.name(),
+            clock.season().name()
+        );
+    }
+    weather.current = next;
+    weather.hours_remaining = rng.gen_range(MIN_WEATHER_HOURS..MAX_WEATHER_HOURS);
+}
+
+fn update_weather_overlay(
+    weather: Res<WeatherState>,
+    time: Res<Time>,
+    mut overlay_query: Query<&mut BackgroundColor, With<WeatherOverlay>>,
+) {
+    let Ok(mut background) = overlay_query.get_single_mut() else {
+        return;
+    };
+
+    let mut color = weather.current.overlay_color();
+
+    // Occasional lightning flash during storms
+    if weather.current == Weather::Storm && (time.elapsed_secs() % 7.0) < 0.08 {
+        color = Color::srgba(1.0, 1.0, 1.0, 0.35);
+    }
+
+    if background.0 != color {
+        background.0 = color;
+    }
+}
+
+// Rain streaks are drawn with gizmos around the camera so they cost nothing when it's dry
+fn draw_rain(
+    weather: Res<WeatherState>,
+    time: Res<Time>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera>>,
+    mut gizmos: Gizmos,
+) {
+    if !matches!(weather.current, Weather::Rain | Weather::Storm) {
+        return;
+    }
+
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let view = projection.area;
+    let center = camera_transform.translation.truncate();
+    let fall_speed = if weather.current == Weather::Storm {
+        900.0
+    } else {
+        600.0
+    };
+    let slant = if weather.current == Weather::Storm {
+        -0.4
+    } else {
+        -0.15
+    };
+    let streak_length = 12.0 * projection.scale;
+
+    for i in 0..RAIN_STREAK_COUNT {
+        // Cheap hash so each streak keeps a stable column and phase
+        let seed = (i as f32 * 12.9898).sin() * 43758.547;
+        let column = seed.fract().abs();
+        let phase = (seed * 1.618).fract().abs();
+
+        let x = view.min.x + column * view.width();
+        let fall = (phase * view.height() + time.elapsed_secs() * fall_speed * projection.scale)
+            % view.height();
+        let y = view.max.y - fall;
+
+        let start = center + Vec2::new(x, y);
+        let end = start + Vec2::new(slant, -1.0) * streak_length;
+        gizmos.line_2d(start, end, Color::srgba(0.7, 0.8, 1.0, 0.35));
+    }
+}
+
+/// Storms occasionally break outdoor furniture and exterior windows, creating repair jobs
+fn damage_outdoor_structures_in_storm(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    weather: Res<WeatherState>,
+    mut storm_hours: Local<f32>,
+    mut last_hours: Local<Option<f32>>,
+    room_query: Query<&Room>,
+    furniture_query: Query<(Entity, &GridPosition), (With<Furniture>, Without<Damaged>)>,
+    window_query: Query<
+        (Entity, &GridPosition),
+        (With<crate::components::Window>, Without<Damaged>),
+    >,
+    building_map: Res<BuildingMap>,
+    grid_settings: Res<GridSettings>,
+) {
+    let elapsed = clock.elapsed_hours - last_hours.unwrap_or(clock.elapsed_hours);
+    *last_hours = Some(clock.elapsed_hours);
+
+    if weather.current != Weather::Storm {
+        *storm_hours = 0.0;
+        return;
+    }
+
+    *storm_hours += elapsed;
+    if *storm_hours < 1.0 {
+        return;
+    }
+    *storm_hours -= 1.0;
+
+    let mut rng = rand::thread_rng();
+    if !rng.gen_bool(STORM_DAMAGE_CHANCE_PER_HOUR) {
+        return;
+    }
+
+    let is_indoors = |pos: IVec2| room_query.iter().any(|room| room.contains_tile(pos));
+
+    let mut candidates: Vec<Entity> = furniture_query
+        .iter()
+        .filter(|(_, pos)| !is_indoors(pos.to_ivec2()))
+        .map(|(entity, _)| entity)
+        .collect();
+
+    // Windows are exposed when any open neighbour lies outside every room
+    for (entity, pos) in &window_query {
+        let tile = pos.to_ivec2();
+        let exposed = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .iter()
+            .map(|offset| tile + *offset)
+            .filter(|neighbor| {
+                neighbor.x >= 0
+                    && neighbor.y >= 0
+                    && neighbor.x < grid_settings.width
+                    && neighbor.y < grid_settings.height
+            })
+            .any(|neighbor| !building_map.is_occupied(neighbor) && !is_indoors(neighbor));
+
+        if exposed {
+            candidates.push(entity);
+        }
+    }
+
+    if let Some(&target) = candidates.choose(&mut rng) {
+        commands.entity(target).insert(Damaged::default());
+        commands.spawn(RepairJob::new(target));
+        info!("Storm damaged a structure; repair job queued");
+    }
+}
@@ -1,92 +1,591 @@
 use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::balance::Balance;
 use crate::systems::building::BuildingMap;
+use crate::systems::economy::Purchases;
+use crate::systems::game_rules::GameRules;
 use crate::systems::grid::*;
+use crate::systems::job_priorities::JobPriorities;
+use crate::systems::reception::reception_post;
+use crate::systems::shared_assets::SharedAssets;
+use crate::systems::staff::{start_staff_breaks, JobFinishedEvent};
+use crate::systems::tools::{free_hammers, needs_hammer};
+use crate::systems::work_spots::{release_work_spots, WorkSpotFinder, WORK_SPOT_ARRIVAL};
+use crate::ui::{Localization, Notification};
 use bevy::prelude::*;
 use bevy::sprite::*;
+use std::collections::{HashMap, HashSet, VecDeque};
 
+/// How often blueprints are checked for a way in while nothing is being built or torn down
+const ACCESS_CHECK_SECONDS: f32 = 1.0;
+
+/// Something new is standing: a blueprint was finished, or furniture was set down. The
+/// flash and sparkle are drawn from this, and anything else that cares can listen too.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ConstructionCompletedEvent {
+    pub entity: Entity,
+    pub building_type: BlueprintType,
+    pub grid_pos: IVec2,
+    pub world_pos: Vec2,
+}
 
 pub struct WorkPlugin;
 
 impl Plugin for WorkPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
+        app.add_event::<ConstructionCompletedEvent>()
+            .add_systems(
+                Update,
                 (
-                    assign_jobs_to_pawns,
-                    assign_deconstruction_jobs_to_pawns,
-                    assign_reception_staff,
+                    (
+                        cancel_orphaned_jobs,
+                        release_work_spots,
+                        batch_construction_jobs,
+                        update_job_batches,
+                        block_unreachable_blueprints,
+                        assign_jobs_to_pawns,
+                        assign_deconstruction_jobs_to_pawns,
+                        assign_repair_jobs_to_pawns,
+                        assign_reception_staff,
+                        assign_spa_therapists,
+                    )
+                        .chain()
+                        // Pawns leaving for a break this frame shouldn't be handed a job too
+                        .after(start_staff_breaks),
+                    (update_blueprint_visuals, update_deconstruction_visuals, draw_damage_markers),
+                    (complete_blueprints, complete_deconstruction, complete_repairs).chain(),
+                    handle_door_interactions,
                 )
-                    .chain(),
-                (work_on_blueprints, work_on_deconstruction).chain(),
-                (update_blueprint_visuals, update_deconstruction_visuals),
-                (complete_blueprints, complete_deconstruction).chain(),
-                handle_door_interactions,
-            ),
-        );
+                    .in_set(GameplaySet),
+            )
+            // Work progress is simulation, so it advances in fixed ticks like the game clock
+            .add_systems(
+                FixedUpdate,
+                (
+                    work_on_blueprints,
+                    finish_blueprints_instantly,
+                    work_on_deconstruction,
+                    work_on_repairs,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+// Jobs can lose their blueprint/marker (or their pawn) without finishing, e.g. when a
+// save is loaded mid-work. Drop those jobs and free the pawns so nobody stays stuck.
+fn cancel_orphaned_jobs(
+    mut commands: Commands,
+    mut pawn_query: Query<(Entity, &mut CurrentJob), With<Pawn>>,
+    mut construction_query: Query<(Entity, &mut ConstructionJob)>,
+    mut deconstruction_query: Query<(Entity, &mut DeconstructionJob)>,
+    mut repair_query: Query<&mut RepairJob>,
+    mut cleaning_query: Query<&mut CleaningJob>,
+    job_exists_query: Query<
+        (),
+        Or<(
+            With<ConstructionJob>,
+            With<DeconstructionJob>,
+            With<RepairJob>,
+            With<CleaningJob>,
+            With<Incident>,
+            With<RoomServiceOrder>,
+            With<Infestation>,
+        )>,
+    >,
+    blueprint_query: Query<(), With<Blueprint>>,
+    marker_query: Query<(Entity, &DeconstructionMarker)>,
+) {
+    let mut cancelled = Vec::new();
+
+    for (job_entity, mut job) in &mut construction_query {
+        if blueprint_query.get(job.blueprint).is_err() {
+            cancelled.push(job_entity);
+            commands.entity(job_entity).despawn();
+        } else if job
+            .assigned_pawn
+            .is_some_and(|pawn| pawn_query.get(pawn).is_err())
+        {
+            job.assigned_pawn = None;
+        }
+    }
+
+    for (job_entity, mut job) in &mut deconstruction_query {
+        if marker_query.get(job.marker).is_err() {
+            cancelled.push(job_entity);
+            commands.entity(job_entity).despawn();
+        } else if job
+            .assigned_pawn
+            .is_some_and(|pawn| pawn_query.get(pawn).is_err())
+        {
+            job.assigned_pawn = None;
+        }
+    }
+
+    // complete_repairs already drops repairs whose target is gone; only the pawn can vanish here
+    for mut job in &mut repair_query {
+        if job
+            .assigned_pawn
+            .is_some_and(|pawn| pawn_query.get(pawn).is_err())
+        {
+            job.assigned_pawn = None;
+        }
+    }
+
+    // Likewise for cleaning, whose jobs are closed when their room goes
+    for mut job in &mut cleaning_query {
+        if job
+            .assigned_pawn
+            .is_some_and(|pawn| pawn_query.get(pawn).is_err())
+        {
+            job.assigned_pawn = None;
+        }
+    }
+
+    // A marker whose target is already gone has nothing left to remove
+    for (marker_entity, marker) in &marker_query {
+        if commands.get_entity(marker.target_entity).is_none() {
+            commands.entity(marker_entity).despawn();
+        }
+    }
+
+    for (pawn_entity, mut current_job) in &mut pawn_query {
+        let Some(job_id) = current_job.job_id else {
+            continue;
+        };
+
+        if cancelled.contains(&job_id) || job_exists_query.get(job_id).is_err() {
+            current_job.job_id = None;
+            commands
+                .entity(pawn_entity)
+                .remove::<(MovementTarget, PawnPath)>();
+        }
+    }
+}
+
+/// Groups newly placed blueprints into batches: each run of touching blueprints of the
+/// same kind becomes one job list, in an order a worker can walk from end to end
+fn batch_construction_jobs(
+    mut commands: Commands,
+    new_jobs: Query<(), Added<ConstructionJob>>,
+    job_query: Query<(Entity, &ConstructionJob)>,
+    blueprint_query: Query<(&GridPosition, &Blueprint)>,
+    batch_query: Query<&JobBatch>,
+) {
+    if new_jobs.is_empty() {
+        return;
+    }
+
+    let batched: HashSet<Entity> = batch_query
+        .iter()
+        .flat_map(|batch| batch.jobs.iter().copied())
+        .collect();
+
+    // Unclaimed jobs not yet in a batch, split up by what they build
+    let mut kinds: Vec<(BlueprintType, HashMap<IVec2, Entity>)> = Vec::new();
+    for (job_entity, job) in &job_query {
+        if job.assigned_pawn.is_some() || batched.contains(&job_entity) {
+            continue;
+        }
+        let Ok((grid_pos, blueprint)) = blueprint_query.get(job.blueprint) else {
+            continue;
+        };
+        let index = match kinds
+            .iter()
+            .position(|(kind, _)| *kind == blueprint.building_type)
+        {
+            Some(index) => index,
+            None => {
+                kinds.push((blueprint.building_type, HashMap::new()));
+                kinds.len() - 1
+            }
+        };
+        kinds[index].1.insert(grid_pos.to_ivec2(), job_entity);
+    }
+
+    for (_, jobs) in kinds {
+        let mut left: HashSet<IVec2> = jobs.keys().copied().collect();
+        let mut starts: Vec<IVec2> = left.iter().copied().collect();
+        starts.sort_by_key(|tile| (tile.x, tile.y));
+
+        for start in starts {
+            if !left.remove(&start) {
+                continue;
+            }
+            let mut run = HashSet::from([start]);
+            let mut queue = VecDeque::from([start]);
+            while let Some(tile) = queue.pop_front() {
+                for offset in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                    if left.remove(&(tile + offset)) {
+                        run.insert(tile + offset);
+                        queue.push_back(tile + offset);
+                    }
+                }
+            }
+
+            // A lone blueprint is just an ordinary job
+            if run.len() > 1 {
+                commands.spawn(JobBatch {
+                    jobs: walk_order(run).iter().map(|tile| jobs[tile]).collect(),
+                    worker: None,
+                });
+            }
+        }
     }
 }
 
-// Assign construction jobs to idle pawns
+/// Orders a run of tiles so each is next to the one before where possible, starting from
+/// an end: along a wall that is one end to the other, across a floor it snakes back and forth
+fn walk_order(mut tiles: HashSet<IVec2>) -> Vec<IVec2> {
+    let touching = |tiles: &HashSet<IVec2>, tile: IVec2| {
+        [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .into_iter()
+            .filter(|offset| tiles.contains(&(tile + *offset)))
+            .count()
+    };
+    let Some(mut current) = tiles
+        .iter()
+        .copied()
+        .min_by_key(|tile| (touching(&tiles, *tile), tile.x, tile.y))
+    else {
+        return Vec::new();
+    };
+
+    let mut order = Vec::with_capacity(tiles.len());
+    while tiles.remove(&current) {
+        order.push(current);
+        let from = current;
+        match tiles
+            .iter()
+            .copied()
+            .min_by_key(|tile| ((*tile - from).abs().element_sum(), tile.x, tile.y))
+        {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    order
+}
+
+/// Drops finished or cancelled jobs from their batch, and frees a batch whose worker is
+/// gone, on a break, off construction or busy with something else
+fn update_job_batches(
+    mut commands: Commands,
+    mut batch_query: Query<(Entity, &mut JobBatch)>,
+    job_query: Query<(), With<ConstructionJob>>,
+    pawn_query: Query<(&CurrentJob, &WorkAssignments, Has<OnBreak>), With<Pawn>>,
+) {
+    for (batch_entity, mut batch) in &mut batch_query {
+        batch.jobs.retain(|job| job_query.contains(*job));
+        if batch.jobs.is_empty() {
+            commands.entity(batch_entity).despawn();
+            continue;
+        }
+
+        let Some(worker) = batch.worker else {
+            continue;
+        };
+        // Between two jobs of the run the worker has no job for a frame, which is fine
+        let still_working = pawn_query
+            .get(worker)
+            .is_ok_and(|(current_job, work_assignments, on_break)| {
+                !on_break
+                    && work_assignments.can_do_work(WorkType::Construction)
+                    && current_job
+                        .job_id
+                        .is_none_or(|job_id| batch.jobs.contains(&job_id))
+            });
+        if !still_working {
+            batch.worker = None;
+        }
+    }
+}
+
+/// Blueprints with nowhere a worker can walk to within reach are blocked, and wait there
+/// until a door or a gap opens a way in. Whoever was on their way to one drops it. Checked
+/// whenever the map changes or blueprints go down, and every so often as pawns move about.
+fn block_unreachable_blueprints(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pawn_query: Query<(Entity, &GridPosition, &mut CurrentJob), With<Pawn>>,
+    mut job_query: Query<(Entity, &mut ConstructionJob)>,
+    new_jobs: Query<(), Added<ConstructionJob>>,
+    mut blueprint_query: Query<(&GridPosition, &mut Blueprint)>,
+    work_spots: WorkSpotFinder,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+    mut since_check: Local<f32>,
+) {
+    *since_check += time.delta_secs();
+    let due = *since_check >= ACCESS_CHECK_SECONDS;
+    if !due && !work_spots.map_changed() && new_jobs.is_empty() {
+        return;
+    }
+    *since_check = 0.0;
+
+    // With nobody hired there is nobody to reach anything
+    if pawn_query.is_empty() {
+        return;
+    }
+    let reachable = work_spots.reachable_from(pawn_query.iter().map(|(_, pos, _)| pos.to_ivec2()));
+
+    let mut newly_blocked = 0;
+    for (job_entity, mut job) in &mut job_query {
+        let Ok((grid_pos, mut blueprint)) = blueprint_query.get_mut(job.blueprint) else {
+            continue;
+        };
+        let blocked = !work_spots.can_reach(grid_pos.to_ivec2(), &reachable);
+        if blocked == blueprint.blocked {
+            continue;
+        }
+        blueprint.blocked = blocked;
+        if !blocked {
+            continue;
+        }
+        newly_blocked += 1;
+
+        if let Some(pawn) = job.assigned_pawn.take() {
+            if let Ok((_, _, mut current_job)) = pawn_query.get_mut(pawn) {
+                if current_job.job_id == Some(job_entity) {
+                    current_job.job_id = None;
+                    commands
+                        .entity(pawn)
+                        .remove::<(MovementTarget, PawnPath, StuckWatch, WorkSpot)>();
+                }
+            }
+        }
+    }
+
+    if newly_blocked > 0 {
+        notifications.send(Notification {
+            message: localization.format("work.blocked", &[("count", newly_blocked.to_string())]),
+        });
+    }
+}
+
+// Assign construction jobs to idle pawns. Workers without a hammer are sent to fetch one
+// from the nearest rack that has one left. A worker who starts on a batch keeps to it, and
+// idle workers with nothing else to do take over the far half of someone else's batch.
 fn assign_jobs_to_pawns(
     mut commands: Commands,
-    mut pawn_query: Query<(Entity, &Transform, &mut CurrentJob, &WorkAssignments), With<Pawn>>,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &WorkAssignments,
+            Has<HeldHammer>,
+            Option<&UnreachableJob>,
+        ),
+        (With<Pawn>, Without<OnBreak>, Without<InTraining>),
+    >,
     mut job_query: Query<(Entity, &mut ConstructionJob)>,
-    blueprint_query: Query<&GridPosition, With<Blueprint>>,
+    blueprint_query: Query<(&GridPosition, &Blueprint)>,
+    mut batch_query: Query<(Entity, &mut JobBatch)>,
+    rack_query: Query<(Entity, &Transform), With<ToolRack>>,
+    claim_query: Query<AnyOf<(&HeldHammer, &FetchingHammer)>>,
     grid_settings: Res<GridSettings>,
+    priorities: Res<JobPriorities>,
+    work_spots: WorkSpotFinder,
 ) {
+    let mut claims = work_spots.claims();
+    let mut free = free_hammers(
+        rack_query.iter().map(|(rack, _)| rack),
+        claim_query.iter().filter_map(|(held, fetching)| {
+            held.map(|held| held.rack)
+                .or(fetching.map(|fetching| fetching.rack))
+        }),
+    );
+
+    let mut batch_of: HashMap<Entity, Entity> = HashMap::new();
+    for (batch_entity, batch) in &batch_query {
+        for job in &batch.jobs {
+            batch_of.insert(*job, batch_entity);
+        }
+    }
+
     // Find idle pawns
-    for (pawn_entity, pawn_transform, mut current_job, work_assignments) in &mut pawn_query {
+    for (
+        pawn_entity,
+        pawn_transform,
+        mut current_job,
+        work_assignments,
+        has_hammer,
+        unreachable,
+    ) in &mut pawn_query
+    {
         if current_job.job_id.is_some() {
             continue; // Pawn already has a job
         }
 
-        // Check if pawn can do construction work
-        if !work_assignments.can_do_work(WorkType::Construction) {
+        // Check if pawn can do construction work, and has nothing more pressing waiting
+        if !work_assignments.can_do_work(WorkType::Construction)
+            || priorities.passes_over(work_assignments, WorkType::Construction)
+        {
             continue;
         }
 
-        // Find the nearest unassigned job
-        let mut nearest_job: Option<(Entity, f32)> = None;
         let pawn_pos = pawn_transform.translation.truncate();
+        let site = |job_entity: Entity| {
+            let (_, job) = job_query.get(job_entity).ok()?;
+            let (blueprint_grid_pos, blueprint) = blueprint_query.get(job.blueprint).ok()?;
+            let position = grid_to_world(
+                blueprint_grid_pos.to_ivec2(),
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            );
+            let available = job.assigned_pawn.is_none() && !blueprint.blocked;
+            Some((position, needs_hammer(blueprint), available))
+        };
 
-        for (job_entity, job) in &job_query {
-            if job.assigned_pawn.is_some() {
-                continue; // Job already assigned
+        let nearest_rack = if has_hammer {
+            None
+        } else {
+            rack_query
+                .iter()
+                .filter(|(rack, _)| free.get(rack).is_some_and(|count| *count > 0))
+                .map(|(rack, transform)| (rack, transform.translation.truncate()))
+                .min_by(|(_, a), (_, b)| a.distance(pawn_pos).total_cmp(&b.distance(pawn_pos)))
+        };
+        // Unassigned, not walled off, and buildable by this pawn; without a hammer to be
+        // had, only tool racks can be built. A job the pawn just couldn't reach is left to
+        // the others.
+        let open = |job_entity: Entity| {
+            if unreachable.is_some_and(|unreachable| unreachable.job == job_entity) {
+                return None;
             }
+            site(job_entity).filter(|(_, hammer, available)| {
+                *available && (has_hammer || nearest_rack.is_some() || !hammer)
+            })
+        };
 
-            if let Ok(blueprint_grid_pos) = blueprint_query.get(job.blueprint) {
-                let blueprint_world_pos = grid_to_world(
-                    blueprint_grid_pos.to_ivec2(),
-                    grid_settings.tile_size,
-                    grid_settings.width,
-                    grid_settings.height,
-                );
-                let distance = pawn_pos.distance(blueprint_world_pos);
+        // Carry on down this pawn's own batch
+        let mut chosen = batch_query
+            .iter()
+            .find(|(_, batch)| batch.worker == Some(pawn_entity))
+            .and_then(|(_, batch)| batch.jobs.iter().copied().find(|job| open(*job).is_some()));
 
-                if nearest_job.is_none() || distance < nearest_job.unwrap().1 {
-                    nearest_job = Some((job_entity, distance));
+        // Otherwise the nearest loose job, or either end of a batch nobody has taken
+        if chosen.is_none() {
+            let mut nearest_job: Option<(Entity, f32)> = None;
+            for (job_entity, _) in &job_query {
+                let available = match batch_of.get(&job_entity) {
+                    None => true,
+                    // Batches split off this frame can't be looked up yet, but are taken
+                    Some(batch_entity) => {
+                        batch_query.get(*batch_entity).is_ok_and(|(_, batch)| {
+                            batch.worker.is_none()
+                                && (batch.jobs.first() == Some(&job_entity)
+                                    || batch.jobs.last() == Some(&job_entity))
+                        })
+                    }
+                };
+                if !available {
+                    continue;
+                }
+                if let Some((position, _, _)) = open(job_entity) {
+                    let distance = pawn_pos.distance(position);
+                    if nearest_job.is_none_or(|(_, nearest)| distance < nearest) {
+                        nearest_job = Some((job_entity, distance));
+                    }
+                }
+            }
+            chosen = nearest_job.map(|(job_entity, _)| job_entity);
+
+            // Take the batch on, working from the end the pawn started at
+            if let Some(batch_entity) = chosen.and_then(|job| batch_of.get(&job)) {
+                if let Ok((_, mut batch)) = batch_query.get_mut(*batch_entity) {
+                    batch.worker = Some(pawn_entity);
+                    if batch.jobs.first() != chosen.as_ref() {
+                        batch.jobs.reverse();
+                    }
                 }
             }
         }
 
-        // Assign the nearest job
-        if let Some((job_entity, _)) = nearest_job {
+        // Nothing free: share out the longest batch still being worked
+        if chosen.is_none() {
+            let longest = batch_query
+                .iter_mut()
+                .filter(|(_, batch)| batch.worker.is_some_and(|worker| worker != pawn_entity))
+                .map(|(_, batch)| {
+                    let waiting = batch.jobs.iter().filter(|job| open(**job).is_some()).count();
+                    (batch, waiting)
+                })
+                .filter(|(_, waiting)| *waiting > 1)
+                .max_by_key(|(_, waiting)| *waiting);
+
+            if let Some((mut batch, waiting)) = longest {
+                // The far end is furthest from the current worker
+                let split_at = batch.jobs.len() - waiting / 2;
+                let mut jobs = batch.jobs.split_off(split_at);
+                let distance_to = |job: Option<&Entity>| {
+                    job.and_then(|job| site(*job))
+                        .map_or(f32::MAX, |(position, _, _)| pawn_pos.distance(position))
+                };
+                if distance_to(jobs.last()) < distance_to(jobs.first()) {
+                    jobs.reverse();
+                }
+
+                chosen = jobs.first().copied();
+                let new_batch = commands.spawn_empty().id();
+                for job in &jobs {
+                    batch_of.insert(*job, new_batch);
+                }
+                commands.entity(new_batch).insert(JobBatch {
+                    jobs,
+                    worker: Some(pawn_entity),
+                });
+            }
+        }
+
+        // Assign the chosen job
+        if let Some(job_entity) = chosen {
             if let Ok((_, mut job)) = job_query.get_mut(job_entity) {
                 job.assigned_pawn = Some(pawn_entity);
                 current_job.job_id = Some(job_entity);
 
-                // Add movement target to the blueprint location
-                if let Ok(blueprint_grid_pos) = blueprint_query.get(job.blueprint) {
-                    let target_pos = grid_to_world(
+                if let Ok((blueprint_grid_pos, blueprint)) = blueprint_query.get(job.blueprint) {
+                    let spot = work_spots.claim(
+                        &mut claims,
+                        pawn_entity,
+                        pawn_pos,
+                        job_entity,
                         blueprint_grid_pos.to_ivec2(),
-                        grid_settings.tile_size,
-                        grid_settings.width,
-                        grid_settings.height,
                     );
-                    commands
-                        .entity(pawn_entity)
-                        .insert(MovementTarget { target: target_pos });
+                    let mut pawn_commands = commands.entity(pawn_entity);
+                    pawn_commands.remove::<ReturningHammer>();
+                    if let Some(spot) = spot {
+                        pawn_commands.insert(spot);
+                    }
+
+                    let fetch_from = nearest_rack.filter(|_| needs_hammer(blueprint));
+                    if let Some((rack, rack_pos)) = fetch_from {
+                        // Head to the rack first; the hammer is set aside for this pawn
+                        if let Some(count) = free.get_mut(&rack) {
+                            *count -= 1;
+                        }
+                        pawn_commands.insert((
+                            FetchingHammer { rack },
+                            MovementTarget { target: rack_pos },
+                        ));
+                    } else {
+                        // Head for the work spot, or the blueprint itself without one
+                        let target_pos = spot.map_or_else(
+                            || {
+                                grid_to_world(
+                                    blueprint_grid_pos.to_ivec2(),
+                                    grid_settings.tile_size,
+                                    grid_settings.width,
+                                    grid_settings.height,
+                                )
+                            },
+                            |spot| spot.position,
+                        );
+                        pawn_commands.insert(MovementTarget { target: target_pos });
+                    }
                 }
             }
         }
@@ -97,15 +596,22 @@ fn assign_jobs_to_pawns(
 fn assign_reception_staff(
     mut commands: Commands,
     mut pawn_query: Query<
-        (Entity, &Transform, &CurrentJob, &WorkAssignments),
-        (With<Pawn>, Without<StaffingReception>),
+        (Entity, &CurrentJob, &WorkAssignments),
+        (
+            With<Pawn>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+            Without<OnBreak>,
+            Without<InTraining>,
+        ),
     >,
-    console_query: Query<(Entity, &GridPosition), With<ReceptionConsole>>,
+    console_query: Query<(Entity, &GridPosition, &ReceptionConsole)>,
     staffed_query: Query<&StaffingReception>,
     grid_settings: Res<GridSettings>,
+    priorities: Res<JobPriorities>,
 ) {
     // Find unstaffed reception desks
-    for (console_entity, console_pos) in &console_query {
+    for (console_entity, console_pos, console) in &console_query {
         // Check if this desk is already staffed
         let is_staffed = staffed_query
             .iter()
@@ -116,28 +622,23 @@ fn assign_reception_staff(
         }
 
         // Find idle pawn with reception work enabled
-        for (pawn_entity, pawn_transform, current_job, work_assignments) in &pawn_query {
+        for (pawn_entity, current_job, work_assignments) in &pawn_query {
             // Pawn must be idle and able to do reception work
             if current_job.job_id.is_some() {
                 continue;
             }
 
-            if !work_assignments.can_do_work(WorkType::Reception) {
+            if !work_assignments.can_do_work(WorkType::Reception)
+                || priorities.passes_over(work_assignments, WorkType::Reception)
+            {
                 continue;
             }
 
-            // Assign this pawn to staff the desk
-            let desk_world_pos = grid_to_world(
-                console_pos.to_ivec2(),
-                grid_settings.tile_size,
-                grid_settings.width,
-                grid_settings.height,
-            );
+            // Assign this pawn to staff the desk, from behind it
+            let post = reception_post(console, console_pos.to_ivec2(), &grid_settings);
 
             commands.entity(pawn_entity).insert((
-                MovementTarget {
-                    target: desk_world_pos,
-                },
+                MovementTarget { target: post },
                 StaffingReception {
                     desk_entity: console_entity,
                 },
@@ -149,32 +650,112 @@ fn assign_reception_staff(
     }
 }
 
+// Assign therapists to spa massage tables, one per table like reception desks
+fn assign_spa_therapists(
+    mut commands: Commands,
+    pawn_query: Query<
+        (Entity, &CurrentJob, &WorkAssignments),
+        (
+            With<Pawn>,
+            Without<StaffingReception>,
+            Without<StaffingSpa>,
+            Without<OnBreak>,
+            Without<InTraining>,
+        ),
+    >,
+    table_query: Query<(Entity, &GridPosition), With<MassageTable>>,
+    staffed_query: Query<&StaffingSpa>,
+    grid_settings: Res<GridSettings>,
+    priorities: Res<JobPriorities>,
+) {
+    let mut assigned = Vec::new();
+
+    for (table_entity, table_pos) in &table_query {
+        let is_staffed = staffed_query
+            .iter()
+            .any(|staffing| staffing.table_entity == table_entity);
+        if is_staffed {
+            continue;
+        }
+
+        // Idle pawn with spa work enabled who wasn't just sent to another table
+        let Some((pawn_entity, _, _)) =
+            pawn_query
+                .iter()
+                .find(|(pawn_entity, current_job, work_assignments)| {
+                    current_job.job_id.is_none()
+                        && work_assignments.can_do_work(WorkType::SpaTherapist)
+                        && !priorities.passes_over(work_assignments, WorkType::SpaTherapist)
+                        && !assigned.contains(pawn_entity)
+                })
+        else {
+            continue;
+        };
+
+        let table_world_pos = grid_to_world(
+            table_pos.to_ivec2(),
+            grid_settings.tile_size,
+            grid_settings.width,
+            grid_settings.height,
+        );
+
+        commands.entity(pawn_entity).insert((
+            MovementTarget {
+                target: table_world_pos,
+            },
+            StaffingSpa { table_entity },
+        ));
+        assigned.push(pawn_entity);
+    }
+}
+
+/// Whether a worker is in place to work on something at `site`: stood on their work spot
+/// if they have one, otherwise anywhere within 3 tiles of it
+fn in_place(pawn_pos: Vec2, site: Vec2, work_spot: Option<&WorkSpot>) -> bool {
+    match work_spot {
+        Some(spot) => pawn_pos.distance(spot.position) <= WORK_SPOT_ARRIVAL,
+        None => pawn_pos.distance(site) < TILE_SIZE * 3.0,
+    }
+}
+
 // Pawns work on blueprints when nearby
-fn work_on_blueprints(
+pub fn work_on_blueprints(
     mut commands: Commands,
-    mut pawn_query: Query<(Entity, &Transform, &CurrentJob), With<Pawn>>,
+    // Workers still on their way to a rack don't start until they have a hammer
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &CurrentJob,
+            &StaffNeeds,
+            &StaffSkills,
+            Option<&WorkSpot>,
+        ),
+        (With<Pawn>, Without<FetchingHammer>),
+    >,
     mut job_query: Query<&ConstructionJob>,
     mut blueprint_query: Query<(&Transform, &mut Blueprint)>,
+    balance: Res<Balance>,
     time: Res<Time>,
 ) {
-    for (pawn_entity, pawn_transform, current_job) in &mut pawn_query {
+    for (pawn_entity, pawn_transform, current_job, needs, skills, work_spot) in &mut pawn_query {
         if let Some(job_id) = current_job.job_id {
             if let Ok(job) = job_query.get_mut(job_id) {
                 if let Ok((blueprint_transform, mut blueprint)) =
                     blueprint_query.get_mut(job.blueprint)
                 {
-                    let distance = pawn_transform
-                        .translation
-                        .truncate()
-                        .distance(blueprint_transform.translation.truncate());
-
-                    // Check if pawn is close enough to work (within 2 tiles)
-                    if distance < TILE_SIZE * 3.0 {
+                    if in_place(
+                        pawn_transform.translation.truncate(),
+                        blueprint_transform.translation.truncate(),
+                        work_spot.filter(|spot| spot.job == job_id),
+                    ) {
                         // Remove movement target if present
                         commands.entity(pawn_entity).remove::<MovementTarget>();
 
                         // Do work
-                        let work_speed = 50.0; // work units per second (faster building)
+                        let work_speed = balance.work.construction
+                            * needs.work_speed()
+                            * skills.speed(WorkType::Construction);
                         blueprint.work_done += work_speed * time.delta_secs();
                         blueprint.work_done = blueprint.work_done.min(blueprint.work_required);
                     }
@@ -184,27 +765,50 @@ fn work_on_blueprints(
     }
 }
 
-// Complete blueprints and turn them into actual buildings
+// Sandbox games skip the wait: blueprints are finished the moment they're placed
+fn finish_blueprints_instantly(
+    rules: Res<GameRules>,
+    mut blueprint_query: Query<&mut Blueprint>,
+) {
+    if !rules.instant_construction() {
+        return;
+    }
+
+    for mut blueprint in &mut blueprint_query {
+        blueprint.work_done = blueprint.work_required;
+    }
+}
+
+// Complete blueprints and turn them into actual buildings, paying for them from escrow
 fn complete_blueprints(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut purchases: Purchases,
+    shared_assets: Res<SharedAssets>,
     blueprint_query: Query<(Entity, &Blueprint, &GridPosition, &Transform)>,
     job_query: Query<(Entity, &ConstructionJob)>,
     mut pawn_query: Query<&mut CurrentJob, With<Pawn>>,
     grid_settings: Res<GridSettings>,
     mut building_map: ResMut<BuildingMap>,
+    mut completed: EventWriter<ConstructionCompletedEvent>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
 ) {
     for (blueprint_entity, blueprint, grid_pos, transform) in &blueprint_query {
         if blueprint.is_complete() {
             // Find and remove the associated job
             for (job_entity, job) in &job_query {
                 if job.blueprint == blueprint_entity {
+                    purchases.settle(job.escrow, transform.translation.truncate());
+
                     // Clear pawn's current job
                     if let Some(pawn_entity) = job.assigned_pawn {
                         if let Ok(mut current_job) = pawn_query.get_mut(pawn_entity) {
                             current_job.job_id = None;
                         }
+                        finished_jobs.send(JobFinishedEvent {
+                            pawn: pawn_entity,
+                            job: job_entity,
+                            kind: JobKind::Construction,
+                        });
                     }
                     commands.entity(job_entity).despawn();
                 }
@@ -213,21 +817,18 @@ fn complete_blueprints(
             // Remove blueprint and spawn actual building (including any child visuals)
             commands.entity(blueprint_entity).despawn_recursive();
 
-            match blueprint.building_type {
+            let building_entity = match blueprint.building_type {
                 BlueprintType::Wall => {
                     let wall_entity = commands
                         .spawn((
-                            Mesh2d(meshes.add(Rectangle::new(
-                                grid_settings.tile_size,
-                                grid_settings.tile_size,
-                            ))),
-                            MeshMaterial2d(materials.add(WallMaterial::Stone.color())),
+                            Mesh2d(shared_assets.wall_mesh(WallConnections::default())),
+                            MeshMaterial2d(shared_assets.wall_material.clone()),
                             Transform::from_xyz(
                                 transform.translation.x,
                                 transform.translation.y,
                                 2.0,
                             ),
-                            Wall,
+                            Wall::default(),
                             Building,
                             GridPosition::new(grid_pos.x, grid_pos.y),
                         ))
@@ -235,19 +836,12 @@ fn complete_blueprints(
 
                     // Update building map to track the completed wall entity
                     building_map.walls.insert(grid_pos.to_ivec2(), wall_entity);
+                    wall_entity
                 }
                 BlueprintType::Door(orientation) => {
-                    let (width, height, offset) = match orientation {
-                        DoorOrientation::Horizontal => (
-                            grid_settings.tile_size * 2.0,
-                            grid_settings.tile_size * DOOR_THICKNESS,
-                            Vec2::new(grid_settings.tile_size / 2.0, 0.0),
-                        ),
-                        DoorOrientation::Vertical => (
-                            grid_settings.tile_size * DOOR_THICKNESS,
-                            grid_settings.tile_size * 2.0,
-                            Vec2::new(0.0, grid_settings.tile_size / 2.0),
-                        ),
+                    let offset = match orientation {
+                        DoorOrientation::Horizontal => Vec2::new(grid_settings.tile_size / 2.0, 0.0),
+                        DoorOrientation::Vertical => Vec2::new(0.0, grid_settings.tile_size / 2.0),
                     };
 
                     let world_pos = grid_to_world(
@@ -257,50 +851,76 @@ fn complete_blueprints(
                         grid_settings.height,
                     ) + offset;
 
-                    commands.spawn((
-                        Mesh2d(meshes.add(Rectangle::new(width, height))),
-                        MeshMaterial2d(materials.add(Color::srgb(0.4, 0.3, 0.2))),
-                        Transform::from_xyz(world_pos.x, world_pos.y, 2.0),
-                        Door::new(orientation),
-                        Building,
-                        GridPosition::new(grid_pos.x, grid_pos.y),
-                    ));
+                    commands
+                        .spawn((
+                            Mesh2d(shared_assets.door_mesh(orientation)),
+                            MeshMaterial2d(shared_assets.door_material.clone()),
+                            Transform::from_xyz(world_pos.x, world_pos.y, 2.0),
+                            Door::new(orientation),
+                            DoorMode::default(),
+                            Building,
+                            GridPosition::new(grid_pos.x, grid_pos.y),
+                        ))
+                        .id()
                 }
-                BlueprintType::Window => {
-                    commands.spawn((
-                        Mesh2d(meshes.add(Rectangle::new(
-                            grid_settings.tile_size,
-                            grid_settings.tile_size * WINDOW_THICKNESS,
-                        ))),
-                        MeshMaterial2d(materials.add(Color::srgb(0.6, 0.8, 1.0))),
+                BlueprintType::Window(orientation) => commands
+                    .spawn((
+                        Mesh2d(shared_assets.window_mesh(orientation)),
+                        MeshMaterial2d(shared_assets.window_material.clone()),
                         Transform::from_xyz(transform.translation.x, transform.translation.y, 2.0),
-                        Window,
+                        crate::components::Window { orientation },
                         Building,
                         GridPosition::new(grid_pos.x, grid_pos.y),
-                    ));
-                }
-                BlueprintType::Floor(floor_type) => {
-                    commands.spawn((
-                        Mesh2d(meshes.add(Rectangle::new(
-                            grid_settings.tile_size,
-                            grid_settings.tile_size,
-                        ))),
-                        MeshMaterial2d(materials.add(floor_type.color())),
+                    ))
+                    .id(),
+                BlueprintType::Floor(floor_type) => commands
+                    .spawn((
+                        Mesh2d(shared_assets.tile_mesh.clone()),
+                        MeshMaterial2d(shared_assets.floor_material(floor_type)),
                         Transform::from_xyz(
                             transform.translation.x,
                             transform.translation.y,
                             0.5, // Floors render below everything else
                         ),
-                        Floor { floor_type },
+                        Floor::new(floor_type),
                         GridPosition::new(grid_pos.x, grid_pos.y),
-                    ));
+                    ))
+                    .id(),
+                BlueprintType::Boundary(boundary_type) => {
+                    let boundary_entity = commands
+                        .spawn((
+                            Mesh2d(shared_assets.boundary_mesh.clone()),
+                            MeshMaterial2d(shared_assets.boundary_material(boundary_type)),
+                            Transform::from_xyz(
+                                transform.translation.x,
+                                transform.translation.y,
+                                2.0,
+                            ),
+                            Boundary { boundary_type },
+                            Building,
+                            GridPosition::new(grid_pos.x, grid_pos.y),
+                        ))
+                        .id();
+
+                    building_map
+                        .boundaries
+                        .insert(grid_pos.to_ivec2(), boundary_entity);
+                    boundary_entity
                 }
                 BlueprintType::Furniture(_furniture_type) => {
                     // Furniture is spawned directly without blueprints, so this case shouldn't occur
                     // But we need it for pattern matching completeness
                     warn!("Furniture blueprint completed unexpectedly - furniture should spawn directly");
+                    continue;
                 }
-            }
+            };
+
+            completed.send(ConstructionCompletedEvent {
+                entity: building_entity,
+                building_type: blueprint.building_type,
+                grid_pos: grid_pos.to_ivec2(),
+                world_pos: transform.translation.truncate(),
+            });
         }
     }
 }
@@ -320,7 +940,9 @@ fn update_blueprint_visuals(
                 _ => 0.4 + (progress * 0.4),  // More visible for structures
             };
 
-            material.color = Color::srgba(1.0, 1.0, 1.0, alpha);
+            // Blocked ones are greyed out until a worker can get to them
+            let shade = if blueprint.blocked { 0.45 } else { 1.0 };
+            material.color = Color::srgba(shade, shade, shade, alpha);
         }
     }
 }
@@ -328,19 +950,36 @@ fn update_blueprint_visuals(
 // Assign deconstruction jobs to idle pawns
 fn assign_deconstruction_jobs_to_pawns(
     mut commands: Commands,
-    mut pawn_query: Query<(Entity, &Transform, &mut CurrentJob, &WorkAssignments), With<Pawn>>,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &WorkAssignments,
+            Option<&UnreachableJob>,
+        ),
+        (With<Pawn>, Without<OnBreak>, Without<InTraining>),
+    >,
     mut job_query: Query<(Entity, &mut DeconstructionJob)>,
     marker_query: Query<&GridPosition, With<DeconstructionMarker>>,
     grid_settings: Res<GridSettings>,
+    priorities: Res<JobPriorities>,
+    work_spots: WorkSpotFinder,
 ) {
+    let mut claims = work_spots.claims();
+
     // Find idle pawns
-    for (pawn_entity, pawn_transform, mut current_job, work_assignments) in &mut pawn_query {
+    for (pawn_entity, pawn_transform, mut current_job, work_assignments, unreachable) in
+        &mut pawn_query
+    {
         if current_job.job_id.is_some() {
             continue; // Pawn already has a job
         }
 
         // Check if pawn can do construction work (deconstruction uses the same skill)
-        if !work_assignments.can_do_work(WorkType::Construction) {
+        if !work_assignments.can_do_work(WorkType::Construction)
+            || priorities.passes_over(work_assignments, WorkType::Construction)
+        {
             continue;
         }
 
@@ -352,6 +991,9 @@ fn assign_deconstruction_jobs_to_pawns(
             if job.assigned_pawn.is_some() {
                 continue; // Job already assigned
             }
+            if unreachable.is_some_and(|unreachable| unreachable.job == job_entity) {
+                continue; // Couldn't get there last time
+            }
 
             if let Ok(marker_grid_pos) = marker_query.get(job.marker) {
                 let marker_world_pos = grid_to_world(
@@ -374,14 +1016,26 @@ fn assign_deconstruction_jobs_to_pawns(
                 job.assigned_pawn = Some(pawn_entity);
                 current_job.job_id = Some(job_entity);
 
-                // Add movement target to the marker location
+                // Add movement target to a work spot by the marker, or the marker itself
                 if let Ok(marker_grid_pos) = marker_query.get(job.marker) {
-                    let target_pos = grid_to_world(
+                    let target_pos = match work_spots.claim(
+                        &mut claims,
+                        pawn_entity,
+                        pawn_pos,
+                        job_entity,
                         marker_grid_pos.to_ivec2(),
-                        grid_settings.tile_size,
-                        grid_settings.width,
-                        grid_settings.height,
-                    );
+                    ) {
+                        Some(spot) => {
+                            commands.entity(pawn_entity).insert(spot);
+                            spot.position
+                        }
+                        None => grid_to_world(
+                            marker_grid_pos.to_ivec2(),
+                            grid_settings.tile_size,
+                            grid_settings.width,
+                            grid_settings.height,
+                        ),
+                    };
                     commands
                         .entity(pawn_entity)
                         .insert(MovementTarget { target: target_pos });
@@ -392,29 +1046,40 @@ fn assign_deconstruction_jobs_to_pawns(
 }
 
 // Pawns work on deconstruction when nearby
-fn work_on_deconstruction(
+pub fn work_on_deconstruction(
     mut commands: Commands,
-    mut pawn_query: Query<(Entity, &Transform, &CurrentJob), With<Pawn>>,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &CurrentJob,
+            &StaffNeeds,
+            &StaffSkills,
+            Option<&WorkSpot>,
+        ),
+        With<Pawn>,
+    >,
     mut job_query: Query<&DeconstructionJob>,
     mut marker_query: Query<(&Transform, &mut DeconstructionMarker)>,
+    balance: Res<Balance>,
     time: Res<Time>,
 ) {
-    for (pawn_entity, pawn_transform, current_job) in &mut pawn_query {
+    for (pawn_entity, pawn_transform, current_job, needs, skills, work_spot) in &mut pawn_query {
         if let Some(job_id) = current_job.job_id {
             if let Ok(job) = job_query.get_mut(job_id) {
                 if let Ok((marker_transform, mut marker)) = marker_query.get_mut(job.marker) {
-                    let distance = pawn_transform
-                        .translation
-                        .truncate()
-                        .distance(marker_transform.translation.truncate());
-
-                    // Check if pawn is close enough to work (within 3 tiles)
-                    if distance < TILE_SIZE * 3.0 {
+                    if in_place(
+                        pawn_transform.translation.truncate(),
+                        marker_transform.translation.truncate(),
+                        work_spot.filter(|spot| spot.job == job_id),
+                    ) {
                         // Remove movement target if present
                         commands.entity(pawn_entity).remove::<MovementTarget>();
 
                         // Do work
-                        let work_speed = 40.0; // Deconstruction is faster than construction
+                        let work_speed = balance.work.deconstruction
+                            * needs.work_speed()
+                            * skills.speed(WorkType::Construction);
                         marker.work_done += work_speed * time.delta_secs();
                         marker.work_done = marker.work_done.min(marker.work_required);
                     }
@@ -452,6 +1117,9 @@ fn complete_deconstruction(
     wall_query: Query<&GridPosition, With<Wall>>,
     door_query: Query<&Door>,
     furniture_query: Query<(), With<Furniture>>,
+    floor_query: Query<(), With<Floor>>,
+    boundary_query: Query<(), With<Boundary>>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
 ) {
     for (marker_entity, marker, grid_pos) in &marker_query {
         if marker.is_complete() {
@@ -463,6 +1131,11 @@ fn complete_deconstruction(
                         if let Ok(mut current_job) = pawn_query.get_mut(pawn_entity) {
                             current_job.job_id = None;
                         }
+                        finished_jobs.send(JobFinishedEvent {
+                            pawn: pawn_entity,
+                            job: job_entity,
+                            kind: JobKind::Deconstruction,
+                        });
                     }
                     commands.entity(job_entity).despawn();
                 }
@@ -495,6 +1168,11 @@ fn complete_deconstruction(
                             .remove(&(grid_ivec + IVec2::new(x, y)));
                     }
                 }
+            } else if floor_query.get(target_entity).is_ok() {
+                building_map.floors.remove(&grid_ivec);
+            } else if boundary_query.get(target_entity).is_ok() {
+                building_map.boundaries.remove(&grid_ivec);
+                building_map.occupied.remove(&grid_ivec);
             } else {
                 // Window or other single-tile structure
                 building_map.occupied.remove(&grid_ivec);
@@ -507,18 +1185,213 @@ fn complete_deconstruction(
     }
 }
 
-// Handle door opening and closing based on pawn proximity
+// Assign repair jobs to idle pawns
+fn assign_repair_jobs_to_pawns(
+    mut commands: Commands,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut CurrentJob,
+            &WorkAssignments,
+            Option<&UnreachableJob>,
+        ),
+        (With<Pawn>, Without<OnBreak>, Without<InTraining>),
+    >,
+    mut job_query: Query<(Entity, &mut RepairJob)>,
+    target_query: Query<&GridPosition, With<Damaged>>,
+    grid_settings: Res<GridSettings>,
+    priorities: Res<JobPriorities>,
+    work_spots: WorkSpotFinder,
+) {
+    let mut claims = work_spots.claims();
+
+    for (pawn_entity, pawn_transform, mut current_job, work_assignments, unreachable) in
+        &mut pawn_query
+    {
+        if current_job.job_id.is_some() {
+            continue; // Pawn already has a job
+        }
+
+        // Repairs use the construction skill
+        if !work_assignments.can_do_work(WorkType::Construction)
+            || priorities.passes_over(work_assignments, WorkType::Construction)
+        {
+            continue;
+        }
+
+        // Find the nearest unassigned repair job
+        let mut nearest_job: Option<(Entity, f32)> = None;
+        let pawn_pos = pawn_transform.translation.truncate();
+
+        for (job_entity, job) in &job_query {
+            if job.assigned_pawn.is_some() {
+                continue; // Job already assigned
+            }
+            if unreachable.is_some_and(|unreachable| unreachable.job == job_entity) {
+                continue; // Couldn't get there last time
+            }
+
+            if let Ok(target_grid_pos) = target_query.get(job.target) {
+                let target_world_pos = grid_to_world(
+                    target_grid_pos.to_ivec2(),
+                    grid_settings.tile_size,
+                    grid_settings.width,
+                    grid_settings.height,
+                );
+                let distance = pawn_pos.distance(target_world_pos);
+
+                if nearest_job.is_none() || distance < nearest_job.unwrap().1 {
+                    nearest_job = Some((job_entity, distance));
+                }
+            }
+        }
+
+        // Assign the nearest job
+        if let Some((job_entity, _)) = nearest_job {
+            if let Ok((_, mut job)) = job_query.get_mut(job_entity) {
+                job.assigned_pawn = Some(pawn_entity);
+                current_job.job_id = Some(job_entity);
+
+                if let Ok(target_grid_pos) = target_query.get(job.target) {
+                    let target_pos = match work_spots.claim(
+                        &mut claims,
+                        pawn_entity,
+                        pawn_pos,
+                        job_entity,
+                        target_grid_pos.to_ivec2(),
+                    ) {
+                        Some(spot) => {
+                            commands.entity(pawn_entity).insert(spot);
+                            spot.position
+                        }
+                        None => grid_to_world(
+                            target_grid_pos.to_ivec2(),
+                            grid_settings.tile_size,
+                            grid_settings.width,
+                            grid_settings.height,
+                        ),
+                    };
+                    commands
+                        .entity(pawn_entity)
+                        .insert(MovementTarget { target: target_pos });
+                }
+            }
+        }
+    }
+}
+
+// Pawns repair damaged structures when nearby
+fn work_on_repairs(
+    mut commands: Commands,
+    pawn_query: Query<
+        (
+            Entity,
+            &Transform,
+            &CurrentJob,
+            &StaffNeeds,
+            &StaffSkills,
+            Option<&WorkSpot>,
+        ),
+        With<Pawn>,
+    >,
+    job_query: Query<&RepairJob>,
+    mut target_query: Query<(&Transform, &mut Damaged)>,
+    balance: Res<Balance>,
+    time: Res<Time>,
+) {
+    for (pawn_entity, pawn_transform, current_job, needs, skills, work_spot) in &pawn_query {
+        let Some(job_id) = current_job.job_id else {
+            continue;
+        };
+        let Ok(job) = job_query.get(job_id) else {
+            continue;
+        };
+        let Ok((target_transform, mut damaged)) = target_query.get_mut(job.target) else {
+            continue;
+        };
+
+        if in_place(
+            pawn_transform.translation.truncate(),
+            target_transform.translation.truncate(),
+            work_spot.filter(|spot| spot.job == job_id),
+        ) {
+            commands.entity(pawn_entity).remove::<MovementTarget>();
+
+            let work_speed =
+                balance.work.repair * needs.work_speed() * skills.speed(WorkType::Construction);
+            damaged.work_done += work_speed * time.delta_secs();
+            damaged.work_done = damaged.work_done.min(damaged.work_required);
+        }
+    }
+}
+
+// Draw an orange cross over anything waiting for repair
+fn draw_damage_markers(damaged_query: Query<&Transform, With<Damaged>>, mut gizmos: Gizmos) {
+    let half = TILE_SIZE * 0.35;
+    let color = Color::srgb(1.0, 0.55, 0.1);
+
+    for transform in &damaged_query {
+        let center = transform.translation.truncate();
+        gizmos.line_2d(center + Vec2::new(-half, -half), center + Vec2::new(half, half), color);
+        gizmos.line_2d(center + Vec2::new(-half, half), center + Vec2::new(half, -half), color);
+    }
+}
+
+// Finish repairs, and drop jobs whose target no longer exists
+fn complete_repairs(
+    mut commands: Commands,
+    job_query: Query<(Entity, &RepairJob)>,
+    damaged_query: Query<&Damaged>,
+    mut pawn_query: Query<&mut CurrentJob, With<Pawn>>,
+    mut finished_jobs: EventWriter<JobFinishedEvent>,
+) {
+    for (job_entity, job) in &job_query {
+        let repaired = damaged_query.get(job.target).ok().map(Damaged::is_repaired);
+        // A target that was deconstructed or already repaired ends the job too
+        if repaired == Some(false) {
+            continue;
+        }
+
+        if let Some(pawn_entity) = job.assigned_pawn {
+            if let Ok(mut current_job) = pawn_query.get_mut(pawn_entity) {
+                current_job.job_id = None;
+            }
+            commands.entity(pawn_entity).remove::<MovementTarget>();
+            if repaired.is_some() {
+                finished_jobs.send(JobFinishedEvent {
+                    pawn: pawn_entity,
+                    job: job_entity,
+                    kind: JobKind::Repair,
+                });
+            }
+        }
+
+        if let Some(mut target) = commands.get_entity(job.target) {
+            target.remove::<Damaged>();
+        }
+        commands.entity(job_entity).despawn();
+    }
+}
+
+// Handle door opening and closing based on pawn proximity, unless the door is held open
+// or locked
 fn handle_door_interactions(
-    mut door_query: Query<(&mut Transform, &mut Door, &MeshMaterial2d<ColorMaterial>)>,
+    mut door_query: Query<(
+        &mut Transform,
+        &mut Door,
+        &DoorMode,
+        &mut MeshMaterial2d<ColorMaterial>,
+    )>,
     pawn_query: Query<&Transform, (With<Pawn>, Without<Door>)>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    shared_assets: Res<SharedAssets>,
     time: Res<Time>,
 ) {
     const DOOR_OPEN_DISTANCE: f32 = TILE_SIZE * 3.0; // Doors open when pawns are within 3 tiles
     const DOOR_CLOSE_DELAY: f32 = 2.0; // Seconds before door closes after pawn leaves
     const DOOR_ANIMATION_SPEED: f32 = 4.0; // Radians per second
 
-    for (mut door_transform, mut door, material_handle) in &mut door_query {
+    for (mut door_transform, mut door, mode, mut material_handle) in &mut door_query {
         let door_pos = door_transform.translation.truncate();
 
         // Check if any pawn is near this door
@@ -537,7 +1410,12 @@ fn handle_door_interactions(
         let previous_state = door.state;
 
         // Update timer and state
-        if pawn_nearby {
+        if *mode == DoorMode::HeldOpen {
+            door.state = DoorState::Open;
+        } else if *mode == DoorMode::Locked {
+            door.state = DoorState::Closed;
+            door.close_timer = 0.0;
+        } else if pawn_nearby {
             // Pawn is nearby - open door and reset timer
             door.state = DoorState::Open;
             door.close_timer = DOOR_CLOSE_DELAY;
@@ -570,20 +1448,195 @@ fn handle_door_interactions(
             door_transform.rotation = Quat::from_rotation_z(new_rotation);
         }
 
-        // Update visual appearance when state changes
+        // Swap between the shared open/closed materials when state changes
         if previous_state != door.state {
-            if let Some(material) = materials.get_mut(&material_handle.0) {
-                match door.state {
-                    DoorState::Open => {
-                        // Make door more transparent when open
-                        material.color = Color::srgb(0.4, 0.3, 0.2).with_alpha(0.3);
-                    }
-                    DoorState::Closed => {
-                        // Solid color when closed
-                        material.color = Color::srgb(0.4, 0.3, 0.2);
-                    }
-                }
-            }
+            material_handle.0 = match door.state {
+                DoorState::Open => shared_assets.door_open_material.clone(), // More transparent
+                DoorState::Closed => shared_assets.door_material.clone(),
+            };
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::demand::GuestSpawnSettings;
+    use crate::systems::economy::Money;
+    use crate::systems::guests::GuestLedger;
+    use crate::systems::save_load::{
+        process_load_requests, write_save_file, LoadRequestState, PlayTime, SaveData,
+        SaveLoadConfig, SaveLoadedEvent,
+    };
+    use crate::systems::stable_id::StableIds;
+    use crate::systems::statistics::StatsHistory;
+    use crate::systems::terrain::Terrain;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_systems(Update, cancel_orphaned_jobs);
+        app
+    }
+
+    /// Enough of the game to load a save, with the orphaned jobs checked right after
+    fn load_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            .init_asset::<Image>()
+            .init_resource::<SharedAssets>()
+            .init_resource::<GridSettings>()
+            .init_resource::<BuildingMap>()
+            .init_resource::<StableIds>()
+            .init_resource::<StatsHistory>()
+            .init_resource::<PlayTime>()
+            .init_resource::<Money>()
+            .init_resource::<GuestLedger>()
+            .init_resource::<Terrain>()
+            .init_resource::<GuestSpawnSettings>()
+            .init_resource::<SaveLoadConfig>()
+            .init_resource::<LoadRequestState>()
+            .add_event::<SaveLoadedEvent>()
+            .add_systems(
+                Update,
+                (process_load_requests, cancel_orphaned_jobs).chain(),
+            );
+        app
+    }
+
+    /// Loads a save of an empty resort over whatever the test built
+    fn load_empty_save(app: &mut App, name: &str) {
+        let path = std::env::temp_dir()
+            .join(format!(
+                "resort-tycoon-{}-{}.json",
+                name,
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .to_string();
+        write_save_file(&path, &SaveData::default()).unwrap();
+        app.world_mut()
+            .resource_mut::<LoadRequestState>()
+            .request(path.clone());
+        app.update();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    /// A worker partway to `job`, with somewhere to go and a route to get there
+    fn spawn_worker(app: &mut App, job: Entity) -> Entity {
+        app.world_mut()
+            .spawn((
+                Pawn::default(),
+                CurrentJob { job_id: Some(job) },
+                MovementTarget { target: Vec2::ONE },
+                PawnPath {
+                    waypoints: vec![Vec2::ONE],
+                    goal: Vec2::ONE,
+                    replan_timer: 0.0,
+                },
+            ))
+            .id()
+    }
+
+    fn assert_freed(app: &App, pawn: Entity) {
+        let pawn = app.world().entity(pawn);
+        assert_eq!(pawn.get::<CurrentJob>().unwrap().job_id, None);
+        assert!(!pawn.contains::<MovementTarget>());
+        assert!(!pawn.contains::<PawnPath>());
+    }
+
+    #[test]
+    fn load_during_construction_frees_the_builder() {
+        let mut app = load_app();
+        let blueprint = app
+            .world_mut()
+            .spawn(Blueprint::new(BlueprintType::Wall))
+            .id();
+        let job = app
+            .world_mut()
+            .spawn(ConstructionJob::new(blueprint, 0))
+            .id();
+        let pawn = spawn_worker(&mut app, job);
+        app.world_mut()
+            .get_mut::<ConstructionJob>(job)
+            .unwrap()
+            .assigned_pawn = Some(pawn);
+
+        // Loading clears the blueprints the saved resort replaces, and their jobs
+        load_empty_save(&mut app, "construction");
+
+        assert!(!app.world().entities().contains(job));
+        assert_freed(&app, pawn);
+    }
+
+    #[test]
+    fn load_during_deconstruction_frees_the_worker() {
+        let mut app = load_app();
+        let wall = app.world_mut().spawn(Wall::default()).id();
+        let marker = app.world_mut().spawn(DeconstructionMarker::new(wall)).id();
+        let job = app.world_mut().spawn(DeconstructionJob::new(marker)).id();
+        let pawn = spawn_worker(&mut app, job);
+        app.world_mut()
+            .get_mut::<DeconstructionJob>(job)
+            .unwrap()
+            .assigned_pawn = Some(pawn);
+
+        load_empty_save(&mut app, "deconstruction");
+
+        assert!(!app.world().entities().contains(job));
+        assert_freed(&app, pawn);
+    }
+
+    #[test]
+    fn room_service_keeps_its_courier() {
+        let mut app = app();
+        let guest = app.world_mut().spawn_empty().id();
+        let room = app.world_mut().spawn_empty().id();
+        let order = app
+            .world_mut()
+            .spawn(RoomServiceOrder::new(
+                RoomServiceItem::Food,
+                guest,
+                room,
+                Vec2::ZERO,
+                Vec2::ONE,
+            ))
+            .id();
+        let pawn = spawn_worker(&mut app, order);
+        app.world_mut()
+            .get_mut::<RoomServiceOrder>(order)
+            .unwrap()
+            .assigned_pawn = Some(pawn);
+
+        app.update();
+
+        let courier = app.world().entity(pawn);
+        assert_eq!(courier.get::<CurrentJob>().unwrap().job_id, Some(order));
+        assert!(courier.contains::<MovementTarget>());
+        assert!(courier.contains::<PawnPath>());
+        let order = app.world().get::<RoomServiceOrder>(order).unwrap();
+        assert_eq!(order.assigned_pawn, Some(pawn));
+    }
+
+    #[test]
+    fn jobs_with_their_blueprint_carry_on() {
+        let mut app = app();
+        let blueprint = app
+            .world_mut()
+            .spawn(Blueprint::new(BlueprintType::Wall))
+            .id();
+        let job = app
+            .world_mut()
+            .spawn(ConstructionJob::new(blueprint, 0))
+            .id();
+        let pawn = spawn_worker(&mut app, job);
+
+        app.update();
+
+        assert!(app.world().entities().contains(job));
+        let pawn = app.world().entity(pawn);
+        assert_eq!(pawn.get::<CurrentJob>().unwrap().job_id, Some(job));
+        assert!(pawn.contains::<MovementTarget>());
+    }
+}
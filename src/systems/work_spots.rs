@@ -0,0 +1,136 @@
+use crate::components::*;
+use crate::systems::building::BuildingMap;
+use crate::systems::grid::{grid_to_world, GridSettings, TILE_SIZE};
+use crate::systems::pathfinding::{has_clearance, reachable_tiles, Walker, ZoneAccessMap};
+use crate::systems::terrain::Terrain;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// How many tiles out from a job's target a worker may stand to work on it. The corners
+/// of that square are left out, so every spot is well inside the three tiles work and
+/// deliveries are done from.
+const WORK_SPOT_RADIUS: i32 = 2;
+/// Workers this close to their spot are stood on it
+pub const WORK_SPOT_ARRIVAL: f32 = TILE_SIZE;
+
+/// Finds each worker a spot of their own to work from, so two workers sent to the same
+/// place spread out around it instead of standing on top of each other
+#[derive(SystemParam)]
+pub struct WorkSpotFinder<'w, 's> {
+    spot_query: Query<'w, 's, (Entity, &'static WorkSpot)>,
+    building_map: Res<'w, BuildingMap>,
+    access_map: Res<'w, ZoneAccessMap>,
+    grid_settings: Res<'w, GridSettings>,
+    terrain: Res<'w, Terrain>,
+}
+
+impl WorkSpotFinder<'_, '_> {
+    /// The spots already held, by the worker holding them
+    pub fn claims(&self) -> HashMap<Entity, IVec2> {
+        self.spot_query
+            .iter()
+            .map(|(pawn, spot)| (pawn, spot.tile))
+            .collect()
+    }
+
+    /// Claims the spot `pawn` should work `job` on `target` from: the target itself if it can
+    /// be stood on and nobody else is there, otherwise the nearest free tile around it,
+    /// on the side the pawn is coming from. None when there is nowhere free to stand.
+    pub fn claim(
+        &self,
+        claims: &mut HashMap<Entity, IVec2>,
+        pawn: Entity,
+        pawn_pos: Vec2,
+        job: Entity,
+        target: IVec2,
+    ) -> Option<WorkSpot> {
+        let taken = |tile: IVec2| {
+            claims.iter().any(|(other, spot)| {
+                // Pawns are two tiles across, so spots closer than that would overlap
+                let gap = (*spot - tile).abs();
+                *other != pawn && gap.x < PAWN_GRID_SIZE && gap.y < PAWN_GRID_SIZE
+            })
+        };
+        let world = |tile: IVec2| {
+            grid_to_world(
+                tile,
+                self.grid_settings.tile_size,
+                self.grid_settings.width,
+                self.grid_settings.height,
+            )
+        };
+
+        let tile = self
+            .spots_around(target)
+            .filter(|tile| !taken(*tile))
+            .min_by(|a, b| {
+                let offset = |tile: &IVec2| (*tile - target).length_squared();
+                offset(a).cmp(&offset(b)).then(
+                    world(*a)
+                        .distance(pawn_pos)
+                        .total_cmp(&world(*b).distance(pawn_pos)),
+                )
+            })?;
+
+        claims.insert(pawn, tile);
+        Some(WorkSpot {
+            job,
+            tile,
+            position: world(tile),
+        })
+    }
+
+    /// Tiles a worker could stand on to work on `target`, whoever holds them. Zones closed
+    /// to staff offer none.
+    fn spots_around(&self, target: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        let closed = self.access_map.closed_to(Walker::Staff);
+        (-WORK_SPOT_RADIUS..=WORK_SPOT_RADIUS)
+            .flat_map(|dx| (-WORK_SPOT_RADIUS..=WORK_SPOT_RADIUS).map(move |dy| IVec2::new(dx, dy)))
+            .filter(|offset| offset.length_squared() <= WORK_SPOT_RADIUS * WORK_SPOT_RADIUS + 1)
+            .map(move |offset| target + offset)
+            .filter(move |tile| {
+                !self.building_map.doors.contains_key(tile)
+                    && !closed.contains(tile)
+                    && has_clearance(
+                        *tile,
+                        &self.building_map,
+                        &self.grid_settings,
+                        &self.terrain,
+                    )
+            })
+    }
+
+    /// Everywhere the pawns standing on `starts` can walk to
+    pub fn reachable_from(&self, starts: impl IntoIterator<Item = IVec2>) -> HashSet<IVec2> {
+        reachable_tiles(
+            starts,
+            &self.building_map,
+            &self.grid_settings,
+            &self.terrain,
+            self.access_map.closed_to(Walker::Staff),
+        )
+    }
+
+    /// Whether any spot to work on `target` from lies within `reachable`
+    pub fn can_reach(&self, target: IVec2, reachable: &HashSet<IVec2>) -> bool {
+        self.spots_around(target)
+            .any(|tile| reachable.contains(&tile))
+    }
+
+    pub fn map_changed(&self) -> bool {
+        self.building_map.is_changed() || self.access_map.is_changed()
+    }
+}
+
+/// Workers who finished, dropped or lost their job give its spot up for the next one
+pub fn release_work_spots(
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &CurrentJob, &WorkSpot)>,
+) {
+    for (pawn_entity, current_job, spot) in &pawn_query {
+        if current_job.job_id != Some(spot.job) {
+            commands.entity(pawn_entity).remove::<WorkSpot>();
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::grid::TILE_SIZE;
+use crate::systems::work::ConstructionCompletedEvent;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+const BAR_WIDTH: f32 = TILE_SIZE * 1.5;
+const BAR_HEIGHT: f32 = 3.0;
+const BAR_OFFSET: f32 = TILE_SIZE * 0.9;
+const BAR_Z: f32 = 40.0;
+const CONSTRUCTION_BAR_COLOR: Color = Color::srgb(0.3, 0.8, 0.3);
+const DECONSTRUCTION_BAR_COLOR: Color = Color::srgb(0.9, 0.35, 0.25);
+
+/// Work units between hammer strikes, so faster work means faster hammering
+const WORK_PER_STRIKE: f32 = 12.0;
+const STRIKE_SQUASH: f32 = 0.2;
+const STRIKE_FLASH: f32 = 0.5;
+const HAMMERING_GRACE_SECONDS: f32 = 0.25;
+
+const EFFECT_Z: f32 = 38.0;
+/// A finished structure glows white for a moment and throws off a little dust
+const FLASH_SECONDS: f32 = 0.35;
+const PUFF_PARTICLES: usize = 6;
+const PUFF_SECONDS: f32 = 0.6;
+const PUFF_SPEED: f32 = TILE_SIZE * 1.5;
+const PUFF_SIZE: f32 = TILE_SIZE * 0.3;
+const PUFF_COLOR: Color = Color::srgba(0.85, 0.8, 0.7, 0.8);
+/// New furniture gets a few glints rising off it instead
+const SPARKLE_PARTICLES: usize = 4;
+const SPARKLE_SECONDS: f32 = 0.8;
+const SPARKLE_SIZE: f32 = TILE_SIZE * 0.25;
+const SPARKLE_COLOR: Color = Color::srgba(1.0, 0.95, 0.6, 1.0);
+
+/// World-space bar floating over a blueprint or deconstruction marker
+#[derive(Component)]
+pub struct WorkProgressBar {
+    pub target: Entity,
+}
+
+#[derive(Component)]
+pub struct WorkProgressBarFill;
+
+/// A pawn swinging a hammer; remembers its colour so the flash can be undone
+#[derive(Component)]
+pub struct HammeringAnimation {
+    pub base_color: Color,
+    pub work_done: f32,
+    pub idle: f32,
+}
+
+/// A short-lived sprite from a completion flash, puff or sparkle. It drifts, grows by
+/// `grow` of its size and fades out over its lifetime, then despawns.
+#[derive(Component)]
+pub struct CompletionEffect {
+    pub age: f32,
+    pub lifetime: f32,
+    pub velocity: Vec2,
+    pub grow: f32,
+    /// Sparkles flicker as they fade rather than fading smoothly
+    pub twinkle: bool,
+    pub base_alpha: f32,
+}
+
+pub struct WorkVisualsPlugin;
+
+impl Plugin for WorkVisualsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                sync_work_progress_bars,
+                animate_working_pawns,
+                (spawn_completion_effects, animate_completion_effects).chain(),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+// Bars appear once work has started and disappear with their blueprint or marker
+fn sync_work_progress_bars(
+    mut commands: Commands,
+    blueprint_query: Query<(Entity, &Blueprint, &Transform)>,
+    marker_query: Query<(Entity, &DeconstructionMarker, &Transform)>,
+    mut bar_query: Query<
+        (Entity, &WorkProgressBar, &Children, &mut Transform),
+        (Without<Blueprint>, Without<DeconstructionMarker>),
+    >,
+    mut fill_query: Query<
+        &mut Transform,
+        (
+            With<WorkProgressBarFill>,
+            Without<WorkProgressBar>,
+            Without<Blueprint>,
+            Without<DeconstructionMarker>,
+        ),
+    >,
+) {
+    let mut targets: HashMap<Entity, (f32, Vec2, Color)> = HashMap::new();
+    for (entity, blueprint, transform) in &blueprint_query {
+        if blueprint.work_done > 0.0 {
+            let position = transform.translation.truncate();
+            targets.insert(
+                entity,
+                (blueprint.progress(), position, CONSTRUCTION_BAR_COLOR),
+            );
+        }
+    }
+    for (entity, marker, transform) in &marker_query {
+        if marker.work_done > 0.0 {
+            let position = transform.translation.truncate();
+            targets.insert(
+                entity,
+                (marker.progress(), position, DECONSTRUCTION_BAR_COLOR),
+            );
+        }
+    }
+
+    for (bar_entity, bar, children, mut transform) in &mut bar_query {
+        let Some((progress, position, _)) = targets.remove(&bar.target) else {
+            commands.entity(bar_entity).despawn_recursive();
+            continue;
+        };
+
+        transform.translation = (position + Vec2::new(0.0, BAR_OFFSET)).extend(BAR_Z);
+        for &child in children.iter() {
+            if let Ok(mut fill_transform) = fill_query.get_mut(child) {
+                set_fill(&mut fill_transform, progress);
+            }
+        }
+    }
+
+    // Whatever is left has started progressing but has no bar yet
+    for (target, (progress, position, color)) in targets {
+        let mut fill_transform = Transform::from_xyz(0.0, 0.0, 0.1);
+        set_fill(&mut fill_transform, progress);
+
+        commands
+            .spawn((
+                Sprite::from_color(
+                    Color::srgba(0.0, 0.0, 0.0, 0.7),
+                    Vec2::new(BAR_WIDTH, BAR_HEIGHT),
+                ),
+                Transform::from_translation((position + Vec2::new(0.0, BAR_OFFSET)).extend(BAR_Z)),
+                WorkProgressBar { target },
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    Sprite::from_color(color, Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                    fill_transform,
+                    WorkProgressBarFill,
+                ));
+            });
+    }
+}
+
+// The fill is a full-width sprite squeezed from the left edge
+fn set_fill(transform: &mut Transform, progress: f32) {
+    let progress = progress.clamp(0.0, 1.0);
+    transform.scale.x = progress;
+    transform.translation.x = -BAR_WIDTH * (1.0 - progress) / 2.0;
+}
+
+// Pawns whose job is advancing bob and flash in time with the work being done
+fn animate_working_pawns(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pawn_query: Query<
+        (
+            Entity,
+            &CurrentJob,
+            &mut Transform,
+            &MeshMaterial2d<ColorMaterial>,
+            Option<&mut HammeringAnimation>,
+        ),
+        With<Pawn>,
+    >,
+    construction_query: Query<&ConstructionJob>,
+    deconstruction_query: Query<&DeconstructionJob>,
+    blueprint_query: Query<Ref<Blueprint>>,
+    marker_query: Query<Ref<DeconstructionMarker>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (pawn_entity, current_job, mut transform, material_handle, hammering) in &mut pawn_query {
+        // Work done on the pawn's job, if it moved on since the last frame
+        let advanced = current_job.job_id.and_then(|job_id| {
+            if let Ok(job) = construction_query.get(job_id) {
+                blueprint_query
+                    .get(job.blueprint)
+                    .ok()
+                    .filter(|blueprint| blueprint.is_changed() && !blueprint.is_complete())
+                    .map(|blueprint| blueprint.work_done)
+            } else if let Ok(job) = deconstruction_query.get(job_id) {
+                marker_query
+                    .get(job.marker)
+                    .ok()
+                    .filter(|marker| marker.is_changed() && !marker.is_complete())
+                    .map(|marker| marker.work_done)
+            } else {
+                None
+            }
+        });
+
+        let Some(mut hammering) = hammering else {
+            if let (Some(work_done), Some(material)) = (advanced, materials.get(&material_handle.0))
+            {
+                commands.entity(pawn_entity).insert(HammeringAnimation {
+                    base_color: material.color,
+                    work_done,
+                    idle: 0.0,
+                });
+            }
+            continue;
+        };
+
+        let Some(material) = materials.get_mut(&material_handle.0) else {
+            continue;
+        };
+
+        match advanced {
+            Some(work_done) => {
+                hammering.work_done = work_done;
+                hammering.idle = 0.0;
+            }
+            None => hammering.idle += time.delta_secs(),
+        }
+
+        // Fixed ticks don't land on every frame, so only stop after a short quiet spell
+        if hammering.idle > HAMMERING_GRACE_SECONDS || current_job.job_id.is_none() {
+            transform.scale = Vec3::ONE;
+            material.color = hammering.base_color;
+            commands.entity(pawn_entity).remove::<HammeringAnimation>();
+            continue;
+        }
+
+        // Each strike is a quick squash that eases back out
+        let strike = 1.0 - (hammering.work_done / WORK_PER_STRIKE).fract();
+        let impact = strike * strike;
+        transform.scale = Vec3::new(
+            1.0 + STRIKE_SQUASH * impact * 0.5,
+            1.0 - STRIKE_SQUASH * impact,
+            1.0,
+        );
+        material.color = hammering
+            .base_color
+            .mix(&Color::WHITE, STRIKE_FLASH * impact);
+    }
+}
+
+// Structures flash and puff when their blueprint turns into the real thing; furniture,
+// which appears finished, sparkles instead
+fn spawn_completion_effects(
+    mut commands: Commands,
+    mut completed: EventReader<ConstructionCompletedEvent>,
+) {
+    for event in completed.read() {
+        // Nothing to show for something cleared away again before the effects were
+        // drawn, as a load in the same frame does
+        if commands.get_entity(event.entity).is_none() {
+            continue;
+        }
+        let position = event.world_pos;
+        // Spread each burst's particles evenly, turned a little differently per tile
+        let turn = (event.grid_pos.x * 7 + event.grid_pos.y * 13).rem_euclid(16) as f32 / 16.0;
+
+        if let BlueprintType::Furniture(_) = event.building_type {
+            for i in 0..SPARKLE_PARTICLES {
+                let angle = (i as f32 + turn) / SPARKLE_PARTICLES as f32 * std::f32::consts::TAU;
+                let offset = Vec2::from_angle(angle) * TILE_SIZE * 0.5;
+                spawn_effect(
+                    &mut commands,
+                    SPARKLE_COLOR,
+                    Vec2::splat(SPARKLE_SIZE),
+                    position + offset,
+                    // Turned 45 degrees so each glint reads as a diamond
+                    Quat::from_rotation_z(std::f32::consts::FRAC_PI_4),
+                    CompletionEffect {
+                        age: -(i as f32) * 0.1,
+                        lifetime: SPARKLE_SECONDS,
+                        velocity: Vec2::new(0.0, TILE_SIZE * 0.8),
+                        grow: -0.5,
+                        twinkle: true,
+                        base_alpha: 1.0,
+                    },
+                );
+            }
+            continue;
+        }
+
+        let flash_size = match event.building_type {
+            BlueprintType::Door(DoorOrientation::Horizontal) => Vec2::new(TILE_SIZE * 2.0, TILE_SIZE),
+            BlueprintType::Door(DoorOrientation::Vertical) => Vec2::new(TILE_SIZE, TILE_SIZE * 2.0),
+            _ => Vec2::splat(TILE_SIZE),
+        };
+        spawn_effect(
+            &mut commands,
+            Color::WHITE,
+            flash_size,
+            position,
+            Quat::IDENTITY,
+            CompletionEffect {
+                age: 0.0,
+                lifetime: FLASH_SECONDS,
+                velocity: Vec2::ZERO,
+                grow: 0.3,
+                twinkle: false,
+                base_alpha: 0.9,
+            },
+        );
+
+        // Floors go down quietly; only standing structures kick up dust
+        if let BlueprintType::Floor(_) = event.building_type {
+            continue;
+        }
+        for i in 0..PUFF_PARTICLES {
+            let angle = (i as f32 + turn) / PUFF_PARTICLES as f32 * std::f32::consts::TAU;
+            let direction = Vec2::from_angle(angle);
+            spawn_effect(
+                &mut commands,
+                PUFF_COLOR,
+                Vec2::splat(PUFF_SIZE),
+                position + direction * flash_size * 0.4,
+                Quat::IDENTITY,
+                CompletionEffect {
+                    age: 0.0,
+                    lifetime: PUFF_SECONDS,
+                    velocity: direction * PUFF_SPEED,
+                    grow: 1.0,
+                    twinkle: false,
+                    base_alpha: PUFF_COLOR.alpha(),
+                },
+            );
+        }
+    }
+}
+
+fn spawn_effect(
+    commands: &mut Commands,
+    color: Color,
+    size: Vec2,
+    position: Vec2,
+    rotation: Quat,
+    effect: CompletionEffect,
+) {
+    // Delayed effects stay hidden until their turn comes
+    let alpha = if effect.age < 0.0 { 0.0 } else { effect.base_alpha };
+    commands.spawn((
+        Sprite::from_color(color.with_alpha(alpha), size),
+        Transform::from_translation(position.extend(EFFECT_Z)).with_rotation(rotation),
+        effect,
+    ));
+}
+
+fn animate_completion_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut effect_query: Query<(Entity, &mut CompletionEffect, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut effect, mut transform, mut sprite) in &mut effect_query {
+        effect.age += delta;
+        if effect.age < 0.0 {
+            continue;
+        }
+        if effect.age >= effect.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let t = effect.age / effect.lifetime;
+        transform.translation += (effect.velocity * delta * (1.0 - t)).extend(0.0);
+        transform.scale = Vec3::splat(1.0 + effect.grow * t);
+
+        let mut alpha = effect.base_alpha * (1.0 - t);
+        if effect.twinkle {
+            alpha *= 0.6 + 0.4 * (effect.age * 30.0).sin().abs();
+        }
+        sprite.color.set_alpha(alpha);
+    }
+}
@@ -1,25 +1,93 @@
 use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::cursor::CursorWorldPosition;
 use crate::systems::grid::*;
+use crate::systems::room_detection::RoomAdjacency;
+use crate::ui::{GameSettings, LocalizedText, UiInputBlocker};
 use bevy::prelude::*;
-use bevy::window::{PrimaryWindow, Window as BevyWindow};
+
+/// Whether zone names are drawn over their rooms in the world
+#[derive(Resource)]
+pub struct ZoneLabelSettings {
+    pub visible: bool,
+}
+
+impl Default for ZoneLabelSettings {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+/// Whether zones are tinted by type and outlined, with the legend explaining the colours
+#[derive(Resource)]
+pub struct ZoneOverlaySettings {
+    pub visible: bool,
+}
+
+impl Default for ZoneOverlaySettings {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+/// Between the floor and the furniture
+const ZONE_OVERLAY_Z: f32 = 0.8;
 
 pub struct ZoneVisualizationPlugin;
 
 impl Plugin for ZoneVisualizationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (update_room_hover_ui,));
+        app.init_resource::<ZoneLabelSettings>()
+            .init_resource::<ZoneOverlaySettings>()
+            .add_systems(Startup, setup_zone_legend)
+            .add_systems(
+                Update,
+                (
+                    update_room_hover_ui,
+                    toggle_zone_labels,
+                    sync_zone_labels,
+                    toggle_zone_overlay,
+                    sync_zone_overlays,
+                    draw_zone_borders,
+                    apply_zone_legend_visibility,
+                    recolor_zone_legend,
+                )
+                    .in_set(GameplaySet),
+            );
     }
 }
 
 #[derive(Component)]
 struct RoomStatsPanel;
 
+/// Tinted tiles covering one zone
+#[derive(Component)]
+struct ZoneOverlay {
+    zone: Entity,
+}
+
+/// Key to the overlay colours, shown while the overlay is
+#[derive(Component)]
+struct ZoneLegend;
+
+/// A legend swatch whose colour depends on the palette
+#[derive(Component, Clone, Copy)]
+enum PaletteSwatch {
+    Zone(ZoneType),
+    InvalidZone,
+}
+
+/// World-space text showing a zone's name, centered over its tiles
+#[derive(Component)]
+struct ZoneLabel {
+    zone: Entity,
+}
+
 /// Shows room stats on hover
 fn update_room_hover_ui(
     mut commands: Commands,
-    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    grid_settings: Res<GridSettings>,
+    cursor: Res<CursorWorldPosition>,
+    adjacency: Res<RoomAdjacency>,
     room_query: Query<&Room>,
     zone_query: Query<&Zone>,
     panel_query: Query<Entity, With<RoomStatsPanel>>,
@@ -29,43 +97,55 @@ fn update_room_hover_ui(
         commands.entity(entity).despawn_recursive();
     }
 
-    let window = window_query.single();
-    let (camera, camera_transform) = camera_query.single();
+    let (Some(cursor_pos), Some(grid_pos)) = (cursor.ui, cursor.grid) else {
+        return;
+    };
+    // Find if cursor is in any room
+    let Some(room_entity) = adjacency.room_at(grid_pos) else {
+        return;
+    };
+    let Ok(room) = room_query.get(room_entity) else {
+        return;
+    };
 
-    // Get cursor position
-    if let Some(cursor_pos) = window.cursor_position() {
-        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-            if let Some(grid_pos) = world_to_grid(
-                world_pos,
-                grid_settings.tile_size,
-                grid_settings.width,
-                grid_settings.height,
-            ) {
-                // Find if cursor is in any room
-                for room in &room_query {
-                    if room.contains_tile(grid_pos) {
-                        // Find the zone for this room
-                        let zone = zone_query.iter().find(|z| z.tiles.contains(&grid_pos));
-
-                        // Create stats panel
-                        spawn_room_stats_panel(&mut commands, room, zone, cursor_pos);
-                        break;
-                    }
-                }
-            }
-        }
-    }
+    // Find the zone for this room
+    let zone = zone_query.iter().find(|z| z.tiles.contains(&grid_pos));
+
+    // Bedrooms are rated on how far guests walk from check-in
+    let lobby_distance = zone
+        .filter(|zone| zone.zone_type == ZoneType::GuestBedroom)
+        .map(|_| {
+            zone_query
+                .iter()
+                .filter(|zone| zone.zone_type == ZoneType::Lobby)
+                .filter_map(|lobby| lobby.tiles.iter().find_map(|t| adjacency.room_at(*t)))
+                .filter_map(|lobby_room| adjacency.door_distance(lobby_room, room_entity))
+                .min()
+        });
+
+    // Create stats panel
+    spawn_room_stats_panel(
+        &mut commands,
+        room,
+        zone,
+        adjacency.opens_outdoors(room_entity),
+        lobby_distance,
+        cursor_pos,
+    );
 }
 
 fn spawn_room_stats_panel(
     commands: &mut Commands,
     room: &Room,
     zone: Option<&Zone>,
+    opens_outdoors: bool,
+    lobby_distance: Option<Option<usize>>,
     cursor_pos: Vec2,
 ) {
-    let panel_text = if let Some(zone) = zone {
+    let mut panel_text = if let Some(zone) = zone {
         format!(
-            "{}\nQuality: {} ({}★)\nSize: {} tiles",
+            "{}\n{}\nQuality: {} ({}★)\nSize: {} tiles",
+            zone.name,
             zone.zone_type.name(),
             zone.quality.name(),
             zone.quality.stars(),
@@ -73,11 +153,32 @@ fn spawn_room_stats_panel(
         )
     } else {
         format!(
-            "Unassigned Room\nSize: {} tiles\n\nAdd furniture to create a zone",
+            "Unassigned Room\nSize: {} tiles",
             room.tile_count(),
         )
     };
 
+    panel_text.push_str(&format!(
+        "\nDoors: {} ({} to other rooms{})",
+        room.doors.len(),
+        room.adjacent_rooms.len(),
+        if opens_outdoors { ", one outside" } else { "" },
+    ));
+    match room.scenic_view {
+        Some(ScenicView::Ocean) => panel_text.push_str("\nOcean view (+1 quality tier)"),
+        Some(ScenicView::Cliff) => panel_text.push_str("\nCliff-top view (+1 quality tier)"),
+        None if room.has_exterior_window => panel_text.push_str("\nWindow view outside"),
+        None => {}
+    }
+    match lobby_distance {
+        Some(Some(doors)) => panel_text.push_str(&format!("\n{} doors from the lobby", doors)),
+        Some(None) => panel_text.push_str("\nNo door route to a lobby"),
+        None => {}
+    }
+    if zone.is_none() {
+        panel_text.push_str("\n\nAdd furniture to create a zone");
+    }
+
     // Spawn UI panel near cursor
     commands
         .spawn((
@@ -102,3 +203,349 @@ fn spawn_room_stats_panel(
             ));
         });
 }
+
+fn toggle_zone_labels(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut label_settings: ResMut<ZoneLabelSettings>,
+) {
+    if ui_blocker.text_input_active {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyN) {
+        label_settings.visible = !label_settings.visible;
+    }
+}
+
+/// Keeps one label per zone, following renames and room shape changes
+fn sync_zone_labels(
+    mut commands: Commands,
+    zone_query: Query<(Entity, &Zone)>,
+    changed_zones: Query<Entity, Changed<Zone>>,
+    mut label_query: Query<(
+        Entity,
+        &ZoneLabel,
+        &mut Text2d,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+    label_settings: Res<ZoneLabelSettings>,
+    grid_settings: Res<GridSettings>,
+) {
+    let visibility = if label_settings.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+
+    let mut labelled_zones = Vec::new();
+
+    for (label_entity, label, mut text, mut transform, mut label_visibility) in &mut label_query {
+        let Ok((_, zone)) = zone_query.get(label.zone) else {
+            commands.entity(label_entity).despawn();
+            continue;
+        };
+        labelled_zones.push(label.zone);
+
+        if changed_zones.contains(label.zone) {
+            text.0 = zone.name.clone();
+            *transform = zone_label_transform(zone, &grid_settings);
+        }
+        label_visibility.set_if_neq(visibility);
+    }
+
+    for (zone_entity, zone) in &zone_query {
+        if labelled_zones.contains(&zone_entity) || zone.tiles.is_empty() {
+            continue;
+        }
+
+        commands.spawn((
+            Text2d::new(zone.name.clone()),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.85)),
+            zone_label_transform(zone, &grid_settings),
+            visibility,
+            ZoneLabel { zone: zone_entity },
+        ));
+    }
+}
+
+fn zone_label_transform(zone: &Zone, grid_settings: &GridSettings) -> Transform {
+    let sum = zone
+        .tiles
+        .iter()
+        .map(|tile| {
+            grid_to_world(
+                *tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            )
+        })
+        .fold(Vec2::ZERO, |acc, pos| acc + pos);
+    let center = sum / zone.tiles.len().max(1) as f32;
+
+    // Above furniture and pawns so the name stays readable
+    Transform::from_xyz(center.x, center.y, 50.0)
+}
+
+fn toggle_zone_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    mut overlay_settings: ResMut<ZoneOverlaySettings>,
+) {
+    if ui_blocker.text_input_active {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::F5) {
+        overlay_settings.visible = !overlay_settings.visible;
+    }
+}
+
+/// Keeps one overlay per zone, rebuilt whenever the zone's tiles, type or quality change,
+/// or the palette does. Better rated zones get a stronger tint.
+fn sync_zone_overlays(
+    mut commands: Commands,
+    zone_query: Query<(Entity, &Zone)>,
+    changed_zones: Query<Entity, Changed<Zone>>,
+    mut overlay_query: Query<(Entity, &ZoneOverlay, &mut Visibility)>,
+    overlay_settings: Res<ZoneOverlaySettings>,
+    grid_settings: Res<GridSettings>,
+    settings: Res<GameSettings>,
+) {
+    let visibility = if overlay_settings.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+
+    let mut overlaid_zones = Vec::new();
+
+    for (overlay_entity, overlay, mut overlay_visibility) in &mut overlay_query {
+        let stale = zone_query.get(overlay.zone).is_err() || changed_zones.contains(overlay.zone);
+        if stale || settings.is_changed() {
+            commands.entity(overlay_entity).despawn_recursive();
+            continue;
+        }
+        overlaid_zones.push(overlay.zone);
+        overlay_visibility.set_if_neq(visibility);
+    }
+
+    for (zone_entity, zone) in &zone_query {
+        if overlaid_zones.contains(&zone_entity) || zone.tiles.is_empty() {
+            continue;
+        }
+
+        let color = settings
+            .palette
+            .zone_color(zone.zone_type)
+            .with_alpha(zone.quality.overlay_alpha());
+        commands
+            .spawn((
+                Transform::default(),
+                visibility,
+                ZoneOverlay { zone: zone_entity },
+            ))
+            .with_children(|parent| {
+                for tile in &zone.tiles {
+                    let pos = grid_to_world(
+                        *tile,
+                        grid_settings.tile_size,
+                        grid_settings.width,
+                        grid_settings.height,
+                    );
+                    parent.spawn((
+                        Sprite::from_color(color, Vec2::splat(grid_settings.tile_size)),
+                        Transform::from_xyz(pos.x, pos.y, ZONE_OVERLAY_Z),
+                    ));
+                }
+            });
+    }
+}
+
+/// Outlines each zone along the tile edges it doesn't share with itself; zones that
+/// can't be rated are outlined in red (yellow in the colour-blind palette)
+fn draw_zone_borders(
+    overlay_settings: Res<ZoneOverlaySettings>,
+    settings: Res<GameSettings>,
+    zone_query: Query<&Zone>,
+    grid_settings: Res<GridSettings>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay_settings.visible {
+        return;
+    }
+
+    let half = grid_settings.tile_size / 2.0;
+    let edges = [
+        (IVec2::X, Vec2::new(half, -half), Vec2::new(half, half)),
+        (IVec2::NEG_X, Vec2::new(-half, -half), Vec2::new(-half, half)),
+        (IVec2::Y, Vec2::new(-half, half), Vec2::new(half, half)),
+        (IVec2::NEG_Y, Vec2::new(-half, -half), Vec2::new(half, -half)),
+    ];
+
+    for zone in &zone_query {
+        let color = if zone.quality == ZoneQuality::None {
+            settings.palette.invalid_zone_border()
+        } else {
+            settings.palette.zone_color(zone.zone_type).with_alpha(0.9)
+        };
+
+        for tile in &zone.tiles {
+            let center = grid_to_world(
+                *tile,
+                grid_settings.tile_size,
+                grid_settings.width,
+                grid_settings.height,
+            );
+            for (offset, start, end) in edges {
+                if !zone.tiles.contains(&(*tile + offset)) {
+                    gizmos.line_2d(center + start, center + end, color);
+                }
+            }
+        }
+    }
+}
+
+fn setup_zone_legend(mut commands: Commands, settings: Res<GameSettings>) {
+    let palette = settings.palette;
+    // Bottom-left corner, above the toolbar
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                bottom: Val::Px(90.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(3.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.85)),
+            ZoneLegend,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("zone_overlay.title"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            for zone_type in ZoneType::all() {
+                spawn_legend_row(
+                    parent,
+                    palette.zone_color(zone_type).with_alpha(0.6),
+                    None,
+                    LocalizedText::term("zone_type", zone_type.name()),
+                    Some(PaletteSwatch::Zone(zone_type)),
+                );
+            }
+
+            // Tint strength by quality, shown on a neutral swatch
+            for quality in [
+                ZoneQuality::Basic,
+                ZoneQuality::Good,
+                ZoneQuality::Excellent,
+                ZoneQuality::Luxury,
+            ] {
+                spawn_legend_row(
+                    parent,
+                    Color::WHITE.with_alpha(quality.overlay_alpha()),
+                    None,
+                    LocalizedText::term("quality", quality.name()),
+                    None,
+                );
+            }
+            spawn_legend_row(
+                parent,
+                Color::NONE,
+                Some(palette.invalid_zone_border()),
+                LocalizedText::new("zone_overlay.invalid"),
+                Some(PaletteSwatch::InvalidZone),
+            );
+        });
+}
+
+fn spawn_legend_row(
+    parent: &mut ChildBuilder,
+    fill: Color,
+    border: Option<Color>,
+    label: LocalizedText,
+    palette_swatch: Option<PaletteSwatch>,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..default()
+        })
+        .with_children(|row| {
+            let mut swatch = row.spawn((
+                Node {
+                    width: Val::Px(12.0),
+                    height: Val::Px(12.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(fill),
+                BorderColor(border.unwrap_or(Color::NONE)),
+            ));
+            if let Some(palette_swatch) = palette_swatch {
+                swatch.insert(palette_swatch);
+            }
+            row.spawn((
+                Text::default(),
+                label,
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            ));
+        });
+}
+
+fn apply_zone_legend_visibility(
+    overlay_settings: Res<ZoneOverlaySettings>,
+    mut legend_query: Query<&mut Node, With<ZoneLegend>>,
+) {
+    if !overlay_settings.is_changed() {
+        return;
+    }
+
+    if let Ok(mut node) = legend_query.get_single_mut() {
+        node.display = if overlay_settings.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn recolor_zone_legend(
+    settings: Res<GameSettings>,
+    mut swatch_query: Query<(&PaletteSwatch, &mut BackgroundColor, &mut BorderColor)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (swatch, mut fill, mut border) in &mut swatch_query {
+        match swatch {
+            PaletteSwatch::Zone(zone_type) => {
+                fill.0 = settings.palette.zone_color(*zone_type).with_alpha(0.6);
+            }
+            PaletteSwatch::InvalidZone => border.0 = settings.palette.invalid_zone_border(),
+        }
+    }
+}
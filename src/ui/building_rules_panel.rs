@@ -0,0 +1,234 @@
+use super::localization::LocalizedText;
+use super::UiInputBlocker;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::factories::BuildingRules;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct BuildingRulesPanel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildingRuleOption {
+    WallsRequireFloor,
+    FurnitureRequiresRoom,
+    OpeningsRequireWall,
+    ProtectGuestRooms,
+    Sandbox,
+}
+
+impl BuildingRuleOption {
+    /// Localization key for the rule's button label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            BuildingRuleOption::WallsRequireFloor => "building_rules.walls_need_floor",
+            BuildingRuleOption::FurnitureRequiresRoom => "building_rules.furniture_needs_room",
+            BuildingRuleOption::OpeningsRequireWall => "building_rules.openings_need_walls",
+            BuildingRuleOption::ProtectGuestRooms => "building_rules.protect_guest_rooms",
+            BuildingRuleOption::Sandbox => "building_rules.sandbox",
+        }
+    }
+
+    fn is_enabled(&self, rules: &BuildingRules) -> bool {
+        match self {
+            BuildingRuleOption::WallsRequireFloor => rules.walls_require_floor,
+            BuildingRuleOption::FurnitureRequiresRoom => rules.furniture_requires_room,
+            BuildingRuleOption::OpeningsRequireWall => rules.openings_require_wall,
+            BuildingRuleOption::ProtectGuestRooms => rules.protect_guest_rooms,
+            BuildingRuleOption::Sandbox => rules.sandbox,
+        }
+    }
+
+    fn toggle(&self, rules: &mut BuildingRules) {
+        match self {
+            BuildingRuleOption::WallsRequireFloor => {
+                rules.walls_require_floor = !rules.walls_require_floor
+            }
+            BuildingRuleOption::FurnitureRequiresRoom => {
+                rules.furniture_requires_room = !rules.furniture_requires_room
+            }
+            BuildingRuleOption::OpeningsRequireWall => {
+                rules.openings_require_wall = !rules.openings_require_wall
+            }
+            BuildingRuleOption::ProtectGuestRooms => {
+                rules.protect_guest_rooms = !rules.protect_guest_rooms
+            }
+            BuildingRuleOption::Sandbox => rules.sandbox = !rules.sandbox,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct BuildingRuleButton {
+    pub option: BuildingRuleOption,
+}
+
+#[derive(Resource, Default)]
+pub struct BuildingRulesPanelState {
+    pub visible: bool,
+}
+
+pub struct BuildingRulesPanelPlugin;
+
+impl Plugin for BuildingRulesPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BuildingRulesPanelState>()
+            .init_resource::<BuildingRules>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_building_rules_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_building_rules_toggle,
+                    apply_building_rules_panel_visibility,
+                    handle_rule_button_clicks,
+                    update_rule_button_colors,
+                    block_map_input_over_building_rules,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_building_rules_panel(mut commands: Commands) {
+    // Initially hidden panel in the bottom-left corner, above the toolbar
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                bottom: Val::Px(90.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(5.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BuildingRulesPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("building_rules.title"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            spawn_rule_button(parent, BuildingRuleOption::WallsRequireFloor);
+            spawn_rule_button(parent, BuildingRuleOption::FurnitureRequiresRoom);
+            spawn_rule_button(parent, BuildingRuleOption::OpeningsRequireWall);
+            spawn_rule_button(parent, BuildingRuleOption::ProtectGuestRooms);
+            spawn_rule_button(parent, BuildingRuleOption::Sandbox);
+        });
+}
+
+fn spawn_rule_button(parent: &mut ChildBuilder, option: BuildingRuleOption) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(200.0),
+                height: Val::Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            BuildingRuleButton { option },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(option.label_key()),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_building_rules_toggle(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel_state: ResMut<BuildingRulesPanelState>,
+    ui_blocker: Res<UiInputBlocker>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::KeyB) {
+        panel_state.visible = !panel_state.visible;
+    }
+}
+
+fn apply_building_rules_panel_visibility(
+    panel_state: Res<BuildingRulesPanelState>,
+    mut panel_query: Query<&mut Node, With<BuildingRulesPanel>>,
+) {
+    if !panel_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        style.display = if panel_state.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn handle_rule_button_clicks(
+    interaction_query: Query<(&Interaction, &BuildingRuleButton), Changed<Interaction>>,
+    mut rules: ResMut<BuildingRules>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            button.option.toggle(&mut rules);
+        }
+    }
+}
+
+fn update_rule_button_colors(
+    mut button_query: Query<(&BuildingRuleButton, &mut BackgroundColor, &Interaction)>,
+    rules: Res<BuildingRules>,
+) {
+    for (button, mut color, interaction) in &mut button_query {
+        // Rules greyed out by sandbox mode still show their setting, just dimmer
+        let overridden = rules.sandbox && button.option != BuildingRuleOption::Sandbox;
+
+        if button.option.is_enabled(&rules) {
+            *color = if overridden {
+                Color::srgb(0.3, 0.4, 0.3).into()
+            } else {
+                Color::srgb(0.4, 0.6, 0.4).into() // Green when enabled
+            };
+        } else {
+            match interaction {
+                Interaction::Hovered => {
+                    *color = Color::srgb(0.35, 0.35, 0.35).into();
+                }
+                _ => {
+                    *color = Color::srgb(0.25, 0.25, 0.25).into();
+                }
+            }
+        }
+    }
+}
+
+fn block_map_input_over_building_rules(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    panel_state: Res<BuildingRulesPanelState>,
+    interaction_query: Query<&Interaction, With<BuildingRuleButton>>,
+) {
+    let should_block = panel_state.visible
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.building_rules_blocking != should_block {
+        ui_blocker.building_rules_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
@@ -0,0 +1,166 @@
+use super::localization::Localization;
+use crate::systems::{
+    next_shuttle_hour, ArrivalForecast, ArrivalPoints, CalendarEvent, GameClock, GameplaySet,
+    GuestDemand, WeatherState,
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct CalendarDisplay;
+
+/// The line under the date listing the event that's on, or the next one coming up
+#[derive(Component)]
+pub struct CalendarSchedule;
+
+pub struct CalendarDisplayPlugin;
+
+impl Plugin for CalendarDisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_calendar_display)
+            .add_systems(Update, update_calendar_display.in_set(GameplaySet));
+    }
+}
+
+fn setup_calendar_display(mut commands: Commands) {
+    // Full-width row so the calendar panel sits centered at the top of the screen
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::axes(Val::Px(14.0), Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+                    CalendarDisplay,
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    ));
+                    panel.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.75, 0.4)),
+                        CalendarSchedule,
+                    ));
+                });
+        });
+}
+
+fn update_calendar_display(
+    clock: Res<GameClock>,
+    weather: Res<WeatherState>,
+    demand: Res<GuestDemand>,
+    arrival_points: Res<ArrivalPoints>,
+    forecast: Res<ArrivalForecast>,
+    query: Query<&Children, With<CalendarDisplay>>,
+    mut text_query: Query<&mut Text, Without<CalendarSchedule>>,
+    mut schedule_query: Query<&mut Text, With<CalendarSchedule>>,
+    localization: Res<Localization>,
+) {
+    let shuttle = if arrival_points.is_empty() {
+        localization.t("calendar.no_shuttle_stop").to_string()
+    } else {
+        localization.format(
+            "calendar.next_shuttle",
+            &[(
+                "time",
+                format!("{:02}:00", next_shuttle_hour(clock.hour()) as u32),
+            )],
+        )
+    };
+    let mut arrivals = localization.format(
+        if forecast.today {
+            "calendar.arrivals_today"
+        } else {
+            "calendar.arrivals_tomorrow"
+        },
+        &[("parties", format!("{:.0}", forecast.parties))],
+    );
+    if forecast.dirty_rooms > 0 {
+        let dirty = localization.format(
+            "calendar.dirty_rooms",
+            &[("rooms", forecast.dirty_rooms.to_string())],
+        );
+        arrivals = format!("{} ({})", arrivals, dirty);
+    }
+    let label = localization.format(
+        "calendar.status",
+        &[
+            ("day", clock.day().to_string()),
+            ("season", localization.term("season", clock.season().name()).to_string()),
+            ("time", clock.display_time()),
+            ("weather", localization.term("weather", weather.current.name()).to_string()),
+            ("demand", format!("{:.0}", demand.arrival_multiplier() * 100.0)),
+            ("shuttle", shuttle),
+            ("arrivals", arrivals),
+        ],
+    );
+
+    for children in &query {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                if **text != label {
+                    **text = label.clone();
+                }
+            }
+        }
+    }
+
+    let day = clock.day();
+    let next = CalendarEvent::next_after(day).map(|(event, start)| {
+        let name = localization.term("event", event.name()).to_string();
+        localization.format(
+            "calendar.next_event",
+            &[
+                ("event", name),
+                ("day", start.to_string()),
+                ("days", (start - day).to_string()),
+            ],
+        )
+    });
+    let schedule = match CalendarEvent::on_day(day) {
+        Some(event) => {
+            let name = localization.term("event", event.name()).to_string();
+            let current = localization.format(
+                "calendar.event_on",
+                &[
+                    ("event", name),
+                    ("day", event.last_day_from(day).to_string()),
+                    (
+                        "demand",
+                        format!("{:.0}", (event.demand_multiplier() - 1.0) * 100.0),
+                    ),
+                ],
+            );
+            match next {
+                Some(next) => format!("{}  |  {}", current, next),
+                None => current,
+            }
+        }
+        None => next.unwrap_or_default(),
+    };
+    if let Ok(mut text) = schedule_query.get_single_mut() {
+        if **text != schedule {
+            **text = schedule;
+        }
+    }
+}
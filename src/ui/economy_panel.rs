@@ -0,0 +1,817 @@
+use super::localization::{LocalizedText, Localization};
+use super::UiInputBlocker;
+use crate::components::ZoneQuality;
+use crate::systems::{
+    FinanceLedger, GameplaySet, GuestDemand, GuestLedger, Marketing, MarketingCampaign, Money,
+    PestControlContract, RoomRates, StaffWages,
+};
+use bevy::prelude::*;
+
+const PANEL_WIDTH: f32 = 420.0;
+const RATE_STEP: i32 = 10;
+const WAGE_STEP: i32 = 10;
+/// Most recent finance ledger entries listed in the panel
+const LEDGER_LINES: usize = 5;
+
+#[derive(Component)]
+pub struct EconomyPanel;
+
+#[derive(Component)]
+pub struct RoomRateButton {
+    pub quality: ZoneQuality,
+    pub delta: i32,
+}
+
+#[derive(Component)]
+pub struct RoomRateText {
+    pub quality: ZoneQuality,
+}
+
+#[derive(Component)]
+pub struct OccupancyText {
+    pub quality: ZoneQuality,
+}
+
+#[derive(Component)]
+pub struct StaffWageButton {
+    pub delta: i32,
+}
+
+#[derive(Component)]
+pub struct StaffWageText;
+
+/// How the wage compares with the going rate
+#[derive(Component)]
+pub struct StaffWageHintText;
+
+#[derive(Component)]
+pub struct MarketingButton {
+    pub campaign: MarketingCampaign,
+}
+
+/// Label on a marketing button, rewritten when the language changes
+#[derive(Component)]
+pub struct MarketingButtonLabel {
+    pub campaign: MarketingCampaign,
+}
+
+#[derive(Component)]
+pub struct ActiveCampaignsText;
+
+#[derive(Component)]
+pub struct PestControlButton;
+
+#[derive(Component)]
+pub struct PestControlButtonLabel;
+
+/// How long the pest-control contract has left
+#[derive(Component)]
+pub struct PestControlText;
+
+#[derive(Component)]
+pub struct GuestSummaryText;
+
+#[derive(Component)]
+pub struct FinanceLedgerText;
+
+#[derive(Resource, Default)]
+pub struct EconomyPanelState {
+    pub visible: bool,
+}
+
+pub struct EconomyPanelPlugin;
+
+impl Plugin for EconomyPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EconomyPanelState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_economy_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_economy_panel_toggle,
+                    apply_economy_panel_visibility,
+                    handle_rate_button_clicks,
+                    handle_wage_button_clicks,
+                    handle_marketing_button_clicks,
+                    handle_pest_control_button_clicks,
+                    update_economy_panel_text,
+                    update_pest_control_text,
+                    update_staff_wage_text,
+                    update_guest_summary_text,
+                    update_finance_ledger_text,
+                    update_economy_panel_button_colors,
+                    block_map_input_over_economy_panel,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_economy_panel(mut commands: Commands) {
+    // Initially hidden panel below the money display
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(60.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            EconomyPanel,
+        ))
+        .with_children(|parent| {
+            spawn_heading(parent, "economy.nightly_rates", 20.0);
+
+            for quality in ZoneQuality::rentable() {
+                spawn_rate_row(parent, quality);
+            }
+
+            spawn_heading(parent, "economy.staff_wages", 20.0);
+            spawn_wage_row(parent);
+
+            spawn_heading(parent, "economy.marketing", 20.0);
+
+            for campaign in MarketingCampaign::all() {
+                spawn_marketing_button(parent, campaign);
+            }
+
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ActiveCampaignsText,
+            ));
+
+            spawn_heading(parent, "economy.pest_control", 20.0);
+            spawn_pest_control_button(parent);
+
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                PestControlText,
+            ));
+
+            spawn_heading(parent, "economy.guests", 20.0);
+
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                GuestSummaryText,
+            ));
+
+            spawn_heading(parent, "economy.transactions", 20.0);
+
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                FinanceLedgerText,
+            ));
+        });
+}
+
+fn spawn_heading(parent: &mut ChildBuilder, label_key: &str, font_size: f32) {
+    parent.spawn((
+        Text::default(),
+        LocalizedText::new(label_key),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    ));
+}
+
+fn spawn_rate_row(parent: &mut ChildBuilder, quality: ZoneQuality) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Node {
+                    width: Val::Px(90.0),
+                    ..default()
+                },
+                Text::default(),
+                LocalizedText::term("quality", quality.name()),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            spawn_step_button(
+                row,
+                RoomRateButton {
+                    quality,
+                    delta: -RATE_STEP,
+                },
+                "-",
+            );
+
+            row.spawn((
+                Node {
+                    width: Val::Px(60.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.2, 0.8, 0.2)),
+                RoomRateText { quality },
+            ));
+
+            spawn_step_button(
+                row,
+                RoomRateButton {
+                    quality,
+                    delta: RATE_STEP,
+                },
+                "+",
+            );
+
+            row.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                OccupancyText { quality },
+            ));
+        });
+}
+
+fn spawn_wage_row(parent: &mut ChildBuilder) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Node {
+                    width: Val::Px(90.0),
+                    ..default()
+                },
+                Text::default(),
+                LocalizedText::new("economy.daily_wage"),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            spawn_step_button(row, StaffWageButton { delta: -WAGE_STEP }, "-");
+
+            row.spawn((
+                Node {
+                    width: Val::Px(60.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.2, 0.8, 0.2)),
+                StaffWageText,
+            ));
+
+            spawn_step_button(row, StaffWageButton { delta: WAGE_STEP }, "+");
+
+            row.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                StaffWageHintText,
+            ));
+        });
+}
+
+/// Small square -/+ button for stepping a price up or down
+fn spawn_step_button(parent: &mut ChildBuilder, button: impl Component, label: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(28.0),
+                height: Val::Px(28.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_marketing_button(parent: &mut ChildBuilder, campaign: MarketingCampaign) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(32.0),
+                padding: UiRect::horizontal(Val::Px(8.0)),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            MarketingButton { campaign },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                MarketingButtonLabel { campaign },
+            ));
+        });
+}
+
+fn spawn_pest_control_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(32.0),
+                padding: UiRect::horizontal(Val::Px(8.0)),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            PestControlButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                PestControlButtonLabel,
+            ));
+        });
+}
+
+fn marketing_button_label(campaign: MarketingCampaign, localization: &Localization) -> String {
+    localization.format(
+        "economy.campaign_button",
+        &[
+            ("campaign", localization.term("campaign", campaign.name()).to_string()),
+            ("cost", campaign.cost().to_string()),
+            ("boost", format!("{:.0}", campaign.arrival_boost() * 100.0)),
+            ("days", format!("{:.0}", campaign.duration_hours() / 24.0)),
+        ],
+    )
+}
+
+fn handle_economy_panel_toggle(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel_state: ResMut<EconomyPanelState>,
+    ui_blocker: Res<UiInputBlocker>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::KeyE) {
+        panel_state.visible = !panel_state.visible;
+    }
+}
+
+fn apply_economy_panel_visibility(
+    panel_state: Res<EconomyPanelState>,
+    mut panel_query: Query<&mut Node, With<EconomyPanel>>,
+) {
+    if !panel_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        style.display = if panel_state.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn handle_rate_button_clicks(
+    interaction_query: Query<(&Interaction, &RoomRateButton), Changed<Interaction>>,
+    mut rates: ResMut<RoomRates>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            rates.adjust(button.quality, button.delta);
+        }
+    }
+}
+
+fn handle_wage_button_clicks(
+    interaction_query: Query<(&Interaction, &StaffWageButton), Changed<Interaction>>,
+    mut wages: ResMut<StaffWages>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            wages.adjust(button.delta);
+        }
+    }
+}
+
+fn handle_marketing_button_clicks(
+    interaction_query: Query<(&Interaction, &MarketingButton), Changed<Interaction>>,
+    mut marketing: ResMut<Marketing>,
+    mut money: ResMut<Money>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            if marketing.launch(button.campaign, &mut money) {
+                info!("Launched {} campaign", button.campaign.name());
+            } else {
+                info!("Not enough money for {} campaign", button.campaign.name());
+            }
+        }
+    }
+}
+
+fn handle_pest_control_button_clicks(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PestControlButton>)>,
+    mut contract: ResMut<PestControlContract>,
+    mut money: ResMut<Money>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            if contract.sign(&mut money) {
+                info!("Signed a pest-control contract");
+            } else {
+                info!("Not enough money for a pest-control contract");
+            }
+        }
+    }
+}
+
+fn update_economy_panel_text(
+    panel_state: Res<EconomyPanelState>,
+    rates: Res<RoomRates>,
+    demand: Res<GuestDemand>,
+    marketing: Res<Marketing>,
+    mut rate_query: Query<
+        (&RoomRateText, &mut Text),
+        (Without<OccupancyText>, Without<ActiveCampaignsText>),
+    >,
+    mut occupancy_query: Query<
+        (&OccupancyText, &mut Text, &mut TextColor),
+        Without<ActiveCampaignsText>,
+    >,
+    mut campaigns_query: Query<
+        &mut Text,
+        (With<ActiveCampaignsText>, Without<MarketingButtonLabel>),
+    >,
+    mut button_label_query: Query<
+        (&MarketingButtonLabel, &mut Text),
+        (Without<RoomRateText>, Without<OccupancyText>),
+    >,
+    localization: Res<Localization>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    if !(panel_state.is_changed()
+        || rates.is_changed()
+        || demand.is_changed()
+        || marketing.is_changed()
+        || localization.is_changed())
+    {
+        return;
+    }
+
+    for (label, mut text) in &mut button_label_query {
+        **text = marketing_button_label(label.campaign, &localization);
+    }
+
+    for (rate_text, mut text) in &mut rate_query {
+        **text = format!("${}", rates.rate(rate_text.quality));
+    }
+
+    for (occupancy_text, mut text, mut color) in &mut occupancy_query {
+        let quality = occupancy_text.quality;
+        let occupancy = demand.expected_occupancy(quality, &rates);
+        **text = localization.format(
+            "economy.occupancy",
+            &[
+                ("fair", quality.fair_nightly_rate().to_string()),
+                ("booked", format!("{:.0}", occupancy * 100.0)),
+            ],
+        );
+
+        // Warn when the asking price is scaring guests away
+        color.0 = if rates.rate(quality) > quality.fair_nightly_rate() * 3 / 2 {
+            Color::srgb(0.9, 0.4, 0.3)
+        } else {
+            Color::srgb(0.7, 0.7, 0.7)
+        };
+    }
+
+    if let Ok(mut text) = campaigns_query.get_single_mut() {
+        **text = if marketing.active.is_empty() {
+            localization.t("economy.no_campaigns").to_string()
+        } else {
+            marketing
+                .active
+                .iter()
+                .map(|active| {
+                    localization.format(
+                        "economy.campaign_active",
+                        &[
+                            (
+                                "campaign",
+                                localization.term("campaign", active.campaign.name()).to_string(),
+                            ),
+                            ("hours", format!("{:.0}", active.hours_remaining.ceil())),
+                        ],
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+    }
+}
+
+fn update_pest_control_text(
+    panel_state: Res<EconomyPanelState>,
+    contract: Res<PestControlContract>,
+    mut label_query: Query<&mut Text, (With<PestControlButtonLabel>, Without<PestControlText>)>,
+    mut status_query: Query<&mut Text, With<PestControlText>>,
+    localization: Res<Localization>,
+) {
+    if !panel_state.visible
+        || !(panel_state.is_changed() || contract.is_changed() || localization.is_changed())
+    {
+        return;
+    }
+
+    if let Ok(mut text) = label_query.get_single_mut() {
+        **text = localization.format(
+            "economy.pest_contract_button",
+            &[
+                ("cost", PestControlContract::COST.to_string()),
+                ("reduction", format!("{:.0}", contract.reduction() * 100.0)),
+                (
+                    "days",
+                    format!("{:.0}", PestControlContract::DURATION_HOURS / 24.0),
+                ),
+            ],
+        );
+    }
+
+    if let Ok(mut text) = status_query.get_single_mut() {
+        **text = if contract.is_active() {
+            localization.format(
+                "economy.pest_contract_active",
+                &[("hours", format!("{:.0}", contract.hours_remaining.ceil()))],
+            )
+        } else {
+            localization.t("economy.no_pest_contract").to_string()
+        };
+    }
+}
+
+fn update_staff_wage_text(
+    wages: Res<StaffWages>,
+    mut wage_query: Query<&mut Text, (With<StaffWageText>, Without<StaffWageHintText>)>,
+    mut hint_query: Query<(&mut Text, &mut TextColor), With<StaffWageHintText>>,
+    localization: Res<Localization>,
+) {
+    if !(wages.is_changed() || localization.is_changed()) {
+        return;
+    }
+
+    if let Ok(mut text) = wage_query.get_single_mut() {
+        **text = format!("${}", wages.daily_wage);
+    }
+
+    if let Ok((mut text, mut color)) = hint_query.get_single_mut() {
+        **text = localization.format(
+            "economy.fair_wage",
+            &[("fair", StaffWages::FAIR_WAGE.to_string())],
+        );
+        // Warn when pay is low enough to wear staff down
+        color.0 = if wages.daily_wage < StaffWages::FAIR_WAGE {
+            Color::srgb(0.9, 0.4, 0.3)
+        } else {
+            Color::srgb(0.7, 0.7, 0.7)
+        };
+    }
+}
+
+fn update_guest_summary_text(
+    panel_state: Res<EconomyPanelState>,
+    ledger: Res<GuestLedger>,
+    mut text_query: Query<&mut Text, With<GuestSummaryText>>,
+    localization: Res<Localization>,
+) {
+    if !panel_state.visible
+        || !(panel_state.is_changed() || ledger.is_changed() || localization.is_changed())
+    {
+        return;
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        **text = format!(
+            "{}\n{}",
+            localization.format(
+                "economy.guest_summary",
+                &[
+                    ("parties", ledger.parties_staying.to_string()),
+                    ("guests", ledger.guests_staying.to_string()),
+                    ("checked_in", ledger.checked_in_today.to_string()),
+                    ("income", ledger.income_today.to_string()),
+                ],
+            ),
+            ledger.turned_away_summary(&localization)
+        );
+    }
+}
+
+fn update_finance_ledger_text(
+    panel_state: Res<EconomyPanelState>,
+    finance: Res<FinanceLedger>,
+    mut text_query: Query<&mut Text, With<FinanceLedgerText>>,
+    localization: Res<Localization>,
+) {
+    if !panel_state.visible
+        || !(panel_state.is_changed() || finance.is_changed() || localization.is_changed())
+    {
+        return;
+    }
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+    if finance.entries.is_empty() {
+        **text = localization.t("economy.no_transactions").to_string();
+        return;
+    }
+
+    **text = finance
+        .entries
+        .iter()
+        .rev()
+        .take(LEDGER_LINES)
+        .map(|entry| {
+            if entry.lost {
+                return localization.format(
+                    "economy.transaction_lost",
+                    &[
+                        ("day", entry.day.to_string()),
+                        ("description", entry.description.clone()),
+                        ("amount", entry.amount.to_string()),
+                    ],
+                );
+            }
+            let amount = if entry.amount < 0 {
+                format!("-${}", -entry.amount)
+            } else {
+                format!("+${}", entry.amount)
+            };
+            localization.format(
+                "economy.transaction",
+                &[
+                    ("day", entry.day.to_string()),
+                    ("description", entry.description.clone()),
+                    ("amount", amount),
+                ],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+fn update_economy_panel_button_colors(
+    mut rate_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        Or<(With<RoomRateButton>, With<StaffWageButton>)>,
+    >,
+    mut marketing_query: Query<
+        (&MarketingButton, &mut BackgroundColor, &Interaction),
+        (Without<RoomRateButton>, Without<StaffWageButton>),
+    >,
+    mut pest_control_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        (
+            With<PestControlButton>,
+            Without<RoomRateButton>,
+            Without<StaffWageButton>,
+            Without<MarketingButton>,
+        ),
+    >,
+    money: Res<Money>,
+) {
+    for (mut color, interaction) in &mut rate_query {
+        match interaction {
+            Interaction::Hovered => {
+                *color = Color::srgb(0.35, 0.35, 0.35).into();
+            }
+            _ => {
+                *color = Color::srgb(0.25, 0.25, 0.25).into();
+            }
+        }
+    }
+
+    for (button, mut color, interaction) in &mut marketing_query {
+        if !money.can_afford(button.campaign.cost()) {
+            *color = Color::srgb(0.35, 0.2, 0.2).into(); // Dim red when unaffordable
+        } else {
+            match interaction {
+                Interaction::Hovered => {
+                    *color = Color::srgb(0.35, 0.35, 0.35).into();
+                }
+                _ => {
+                    *color = Color::srgb(0.25, 0.25, 0.25).into();
+                }
+            }
+        }
+    }
+
+    for (mut color, interaction) in &mut pest_control_query {
+        if !money.can_afford(PestControlContract::COST) {
+            *color = Color::srgb(0.35, 0.2, 0.2).into();
+        } else if *interaction == Interaction::Hovered {
+            *color = Color::srgb(0.35, 0.35, 0.35).into();
+        } else {
+            *color = Color::srgb(0.25, 0.25, 0.25).into();
+        }
+    }
+}
+
+fn block_map_input_over_economy_panel(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    panel_state: Res<EconomyPanelState>,
+    interaction_query: Query<
+        &Interaction,
+        Or<(
+            With<RoomRateButton>,
+            With<StaffWageButton>,
+            With<MarketingButton>,
+            With<PestControlButton>,
+        )>,
+    >,
+) {
+    let should_block = panel_state.visible
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.economy_panel_blocking != should_block {
+        ui_blocker.economy_panel_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
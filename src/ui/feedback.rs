@@ -0,0 +1,213 @@
+use super::localization::Localization;
+use super::MoneyDisplay;
+use crate::systems::{CursorWorldPosition, GameplaySet};
+use bevy::prelude::*;
+
+const FLOATING_TEXT_SECONDS: f32 = 1.2;
+const FLOATING_TEXT_RISE: f32 = 30.0;
+const FUNDS_FLASH_SECONDS: f32 = 0.6;
+const FUNDS_SHAKE_PIXELS: f32 = 6.0;
+const TOOLTIP_SECONDS: f32 = 2.0;
+
+const MONEY_DISPLAY_LEFT: f32 = 10.0;
+const MONEY_DISPLAY_BACKGROUND: Color = Color::srgba(0.1, 0.1, 0.1, 0.9);
+const INSUFFICIENT_FUNDS_COLOR: Color = Color::srgba(0.7, 0.1, 0.1, 0.95);
+
+/// Something the player should see happen to their money
+#[derive(Event, Debug, Clone, Copy)]
+pub enum UiFeedbackEvent {
+    /// Money was spent on something placed at this world position
+    Spent { amount: i32, world_pos: Vec2 },
+    /// Money came in from something at this world position, e.g. a guest checking out
+    Earned { amount: i32, world_pos: Vec2 },
+    /// A purchase at this world position was refused for lack of money
+    InsufficientFunds { cost: i32, world_pos: Vec2 },
+}
+
+#[derive(Component)]
+pub struct FloatingMoneyText {
+    pub start: Vec2,
+    pub elapsed: f32,
+}
+
+#[derive(Component)]
+pub struct FeedbackTooltip;
+
+#[derive(Resource, Default)]
+struct FeedbackTimers {
+    funds_flash: f32,
+    tooltip: f32,
+}
+
+pub struct UiFeedbackPlugin;
+
+impl Plugin for UiFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<UiFeedbackEvent>()
+            .init_resource::<FeedbackTimers>()
+            .add_systems(Startup, setup_feedback_tooltip)
+            .add_systems(
+                Update,
+                (
+                    handle_feedback_events,
+                    animate_floating_money_text,
+                    animate_money_display_flash,
+                    update_feedback_tooltip,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_feedback_tooltip(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(INSUFFICIENT_FUNDS_COLOR),
+        Visibility::Hidden,
+        FeedbackTooltip,
+    ));
+}
+
+fn handle_feedback_events(
+    mut commands: Commands,
+    mut events: EventReader<UiFeedbackEvent>,
+    mut timers: ResMut<FeedbackTimers>,
+    cursor: Res<CursorWorldPosition>,
+    mut tooltip_query: Query<(&mut Text, &mut Node), With<FeedbackTooltip>>,
+    localization: Res<Localization>,
+) {
+    for event in events.read() {
+        match *event {
+            UiFeedbackEvent::Spent { amount, world_pos } => {
+                commands.spawn((
+                    Text2d::new(format!("-${}", amount)),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                    Transform::from_translation(world_pos.extend(50.0)),
+                    FloatingMoneyText {
+                        start: world_pos,
+                        elapsed: 0.0,
+                    },
+                ));
+            }
+            UiFeedbackEvent::Earned { amount, world_pos } => {
+                commands.spawn((
+                    Text2d::new(format!("+${}", amount)),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.4, 1.0, 0.4)),
+                    Transform::from_translation(world_pos.extend(50.0)),
+                    FloatingMoneyText {
+                        start: world_pos,
+                        elapsed: 0.0,
+                    },
+                ));
+            }
+            UiFeedbackEvent::InsufficientFunds { cost, world_pos } => {
+                commands.spawn((
+                    Text2d::new(format!("${}", cost)),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.3, 0.3)),
+                    Transform::from_translation(world_pos.extend(50.0)),
+                    FloatingMoneyText {
+                        start: world_pos,
+                        elapsed: 0.0,
+                    },
+                ));
+
+                timers.funds_flash = FUNDS_FLASH_SECONDS;
+                timers.tooltip = TOOLTIP_SECONDS;
+
+                // Tooltip sits next to the cursor that made the failed click
+                if let Ok((mut text, mut node)) = tooltip_query.get_single_mut() {
+                    **text = localization
+                        .format("feedback.not_enough_money", &[("cost", cost.to_string())]);
+                    if let Some(cursor) = cursor.ui {
+                        node.left = Val::Px(cursor.x + 16.0);
+                        node.top = Val::Px(cursor.y - 32.0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Amounts drift upwards and fade out, then remove themselves
+fn animate_floating_money_text(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut text_query: Query<(
+        Entity,
+        &mut FloatingMoneyText,
+        &mut Transform,
+        &mut TextColor,
+    )>,
+) {
+    for (entity, mut floating, mut transform, mut color) in &mut text_query {
+        floating.elapsed += time.delta_secs();
+        let progress = floating.elapsed / FLOATING_TEXT_SECONDS;
+
+        if progress >= 1.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.y = floating.start.y + FLOATING_TEXT_RISE * progress;
+        color.0 = color.0.with_alpha(1.0 - progress);
+    }
+}
+
+// Flash the money display red and shake it sideways after a refused purchase
+fn animate_money_display_flash(
+    time: Res<Time<Real>>,
+    mut timers: ResMut<FeedbackTimers>,
+    mut display_query: Query<(&mut Node, &mut BackgroundColor), With<MoneyDisplay>>,
+) {
+    if timers.funds_flash <= 0.0 {
+        return;
+    }
+
+    timers.funds_flash = (timers.funds_flash - time.delta_secs()).max(0.0);
+    let strength = timers.funds_flash / FUNDS_FLASH_SECONDS;
+
+    for (mut node, mut background) in &mut display_query {
+        let shake = (time.elapsed_secs() * 60.0).sin() * FUNDS_SHAKE_PIXELS * strength;
+        node.left = Val::Px(MONEY_DISPLAY_LEFT + shake);
+        background.0 = MONEY_DISPLAY_BACKGROUND.mix(&INSUFFICIENT_FUNDS_COLOR, strength);
+    }
+}
+
+fn update_feedback_tooltip(
+    time: Res<Time<Real>>,
+    mut timers: ResMut<FeedbackTimers>,
+    mut tooltip_query: Query<&mut Visibility, With<FeedbackTooltip>>,
+) {
+    timers.tooltip = (timers.tooltip - time.delta_secs()).max(0.0);
+
+    if let Ok(mut visibility) = tooltip_query.get_single_mut() {
+        visibility.set_if_neq(if timers.tooltip > 0.0 {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
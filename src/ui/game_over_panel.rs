@@ -0,0 +1,246 @@
+use super::localization::{Localization, LocalizedText};
+use super::save_load_panel::SaveLoadPanelState;
+use super::UiInputBlocker;
+use crate::systems::{
+    resort_reputation, GameMode, GameRules, GameplaySet, GuestDemand, ScenarioGoals,
+    ScenarioOutcome, ScenarioState, SpeedOption, StatsHistory, TimeSpeed,
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct GameOverPanel;
+
+#[derive(Component)]
+struct GameOverTitle;
+
+#[derive(Component)]
+struct GameOverDetail;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum GameOverButton {
+    ContinueInSandbox,
+    LoadSave,
+}
+
+pub struct GameOverPanelPlugin;
+
+impl Plugin for GameOverPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_game_over_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_game_over_button_clicks,
+                    update_game_over_button_colors,
+                    update_game_over_text,
+                    apply_game_over_panel_visibility,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_game_over_panel(mut commands: Commands) {
+    // Full-screen backdrop like the new game screen, hidden until the game ends
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(10),
+            GameOverPanel,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(420.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("game_over.won.title"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        GameOverTitle,
+                    ));
+                    parent.spawn((
+                        Text::default(),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        GameOverDetail,
+                    ));
+
+                    spawn_game_over_button(
+                        parent,
+                        GameOverButton::ContinueInSandbox,
+                        "game_over.continue_sandbox",
+                    );
+                    spawn_game_over_button(
+                        parent,
+                        GameOverButton::LoadSave,
+                        "game_over.load_save",
+                    );
+                });
+        });
+}
+
+fn spawn_game_over_button(parent: &mut ChildBuilder, button: GameOverButton, label_key: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_game_over_button_clicks(
+    interaction_query: Query<(&Interaction, &GameOverButton), Changed<Interaction>>,
+    mut state: ResMut<ScenarioState>,
+    mut rules: ResMut<GameRules>,
+    mut time_speed: ResMut<TimeSpeed>,
+    mut save_load_state: ResMut<SaveLoadPanelState>,
+) {
+    if !state.is_over() {
+        return;
+    }
+
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            GameOverButton::ContinueInSandbox => {
+                rules.choose(GameMode::Sandbox);
+                state.outcome = ScenarioOutcome::Continued;
+                time_speed.set_speed(SpeedOption::Normal);
+                info!("Continuing the resort in sandbox mode");
+            }
+            // Loading a save resets the goals; closing the panel brings this screen back
+            GameOverButton::LoadSave => {
+                save_load_state.visible = true;
+            }
+        }
+    }
+}
+
+fn update_game_over_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<GameOverButton>>,
+) {
+    for (mut color, interaction) in &mut button_query {
+        match interaction {
+            Interaction::Hovered => {
+                *color = Color::srgb(0.35, 0.35, 0.35).into();
+            }
+            _ => {
+                *color = Color::srgb(0.25, 0.25, 0.25).into();
+            }
+        }
+    }
+}
+
+fn update_game_over_text(
+    state: Res<ScenarioState>,
+    goals: Res<ScenarioGoals>,
+    stats: Res<StatsHistory>,
+    demand: Res<GuestDemand>,
+    localization: Res<Localization>,
+    mut title_query: Query<&mut LocalizedText, With<GameOverTitle>>,
+    mut detail_query: Query<&mut Text, With<GameOverDetail>>,
+) {
+    if !state.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    let (title_key, detail) = match state.outcome {
+        ScenarioOutcome::Won => (
+            "game_over.won.title",
+            localization.format(
+                "game_over.won.detail",
+                &[("stars", format!("{:.1}", resort_reputation(&stats, &demand)))],
+            ),
+        ),
+        ScenarioOutcome::Bankrupt => (
+            "game_over.bankrupt.title",
+            localization.format(
+                "game_over.bankrupt.detail",
+                &[
+                    ("limit", goals.bankruptcy_limit.to_string()),
+                    ("days", goals.bankruptcy_days.to_string()),
+                ],
+            ),
+        ),
+        ScenarioOutcome::Playing | ScenarioOutcome::Continued => return,
+    };
+
+    if let Ok(mut title) = title_query.get_single_mut() {
+        if title.key != title_key {
+            title.key = title_key.to_string();
+        }
+    }
+    if let Ok(mut text) = detail_query.get_single_mut() {
+        text.0 = detail;
+    }
+}
+
+// Steps aside while the Save/Load panel is open so a save can be picked
+fn apply_game_over_panel_visibility(
+    state: Res<ScenarioState>,
+    save_load_state: Res<SaveLoadPanelState>,
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    mut panel_query: Query<&mut Node, With<GameOverPanel>>,
+) {
+    let visible = state.is_over() && !save_load_state.visible;
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        let display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    if ui_blocker.game_over_blocking != visible {
+        ui_blocker.game_over_blocking = visible;
+        ui_blocker.recompute();
+    }
+}
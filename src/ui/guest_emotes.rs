@@ -0,0 +1,173 @@
+use super::settings::GameSettings;
+use crate::components::*;
+use crate::systems::{GameplaySet, SatisfactionCause, SatisfactionEvent, TILE_SIZE};
+use bevy::prelude::*;
+
+const EMOTE_SECONDS: f32 = 2.0;
+const EMOTE_RISE: f32 = 24.0;
+/// How far above a guest's middle an emote starts
+const EMOTE_OFFSET: f32 = TILE_SIZE;
+/// A sleeping guest lets out another "z" this often
+const SNORE_SECONDS: f32 = 2.5;
+
+/// What a guest is feeling, shown as a small face above them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emote {
+    Heart,
+    Smile,
+    Frown,
+    Angry,
+    Sleep,
+}
+
+impl Emote {
+    pub fn for_cause(cause: SatisfactionCause) -> Self {
+        match cause {
+            SatisfactionCause::GreatRoom => Emote::Heart,
+            SatisfactionCause::RoomServiceDelivered => Emote::Smile,
+            SatisfactionCause::DisappointingRoom
+            | SatisfactionCause::Renovation
+            | SatisfactionCause::Pests(_)
+            | SatisfactionCause::ShabbyPublicSpace => Emote::Frown,
+            SatisfactionCause::Complaint(_)
+            | SatisfactionCause::ComplaintIgnored
+            | SatisfactionCause::RoomServiceLate => Emote::Angry,
+        }
+    }
+
+    // Plain text, since the default font has no picture glyphs
+    fn glyph(&self) -> &'static str {
+        match self {
+            Emote::Heart => "<3",
+            Emote::Smile => ":)",
+            Emote::Frown => ":(",
+            Emote::Angry => ">:(",
+            Emote::Sleep => "z",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Emote::Heart => Color::srgb(1.0, 0.4, 0.6),
+            Emote::Smile => Color::srgb(0.5, 1.0, 0.5),
+            Emote::Frown => Color::srgb(1.0, 0.75, 0.35),
+            Emote::Angry => Color::srgb(1.0, 0.3, 0.3),
+            Emote::Sleep => Color::srgb(0.7, 0.8, 1.0),
+        }
+    }
+}
+
+/// An emote drifting up from the guest it belongs to, as a child of them
+#[derive(Component)]
+pub struct FloatingEmote {
+    pub elapsed: f32,
+}
+
+pub struct GuestEmotesPlugin;
+
+impl Plugin for GuestEmotesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                show_satisfaction_emotes,
+                show_sleep_emotes,
+                animate_floating_emotes,
+            )
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+fn spawn_emote(commands: &mut Commands, guest: Entity, emote: Emote) {
+    let Some(mut guest_commands) = commands.get_entity(guest) else {
+        return;
+    };
+    guest_commands.with_children(|parent| {
+        parent.spawn((
+            Text2d::new(emote.glyph()),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(emote.color()),
+            Transform::from_xyz(0.0, EMOTE_OFFSET, 40.0),
+            FloatingEmote { elapsed: 0.0 },
+        ));
+    });
+}
+
+/// Each change in satisfaction shows above the guest it happened to, or above every
+/// guest of the party when it was the whole party's
+fn show_satisfaction_emotes(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    mut events: EventReader<SatisfactionEvent>,
+    guest_query: Query<(Entity, &Guest)>,
+) {
+    if !settings.guest_emotes {
+        events.clear();
+        return;
+    }
+
+    for event in events.read() {
+        let emote = Emote::for_cause(event.cause);
+        match event.guest {
+            Some(guest) => spawn_emote(&mut commands, guest, emote),
+            None => {
+                for (entity, _) in guest_query
+                    .iter()
+                    .filter(|(_, guest)| guest.room == event.room)
+                {
+                    spawn_emote(&mut commands, entity, emote);
+                }
+            }
+        }
+    }
+}
+
+fn show_sleep_emotes(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    guest_query: Query<(Entity, &Guest)>,
+    mut since_snore: Local<f32>,
+) {
+    *since_snore += time.delta_secs();
+    if *since_snore < SNORE_SECONDS {
+        return;
+    }
+    *since_snore = 0.0;
+
+    if !settings.guest_emotes {
+        return;
+    }
+    for (entity, guest) in &guest_query {
+        if guest.activity == GuestActivity::Sleeping {
+            spawn_emote(&mut commands, entity, Emote::Sleep);
+        }
+    }
+}
+
+// Emotes drift upwards and fade out, then remove themselves
+fn animate_floating_emotes(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    settings: Res<GameSettings>,
+    mut emote_query: Query<(Entity, &mut FloatingEmote, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut floating, mut transform, mut color) in &mut emote_query {
+        floating.elapsed += time.delta_secs();
+        let progress = floating.elapsed / EMOTE_SECONDS;
+
+        // Switching emotes off clears the ones already up
+        if progress >= 1.0 || !settings.guest_emotes {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation.y = EMOTE_OFFSET + EMOTE_RISE * progress;
+        color.0 = color.0.with_alpha(1.0 - progress);
+    }
+}
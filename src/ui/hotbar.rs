@@ -0,0 +1,303 @@
+use super::localization::LocalizedText;
+use super::save_load_panel::SaveLoadPanelState;
+use super::toolbar::{build_item_label, is_locked, TabSwitcher, PLANNING_COLOR, TOOLBAR_HEIGHT};
+use super::{BuildButton, BuildingType, ToolbarState, UiInputBlocker};
+use crate::components::FurnitureRegistry;
+use crate::systems::{GameClock, GameRules, GameplaySet};
+use bevy::prelude::*;
+
+pub const HOTBAR_SLOTS: usize = 9;
+const SLOT_SIZE: f32 = 52.0;
+
+/// Shift and a number key select the matching slot
+const SLOT_KEYS: [KeyCode; HOTBAR_SLOTS] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Build items pinned for quick selection, whatever tab they're under
+#[derive(Resource, Default)]
+pub struct Hotbar {
+    pub slots: [Option<BuildingType>; HOTBAR_SLOTS],
+}
+
+impl Hotbar {
+    /// Pins `build_type` to the first free slot, or unpins it if it's already there.
+    /// Does nothing when every slot is taken.
+    pub fn toggle_pin(&mut self, build_type: BuildingType) {
+        if let Some(slot) = self.slots.iter().position(|slot| *slot == Some(build_type)) {
+            self.slots[slot] = None;
+        } else if let Some(slot) = self.slots.iter().position(Option::is_none) {
+            self.slots[slot] = Some(build_type);
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HotbarPanel;
+
+#[derive(Component)]
+pub struct HotbarSlotButton {
+    pub slot: usize,
+}
+
+pub struct HotbarPlugin;
+
+impl Plugin for HotbarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Hotbar>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_hotbar)
+            .add_systems(
+                Update,
+                (
+                    pin_build_buttons,
+                    unpin_hotbar_slots,
+                    handle_hotbar_slot_clicks,
+                    handle_hotbar_hotkeys,
+                    update_hotbar_slots,
+                    update_hotbar_slot_colors,
+                    block_map_input_over_hotbar,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_hotbar(mut commands: Commands) {
+    // Full-width row so the slots sit centered just above the toolbar
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(TOOLBAR_HEIGHT + 5.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(4.0),
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                HotbarPanel,
+            ));
+        });
+}
+
+/// Right-clicking a build button on the toolbar pins it to the hotbar, or unpins it
+fn pin_build_buttons(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    button_query: Query<(&Interaction, &BuildButton)>,
+    mut hotbar: ResMut<Hotbar>,
+) {
+    if !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+
+    for (interaction, button) in &button_query {
+        if *interaction == Interaction::Hovered {
+            hotbar.toggle_pin(button.build_type);
+        }
+    }
+}
+
+/// Right-clicking a slot empties it
+fn unpin_hotbar_slots(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    slot_query: Query<(&Interaction, &HotbarSlotButton)>,
+    mut hotbar: ResMut<Hotbar>,
+) {
+    if !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+
+    for (interaction, slot_button) in &slot_query {
+        if *interaction == Interaction::Hovered && hotbar.slots[slot_button.slot].is_some() {
+            hotbar.slots[slot_button.slot] = None;
+        }
+    }
+}
+
+fn handle_hotbar_slot_clicks(
+    interaction_query: Query<(&Interaction, &HotbarSlotButton), Changed<Interaction>>,
+    hotbar: Res<Hotbar>,
+    rules: Res<GameRules>,
+    clock: Res<GameClock>,
+    mut tabs: TabSwitcher,
+) {
+    for (interaction, slot_button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            select_slot(slot_button.slot, &hotbar, &rules, &clock, &mut tabs);
+        }
+    }
+}
+
+fn handle_hotbar_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    save_load_state: Res<SaveLoadPanelState>,
+    hotbar: Res<Hotbar>,
+    rules: Res<GameRules>,
+    clock: Res<GameClock>,
+    mut tabs: TabSwitcher,
+) {
+    // The save name field takes number keys too
+    if ui_blocker.text_input_active || save_load_state.visible {
+        return;
+    }
+    if !keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        return;
+    }
+
+    for (slot, key) in SLOT_KEYS.into_iter().enumerate() {
+        if keyboard.just_pressed(key) {
+            select_slot(slot, &hotbar, &rules, &clock, &mut tabs);
+        }
+    }
+}
+
+/// Picks up what's pinned to `slot`, or puts it down if it's already selected. Locked
+/// items stay pinned but can't be selected.
+fn select_slot(
+    slot: usize,
+    hotbar: &Hotbar,
+    rules: &GameRules,
+    clock: &GameClock,
+    tabs: &mut TabSwitcher,
+) {
+    let Some(build_type) = hotbar.slots[slot] else {
+        return;
+    };
+    if is_locked(build_type, rules, clock) {
+        return;
+    }
+
+    tabs.toggle_building(build_type);
+}
+
+/// Respawns the slots whenever something is pinned or unpinned
+fn update_hotbar_slots(
+    mut commands: Commands,
+    hotbar: Res<Hotbar>,
+    furniture: Res<FurnitureRegistry>,
+    panel_query: Query<Entity, With<HotbarPanel>>,
+    children_query: Query<&Children>,
+) {
+    if !hotbar.is_changed() {
+        return;
+    }
+    let Ok(panel_entity) = panel_query.get_single() else {
+        return;
+    };
+
+    if let Ok(children) = children_query.get(panel_entity) {
+        for &child in children.iter() {
+            commands.entity(child).despawn_recursive();
+        }
+    }
+
+    commands.entity(panel_entity).with_children(|parent| {
+        for (slot, build_type) in hotbar.slots.iter().enumerate() {
+            let label = build_type.and_then(|build_type| build_item_label(build_type, &furniture));
+            spawn_slot(parent, slot, label);
+        }
+    });
+}
+
+fn spawn_slot(parent: &mut ChildBuilder, slot: usize, label: Option<LocalizedText>) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(SLOT_SIZE),
+                height: Val::Px(SLOT_SIZE),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+            HotbarSlotButton { slot },
+        ))
+        .with_children(|parent| {
+            if let Some(label) = label {
+                parent.spawn((
+                    Text::default(),
+                    label,
+                    TextFont {
+                        font_size: 11.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            }
+            // Number key that selects the slot along with Shift
+            parent.spawn((
+                Text::new((slot + 1).to_string()),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(2.0),
+                    left: Val::Px(4.0),
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn update_hotbar_slot_colors(
+    mut slot_query: Query<(&HotbarSlotButton, &Interaction, &mut BackgroundColor)>,
+    hotbar: Res<Hotbar>,
+    toolbar_state: Res<ToolbarState>,
+    rules: Res<GameRules>,
+    clock: Res<GameClock>,
+) {
+    for (slot_button, interaction, mut color) in &mut slot_query {
+        let pinned = hotbar.slots[slot_button.slot];
+        let target: BackgroundColor = match pinned {
+            Some(build_type) if toolbar_state.selected_building == Some(build_type) => {
+                if toolbar_state.planning {
+                    PLANNING_COLOR.into()
+                } else {
+                    Color::srgb(0.5, 0.7, 0.5).into() // Green when selected
+                }
+            }
+            Some(build_type) if is_locked(build_type, &rules, &clock) => {
+                Color::srgb(0.15, 0.15, 0.15).into() // Dark when locked
+            }
+            Some(_) if *interaction == Interaction::Hovered => Color::srgb(0.4, 0.4, 0.4).into(),
+            Some(_) => Color::srgb(0.3, 0.3, 0.3).into(),
+            None => Color::srgb(0.2, 0.2, 0.2).into(),
+        };
+        color.set_if_neq(target);
+    }
+}
+
+fn block_map_input_over_hotbar(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    interaction_query: Query<&Interaction, With<HotbarSlotButton>>,
+) {
+    let should_block = interaction_query
+        .iter()
+        .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.hotbar_blocking != should_block {
+        ui_blocker.hotbar_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
@@ -0,0 +1,497 @@
+use super::localization::Localization;
+use super::UiInputBlocker;
+use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::building::BuildingMap;
+use crate::systems::cursor::CursorWorldPosition;
+use crate::systems::grid::GridSettings;
+use crate::systems::room_detection::RoomAdjacency;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, Window as BevyWindow};
+
+/// How long the cursor has to rest on a tile before the tooltip appears
+const HOVER_DELAY_SECONDS: f32 = 0.6;
+
+/// Readout of everything on the tile under the cursor
+#[derive(Component)]
+pub struct HoverTooltip;
+
+/// The tile the cursor is resting on, and for how long (real time, so it works paused)
+#[derive(Default)]
+struct HoverState {
+    tile: Option<IVec2>,
+    seconds: f32,
+}
+
+/// What can be found on a tile
+#[derive(SystemParam)]
+struct HoverTargets<'w, 's> {
+    building_map: Res<'w, BuildingMap>,
+    floors: Query<'w, 's, (&'static GridPosition, &'static Floor)>,
+    walls: Query<'w, 's, (), With<Wall>>,
+    doors: Query<'w, 's, &'static DoorMode, With<Door>>,
+    windows: Query<'w, 's, (Entity, &'static GridPosition), With<crate::components::Window>>,
+    boundaries: Query<'w, 's, &'static Boundary>,
+    blueprints: Query<'w, 's, (Entity, &'static GridPosition, &'static Blueprint)>,
+    furniture: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GridPosition,
+            &'static FurnitureType,
+            &'static FurnitureOrientation,
+            Option<&'static ReceptionConsole>,
+            Option<&'static FurnitureClaim>,
+        ),
+    >,
+    damaged: Query<'w, 's, &'static Damaged>,
+    markers: Query<'w, 's, &'static DeconstructionMarker>,
+    pawns: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static Pawn,
+            &'static CurrentJob,
+            Option<&'static JobHistory>,
+            Has<OnBreak>,
+            Has<StaffingReception>,
+            Has<StaffingSpa>,
+            Has<FetchingHammer>,
+            Has<ReturningHammer>,
+        ),
+        // Staff away training aren't on the map
+        Without<InTraining>,
+    >,
+    guests: Query<'w, 's, (Entity, &'static Transform, &'static Guest)>,
+    items: Query<'w, 's, (&'static Transform, &'static Item, Option<&'static Carried>)>,
+    construction_jobs: Query<'w, 's, &'static ConstructionJob>,
+    deconstruction_jobs: Query<'w, 's, (), With<DeconstructionJob>>,
+    repair_jobs: Query<'w, 's, (), With<RepairJob>>,
+    incidents: Query<'w, 's, &'static Incident>,
+    room_service: Query<'w, 's, &'static RoomServiceOrder>,
+    pests: Query<'w, 's, &'static Infestation>,
+    adjacency: Res<'w, RoomAdjacency>,
+    public_spaces: Query<'w, 's, &'static PublicSpace>,
+}
+
+pub struct HoverTooltipPlugin;
+
+impl Plugin for HoverTooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_hover_tooltip)
+            .add_systems(Update, update_hover_tooltip.in_set(GameplaySet));
+    }
+}
+
+fn setup_hover_tooltip(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+        Visibility::Hidden,
+        HoverTooltip,
+    ));
+}
+
+fn percent(progress: f32) -> String {
+    ((progress * 100.0).clamp(0.0, 100.0) as i32).to_string()
+}
+
+fn floor_name(floor_type: FloorType, localization: &Localization) -> String {
+    let key = match floor_type {
+        FloorType::Wood => "toolbar.floor.wood",
+        FloorType::Stone => "toolbar.floor.stone",
+        FloorType::Carpet => "toolbar.floor.carpet",
+        FloorType::Tile => "toolbar.floor.tile",
+    };
+    localization.format("hover.floor", &[("floor", localization.t(key).to_string())])
+}
+
+fn boundary_name(boundary_type: BoundaryType, localization: &Localization) -> String {
+    let key = match boundary_type {
+        BoundaryType::Fence => "toolbar.build.fence",
+        BoundaryType::Hedge => "toolbar.build.hedge",
+        BoundaryType::Gate => "toolbar.build.gate",
+    };
+    localization.t(key).to_string()
+}
+
+fn item_name(item: &Item, localization: &Localization) -> String {
+    localization.term("item", item.kind.name()).to_string()
+}
+
+fn building_name(building_type: BlueprintType, localization: &Localization) -> String {
+    match building_type {
+        BlueprintType::Wall => localization.t("toolbar.build.wall").to_string(),
+        BlueprintType::Door(_) => localization.t("toolbar.build.door").to_string(),
+        BlueprintType::Window(_) => localization.t("toolbar.build.window").to_string(),
+        BlueprintType::Floor(floor_type) => floor_name(floor_type, localization),
+        BlueprintType::Furniture(furniture_type) => localization
+            .term("furniture", furniture_type.name())
+            .to_string(),
+        BlueprintType::Boundary(boundary_type) => boundary_name(boundary_type, localization),
+    }
+}
+
+/// A worker's recent record: how many jobs, what they mostly do and how long each takes
+fn job_history_line(history: &JobHistory, localization: &Localization) -> Option<String> {
+    let average = history.average_hours()?;
+    let kind = history.usual_kind()?;
+    Some(localization.format(
+        "hover.pawn_jobs",
+        &[
+            ("jobs", history.records().count().to_string()),
+            (
+                "kind",
+                localization.term("job_kind", kind.name()).to_string(),
+            ),
+            ("hours", format!("{:.1}", average)),
+        ],
+    ))
+}
+
+/// Whether a reception desk is taking guests, for the console's line
+fn reception_state(
+    console: &ReceptionConsole,
+    name: String,
+    localization: &Localization,
+) -> String {
+    let key = if !console.open {
+        "hover.reception.closed"
+    } else if console.is_busy() {
+        "hover.reception.busy"
+    } else {
+        "hover.reception.open"
+    };
+    localization.format(key, &[("name", name)])
+}
+
+impl HoverTargets<'_, '_> {
+    /// A finished structure or piece of furniture, noting repairs and deconstruction
+    fn describe_built(&self, entity: Entity, name: String, localization: &Localization) -> String {
+        if let Some(marker) = self.markers.iter().find(|m| m.target_entity == entity) {
+            return localization.format(
+                "hover.deconstructing",
+                &[("name", name), ("progress", percent(marker.progress()))],
+            );
+        }
+        if let Ok(damaged) = self.damaged.get(entity) {
+            let progress = damaged.work_done / damaged.work_required;
+            return localization.format(
+                "hover.damaged",
+                &[("name", name), ("progress", percent(progress))],
+            );
+        }
+        name
+    }
+
+    fn describe_blueprint(&self, blueprint: &Blueprint, localization: &Localization) -> String {
+        let key = if blueprint.blocked {
+            "hover.blueprint_blocked"
+        } else {
+            "hover.blueprint"
+        };
+        localization.format(
+            key,
+            &[
+                ("name", building_name(blueprint.building_type, localization)),
+                ("progress", percent(blueprint.progress())),
+            ],
+        )
+    }
+
+    /// What a worker is busy with, from their current job or post
+    fn describe_job(
+        &self,
+        current_job: &CurrentJob,
+        on_break: bool,
+        at_desk: bool,
+        at_spa: bool,
+        localization: &Localization,
+    ) -> String {
+        if let Some(job) = current_job.job_id {
+            if let Ok(construction) = self.construction_jobs.get(job) {
+                let name = self
+                    .blueprints
+                    .get(construction.blueprint)
+                    .map(|(_, _, blueprint)| building_name(blueprint.building_type, localization))
+                    .unwrap_or_default();
+                return localization.format("hover.job.building", &[("name", name)]);
+            }
+            if self.deconstruction_jobs.contains(job) {
+                return localization.t("hover.job.deconstructing").to_string();
+            }
+            if self.repair_jobs.contains(job) {
+                return localization.t("hover.job.repairing").to_string();
+            }
+            if let Ok(incident) = self.incidents.get(job) {
+                let complaint = localization.term("incident_kind", incident.kind.name());
+                return localization
+                    .format("hover.job.complaint", &[("complaint", complaint.to_string())]);
+            }
+            if let Ok(order) = self.room_service.get(job) {
+                let item = localization.term("room_service", order.item.name());
+                return localization
+                    .format("hover.job.room_service", &[("item", item.to_string())]);
+            }
+            if let Ok(pests) = self.pests.get(job) {
+                let pest = localization.term("pest", pests.kind.name());
+                return localization.format("hover.job.pests", &[("pest", pest.to_string())]);
+            }
+        }
+
+        if on_break {
+            localization.t("work.on_break").to_string()
+        } else if at_desk {
+            localization.term("work_type", "Reception").to_string()
+        } else if at_spa {
+            localization.term("work_type", "Spa Therapist").to_string()
+        } else {
+            localization.t("hover.job.idle").to_string()
+        }
+    }
+
+    /// One line per thing on the tile: the tile and its floor, structures, blueprints,
+    /// furniture, then any workers or guests standing there
+    /// Adds whatever `carrier` has in their hands to their line
+    fn with_carried(&self, carrier: Entity, line: String, localization: &Localization) -> String {
+        let carried = self
+            .items
+            .iter()
+            .find(|(_, _, carried)| carried.is_some_and(|carried| carried.by == carrier));
+        match carried {
+            Some((_, item, _)) => {
+                let item = item_name(item, localization);
+                format!(
+                    "{} - {}",
+                    line,
+                    localization.format("hover.carrying", &[("item", item)])
+                )
+            }
+            None => line,
+        }
+    }
+
+    fn describe(
+        &self,
+        tile: IVec2,
+        world_pos: Vec2,
+        tile_size: f32,
+        localization: &Localization,
+    ) -> String {
+        let mut tile_line = localization.format(
+            "hover.tile",
+            &[("x", tile.x.to_string()), ("y", tile.y.to_string())],
+        );
+        if let Some((_, floor)) = self
+            .floors
+            .iter()
+            .find(|(grid_pos, _)| grid_pos.to_ivec2() == tile)
+        {
+            tile_line = format!(
+                "{} - {}",
+                tile_line,
+                floor_name(floor.floor_type, localization)
+            );
+        }
+        let mut lines = vec![tile_line];
+
+        let public_space = self
+            .adjacency
+            .room_at(tile)
+            .and_then(|room| self.public_spaces.get(room).ok());
+        if let Some(space) = public_space {
+            let quality = localization.term("quality", space.quality.name());
+            let mut line = localization.format(
+                "hover.public_space",
+                &[
+                    ("quality", quality.to_string()),
+                    ("stars", space.quality.stars().to_string()),
+                ],
+            );
+            if !space.shortfalls.is_empty() {
+                let shortfalls: Vec<&str> = space
+                    .shortfalls
+                    .iter()
+                    .map(|shortfall| localization.term("public_space", shortfall.name()))
+                    .collect();
+                line = format!("{} - {}", line, shortfalls.join(", "));
+            }
+            lines.push(line);
+        }
+
+        let structures = [
+            self.building_map.walls.get(&tile),
+            self.building_map.doors.get(&tile),
+            self.building_map.boundaries.get(&tile),
+        ];
+        for &entity in structures.into_iter().flatten() {
+            if self.walls.contains(entity) {
+                let name = localization.t("toolbar.build.wall").to_string();
+                lines.push(self.describe_built(entity, name, localization));
+            } else if let Ok(mode) = self.doors.get(entity) {
+                let mut name = localization.t("toolbar.build.door").to_string();
+                if *mode != DoorMode::Auto {
+                    let mode_name = localization.term("door_mode", mode.name());
+                    name = format!("{} ({})", name, mode_name);
+                }
+                lines.push(self.describe_built(entity, name, localization));
+            } else if let Ok(boundary) = self.boundaries.get(entity) {
+                let name = boundary_name(boundary.boundary_type, localization);
+                lines.push(self.describe_built(entity, name, localization));
+            }
+        }
+        for (entity, grid_pos) in &self.windows {
+            if grid_pos.to_ivec2() == tile {
+                let name = localization.t("toolbar.build.window").to_string();
+                lines.push(self.describe_built(entity, name, localization));
+            }
+        }
+
+        // Door blueprints cover two tiles but sit on the first
+        for (entity, grid_pos, blueprint) in &self.blueprints {
+            let on_tile =
+                grid_pos.to_ivec2() == tile || self.building_map.doors.get(&tile) == Some(&entity);
+            if on_tile {
+                lines.push(self.describe_blueprint(blueprint, localization));
+            }
+        }
+
+        for (entity, grid_pos, furniture_type, orientation, console, claim) in &self.furniture {
+            let tiles = furniture_type.tiles_occupied(grid_pos.to_ivec2(), *orientation);
+            if tiles.contains(&tile) {
+                let mut name = localization
+                    .term("furniture", furniture_type.name())
+                    .to_string();
+                if let Some(console) = console {
+                    name = reception_state(console, name, localization);
+                }
+                // Beds and toilets say who is using them
+                if let Some((_, _, guest)) =
+                    claim.and_then(|claim| self.guests.get(claim.claimant).ok())
+                {
+                    let party = localization.term("party", guest.party.name()).to_string();
+                    let args = [("name", name), ("party", party)];
+                    name = localization.format("hover.claimed", &args);
+                }
+                lines.push(self.describe_built(entity, name, localization));
+            }
+        }
+
+        // People and loose items are picked by distance, since they move between tiles
+        for (transform, item, carried) in &self.items {
+            let near = transform.translation.truncate().distance(world_pos) <= tile_size;
+            if near && carried.is_none() {
+                lines.push(item_name(item, localization));
+            }
+        }
+        for (
+            entity,
+            transform,
+            pawn,
+            current_job,
+            history,
+            on_break,
+            at_desk,
+            at_spa,
+            fetching,
+            returning,
+        ) in &self.pawns
+        {
+            if transform.translation.truncate().distance(world_pos) <= tile_size {
+                let job = if fetching {
+                    localization.t("hover.job.fetching_hammer").to_string()
+                } else if returning {
+                    localization.t("hover.job.returning_hammer").to_string()
+                } else {
+                    self.describe_job(current_job, on_break, at_desk, at_spa, localization)
+                };
+                let line =
+                    localization.format("hover.pawn", &[("name", pawn.name.clone()), ("job", job)]);
+                lines.push(self.with_carried(entity, line, localization));
+                if let Some(line) =
+                    history.and_then(|history| job_history_line(history, localization))
+                {
+                    lines.push(line);
+                }
+            }
+        }
+        for (entity, transform, guest) in &self.guests {
+            if transform.translation.truncate().distance(world_pos) <= tile_size {
+                let party = localization.term("party", guest.party.name()).to_string();
+                let kind = localization
+                    .term("archetype", guest.party.archetype.name())
+                    .to_string();
+                let line = localization.format("hover.guest", &[("party", party), ("kind", kind)]);
+                lines.push(self.with_carried(entity, line, localization));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Shows the tooltip once the cursor has rested on a tile for a moment. It opens up and
+/// to the left of the cursor so it stays clear of the room stats panel.
+fn update_hover_tooltip(
+    time: Res<Time<Real>>,
+    localization: Res<Localization>,
+    ui_blocker: Res<UiInputBlocker>,
+    grid_settings: Res<GridSettings>,
+    cursor: Res<CursorWorldPosition>,
+    targets: HoverTargets,
+    ui_scale: Res<UiScale>,
+    window_query: Query<&BevyWindow, With<PrimaryWindow>>,
+    mut tooltip_query: Query<(&mut Text, &mut Node, &mut Visibility), With<HoverTooltip>>,
+    mut hover: Local<HoverState>,
+) {
+    let Ok((mut text, mut node, mut visibility)) = tooltip_query.get_single_mut() else {
+        return;
+    };
+
+    let hovered = match (cursor.ui, cursor.world, cursor.grid) {
+        (Some(screen), Some(world), Some(tile)) => Some((screen, world, tile)),
+        _ => None,
+    };
+    let Some((cursor_pos, world_pos, tile)) =
+        hovered.filter(|_| !cursor.over_ui && !ui_blocker.block_world_input)
+    else {
+        hover.tile = None;
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    if hover.tile != Some(tile) {
+        hover.tile = Some(tile);
+        hover.seconds = 0.0;
+    }
+    hover.seconds += time.delta_secs();
+    if hover.seconds < HOVER_DELAY_SECONDS {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+
+    let label = targets.describe(tile, world_pos, grid_settings.tile_size, &localization);
+    if **text != label {
+        **text = label;
+    }
+    if let Ok(window) = window_query.get_single() {
+        let ui_size = window.size() / ui_scale.0;
+        node.right = Val::Px(ui_size.x - cursor_pos.x + 16.0);
+        node.bottom = Val::Px(ui_size.y - cursor_pos.y + 16.0);
+    }
+    visibility.set_if_neq(Visibility::Visible);
+}
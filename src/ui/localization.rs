@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Every `<code>.json` file in here is a translation; contributors add a language by
+/// dropping in a copy of `en.json` with the values translated
+pub const LOCALE_DIR: &str = "assets/locale";
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// English is compiled in so every key has a fallback even without an assets folder
+const ENGLISH: &str = include_str!("../../assets/locale/en.json");
+
+/// Translated strings for every available language, plus which one is showing
+#[derive(Resource)]
+pub struct Localization {
+    language: String,
+    languages: Vec<String>,
+    strings: HashMap<String, HashMap<String, String>>,
+}
+
+impl Localization {
+    pub fn load(dir: &Path) -> Self {
+        let mut localization = Self {
+            language: DEFAULT_LANGUAGE.to_string(),
+            languages: Vec::new(),
+            strings: HashMap::new(),
+        };
+        localization.add_language(DEFAULT_LANGUAGE, "built-in English", ENGLISH);
+
+        let mut files: Vec<_> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect(),
+            Err(err) => {
+                warn!("No translations in {}: {}", dir.display(), err);
+                Vec::new()
+            }
+        };
+        files.sort();
+
+        for path in files {
+            let Some(code) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    localization.add_language(code, &path.display().to_string(), &contents)
+                }
+                Err(err) => warn!("Failed to read {}: {}", path.display(), err),
+            }
+        }
+
+        localization
+    }
+
+    // A file on disk for a language that's already loaded fills in or replaces its strings
+    fn add_language(&mut self, code: &str, source: &str, contents: &str) {
+        let strings: HashMap<String, String> = match serde_json::from_str(contents) {
+            Ok(strings) => strings,
+            Err(err) => {
+                warn!("Skipping translation {}: {}", source, err);
+                return;
+            }
+        };
+
+        if !self.languages.iter().any(|language| language == code) {
+            self.languages.push(code.to_string());
+        }
+        self.strings.entry(code.to_string()).or_default().extend(strings);
+    }
+
+    /// The current language's name for itself, e.g. "Español"
+    pub fn language_name(&self) -> &str {
+        self.t_or("language.name", &self.language)
+    }
+
+    pub fn set_language(&mut self, code: &str) -> bool {
+        if !self.languages.iter().any(|language| language == code) {
+            return false;
+        }
+        self.language = code.to_string();
+        true
+    }
+
+    pub fn cycle_language(&mut self) {
+        let current = self
+            .languages
+            .iter()
+            .position(|language| *language == self.language)
+            .unwrap_or(0);
+        self.language = self.languages[(current + 1) % self.languages.len()].clone();
+    }
+
+    fn lookup(&self, key: &str) -> Option<&str> {
+        self.strings
+            .get(&self.language)
+            .and_then(|strings| strings.get(key))
+            .or_else(|| {
+                self.strings
+                    .get(DEFAULT_LANGUAGE)
+                    .and_then(|strings| strings.get(key))
+            })
+            .map(String::as_str)
+    }
+
+    /// The text for `key`, falling back to English and then to the key itself so
+    /// missing translations are easy to spot
+    pub fn t<'a>(&'a self, key: &'a str) -> &'a str {
+        self.lookup(key).unwrap_or(key)
+    }
+
+    pub fn t_or<'a>(&'a self, key: &str, fallback: &'a str) -> &'a str {
+        self.lookup(key).unwrap_or(fallback)
+    }
+
+    /// Fills `{name}` placeholders in the text for `key`
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        args.iter()
+            .fold(self.t(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+
+    /// A game term such as a season or zone type, kept in English until translated
+    pub fn term<'a>(&'a self, category: &str, english: &'a str) -> &'a str {
+        self.t_or(&term_key(category, english), english)
+    }
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        let mut localization = Self::load(Path::new(LOCALE_DIR));
+
+        // Start in the system language when there's a translation for it
+        if let Some(code) = std::env::var("LANG")
+            .ok()
+            .and_then(|lang| lang.get(..2).map(str::to_lowercase))
+        {
+            localization.set_language(&code);
+        }
+
+        localization
+    }
+}
+
+/// Key for a game term, derived from its English name: ("zone_type", "Guest Bedroom")
+/// becomes "zone_type.guest_bedroom"
+pub fn term_key(category: &str, english: &str) -> String {
+    let slug: String = english
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.{}", category, slug)
+}
+
+/// UI text that is looked up by key and refreshed whenever the language changes
+#[derive(Component)]
+pub struct LocalizedText {
+    pub key: String,
+    /// Shown instead of the key when no language has this string, e.g. mod furniture
+    pub fallback: Option<String>,
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            fallback: None,
+        }
+    }
+
+    pub fn with_fallback(key: impl Into<String>, fallback: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            fallback: Some(fallback.into()),
+        }
+    }
+
+    /// Label for a game term; see [`term_key`]
+    pub fn term(category: &str, english: &str) -> Self {
+        Self::with_fallback(term_key(category, english), english)
+    }
+}
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Localization>().add_systems(
+            PostUpdate,
+            apply_localized_text.before(UiSystem::Prepare),
+        );
+    }
+}
+
+// Runs after Update so labels spawned this frame are filled in before layout
+fn apply_localized_text(
+    localization: Res<Localization>,
+    mut text_query: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let language_changed = localization.is_changed();
+
+    for (localized, mut text) in &mut text_query {
+        if !language_changed && !localized.is_changed() {
+            continue;
+        }
+
+        let value = match &localized.fallback {
+            Some(fallback) => localization.t_or(&localized.key, fallback),
+            None => localization.t(&localized.key),
+        };
+        if text.0 != value {
+            text.0 = value.to_string();
+        }
+    }
+}
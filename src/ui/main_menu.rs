@@ -0,0 +1,262 @@
+use super::localization::LocalizedText;
+use super::settings_panel::SettingsPanelState;
+use crate::systems::save_load::{list_save_files, LoadRequestState, SaveFormat, SAVE_DIR};
+use crate::systems::{AppState, GameMode, GameRules};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+#[derive(Component)]
+pub struct MainMenuPanel;
+
+#[derive(Component)]
+pub struct MainMenuSaveList;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum MainMenuButton {
+    NewGame,
+    Load,
+    Settings,
+    Quit,
+}
+
+/// Continues a saved resort from the main menu
+#[derive(Component)]
+pub struct MainMenuSaveButton {
+    pub path: String,
+}
+
+/// Which part of the main menu is open
+#[derive(Resource, Default)]
+pub struct MainMenuState {
+    /// The game mode choice, shown over the menu
+    pub new_game_open: bool,
+    pub saves_open: bool,
+}
+
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MainMenuState>()
+            .add_systems(Startup, setup_main_menu)
+            .add_systems(
+                Update,
+                (
+                    handle_main_menu_buttons,
+                    handle_main_menu_save_buttons,
+                    apply_main_menu_visibility,
+                    update_main_menu_button_colors,
+                ),
+            );
+    }
+}
+
+fn setup_main_menu(mut commands: Commands) {
+    // Saves are listed once; nothing new can be saved before the menu is left
+    let saves = list_save_files(SAVE_DIR);
+
+    // Opaque and full-screen, so nothing of the world shows until a game starts
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.06, 0.08, 0.1)),
+            FocusPolicy::Block,
+            GlobalZIndex(11),
+            MainMenuPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("main_menu.title"),
+                TextFont {
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            spawn_main_menu_button(parent, "main_menu.new_game", MainMenuButton::NewGame);
+            spawn_main_menu_button(parent, "main_menu.load", MainMenuButton::Load);
+            spawn_main_menu_button(parent, "main_menu.settings", MainMenuButton::Settings);
+            spawn_main_menu_button(parent, "main_menu.quit", MainMenuButton::Quit);
+
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(5.0),
+                        margin: UiRect::top(Val::Px(10.0)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    MainMenuSaveList,
+                ))
+                .with_children(|parent| {
+                    if saves.is_empty() {
+                        parent.spawn((
+                            Text::default(),
+                            LocalizedText::new("main_menu.no_saves"),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        ));
+                    }
+                    for filename in saves {
+                        spawn_save_button(parent, &filename);
+                    }
+                });
+        });
+}
+
+fn spawn_main_menu_button(parent: &mut ChildBuilder, label_key: &str, button: MainMenuButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(260.0),
+                height: Val::Px(44.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_save_button(parent: &mut ChildBuilder, filename: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(260.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            MainMenuSaveButton {
+                path: format!("{}/{}", SAVE_DIR, filename),
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(SaveFormat::strip_extension(filename)),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_main_menu_buttons(
+    interaction_query: Query<(&Interaction, &MainMenuButton), Changed<Interaction>>,
+    mut menu: ResMut<MainMenuState>,
+    mut settings_panel: ResMut<SettingsPanelState>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MainMenuButton::NewGame => menu.new_game_open = true,
+            MainMenuButton::Load => menu.saves_open = !menu.saves_open,
+            MainMenuButton::Settings => settings_panel.visible = !settings_panel.visible,
+            MainMenuButton::Quit => {
+                exit.send(AppExit::Success);
+            }
+        }
+    }
+}
+
+// A save doesn't say which mode it was played in, so it carries on as a normal game
+fn handle_main_menu_save_buttons(
+    interaction_query: Query<(&Interaction, &MainMenuSaveButton), Changed<Interaction>>,
+    mut rules: ResMut<GameRules>,
+    mut load_state: ResMut<LoadRequestState>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed && !rules.chosen {
+            rules.choose(GameMode::Normal);
+            load_state.request(button.path.clone());
+            next_state.set(AppState::Loading);
+            info!("Continuing the resort saved in {}", button.path);
+        }
+    }
+}
+
+fn apply_main_menu_visibility(
+    state: Res<State<AppState>>,
+    menu: Res<MainMenuState>,
+    mut panel_query: Query<&mut Node, (With<MainMenuPanel>, Without<MainMenuSaveList>)>,
+    mut save_list_query: Query<&mut Node, (With<MainMenuSaveList>, Without<MainMenuPanel>)>,
+) {
+    if !state.is_changed() && !menu.is_changed() {
+        return;
+    }
+
+    // Kept up while the chosen save is built so the half-loaded world never shows
+    let visible = matches!(state.get(), AppState::MainMenu | AppState::Loading);
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        node.display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    if let Ok(mut node) = save_list_query.get_single_mut() {
+        node.display = if menu.saves_open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn update_main_menu_button_colors(
+    mut button_query: Query<
+        (&mut BackgroundColor, &Interaction, Option<&MainMenuButton>),
+        Or<(With<MainMenuButton>, With<MainMenuSaveButton>)>,
+    >,
+    menu: Res<MainMenuState>,
+) {
+    for (mut color, interaction, button) in &mut button_query {
+        *color = if button == Some(&MainMenuButton::Load) && menu.saves_open {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+    }
+}
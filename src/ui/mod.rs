@@ -1,14 +1,60 @@
 use bevy::prelude::Resource;
 
+pub mod building_rules_panel;
+pub mod calendar_display;
+pub mod economy_panel;
+pub mod feedback;
+pub mod game_over_panel;
+pub mod guest_emotes;
+pub mod hotbar;
+pub mod hover_tooltip;
+pub mod localization;
+pub mod main_menu;
 pub mod money_display;
+pub mod new_game_panel;
+pub mod night_audit_panel;
+pub mod notifications;
+pub mod pause_menu;
+pub mod receipt_popup;
+pub mod reviews_panel;
+pub mod room_inspector;
+pub mod rooms_panel;
 pub mod save_load_panel;
+pub mod scenario_editor;
+pub mod settings;
+pub mod settings_panel;
+pub mod sign_editor;
 pub mod speed_control;
+pub mod statistics_panel;
 pub mod toolbar;
 pub mod work_assignments;
 
+pub use building_rules_panel::*;
+pub use calendar_display::*;
+pub use economy_panel::*;
+pub use feedback::*;
+pub use game_over_panel::*;
+pub use guest_emotes::*;
+pub use hotbar::*;
+pub use hover_tooltip::*;
+pub use localization::*;
+pub use main_menu::*;
 pub use money_display::*;
+pub use new_game_panel::*;
+pub use night_audit_panel::*;
+pub use notifications::*;
+pub use pause_menu::*;
+pub use receipt_popup::*;
+pub use reviews_panel::*;
+pub use room_inspector::*;
+pub use rooms_panel::*;
 pub use save_load_panel::*;
+pub use scenario_editor::*;
+pub use settings::*;
+pub use settings_panel::*;
+pub use sign_editor::*;
 pub use speed_control::*;
+pub use statistics_panel::*;
 pub use toolbar::*;
 pub use work_assignments::*;
 
@@ -16,11 +62,48 @@ pub use work_assignments::*;
 pub struct UiInputBlocker {
     pub block_world_input: bool,
     pub speed_controls_blocking: bool,
+    pub hotbar_blocking: bool,
     pub context_menu_blocking: bool,
+    pub economy_panel_blocking: bool,
+    pub room_inspector_blocking: bool,
+    pub building_rules_blocking: bool,
+    pub game_over_blocking: bool,
+    pub deconstruct_confirm_blocking: bool,
+    pub photo_mode_blocking: bool,
+    pub rooms_panel_blocking: bool,
+    pub reviews_panel_blocking: bool,
+    pub settings_panel_blocking: bool,
+    pub sign_editor_blocking: bool,
+    pub night_audit_blocking: bool,
+    pub scenario_editor_blocking: bool,
+    pub zone_name_editing: bool,
+    pub pawn_name_editing: bool,
+    pub sign_text_editing: bool,
+    pub scenario_name_editing: bool,
+    /// Set while a text field has focus so keyboard shortcuts don't fire
+    pub text_input_active: bool,
 }
 
 impl UiInputBlocker {
     pub fn recompute(&mut self) {
-        self.block_world_input = self.speed_controls_blocking || self.context_menu_blocking;
+        self.block_world_input = self.speed_controls_blocking
+            || self.hotbar_blocking
+            || self.context_menu_blocking
+            || self.economy_panel_blocking
+            || self.room_inspector_blocking
+            || self.building_rules_blocking
+            || self.game_over_blocking
+            || self.deconstruct_confirm_blocking
+            || self.photo_mode_blocking
+            || self.rooms_panel_blocking
+            || self.reviews_panel_blocking
+            || self.settings_panel_blocking
+            || self.sign_editor_blocking
+            || self.night_audit_blocking
+            || self.scenario_editor_blocking;
+        self.text_input_active = self.zone_name_editing
+            || self.pawn_name_editing
+            || self.sign_text_editing
+            || self.scenario_name_editing;
     }
 }
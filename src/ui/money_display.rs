@@ -1,15 +1,25 @@
-use crate::systems::Money;
+use super::localization::Localization;
+use crate::systems::{GameplaySet, Money};
 use bevy::prelude::*;
 
+const MONEY_COLOR: Color = Color::srgb(0.2, 0.8, 0.2);
+const DEBT_COLOR: Color = Color::srgb(0.9, 0.25, 0.2);
+
 #[derive(Component)]
 pub struct MoneyDisplay;
 
+/// Shown while hovering the balance: how much is tied up in unbuilt blueprints
+#[derive(Component)]
+pub struct MoneyTooltip;
+
 pub struct MoneyDisplayPlugin;
 
 impl Plugin for MoneyDisplayPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_money_display)
-            .add_systems(Update, update_money_display);
+        app.add_systems(Startup, setup_money_display).add_systems(
+            Update,
+            (update_money_display, update_money_tooltip).in_set(GameplaySet),
+        );
     }
 }
 
@@ -25,6 +35,7 @@ fn setup_money_display(mut commands: Commands) {
                 ..default()
             },
             BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+            Interaction::default(),
             MoneyDisplay,
         ))
         .with_children(|parent| {
@@ -34,15 +45,35 @@ fn setup_money_display(mut commands: Commands) {
                     font_size: 24.0,
                     ..default()
                 },
-                TextColor(Color::srgb(0.2, 0.8, 0.2)), // Green for money
+                TextColor(MONEY_COLOR),
             ));
         });
+
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(62.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.9)),
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        MoneyTooltip,
+    ));
 }
 
 fn update_money_display(
     money: Res<Money>,
     query: Query<Entity, With<MoneyDisplay>>,
-    mut text_query: Query<&mut Text>,
+    mut text_query: Query<(&mut Text, &mut TextColor)>,
     children_query: Query<&Children>,
 ) {
     if !money.is_changed() {
@@ -52,10 +83,51 @@ fn update_money_display(
     for entity in &query {
         if let Ok(children) = children_query.get(entity) {
             for &child in children.iter() {
-                if let Ok(mut text) = text_query.get_mut(child) {
-                    **text = format!("${}", money.amount);
+                if let Ok((mut text, mut color)) = text_query.get_mut(child) {
+                    **text = if money.infinite {
+                        "$ Unlimited".to_string()
+                    } else {
+                        format!("${}", money.amount)
+                    };
+                    // Red while in debt
+                    color.0 = if money.amount < 0 {
+                        DEBT_COLOR
+                    } else {
+                        MONEY_COLOR
+                    };
                 }
             }
         }
     }
 }
+
+fn update_money_tooltip(
+    money: Res<Money>,
+    localization: Res<Localization>,
+    display_query: Query<&Interaction, With<MoneyDisplay>>,
+    mut tooltip_query: Query<(&mut Text, &mut Visibility), With<MoneyTooltip>>,
+) {
+    let Ok((mut text, mut visibility)) = tooltip_query.get_single_mut() else {
+        return;
+    };
+
+    let hovered = display_query
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    if !hovered || money.infinite {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+
+    let label = localization.format(
+        "money.reserved",
+        &[
+            ("reserved", money.reserved.to_string()),
+            ("available", money.available().to_string()),
+        ],
+    );
+    if **text != label {
+        **text = label;
+    }
+    visibility.set_if_neq(Visibility::Visible);
+}
@@ -0,0 +1,282 @@
+use super::localization::{term_key, LocalizedText};
+use super::main_menu::MainMenuState;
+use super::scenario_editor::{scenario_files, SCENARIO_DIR};
+use crate::systems::save_load::{LoadRequestState, SaveFormat};
+use crate::systems::{AppState, GameMode, GameRules};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+#[derive(Component)]
+pub struct NewGamePanel;
+
+#[derive(Component)]
+pub struct GameModeButton {
+    pub mode: GameMode,
+}
+
+/// Starts a normal game on an exported scenario
+#[derive(Component)]
+pub struct ScenarioButton {
+    pub path: String,
+}
+
+/// Goes back to the main menu without starting anything
+#[derive(Component)]
+pub struct NewGameBackButton;
+
+pub struct NewGamePanelPlugin;
+
+impl Plugin for NewGamePanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRules>()
+            .init_resource::<MainMenuState>()
+            .add_systems(Startup, setup_new_game_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_game_mode_button_clicks,
+                    handle_scenario_button_clicks,
+                    handle_new_game_back_button,
+                    update_game_mode_button_colors,
+                    apply_new_game_panel_visibility,
+                ),
+            );
+    }
+}
+
+fn setup_new_game_panel(mut commands: Commands) {
+    // Full-screen backdrop over the main menu with the mode choice centered on it
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            FocusPolicy::Block,
+            GlobalZIndex(12),
+            NewGamePanel,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("new_game.title"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+
+                    for mode in GameMode::all() {
+                        spawn_game_mode_button(parent, mode);
+                    }
+
+                    // Scenarios are read once; ones exported this session show up next launch
+                    let scenarios = scenario_files();
+                    if !scenarios.is_empty() {
+                        parent.spawn((
+                            Text::default(),
+                            LocalizedText::new("new_game.scenarios"),
+                            TextFont {
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    }
+                    for filename in scenarios {
+                        spawn_scenario_button(parent, &filename);
+                    }
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(420.0),
+                                padding: UiRect::all(Val::Px(10.0)),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                            NewGameBackButton,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("main_menu.back"),
+                                TextFont {
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                });
+        });
+}
+
+fn spawn_game_mode_button(parent: &mut ChildBuilder, mode: GameMode) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(420.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            GameModeButton { mode },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::term("game_mode", mode.name()),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::default(),
+                LocalizedText::with_fallback(
+                    format!("{}.description", term_key("game_mode", mode.name())),
+                    mode.description(),
+                ),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+fn spawn_scenario_button(parent: &mut ChildBuilder, filename: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            ScenarioButton {
+                path: format!("{}/{}", SCENARIO_DIR, filename),
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(SaveFormat::strip_extension(filename)),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_game_mode_button_clicks(
+    interaction_query: Query<(&Interaction, &GameModeButton), Changed<Interaction>>,
+    mut rules: ResMut<GameRules>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed && !rules.chosen {
+            rules.choose(button.mode);
+            next_state.set(AppState::Loading);
+            info!("Started a new {} game", button.mode.name());
+        }
+    }
+}
+
+// The scenario is loaded like a save, with its ground, guests and starting money
+fn handle_scenario_button_clicks(
+    interaction_query: Query<(&Interaction, &ScenarioButton), Changed<Interaction>>,
+    mut rules: ResMut<GameRules>,
+    mut load_state: ResMut<LoadRequestState>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed && !rules.chosen {
+            rules.choose(GameMode::Normal);
+            load_state.request(button.path.clone());
+            next_state.set(AppState::Loading);
+            info!("Started a new game on scenario {}", button.path);
+        }
+    }
+}
+
+fn handle_new_game_back_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<NewGameBackButton>)>,
+    mut menu: ResMut<MainMenuState>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        menu.new_game_open = false;
+    }
+}
+
+fn update_game_mode_button_colors(
+    mut button_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        Or<(
+            With<GameModeButton>,
+            With<ScenarioButton>,
+            With<NewGameBackButton>,
+        )>,
+    >,
+) {
+    for (mut color, interaction) in &mut button_query {
+        match interaction {
+            Interaction::Hovered => {
+                *color = Color::srgb(0.35, 0.35, 0.35).into();
+            }
+            _ => {
+                *color = Color::srgb(0.25, 0.25, 0.25).into();
+            }
+        }
+    }
+}
+
+// Opened from the main menu's New Game button
+fn apply_new_game_panel_visibility(
+    state: Res<State<AppState>>,
+    menu: Res<MainMenuState>,
+    mut panel_query: Query<&mut Node, With<NewGamePanel>>,
+) {
+    let visible = *state.get() == AppState::MainMenu && menu.new_game_open;
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        let display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use super::localization::{Localization, LocalizedText};
+use super::settings::GameSettings;
+use super::statistics_panel::StatisticsPanelState;
+use super::UiInputBlocker;
+use crate::systems::{
+    sample_daily_stats, DayLog, FinanceLedger, GameClock, GameplaySet, Review, ScenarioState,
+    SpeedOption, StatsHistory, TimeSpeed,
+};
+use bevy::prelude::*;
+
+/// Incidents listed by name before the rest are just counted
+const MAX_LISTED_INCIDENTS: usize = 4;
+
+#[derive(Component)]
+pub struct NightAuditPanel;
+
+#[derive(Component)]
+struct NightAuditTitle;
+
+#[derive(Component)]
+struct NightAuditDetail;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum NightAuditButton {
+    OpenStatistics,
+    Close,
+}
+
+/// How the resort did on one day, shown when the day is over
+#[derive(Debug, Clone, Default)]
+pub struct NightAuditReport {
+    pub day: u32,
+    pub revenue: i32,
+    pub expenses: i32,
+    pub good_reviews: usize,
+    pub bad_reviews: usize,
+    /// None when no statistics were recorded for the day
+    pub occupancy: Option<f32>,
+    pub incidents: Vec<String>,
+}
+
+#[derive(Resource, Default)]
+pub struct NightAuditState {
+    pub report: Option<NightAuditReport>,
+    /// Set when the audit paused the game, so closing it picks the game back up
+    pub paused_game: bool,
+}
+
+pub struct NightAuditPanelPlugin;
+
+impl Plugin for NightAuditPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NightAuditState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_night_audit_panel)
+            .add_systems(
+                Update,
+                (
+                    run_night_audit.after(sample_daily_stats),
+                    handle_night_audit_button_clicks,
+                    update_night_audit_button_colors,
+                    update_night_audit_text,
+                    apply_night_audit_panel_visibility,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_night_audit_panel(mut commands: Commands) {
+    // Hidden until the end of the day, centred near the top clear of the side panels
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(80.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-170.0)),
+                width: Val::Px(340.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(15.0)),
+                row_gap: Val::Px(8.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            GlobalZIndex(5),
+            NightAuditPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                NightAuditTitle,
+            ));
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                NightAuditDetail,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(5.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_night_audit_button(
+                        row,
+                        NightAuditButton::OpenStatistics,
+                        "night_audit.open_stats",
+                    );
+                    spawn_night_audit_button(row, NightAuditButton::Close, "inspector.close");
+                });
+        });
+}
+
+fn spawn_night_audit_button(parent: &mut ChildBuilder, button: NightAuditButton, label_key: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(30.0),
+                padding: UiRect::horizontal(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Closes the books on each day as the next begins: money in and out, the reviews and
+/// complaints it brought, and how full the rooms were
+fn run_night_audit(
+    clock: Res<GameClock>,
+    finance: Res<FinanceLedger>,
+    stats: Res<StatsHistory>,
+    scenario: Res<ScenarioState>,
+    settings: Res<GameSettings>,
+    mut day_log: ResMut<DayLog>,
+    mut time_speed: ResMut<TimeSpeed>,
+    mut state: ResMut<NightAuditState>,
+    mut last_day: Local<Option<u32>>,
+) {
+    let day = clock.day();
+    let Some(previous) = last_day.replace(day) else {
+        return;
+    };
+    if previous == day {
+        return;
+    }
+
+    let log = day_log.close_day(previous);
+    // The game over screen says all there is to say
+    if scenario.is_over() {
+        return;
+    }
+
+    let mut report = NightAuditReport {
+        day: previous,
+        occupancy: stats
+            .latest()
+            .filter(|sample| sample.day == previous)
+            .map(|sample| sample.occupancy),
+        incidents: log
+            .incidents
+            .into_iter()
+            .map(|(_, incident)| incident)
+            .collect(),
+        ..default()
+    };
+    for (_, review) in &log.reviews {
+        match review {
+            Review::Good => report.good_reviews += 1,
+            Review::Bad => report.bad_reviews += 1,
+        }
+    }
+    // Money that never came in was not earned, so it stays out of the day's takings
+    for entry in finance
+        .entries
+        .iter()
+        .filter(|entry| entry.day == previous && !entry.lost)
+    {
+        if entry.amount >= 0 {
+            report.revenue += entry.amount;
+        } else {
+            report.expenses -= entry.amount;
+        }
+    }
+    state.report = Some(report);
+
+    if settings.pause_for_night_audit && !time_speed.is_paused() {
+        time_speed.set_speed(SpeedOption::Paused);
+        state.paused_game = true;
+    }
+}
+
+fn handle_night_audit_button_clicks(
+    interaction_query: Query<(&Interaction, &NightAuditButton), Changed<Interaction>>,
+    mut state: ResMut<NightAuditState>,
+    mut time_speed: ResMut<TimeSpeed>,
+    mut statistics_state: ResMut<StatisticsPanelState>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed || state.report.is_none() {
+            continue;
+        }
+
+        if *button == NightAuditButton::OpenStatistics {
+            statistics_state.visible = true;
+        }
+
+        state.report = None;
+        // Only undo a pause the audit made; one the player made since is theirs
+        if std::mem::take(&mut state.paused_game) && time_speed.is_paused() {
+            time_speed.toggle_pause();
+        }
+    }
+}
+
+fn update_night_audit_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<NightAuditButton>>,
+) {
+    for (mut color, interaction) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => Color::srgb(0.35, 0.35, 0.35),
+            Interaction::None => Color::srgb(0.25, 0.25, 0.25),
+        }
+        .into();
+    }
+}
+
+fn update_night_audit_text(
+    state: Res<NightAuditState>,
+    localization: Res<Localization>,
+    mut title_query: Query<&mut Text, (With<NightAuditTitle>, Without<NightAuditDetail>)>,
+    mut detail_query: Query<&mut Text, (With<NightAuditDetail>, Without<NightAuditTitle>)>,
+) {
+    if !state.is_changed() && !localization.is_changed() {
+        return;
+    }
+    let Some(report) = &state.report else {
+        return;
+    };
+
+    if let Ok(mut title) = title_query.get_single_mut() {
+        title.0 = localization.format("night_audit.title", &[("day", report.day.to_string())]);
+    }
+
+    let occupancy = report.occupancy.map_or_else(
+        || "-".to_string(),
+        |occupancy| format!("{:.0}%", occupancy * 100.0),
+    );
+    let mut lines = vec![
+        localization.format(
+            "night_audit.revenue",
+            &[("amount", report.revenue.to_string())],
+        ),
+        localization.format(
+            "night_audit.expenses",
+            &[("amount", report.expenses.to_string())],
+        ),
+        localization.format(
+            "night_audit.net",
+            &[("amount", (report.revenue - report.expenses).to_string())],
+        ),
+        localization.format("night_audit.occupancy", &[("occupancy", occupancy)]),
+        localization.format(
+            "night_audit.reviews",
+            &[
+                ("good", report.good_reviews.to_string()),
+                ("bad", report.bad_reviews.to_string()),
+            ],
+        ),
+        String::new(),
+    ];
+
+    if report.incidents.is_empty() {
+        lines.push(localization.t("night_audit.no_incidents").to_string());
+    } else {
+        lines.push(localization.t("night_audit.incidents").to_string());
+        lines.extend(
+            report
+                .incidents
+                .iter()
+                .take(MAX_LISTED_INCIDENTS)
+                .map(|incident| format!("- {}", incident)),
+        );
+        let unlisted = report.incidents.len().saturating_sub(MAX_LISTED_INCIDENTS);
+        if unlisted > 0 {
+            lines.push(localization.format(
+                "night_audit.more_incidents",
+                &[("count", unlisted.to_string())],
+            ));
+        }
+    }
+
+    if let Ok(mut detail) = detail_query.get_single_mut() {
+        detail.0 = lines.join("\n");
+    }
+}
+
+fn apply_night_audit_panel_visibility(
+    state: Res<NightAuditState>,
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    mut panel_query: Query<&mut Node, With<NightAuditPanel>>,
+    interaction_query: Query<&Interaction, With<NightAuditButton>>,
+) {
+    let visible = state.report.is_some();
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        let display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    let should_block = visible
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+    if ui_blocker.night_audit_blocking != should_block {
+        ui_blocker.night_audit_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+/// Notifications are read at any game speed, so they count down in real time
+const NOTIFICATION_SECONDS: f32 = 8.0;
+const MAX_NOTIFICATIONS: usize = 4;
+
+/// Something that happened off-screen the player should hear about, such as staff quitting
+#[derive(Event, Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+}
+
+/// Column of notifications under the top bar, newest at the bottom
+#[derive(Component)]
+pub struct NotificationStack;
+
+#[derive(Component)]
+pub struct NotificationToast {
+    pub seconds_left: f32,
+}
+
+pub struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Notification>()
+            .add_systems(Startup, setup_notification_stack)
+            .add_systems(Update, (show_notifications, expire_notifications).chain());
+    }
+}
+
+fn setup_notification_stack(mut commands: Commands) {
+    // Full-width row so the stack sits centered below the calendar
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(56.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                NotificationStack,
+            ));
+        });
+}
+
+fn show_notifications(
+    mut commands: Commands,
+    mut events: EventReader<Notification>,
+    stack_query: Query<(Entity, Option<&Children>), With<NotificationStack>>,
+) {
+    let Ok((stack, children)) = stack_query.get_single() else {
+        events.clear();
+        return;
+    };
+
+    let messages: Vec<_> = events.read().map(|event| &event.message).collect();
+    if messages.is_empty() {
+        return;
+    }
+
+    // Make room for the new notifications by dropping the oldest ones
+    let shown = children.map_or(0, |children| children.len());
+    let excess = (shown + messages.len()).saturating_sub(MAX_NOTIFICATIONS);
+    if let Some(children) = children {
+        for &child in children.iter().take(excess) {
+            commands.entity(child).despawn_recursive();
+        }
+    }
+
+    let skip = messages.len().saturating_sub(MAX_NOTIFICATIONS);
+    commands.entity(stack).with_children(|parent| {
+        for message in messages.into_iter().skip(skip) {
+            info!("{}", message);
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.35, 0.15, 0.1, 0.9)),
+                    NotificationToast {
+                        seconds_left: NOTIFICATION_SECONDS,
+                    },
+                ))
+                .with_children(|toast| {
+                    toast.spawn((
+                        Text::new(message.clone()),
+                        TextFont {
+                            font_size: 15.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        }
+    });
+}
+
+fn expire_notifications(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toast_query: Query<(Entity, &mut NotificationToast)>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        toast.seconds_left -= time.delta_secs();
+        if toast.seconds_left <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use super::localization::LocalizedText;
+use super::settings_panel::SettingsPanelState;
+use crate::systems::AppState;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+#[derive(Component)]
+pub struct PauseMenuPanel;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseMenuButton {
+    Resume,
+    Settings,
+    Quit,
+}
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_pause_menu)
+            .add_systems(OnEnter(AppState::Paused), show_pause_menu)
+            .add_systems(OnExit(AppState::Paused), hide_pause_menu)
+            .add_systems(
+                Update,
+                (handle_pause_menu_buttons, update_pause_menu_button_colors)
+                    .run_if(in_state(AppState::Paused)),
+            );
+    }
+}
+
+fn setup_pause_menu(mut commands: Commands) {
+    // Dims the resort behind it and keeps clicks off the panels underneath
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            FocusPolicy::Block,
+            GlobalZIndex(11),
+            PauseMenuPanel,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("pause_menu.title"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+
+                    spawn_pause_menu_button(parent, "pause_menu.resume", PauseMenuButton::Resume);
+                    spawn_pause_menu_button(
+                        parent,
+                        "main_menu.settings",
+                        PauseMenuButton::Settings,
+                    );
+                    spawn_pause_menu_button(parent, "main_menu.quit", PauseMenuButton::Quit);
+                });
+        });
+}
+
+fn spawn_pause_menu_button(parent: &mut ChildBuilder, label_key: &str, button: PauseMenuButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(220.0),
+                height: Val::Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn show_pause_menu(mut panel_query: Query<&mut Node, With<PauseMenuPanel>>) {
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        node.display = Display::Flex;
+    }
+}
+
+fn hide_pause_menu(mut panel_query: Query<&mut Node, With<PauseMenuPanel>>) {
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        node.display = Display::None;
+    }
+}
+
+fn handle_pause_menu_buttons(
+    interaction_query: Query<(&Interaction, &PauseMenuButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut settings_panel: ResMut<SettingsPanelState>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            PauseMenuButton::Resume => next_state.set(AppState::InGame),
+            PauseMenuButton::Settings => settings_panel.visible = !settings_panel.visible,
+            PauseMenuButton::Quit => {
+                exit.send(AppExit::Success);
+            }
+        }
+    }
+}
+
+fn update_pause_menu_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<PauseMenuButton>>,
+) {
+    for (mut color, interaction) in &mut button_query {
+        *color = if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+    }
+}
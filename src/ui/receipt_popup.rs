@@ -0,0 +1,151 @@
+use super::localization::Localization;
+use crate::systems::{GameplaySet, GuestBill, GuestCheckedOut};
+use bevy::prelude::*;
+
+/// Receipts are read at any game speed, so they count down in real time
+const RECEIPT_SECONDS: f32 = 6.0;
+const MAX_RECEIPTS: usize = 3;
+
+/// Column of receipts above the hotbar, newest at the bottom
+#[derive(Component)]
+pub struct ReceiptStack;
+
+#[derive(Component)]
+pub struct ReceiptPopup {
+    pub seconds_left: f32,
+}
+
+pub struct ReceiptPopupPlugin;
+
+impl Plugin for ReceiptPopupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_receipt_stack).add_systems(
+            Update,
+            (show_receipts, expire_receipts).chain().in_set(GameplaySet),
+        );
+    }
+}
+
+fn setup_receipt_stack(mut commands: Commands) {
+    // Full-width row so the stack sits centered above the toolbar and hotbar
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(150.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ReceiptStack,
+            ));
+        });
+}
+
+/// The itemised lines of a bill, leaving out charges the party never ran up
+fn receipt_text(bill: &GuestBill, localization: &Localization) -> String {
+    let mut lines = vec![localization.format(
+        "receipt.title",
+        &[
+            (
+                "party",
+                localization.term("party", bill.party.name()).to_string(),
+            ),
+            ("room", bill.room.clone()),
+        ],
+    )];
+    lines.push(localization.format(
+        "receipt.room",
+        &[
+            ("nights", bill.nights.to_string()),
+            ("rate", bill.nightly_rate.to_string()),
+            ("amount", bill.room_charges().to_string()),
+        ],
+    ));
+    if bill.restaurant > 0 {
+        lines.push(localization.format(
+            "receipt.restaurant",
+            &[("amount", bill.restaurant.to_string())],
+        ));
+    }
+    if bill.extras > 0 {
+        lines.push(localization.format("receipt.extras", &[("amount", bill.extras.to_string())]));
+    }
+    if bill.refund > 0 {
+        lines.push(localization.format("receipt.refund", &[("amount", bill.refund.to_string())]));
+    }
+    lines.push(localization.format("receipt.total", &[("amount", bill.total().to_string())]));
+    lines.join("\n")
+}
+
+fn show_receipts(
+    mut commands: Commands,
+    mut events: EventReader<GuestCheckedOut>,
+    localization: Res<Localization>,
+    stack_query: Query<(Entity, Option<&Children>), With<ReceiptStack>>,
+) {
+    let Ok((stack, children)) = stack_query.get_single() else {
+        events.clear();
+        return;
+    };
+
+    let bills: Vec<_> = events.read().map(|event| &event.bill).collect();
+    if bills.is_empty() {
+        return;
+    }
+
+    // Make room for the new receipts by dropping the oldest ones
+    let shown = children.map_or(0, |children| children.len());
+    let excess = (shown + bills.len()).saturating_sub(MAX_RECEIPTS);
+    if let Some(children) = children {
+        for &child in children.iter().take(excess) {
+            commands.entity(child).despawn_recursive();
+        }
+    }
+
+    let skip = bills.len().saturating_sub(MAX_RECEIPTS);
+    commands.entity(stack).with_children(|parent| {
+        for bill in bills.into_iter().skip(skip) {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+                    ReceiptPopup {
+                        seconds_left: RECEIPT_SECONDS,
+                    },
+                ))
+                .with_children(|popup| {
+                    popup.spawn((
+                        Text::new(receipt_text(bill, &localization)),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    ));
+                });
+        }
+    });
+}
+
+fn expire_receipts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut popup_query: Query<(Entity, &mut ReceiptPopup)>,
+) {
+    for (entity, mut popup) in &mut popup_query {
+        popup.seconds_left -= time.delta_secs();
+        if popup.seconds_left <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use super::localization::{Localization, LocalizedText};
+use super::room_inspector::RoomInspectorState;
+use super::work_assignments::WorkAssignmentsPanelState;
+use super::UiInputBlocker;
+use crate::components::*;
+use crate::systems::{zone_center, CameraController, GameplaySet, GridSettings, GuestReviews};
+use bevy::prelude::*;
+
+const PANEL_WIDTH: f32 = 420.0;
+/// Complaints shown on each review; the rest are left out
+const TOP_COMPLAINTS: usize = 3;
+const GOOD_COLOR: Color = Color::srgb(0.9, 0.75, 0.3);
+const BAD_COLOR: Color = Color::srgb(0.9, 0.4, 0.3);
+
+#[derive(Component)]
+pub struct ReviewsPanel;
+
+#[derive(Component)]
+pub struct ReviewsListContent;
+
+/// A room name or complaint on a review. Clicking it shows the room, or for complaints
+/// the staff have to put right, the work panel.
+#[derive(Component)]
+pub struct ReviewLinkButton {
+    pub room: Option<Entity>,
+}
+
+#[derive(Resource, Default)]
+pub struct ReviewsPanelState {
+    pub visible: bool,
+}
+
+pub struct ReviewsPanelPlugin;
+
+impl Plugin for ReviewsPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReviewsPanelState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_reviews_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_reviews_panel_toggle,
+                    apply_reviews_panel_visibility,
+                    handle_review_link_clicks,
+                    update_reviews_list,
+                    update_review_link_colors,
+                    block_map_input_over_reviews_panel,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_reviews_panel(mut commands: Commands) {
+    // Initially hidden panel, on the right beside the work panel
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                top: Val::Px(60.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            ReviewsPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("reviews.title"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("reviews.hint"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+
+            // Container used for rebuilding the review rows
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ReviewsListContent,
+            ));
+        });
+}
+
+fn handle_reviews_panel_toggle(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel_state: ResMut<ReviewsPanelState>,
+    ui_blocker: Res<UiInputBlocker>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::KeyY) {
+        panel_state.visible = !panel_state.visible;
+    }
+}
+
+fn apply_reviews_panel_visibility(
+    panel_state: Res<ReviewsPanelState>,
+    mut panel_query: Query<&mut Node, With<ReviewsPanel>>,
+) {
+    if !panel_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        style.display = if panel_state.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// Rooms open in the inspector with the camera moved over them; a room that has since
+/// gone is left alone
+fn handle_review_link_clicks(
+    interaction_query: Query<(&Interaction, &ReviewLinkButton), Changed<Interaction>>,
+    zone_query: Query<&Zone>,
+    grid_settings: Res<GridSettings>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
+    mut inspector: ResMut<RoomInspectorState>,
+    mut work_panel: ResMut<WorkAssignmentsPanelState>,
+) {
+    for (interaction, link) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(room) = link.room else {
+            work_panel.visible = true;
+            continue;
+        };
+        let Ok(zone) = zone_query.get(room) else {
+            continue;
+        };
+        inspector.select(room);
+        if let Ok(mut transform) = camera_query.get_single_mut() {
+            let center = zone_center(zone, &grid_settings);
+            transform.translation.x = center.x;
+            transform.translation.y = center.y;
+        }
+    }
+}
+
+fn update_reviews_list(
+    mut commands: Commands,
+    panel_state: Res<ReviewsPanelState>,
+    reviews: Res<GuestReviews>,
+    content_query: Query<Entity, With<ReviewsListContent>>,
+    children_query: Query<&Children>,
+    localization: Res<Localization>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    // Only rebuild when the panel opens, the language changes or a review comes in
+    if !(panel_state.is_changed() || reviews.is_changed() || localization.is_changed()) {
+        return;
+    }
+
+    let Ok(content_entity) = content_query.get_single() else {
+        return;
+    };
+
+    // Remove old rows
+    if let Ok(children) = children_query.get(content_entity) {
+        for &child in children.iter() {
+            commands.entity(child).despawn_recursive();
+        }
+    }
+
+    commands.entity(content_entity).with_children(|parent| {
+        let Some(average) = reviews.average_stars() else {
+            spawn_line(
+                parent,
+                localization.t("reviews.empty"),
+                Color::srgb(0.7, 0.7, 0.7),
+            );
+            return;
+        };
+
+        spawn_line(
+            parent,
+            &localization.format(
+                "reviews.summary",
+                &[
+                    ("count", reviews.recent.len().to_string()),
+                    ("stars", format!("{:.1}", average)),
+                ],
+            ),
+            Color::WHITE,
+        );
+        if let Some(complaint) = reviews.most_common_complaint() {
+            spawn_line(
+                parent,
+                &localization.format(
+                    "reviews.most_common",
+                    &[(
+                        "complaint",
+                        localization.term("complaint", complaint.name()).to_string(),
+                    )],
+                ),
+                BAD_COLOR,
+            );
+        }
+
+        for review in &reviews.recent {
+            let party = localization.term("party", review.party.name());
+            let day = localization.format("reviews.day", &[("day", review.day.to_string())]);
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    let stars_color = if review.is_good() {
+                        GOOD_COLOR
+                    } else {
+                        BAD_COLOR
+                    };
+                    spawn_text(row, &"★".repeat(review.stars as usize), stars_color);
+                    spawn_text(row, party, Color::WHITE);
+                    spawn_link(row, &review.room_name, Some(review.room), Color::WHITE);
+                    spawn_text(row, &day, Color::srgb(0.7, 0.7, 0.7));
+                });
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Val::Px(4.0),
+                    row_gap: Val::Px(2.0),
+                    margin: UiRect::left(Val::Px(8.0)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    let complaints = review.top_complaints(TOP_COMPLAINTS);
+                    if complaints.is_empty() {
+                        spawn_text(
+                            row,
+                            localization.t("reviews.no_complaints"),
+                            Color::srgb(0.5, 0.8, 0.5),
+                        );
+                    }
+                    for complaint in complaints {
+                        let label = localization.term("complaint", complaint.name());
+                        let room = complaint.about_room().then_some(review.room);
+                        spawn_link(row, label, room, BAD_COLOR);
+                    }
+                });
+        }
+    });
+}
+
+fn spawn_line(parent: &mut ChildBuilder, label: &str, color: Color) {
+    parent.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn spawn_text(parent: &mut ChildBuilder, label: &str, color: Color) {
+    parent.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn spawn_link(parent: &mut ChildBuilder, label: &str, room: Option<Entity>, color: Color) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            ReviewLinkButton { room },
+        ))
+        .with_children(|button| {
+            spawn_text(button, label, color);
+        });
+}
+
+fn update_review_link_colors(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ReviewLinkButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => Color::srgb(0.35, 0.35, 0.35).into(),
+            Interaction::None => Color::srgb(0.25, 0.25, 0.25).into(),
+        };
+    }
+}
+
+fn block_map_input_over_reviews_panel(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    panel_state: Res<ReviewsPanelState>,
+    interaction_query: Query<&Interaction, With<ReviewLinkButton>>,
+) {
+    let should_block = panel_state.visible
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.reviews_panel_blocking != should_block {
+        ui_blocker.reviews_panel_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
@@ -0,0 +1,571 @@
+use super::localization::{LocalizedText, Localization};
+use super::{ToolbarState, UiInputBlocker};
+use crate::components::*;
+use crate::systems::{
+    AmbienceTracks, CursorWorldPosition, GameplaySet, PointerAction, RoomNoise, ZoneLabelSettings,
+};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+const MAX_ZONE_NAME_LENGTH: usize = 32;
+
+#[derive(Component)]
+pub struct RoomInspectorPanel;
+
+#[derive(Component)]
+pub struct RoomInspectorTitle;
+
+#[derive(Component)]
+pub struct RoomInspectorDetails;
+
+#[derive(Component)]
+pub struct RenameZoneButton;
+
+#[derive(Component)]
+pub struct ZoneLabelToggleButton;
+
+#[derive(Component)]
+pub struct ZoneAccessButton;
+
+#[derive(Component)]
+pub struct ZoneAmbienceButton;
+
+#[derive(Component)]
+pub struct CloseInspectorButton;
+
+/// Which zone the inspector is showing, and the in-progress name while renaming
+#[derive(Resource, Default)]
+pub struct RoomInspectorState {
+    pub selected_zone: Option<Entity>,
+    pub editing: bool,
+    pub name_buffer: String,
+}
+
+impl RoomInspectorState {
+    /// Shows `zone`, dropping any rename in progress on the last one
+    pub fn select(&mut self, zone: Entity) {
+        self.selected_zone = Some(zone);
+        self.editing = false;
+        self.name_buffer.clear();
+    }
+
+    fn close(&mut self) {
+        self.selected_zone = None;
+        self.editing = false;
+        self.name_buffer.clear();
+    }
+}
+
+pub struct RoomInspectorPlugin;
+
+impl Plugin for RoomInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoomInspectorState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_room_inspector)
+            .add_systems(
+                Update,
+                (
+                    block_map_input_over_room_inspector,
+                    select_zone_on_click,
+                    handle_rename_zone_button,
+                    handle_zone_label_toggle_button,
+                    handle_zone_access_button,
+                    handle_zone_ambience_button,
+                    handle_close_inspector_button,
+                    handle_zone_name_input,
+                    update_room_inspector,
+                    update_room_inspector_button_colors,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_room_inspector(mut commands: Commands) {
+    // Initially hidden panel above the speed controls
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(150.0),
+                width: Val::Px(320.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None, // Hidden until a zone is selected
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            RoomInspectorPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                RoomInspectorTitle,
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                RoomInspectorDetails,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Val::Px(5.0),
+                    row_gap: Val::Px(5.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_inspector_button(row, "inspector.rename", RenameZoneButton);
+                    spawn_inspector_button(row, "inspector.labels", ZoneLabelToggleButton);
+                    spawn_inspector_button(row, "inspector.access", ZoneAccessButton);
+                    spawn_inspector_button(row, "inspector.ambience", ZoneAmbienceButton);
+                    spawn_inspector_button(row, "inspector.close", CloseInspectorButton);
+                });
+        });
+}
+
+fn spawn_inspector_button(parent: &mut ChildBuilder, label_key: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(30.0),
+                padding: UiRect::horizontal(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn block_map_input_over_room_inspector(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    state: Res<RoomInspectorState>,
+    interaction_query: Query<
+        &Interaction,
+        Or<(
+            With<RenameZoneButton>,
+            With<ZoneLabelToggleButton>,
+            With<ZoneAccessButton>,
+            With<ZoneAmbienceButton>,
+            With<CloseInspectorButton>,
+        )>,
+    >,
+) {
+    let should_block = state.selected_zone.is_some()
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.room_inspector_blocking != should_block {
+        ui_blocker.room_inspector_blocking = should_block;
+        ui_blocker.recompute();
+    }
+
+    if ui_blocker.zone_name_editing != state.editing {
+        ui_blocker.zone_name_editing = state.editing;
+        ui_blocker.recompute();
+    }
+}
+
+/// Clicking inside a zone with no build tool selected opens the inspector for it. Signs
+/// open their own editor instead.
+fn select_zone_on_click(
+    actions: Res<ButtonInput<PointerAction>>,
+    toolbar_state: Res<ToolbarState>,
+    ui_blocker: Res<UiInputBlocker>,
+    cursor: Res<CursorWorldPosition>,
+    zone_query: Query<(Entity, &Zone)>,
+    sign_query: Query<(&GridPosition, &FurnitureType, &FurnitureOrientation), With<Sign>>,
+    mut state: ResMut<RoomInspectorState>,
+) {
+    if !actions.just_pressed(PointerAction::Confirm) || ui_blocker.block_world_input {
+        return;
+    }
+
+    if toolbar_state.selected_building.is_some() || toolbar_state.selected_order.is_some() {
+        return;
+    }
+
+    if cursor.world.is_none() {
+        return;
+    }
+
+    let on_sign = cursor.grid.is_some_and(|grid_pos| {
+        sign_query.iter().any(|(pos, furniture_type, orientation)| {
+            furniture_type.covers(pos.to_ivec2(), *orientation, grid_pos)
+        })
+    });
+    if on_sign {
+        state.close();
+        return;
+    }
+
+    let clicked_zone = cursor.grid.and_then(|grid_pos| {
+        zone_query
+            .iter()
+            .find(|(_, zone)| zone.contains_tile(grid_pos))
+            .map(|(entity, _)| entity)
+    });
+
+    match clicked_zone {
+        Some(zone_entity) if state.selected_zone != Some(zone_entity) => state.select(zone_entity),
+        None if state.selected_zone.is_some() => state.close(),
+        _ => {}
+    }
+}
+
+fn handle_rename_zone_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<RenameZoneButton>)>,
+    zone_query: Query<&Zone>,
+    mut state: ResMut<RoomInspectorState>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(zone) = state
+            .selected_zone
+            .and_then(|entity| zone_query.get(entity).ok())
+        else {
+            continue;
+        };
+
+        state.name_buffer = zone.name.clone();
+        state.editing = true;
+    }
+}
+
+fn handle_zone_label_toggle_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ZoneLabelToggleButton>)>,
+    mut label_settings: ResMut<ZoneLabelSettings>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            label_settings.visible = !label_settings.visible;
+        }
+    }
+}
+
+/// Steps the selected zone on to the next access setting. Anyone already inside a zone
+/// closed to them finishes what they came for and walks out.
+fn handle_zone_access_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ZoneAccessButton>)>,
+    state: Res<RoomInspectorState>,
+    mut zone_query: Query<&mut Zone>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if let Some(mut zone) = state
+            .selected_zone
+            .and_then(|entity| zone_query.get_mut(entity).ok())
+        {
+            zone.access = zone.access.next();
+            info!("Zone '{}' is now {}", zone.name, zone.access.name());
+        }
+    }
+}
+
+fn handle_zone_ambience_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ZoneAmbienceButton>)>,
+    state: Res<RoomInspectorState>,
+    mut zone_query: Query<&mut Zone>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if let Some(mut zone) = state
+            .selected_zone
+            .and_then(|entity| zone_query.get_mut(entity).ok())
+        {
+            zone.ambience = zone.ambience.next();
+            info!("Zone '{}' now plays {}", zone.name, zone.ambience.name());
+        }
+    }
+}
+
+fn handle_close_inspector_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CloseInspectorButton>)>,
+    mut state: ResMut<RoomInspectorState>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            state.close();
+        }
+    }
+}
+
+// Typed characters go into the name buffer; Enter applies it, Escape cancels
+fn handle_zone_name_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut state: ResMut<RoomInspectorState>,
+    mut zone_query: Query<&mut Zone>,
+) {
+    if !state.editing {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let new_name = state.name_buffer.trim().to_string();
+                if let Some(mut zone) = state
+                    .selected_zone
+                    .and_then(|entity| zone_query.get_mut(entity).ok())
+                {
+                    if !new_name.is_empty() {
+                        info!("Renamed zone '{}' to '{}'", zone.name, new_name);
+                        zone.name = new_name;
+                    }
+                }
+                state.editing = false;
+            }
+            Key::Escape => {
+                state.editing = false;
+            }
+            Key::Backspace => {
+                state.name_buffer.pop();
+            }
+            Key::Space if state.name_buffer.chars().count() < MAX_ZONE_NAME_LENGTH => {
+                state.name_buffer.push(' ');
+            }
+            Key::Character(text) => {
+                for character in text.chars().filter(|c| !c.is_control()) {
+                    if state.name_buffer.chars().count() < MAX_ZONE_NAME_LENGTH {
+                        state.name_buffer.push(character);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_room_inspector(
+    mut state: ResMut<RoomInspectorState>,
+    zone_query: Query<(&Zone, Option<&ZoneVisitors>)>,
+    guest_query: Query<&Guest>,
+    noise: Res<RoomNoise>,
+    tracks: Res<AmbienceTracks>,
+    mut panel_query: Query<&mut Node, With<RoomInspectorPanel>>,
+    mut title_query: Query<&mut Text, (With<RoomInspectorTitle>, Without<RoomInspectorDetails>)>,
+    mut details_query: Query<&mut Text, (With<RoomInspectorDetails>, Without<RoomInspectorTitle>)>,
+    localization: Res<Localization>,
+) {
+    let zone = state
+        .selected_zone
+        .and_then(|entity| zone_query.get(entity).ok());
+
+    // The selected zone may have been removed since it was clicked
+    if state.selected_zone.is_some() && zone.is_none() {
+        state.close();
+    }
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        let display = if zone.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    let Some((zone, visitors)) = zone else {
+        return;
+    };
+
+    if let Ok(mut title) = title_query.get_single_mut() {
+        let label = if state.editing {
+            format!("{}_", state.name_buffer)
+        } else {
+            zone.name.clone()
+        };
+        if **title != label {
+            **title = label;
+        }
+    }
+
+    if let Ok(mut details) = details_query.get_single_mut() {
+        let mut label = localization.format(
+            "inspector.details",
+            &[
+                ("type", localization.term("zone_type", zone.zone_type.name()).to_string()),
+                ("quality", localization.term("quality", zone.quality.name()).to_string()),
+                ("stars", zone.quality.stars().to_string()),
+                ("tiles", zone.tile_count().to_string()),
+            ],
+        );
+        label.push('\n');
+        label.push_str(&localization.format(
+            "inspector.access_details",
+            &[(
+                "access",
+                localization.term("access", zone.access.name()).to_string(),
+            )],
+        ));
+        let ambience = localization.term("ambience", zone.ambience.name());
+        label.push('\n');
+        label.push_str(&localization.format(
+            "inspector.ambience_details",
+            &[("ambience", ambience.to_string())],
+        ));
+        // Tracks aren't bundled, so say which file a silent speaker is missing
+        let missing = zone
+            .ambience
+            .track()
+            .and_then(|track| tracks.missing_file(track));
+        if let Some(file) = missing {
+            label.push(' ');
+            label.push_str(
+                &localization.format("inspector.ambience_missing", &[("file", file.to_string())]),
+            );
+        }
+        if let Some(zone_entity) = state.selected_zone {
+            let level = noise.level(zone_entity);
+            let level_name = localization.term("noise", RoomNoise::describe(level));
+            label.push('\n');
+            label.push_str(&localization.format(
+                "inspector.noise",
+                &[
+                    ("level", level_name.to_string()),
+                    ("percent", format!("{:.0}", level * 100.0)),
+                ],
+            ));
+            // Only bedrooms are slept in, so only they say what the noise costs
+            if zone.zone_type == ZoneType::GuestBedroom {
+                let sleep = noise.sleep_quality(zone_entity) * 100.0;
+                label.push('\n');
+                label.push_str(&localization.format(
+                    "inspector.sleep_quality",
+                    &[("percent", format!("{:.0}", sleep))],
+                ));
+            }
+        }
+        if let Some(visitors) = visitors {
+            let visiting = state.selected_zone.map(GuestActivity::Visiting);
+            let here_now = guest_query
+                .iter()
+                .filter(|guest| Some(guest.activity) == visiting)
+                .count();
+            label.push('\n');
+            label.push_str(&localization.format(
+                "inspector.visitors",
+                &[
+                    ("now", here_now.to_string()),
+                    ("today", visitors.visits_today.to_string()),
+                    ("total", visitors.visits_total.to_string()),
+                ],
+            ));
+        }
+        if state.editing {
+            label.push_str("\n\n");
+            label.push_str(localization.t("inspector.rename_hint"));
+        }
+        if **details != label {
+            **details = label;
+        }
+    }
+}
+
+fn update_room_inspector_button_colors(
+    mut rename_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        (With<RenameZoneButton>, Without<ZoneLabelToggleButton>),
+    >,
+    mut label_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        (With<ZoneLabelToggleButton>, Without<RenameZoneButton>),
+    >,
+    mut access_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        (
+            Or<(With<ZoneAccessButton>, With<ZoneAmbienceButton>)>,
+            Without<RenameZoneButton>,
+            Without<ZoneLabelToggleButton>,
+        ),
+    >,
+    mut close_query: Query<
+        (&mut BackgroundColor, &Interaction),
+        (
+            With<CloseInspectorButton>,
+            Without<RenameZoneButton>,
+            Without<ZoneLabelToggleButton>,
+            Without<ZoneAccessButton>,
+            Without<ZoneAmbienceButton>,
+        ),
+    >,
+    state: Res<RoomInspectorState>,
+    label_settings: Res<ZoneLabelSettings>,
+) {
+    let base_color = |interaction: &Interaction| match interaction {
+        Interaction::Hovered => Color::srgb(0.35, 0.35, 0.35),
+        _ => Color::srgb(0.25, 0.25, 0.25),
+    };
+
+    for (mut color, interaction) in &mut rename_query {
+        *color = if state.editing {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else {
+            base_color(interaction).into()
+        };
+    }
+
+    for (mut color, interaction) in &mut label_query {
+        *color = if label_settings.visible {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else {
+            base_color(interaction).into()
+        };
+    }
+
+    for (mut color, interaction) in &mut access_query {
+        *color = base_color(interaction).into();
+    }
+
+    for (mut color, interaction) in &mut close_query {
+        *color = base_color(interaction).into();
+    }
+}
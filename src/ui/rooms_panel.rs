@@ -0,0 +1,582 @@
+use super::localization::{LocalizedText, Localization};
+use super::UiInputBlocker;
+use crate::components::*;
+use crate::systems::{GameplaySet, RoomRates};
+use bevy::prelude::*;
+
+const PANEL_WIDTH: f32 = 640.0;
+const ROW_HEIGHT: f32 = 28.0;
+const NAME_COLUMN_WIDTH: f32 = 170.0;
+const COLUMN_WIDTH: f32 = 80.0;
+const OCCUPANT_COLUMN_WIDTH: f32 = 150.0;
+
+#[derive(Component)]
+pub struct RoomsPanel;
+
+#[derive(Component)]
+pub struct RoomsListContent;
+
+#[derive(Component)]
+pub struct RoomSortButton;
+
+#[derive(Component)]
+pub struct RoomFilterButton;
+
+#[derive(Component)]
+pub struct RoomBlockButton {
+    pub zone: Entity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoomSort {
+    #[default]
+    Name,
+    Quality,
+    Rate,
+    Cleanliness,
+}
+
+impl RoomSort {
+    /// Localization key for the sort's name
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            RoomSort::Name => "rooms.sort.name",
+            RoomSort::Quality => "rooms.sort.quality",
+            RoomSort::Rate => "rooms.sort.rate",
+            RoomSort::Cleanliness => "rooms.sort.cleanliness",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            RoomSort::Name => RoomSort::Quality,
+            RoomSort::Quality => RoomSort::Rate,
+            RoomSort::Rate => RoomSort::Cleanliness,
+            RoomSort::Cleanliness => RoomSort::Name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoomFilter {
+    #[default]
+    All,
+    Vacant,
+    Occupied,
+    Blocked,
+}
+
+impl RoomFilter {
+    /// Localization key for the filter's name
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            RoomFilter::All => "rooms.filter.all",
+            RoomFilter::Vacant => "rooms.filter.vacant",
+            RoomFilter::Occupied => "rooms.filter.occupied",
+            RoomFilter::Blocked => "rooms.filter.blocked",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            RoomFilter::All => RoomFilter::Vacant,
+            RoomFilter::Vacant => RoomFilter::Occupied,
+            RoomFilter::Occupied => RoomFilter::Blocked,
+            RoomFilter::Blocked => RoomFilter::All,
+        }
+    }
+
+    fn matches(&self, occupied: bool, blocked: bool) -> bool {
+        match self {
+            RoomFilter::All => true,
+            RoomFilter::Vacant => !occupied && !blocked,
+            RoomFilter::Occupied => occupied,
+            RoomFilter::Blocked => blocked,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct RoomsPanelState {
+    pub visible: bool,
+    pub sort: RoomSort,
+    pub filter: RoomFilter,
+}
+
+pub struct RoomsPanelPlugin;
+
+impl Plugin for RoomsPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoomsPanelState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_rooms_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_rooms_panel_toggle,
+                    apply_rooms_panel_visibility,
+                    handle_sort_and_filter_buttons,
+                    handle_block_button_clicks,
+                    update_rooms_list,
+                    update_rooms_panel_button_colors,
+                    block_map_input_over_rooms_panel,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_rooms_panel(mut commands: Commands) {
+    // Initially hidden panel, to the right of the economy panel
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(440.0),
+                top: Val::Px(60.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            RoomsPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("rooms.title"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_panel_button(row, RoomSortButton);
+                    spawn_panel_button(row, RoomFilterButton);
+                });
+
+            // Container used for rebuilding the room rows
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    ..default()
+                },
+                RoomsListContent,
+            ));
+        });
+}
+
+fn spawn_panel_button(parent: &mut ChildBuilder, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(28.0),
+                padding: UiRect::horizontal(Val::Px(8.0)),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            marker,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_rooms_panel_toggle(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel_state: ResMut<RoomsPanelState>,
+    ui_blocker: Res<UiInputBlocker>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::KeyG) {
+        panel_state.visible = !panel_state.visible;
+    }
+}
+
+fn apply_rooms_panel_visibility(
+    panel_state: Res<RoomsPanelState>,
+    mut panel_query: Query<&mut Node, With<RoomsPanel>>,
+) {
+    if !panel_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        style.display = if panel_state.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn handle_sort_and_filter_buttons(
+    sort_query: Query<&Interaction, (Changed<Interaction>, With<RoomSortButton>)>,
+    filter_query: Query<&Interaction, (Changed<Interaction>, With<RoomFilterButton>)>,
+    mut panel_state: ResMut<RoomsPanelState>,
+) {
+    if sort_query.iter().any(|i| *i == Interaction::Pressed) {
+        panel_state.sort = panel_state.sort.next();
+    }
+    if filter_query.iter().any(|i| *i == Interaction::Pressed) {
+        panel_state.filter = panel_state.filter.next();
+    }
+}
+
+fn handle_block_button_clicks(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &RoomBlockButton), Changed<Interaction>>,
+    zone_query: Query<(&Zone, Has<RoomBlocked>)>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Ok((zone, blocked)) = zone_query.get(button.zone) else {
+            continue;
+        };
+
+        if blocked {
+            commands.entity(button.zone).remove::<RoomBlocked>();
+            info!("{} is open for booking again", zone.name);
+        } else {
+            commands.entity(button.zone).insert(RoomBlocked);
+            info!("{} is blocked from booking", zone.name);
+        }
+    }
+}
+
+struct RoomRow {
+    entity: Entity,
+    name: String,
+    quality: ZoneQuality,
+    rate: i32,
+    occupant: Option<String>,
+    cleanliness: Option<u32>,
+    blocked: bool,
+    privacy: Option<PrivacyIssue>,
+}
+
+fn update_rooms_list(
+    mut commands: Commands,
+    panel_state: Res<RoomsPanelState>,
+    rates: Res<RoomRates>,
+    content_query: Query<Entity, With<RoomsListContent>>,
+    children_query: Query<&Children>,
+    zone_query: Query<(
+        Entity,
+        &Zone,
+        Option<&Booking>,
+        Option<&Cleanliness>,
+        Has<RoomBlocked>,
+        Option<&PrivacyIssue>,
+    )>,
+    changed_query: Query<
+        (),
+        Or<(
+            Changed<Zone>,
+            Changed<Booking>,
+            Changed<Cleanliness>,
+            Changed<RoomBlocked>,
+            Changed<PrivacyIssue>,
+        )>,
+    >,
+    mut removed_bookings: RemovedComponents<Booking>,
+    mut removed_blocks: RemovedComponents<RoomBlocked>,
+    mut removed_zones: RemovedComponents<Zone>,
+    mut removed_privacy: RemovedComponents<PrivacyIssue>,
+    localization: Res<Localization>,
+) {
+    let removed = removed_bookings.read().count()
+        + removed_blocks.read().count()
+        + removed_zones.read().count()
+        + removed_privacy.read().count()
+        > 0;
+
+    if !panel_state.visible {
+        return;
+    }
+
+    // Only rebuild when the panel opens, the view or language changes, or a room changes
+    if !(panel_state.is_changed()
+        || rates.is_changed()
+        || localization.is_changed()
+        || removed
+        || !changed_query.is_empty())
+    {
+        return;
+    }
+
+    let Ok(content_entity) = content_query.get_single() else {
+        return;
+    };
+
+    let mut rows: Vec<RoomRow> = zone_query
+        .iter()
+        .filter(|(_, zone, ..)| zone.zone_type == ZoneType::GuestBedroom)
+        // Rooms that aren't private can't be booked, so they list with the blocked ones
+        .filter(|(_, _, booking, _, blocked, privacy)| {
+            panel_state
+                .filter
+                .matches(booking.is_some(), *blocked || privacy.is_some())
+        })
+        .map(|(entity, zone, booking, cleanliness, blocked, privacy)| RoomRow {
+            entity,
+            name: zone.name.clone(),
+            quality: zone.quality,
+            rate: rates.rate(zone.quality),
+            occupant: booking.map(|booking| {
+                localization.format(
+                    "rooms.occupant",
+                    &[
+                        ("party", localization.term("party", booking.party.name()).to_string()),
+                        ("nights", booking.nights_remaining.to_string()),
+                    ],
+                )
+            }),
+            cleanliness: cleanliness.map(Cleanliness::percent),
+            blocked,
+            privacy: privacy.copied(),
+        })
+        .collect();
+
+    match panel_state.sort {
+        RoomSort::Name => rows.sort_by(|a, b| a.name.cmp(&b.name)),
+        // Best and priciest first, dirtiest first so rooms needing attention stand out
+        RoomSort::Quality => rows.sort_by_key(|row| std::cmp::Reverse(row.quality)),
+        RoomSort::Rate => rows.sort_by_key(|row| std::cmp::Reverse(row.rate)),
+        RoomSort::Cleanliness => rows.sort_by_key(|row| row.cleanliness),
+    }
+
+    // Remove old rows
+    if let Ok(children) = children_query.get(content_entity) {
+        for &child in children.iter() {
+            commands.entity(child).despawn_recursive();
+        }
+    }
+
+    commands.entity(content_entity).with_children(|parent| {
+        spawn_header_row(parent, &localization);
+
+        if rows.is_empty() {
+            parent.spawn((
+                Text::new(localization.t("rooms.empty")),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        }
+
+        for row in &rows {
+            spawn_room_row(parent, row, &localization);
+        }
+    });
+}
+
+fn spawn_header_row(parent: &mut ChildBuilder, localization: &Localization) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|row| {
+            let header = |key| localization.t(key);
+            spawn_cell(row, header("rooms.header.room"), NAME_COLUMN_WIDTH, Color::WHITE);
+            spawn_cell(row, header("rooms.header.quality"), COLUMN_WIDTH, Color::WHITE);
+            spawn_cell(row, header("rooms.header.rate"), COLUMN_WIDTH, Color::WHITE);
+            spawn_cell(row, header("rooms.header.occupant"), OCCUPANT_COLUMN_WIDTH, Color::WHITE);
+            spawn_cell(row, header("rooms.header.clean"), COLUMN_WIDTH, Color::WHITE);
+        });
+}
+
+fn spawn_room_row(parent: &mut ChildBuilder, room: &RoomRow, localization: &Localization) {
+    let occupant = match (&room.occupant, room.blocked, room.privacy) {
+        (Some(occupant), ..) => occupant.clone(),
+        (None, true, _) => localization.t("rooms.blocked").to_string(),
+        (None, false, Some(_)) => localization.t("rooms.not_private").to_string(),
+        (None, false, None) => localization.t("rooms.vacant").to_string(),
+    };
+    let occupant_color = if room.blocked || (room.occupant.is_none() && room.privacy.is_some()) {
+        Color::srgb(0.9, 0.4, 0.3)
+    } else if room.occupant.is_some() {
+        Color::srgb(0.2, 0.8, 0.2)
+    } else {
+        Color::srgb(0.7, 0.7, 0.7)
+    };
+    let cleanliness = room
+        .cleanliness
+        .map(|percent| format!("{}%", percent))
+        .unwrap_or_else(|| "-".to_string());
+
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_cell(row, &room.name, NAME_COLUMN_WIDTH, Color::WHITE);
+            let quality = localization.term("quality", room.quality.name());
+            spawn_cell(row, quality, COLUMN_WIDTH, Color::WHITE);
+            spawn_cell(row, &format!("${}", room.rate), COLUMN_WIDTH, Color::WHITE);
+            spawn_cell(row, &occupant, OCCUPANT_COLUMN_WIDTH, occupant_color);
+            spawn_cell(row, &cleanliness, COLUMN_WIDTH, Color::WHITE);
+
+            row.spawn((
+                Button,
+                Node {
+                    width: Val::Px(COLUMN_WIDTH),
+                    height: Val::Px(ROW_HEIGHT),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                RoomBlockButton { zone: room.entity },
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(localization.t(if room.blocked {
+                        "rooms.unblock"
+                    } else {
+                        "rooms.block"
+                    })),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        });
+
+    // Say why the room can't be let, so it's clear why it earns nothing
+    if let Some(privacy) = room.privacy {
+        parent.spawn((
+            Text::new(localization.format(
+                "rooms.privacy",
+                &[("reason", localization.t(privacy.reason_key()).to_string())],
+            )),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.4, 0.3)),
+            Node {
+                margin: UiRect::left(Val::Px(8.0)),
+                ..default()
+            },
+        ));
+    }
+}
+
+fn spawn_cell(parent: &mut ChildBuilder, label: &str, width: f32, color: Color) {
+    parent.spawn((
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(ROW_HEIGHT),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Text::new(label),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn update_rooms_panel_button_colors(
+    panel_state: Res<RoomsPanelState>,
+    mut button_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            &Children,
+            Has<RoomSortButton>,
+            Has<RoomFilterButton>,
+        ),
+        Or<(
+            With<RoomSortButton>,
+            With<RoomFilterButton>,
+            With<RoomBlockButton>,
+        )>,
+    >,
+    mut text_query: Query<&mut Text>,
+    localization: Res<Localization>,
+) {
+    for (interaction, mut color, children, is_sort, is_filter) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => Color::srgb(0.35, 0.35, 0.35).into(),
+            Interaction::None => Color::srgb(0.25, 0.25, 0.25).into(),
+        };
+
+        let label = if is_sort {
+            let sort = localization.t(panel_state.sort.name_key()).to_string();
+            localization.format("rooms.sort", &[("sort", sort)])
+        } else if is_filter {
+            let filter = localization.t(panel_state.filter.name_key()).to_string();
+            localization.format("rooms.show", &[("filter", filter)])
+        } else {
+            continue;
+        };
+
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                if **text != label {
+                    **text = label.clone();
+                }
+            }
+        }
+    }
+}
+
+fn block_map_input_over_rooms_panel(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    panel_state: Res<RoomsPanelState>,
+    interaction_query: Query<
+        &Interaction,
+        Or<(
+            With<RoomSortButton>,
+            With<RoomFilterButton>,
+            With<RoomBlockButton>,
+        )>,
+    >,
+) {
+    let should_block = panel_state.visible
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.rooms_panel_blocking != should_block {
+        ui_blocker.rooms_panel_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
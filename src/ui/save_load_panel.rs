@@ -1,23 +1,25 @@
+use bevy::image::ImageSampler;
 use bevy::prelude::*;
-use bevy::ecs::system::SystemParam;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::tasks::{block_on, futures_lite::future, IoTaskPool, Task};
 use std::fs;
 use std::path::Path;
+use std::time::SystemTime;
 
+use super::localization::{Localization, LocalizedText};
 use crate::components::*;
+use crate::systems::app_state::GameplaySet;
+use crate::systems::economy::Money;
 use crate::systems::grid::GridSettings;
-use crate::systems::{save_load::SaveLoadConfig, BuildingMap};
-
-#[derive(SystemParam)]
-struct ClearQueries<'w, 's> {
-    walls: Query<'w, 's, Entity, With<Wall>>,
-    floors: Query<'w, 's, Entity, With<Floor>>,
-    doors: Query<'w, 's, Entity, With<Door>>,
-    furniture: Query<'w, 's, Entity, With<Furniture>>,
-    blueprints: Query<'w, 's, Entity, With<Blueprint>>,
-    construction_jobs: Query<'w, 's, Entity, With<ConstructionJob>>,
-    deconstruction_jobs: Query<'w, 's, Entity, With<DeconstructionJob>>,
-    markers: Query<'w, 's, Entity, With<DeconstructionMarker>>,
-}
+use crate::systems::save_load::{
+    read_save_metadata, ClearQueries, MapSettings, PlayTime, ReferenceHolders, ReferenceSources,
+    SaveFilesChanged, SaveFormat, SaveLoadConfig, SaveLoadedEvent, SaveMetadata,
+    SaveMetadataSources, SaveThumbnail, SAVE_DIR,
+};
+use crate::systems::stable_id::EntityRelinker;
+use crate::systems::statistics::StatsHistory;
+use crate::systems::{shared_assets::SharedAssets, BuildingMap};
 
 #[derive(Component)]
 pub struct SaveLoadPanel;
@@ -52,12 +54,33 @@ pub struct SaveListContainer;
 #[derive(Component)]
 pub struct SaveNameText;
 
+/// Switches new saves between plain and compressed JSON
+#[derive(Component)]
+pub struct SaveFormatButton;
+
+/// A save file found in the saves folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveEntry {
+    pub filename: String,
+    /// When the file was last written, if the file system says
+    pub modified: Option<SystemTime>,
+    /// Date, money and thumbnail, for saves that have them
+    pub metadata: Option<SaveMetadata>,
+}
+
 #[derive(Resource, Default)]
 pub struct SaveLoadPanelState {
     pub visible: bool,
     pub current_save_name: String,
-    pub saves_list: Vec<String>,
+    /// Saves found by the last scan, most recently written first
+    pub saves: Vec<SaveEntry>,
+    /// Bumped whenever a scan finds something different, so the list is only rebuilt then
+    listing_version: u32,
+    /// Set when the saves folder should be scanned again
+    refresh_pending: bool,
     pub editing_mode: bool,
+    /// Format new saves are written in
+    pub format: SaveFormat,
 }
 
 impl SaveLoadPanelState {
@@ -65,29 +88,62 @@ impl SaveLoadPanelState {
         self.visible = !self.visible;
     }
 
+    /// Asks for the saves folder to be scanned again. The scan runs off the main thread
+    /// and the list updates once it is done.
     pub fn refresh_saves_list(&mut self) {
-        self.saves_list.clear();
-        if let Ok(entries) = fs::read_dir("assets/saves") {
-            for entry in entries.flatten() {
-                if let Some(filename) = entry.file_name().to_str() {
-                    if filename.ends_with(".json") {
-                        // Only add if the name without .json extension is not empty
-                        let name_without_ext = filename.trim_end_matches(".json");
-                        if !name_without_ext.is_empty() {
-                            self.saves_list.push(filename.to_string());
-                            info!("Added save file: {}", filename);
-                        } else {
-                            warn!("Skipping save file with empty name: {}", filename);
-                        }
-                    }
-                }
-            }
+        self.refresh_pending = true;
+    }
+
+    fn set_saves(&mut self, saves: Vec<SaveEntry>) {
+        if self.saves != saves {
+            self.saves = saves;
+            self.listing_version = self.listing_version.wrapping_add(1);
         }
-        self.saves_list.sort();
-        info!("Total saves in list: {}", self.saves_list.len());
     }
 }
 
+/// Every save in `dir` with its details, most recently written first. Reads each file's
+/// header, so it belongs on a background task.
+fn scan_saves(dir: &str) -> Vec<SaveEntry> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut saves: Vec<SaveEntry> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let filename = entry.file_name().to_str()?.to_string();
+            // Only add saves whose name without the extension is not empty
+            if !SaveFormat::is_save_file(&filename) {
+                return None;
+            }
+
+            let path = format!("{}/{}", dir, filename);
+            let metadata = match read_save_metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(err) => {
+                    warn!("Failed to read details of {}: {}", path, err);
+                    None
+                }
+            };
+            let modified = entry.metadata().and_then(|file| file.modified()).ok();
+            Some(SaveEntry {
+                filename,
+                modified,
+                metadata,
+            })
+        })
+        .collect();
+
+    // Newest first; saves the file system can't date go last, by name
+    saves.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.filename.cmp(&b.filename))
+    });
+    saves
+}
+
 pub struct SaveLoadPanelPlugin;
 
 impl Plugin for SaveLoadPanelPlugin {
@@ -99,9 +155,11 @@ impl Plugin for SaveLoadPanelPlugin {
                 (
                     update_panel_visibility,
                     handle_save_button,
+                    handle_save_format_button,
                     handle_load_button,
                     handle_rename_button,
-                ),
+                )
+                    .in_set(GameplaySet),
             )
             .add_systems(
                 Update,
@@ -109,15 +167,16 @@ impl Plugin for SaveLoadPanelPlugin {
                     handle_delete_button,
                     handle_keyboard_input,
                     update_save_name_display,
+                    refresh_on_save_files_changed,
+                    scan_saves_in_background,
                     update_save_list,
-                ),
+                )
+                    .in_set(GameplaySet),
             );
     }
 }
 
 fn setup_save_load_panel(mut commands: Commands, mut state: ResMut<SaveLoadPanelState>) {
-    // Refresh saves list on startup
-    state.refresh_saves_list();
     state.current_save_name = "my_resort".to_string();
 
     // Create the save/load panel (hidden by default)
@@ -141,7 +200,8 @@ fn setup_save_load_panel(mut commands: Commands, mut state: ResMut<SaveLoadPanel
         .with_children(|parent| {
             // Title
             parent.spawn((
-                Text::new("Save / Load"),
+                Text::default(),
+                LocalizedText::new("save_load.title"),
                 TextFont {
                     font_size: 24.0,
                     ..default()
@@ -160,7 +220,8 @@ fn setup_save_load_panel(mut commands: Commands, mut state: ResMut<SaveLoadPanel
                 })
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Save name:"),
+                        Text::default(),
+                        LocalizedText::new("save_load.save_name"),
                         TextFont {
                             font_size: 16.0,
                             ..default()
@@ -209,7 +270,8 @@ fn setup_save_load_panel(mut commands: Commands, mut state: ResMut<SaveLoadPanel
                 ))
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Save Game"),
+                        Text::default(),
+                        LocalizedText::new("save_load.save"),
                         TextFont {
                             font_size: 18.0,
                             ..default()
@@ -218,44 +280,71 @@ fn setup_save_load_panel(mut commands: Commands, mut state: ResMut<SaveLoadPanel
                     ));
                 });
 
-            // Separator
-            parent.spawn((
-                Text::new("Saved Games:"),
-                TextFont {
-                    font_size: 18.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-            ));
-
-            // Saves list container (scrollable)
+            // Format toggle
             parent
                 .spawn((
+                    Button,
                     Node {
                         width: Val::Percent(100.0),
-                        height: Val::Px(300.0),
-                        flex_direction: FlexDirection::Column,
-                        row_gap: Val::Px(5.0),
-                        overflow: Overflow::scroll_y(),
+                        height: Val::Px(30.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
                         ..default()
                     },
-                    BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
-                    SaveListContainer,
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    SaveFormatButton,
                 ))
                 .with_children(|parent| {
-                    // List will be populated dynamically
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new(save_format_label_key(SaveFormat::default())),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
                 });
+
+            // Separator
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("save_load.saved_games"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            // Saves list container (scrollable), populated dynamically
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(300.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(5.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                SaveListContainer,
+            ));
         });
 }
 
 fn update_panel_visibility(
-    state: Res<SaveLoadPanelState>,
+    mut state: ResMut<SaveLoadPanelState>,
     mut panel_query: Query<&mut Node, With<SaveLoadPanel>>,
     mut last_visible: Local<bool>,
 ) {
     // Check if visibility actually changed
     if state.visible != *last_visible {
         *last_visible = state.visible;
+        // Saves may have come and gone since the panel was last open
+        if state.visible {
+            state.refresh_saves_list();
+        }
 
         for mut style in &mut panel_query {
             style.display = if state.visible {
@@ -267,21 +356,54 @@ fn update_panel_visibility(
     }
 }
 
+/// Saves written from outside the panel, such as with the save hotkey, show up in the
+/// list while it is open
+fn refresh_on_save_files_changed(
+    mut events: EventReader<SaveFilesChanged>,
+    mut state: ResMut<SaveLoadPanelState>,
+) {
+    if events.read().count() > 0 && state.visible {
+        state.refresh_saves_list();
+    }
+}
+
+/// Starts a scan of the saves folder when one is asked for, and hands its results to the
+/// panel once it finishes. Only one scan runs at a time; a request made during one starts
+/// another after it.
+fn scan_saves_in_background(
+    mut state: ResMut<SaveLoadPanelState>,
+    mut scan: Local<Option<Task<Vec<SaveEntry>>>>,
+) {
+    if let Some(task) = scan.as_mut() {
+        let Some(saves) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        *scan = None;
+        info!("Found {} saves", saves.len());
+        state.set_saves(saves);
+    }
+
+    if state.refresh_pending {
+        state.refresh_pending = false;
+        *scan = Some(IoTaskPool::get().spawn(async { scan_saves(SAVE_DIR) }));
+    }
+}
+
 fn update_save_list(
     mut commands: Commands,
     state: Res<SaveLoadPanelState>,
+    localization: Res<Localization>,
+    mut images: ResMut<Assets<Image>>,
     list_container_query: Query<Entity, With<SaveListContainer>>,
-    mut last_saves_list: Local<Option<Vec<String>>>,
+    mut last_version: Local<Option<u32>>,
 ) {
-    // Only rebuild if saves list actually changed (different files, not just refreshed)
-    let saves_changed = last_saves_list.as_ref()
-        .map_or(true, |last| last != &state.saves_list);
-
-    if !saves_changed {
+    // Only rebuild if a scan found different files or details, or the details need
+    // translating again
+    let saves_changed = *last_version != Some(state.listing_version);
+    if !saves_changed && !localization.is_changed() {
         return;
     }
-
-    *last_saves_list = Some(state.saves_list.clone());
+    *last_version = Some(state.listing_version);
 
     // Clear all existing children of the container
     let Ok(container) = list_container_query.get_single() else {
@@ -291,30 +413,70 @@ fn update_save_list(
     commands.entity(container).despawn_descendants();
 
     commands.entity(container).with_children(|parent| {
-        info!("Rebuilding save list UI with {} entries", state.saves_list.len());
-        for save_name in &state.saves_list {
-            let display_name = save_name.trim_end_matches(".json");
+        info!("Rebuilding save list UI with {} entries", state.saves.len());
+        for save in &state.saves {
+            let save_name = &save.filename;
+            let display_name = SaveFormat::strip_extension(save_name);
             info!("Creating UI entry for: '{}' (display: '{}')", save_name, display_name);
+            let metadata = save.metadata.as_ref();
+            let details = metadata.map_or_else(
+                || localization.t("save_load.no_details").to_string(),
+                |metadata| save_details_text(metadata, &localization),
+            );
+            let thumbnail = metadata.and_then(|metadata| {
+                thumbnail_image(&metadata.thumbnail).map(|image| {
+                    (images.add(image), thumbnail_size(&metadata.thumbnail))
+                })
+            });
+
             // Container for each save item
             parent
                 .spawn(Node {
                     width: Val::Percent(100.0),
-                    height: Val::Px(40.0),
+                    height: Val::Px(52.0),
                     flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
                     column_gap: Val::Px(5.0),
                     margin: UiRect::all(Val::Px(2.0)),
                     ..default()
                 })
                 .with_children(|parent| {
+                    // Thumbnail, letterboxed so every entry lines up
+                    parent
+                        .spawn((
+                            Node {
+                                width: Val::Px(SaveThumbnail::MAX_WIDTH as f32),
+                                height: Val::Px(SaveThumbnail::MAX_HEIGHT as f32),
+                                flex_shrink: 0.0,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.08, 0.08, 0.08)),
+                        ))
+                        .with_children(|parent| {
+                            if let Some((image, size)) = thumbnail {
+                                parent.spawn((
+                                    ImageNode::new(image),
+                                    Node {
+                                        width: Val::Px(size.x),
+                                        height: Val::Px(size.y),
+                                        ..default()
+                                    },
+                                ));
+                            }
+                        });
+
                     // Load button
                     parent
                         .spawn((
                             Button,
                             Node {
-                                width: Val::Percent(60.0),
-                                height: Val::Px(35.0),
+                                flex_grow: 1.0,
+                                height: Val::Px(46.0),
+                                flex_direction: FlexDirection::Column,
                                 justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
+                                padding: UiRect::horizontal(Val::Px(6.0)),
                                 ..default()
                             },
                             BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
@@ -331,6 +493,14 @@ fn update_save_list(
                                 },
                                 TextColor(Color::WHITE),
                             ));
+                            parent.spawn((
+                                Text::new(details),
+                                TextFont {
+                                    font_size: 10.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                            ));
                         });
 
                     // Rename button
@@ -338,7 +508,7 @@ fn update_save_list(
                         .spawn((
                             Button,
                             Node {
-                                width: Val::Percent(20.0),
+                                width: Val::Px(55.0),
                                 height: Val::Px(35.0),
                                 justify_content: JustifyContent::Center,
                                 align_items: AlignItems::Center,
@@ -351,7 +521,8 @@ fn update_save_list(
                         ))
                         .with_children(|parent| {
                             parent.spawn((
-                                Text::new("Rename"),
+                                Text::default(),
+                                LocalizedText::new("save_load.rename"),
                                 TextFont {
                                     font_size: 12.0,
                                     ..default()
@@ -365,7 +536,7 @@ fn update_save_list(
                         .spawn((
                             Button,
                             Node {
-                                width: Val::Percent(20.0),
+                                width: Val::Px(55.0),
                                 height: Val::Px(35.0),
                                 justify_content: JustifyContent::Center,
                                 align_items: AlignItems::Center,
@@ -378,7 +549,8 @@ fn update_save_list(
                         ))
                         .with_children(|parent| {
                             parent.spawn((
-                                Text::new("Delete"),
+                                Text::default(),
+                                LocalizedText::new("save_load.delete"),
                                 TextFont {
                                     font_size: 12.0,
                                     ..default()
@@ -391,14 +563,74 @@ fn update_save_list(
     });
 }
 
+/// One line under the save's name: the date in game, money, guests and time played
+fn save_details_text(metadata: &SaveMetadata, localization: &Localization) -> String {
+    let minutes = (metadata.play_time_secs / 60.0) as u32;
+    localization.format(
+        "save_load.details",
+        &[
+            ("day", metadata.day.to_string()),
+            (
+                "time",
+                format!("{:02}:{:02}", metadata.hour as u32, (metadata.hour.fract() * 60.0) as u32),
+            ),
+            ("money", metadata.money.to_string()),
+            ("guests", metadata.guests.to_string()),
+            ("played", format!("{}h {:02}m", minutes / 60, minutes % 60)),
+        ],
+    )
+}
+
+fn thumbnail_image(thumbnail: &SaveThumbnail) -> Option<Image> {
+    let mut image = Image::new(
+        Extent3d {
+            width: thumbnail.width,
+            height: thumbnail.height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        thumbnail.rgba()?,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // Keep tiles crisp when the thumbnail is scaled up
+    image.sampler = ImageSampler::nearest();
+    Some(image)
+}
+
+/// Scales the thumbnail up to fill its box without stretching it
+fn thumbnail_size(thumbnail: &SaveThumbnail) -> Vec2 {
+    let size = Vec2::new(thumbnail.width as f32, thumbnail.height as f32);
+    let fit = Vec2::new(
+        SaveThumbnail::MAX_WIDTH as f32,
+        SaveThumbnail::MAX_HEIGHT as f32,
+    ) / size;
+    size * fit.min_element()
+}
+
 fn handle_save_button(
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<SaveButton>)>,
-    state: Res<SaveLoadPanelState>,
+    mut state: ResMut<SaveLoadPanelState>,
     mut config: ResMut<SaveLoadConfig>,
-    wall_query: Query<&GridPosition, With<Wall>>,
+    wall_query: Query<(&GridPosition, &Wall)>,
     floor_query: Query<(&GridPosition, &Floor)>,
-    door_query: Query<(&GridPosition, &Door)>,
-    furniture_query: Query<(&GridPosition, &Furniture, &FurnitureType, &FurnitureOrientation)>,
+    door_query: Query<(&GridPosition, &Door, &DoorMode)>,
+    window_query: Query<(&GridPosition, &crate::components::Window, Option<&StableId>)>,
+    boundary_query: Query<(&GridPosition, &Boundary)>,
+    furniture_query: Query<(
+        &GridPosition,
+        &Furniture,
+        &FurnitureType,
+        &FurnitureOrientation,
+        Option<&Sign>,
+        Option<&StableId>,
+    )>,
+    zone_query: Query<(&Zone, Option<&StableId>)>,
+    pawn_query: Query<(Entity, &Pawn, Option<&StableId>)>,
+    stats: Res<StatsHistory>,
+    metadata_sources: SaveMetadataSources,
+    map_settings: MapSettings,
+    references: ReferenceSources,
 ) {
     for (interaction, mut color) in &mut interaction_query {
         match *interaction {
@@ -406,24 +638,40 @@ fn handle_save_button(
                 *color = BackgroundColor(Color::srgb(0.15, 0.5, 0.15));
 
                 // Save the game
-                let filename = if state.current_save_name.is_empty() {
-                    "unnamed_save.json".to_string()
+                let name = if state.current_save_name.is_empty() {
+                    "unnamed_save"
                 } else {
-                    format!("{}.json", state.current_save_name.trim_end_matches(".json"))
+                    SaveFormat::strip_extension(&state.current_save_name)
                 };
+                let filename = format!("{}{}", name, state.format.extension());
 
                 let path = format!("assets/saves/{}", filename);
 
                 // Use the existing save logic
                 use crate::systems::save_load::{collect_save_data, write_save_file, sort_save_data};
-                let mut data = collect_save_data(&wall_query, &floor_query, &door_query, &furniture_query);
+                let mut data = collect_save_data(
+                    &wall_query,
+                    &floor_query,
+                    &door_query,
+                    &window_query,
+                    &boundary_query,
+                    &furniture_query,
+                    &zone_query,
+                    &pawn_query,
+                    &stats,
+                );
                 sort_save_data(&mut data);
+                map_settings.capture(&mut data);
+                references.capture(&mut data);
+                data.metadata = Some(metadata_sources.capture(&data));
 
                 if let Err(err) = write_save_file(&path, &data) {
                     error!("Failed to save to {}: {}", path, err);
                 } else {
                     info!("Saved game to {}", path);
                     config.path = path;
+                    // Show the new save, or the fresh details of the one overwritten
+                    state.refresh_saves_list();
                 }
             }
             Interaction::Hovered => {
@@ -436,6 +684,44 @@ fn handle_save_button(
     }
 }
 
+fn save_format_label_key(format: SaveFormat) -> &'static str {
+    match format {
+        SaveFormat::Json => "save_load.format_json",
+        SaveFormat::CompressedJson => "save_load.format_compressed",
+    }
+}
+
+fn handle_save_format_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &Children),
+        (Changed<Interaction>, With<SaveFormatButton>),
+    >,
+    mut label_query: Query<&mut LocalizedText>,
+    mut state: ResMut<SaveLoadPanelState>,
+) {
+    for (interaction, mut color, children) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                state.format = match state.format {
+                    SaveFormat::Json => SaveFormat::CompressedJson,
+                    SaveFormat::CompressedJson => SaveFormat::Json,
+                };
+                for &child in children.iter() {
+                    if let Ok(mut label) = label_query.get_mut(child) {
+                        label.key = save_format_label_key(state.format).to_string();
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(Color::srgb(0.35, 0.35, 0.35));
+            }
+            Interaction::None => {
+                *color = BackgroundColor(Color::srgb(0.25, 0.25, 0.25));
+            }
+        }
+    }
+}
+
 fn handle_load_button(
     mut interaction_query: Query<
         (&Interaction, &LoadButton, &mut BackgroundColor),
@@ -443,12 +729,18 @@ fn handle_load_button(
     >,
     mut config: ResMut<SaveLoadConfig>,
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    shared_assets: Res<SharedAssets>,
     asset_server: Res<AssetServer>,
     grid_settings: Res<GridSettings>,
     mut building_map: ResMut<BuildingMap>,
     clear_queries: ClearQueries,
+    mut references: ReferenceHolders,
+    mut stats: ResMut<StatsHistory>,
+    mut play_time: ResMut<PlayTime>,
+    mut money: ResMut<Money>,
+    mut map_settings: MapSettings,
+    mut relinker: EntityRelinker,
+    mut loaded: EventWriter<SaveLoadedEvent>,
     mut state: ResMut<SaveLoadPanelState>,
 ) {
     for (interaction, load_btn, mut color) in &mut interaction_query {
@@ -458,14 +750,29 @@ fn handle_load_button(
 
                 // Load the game
                 let path = format!("assets/saves/{}", load_btn.filename);
-                config.path = path.clone();
 
-                use crate::systems::save_load::{read_or_create_save_file, clear_structures, apply_save_data};
+                use crate::systems::save_load::{
+                    apply_money, apply_play_time, apply_save_data, clear_structures,
+                    read_or_create_save_file, repair_save_data,
+                };
 
-                let (data, source) = read_or_create_save_file(&path);
+                let (mut data, source) = match read_or_create_save_file(&path) {
+                    Ok(read) => read,
+                    Err(err) => {
+                        error!(
+                            "Failed to read {}: {}. Keeping the current resort.",
+                            path, err
+                        );
+                        continue;
+                    }
+                };
+                // Quick saves go to the file loaded, once it has been read
+                config.path = path.clone();
+                let repairs = repair_save_data(&mut data, &grid_settings);
                 clear_structures(
                     &mut commands,
                     &clear_queries.walls,
+                    &clear_queries.boundaries,
                     &clear_queries.floors,
                     &clear_queries.doors,
                     &clear_queries.furniture,
@@ -473,21 +780,35 @@ fn handle_load_button(
                     &clear_queries.construction_jobs,
                     &clear_queries.deconstruction_jobs,
                     &clear_queries.markers,
+                    &clear_queries.zones,
                 );
                 apply_save_data(
                     &mut commands,
-                    &mut meshes,
-                    &mut materials,
+                    &shared_assets,
                     &asset_server,
                     &grid_settings,
                     &mut building_map,
+                    &mut relinker.stable_ids,
+                    &data,
+                );
+                relinker.relink();
+                references.apply(
+                    &mut commands,
+                    &mut relinker.stable_ids,
+                    &grid_settings,
                     &data,
                 );
+                *stats = data.stats.clone();
+                apply_play_time(&data, &mut play_time);
+                apply_money(&data, &mut money);
+                map_settings.apply(&data, &grid_settings);
+                loaded.send(SaveLoadedEvent { repairs });
 
                 info!("Loaded game from {}", source);
 
                 // Update current save name
-                state.current_save_name = load_btn.filename.trim_end_matches(".json").to_string();
+                state.current_save_name =
+                    SaveFormat::strip_extension(&load_btn.filename).to_string();
 
                 // Close panel after loading
                 state.visible = false;
@@ -734,8 +1055,10 @@ fn handle_rename_button(
             Interaction::Pressed => {
                 *color = BackgroundColor(Color::srgb(0.3, 0.3, 0.15));
 
-                // Set the current name to the old name (without .json)
-                state.current_save_name = rename_btn.old_filename.trim_end_matches(".json").to_string();
+                // Set the current name to the old name (without the extension)
+                state.current_save_name =
+                    SaveFormat::strip_extension(&rename_btn.old_filename).to_string();
+                state.format = SaveFormat::from_path(&rename_btn.old_filename);
                 info!("Set save name to {} for renaming", state.current_save_name);
             }
             Interaction::Hovered => {
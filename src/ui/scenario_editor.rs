@@ -0,0 +1,585 @@
+use super::localization::{Localization, LocalizedText};
+use super::{Notification, ToolbarState, UiInputBlocker};
+use crate::components::*;
+use crate::systems::demand::GuestSpawnSettings;
+use crate::systems::save_load::{
+    collect_save_data, list_save_files, sort_save_data, write_save_file, MapSettings, SaveFormat,
+    SaveMetadata, SaveThumbnail,
+};
+use crate::systems::statistics::StatsHistory;
+use crate::systems::terrain::{Terrain, TerrainBrush};
+use crate::systems::time_control::GameClock;
+use crate::systems::{BuildingMap, CursorWorldPosition, GameRules, GameplaySet, PointerAction};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+/// Where exported scenarios go, and where the New Game panel looks for them
+pub const SCENARIO_DIR: &str = "assets/scenarios";
+
+const MAX_SCENARIO_NAME_LENGTH: usize = 24;
+/// Tiles painted either side of the cursor
+const BRUSH_RADIUS: i32 = 1;
+const ARRIVAL_RATE_STEP: f32 = 0.25;
+const STARTING_MONEY_STEP: i32 = 1000;
+
+#[derive(Component)]
+pub struct ScenarioEditorPanel;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum ScenarioEditorButton {
+    Brush(TerrainBrush),
+    FewerArrivals,
+    MoreArrivals,
+    Archetype(usize),
+    LessMoney,
+    MoreMoney,
+    EditName,
+    Export,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum ScenarioEditorText {
+    Arrivals,
+    StartingMoney,
+    Name,
+}
+
+/// The brush in hand and what the exported scenario will be called and start with
+#[derive(Resource)]
+pub struct ScenarioEditorState {
+    pub brush: Option<TerrainBrush>,
+    pub name: String,
+    pub editing_name: bool,
+    pub starting_money: i32,
+}
+
+impl Default for ScenarioEditorState {
+    fn default() -> Self {
+        Self {
+            brush: None,
+            name: "my_scenario".to_string(),
+            editing_name: false,
+            starting_money: 10000,
+        }
+    }
+}
+
+/// Exported scenarios, by file name, in name order
+pub fn scenario_files() -> Vec<String> {
+    list_save_files(SCENARIO_DIR)
+}
+
+pub struct ScenarioEditorPlugin;
+
+impl Plugin for ScenarioEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScenarioEditorState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_scenario_editor)
+            .add_systems(
+                Update,
+                (
+                    block_map_input_over_scenario_editor,
+                    handle_scenario_editor_buttons,
+                    handle_scenario_name_input,
+                    export_scenario,
+                    paint_terrain,
+                    update_scenario_editor,
+                    update_scenario_editor_button_colors,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_scenario_editor(mut commands: Commands) {
+    // Along the right-hand side, clear of the economy and rooms panels on the left
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                top: Val::Px(60.0),
+                width: Val::Px(280.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            ScenarioEditorPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("scenario_editor.title"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            spawn_heading(parent, "scenario_editor.terrain");
+            spawn_button_row(parent, |row| {
+                for brush in TerrainBrush::ALL {
+                    spawn_scenario_editor_button(
+                        row,
+                        (
+                            Text::default(),
+                            LocalizedText::term("terrain", brush.name()),
+                        ),
+                        ScenarioEditorButton::Brush(brush),
+                    );
+                }
+            });
+
+            spawn_heading(parent, "scenario_editor.guests");
+            spawn_button_row(parent, |row| {
+                spawn_scenario_editor_button(
+                    row,
+                    Text::new("-"),
+                    ScenarioEditorButton::FewerArrivals,
+                );
+                spawn_value_text(row, ScenarioEditorText::Arrivals);
+                spawn_scenario_editor_button(
+                    row,
+                    Text::new("+"),
+                    ScenarioEditorButton::MoreArrivals,
+                );
+            });
+            spawn_button_row(parent, |row| {
+                for (index, archetype) in GuestArchetype::ALL.iter().enumerate() {
+                    spawn_scenario_editor_button(
+                        row,
+                        (
+                            Text::default(),
+                            LocalizedText::term("archetype", archetype.name()),
+                        ),
+                        ScenarioEditorButton::Archetype(index),
+                    );
+                }
+            });
+
+            spawn_button_row(parent, |row| {
+                spawn_scenario_editor_button(row, Text::new("-"), ScenarioEditorButton::LessMoney);
+                spawn_value_text(row, ScenarioEditorText::StartingMoney);
+                spawn_scenario_editor_button(row, Text::new("+"), ScenarioEditorButton::MoreMoney);
+            });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        height: Val::Px(30.0),
+                        padding: UiRect::horizontal(Val::Px(10.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    ScenarioEditorButton::EditName,
+                ))
+                .with_children(|parent| {
+                    spawn_value_text(parent, ScenarioEditorText::Name);
+                });
+
+            spawn_button_row(parent, |row| {
+                spawn_scenario_editor_button(
+                    row,
+                    (
+                        Text::default(),
+                        LocalizedText::new("scenario_editor.export"),
+                    ),
+                    ScenarioEditorButton::Export,
+                );
+            });
+        });
+}
+
+fn spawn_heading(parent: &mut ChildBuilder, label_key: &str) {
+    parent.spawn((
+        Text::default(),
+        LocalizedText::new(label_key),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+    ));
+}
+
+fn spawn_button_row(parent: &mut ChildBuilder, spawn_buttons: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(5.0),
+            row_gap: Val::Px(5.0),
+            ..default()
+        })
+        .with_children(spawn_buttons);
+}
+
+fn spawn_value_text(parent: &mut ChildBuilder, text: ScenarioEditorText) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        text,
+    ));
+}
+
+fn spawn_scenario_editor_button(
+    parent: &mut ChildBuilder,
+    label: impl Bundle,
+    button: ScenarioEditorButton,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(26.0),
+                padding: UiRect::horizontal(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                label,
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn block_map_input_over_scenario_editor(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    rules: Res<GameRules>,
+    state: Res<ScenarioEditorState>,
+    interaction_query: Query<&Interaction, With<ScenarioEditorButton>>,
+) {
+    let should_block = rules.is_editor()
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.scenario_editor_blocking != should_block {
+        ui_blocker.scenario_editor_blocking = should_block;
+        ui_blocker.recompute();
+    }
+
+    if ui_blocker.scenario_name_editing != state.editing_name {
+        ui_blocker.scenario_name_editing = state.editing_name;
+        ui_blocker.recompute();
+    }
+}
+
+fn handle_scenario_editor_buttons(
+    interaction_query: Query<(&Interaction, &ScenarioEditorButton), Changed<Interaction>>,
+    mut state: ResMut<ScenarioEditorState>,
+    mut spawn_settings: ResMut<GuestSpawnSettings>,
+    mut toolbar_state: ResMut<ToolbarState>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match *button {
+            ScenarioEditorButton::Brush(brush) => {
+                if state.brush == Some(brush) {
+                    state.brush = None;
+                } else {
+                    // Painting and building share the left mouse button
+                    state.brush = Some(brush);
+                    toolbar_state.selected_building = None;
+                    toolbar_state.selected_order = None;
+                }
+            }
+            ScenarioEditorButton::FewerArrivals => {
+                spawn_settings.arrival_rate = (spawn_settings.arrival_rate - ARRIVAL_RATE_STEP)
+                    .max(GuestSpawnSettings::MIN_ARRIVAL_RATE);
+            }
+            ScenarioEditorButton::MoreArrivals => {
+                spawn_settings.arrival_rate = (spawn_settings.arrival_rate + ARRIVAL_RATE_STEP)
+                    .min(GuestSpawnSettings::MAX_ARRIVAL_RATE);
+            }
+            ScenarioEditorButton::Archetype(index) => {
+                // Somebody has to be able to come
+                let allowed = spawn_settings.archetypes.iter().filter(|allowed| **allowed);
+                if !spawn_settings.archetypes[index] || allowed.count() > 1 {
+                    spawn_settings.archetypes[index] = !spawn_settings.archetypes[index];
+                }
+            }
+            ScenarioEditorButton::LessMoney => {
+                state.starting_money = (state.starting_money - STARTING_MONEY_STEP).max(0);
+            }
+            ScenarioEditorButton::MoreMoney => {
+                state.starting_money += STARTING_MONEY_STEP;
+            }
+            ScenarioEditorButton::EditName => {
+                state.editing_name = true;
+            }
+            // Written out by export_scenario
+            ScenarioEditorButton::Export => {}
+        }
+    }
+}
+
+// The name becomes the file name, so only letters, digits, '-' and '_' are taken and
+// spaces turn into underscores. Enter or Escape finishes typing.
+fn handle_scenario_name_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut state: ResMut<ScenarioEditorState>,
+) {
+    if !state.editing_name {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter | Key::Escape => {
+                if state.name.is_empty() {
+                    state.name = ScenarioEditorState::default().name;
+                }
+                state.editing_name = false;
+            }
+            Key::Backspace => {
+                state.name.pop();
+            }
+            Key::Space if state.name.chars().count() < MAX_SCENARIO_NAME_LENGTH => {
+                state.name.push('_');
+            }
+            Key::Character(text) => {
+                for character in text
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                {
+                    if state.name.chars().count() < MAX_SCENARIO_NAME_LENGTH {
+                        state.name.push(character);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes what has been built, the painted ground and the guest settings out as a
+/// scenario. It is a save with nobody in it that starts on day one with the chosen money.
+fn export_scenario(
+    interaction_query: Query<(&Interaction, &ScenarioEditorButton), Changed<Interaction>>,
+    wall_query: Query<(&GridPosition, &Wall)>,
+    floor_query: Query<(&GridPosition, &Floor)>,
+    door_query: Query<(&GridPosition, &Door, &DoorMode)>,
+    window_query: Query<(&GridPosition, &crate::components::Window, Option<&StableId>)>,
+    boundary_query: Query<(&GridPosition, &Boundary)>,
+    furniture_query: Query<(
+        &GridPosition,
+        &Furniture,
+        &FurnitureType,
+        &FurnitureOrientation,
+        Option<&Sign>,
+        Option<&StableId>,
+    )>,
+    zone_query: Query<(&Zone, Option<&StableId>)>,
+    pawn_query: Query<(Entity, &Pawn, Option<&StableId>)>,
+    map_settings: MapSettings,
+    mut state: ResMut<ScenarioEditorState>,
+    localization: Res<Localization>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let export_pressed = interaction_query.iter().any(|(interaction, button)| {
+        *interaction == Interaction::Pressed && *button == ScenarioEditorButton::Export
+    });
+    if !export_pressed {
+        return;
+    }
+    state.editing_name = false;
+
+    let mut data = collect_save_data(
+        &wall_query,
+        &floor_query,
+        &door_query,
+        &window_query,
+        &boundary_query,
+        &furniture_query,
+        &zone_query,
+        &pawn_query,
+        &StatsHistory::default(),
+    );
+    // Players hire their own staff
+    data.pawns.clear();
+    sort_save_data(&mut data);
+    map_settings.capture(&mut data);
+
+    let clock = GameClock::default();
+    data.metadata = Some(SaveMetadata {
+        day: clock.day(),
+        hour: clock.hour(),
+        money: state.starting_money,
+        guests: 0,
+        play_time_secs: 0.0,
+        thumbnail: SaveThumbnail::render(&data),
+    });
+
+    let path = format!(
+        "{}/{}{}",
+        SCENARIO_DIR,
+        state.name,
+        SaveFormat::Json.extension()
+    );
+    let key = match write_save_file(&path, &data) {
+        Ok(()) => {
+            info!("Exported scenario to {}", path);
+            "scenario_editor.exported"
+        }
+        Err(err) => {
+            error!("Failed to export scenario to {}: {}", path, err);
+            "scenario_editor.export_failed"
+        }
+    };
+    notifications.send(Notification {
+        message: localization.format(key, &[("name", state.name.clone())]),
+    });
+}
+
+/// Holding the left button with a brush in hand paints the ground under the cursor.
+/// Ground with something built on it stays as it is.
+fn paint_terrain(
+    actions: Res<ButtonInput<PointerAction>>,
+    toolbar_state: Res<ToolbarState>,
+    ui_blocker: Res<UiInputBlocker>,
+    cursor: Res<CursorWorldPosition>,
+    rules: Res<GameRules>,
+    state: Res<ScenarioEditorState>,
+    building_map: Res<BuildingMap>,
+    mut terrain: ResMut<Terrain>,
+) {
+    let Some(brush) = state.brush.filter(|_| rules.is_editor()) else {
+        return;
+    };
+    if !actions.pressed(PointerAction::Confirm) || ui_blocker.block_world_input {
+        return;
+    }
+    if toolbar_state.selected_building.is_some() || toolbar_state.selected_order.is_some() {
+        return;
+    }
+    let Some(center) = cursor.grid else {
+        return;
+    };
+
+    let mut changed = false;
+    for dx in -BRUSH_RADIUS..=BRUSH_RADIUS {
+        for dy in -BRUSH_RADIUS..=BRUSH_RADIUS {
+            let tile = center + IVec2::new(dx, dy);
+            if building_map.floors.contains(&tile)
+                || building_map.doors.contains_key(&tile)
+                || building_map.boundaries.contains_key(&tile)
+                || building_map.is_occupied(tile)
+            {
+                continue;
+            }
+            // Only flag a change when the ground moved, so the backdrop isn't redrawn
+            // every frame the button is held
+            changed |= terrain.bypass_change_detection().paint(tile, brush);
+        }
+    }
+    if changed {
+        terrain.set_changed();
+    }
+}
+
+fn update_scenario_editor(
+    rules: Res<GameRules>,
+    mut state: ResMut<ScenarioEditorState>,
+    spawn_settings: Res<GuestSpawnSettings>,
+    localization: Res<Localization>,
+    mut panel_query: Query<&mut Node, With<ScenarioEditorPanel>>,
+    mut text_query: Query<(&mut Text, &ScenarioEditorText)>,
+) {
+    let visible = rules.is_editor();
+    if !visible && (state.brush.is_some() || state.editing_name) {
+        state.brush = None;
+        state.editing_name = false;
+    }
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        let display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    if !visible {
+        return;
+    }
+
+    for (mut text, kind) in &mut text_query {
+        let label = match kind {
+            ScenarioEditorText::Arrivals => localization.format(
+                "scenario_editor.arrivals",
+                &[(
+                    "rate",
+                    format!("{:.0}", spawn_settings.arrival_rate * 100.0),
+                )],
+            ),
+            ScenarioEditorText::StartingMoney => localization.format(
+                "scenario_editor.starting_money",
+                &[("money", state.starting_money.to_string())],
+            ),
+            ScenarioEditorText::Name if state.editing_name => format!("{}_", state.name),
+            ScenarioEditorText::Name => {
+                localization.format("scenario_editor.name", &[("name", state.name.clone())])
+            }
+        };
+        if **text != label {
+            **text = label;
+        }
+    }
+}
+
+fn update_scenario_editor_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction, &ScenarioEditorButton)>,
+    state: Res<ScenarioEditorState>,
+    spawn_settings: Res<GuestSpawnSettings>,
+) {
+    for (mut color, interaction, button) in &mut button_query {
+        let selected = match *button {
+            ScenarioEditorButton::Brush(brush) => state.brush == Some(brush),
+            ScenarioEditorButton::Archetype(index) => spawn_settings.archetypes[index],
+            ScenarioEditorButton::EditName => state.editing_name,
+            _ => false,
+        };
+        *color = if selected {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+    }
+}
@@ -0,0 +1,222 @@
+use crate::components::ZoneType;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Player preferences, kept apart from saves so they carry over between games
+pub const SETTINGS_PATH: &str = "assets/settings.json";
+
+pub const MIN_UI_SCALE: f32 = 0.75;
+pub const MAX_UI_SCALE: f32 = 2.0;
+pub const UI_SCALE_STEP: f32 = 0.25;
+pub const MIN_CAMERA_SPEED: f32 = 0.5;
+pub const MAX_CAMERA_SPEED: f32 = 3.0;
+pub const CAMERA_SPEED_STEP: f32 = 0.25;
+pub const MAX_LOW_FUNDS_ALERT: i32 = 10000;
+pub const LOW_FUNDS_ALERT_STEP: i32 = 500;
+/// How much bigger text is in larger-text mode
+const LARGE_TEXT_FACTOR: f32 = 1.25;
+
+/// Colours for everything that tells the player something by colour alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    /// Blue against orange instead of white against red, and zone tints that stay apart
+    /// for red-green colour blindness
+    Colorblind,
+}
+
+impl ColorPalette {
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            ColorPalette::Standard => "settings.palette.standard",
+            ColorPalette::Colorblind => "settings.palette.colorblind",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ColorPalette::Standard => ColorPalette::Colorblind,
+            ColorPalette::Colorblind => ColorPalette::Standard,
+        }
+    }
+
+    /// Outline of tiles a blueprint can go on
+    pub fn preview_valid(&self) -> Color {
+        match self {
+            ColorPalette::Standard => Color::srgba(1.0, 1.0, 1.0, 0.7),
+            ColorPalette::Colorblind => Color::srgba(0.35, 0.7, 0.9, 0.8),
+        }
+    }
+
+    /// Outline of tiles a blueprint can't go on
+    pub fn preview_blocked(&self) -> Color {
+        match self {
+            ColorPalette::Standard => Color::srgba(1.0, 0.3, 0.3, 0.9),
+            ColorPalette::Colorblind => Color::srgba(0.9, 0.6, 0.0, 0.9),
+        }
+    }
+
+    /// Tint over a furniture preview's sprite; the valid tint keeps the sprite's own colours
+    pub fn preview_tint(&self, blocked: bool) -> Color {
+        match (self, blocked) {
+            (_, false) => Color::srgba(1.0, 1.0, 1.0, 0.7),
+            (ColorPalette::Standard, true) => Color::srgba(1.0, 0.3, 0.3, 1.0),
+            (ColorPalette::Colorblind, true) => Color::srgba(1.0, 0.6, 0.1, 1.0),
+        }
+    }
+
+    pub fn zone_color(&self, zone_type: ZoneType) -> Color {
+        match self {
+            ColorPalette::Standard => zone_type.color(),
+            ColorPalette::Colorblind => zone_type.colorblind_color(),
+        }
+    }
+
+    /// Outline of zones whose room is too small or bare to be rated
+    pub fn invalid_zone_border(&self) -> Color {
+        match self {
+            ColorPalette::Standard => Color::srgb(0.9, 0.2, 0.2),
+            ColorPalette::Colorblind => Color::srgb(0.95, 0.9, 0.25),
+        }
+    }
+}
+
+/// Display and accessibility options, loaded at startup and written back whenever they
+/// change
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    /// Size of every panel and toolbar, for high-DPI screens or players who need it bigger
+    pub ui_scale: f32,
+    pub palette: ColorPalette,
+    pub large_text: bool,
+    /// Stop the clock while each night audit is on screen
+    pub pause_for_night_audit: bool,
+    /// Float hearts, frowns and the like above guests whose satisfaction changes
+    pub guest_emotes: bool,
+    /// How fast the keyboard and right stick pan the camera, against the usual speed
+    pub camera_speed: f32,
+    /// Warn when money free to spend drops below this; 0 never warns
+    pub low_funds_alert: i32,
+    /// Stop the clock the moment the balance goes below zero
+    pub pause_in_debt: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            palette: ColorPalette::Standard,
+            large_text: false,
+            pause_for_night_audit: false,
+            guest_emotes: true,
+            camera_speed: 1.0,
+            low_funds_alert: 1000,
+            pause_in_debt: false,
+        }
+    }
+}
+
+impl GameSettings {
+    pub fn load() -> Self {
+        let contents = match fs::read_to_string(SETTINGS_PATH) {
+            Ok(contents) => contents,
+            // No file yet is the usual first run
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str::<Self>(&contents) {
+            Ok(mut settings) => {
+                settings.ui_scale = settings.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+                settings.camera_speed = settings
+                    .camera_speed
+                    .clamp(MIN_CAMERA_SPEED, MAX_CAMERA_SPEED);
+                settings.low_funds_alert = settings.low_funds_alert.clamp(0, MAX_LOW_FUNDS_ALERT);
+                settings
+            }
+            Err(err) => {
+                warn!("Ignoring {}: {}", SETTINGS_PATH, err);
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(std::io::Error::from)
+            .and_then(|json| fs::write(SETTINGS_PATH, json));
+        if let Err(err) = result {
+            warn!("Failed to write {}: {}", SETTINGS_PATH, err);
+        }
+    }
+
+    /// One step bigger or smaller, staying within the allowed range
+    pub fn step_ui_scale(&mut self, steps: f32) {
+        self.ui_scale = (self.ui_scale + steps * UI_SCALE_STEP).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    }
+
+    pub fn step_camera_speed(&mut self, steps: f32) {
+        self.camera_speed = (self.camera_speed + steps * CAMERA_SPEED_STEP)
+            .clamp(MIN_CAMERA_SPEED, MAX_CAMERA_SPEED);
+    }
+
+    pub fn step_low_funds_alert(&mut self, steps: i32) {
+        self.low_funds_alert =
+            (self.low_funds_alert + steps * LOW_FUNDS_ALERT_STEP).clamp(0, MAX_LOW_FUNDS_ALERT);
+    }
+
+    pub fn text_factor(&self) -> f32 {
+        if self.large_text {
+            LARGE_TEXT_FACTOR
+        } else {
+            1.0
+        }
+    }
+}
+
+/// The size a piece of UI text was spawned with, before larger-text mode scaled it
+#[derive(Component)]
+struct BaseFontSize(f32);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameSettings::load())
+            .add_systems(Update, (apply_ui_scale, apply_text_size, save_settings));
+    }
+}
+
+fn apply_ui_scale(settings: Res<GameSettings>, mut ui_scale: ResMut<UiScale>) {
+    if settings.is_changed() {
+        ui_scale.0 = settings.ui_scale;
+    }
+}
+
+/// Sizes new UI text for larger-text mode as it appears, and resizes all of it when the
+/// mode is switched
+fn apply_text_size(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    mut new_text_query: Query<(Entity, &mut TextFont), (With<Text>, Without<BaseFontSize>)>,
+    mut sized_text_query: Query<(&BaseFontSize, &mut TextFont), With<Text>>,
+) {
+    let factor = settings.text_factor();
+    for (entity, mut font) in &mut new_text_query {
+        commands.entity(entity).insert(BaseFontSize(font.font_size));
+        font.font_size *= factor;
+    }
+
+    if settings.is_changed() {
+        for (base, mut font) in &mut sized_text_query {
+            font.font_size = base.0 * factor;
+        }
+    }
+}
+
+fn save_settings(settings: Res<GameSettings>) {
+    if settings.is_changed() && !settings.is_added() {
+        settings.save();
+    }
+}
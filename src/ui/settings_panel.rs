@@ -0,0 +1,358 @@
+use super::localization::{Localization, LocalizedText};
+use super::settings::GameSettings;
+use super::UiInputBlocker;
+use bevy::prelude::*;
+
+const PANEL_WIDTH: f32 = 300.0;
+const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.35);
+
+/// What a button in the settings panel changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsAction {
+    ShrinkUi,
+    GrowUi,
+    CyclePalette,
+    ToggleLargeText,
+    ToggleNightAuditPause,
+    ToggleGuestEmotes,
+    SlowerCamera,
+    FasterCamera,
+    LowerFundsAlert,
+    RaiseFundsAlert,
+    TogglePauseInDebt,
+}
+
+/// Which setting a value label shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsValue {
+    UiScale,
+    Palette,
+    LargeText,
+    NightAuditPause,
+    GuestEmotes,
+    CameraSpeed,
+    LowFundsAlert,
+    PauseInDebt,
+}
+
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Component)]
+pub struct SettingsActionButton {
+    pub action: SettingsAction,
+}
+
+#[derive(Component)]
+pub struct SettingsValueText {
+    pub value: SettingsValue,
+}
+
+#[derive(Resource, Default)]
+pub struct SettingsPanelState {
+    pub visible: bool,
+}
+
+pub struct SettingsPanelPlugin;
+
+impl Plugin for SettingsPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsPanelState>()
+            .add_systems(Startup, setup_settings_panel)
+            .add_systems(
+                Update,
+                (
+                    apply_settings_panel_visibility,
+                    handle_settings_action_clicks,
+                    update_settings_values,
+                    update_settings_button_colors,
+                    block_map_input_over_settings_panel,
+                ),
+            );
+    }
+}
+
+fn setup_settings_panel(mut commands: Commands) {
+    // Initially hidden, in the bottom right corner just above the toolbar
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(90.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(8.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            // Above the main and pause menus, which open it too
+            GlobalZIndex(15),
+            SettingsPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("settings.title"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            spawn_settings_row(parent, "settings.ui_scale", |row| {
+                spawn_action_button(row, "-", SettingsAction::ShrinkUi);
+                spawn_value_text(row, SettingsValue::UiScale);
+                spawn_action_button(row, "+", SettingsAction::GrowUi);
+            });
+            spawn_settings_row(parent, "settings.palette", |row| {
+                spawn_toggle_button(row, SettingsAction::CyclePalette, SettingsValue::Palette);
+            });
+            spawn_settings_row(parent, "settings.large_text", |row| {
+                spawn_toggle_button(
+                    row,
+                    SettingsAction::ToggleLargeText,
+                    SettingsValue::LargeText,
+                );
+            });
+            spawn_settings_row(parent, "settings.night_audit_pause", |row| {
+                spawn_toggle_button(
+                    row,
+                    SettingsAction::ToggleNightAuditPause,
+                    SettingsValue::NightAuditPause,
+                );
+            });
+            spawn_settings_row(parent, "settings.guest_emotes", |row| {
+                spawn_toggle_button(
+                    row,
+                    SettingsAction::ToggleGuestEmotes,
+                    SettingsValue::GuestEmotes,
+                );
+            });
+            spawn_settings_row(parent, "settings.camera_speed", |row| {
+                spawn_action_button(row, "-", SettingsAction::SlowerCamera);
+                spawn_value_text(row, SettingsValue::CameraSpeed);
+                spawn_action_button(row, "+", SettingsAction::FasterCamera);
+            });
+            spawn_settings_row(parent, "settings.low_funds_alert", |row| {
+                spawn_action_button(row, "-", SettingsAction::LowerFundsAlert);
+                spawn_value_text(row, SettingsValue::LowFundsAlert);
+                spawn_action_button(row, "+", SettingsAction::RaiseFundsAlert);
+            });
+            spawn_settings_row(parent, "settings.pause_in_debt", |row| {
+                spawn_toggle_button(
+                    row,
+                    SettingsAction::TogglePauseInDebt,
+                    SettingsValue::PauseInDebt,
+                );
+            });
+        });
+}
+
+/// A label on the left and the setting's controls on the right
+fn spawn_settings_row(
+    parent: &mut ChildBuilder,
+    label_key: &str,
+    controls: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            row.spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(6.0),
+                ..default()
+            })
+            .with_children(controls);
+        });
+}
+
+fn spawn_action_button(parent: &mut ChildBuilder, label: &str, action: SettingsAction) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(28.0),
+                height: Val::Px(28.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            SettingsActionButton { action },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// A button that shows the setting's current value and switches it when clicked
+fn spawn_toggle_button(parent: &mut ChildBuilder, action: SettingsAction, value: SettingsValue) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                min_width: Val::Px(110.0),
+                height: Val::Px(28.0),
+                padding: UiRect::horizontal(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            SettingsActionButton { action },
+        ))
+        .with_children(|parent| spawn_value_text(parent, value));
+}
+
+fn spawn_value_text(parent: &mut ChildBuilder, value: SettingsValue) {
+    parent.spawn((
+        Text::default(),
+        TextFont {
+            font_size: 15.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        SettingsValueText { value },
+    ));
+}
+
+fn apply_settings_panel_visibility(
+    panel_state: Res<SettingsPanelState>,
+    mut panel_query: Query<&mut Node, With<SettingsPanel>>,
+) {
+    if !panel_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        node.display = if panel_state.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn handle_settings_action_clicks(
+    interaction_query: Query<(&Interaction, &SettingsActionButton), Changed<Interaction>>,
+    mut settings: ResMut<GameSettings>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button.action {
+            SettingsAction::ShrinkUi => settings.step_ui_scale(-1.0),
+            SettingsAction::GrowUi => settings.step_ui_scale(1.0),
+            SettingsAction::CyclePalette => settings.palette = settings.palette.next(),
+            SettingsAction::ToggleLargeText => settings.large_text = !settings.large_text,
+            SettingsAction::ToggleNightAuditPause => {
+                settings.pause_for_night_audit = !settings.pause_for_night_audit
+            }
+            SettingsAction::ToggleGuestEmotes => settings.guest_emotes = !settings.guest_emotes,
+            SettingsAction::SlowerCamera => settings.step_camera_speed(-1.0),
+            SettingsAction::FasterCamera => settings.step_camera_speed(1.0),
+            SettingsAction::LowerFundsAlert => settings.step_low_funds_alert(-1),
+            SettingsAction::RaiseFundsAlert => settings.step_low_funds_alert(1),
+            SettingsAction::TogglePauseInDebt => settings.pause_in_debt = !settings.pause_in_debt,
+        }
+    }
+}
+
+fn update_settings_values(
+    settings: Res<GameSettings>,
+    localization: Res<Localization>,
+    mut value_query: Query<(&mut Text, &SettingsValueText)>,
+) {
+    if !settings.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    for (mut text, value) in &mut value_query {
+        **text = match value.value {
+            SettingsValue::UiScale => format!("{:.0}%", settings.ui_scale * 100.0),
+            SettingsValue::Palette => localization.t(settings.palette.label_key()).to_string(),
+            SettingsValue::LargeText => localization.t(on_off_key(settings.large_text)).to_string(),
+            SettingsValue::NightAuditPause => localization
+                .t(on_off_key(settings.pause_for_night_audit))
+                .to_string(),
+            SettingsValue::GuestEmotes => localization
+                .t(on_off_key(settings.guest_emotes))
+                .to_string(),
+            SettingsValue::CameraSpeed => format!("{:.0}%", settings.camera_speed * 100.0),
+            SettingsValue::LowFundsAlert => match settings.low_funds_alert {
+                0 => localization.t("settings.off").to_string(),
+                alert => format!("${}", alert),
+            },
+            SettingsValue::PauseInDebt => localization
+                .t(on_off_key(settings.pause_in_debt))
+                .to_string(),
+        };
+    }
+}
+
+fn on_off_key(enabled: bool) -> &'static str {
+    if enabled {
+        "settings.on"
+    } else {
+        "settings.off"
+    }
+}
+
+fn update_settings_button_colors(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SettingsActionButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => BUTTON_HOVER_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        }
+        .into();
+    }
+}
+
+fn block_map_input_over_settings_panel(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    panel_state: Res<SettingsPanelState>,
+    interaction_query: Query<&Interaction, With<SettingsActionButton>>,
+) {
+    let should_block = panel_state.visible
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.settings_panel_blocking != should_block {
+        ui_blocker.settings_panel_blocking = should_block;
+        ui_blocker.recompute();
+    }
+}
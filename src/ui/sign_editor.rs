@@ -0,0 +1,362 @@
+use super::localization::{Localization, LocalizedText};
+use super::{ToolbarState, UiInputBlocker};
+use crate::components::*;
+use crate::systems::{sign_text, CursorWorldPosition, GameplaySet, PointerAction};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+const MAX_SIGN_TEXT_LENGTH: usize = 20;
+
+#[derive(Component)]
+pub struct SignEditorPanel;
+
+#[derive(Component)]
+pub struct SignEditorTitle;
+
+#[derive(Component)]
+pub struct SignEditorDetails;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SignEditorButton {
+    EditText,
+    ChangeIcon,
+    Close,
+}
+
+/// Which sign the editor is showing, and the in-progress text while typing
+#[derive(Resource, Default)]
+pub struct SignEditorState {
+    pub selected_sign: Option<Entity>,
+    pub editing: bool,
+    pub text_buffer: String,
+}
+
+impl SignEditorState {
+    fn close(&mut self) {
+        self.selected_sign = None;
+        self.editing = false;
+        self.text_buffer.clear();
+    }
+}
+
+pub struct SignEditorPlugin;
+
+impl Plugin for SignEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SignEditorState>()
+            .init_resource::<UiInputBlocker>()
+            .add_systems(Startup, setup_sign_editor)
+            .add_systems(
+                Update,
+                (
+                    block_map_input_over_sign_editor,
+                    select_sign_on_click,
+                    handle_sign_editor_buttons,
+                    handle_sign_text_input,
+                    update_sign_editor,
+                    update_sign_editor_button_colors,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_sign_editor(mut commands: Commands) {
+    // Shares the room inspector's spot; clicking a sign opens this instead of that
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(150.0),
+                width: Val::Px(260.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            SignEditorPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                SignEditorTitle,
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                SignEditorDetails,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(5.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_sign_editor_button(row, "sign.edit_text", SignEditorButton::EditText);
+                    spawn_sign_editor_button(row, "sign.change_icon", SignEditorButton::ChangeIcon);
+                    spawn_sign_editor_button(row, "inspector.close", SignEditorButton::Close);
+                });
+        });
+}
+
+fn spawn_sign_editor_button(parent: &mut ChildBuilder, label_key: &str, button: SignEditorButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Val::Px(30.0),
+                padding: UiRect::horizontal(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn block_map_input_over_sign_editor(
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    state: Res<SignEditorState>,
+    interaction_query: Query<&Interaction, With<SignEditorButton>>,
+) {
+    let should_block = state.selected_sign.is_some()
+        && interaction_query
+            .iter()
+            .any(|interaction| matches!(*interaction, Interaction::Hovered | Interaction::Pressed));
+
+    if ui_blocker.sign_editor_blocking != should_block {
+        ui_blocker.sign_editor_blocking = should_block;
+        ui_blocker.recompute();
+    }
+
+    if ui_blocker.sign_text_editing != state.editing {
+        ui_blocker.sign_text_editing = state.editing;
+        ui_blocker.recompute();
+    }
+}
+
+/// Clicking a sign with no build tool selected opens the editor for it
+fn select_sign_on_click(
+    actions: Res<ButtonInput<PointerAction>>,
+    toolbar_state: Res<ToolbarState>,
+    ui_blocker: Res<UiInputBlocker>,
+    cursor: Res<CursorWorldPosition>,
+    sign_query: Query<(Entity, &GridPosition, &FurnitureType, &FurnitureOrientation), With<Sign>>,
+    mut state: ResMut<SignEditorState>,
+) {
+    if !actions.just_pressed(PointerAction::Confirm) || ui_blocker.block_world_input {
+        return;
+    }
+
+    if toolbar_state.selected_building.is_some() || toolbar_state.selected_order.is_some() {
+        return;
+    }
+
+    let Some(grid_pos) = cursor.grid else {
+        return;
+    };
+
+    let clicked_sign = sign_query
+        .iter()
+        .find(|(_, pos, furniture_type, orientation)| {
+            furniture_type.covers(pos.to_ivec2(), **orientation, grid_pos)
+        })
+        .map(|(entity, ..)| entity);
+
+    match clicked_sign {
+        Some(sign_entity) if state.selected_sign != Some(sign_entity) => {
+            state.selected_sign = Some(sign_entity);
+            state.editing = false;
+            state.text_buffer.clear();
+        }
+        None if state.selected_sign.is_some() => state.close(),
+        _ => {}
+    }
+}
+
+fn handle_sign_editor_buttons(
+    interaction_query: Query<(&Interaction, &SignEditorButton), Changed<Interaction>>,
+    mut sign_query: Query<&mut Sign>,
+    mut state: ResMut<SignEditorState>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(sign_entity) = state.selected_sign else {
+            continue;
+        };
+        let Ok(mut sign) = sign_query.get_mut(sign_entity) else {
+            continue;
+        };
+
+        match button {
+            SignEditorButton::EditText => {
+                state.text_buffer = sign.text.clone();
+                state.editing = true;
+            }
+            SignEditorButton::ChangeIcon => {
+                sign.icon = sign.icon.next();
+            }
+            SignEditorButton::Close => state.close(),
+        }
+    }
+}
+
+// Typed characters go into the text buffer; Enter applies it, Escape cancels. A blank
+// sign goes back to showing its icon's name.
+fn handle_sign_text_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut state: ResMut<SignEditorState>,
+    mut sign_query: Query<&mut Sign>,
+) {
+    if !state.editing {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let new_text = state.text_buffer.trim().to_string();
+                if let Some(mut sign) = state
+                    .selected_sign
+                    .and_then(|entity| sign_query.get_mut(entity).ok())
+                {
+                    sign.text = new_text;
+                }
+                state.editing = false;
+            }
+            Key::Escape => {
+                state.editing = false;
+            }
+            Key::Backspace => {
+                state.text_buffer.pop();
+            }
+            Key::Space if state.text_buffer.chars().count() < MAX_SIGN_TEXT_LENGTH => {
+                state.text_buffer.push(' ');
+            }
+            Key::Character(text) => {
+                for character in text.chars().filter(|c| !c.is_control()) {
+                    if state.text_buffer.chars().count() < MAX_SIGN_TEXT_LENGTH {
+                        state.text_buffer.push(character);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_sign_editor(
+    mut state: ResMut<SignEditorState>,
+    sign_query: Query<(&Sign, &FurnitureOrientation)>,
+    mut panel_query: Query<&mut Node, With<SignEditorPanel>>,
+    mut title_query: Query<&mut Text, (With<SignEditorTitle>, Without<SignEditorDetails>)>,
+    mut details_query: Query<&mut Text, (With<SignEditorDetails>, Without<SignEditorTitle>)>,
+    localization: Res<Localization>,
+) {
+    let sign = state
+        .selected_sign
+        .and_then(|entity| sign_query.get(entity).ok());
+
+    // The sign may have been torn down since it was clicked
+    if state.selected_sign.is_some() && sign.is_none() {
+        state.close();
+    }
+
+    if let Ok(mut node) = panel_query.get_single_mut() {
+        let display = if sign.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    let Some((sign, orientation)) = sign else {
+        return;
+    };
+
+    if let Ok(mut title) = title_query.get_single_mut() {
+        let label = if state.editing {
+            format!("{}_", state.text_buffer)
+        } else {
+            sign_text(sign, *orientation, &localization)
+        };
+        if **title != label {
+            **title = label;
+        }
+    }
+
+    if let Ok(mut details) = details_query.get_single_mut() {
+        let destination = sign.icon.destination();
+        let mut label = localization.format(
+            "sign.details",
+            &[(
+                "destination",
+                localization
+                    .term("zone_type", destination.name())
+                    .to_string(),
+            )],
+        );
+        if state.editing {
+            label.push_str("\n\n");
+            label.push_str(localization.t("sign.edit_hint"));
+        }
+        if **details != label {
+            **details = label;
+        }
+    }
+}
+
+fn update_sign_editor_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction, &SignEditorButton)>,
+    state: Res<SignEditorState>,
+) {
+    for (mut color, interaction, button) in &mut button_query {
+        *color = if *button == SignEditorButton::EditText && state.editing {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+    }
+}
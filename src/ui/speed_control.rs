@@ -1,4 +1,5 @@
 use super::UiInputBlocker;
+use crate::systems::app_state::GameplaySet;
 use crate::systems::time_control::{SpeedOption, TimeSpeed};
 use bevy::prelude::*;
 
@@ -19,10 +20,12 @@ impl Plugin for SpeedControlPlugin {
             .add_systems(
                 Update,
                 (
+                    handle_pause_hotkey,
                     handle_speed_button_clicks,
                     update_speed_button_colors,
                     block_map_input_over_speed_controls,
-                ),
+                )
+                    .in_set(GameplaySet),
             );
     }
 }
@@ -44,13 +47,13 @@ fn setup_speed_control(mut commands: Commands) {
             SpeedControlPanel,
         ))
         .with_children(|parent| {
-            spawn_speed_button(parent, SpeedOption::Normal, "1x");
-            spawn_speed_button(parent, SpeedOption::Fast, "2x");
-            spawn_speed_button(parent, SpeedOption::VeryFast, "3x");
+            for speed in SpeedOption::all() {
+                spawn_speed_button(parent, speed);
+            }
         });
 }
 
-fn spawn_speed_button(parent: &mut ChildBuilder, speed: SpeedOption, label: &str) {
+fn spawn_speed_button(parent: &mut ChildBuilder, speed: SpeedOption) {
     parent
         .spawn((
             Button,
@@ -66,7 +69,7 @@ fn spawn_speed_button(parent: &mut ChildBuilder, speed: SpeedOption, label: &str
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(label),
+                Text::new(speed.label()),
                 TextFont {
                     font_size: 18.0,
                     ..default()
@@ -76,6 +79,16 @@ fn spawn_speed_button(parent: &mut ChildBuilder, speed: SpeedOption, label: &str
         });
 }
 
+fn handle_pause_hotkey(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut time_speed: ResMut<TimeSpeed>,
+    ui_blocker: Res<UiInputBlocker>,
+) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::Space) {
+        time_speed.toggle_pause();
+    }
+}
+
 fn handle_speed_button_clicks(
     mut interaction_query: Query<(&Interaction, &SpeedButton), Changed<Interaction>>,
     mut time_speed: ResMut<TimeSpeed>,
@@ -92,13 +105,7 @@ fn update_speed_button_colors(
     time_speed: Res<TimeSpeed>,
 ) {
     for (speed_button, mut bg_color, interaction) in &mut button_query {
-        let is_active = match speed_button.speed {
-            SpeedOption::Normal => time_speed.multiplier == 1.0,
-            SpeedOption::Fast => time_speed.multiplier == 2.0,
-            SpeedOption::VeryFast => time_speed.multiplier == 3.0,
-        };
-
-        if is_active {
+        if time_speed.is(speed_button.speed) {
             *bg_color = BackgroundColor(Color::srgb(0.3, 0.6, 0.3)); // Green when active
         } else {
             match interaction {
@@ -0,0 +1,354 @@
+use super::localization::{Localization, LocalizedText};
+use crate::components::GuestArchetype;
+use crate::systems::{DailyStats, GameplaySet, GuestDemand, GuestLedger, StatsHistory};
+use bevy::prelude::*;
+
+const PANEL_WIDTH: f32 = 460.0;
+const CHART_HEIGHT: f32 = 48.0;
+/// Most recent days drawn in each chart
+const CHART_DAYS: usize = 30;
+
+/// What each chart in the statistics panel plots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsMetric {
+    Occupancy,
+    RoomQuality,
+    Guests,
+    RevPar,
+    StaffUtilization,
+}
+
+impl StatsMetric {
+    pub fn all() -> [StatsMetric; 5] {
+        [
+            StatsMetric::Occupancy,
+            StatsMetric::RoomQuality,
+            StatsMetric::Guests,
+            StatsMetric::RevPar,
+            StatsMetric::StaffUtilization,
+        ]
+    }
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            StatsMetric::Occupancy => "stats.occupancy",
+            StatsMetric::RoomQuality => "stats.room_quality",
+            StatsMetric::Guests => "stats.guests",
+            StatsMetric::RevPar => "stats.revpar",
+            StatsMetric::StaffUtilization => "stats.staff_utilization",
+        }
+    }
+
+    pub fn value(&self, sample: &DailyStats) -> f32 {
+        match self {
+            StatsMetric::Occupancy => sample.occupancy,
+            StatsMetric::RoomQuality => sample.average_quality,
+            StatsMetric::Guests => sample.guests as f32,
+            StatsMetric::RevPar => sample.revpar,
+            StatsMetric::StaffUtilization => sample.staff_utilization,
+        }
+    }
+
+    /// Top of the chart: shares and star ratings have a fixed scale, counts and money
+    /// scale to the best day shown
+    fn chart_max(&self, samples: &[DailyStats]) -> f32 {
+        match self {
+            StatsMetric::Occupancy | StatsMetric::StaffUtilization => 1.0,
+            StatsMetric::RoomQuality => 4.0,
+            StatsMetric::Guests | StatsMetric::RevPar => samples
+                .iter()
+                .map(|sample| self.value(sample))
+                .fold(1.0, f32::max),
+        }
+    }
+
+    fn format(&self, sample: &DailyStats) -> String {
+        let value = self.value(sample);
+        match self {
+            StatsMetric::Occupancy | StatsMetric::StaffUtilization => {
+                format!("{:.0}%", value * 100.0)
+            }
+            StatsMetric::RoomQuality => format!("{:.1}★", value),
+            StatsMetric::Guests => format!("{}", value as u32),
+            StatsMetric::RevPar => format!("${:.0}", value),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            StatsMetric::Occupancy => Color::srgb(0.3, 0.6, 0.9),
+            StatsMetric::RoomQuality => Color::srgb(0.9, 0.75, 0.3),
+            StatsMetric::Guests => Color::srgb(0.5, 0.8, 0.5),
+            StatsMetric::RevPar => Color::srgb(0.2, 0.8, 0.2),
+            StatsMetric::StaffUtilization => Color::srgb(0.8, 0.5, 0.8),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct StatisticsPanel;
+
+/// Bar chart of one metric, one bar per day
+#[derive(Component)]
+pub struct StatsChart {
+    pub metric: StatsMetric,
+}
+
+/// The metric's value on the most recent day
+#[derive(Component)]
+pub struct StatsValueText {
+    pub metric: StatsMetric,
+}
+
+/// Shown until the first day has been recorded
+#[derive(Component)]
+pub struct StatsEmptyText;
+
+/// Who is staying now and who the resort is drawing, by kind of party
+#[derive(Component)]
+pub struct GuestMixText;
+
+#[derive(Resource, Default)]
+pub struct StatisticsPanelState {
+    pub visible: bool,
+}
+
+pub struct StatisticsPanelPlugin;
+
+impl Plugin for StatisticsPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatisticsPanelState>()
+            .add_systems(Startup, setup_statistics_panel)
+            .add_systems(
+                Update,
+                (
+                    apply_statistics_panel_visibility,
+                    update_statistics_charts,
+                    update_guest_mix,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_statistics_panel(mut commands: Commands) {
+    // Initially hidden panel on the right, clear of the economy panel
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                top: Val::Px(60.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None, // Hidden by default
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            StatisticsPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("stats.title"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("stats.no_data"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                StatsEmptyText,
+            ));
+
+            for metric in StatsMetric::all() {
+                spawn_chart_row(parent, metric);
+            }
+
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("stats.guest_mix"),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                GuestMixText,
+            ));
+        });
+}
+
+fn spawn_chart_row(parent: &mut ChildBuilder, metric: StatsMetric) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::default(),
+                LocalizedText::new(metric.label_key()),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            row.spawn((
+                Text::new("-"),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(metric.color()),
+                StatsValueText { metric },
+            ));
+        });
+
+    parent.spawn((
+        Node {
+            height: Val::Px(CHART_HEIGHT),
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::FlexEnd,
+            column_gap: Val::Px(2.0),
+            padding: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+        StatsChart { metric },
+    ));
+}
+
+fn apply_statistics_panel_visibility(
+    panel_state: Res<StatisticsPanelState>,
+    mut panel_query: Query<&mut Node, With<StatisticsPanel>>,
+) {
+    if !panel_state.is_changed() {
+        return;
+    }
+
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        style.display = if panel_state.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// Redraws the charts whenever a day is recorded or a save is loaded
+fn update_statistics_charts(
+    mut commands: Commands,
+    history: Res<StatsHistory>,
+    chart_query: Query<(Entity, &StatsChart)>,
+    mut value_query: Query<(&mut Text, &StatsValueText)>,
+    mut empty_query: Query<&mut Node, With<StatsEmptyText>>,
+) {
+    if !history.is_changed() {
+        return;
+    }
+
+    let start = history.samples.len().saturating_sub(CHART_DAYS);
+    let samples = &history.samples[start..];
+
+    if let Ok(mut node) = empty_query.get_single_mut() {
+        node.display = if samples.is_empty() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+
+    for (mut text, value) in &mut value_query {
+        **text = history
+            .latest()
+            .map_or_else(|| "-".to_string(), |sample| value.metric.format(sample));
+    }
+
+    for (chart_entity, chart) in &chart_query {
+        let metric = chart.metric;
+        let max = metric.chart_max(samples);
+
+        commands
+            .entity(chart_entity)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for sample in samples {
+                    let share = (metric.value(sample) / max).clamp(0.0, 1.0);
+                    parent.spawn((
+                        Node {
+                            width: Val::Px((PANEL_WIDTH - 24.0) / CHART_DAYS as f32 - 2.0),
+                            // A sliver even on empty days, so the timeline stays readable
+                            height: Val::Percent((share * 100.0).max(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(metric.color()),
+                    ));
+                }
+            });
+    }
+}
+
+/// One line per kind of party: how many are staying and their share of new arrivals,
+/// which shifts with reputation and room prices
+fn update_guest_mix(
+    ledger: Res<GuestLedger>,
+    demand: Res<GuestDemand>,
+    localization: Res<Localization>,
+    mut text_query: Query<&mut Text, With<GuestMixText>>,
+) {
+    if !ledger.is_changed() && !demand.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    let total: f32 = demand.archetype_mix.iter().sum();
+    let lines: Vec<String> = GuestArchetype::ALL
+        .iter()
+        .map(|archetype| {
+            let index = *archetype as usize;
+            let share = if total > 0.0 {
+                demand.archetype_mix[index] / total
+            } else {
+                0.0
+            };
+            localization.format(
+                "stats.guest_mix_line",
+                &[
+                    (
+                        "kind",
+                        localization.term("archetype", archetype.name()).to_string(),
+                    ),
+                    ("staying", ledger.archetypes_staying[index].to_string()),
+                    ("share", format!("{:.0}", share * 100.0)),
+                ],
+            )
+        })
+        .collect();
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        let label = lines.join("\n");
+        if **text != label {
+            **text = label;
+        }
+    }
+}
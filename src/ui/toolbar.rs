@@ -1,10 +1,26 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
+use super::economy_panel::EconomyPanelState;
+use super::save_load_panel::SaveLoadPanelState;
+use super::settings_panel::SettingsPanelState;
+use super::statistics_panel::StatisticsPanelState;
 use super::work_assignments::WorkAssignmentsPanelState;
+use super::localization::{LocalizedText, Localization};
+use super::UiInputBlocker;
+use crate::components::{
+    Blueprint, BoundaryType, FurnitureCategory, FurnitureRegistry, PaintColor,
+};
+use crate::systems::building::plans::PlanOrder;
+use crate::systems::{Balance, CalendarEvent, GameClock, GameRules, GameplaySet};
 
-const TOOLBAR_HEIGHT: f32 = 80.0;
+pub const TOOLBAR_HEIGHT: f32 = 80.0;
 const TAB_WIDTH: f32 = 100.0;
 const BUTTON_SIZE: f32 = 60.0;
+/// Paint swatches are narrower so the whole palette fits beside the other orders
+const SWATCH_WIDTH: f32 = 36.0;
+/// Blue for planning, in place of the usual green, on whatever is selected
+pub(super) const PLANNING_COLOR: Color = Color::srgb(0.4, 0.55, 0.8);
 
 #[derive(Component)]
 pub struct Toolbar;
@@ -24,12 +40,51 @@ pub struct OrderButton {
     pub order_type: OrderType,
 }
 
+#[derive(Component)]
+pub struct DeconstructFilterButton {
+    pub filter: DeconstructFilter,
+}
+
+#[derive(Component)]
+pub struct PaintColorButton {
+    pub color: PaintColor,
+}
+
+#[derive(Component)]
+pub struct PlanButton {
+    pub action: PlanAction,
+}
+
+/// What a button under Orders does with planning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanAction {
+    /// Switches planning on or off
+    Toggle,
+    Order(PlanOrder),
+}
+
 #[derive(Component)]
 pub struct WorkAssignmentsButton;
 
 #[derive(Component)]
 pub struct SaveLoadButton;
 
+#[derive(Component)]
+pub struct EconomyButton;
+
+#[derive(Component)]
+pub struct StatisticsButton;
+
+#[derive(Component)]
+pub struct SettingsButton;
+
+/// Cycles through the installed translations
+#[derive(Component)]
+pub struct LanguageButton;
+
+#[derive(Component)]
+pub struct LanguageButtonLabel;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConstructionTab {
     Orders,
@@ -41,6 +96,50 @@ pub enum ConstructionTab {
     Floors,
 }
 
+impl ConstructionTab {
+    /// In toolbar order; tab N opens with number key N
+    pub fn all() -> [ConstructionTab; 7] {
+        [
+            ConstructionTab::Orders,
+            ConstructionTab::Structure,
+            ConstructionTab::Furniture,
+            ConstructionTab::Bath,
+            ConstructionTab::Staff,
+            ConstructionTab::Decoration,
+            ConstructionTab::Floors,
+        ]
+    }
+
+    /// The tab whose buttons include `building_type`
+    pub fn containing(building_type: BuildingType) -> ConstructionTab {
+        match building_type {
+            BuildingType::Wall
+            | BuildingType::Door
+            | BuildingType::Window
+            | BuildingType::Boundary(_) => ConstructionTab::Structure,
+            BuildingType::Floor(_) => ConstructionTab::Floors,
+            BuildingType::Furniture(furniture_type) => {
+                match furniture_type.definition().category {
+                    FurnitureCategory::Furniture => ConstructionTab::Furniture,
+                    FurnitureCategory::Bath => ConstructionTab::Bath,
+                    FurnitureCategory::Staff => ConstructionTab::Staff,
+                }
+            }
+        }
+    }
+}
+
+/// Number keys that open each tab, in `ConstructionTab::all` order
+const TAB_KEYS: [KeyCode; 7] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildingType {
     Wall,
@@ -48,43 +147,65 @@ pub enum BuildingType {
     Window,
     Floor(crate::components::FloorType),
     Furniture(crate::components::FurnitureType),
+    Boundary(crate::components::BoundaryType),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderType {
     Deconstruct,
+    /// Recolours walls and floors in place
+    Paint,
+}
+
+/// Limits what a deconstruct drag picks up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeconstructFilter {
+    #[default]
+    All,
+    Structures,
+    Furniture,
+    Floors,
+}
+
+impl DeconstructFilter {
+    pub fn all() -> [DeconstructFilter; 4] {
+        [
+            DeconstructFilter::All,
+            DeconstructFilter::Structures,
+            DeconstructFilter::Furniture,
+            DeconstructFilter::Floors,
+        ]
+    }
+
+    /// Localization key for the filter's button label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            DeconstructFilter::All => "toolbar.filter.all",
+            DeconstructFilter::Structures => "toolbar.filter.structures",
+            DeconstructFilter::Furniture => "toolbar.filter.furniture",
+            DeconstructFilter::Floors => "toolbar.filter.floors",
+        }
+    }
 }
 
 impl BuildingType {
-    pub fn cost(&self) -> i32 {
+    pub fn cost(&self, balance: &Balance) -> i32 {
         match self {
-            BuildingType::Wall => 10,
-            BuildingType::Door => 50,
-            BuildingType::Window => 30,
-            BuildingType::Floor(floor_type) => {
-                use crate::components::FloorType;
-                match floor_type {
-                    FloorType::Wood => 5,
-                    FloorType::Stone => 8,
-                    FloorType::Carpet => 12,
-                    FloorType::Tile => 10,
-                }
-            }
-            BuildingType::Furniture(furniture_type) => {
-                use crate::components::{BedType, FurnitureType};
-                match furniture_type {
-                    FurnitureType::Bed(BedType::Single) => 200,
-                    FurnitureType::Bed(BedType::Double) => 350,
-                    FurnitureType::Desk => 100,
-                    FurnitureType::Chair => 50,
-                    FurnitureType::Dresser => 150,
-                    FurnitureType::Nightstand => 75,
-                    FurnitureType::Toilet => 125,
-                    FurnitureType::Sink => 80,
-                    FurnitureType::Tub => 275,
-                    FurnitureType::ReceptionConsole => 300,
-                }
-            }
+            BuildingType::Wall => balance.costs.wall,
+            BuildingType::Door => balance.costs.door,
+            BuildingType::Window => balance.costs.window,
+            BuildingType::Floor(floor_type) => balance.costs.floor(*floor_type),
+            BuildingType::Furniture(furniture_type) => furniture_type.cost(),
+            BuildingType::Boundary(boundary_type) => balance.costs.boundary(*boundary_type),
+        }
+    }
+
+    /// Walls, floors, fences and hedges go down over every tile of a drag
+    pub fn drag_buildable(&self) -> bool {
+        match self {
+            BuildingType::Wall | BuildingType::Floor(_) => true,
+            BuildingType::Boundary(boundary_type) => *boundary_type != BoundaryType::Gate,
+            BuildingType::Door | BuildingType::Window | BuildingType::Furniture(_) => false,
         }
     }
 }
@@ -94,6 +215,13 @@ pub struct ToolbarState {
     pub active_tab: Option<ConstructionTab>,
     pub selected_building: Option<BuildingType>,
     pub selected_order: Option<OrderType>,
+    pub deconstruct_filter: DeconstructFilter,
+    /// What a Paint drag puts on the walls and floors
+    pub paint_color: PaintColor,
+    /// The last thing a blueprint was placed for, which Q selects again
+    pub last_built: Option<BuildingType>,
+    /// Placing only sketches free plan markers, kept while switching tabs
+    pub planning: bool,
 }
 
 pub struct ToolbarPlugin;
@@ -106,15 +234,36 @@ impl Plugin for ToolbarPlugin {
                 Update,
                 (
                     handle_tab_clicks,
+                    handle_toolbar_hotkeys,
+                    remember_last_built,
+                    update_tab_button_colors,
                     handle_build_button_clicks,
                     handle_order_button_clicks,
+                    handle_deconstruct_filter_clicks,
+                    handle_paint_color_clicks,
+                    handle_plan_button_clicks,
                     update_button_colors,
                     update_order_button_colors,
-                    handle_work_assignments_button_clicks,
-                    update_work_assignments_button_colors,
-                    handle_save_load_button_clicks,
-                    update_save_load_button_colors,
-                ),
+                    update_deconstruct_filter_colors,
+                    update_paint_color_borders,
+                    update_plan_button_colors,
+                    // Panel shortcuts
+                    (
+                        handle_work_assignments_button_clicks,
+                        update_work_assignments_button_colors,
+                        handle_save_load_button_clicks,
+                        update_save_load_button_colors,
+                        handle_economy_button_clicks,
+                        update_economy_button_colors,
+                        handle_statistics_button_clicks,
+                        update_statistics_button_colors,
+                        handle_settings_button_clicks,
+                        update_settings_button_colors,
+                        handle_language_button_clicks,
+                        update_language_button,
+                    ),
+                )
+                    .in_set(GameplaySet),
             );
     }
 }
@@ -139,21 +288,30 @@ fn setup_toolbar(mut commands: Commands) {
         ))
         .with_children(|parent| {
             // Tab buttons
-            spawn_tab_button(parent, ConstructionTab::Orders, "Orders");
-            spawn_tab_button(parent, ConstructionTab::Structure, "Structure");
-            spawn_tab_button(parent, ConstructionTab::Furniture, "Furniture");
-            spawn_tab_button(parent, ConstructionTab::Bath, "Bath");
-            spawn_tab_button(parent, ConstructionTab::Staff, "Staff");
-            spawn_tab_button(parent, ConstructionTab::Decoration, "Decoration");
-            spawn_tab_button(parent, ConstructionTab::Floors, "Floors");
+            spawn_tab_button(parent, ConstructionTab::Orders, "toolbar.tab.orders", 1);
+            spawn_tab_button(parent, ConstructionTab::Structure, "toolbar.tab.structure", 2);
+            spawn_tab_button(parent, ConstructionTab::Furniture, "toolbar.tab.furniture", 3);
+            spawn_tab_button(parent, ConstructionTab::Bath, "toolbar.tab.bath", 4);
+            spawn_tab_button(parent, ConstructionTab::Staff, "toolbar.tab.staff", 5);
+            spawn_tab_button(parent, ConstructionTab::Decoration, "toolbar.tab.decoration", 6);
+            spawn_tab_button(parent, ConstructionTab::Floors, "toolbar.tab.floors", 7);
 
             // Panel shortcuts
             spawn_work_assignments_button(parent);
             spawn_save_load_button(parent);
+            spawn_economy_button(parent);
+            spawn_statistics_button(parent);
+            spawn_settings_button(parent);
+            spawn_language_button(parent);
         });
 }
 
-fn spawn_tab_button(parent: &mut ChildBuilder, tab: ConstructionTab, label: &str) {
+fn spawn_tab_button(
+    parent: &mut ChildBuilder,
+    tab: ConstructionTab,
+    label_key: &str,
+    hotkey: usize,
+) {
     parent
         .spawn((
             Button,
@@ -169,17 +327,33 @@ fn spawn_tab_button(parent: &mut ChildBuilder, tab: ConstructionTab, label: &str
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(label),
+                Text::default(),
+                LocalizedText::new(label_key),
                 TextFont {
                     font_size: 16.0,
                     ..default()
                 },
                 TextColor(Color::WHITE),
             ));
+            // Number key that opens the tab
+            parent.spawn((
+                Text::new(hotkey.to_string()),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(3.0),
+                    left: Val::Px(5.0),
+                    ..default()
+                },
+            ));
         });
 }
 
-fn spawn_build_button(parent: &mut ChildBuilder, build_type: BuildingType, label: &str) {
+fn spawn_build_button(parent: &mut ChildBuilder, build_type: BuildingType, label: LocalizedText) {
     parent
         .spawn((
             Button,
@@ -196,7 +370,8 @@ fn spawn_build_button(parent: &mut ChildBuilder, build_type: BuildingType, label
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(label),
+                Text::default(),
+                label,
                 TextFont {
                     font_size: 12.0,
                     ..default()
@@ -206,7 +381,7 @@ fn spawn_build_button(parent: &mut ChildBuilder, build_type: BuildingType, label
         });
 }
 
-fn spawn_order_button(parent: &mut ChildBuilder, order_type: OrderType, label: &str) {
+fn spawn_order_button(parent: &mut ChildBuilder, order_type: OrderType, label_key: &str) {
     parent
         .spawn((
             Button,
@@ -223,7 +398,94 @@ fn spawn_order_button(parent: &mut ChildBuilder, order_type: OrderType, label: &
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(label),
+                Text::default(),
+                LocalizedText::new(label_key),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_deconstruct_filter_button(parent: &mut ChildBuilder, filter: DeconstructFilter) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(BUTTON_SIZE),
+                height: Val::Px(BUTTON_SIZE),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+            DeconstructFilterButton { filter },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(filter.label_key()),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_paint_color_button(parent: &mut ChildBuilder, color: PaintColor) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(SWATCH_WIDTH),
+                height: Val::Px(BUTTON_SIZE),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::End,
+                margin: UiRect::all(Val::Px(2.0)),
+                border: UiRect::all(Val::Px(3.0)),
+                ..default()
+            },
+            BackgroundColor(color.color()),
+            BorderColor(Color::NONE),
+            PaintColorButton { color },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(color.label_key()),
+                TextFont {
+                    font_size: 9.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.1, 0.1, 0.1)),
+            ));
+        });
+}
+
+fn spawn_plan_button(parent: &mut ChildBuilder, action: PlanAction, label_key: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(BUTTON_SIZE),
+                height: Val::Px(BUTTON_SIZE),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+            PlanButton { action },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(label_key),
                 TextFont {
                     font_size: 12.0,
                     ..default()
@@ -249,7 +511,89 @@ fn spawn_work_assignments_button(parent: &mut ChildBuilder) {
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("Assignments"),
+                Text::default(),
+                LocalizedText::new("toolbar.assignments"),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_economy_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(120.0),
+                height: Val::Px(70.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            EconomyButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("toolbar.economy"),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_statistics_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(120.0),
+                height: Val::Px(70.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            StatisticsButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("toolbar.statistics"),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_settings_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(100.0),
+                height: Val::Px(70.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            SettingsButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("toolbar.settings"),
                 TextFont {
                     font_size: 16.0,
                     ..default()
@@ -275,7 +619,8 @@ fn spawn_save_load_button(parent: &mut ChildBuilder) {
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("Save/Load"),
+                Text::default(),
+                LocalizedText::new("toolbar.save_load"),
                 TextFont {
                     font_size: 16.0,
                     ..default()
@@ -285,172 +630,372 @@ fn spawn_save_load_button(parent: &mut ChildBuilder) {
         });
 }
 
-fn handle_tab_clicks(
-    mut interaction_query: Query<
-        (&Interaction, &TabButton, &mut BackgroundColor),
-        Changed<Interaction>,
+fn spawn_language_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(100.0),
+                height: Val::Px(70.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            LanguageButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                LanguageButtonLabel,
+            ));
+        });
+}
+
+/// Everything needed to switch the toolbar to another tab
+#[derive(SystemParam)]
+pub(super) struct TabSwitcher<'w, 's> {
+    commands: Commands<'w, 's>,
+    toolbar_state: ResMut<'w, ToolbarState>,
+    toolbar_query: Query<'w, 's, Entity, With<Toolbar>>,
+    button_query: Query<
+        'w,
+        's,
+        Entity,
+        Or<(
+            With<BuildButton>,
+            With<OrderButton>,
+            With<DeconstructFilterButton>,
+            With<PaintColorButton>,
+            With<PlanButton>,
+        )>,
     >,
-    mut toolbar_state: ResMut<ToolbarState>,
-    mut commands: Commands,
-    toolbar_query: Query<Entity, With<Toolbar>>,
-    build_button_query: Query<Entity, With<BuildButton>>,
-    order_button_query: Query<Entity, With<OrderButton>>,
-) {
-    for (interaction, tab_button, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                // Toggle tab or select new tab
-                if toolbar_state.active_tab == Some(tab_button.tab) {
-                    toolbar_state.active_tab = None;
-                    toolbar_state.selected_building = None;
-                    toolbar_state.selected_order = None;
-                    // Remove build buttons
-                    for entity in &build_button_query {
-                        commands.entity(entity).despawn_recursive();
-                    }
-                } else {
-                    toolbar_state.active_tab = Some(tab_button.tab);
-                    toolbar_state.selected_building = None;
-                    toolbar_state.selected_order = None;
+    furniture: Res<'w, FurnitureRegistry>,
+}
 
-                    // Remove existing build buttons and order buttons
-                    for entity in &build_button_query {
-                        commands.entity(entity).despawn_recursive();
-                    }
-                    for entity in &order_button_query {
-                        commands.entity(entity).despawn_recursive();
-                    }
+impl TabSwitcher<'_, '_> {
+    /// Opens `tab`, or closes it if it is already open
+    fn toggle(&mut self, tab: ConstructionTab) {
+        if self.toolbar_state.active_tab == Some(tab) {
+            self.set_tab(None);
+        } else {
+            self.set_tab(Some(tab));
+        }
+    }
+
+    /// Swaps the buttons after the tabs for the ones in `tab`, clearing the selection
+    fn set_tab(&mut self, tab: Option<ConstructionTab>) {
+        self.toolbar_state.active_tab = tab;
+        self.toolbar_state.selected_building = None;
+        self.toolbar_state.selected_order = None;
 
-                    // Spawn new buttons for this tab
-                    if let Ok(toolbar_entity) = toolbar_query.get_single() {
-                        commands.entity(toolbar_entity).with_children(|parent| {
-                            match tab_button.tab {
-                                ConstructionTab::Orders => {
-                                    spawn_order_button(
-                                        parent,
-                                        OrderType::Deconstruct,
-                                        "Deconstruct",
-                                    );
-                                }
-                                ConstructionTab::Structure => {
-                                    spawn_build_button(parent, BuildingType::Wall, "Wall");
-                                    spawn_build_button(parent, BuildingType::Door, "Door");
-                                    spawn_build_button(parent, BuildingType::Window, "Window");
-                                }
-                                ConstructionTab::Furniture => {
-                                    use crate::components::{BedType, FurnitureType};
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Bed(
-                                            BedType::Single,
-                                        )),
-                                        "Single Bed",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Bed(
-                                            BedType::Double,
-                                        )),
-                                        "Double Bed",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Desk),
-                                        "Desk",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Chair),
-                                        "Chair",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Dresser),
-                                        "Dresser",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Nightstand),
-                                        "Nightstand",
-                                    );
-                                }
-                                ConstructionTab::Bath => {
-                                    use crate::components::FurnitureType;
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Tub),
-                                        "Tub",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Sink),
-                                        "Sink",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::Toilet),
-                                        "Toilet",
-                                    );
-                                }
-                                ConstructionTab::Staff => {
-                                    use crate::components::FurnitureType;
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Furniture(FurnitureType::ReceptionConsole),
-                                        "Reception",
-                                    );
-                                }
-                                ConstructionTab::Floors => {
-                                    use crate::components::FloorType;
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Floor(FloorType::Wood),
-                                        "Wood",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Floor(FloorType::Stone),
-                                        "Stone",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Floor(FloorType::Carpet),
-                                        "Carpet",
-                                    );
-                                    spawn_build_button(
-                                        parent,
-                                        BuildingType::Floor(FloorType::Tile),
-                                        "Tile",
-                                    );
-                                }
-                                _ => {
-                                    // TODO: Add other categories
-                                }
-                            }
-                        });
+        for entity in &self.button_query {
+            self.commands.entity(entity).despawn_recursive();
+        }
+
+        let (Some(tab), Ok(toolbar_entity)) = (tab, self.toolbar_query.get_single()) else {
+            return;
+        };
+        let furniture = &self.furniture;
+        self.commands
+            .entity(toolbar_entity)
+            .with_children(|parent| {
+                if tab == ConstructionTab::Orders {
+                    spawn_order_button(
+                        parent,
+                        OrderType::Deconstruct,
+                        "toolbar.order.deconstruct",
+                    );
+                    for filter in DeconstructFilter::all() {
+                        spawn_deconstruct_filter_button(parent, filter);
+                    }
+                    spawn_order_button(parent, OrderType::Paint, "toolbar.order.paint");
+                    for color in PaintColor::all() {
+                        spawn_paint_color_button(parent, color);
                     }
+                    spawn_plan_button(parent, PlanAction::Toggle, "toolbar.plan.toggle");
+                    spawn_plan_button(
+                        parent,
+                        PlanAction::Order(PlanOrder::Convert),
+                        "toolbar.plan.convert",
+                    );
+                    spawn_plan_button(
+                        parent,
+                        PlanAction::Order(PlanOrder::Clear),
+                        "toolbar.plan.clear",
+                    );
                 }
+                for (build_type, label) in tab_build_items(tab, furniture) {
+                    spawn_build_button(parent, build_type, label);
+                }
+            });
+    }
+
+    /// Selects `build_type`, opening the tab it's under first
+    pub(super) fn select_building(&mut self, build_type: BuildingType) {
+        let tab = ConstructionTab::containing(build_type);
+        if self.toolbar_state.active_tab != Some(tab) {
+            self.set_tab(Some(tab));
+        }
+        self.toolbar_state.selected_order = None;
+        self.toolbar_state.selected_building = Some(build_type);
+    }
+
+    /// Selects `build_type`, or puts it down if it's already selected
+    pub(super) fn toggle_building(&mut self, build_type: BuildingType) {
+        if self.toolbar_state.selected_building == Some(build_type) {
+            self.toolbar_state.selected_building = None;
+        } else {
+            self.select_building(build_type);
+        }
+    }
+}
+
+/// The label `build_type` has on its build button
+pub(super) fn build_item_label(
+    build_type: BuildingType,
+    furniture: &FurnitureRegistry,
+) -> Option<LocalizedText> {
+    tab_build_items(ConstructionTab::containing(build_type), furniture)
+        .into_iter()
+        .find(|(item, _)| *item == build_type)
+        .map(|(_, label)| label)
+}
+
+/// What each build button in a tab places, in button order, with its label.
+/// Mod furniture shows up alongside the stock pieces of the same category.
+fn tab_build_items(
+    tab: ConstructionTab,
+    furniture: &FurnitureRegistry,
+) -> Vec<(BuildingType, LocalizedText)> {
+    use crate::components::FloorType;
+
+    let furniture_items = |category| {
+        furniture
+            .in_category(category)
+            .map(|(furniture_type, definition)| {
+                (
+                    BuildingType::Furniture(furniture_type),
+                    LocalizedText::with_fallback(
+                        format!("furniture.{}", definition.id),
+                        definition.name.clone(),
+                    ),
+                )
+            })
+            .collect()
+    };
+
+    match tab {
+        ConstructionTab::Structure => vec![
+            (BuildingType::Wall, LocalizedText::new("toolbar.build.wall")),
+            (BuildingType::Door, LocalizedText::new("toolbar.build.door")),
+            (BuildingType::Window, LocalizedText::new("toolbar.build.window")),
+            (
+                BuildingType::Boundary(BoundaryType::Fence),
+                LocalizedText::new("toolbar.build.fence"),
+            ),
+            (
+                BuildingType::Boundary(BoundaryType::Hedge),
+                LocalizedText::new("toolbar.build.hedge"),
+            ),
+            (
+                BuildingType::Boundary(BoundaryType::Gate),
+                LocalizedText::new("toolbar.build.gate"),
+            ),
+        ],
+        ConstructionTab::Furniture => furniture_items(FurnitureCategory::Furniture),
+        ConstructionTab::Bath => furniture_items(FurnitureCategory::Bath),
+        ConstructionTab::Staff => furniture_items(FurnitureCategory::Staff),
+        ConstructionTab::Floors => vec![
+            (
+                BuildingType::Floor(FloorType::Wood),
+                LocalizedText::new("toolbar.floor.wood"),
+            ),
+            (
+                BuildingType::Floor(FloorType::Stone),
+                LocalizedText::new("toolbar.floor.stone"),
+            ),
+            (
+                BuildingType::Floor(FloorType::Carpet),
+                LocalizedText::new("toolbar.floor.carpet"),
+            ),
+            (
+                BuildingType::Floor(FloorType::Tile),
+                LocalizedText::new("toolbar.floor.tile"),
+            ),
+        ],
+        // Orders have their own buttons, and decorations are still to come
+        ConstructionTab::Orders | ConstructionTab::Decoration => Vec::new(),
+    }
+}
+
+fn handle_tab_clicks(
+    interaction_query: Query<(&Interaction, &TabButton), Changed<Interaction>>,
+    mut tabs: TabSwitcher,
+) {
+    for (interaction, tab_button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            tabs.toggle(tab_button.tab);
+        }
+    }
+}
+
+fn update_tab_button_colors(
+    mut tab_query: Query<(&TabButton, &Interaction, &mut BackgroundColor)>,
+    toolbar_state: Res<ToolbarState>,
+) {
+    for (tab_button, interaction, mut color) in &mut tab_query {
+        // Tabs opened by hotkey light up just like clicked ones
+        let target: BackgroundColor = if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else if toolbar_state.active_tab == Some(tab_button.tab) {
+            Color::srgb(0.4, 0.4, 0.4).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+        color.set_if_neq(target);
+    }
+}
+
+/// Number keys open tabs, Tab and Shift+Tab step through what the open tab builds (or the
+/// deconstruct filters under Orders), and Q picks up whatever was last built. Number keys
+/// with Shift held are left to the hotbar.
+fn handle_toolbar_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ui_blocker: Res<UiInputBlocker>,
+    save_load_state: Res<SaveLoadPanelState>,
+    rules: Res<GameRules>,
+    clock: Res<GameClock>,
+    mut tabs: TabSwitcher,
+) {
+    // The save name field takes number keys too
+    if ui_blocker.text_input_active || save_load_state.visible {
+        return;
+    }
+
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for (tab, key) in ConstructionTab::all().into_iter().zip(TAB_KEYS) {
+        if keyboard.just_pressed(key) && !shift {
+            tabs.toggle(tab);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Tab) {
+        let step = |index: Option<usize>, len: usize| match (index, shift) {
+            (Some(index), false) => (index + 1) % len,
+            (Some(index), true) => (index + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+
+        match tabs.toolbar_state.active_tab {
+            Some(ConstructionTab::Orders)
+                if tabs.toolbar_state.selected_order == Some(OrderType::Paint) =>
+            {
+                let colors = PaintColor::all();
+                let current = colors
+                    .iter()
+                    .position(|color| *color == tabs.toolbar_state.paint_color);
+                tabs.toolbar_state.paint_color = colors[step(current, colors.len())];
             }
-            Interaction::Hovered => {
-                *color = Color::srgb(0.35, 0.35, 0.35).into();
-            }
-            Interaction::None => {
-                if toolbar_state.active_tab == Some(tab_button.tab) {
-                    *color = Color::srgb(0.4, 0.4, 0.4).into();
+            Some(ConstructionTab::Orders) => {
+                let filters = DeconstructFilter::all();
+                let current = filters
+                    .iter()
+                    .position(|filter| *filter == tabs.toolbar_state.deconstruct_filter);
+                // The first press just picks up the deconstruct order
+                let index = if tabs.toolbar_state.selected_order.is_some() {
+                    step(current, filters.len())
                 } else {
-                    *color = Color::srgb(0.25, 0.25, 0.25).into();
+                    current.unwrap_or(0)
+                };
+                tabs.toolbar_state.deconstruct_filter = filters[index];
+                tabs.toolbar_state.selected_order = Some(OrderType::Deconstruct);
+            }
+            Some(tab) => {
+                let items: Vec<BuildingType> = tab_build_items(tab, &tabs.furniture)
+                    .into_iter()
+                    .map(|(build_type, _)| build_type)
+                    .filter(|build_type| !is_locked(*build_type, &rules, &clock))
+                    .collect();
+                if !items.is_empty() {
+                    let current = tabs
+                        .toolbar_state
+                        .selected_building
+                        .and_then(|selected| items.iter().position(|item| *item == selected));
+                    tabs.toolbar_state.selected_building = Some(items[step(current, items.len())]);
                 }
             }
+            None => {}
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyQ) {
+        if let Some(last_built) = tabs.toolbar_state.last_built {
+            if !is_locked(last_built, &rules, &clock) {
+                tabs.select_building(last_built);
+            }
         }
     }
 }
 
+/// Notes what was selected whenever a blueprint goes down, for Q to repeat
+fn remember_last_built(
+    blueprint_query: Query<(), Added<Blueprint>>,
+    mut toolbar_state: ResMut<ToolbarState>,
+) {
+    if blueprint_query.is_empty() {
+        return;
+    }
+    if let Some(selected) = toolbar_state.selected_building {
+        if toolbar_state.last_built != Some(selected) {
+            toolbar_state.last_built = Some(selected);
+        }
+    }
+}
+
+// Furniture that hasn't been unlocked yet in a normal game can't be selected
+pub(super) fn is_locked(build_type: BuildingType, rules: &GameRules, clock: &GameClock) -> bool {
+    match build_type {
+        BuildingType::Furniture(furniture_type) => {
+            !rules.is_furniture_unlocked(furniture_type, clock.day())
+        }
+        _ => false,
+    }
+}
+
 fn handle_build_button_clicks(
     mut interaction_query: Query<(&Interaction, &BuildButton), Changed<Interaction>>,
     mut toolbar_state: ResMut<ToolbarState>,
+    rules: Res<GameRules>,
+    clock: Res<GameClock>,
 ) {
     for (interaction, build_button) in &mut interaction_query {
         if *interaction == Interaction::Pressed {
+            if is_locked(build_button.build_type, &rules, &clock) {
+                if let BuildingType::Furniture(furniture_type) = build_button.build_type {
+                    if let Some(event) = furniture_type.event().and_then(CalendarEvent::from_id) {
+                        info!(
+                            "{} is only sold during the {}",
+                            furniture_type.name(),
+                            event.name()
+                        );
+                    } else {
+                        info!(
+                            "{} unlocks on day {}",
+                            furniture_type.name(),
+                            furniture_type.unlock_day()
+                        );
+                    }
+                }
+                continue;
+            }
+
             if toolbar_state.selected_building == Some(build_button.build_type) {
                 toolbar_state.selected_building = None;
             } else {
@@ -463,10 +1008,18 @@ fn handle_build_button_clicks(
 fn update_button_colors(
     mut build_button_query: Query<(&BuildButton, &mut BackgroundColor, &Interaction)>,
     toolbar_state: Res<ToolbarState>,
+    rules: Res<GameRules>,
+    clock: Res<GameClock>,
 ) {
     for (build_button, mut color, interaction) in &mut build_button_query {
         if toolbar_state.selected_building == Some(build_button.build_type) {
-            *color = Color::srgb(0.5, 0.7, 0.5).into(); // Green when selected
+            *color = if toolbar_state.planning {
+                PLANNING_COLOR.into()
+            } else {
+                Color::srgb(0.5, 0.7, 0.5).into() // Green when selected
+            };
+        } else if is_locked(build_button.build_type, &rules, &clock) {
+            *color = Color::srgb(0.15, 0.15, 0.15).into(); // Dark when locked
         } else {
             match interaction {
                 Interaction::Hovered => {
@@ -502,7 +1055,110 @@ fn update_order_button_colors(
 ) {
     for (order_button, mut color, interaction) in &mut order_button_query {
         if toolbar_state.selected_order == Some(order_button.order_type) {
-            *color = Color::srgb(0.7, 0.4, 0.4).into(); // Red when selected (destructive action)
+            *color = match order_button.order_type {
+                OrderType::Deconstruct => Color::srgb(0.7, 0.4, 0.4).into(), // Red: destructive
+                OrderType::Paint => Color::srgb(0.5, 0.7, 0.5).into(),
+            };
+        } else {
+            match interaction {
+                Interaction::Hovered => {
+                    *color = Color::srgb(0.4, 0.4, 0.4).into();
+                }
+                _ => {
+                    *color = Color::srgb(0.3, 0.3, 0.3).into();
+                }
+            }
+        }
+    }
+}
+
+fn handle_deconstruct_filter_clicks(
+    interaction_query: Query<(&Interaction, &DeconstructFilterButton), Changed<Interaction>>,
+    mut toolbar_state: ResMut<ToolbarState>,
+) {
+    for (interaction, filter_button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            toolbar_state.deconstruct_filter = filter_button.filter;
+        }
+    }
+}
+
+/// Picking a colour also picks up the Paint order
+fn handle_paint_color_clicks(
+    interaction_query: Query<(&Interaction, &PaintColorButton), Changed<Interaction>>,
+    mut toolbar_state: ResMut<ToolbarState>,
+) {
+    for (interaction, paint_button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            toolbar_state.paint_color = paint_button.color;
+            toolbar_state.selected_order = Some(OrderType::Paint);
+            toolbar_state.selected_building = None;
+        }
+    }
+}
+
+/// The planning toggle leaves whatever building is selected alone, so sketching can go
+/// straight on from another tab
+fn handle_plan_button_clicks(
+    interaction_query: Query<(&Interaction, &PlanButton), Changed<Interaction>>,
+    mut toolbar_state: ResMut<ToolbarState>,
+    mut plan_orders: EventWriter<PlanOrder>,
+) {
+    for (interaction, plan_button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match plan_button.action {
+                PlanAction::Toggle => toolbar_state.planning = !toolbar_state.planning,
+                PlanAction::Order(order) => {
+                    plan_orders.send(order);
+                }
+            }
+        }
+    }
+}
+
+fn update_plan_button_colors(
+    mut plan_button_query: Query<(&PlanButton, &mut BackgroundColor, &Interaction)>,
+    toolbar_state: Res<ToolbarState>,
+) {
+    for (plan_button, mut color, interaction) in &mut plan_button_query {
+        if plan_button.action == PlanAction::Toggle && toolbar_state.planning {
+            *color = PLANNING_COLOR.into();
+        } else {
+            match interaction {
+                Interaction::Hovered => {
+                    *color = Color::srgb(0.4, 0.4, 0.4).into();
+                }
+                _ => {
+                    *color = Color::srgb(0.3, 0.3, 0.3).into();
+                }
+            }
+        }
+    }
+}
+
+/// Swatches keep their own colour, so the chosen one is outlined instead
+fn update_paint_color_borders(
+    mut paint_button_query: Query<(&PaintColorButton, &mut BorderColor, &Interaction)>,
+    toolbar_state: Res<ToolbarState>,
+) {
+    for (paint_button, mut border, interaction) in &mut paint_button_query {
+        *border = if toolbar_state.paint_color == paint_button.color {
+            Color::WHITE.into()
+        } else if *interaction == Interaction::Hovered {
+            Color::srgb(0.6, 0.6, 0.6).into()
+        } else {
+            Color::NONE.into()
+        };
+    }
+}
+
+fn update_deconstruct_filter_colors(
+    mut filter_button_query: Query<(&DeconstructFilterButton, &mut BackgroundColor, &Interaction)>,
+    toolbar_state: Res<ToolbarState>,
+) {
+    for (filter_button, mut color, interaction) in &mut filter_button_query {
+        if toolbar_state.deconstruct_filter == filter_button.filter {
+            *color = Color::srgb(0.5, 0.7, 0.5).into(); // Green when active
         } else {
             match interaction {
                 Interaction::Hovered => {
@@ -554,9 +1210,6 @@ fn handle_save_load_button_clicks(
     for interaction in &mut interaction_query {
         if *interaction == Interaction::Pressed {
             panel_state.toggle();
-            if panel_state.visible {
-                panel_state.refresh_saves_list();
-            }
         }
     }
 }
@@ -580,3 +1233,122 @@ fn update_save_load_button_colors(
         }
     }
 }
+
+fn handle_economy_button_clicks(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<EconomyButton>)>,
+    mut panel_state: ResMut<EconomyPanelState>,
+) {
+    for interaction in &mut interaction_query {
+        if *interaction == Interaction::Pressed {
+            panel_state.visible = !panel_state.visible;
+        }
+    }
+}
+
+fn update_economy_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<EconomyButton>>,
+    panel_state: Res<EconomyPanelState>,
+) {
+    for (mut color, interaction) in &mut button_query {
+        if panel_state.visible {
+            *color = Color::srgb(0.4, 0.6, 0.4).into();
+        } else {
+            match interaction {
+                Interaction::Hovered => {
+                    *color = Color::srgb(0.35, 0.35, 0.35).into();
+                }
+                _ => {
+                    *color = Color::srgb(0.25, 0.25, 0.25).into();
+                }
+            }
+        }
+    }
+}
+
+fn handle_statistics_button_clicks(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<StatisticsButton>)>,
+    mut panel_state: ResMut<StatisticsPanelState>,
+) {
+    for interaction in &mut interaction_query {
+        if *interaction == Interaction::Pressed {
+            panel_state.visible = !panel_state.visible;
+        }
+    }
+}
+
+fn update_statistics_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<StatisticsButton>>,
+    panel_state: Res<StatisticsPanelState>,
+) {
+    for (mut color, interaction) in &mut button_query {
+        if panel_state.visible {
+            *color = Color::srgb(0.4, 0.6, 0.4).into();
+        } else {
+            match interaction {
+                Interaction::Hovered => {
+                    *color = Color::srgb(0.35, 0.35, 0.35).into();
+                }
+                _ => {
+                    *color = Color::srgb(0.25, 0.25, 0.25).into();
+                }
+            }
+        }
+    }
+}
+
+fn handle_settings_button_clicks(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut panel_state: ResMut<SettingsPanelState>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            panel_state.visible = !panel_state.visible;
+        }
+    }
+}
+
+fn update_settings_button_colors(
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<SettingsButton>>,
+    panel_state: Res<SettingsPanelState>,
+) {
+    for (mut color, interaction) in &mut button_query {
+        *color = if panel_state.visible {
+            Color::srgb(0.4, 0.6, 0.4).into()
+        } else if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+    }
+}
+
+fn handle_language_button_clicks(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<LanguageButton>)>,
+    mut localization: ResMut<Localization>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            localization.cycle_language();
+            info!("Language set to {}", localization.language_name());
+        }
+    }
+}
+
+fn update_language_button(
+    localization: Res<Localization>,
+    mut label_query: Query<&mut Text, With<LanguageButtonLabel>>,
+    mut button_query: Query<(&mut BackgroundColor, &Interaction), With<LanguageButton>>,
+) {
+    if localization.is_changed() {
+        for mut text in &mut label_query {
+            text.0 = localization.language_name().to_string();
+        }
+    }
+
+    for (mut color, interaction) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered => Color::srgb(0.35, 0.35, 0.35).into(),
+            _ => Color::srgb(0.25, 0.25, 0.25).into(),
+        };
+    }
+}
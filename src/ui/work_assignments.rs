@@ -1,9 +1,24 @@
+use super::localization::{Localization, LocalizedText};
+use super::UiInputBlocker;
 use crate::components::*;
+use crate::systems::{
+    generate_pawn_name, spawn_pawn, training_cost, FinanceLedger, GameClock, GameplaySet,
+    JobPriorities, Money, HIRE_COST, MAX_JOB_LEVEL, MAX_PAWN_NAME_LENGTH,
+};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
 use bevy::prelude::*;
 
-const PANEL_WIDTH: f32 = 600.0;
+const PANEL_WIDTH: f32 = 730.0;
 const CELL_SIZE: f32 = 40.0;
 const HEADER_HEIGHT: f32 = 30.0;
+const MOOD_WIDTH: f32 = 110.0;
+const JOBS_WIDTH: f32 = 120.0;
+const TRAINING_WIDTH: f32 = 100.0;
+const SLIDER_STEP_WIDTH: f32 = 28.0;
+const SLIDER_HEIGHT: f32 = 16.0;
+/// Game hours the jobs-per-day figure counts back over
+const JOBS_WINDOW_HOURS: f32 = 24.0;
 
 #[derive(Component)]
 pub struct WorkAssignmentsPanel;
@@ -17,16 +32,93 @@ pub struct WorkAssignmentCell {
     pub work_type: WorkType,
 }
 
+/// Clicking a pawn's name starts renaming them
+#[derive(Component)]
+pub struct PawnNameCell {
+    pub pawn_entity: Entity,
+}
+
+/// A pawn's energy and morale, or that they're on a break
+#[derive(Component)]
+pub struct PawnMoodCell {
+    pub pawn_entity: Entity,
+}
+
+/// How many jobs a pawn has finished lately, and how long they take over one
+#[derive(Component)]
+pub struct PawnJobsCell {
+    pub pawn_entity: Entity,
+}
+
+/// Opens the training modal for a pawn, or shows the course they're booked on
+#[derive(Component)]
+pub struct TrainButton {
+    pub pawn_entity: Entity,
+}
+
+/// Lists the courses a pawn can be sent on, over the middle of the screen
+#[derive(Component)]
+pub struct TrainingModal;
+
+#[derive(Component)]
+pub struct TrainingModalContent;
+
+/// Books a pawn onto a course raising one skill
+#[derive(Component)]
+pub struct TrainingCourseButton {
+    pub pawn_entity: Entity,
+    pub skill: WorkType,
+}
+
+#[derive(Component)]
+pub struct TrainingCancelButton;
+
+/// One step along a kind of work's priority slider; clicking it sets the slider there
+#[derive(Component)]
+pub struct PrioritySliderStep {
+    pub work_type: WorkType,
+    pub level: u8,
+}
+
+/// Names the setting a priority slider is at
+#[derive(Component)]
+pub struct PrioritySliderLabel {
+    pub work_type: WorkType,
+}
+
+/// Takes on a new worker for a one-off fee
+#[derive(Component)]
+pub struct HireButton;
+
+#[derive(Component)]
+pub struct HireButtonLabel;
+
 #[derive(Resource, Default)]
 pub struct WorkAssignmentsPanelState {
     pub visible: bool,
 }
 
+/// The pawn the training modal is open for
+#[derive(Resource, Default)]
+pub struct TrainingModalState {
+    pub pawn: Option<Entity>,
+}
+
+/// The pawn being renamed, and the in-progress name
+#[derive(Resource, Default)]
+pub struct PawnRenameState {
+    pub pawn: Option<Entity>,
+    pub name_buffer: String,
+}
+
 pub struct WorkAssignmentsPlugin;
 
 impl Plugin for WorkAssignmentsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<WorkAssignmentsPanelState>()
+            .init_resource::<PawnRenameState>()
+            .init_resource::<TrainingModalState>()
+            .init_resource::<UiInputBlocker>()
             .add_systems(Startup, setup_work_assignments_panel)
             .add_systems(
                 Update,
@@ -35,7 +127,28 @@ impl Plugin for WorkAssignmentsPlugin {
                     apply_panel_visibility,
                     update_work_assignments_panel,
                     handle_cell_clicks,
-                ),
+                    update_pawn_mood_cells,
+                    update_pawn_jobs_cells,
+                    (handle_priority_slider_clicks, update_priority_sliders).chain(),
+                    handle_hire_button_clicks,
+                    update_hire_button,
+                    (
+                        handle_train_button_clicks,
+                        update_train_buttons,
+                        handle_training_course_clicks,
+                        handle_training_cancel_clicks,
+                        update_training_modal,
+                        update_training_button_colors,
+                    )
+                        .chain(),
+                    (
+                        handle_pawn_name_clicks,
+                        handle_pawn_name_input,
+                        update_pawn_name_cells,
+                    )
+                        .chain(),
+                )
+                    .in_set(GameplaySet),
             );
     }
 }
@@ -61,7 +174,8 @@ fn setup_work_assignments_panel(mut commands: Commands) {
         .with_children(|parent| {
             // Title
             parent.spawn((
-                Text::new("Work Assignments"),
+                Text::default(),
+                LocalizedText::new("work.title"),
                 TextFont {
                     font_size: 20.0,
                     ..default()
@@ -78,26 +192,196 @@ fn setup_work_assignments_panel(mut commands: Commands) {
                 },
                 WorkAssignmentsContent,
             ));
+
+            spawn_priority_sliders(parent);
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        height: Val::Px(32.0),
+                        padding: UiRect::horizontal(Val::Px(8.0)),
+                        align_self: AlignSelf::FlexStart,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    HireButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::default(),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        HireButtonLabel,
+                    ));
+                });
+        });
+
+    // Hidden until a pawn's train button is clicked, centred over the work panel's table
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(120.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-180.0)),
+                width: Val::Px(360.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(15.0)),
+                row_gap: Val::Px(6.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.12, 0.12, 0.12, 0.98)),
+            GlobalZIndex(6),
+            TrainingModal,
+        ))
+        .with_children(|parent| {
+            // Rebuilt for whichever pawn the modal opens for
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                TrainingModalContent,
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        height: Val::Px(28.0),
+                        padding: UiRect::horizontal(Val::Px(8.0)),
+                        align_self: AlignSelf::FlexEnd,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    TrainingCancelButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::default(),
+                        LocalizedText::new("work.training.cancel"),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
         });
 }
 
+/// A slider per kind of work, leaning the whole staff towards it when several kinds of
+/// work are waiting at once
+fn spawn_priority_sliders(parent: &mut ChildBuilder) {
+    parent.spawn((
+        Text::default(),
+        LocalizedText::new("work.priorities"),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    ));
+    parent.spawn((
+        Text::default(),
+        LocalizedText::new("work.priorities_hint"),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+    ));
+
+    for work_type in WorkType::all() {
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(2.0),
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn((
+                    Text::default(),
+                    LocalizedText::term("work_type", work_type.name()),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    Node {
+                        width: Val::Px(150.0),
+                        ..default()
+                    },
+                ));
+
+                for level in 1..=MAX_JOB_LEVEL {
+                    row.spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(SLIDER_STEP_WIDTH),
+                            height: Val::Px(SLIDER_HEIGHT),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                        PrioritySliderStep { work_type, level },
+                    ));
+                }
+
+                row.spawn((
+                    Text::default(),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    Node {
+                        margin: UiRect::left(Val::Px(8.0)),
+                        ..default()
+                    },
+                    PrioritySliderLabel { work_type },
+                ));
+            });
+    }
+}
+
 fn handle_keyboard_panel_toggle(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut panel_state: ResMut<WorkAssignmentsPanelState>,
+    ui_blocker: Res<UiInputBlocker>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyW) {
+    if !ui_blocker.text_input_active && keyboard.just_pressed(KeyCode::KeyW) {
         panel_state.visible = !panel_state.visible;
     }
 }
 
 fn apply_panel_visibility(
     panel_state: Res<WorkAssignmentsPanelState>,
+    mut rename_state: ResMut<PawnRenameState>,
+    mut training_state: ResMut<TrainingModalState>,
     mut panel_query: Query<&mut Node, With<WorkAssignmentsPanel>>,
 ) {
     if !panel_state.is_changed() {
         return;
     }
 
+    // Closing the panel abandons any rename in progress, and the training modal with it
+    if !panel_state.visible && rename_state.pawn.is_some() {
+        rename_state.pawn = None;
+        rename_state.name_buffer.clear();
+    }
+    if !panel_state.visible && training_state.pawn.is_some() {
+        training_state.pawn = None;
+    }
+
     if let Ok(mut style) = panel_query.get_single_mut() {
         style.display = if panel_state.visible {
             Display::Flex
@@ -111,9 +395,13 @@ fn update_work_assignments_panel(
     mut commands: Commands,
     content_query: Query<Entity, With<WorkAssignmentsContent>>,
     pawn_query: Query<(Entity, &Pawn, &WorkAssignments)>,
+    hired_query: Query<(), Added<Pawn>>,
+    mut departed: RemovedComponents<Pawn>,
     panel_state: Res<WorkAssignmentsPanelState>,
     children_query: Query<&Children>,
 ) {
+    let roster_changed = !hired_query.is_empty() || departed.read().count() > 0;
+
     if !panel_state.visible {
         return;
     }
@@ -122,8 +410,8 @@ fn update_work_assignments_panel(
         return;
     };
 
-    // Only rebuild when panel visibility changes
-    if !panel_state.is_changed() {
+    // Only rebuild when panel visibility changes or staff are hired or leave
+    if !panel_state.is_changed() && !roster_changed {
         return;
     }
 
@@ -157,7 +445,8 @@ fn update_work_assignments_panel(
                 ))
                 .with_children(|cell| {
                     cell.spawn((
-                        Text::new("Pawn"),
+                        Text::default(),
+                        LocalizedText::new("work.header.pawn"),
                         TextFont {
                             font_size: 14.0,
                             ..default()
@@ -180,7 +469,8 @@ fn update_work_assignments_panel(
                     ))
                     .with_children(|cell| {
                         cell.spawn((
-                            Text::new(work_type.name()),
+                            Text::default(),
+                            LocalizedText::term("work_type", work_type.name()),
                             TextFont {
                                 font_size: 12.0,
                                 ..default()
@@ -189,6 +479,72 @@ fn update_work_assignments_panel(
                         ));
                     });
                 }
+
+                row.spawn((
+                    Node {
+                        width: Val::Px(MOOD_WIDTH),
+                        height: Val::Px(HEADER_HEIGHT),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                ))
+                .with_children(|cell| {
+                    cell.spawn((
+                        Text::default(),
+                        LocalizedText::new("work.header.mood"),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+                row.spawn((
+                    Node {
+                        width: Val::Px(JOBS_WIDTH),
+                        height: Val::Px(HEADER_HEIGHT),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                ))
+                .with_children(|cell| {
+                    cell.spawn((
+                        Text::default(),
+                        LocalizedText::new("work.header.jobs"),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+                row.spawn((
+                    Node {
+                        width: Val::Px(TRAINING_WIDTH),
+                        height: Val::Px(HEADER_HEIGHT),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                ))
+                .with_children(|cell| {
+                    cell.spawn((
+                        Text::default(),
+                        LocalizedText::new("work.header.training"),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
             });
 
         // Pawn rows
@@ -200,8 +556,9 @@ fn update_work_assignments_panel(
                     ..default()
                 })
                 .with_children(|row| {
-                    // Pawn name
+                    // Pawn name, click to rename
                     row.spawn((
+                        Button,
                         Node {
                             width: Val::Px(150.0),
                             height: Val::Px(CELL_SIZE),
@@ -210,6 +567,7 @@ fn update_work_assignments_panel(
                             ..default()
                         },
                         BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                        PawnNameCell { pawn_entity },
                     ))
                     .with_children(|cell| {
                         cell.spawn((
@@ -257,6 +615,76 @@ fn update_work_assignments_panel(
                             ));
                         });
                     }
+
+                    // Filled in by update_pawn_mood_cells
+                    row.spawn((
+                        Node {
+                            width: Val::Px(MOOD_WIDTH),
+                            height: Val::Px(CELL_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                    ))
+                    .with_children(|cell| {
+                        cell.spawn((
+                            Text::default(),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            PawnMoodCell { pawn_entity },
+                        ));
+                    });
+
+                    // Filled in by update_pawn_jobs_cells
+                    row.spawn((
+                        Node {
+                            width: Val::Px(JOBS_WIDTH),
+                            height: Val::Px(CELL_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                    ))
+                    .with_children(|cell| {
+                        cell.spawn((
+                            Text::default(),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            PawnJobsCell { pawn_entity },
+                        ));
+                    });
+
+                    // Labelled by update_train_buttons
+                    row.spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(TRAINING_WIDTH),
+                            height: Val::Px(CELL_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                        TrainButton { pawn_entity },
+                    ))
+                    .with_children(|cell| {
+                        cell.spawn((
+                            Text::default(),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
                 });
         }
     });
@@ -309,3 +737,561 @@ fn handle_cell_clicks(
         }
     }
 }
+
+fn handle_priority_slider_clicks(
+    interaction_query: Query<(&Interaction, &PrioritySliderStep), Changed<Interaction>>,
+    mut priorities: ResMut<JobPriorities>,
+) {
+    for (interaction, step) in &interaction_query {
+        if *interaction == Interaction::Pressed && priorities.level(step.work_type) != step.level {
+            priorities.set_level(step.work_type, step.level);
+        }
+    }
+}
+
+// Steps up to the current setting are filled in, like a bar
+fn update_priority_sliders(
+    panel_state: Res<WorkAssignmentsPanelState>,
+    priorities: Res<JobPriorities>,
+    localization: Res<Localization>,
+    mut step_query: Query<(&PrioritySliderStep, &Interaction, &mut BackgroundColor)>,
+    mut label_query: Query<(&PrioritySliderLabel, &mut Text)>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    for (step, interaction, mut color) in &mut step_query {
+        let filled = step.level <= priorities.level(step.work_type);
+        let tint = match (filled, interaction) {
+            (true, _) => Color::srgb(0.3, 0.5, 0.3),
+            (false, Interaction::Hovered) => Color::srgb(0.3, 0.3, 0.3),
+            (false, _) => Color::srgb(0.2, 0.2, 0.2),
+        };
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
+
+    for (slider, mut text) in &mut label_query {
+        let level = JobPriorities::level_name(priorities.level(slider.work_type));
+        let label = localization.term("job_level", level);
+        if **text != label {
+            **text = label.to_string();
+        }
+    }
+}
+
+fn handle_pawn_name_clicks(
+    interaction_query: Query<(&Interaction, &PawnNameCell), Changed<Interaction>>,
+    pawn_query: Query<&Pawn>,
+    mut rename_state: ResMut<PawnRenameState>,
+) {
+    for (interaction, cell) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if let Ok(pawn) = pawn_query.get(cell.pawn_entity) {
+            rename_state.pawn = Some(cell.pawn_entity);
+            rename_state.name_buffer = pawn.name.clone();
+        }
+    }
+}
+
+fn handle_pawn_name_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut rename_state: ResMut<PawnRenameState>,
+    mut ui_blocker: ResMut<UiInputBlocker>,
+    mut pawn_query: Query<&mut Pawn>,
+) {
+    let editing = rename_state.pawn.is_some();
+    if ui_blocker.pawn_name_editing != editing {
+        ui_blocker.pawn_name_editing = editing;
+        ui_blocker.recompute();
+    }
+
+    let Some(pawn_entity) = rename_state.pawn else {
+        keyboard_events.clear();
+        return;
+    };
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let new_name = rename_state.name_buffer.trim().to_string();
+                if let Ok(mut pawn) = pawn_query.get_mut(pawn_entity) {
+                    if !new_name.is_empty() {
+                        info!("Renamed pawn '{}' to '{}'", pawn.name, new_name);
+                        pawn.name = new_name;
+                    }
+                }
+                rename_state.pawn = None;
+            }
+            Key::Escape => {
+                rename_state.pawn = None;
+            }
+            Key::Backspace => {
+                rename_state.name_buffer.pop();
+            }
+            Key::Space if rename_state.name_buffer.chars().count() < MAX_PAWN_NAME_LENGTH => {
+                rename_state.name_buffer.push(' ');
+            }
+            Key::Character(text) => {
+                for character in text.chars().filter(|c| !c.is_control()) {
+                    if rename_state.name_buffer.chars().count() < MAX_PAWN_NAME_LENGTH {
+                        rename_state.name_buffer.push(character);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Show the edit buffer with a cursor in the cell being renamed, names elsewhere
+fn update_pawn_name_cells(
+    rename_state: Res<PawnRenameState>,
+    cell_query: Query<(&PawnNameCell, &Children)>,
+    pawn_query: Query<Ref<Pawn>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (cell, children) in &cell_query {
+        let Ok(pawn) = pawn_query.get(cell.pawn_entity) else {
+            continue;
+        };
+        if !rename_state.is_changed() && !pawn.is_changed() {
+            continue;
+        }
+
+        let label = if rename_state.pawn == Some(cell.pawn_entity) {
+            format!("{}_", rename_state.name_buffer)
+        } else {
+            pawn.name.clone()
+        };
+
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                **text = label.clone();
+            }
+        }
+    }
+}
+
+// Energy and morale change constantly, so these cells refresh while the panel is open
+fn update_pawn_mood_cells(
+    panel_state: Res<WorkAssignmentsPanelState>,
+    localization: Res<Localization>,
+    pawn_query: Query<(&StaffNeeds, Has<OnBreak>)>,
+    mut cell_query: Query<(&PawnMoodCell, &mut Text, &mut TextColor)>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    for (cell, mut text, mut color) in &mut cell_query {
+        let Ok((needs, on_break)) = pawn_query.get(cell.pawn_entity) else {
+            continue;
+        };
+
+        let label = if on_break {
+            localization.t("work.on_break").to_string()
+        } else {
+            localization.format(
+                "work.mood",
+                &[
+                    ("energy", format!("{:.0}", needs.energy * 100.0)),
+                    ("morale", format!("{:.0}", needs.morale * 100.0)),
+                ],
+            )
+        };
+        if **text != label {
+            **text = label;
+        }
+
+        // Unhappy staff are flagged so the player notices before work slows down
+        let tint = if needs.morale < 0.5 {
+            Color::srgb(1.0, 0.5, 0.4)
+        } else {
+            Color::WHITE
+        };
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
+}
+
+/// Jobs finished over the last day and the average time each took, so slow or idle
+/// staff stand out when deciding who to move or let go
+fn update_pawn_jobs_cells(
+    panel_state: Res<WorkAssignmentsPanelState>,
+    localization: Res<Localization>,
+    clock: Res<GameClock>,
+    pawn_query: Query<&JobHistory>,
+    mut cell_query: Query<(&PawnJobsCell, &mut Text)>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    for (cell, mut text) in &mut cell_query {
+        let Ok(history) = pawn_query.get(cell.pawn_entity) else {
+            continue;
+        };
+
+        let label = match history.average_hours() {
+            Some(average) => localization.format(
+                "work.jobs",
+                &[
+                    (
+                        "jobs",
+                        history
+                            .jobs_within(clock.elapsed_hours, JOBS_WINDOW_HOURS)
+                            .to_string(),
+                    ),
+                    ("hours", format!("{:.1}", average)),
+                ],
+            ),
+            None => localization.t("work.no_jobs").to_string(),
+        };
+        if **text != label {
+            **text = label;
+        }
+    }
+}
+
+fn handle_hire_button_clicks(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HireButton>)>,
+    pawn_query: Query<&Pawn>,
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut money: ResMut<Money>,
+    mut finance: ResMut<FinanceLedger>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if !money.deduct(HIRE_COST) {
+            info!("Not enough money to hire a worker");
+            continue;
+        }
+
+        let taken: Vec<String> = pawn_query.iter().map(|pawn| pawn.name.clone()).collect();
+        let name = generate_pawn_name(&mut rand::thread_rng(), &taken);
+        info!("Hired {}", name);
+        finance.record(
+            clock.day(),
+            localization.format("staff.hired", &[("name", name.clone())]),
+            -HIRE_COST,
+        );
+        spawn_pawn(&mut commands, &mut meshes, &mut materials, Vec2::ZERO, name);
+    }
+}
+
+fn update_hire_button(
+    panel_state: Res<WorkAssignmentsPanelState>,
+    localization: Res<Localization>,
+    money: Res<Money>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), With<HireButton>>,
+    mut label_query: Query<&mut Text, With<HireButtonLabel>>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    if let Ok(mut text) = label_query.get_single_mut() {
+        let label = localization.format("work.hire", &[("cost", HIRE_COST.to_string())]);
+        if **text != label {
+            **text = label;
+        }
+    }
+
+    if let Ok((interaction, mut color)) = button_query.get_single_mut() {
+        *color = if !money.can_afford(HIRE_COST) {
+            Color::srgb(0.35, 0.2, 0.2).into() // Dim red when unaffordable
+        } else if *interaction == Interaction::Hovered {
+            Color::srgb(0.35, 0.35, 0.35).into()
+        } else {
+            Color::srgb(0.25, 0.25, 0.25).into()
+        };
+    }
+}
+
+/// Pawns already booked on a course or away on one can't be sent on another
+fn handle_train_button_clicks(
+    interaction_query: Query<(&Interaction, &TrainButton), Changed<Interaction>>,
+    pawn_query: Query<(), (Without<TrainingEnrolment>, Without<InTraining>)>,
+    mut training_state: ResMut<TrainingModalState>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed && pawn_query.contains(button.pawn_entity) {
+            training_state.pawn = Some(button.pawn_entity);
+        }
+    }
+}
+
+fn update_train_buttons(
+    panel_state: Res<WorkAssignmentsPanelState>,
+    localization: Res<Localization>,
+    clock: Res<GameClock>,
+    pawn_query: Query<(Option<&TrainingEnrolment>, Option<&InTraining>)>,
+    button_query: Query<(&TrainButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !panel_state.visible {
+        return;
+    }
+
+    for (button, children) in &button_query {
+        let Ok((enrolment, training)) = pawn_query.get(button.pawn_entity) else {
+            continue;
+        };
+
+        let label = if let Some(training) = training {
+            let hours_left = (training.ends_hours - clock.elapsed_hours).max(0.0).ceil();
+            localization.format(
+                "work.training.away",
+                &[("hours", format!("{:.0}", hours_left))],
+            )
+        } else if let Some(enrolment) = enrolment {
+            localization.format(
+                "work.training.enrolled",
+                &[(
+                    "skill",
+                    localization
+                        .term("work_type", enrolment.skill.name())
+                        .to_string(),
+                )],
+            )
+        } else {
+            localization.t("work.training.train").to_string()
+        };
+
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                if **text != label {
+                    **text = label.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Paying for a course books the pawn onto it and closes the modal
+fn handle_training_course_clicks(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &TrainingCourseButton), Changed<Interaction>>,
+    pawn_query: Query<(&Pawn, &StaffSkills)>,
+    clock: Res<GameClock>,
+    localization: Res<Localization>,
+    mut money: ResMut<Money>,
+    mut finance: ResMut<FinanceLedger>,
+    mut training_state: ResMut<TrainingModalState>,
+) {
+    for (interaction, course) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((pawn, skills)) = pawn_query.get(course.pawn_entity) else {
+            continue;
+        };
+
+        let cost = training_cost(skills.level(course.skill));
+        if !money.deduct(cost) {
+            info!("Not enough money to train {}", pawn.name);
+            continue;
+        }
+
+        info!("Booked {} onto a {} course", pawn.name, course.skill.name());
+        finance.record(
+            clock.day(),
+            localization.format(
+                "staff.training",
+                &[
+                    ("name", pawn.name.clone()),
+                    (
+                        "skill",
+                        localization
+                            .term("work_type", course.skill.name())
+                            .to_string(),
+                    ),
+                ],
+            ),
+            -cost,
+        );
+        commands
+            .entity(course.pawn_entity)
+            .insert(TrainingEnrolment {
+                skill: course.skill,
+            });
+        training_state.pawn = None;
+    }
+}
+
+fn handle_training_cancel_clicks(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TrainingCancelButton>)>,
+    mut training_state: ResMut<TrainingModalState>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            training_state.pawn = None;
+        }
+    }
+}
+
+/// Shows the modal while it's open for a pawn, with a course per trainable kind of work:
+/// the level it takes them to and what it costs. Skills already at the top have no course left.
+fn update_training_modal(
+    mut commands: Commands,
+    mut training_state: ResMut<TrainingModalState>,
+    localization: Res<Localization>,
+    money: Res<Money>,
+    pawn_query: Query<(&Pawn, &StaffSkills)>,
+    mut modal_query: Query<&mut Node, With<TrainingModal>>,
+    content_query: Query<Entity, With<TrainingModalContent>>,
+    children_query: Query<&Children>,
+) {
+    // The pawn may have quit while the modal was open
+    if let Some(pawn_entity) = training_state.pawn {
+        if !pawn_query.contains(pawn_entity) {
+            training_state.pawn = None;
+        }
+    }
+
+    if !(training_state.is_changed() || localization.is_changed()) {
+        return;
+    }
+
+    if let Ok(mut style) = modal_query.get_single_mut() {
+        style.display = if training_state.pawn.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+
+    let Some(pawn_entity) = training_state.pawn else {
+        return;
+    };
+    let Ok((pawn, skills)) = pawn_query.get(pawn_entity) else {
+        return;
+    };
+    let Ok(content_entity) = content_query.get_single() else {
+        return;
+    };
+
+    if let Ok(children) = children_query.get(content_entity) {
+        for &child in children.iter() {
+            commands.entity(child).despawn_recursive();
+        }
+    }
+
+    commands.entity(content_entity).with_children(|parent| {
+        parent.spawn((
+            Text::new(localization.format("work.training.title", &[("name", pawn.name.clone())])),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+        parent.spawn((
+            Text::new(localization.t("work.training.hint")),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+        ));
+
+        for skill in StaffSkills::TRAINABLE {
+            let level = skills.level(skill);
+            let name = localization.term("work_type", skill.name()).to_string();
+
+            if level >= StaffSkills::MAX_LEVEL {
+                parent.spawn((
+                    Text::new(localization.format(
+                        "work.training.maxed",
+                        &[("skill", name), ("level", level.to_string())],
+                    )),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.5, 0.8, 0.5)),
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                ));
+                continue;
+            }
+
+            let cost = training_cost(level);
+            let label = localization.format(
+                "work.training.course",
+                &[
+                    ("skill", name),
+                    ("level", level.to_string()),
+                    ("next", (level + 1).to_string()),
+                    ("cost", cost.to_string()),
+                ],
+            );
+            let text_color = if money.can_afford(cost) {
+                Color::WHITE
+            } else {
+                Color::srgb(1.0, 0.5, 0.4)
+            };
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    TrainingCourseButton { pawn_entity, skill },
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new(label),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(text_color),
+                    ));
+                });
+        }
+    });
+}
+
+fn update_training_button_colors(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (
+            Changed<Interaction>,
+            Or<(
+                With<TrainButton>,
+                With<TrainingCourseButton>,
+                With<TrainingCancelButton>,
+            )>,
+        ),
+    >,
+) {
+    for (interaction, mut color) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => Color::srgb(0.35, 0.35, 0.35).into(),
+            Interaction::None => Color::srgb(0.25, 0.25, 0.25).into(),
+        };
+    }
+}